@@ -0,0 +1,543 @@
+//! Accounting - where the money went, rolled up into monthly reports
+//!
+//! Spending goes through [`GameState::spend`] so every dollar lands in a category.
+//! At the start of each game month the previous month is closed into a
+//! [`MonthlyReport`] and kept in the inbox. A new quarter also notes how the
+//! last three months added up.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::SystemParam;
+use crate::ai_boom::{AiEvent, AiStage};
+use crate::counterfeits::{CounterfeitEvent, CounterfeitStage};
+use crate::crypto::{CryptoEvent, CryptoStage};
+use crate::currency::{money, money_whole};
+use crate::eco::{ActivistAction, ActivistEvent};
+use crate::economy::{GameDate, MonthStartedEvent, QuarterStartedEvent, WorldState};
+use crate::gift_cards::{GiftCardActivity, GiftCardEvent, GiftCards};
+use crate::game_state::{AppState, GameState, InGame, MilestoneEvent, MilestoneType, MoneyChangedEvent};
+use crate::labor::{StrikeEvent, StrikeStage};
+use crate::limited_editions::{EditionEvent, EditionStage};
+use crate::pandemic::{PandemicEvent, PandemicStage};
+use crate::pivot::PivotEvent;
+use crate::price_war::{PriceWarEvent, PriceWarStage};
+use crate::recovery::{RecoveryEvent, RecoveryStage};
+use crate::reputation::ReputationTierChangedEvent;
+use crate::returns::RefundEvent;
+use crate::research::ResearchCompletedEvent;
+use crate::rewards::{Reward, RewardEvent};
+use crate::savings::{SavingsActivity, SavingsEvent};
+use crate::utilities::{OutageCause, PowerOutageEvent};
+use crate::seminars::SeminarEvent;
+use crate::suppliers::{SupplierEvent, SupplierStage};
+use crate::thing_type::ThingRegistry;
+use crate::venues::{VenueEvent, VenueStage};
+
+/// Most notable events listed in one report
+const MAX_NOTABLE: usize = 8;
+
+/// What money was spent on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum ExpenseCategory {
+    Upgrades,
+    Marketing,
+    Research,
+    Recovery,
+    Pivot,
+    Coffee,
+    Payroll,
+    Utilities,
+    Eco,
+    Returns,
+    Venues,
+    Materials,
+    Seminars,
+    Legal,
+}
+
+impl ExpenseCategory {
+    pub const ALL: [ExpenseCategory; 14] = [
+        ExpenseCategory::Upgrades,
+        ExpenseCategory::Marketing,
+        ExpenseCategory::Research,
+        ExpenseCategory::Recovery,
+        ExpenseCategory::Pivot,
+        ExpenseCategory::Coffee,
+        ExpenseCategory::Payroll,
+        ExpenseCategory::Utilities,
+        ExpenseCategory::Eco,
+        ExpenseCategory::Returns,
+        ExpenseCategory::Venues,
+        ExpenseCategory::Materials,
+        ExpenseCategory::Seminars,
+        ExpenseCategory::Legal,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExpenseCategory::Upgrades => "Upgrades",
+            ExpenseCategory::Marketing => "Marketing",
+            ExpenseCategory::Research => "R&D",
+            ExpenseCategory::Recovery => "Reputation recovery",
+            ExpenseCategory::Pivot => "Pivot & retooling",
+            ExpenseCategory::Coffee => "Coffee",
+            ExpenseCategory::Payroll => "Managers",
+            ExpenseCategory::Utilities => "Utilities",
+            ExpenseCategory::Eco => "Eco-certification",
+            ExpenseCategory::Returns => "Returns handling",
+            ExpenseCategory::Venues => "Pop-up venues",
+            ExpenseCategory::Materials => "Materials",
+            ExpenseCategory::Seminars => "Seminars",
+            ExpenseCategory::Legal => "Legal",
+        }
+    }
+
+    fn index(&self) -> usize {
+        Self::ALL.iter().position(|c| c == self).unwrap_or(0)
+    }
+}
+
+/// Running spend per category over the whole run
+#[derive(Debug, Clone, Copy, Default, Reflect)]
+pub struct ExpenseTally {
+    totals: [f64; ExpenseCategory::ALL.len()],
+}
+
+impl ExpenseTally {
+    pub fn add(&mut self, category: ExpenseCategory, amount: f64) {
+        self.totals[category.index()] += amount;
+    }
+
+    pub fn get(&self, category: ExpenseCategory) -> f64 {
+        self.totals[category.index()]
+    }
+}
+
+/// One closed month of business
+#[derive(Debug, Clone, Reflect)]
+pub struct MonthlyReport {
+    /// e.g. "January 2012"
+    pub month: String,
+    pub revenue: f64,
+    /// Money handed back for returned Things
+    pub refunds: f64,
+    /// Cash taken for gift cards (owed, so not revenue yet)
+    pub gift_cards_sold: f64,
+    /// Unredeemed gift cards written off as income
+    pub breakage: f64,
+    /// Gift cards still owed when the month closed
+    pub gift_cards_outstanding: f64,
+    /// Interest earned on savings
+    pub interest: f64,
+    /// ThingCoin gains and losses (sales, and any coin of our own)
+    pub crypto: f64,
+    /// Spend per category (categories with no spend are left out)
+    pub expenses: Vec<(ExpenseCategory, f64)>,
+    pub units_sold: u64,
+    pub units_returned: u64,
+    pub reputation_start: f32,
+    pub reputation_end: f32,
+    pub notable: Vec<String>,
+}
+
+impl MonthlyReport {
+    pub fn total_expenses(&self) -> f64 {
+        self.expenses.iter().map(|(_, amount)| amount).sum()
+    }
+
+    pub fn profit(&self) -> f64 {
+        self.revenue + self.breakage + self.interest + self.crypto - self.refunds - self.total_expenses()
+    }
+
+    /// One line for the inbox list
+    pub fn summary(&self) -> String {
+        format!("{}: {} profit", self.month, money_whole(self.profit()))
+    }
+
+    /// The full report as display text
+    pub fn body(&self) -> String {
+        let mut lines = vec![format!("Revenue: {}", money(self.revenue))];
+        if self.refunds > 0.0 {
+            lines.push(format!("Refunds: {}", money(-self.refunds)));
+        }
+        if self.breakage > 0.0 {
+            lines.push(format!("Gift card breakage: {}", money(self.breakage)));
+        }
+        if self.interest > 0.0 {
+            lines.push(format!("Interest on savings: {}", money(self.interest)));
+        }
+        if self.crypto != 0.0 {
+            lines.push(format!("ThingCoin: {}", money(self.crypto)));
+        }
+        lines.push("Expenses:".to_string());
+        if self.expenses.is_empty() {
+            lines.push("  (none)".to_string());
+        }
+        for (category, amount) in &self.expenses {
+            lines.push(format!("  {}: {}", category.name(), money(*amount)));
+        }
+        lines.push(format!("Profit: {}", money(self.profit())));
+        if self.gift_cards_sold > 0.0 || self.gift_cards_outstanding > 0.0 {
+            lines.push(format!(
+                "Gift cards sold: {} (cash in hand, but owed until redeemed)",
+                money(self.gift_cards_sold)
+            ));
+            lines.push(format!("Gift cards still owed: {}", money(self.gift_cards_outstanding)));
+        }
+        lines.push(format!("Units sold: {}", self.units_sold));
+        if self.units_returned > 0 {
+            lines.push(format!("Units returned: {}", self.units_returned));
+        }
+        lines.push(format!(
+            "Reputation: {:.2} -> {:.2} ({:+.2})",
+            self.reputation_start,
+            self.reputation_end,
+            self.reputation_end - self.reputation_start
+        ));
+
+        lines.push(String::new());
+        lines.push("Notable events:".to_string());
+        if self.notable.is_empty() {
+            lines.push("  A quiet month.".to_string());
+        }
+        for event in &self.notable {
+            lines.push(format!("  - {}", event));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Figures for the month that's still running
+#[derive(Debug, Clone, Reflect)]
+struct OpenMonth {
+    month: String,
+    revenue: f64,
+    refunds: f64,
+    units_returned: u64,
+    gift_cards_sold: f64,
+    breakage: f64,
+    interest: f64,
+    crypto: f64,
+    expenses_at_start: ExpenseTally,
+    customers_at_start: u64,
+    reputation_at_start: f32,
+    notable: Vec<String>,
+}
+
+impl OpenMonth {
+    fn new(date: GameDate, game_state: &GameState) -> Self {
+        Self {
+            month: date.month_label(),
+            revenue: 0.0,
+            refunds: 0.0,
+            units_returned: 0,
+            gift_cards_sold: 0.0,
+            breakage: 0.0,
+            interest: 0.0,
+            crypto: 0.0,
+            expenses_at_start: game_state.expenses,
+            customers_at_start: game_state.customers_served,
+            reputation_at_start: game_state.reputation,
+            notable: Vec::new(),
+        }
+    }
+
+    fn close(self, game_state: &GameState, gift_cards: &GiftCards) -> MonthlyReport {
+        let expenses = ExpenseCategory::ALL
+            .iter()
+            .map(|c| (*c, game_state.expenses.get(*c) - self.expenses_at_start.get(*c)))
+            .filter(|(_, amount)| *amount > 0.005)
+            .collect();
+
+        MonthlyReport {
+            month: self.month,
+            revenue: self.revenue,
+            refunds: self.refunds,
+            gift_cards_sold: self.gift_cards_sold,
+            breakage: self.breakage,
+            gift_cards_outstanding: gift_cards.outstanding.dollars(),
+            interest: self.interest,
+            crypto: self.crypto,
+            expenses,
+            units_sold: game_state.customers_served - self.customers_at_start,
+            units_returned: self.units_returned,
+            reputation_start: self.reputation_at_start,
+            reputation_end: game_state.reputation,
+            notable: self.notable,
+        }
+    }
+}
+
+/// The month being tallied and every report closed so far (the inbox)
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct Accounting {
+    open: Option<OpenMonth>,
+    /// Oldest first
+    pub reports: Vec<MonthlyReport>,
+}
+
+impl Accounting {
+    fn note(&mut self, event: String) {
+        if let Some(open) = self.open.as_mut() {
+            if open.notable.len() < MAX_NOTABLE {
+                open.notable.push(event);
+            }
+        }
+    }
+}
+
+/// Message fired when a month closes; `index` is the report's place in the inbox
+#[derive(Event, Message, Clone)]
+pub struct MonthlyReportEvent {
+    pub index: usize,
+}
+
+pub struct AccountingPlugin;
+
+impl Plugin for AccountingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Accounting>()
+            .add_message::<MonthlyReportEvent>()
+            .add_systems(OnEnter(InGame), open_first_month)
+            .add_systems(
+                Update,
+                (record_revenue, record_notable_events, close_month, review_quarter)
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+fn open_first_month(world: Res<WorldState>, game_state: Res<GameState>, mut accounting: ResMut<Accounting>) {
+    if accounting.open.is_none() {
+        accounting.open = Some(OpenMonth::new(world.date, &game_state));
+    }
+}
+
+/// Sales revenue arrives as money changes; refunds (and open-box resales) come from returns,
+/// gift cards only count once they're redeemed or expire, savings only count as interest,
+/// and ThingCoin only counts once it's sold
+fn record_revenue(
+    mut money_events: MessageReader<MoneyChangedEvent>,
+    mut refund_events: MessageReader<RefundEvent>,
+    mut gift_card_events: MessageReader<GiftCardEvent>,
+    mut savings_events: MessageReader<SavingsEvent>,
+    mut crypto_events: MessageReader<CryptoEvent>,
+    mut accounting: ResMut<Accounting>,
+) {
+    for event in money_events.read() {
+        if let Some(open) = accounting.open.as_mut() {
+            open.revenue += event.delta;
+        }
+    }
+    for event in refund_events.read() {
+        if let Some(open) = accounting.open.as_mut() {
+            open.refunds += event.amount;
+            open.units_returned += event.units;
+            open.revenue += event.resale;
+        }
+    }
+    for event in gift_card_events.read() {
+        if let Some(open) = accounting.open.as_mut() {
+            match event.activity {
+                GiftCardActivity::Sold => open.gift_cards_sold += event.amount,
+                GiftCardActivity::Redeemed => open.revenue += event.amount + event.cash,
+                GiftCardActivity::Breakage => open.breakage += event.amount,
+            }
+        }
+    }
+    for event in savings_events.read() {
+        if event.activity != SavingsActivity::Interest {
+            continue;
+        }
+        if let Some(open) = accounting.open.as_mut() {
+            open.interest += event.amount;
+        }
+    }
+    for event in crypto_events.read() {
+        let amount = match event.stage {
+            CryptoStage::Sold { gain, .. } => gain,
+            CryptoStage::Issued { raised } => raised,
+            CryptoStage::RugPulled { refund } => -refund,
+            _ => continue,
+        };
+        if let Some(open) = accounting.open.as_mut() {
+            open.crypto += amount;
+        }
+    }
+}
+
+/// Everything that can make a month's list of notable events
+#[derive(SystemParam)]
+struct NotableEvents<'w, 's> {
+    milestones: MessageReader<'w, 's, MilestoneEvent>,
+    tiers: MessageReader<'w, 's, ReputationTierChangedEvent>,
+    research: MessageReader<'w, 's, ResearchCompletedEvent>,
+    pivots: MessageReader<'w, 's, PivotEvent>,
+    things: Res<'w, ThingRegistry>,
+    recovery: MessageReader<'w, 's, RecoveryEvent>,
+    strikes: MessageReader<'w, 's, StrikeEvent>,
+    outages: MessageReader<'w, 's, PowerOutageEvent>,
+    activists: MessageReader<'w, 's, ActivistEvent>,
+    venues: MessageReader<'w, 's, VenueEvent>,
+    suppliers: MessageReader<'w, 's, SupplierEvent>,
+    seminars: MessageReader<'w, 's, SeminarEvent>,
+    rewards: MessageReader<'w, 's, RewardEvent>,
+    pandemic: MessageReader<'w, 's, PandemicEvent>,
+    ai: MessageReader<'w, 's, AiEvent>,
+    price_war: MessageReader<'w, 's, PriceWarEvent>,
+    counterfeits: MessageReader<'w, 's, CounterfeitEvent>,
+    editions: MessageReader<'w, 's, EditionEvent>,
+}
+
+/// Keep a short list of what happened this month
+fn record_notable_events(mut events: NotableEvents, mut accounting: ResMut<Accounting>) {
+    for event in events.milestones.read() {
+        accounting.note(match event.milestone_type {
+            MilestoneType::ThingsProduced(n) => format!("{} Things produced", n),
+            MilestoneType::MoneyEarned(n) => format!("{} in the bank", money_whole(n as f64)),
+            MilestoneType::CustomersServed(n) => format!("{} customers served", n),
+            MilestoneType::ReputationReached(n) => format!("Reached {} stars", n),
+        });
+    }
+    for event in events.tiers.read() {
+        accounting.note(format!("Reputation went from {} to {}", event.old_tier.name(), event.new_tier.name()));
+    }
+    for event in events.research.read() {
+        accounting.note(format!("Researched {}", event.tech.name()));
+    }
+    for event in events.pivots.read() {
+        accounting.note(if event.completed {
+            format!("Finished pivoting to {}", events.things[event.to].name)
+        } else {
+            format!("Started pivoting from {} to {}", events.things[event.from].name, events.things[event.to].name)
+        });
+    }
+    for event in events.recovery.read() {
+        let action = event.action.map(|a| a.name()).unwrap_or("Redemption");
+        match event.stage {
+            RecoveryStage::Started => accounting.note(format!("{} began", action)),
+            RecoveryStage::Succeeded => accounting.note(format!("{} worked", action)),
+            RecoveryStage::Failed => accounting.note(format!("{} flopped", action)),
+            RecoveryStage::Unlocked | RecoveryStage::Midway => {}
+        }
+    }
+    for event in events.strikes.read() {
+        match event.stage {
+            StrikeStage::Started => accounting.note("Workers went on strike".to_string()),
+            StrikeStage::Resolving(resolution) => accounting.note(format!("Strike: chose to {}", resolution.name().to_lowercase())),
+            StrikeStage::Ended(_) => accounting.note("Strike ended".to_string()),
+        }
+    }
+    for event in events.outages.read() {
+        match event.cause {
+            Some(OutageCause::HeatWave) => accounting.note("Heat wave blackout".to_string()),
+            Some(OutageCause::UnpaidBill) => accounting.note("Power cut off for an unpaid bill".to_string()),
+            None => {}
+        }
+    }
+    for event in events.activists.read() {
+        accounting.note(match event.action {
+            ActivistAction::Protest => "Environmental activists protested outside".to_string(),
+            ActivistAction::Praise => "Environmental activists praised the business".to_string(),
+        });
+    }
+    for event in events.venues.read() {
+        match event.stage {
+            VenueStage::Opened => accounting.note(format!("Opened a pop-up at the {}", event.kind.name().to_lowercase())),
+            VenueStage::WashedOut => accounting.note(format!("Weather washed out the {}", event.kind.name().to_lowercase())),
+            VenueStage::Offered | VenueStage::Closed => {}
+        }
+    }
+    for event in events.suppliers.read() {
+        match event.stage {
+            SupplierStage::WalkedAway => accounting.note("Walked away from the supplier".to_string()),
+            SupplierStage::Shortfall => accounting.note("Missed the supplier volume commitment".to_string()),
+            SupplierStage::Disrupted => accounting.note("Supplier stopped delivering".to_string()),
+            SupplierStage::Restored => accounting.note("Supplier deliveries resumed".to_string()),
+            SupplierStage::Offered | SupplierStage::Accepted | SupplierStage::Haggled { .. } => {}
+        }
+    }
+    for event in events.seminars.read() {
+        if event.completed {
+            accounting.note(format!("Terry taught {}", event.course.name()));
+        }
+    }
+    for event in events.rewards.read() {
+        // The card that gets picked is noted, not the offer
+        if !matches!(event.reward, Reward::Choice(_)) {
+            accounting.note(format!("Milestone reward: {}", event.reward.describe()));
+        }
+    }
+    for event in events.pandemic.read() {
+        match event.stage {
+            PandemicStage::Began(phase) => accounting.note(format!("Pandemic: {}", phase.name())),
+            PandemicStage::Over => accounting.note("Pandemic restrictions lifted".to_string()),
+            PandemicStage::PivotedToDelivery => accounting.note("Pivoted to delivery".to_string()),
+            PandemicStage::MaskRushStarted => accounting.note("Masks and sanitizer rush".to_string()),
+            PandemicStage::MaskRushEnded => {}
+        }
+    }
+    for event in events.ai.read() {
+        match event.stage {
+            AiStage::Bought(upgrade) => accounting.note(format!("Bought {}", upgrade.name())),
+            AiStage::Hallucinated(upgrade) => accounting.note(format!("{} hallucinated", upgrade.name())),
+            AiStage::Unlocked | AiStage::BackOnline(_) => {}
+        }
+    }
+    for event in events.price_war.read() {
+        match event.stage {
+            PriceWarStage::Started => accounting.note("Price war started".to_string()),
+            PriceWarStage::TruceAccepted => accounting.note("Price war truce".to_string()),
+            PriceWarStage::Ceasefire => accounting.note("Price war over".to_string()),
+            PriceWarStage::TruceOffered | PriceWarStage::Recovered => {}
+        }
+    }
+    for event in events.counterfeits.read() {
+        match event.stage {
+            CounterfeitStage::Appeared => accounting.note("Counterfeits appeared".to_string()),
+            CounterfeitStage::LegalActionWon => accounting.note("Takedowns against counterfeiters".to_string()),
+            CounterfeitStage::Forfeited => accounting.note("Customs destroyed seized fakes".to_string()),
+            _ => {}
+        }
+    }
+    for event in events.editions.read() {
+        match event.stage {
+            EditionStage::Launched => accounting.note(format!("{} launched", event.edition.name())),
+            EditionStage::SoldOut => accounting.note(format!("{} sold out", event.edition.name())),
+            EditionStage::Leftover(units) => accounting.note(format!("{} unsold {} cleared", units, event.edition.name())),
+            EditionStage::Ordered | EditionStage::SellingOut => {}
+        }
+    }
+}
+
+/// On the first of the month, close last month into a report
+fn close_month(
+    mut month_events: MessageReader<MonthStartedEvent>,
+    game_state: Res<GameState>,
+    gift_cards: Res<GiftCards>,
+    mut accounting: ResMut<Accounting>,
+    mut report_events: MessageWriter<MonthlyReportEvent>,
+) {
+    for event in month_events.read() {
+        let next = OpenMonth::new(event.date, &game_state);
+        if let Some(open) = accounting.open.replace(next) {
+            accounting.reports.push(open.close(&game_state, &gift_cards));
+            report_events.write(MonthlyReportEvent { index: accounting.reports.len() - 1 });
+        }
+    }
+}
+
+/// At the start of a quarter, note the last one's profit in the new month's report
+fn review_quarter(mut quarter_events: MessageReader<QuarterStartedEvent>, mut accounting: ResMut<Accounting>) {
+    for event in quarter_events.read() {
+        let months = accounting.reports.len().min(3);
+        if months == 0 {
+            continue;
+        }
+        let profit: f64 = accounting.reports[accounting.reports.len() - months..].iter().map(MonthlyReport::profit).sum();
+        let (quarter, year) = if event.quarter == 1 { (4, event.date.year() - 1) } else { (event.quarter - 1, event.date.year()) };
+        accounting.note(format!("Q{} {}: {} profit", quarter, year, money_whole(profit)));
+    }
+}
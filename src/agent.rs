@@ -0,0 +1,203 @@
+//! Bot players - a small API for strategies that play the game by themselves
+//!
+//! A [`PlayerAgent`] sees a condensed [`Observation`] once a game day and
+//! answers with [`AgentAction`]s. Put an [`AgentDriver`] resource in the world
+//! and the agent plays the run: it drives the attract-mode demo and the
+//! headless balancing runs (`--balance`).
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::business::{UpgradePurchasedEvent, UpgradeState, UpgradeType};
+use crate::clicker::{ClickEvent, HustleMeter};
+use crate::economy::DayAdvancedEvent;
+use crate::game_clock::GameClock;
+use crate::game_state::{AppState, GameState};
+use crate::marketing::{MarketingChannel, MarketingState};
+use crate::reputation::ReputationTier;
+
+/// What an agent gets to look at each day
+#[derive(Debug, Clone)]
+pub struct Observation {
+    pub money: f64,
+    pub tier: ReputationTier,
+    /// How full the hustle meter is (0-1)
+    pub hustle: f32,
+    /// Every upgrade with its current price
+    pub upgrade_costs: Vec<(UpgradeType, f64)>,
+    pub active_channels: Vec<MarketingChannel>,
+    /// What the active channels cost per day
+    pub marketing_spend: f32,
+}
+
+impl Observation {
+    /// The cheapest upgrade out of `options` that the money covers
+    pub fn cheapest_affordable(&self, options: &[UpgradeType]) -> Option<UpgradeType> {
+        self.upgrade_costs
+            .iter()
+            .filter(|(upgrade, cost)| options.contains(upgrade) && *cost <= self.money)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(upgrade, _)| *upgrade)
+    }
+}
+
+/// Something an agent can do
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AgentAction {
+    /// Click Make Thing this many times a second until told otherwise
+    ClickRate(f32),
+    /// Buy one of an upgrade
+    Buy(UpgradeType),
+    /// Switch a marketing channel on or off
+    SetChannel(MarketingChannel, bool),
+}
+
+/// A strategy that plays the game
+pub trait PlayerAgent: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Decide what to do today
+    fn decide(&mut self, observation: &Observation) -> Vec<AgentAction>;
+}
+
+/// Clicks as fast as hustle allows and spends everything on production
+pub struct GreedyClicker;
+
+impl PlayerAgent for GreedyClicker {
+    fn name(&self) -> &'static str {
+        "Greedy clicker"
+    }
+
+    fn decide(&mut self, observation: &Observation) -> Vec<AgentAction> {
+        // Flat out, then ease off to what the meter refills
+        let rate = if observation.hustle > 0.2 { 5.0 } else { 0.5 };
+        let mut actions = vec![AgentAction::ClickRate(rate)];
+        let production = [
+            UpgradeType::BetterTools,
+            UpgradeType::HireWorker,
+            UpgradeType::Automation,
+            UpgradeType::StandingDesk,
+        ];
+        if let Some(upgrade) = observation.cheapest_affordable(&production) {
+            actions.push(AgentAction::Buy(upgrade));
+        }
+        actions
+    }
+}
+
+/// Keeps just enough production going to pay for every ad it can switch on
+pub struct MarketingMaximalist;
+
+impl PlayerAgent for MarketingMaximalist {
+    fn name(&self) -> &'static str {
+        "Marketing maximalist"
+    }
+
+    fn decide(&mut self, observation: &Observation) -> Vec<AgentAction> {
+        let mut actions = vec![AgentAction::ClickRate(1.0)];
+        let marketing = [
+            UpgradeType::SocialMedia,
+            UpgradeType::Billboard,
+            UpgradeType::InfluencerDeal,
+            UpgradeType::HireWorker,
+        ];
+        if let Some(upgrade) = observation.cheapest_affordable(&marketing) {
+            actions.push(AgentAction::Buy(upgrade));
+        }
+
+        // Run every ad while there's a month of spending in the bank, drop them all when there isn't
+        let flush = observation.money > observation.marketing_spend as f64 * 30.0 + 100.0;
+        for channel in MarketingChannel::ADVERTISING.into_iter().filter(|c| c.available_at(observation.tier)) {
+            let active = observation.active_channels.contains(&channel);
+            if active != flush {
+                actions.push(AgentAction::SetChannel(channel, flush));
+            }
+        }
+        actions
+    }
+}
+
+/// The agent playing the current run
+#[derive(Resource)]
+pub struct AgentDriver {
+    agent: Box<dyn PlayerAgent>,
+    clicks_per_sec: f32,
+    pending_clicks: f32,
+    started: bool,
+}
+
+impl AgentDriver {
+    pub fn new(agent: Box<dyn PlayerAgent>) -> Self {
+        Self {
+            agent,
+            clicks_per_sec: 0.0,
+            pending_clicks: 0.0,
+            started: false,
+        }
+    }
+}
+
+pub struct AgentPlugin;
+
+impl Plugin for AgentPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (decide_daily, click_for_agent)
+                .chain()
+                .run_if(in_state(AppState::Playing))
+                .run_if(resource_exists::<AgentDriver>),
+        );
+    }
+}
+
+/// Show the agent the day and carry out its decisions
+fn decide_daily(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    mut driver: ResMut<AgentDriver>,
+    mut game_state: ResMut<GameState>,
+    mut upgrade_state: ResMut<UpgradeState>,
+    mut marketing: ResMut<MarketingState>,
+    hustle: Res<HustleMeter>,
+    mut purchase_events: MessageWriter<UpgradePurchasedEvent>,
+) {
+    let new_day = day_events.read().count() > 0;
+    if driver.started && !new_day {
+        return;
+    }
+    driver.started = true;
+
+    let tier = ReputationTier::from_reputation(game_state.reputation);
+    let active_channels: Vec<MarketingChannel> =
+        MarketingChannel::ALL.into_iter().filter(|c| marketing.is_active(*c)).collect();
+    let observation = Observation {
+        money: game_state.money.dollars(),
+        tier,
+        hustle: hustle.fraction(),
+        upgrade_costs: UpgradeType::ALL.iter().map(|u| (*u, upgrade_state.cost(*u))).collect(),
+        marketing_spend: active_channels.iter().map(|c| marketing.channel_daily_cost(*c)).sum(),
+        active_channels,
+    };
+
+    for action in driver.agent.decide(&observation) {
+        match action {
+            AgentAction::ClickRate(rate) => driver.clicks_per_sec = rate.max(0.0),
+            AgentAction::Buy(upgrade) => {
+                if game_state.money >= upgrade_state.cost(upgrade) && upgrade_state.purchase(upgrade, &mut game_state) {
+                    purchase_events.write(UpgradePurchasedEvent { upgrade });
+                }
+            }
+            AgentAction::SetChannel(channel, active) => {
+                marketing.set_active(channel, active, tier);
+            }
+        }
+    }
+}
+
+/// Spread the agent's clicks over the day
+fn click_for_agent(clock: Res<GameClock>, mut driver: ResMut<AgentDriver>, mut click_events: MessageWriter<ClickEvent>) {
+    driver.pending_clicks += driver.clicks_per_sec * clock.delta_secs();
+    while driver.pending_clicks >= 1.0 {
+        driver.pending_clicks -= 1.0;
+        click_events.write(ClickEvent);
+    }
+}
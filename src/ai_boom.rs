@@ -0,0 +1,260 @@
+//! AI boom - "AI-powered" upgrades from 2023
+//!
+//! Once the timeline reaches 2023 two one-off upgrades go on sale: a chatbot
+//! for customer service, which brings in more customers, and generative ad
+//! copy, which makes every advertising channel work harder. Both are strong,
+//! and both occasionally hallucinate in public - a reputation hit, and the
+//! tool is taken offline for a while.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::accounting::ExpenseCategory;
+use crate::economy::{DayAdvancedEvent, GameDate, WorldState};
+use crate::fortune::RunModifiers;
+use crate::game_state::{AppState, GameState, ReputationChangedEvent};
+use crate::marketing::MarketingState;
+use crate::social::{Post, Sentiment, SocialFeed};
+use crate::thing_type::ThingRegistry;
+
+/// Customers won by the chatbot while it's online
+const CHATBOT_BOOST: f64 = 1.2;
+/// How much harder ads work with generative copy
+const AD_COPY_BOOST: f32 = 1.5;
+/// Daily chance an online tool hallucinates in public
+const HALLUCINATION_CHANCE: f32 = 0.01;
+/// Reputation lost when it does
+const HALLUCINATION_REPUTATION: f32 = 0.4;
+/// Days a tool stays offline afterwards
+const OFFLINE_DAYS: u32 = 10;
+
+/// Has the AI boom reached this date?
+pub fn ai_boom(date: GameDate) -> bool {
+    date.year() >= 2023
+}
+
+/// The AI-powered upgrades
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AiUpgrade {
+    Chatbot,
+    AdCopy,
+}
+
+impl AiUpgrade {
+    pub const ALL: [AiUpgrade; 2] = [AiUpgrade::Chatbot, AiUpgrade::AdCopy];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AiUpgrade::Chatbot => "Chatbot Customer Service",
+            AiUpgrade::AdCopy => "Generative Ad Copy",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            AiUpgrade::Chatbot => "+20% customers. Occasionally invents a refund policy.",
+            AiUpgrade::AdCopy => "Ads work 50% harder. Occasionally invents a product feature.",
+        }
+    }
+
+    pub fn cost(&self) -> f64 {
+        match self {
+            AiUpgrade::Chatbot => 4_000.0,
+            AiUpgrade::AdCopy => 6_000.0,
+        }
+    }
+}
+
+/// One AI tool the company might run
+#[derive(Debug, Clone, Copy, Default, Reflect)]
+pub struct AiTool {
+    pub owned: bool,
+    /// Days left offline after a hallucination
+    pub offline_days: u32,
+}
+
+impl AiTool {
+    pub fn online(&self) -> bool {
+        self.owned && self.offline_days == 0
+    }
+}
+
+/// AI-powered upgrades and how they're behaving
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct AiState {
+    /// The boom has reached us
+    pub available: bool,
+    pub chatbot: AiTool,
+    pub ad_copy: AiTool,
+}
+
+impl AiState {
+    pub fn tool(&self, upgrade: AiUpgrade) -> &AiTool {
+        match upgrade {
+            AiUpgrade::Chatbot => &self.chatbot,
+            AiUpgrade::AdCopy => &self.ad_copy,
+        }
+    }
+
+    fn tool_mut(&mut self, upgrade: AiUpgrade) -> &mut AiTool {
+        match upgrade {
+            AiUpgrade::Chatbot => &mut self.chatbot,
+            AiUpgrade::AdCopy => &mut self.ad_copy,
+        }
+    }
+
+    pub fn can_buy(&self, upgrade: AiUpgrade, game_state: &GameState) -> bool {
+        self.available && !self.tool(upgrade).owned && game_state.money >= upgrade.cost()
+    }
+
+    /// Multiplier on would-be customers
+    pub fn customer_factor(&self) -> f64 {
+        if self.chatbot.online() { CHATBOT_BOOST } else { 1.0 }
+    }
+}
+
+/// What happened with the AI tools
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AiStage {
+    /// 2023 arrived
+    Unlocked,
+    Bought(AiUpgrade),
+    /// Said something untrue in public and was taken offline
+    Hallucinated(AiUpgrade),
+    BackOnline(AiUpgrade),
+}
+
+/// Message fired for everything that happens with the AI tools
+#[derive(Event, Message, Clone)]
+pub struct AiEvent {
+    pub stage: AiStage,
+}
+
+/// Message requesting an AI upgrade (sent by the UI)
+#[derive(Event, Message, Clone)]
+pub struct BuyAiUpgradeEvent {
+    pub upgrade: AiUpgrade,
+}
+
+pub struct AiBoomPlugin;
+
+impl Plugin for AiBoomPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AiState>()
+            .add_message::<AiEvent>()
+            .add_message::<BuyAiUpgradeEvent>()
+            .add_systems(
+                Update,
+                (handle_ai_purchases, run_ai_tools, post_ai_chatter)
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// Pay for an AI upgrade
+fn handle_ai_purchases(
+    mut buy_events: MessageReader<BuyAiUpgradeEvent>,
+    mut game_state: ResMut<GameState>,
+    mut ai: ResMut<AiState>,
+    mut marketing: ResMut<MarketingState>,
+    mut ai_events: MessageWriter<AiEvent>,
+) {
+    for event in buy_events.read() {
+        if !ai.can_buy(event.upgrade, &game_state) {
+            continue;
+        }
+        game_state.spend(ExpenseCategory::Upgrades, event.upgrade.cost());
+        ai.tool_mut(event.upgrade).owned = true;
+        marketing.ad_copy = if ai.ad_copy.online() { AD_COPY_BOOST } else { 1.0 };
+        ai_events.write(AiEvent {
+            stage: AiStage::Bought(event.upgrade),
+        });
+    }
+}
+
+/// Each game day: open the boom, bring tools back online, and roll for hallucinations
+fn run_ai_tools(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    modifiers: Res<RunModifiers>,
+    mut game_state: ResMut<GameState>,
+    mut ai: ResMut<AiState>,
+    mut marketing: ResMut<MarketingState>,
+    mut ai_events: MessageWriter<AiEvent>,
+    mut rep_events: MessageWriter<ReputationChangedEvent>,
+) {
+    for event in day_events.read() {
+        if !ai.available {
+            if ai_boom(event.date) {
+                ai.available = true;
+                ai_events.write(AiEvent { stage: AiStage::Unlocked });
+            }
+            continue;
+        }
+
+        for upgrade in AiUpgrade::ALL {
+            let tool = ai.tool_mut(upgrade);
+            if !tool.owned {
+                continue;
+            }
+            if tool.offline_days > 0 {
+                tool.offline_days -= 1;
+                if tool.offline_days == 0 {
+                    ai_events.write(AiEvent {
+                        stage: AiStage::BackOnline(upgrade),
+                    });
+                }
+            } else if rand::random::<f32>() < HALLUCINATION_CHANCE {
+                tool.offline_days = OFFLINE_DAYS;
+                let change = modifiers.scale_reputation_change(-HALLUCINATION_REPUTATION);
+                game_state.reputation = (game_state.reputation + change).clamp(0.0, 5.0);
+                rep_events.write(ReputationChangedEvent {
+                    new_reputation: game_state.reputation,
+                });
+                ai_events.write(AiEvent {
+                    stage: AiStage::Hallucinated(upgrade),
+                });
+            }
+        }
+        marketing.ad_copy = if ai.ad_copy.online() { AD_COPY_BOOST } else { 1.0 };
+    }
+}
+
+/// Chirper screenshots every hallucination
+fn post_ai_chatter(
+    mut ai_events: MessageReader<AiEvent>,
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    world: Res<WorldState>,
+    mut feed: ResMut<SocialFeed>,
+) {
+    let thing = game_state.thing_type.map_or("Thing", |t| &things[t].name);
+
+    for event in ai_events.read() {
+        let (author, text, sentiment) = match event.stage {
+            AiStage::Unlocked => (
+                "@tech_tina",
+                "Every company is \"AI-powered\" now. My toaster is AI-powered. It still burns the toast, but confidently.".to_string(),
+                Sentiment::Neutral,
+            ),
+            AiStage::Hallucinated(AiUpgrade::Chatbot) => (
+                "@actual_reviewer",
+                format!("The {} Thing chatbot just promised me a lifetime supply and a pony. Screenshotted. Waiting on my pony.", thing),
+                Sentiment::Negative,
+            ),
+            AiStage::Hallucinated(AiUpgrade::AdCopy) => (
+                "@skeptical_steve",
+                format!("New {} Thing ad says it's \"clinically proven to cure Mondays\". Which clinic?", thing),
+                Sentiment::Negative,
+            ),
+            AiStage::Bought(_) | AiStage::BackOnline(_) => continue,
+        };
+        feed.push(Post {
+            date: world.date,
+            author: author.to_string(),
+            text,
+            sentiment,
+            astroturfed: false,
+        });
+    }
+}
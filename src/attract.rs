@@ -0,0 +1,98 @@
+//! Attract mode - left alone on the selection screen, Terry plays a demo run
+//!
+//! After a while without input a bot picks a Thing and plays a sped-up run
+//! (the [`GreedyClicker`] agent) while Terry narrates.
+//! Any key, click or mouse movement ends the demo, throws the run away and
+//! returns to the selection screen.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::input::mouse::AccumulatedMouseMotion;
+use crate::agent::{AgentDriver, GreedyClicker};
+use crate::game_state::{AppState, GameState, InGame};
+use crate::thing_type::ThingType;
+
+/// Seconds without input on the selection screen before the demo starts
+const IDLE_SECS: f32 = 90.0;
+/// How much faster than normal the demo plays
+const DEMO_SPEED: f32 = 4.0;
+
+/// Whether a demo is running
+#[derive(Resource, Default)]
+pub struct AttractMode {
+    pub running: bool,
+    idle_secs: f32,
+}
+
+/// Run condition: a demo is playing
+pub fn attract_running(attract: Res<AttractMode>) -> bool {
+    attract.running
+}
+
+pub struct AttractPlugin;
+
+impl Plugin for AttractPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AttractMode>()
+            .add_systems(Update, start_when_idle.run_if(in_state(AppState::ThingSelection)))
+            .add_systems(OnEnter(InGame), start_demo.run_if(attract_running))
+            .add_systems(Update, stop_on_input.run_if(in_state(AppState::Playing)).run_if(attract_running))
+            .add_systems(OnExit(InGame), end_demo.run_if(attract_running));
+    }
+}
+
+/// Did the player touch anything this frame?
+fn any_input(keys: &ButtonInput<KeyCode>, mouse: &ButtonInput<MouseButton>, motion: &AccumulatedMouseMotion) -> bool {
+    keys.get_just_pressed().next().is_some() || mouse.get_just_pressed().next().is_some() || motion.delta != Vec2::ZERO
+}
+
+/// Start a demo once the selection screen has sat idle long enough
+fn start_when_idle(
+    time: Res<Time<Real>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    motion: Res<AccumulatedMouseMotion>,
+    mut attract: ResMut<AttractMode>,
+    mut game_state: ResMut<GameState>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if any_input(&keys, &mouse, &motion) {
+        attract.idle_secs = 0.0;
+        return;
+    }
+
+    attract.idle_secs += time.delta_secs();
+    if attract.idle_secs < IDLE_SECS {
+        return;
+    }
+
+    attract.idle_secs = 0.0;
+    attract.running = true;
+    game_state.thing_type = Some(ThingType::BUILT_IN[rand::random_range(0..ThingType::BUILT_IN.len())]);
+    next_state.set(AppState::Playing);
+}
+
+/// Hand the run to the bot and speed the clock up
+fn start_demo(mut commands: Commands, mut virtual_time: ResMut<Time<Virtual>>) {
+    commands.insert_resource(AgentDriver::new(Box::new(GreedyClicker)));
+    virtual_time.set_relative_speed(DEMO_SPEED);
+}
+
+/// Any input hands the game back to the player
+fn stop_on_input(
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    motion: Res<AccumulatedMouseMotion>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if any_input(&keys, &mouse, &motion) {
+        next_state.set(AppState::ThingSelection);
+    }
+}
+
+/// Take the run back from the bot (leaving the run throws it away, so the player's own starts fresh)
+fn end_demo(mut commands: Commands, mut attract: ResMut<AttractMode>, mut virtual_time: ResMut<Time<Virtual>>) {
+    attract.running = false;
+    virtual_time.set_relative_speed(1.0);
+    commands.remove_resource::<AgentDriver>();
+}
@@ -0,0 +1,304 @@
+//! Automation rules - simple "if this, then that" rules run once per game day
+//!
+//! Unlocked by researching Business Automation. Each rule checks one metric
+//! against a threshold and, if it holds, takes one action.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use std::collections::VecDeque;
+use crate::business::{UpgradePurchasedEvent, UpgradeState, UpgradeType};
+use crate::currency::money_whole;
+use crate::economy::{DayAdvancedEvent, WorldState};
+use crate::game_state::{AppState, GameState};
+use crate::marketing::{MarketingChannel, MarketingState};
+use crate::reputation::ReputationTier;
+use crate::research::{ResearchState, Tech};
+
+/// Maximum rules the player can define
+pub const MAX_RULES: usize = 8;
+/// How many execution log entries are kept
+pub const LOG_LENGTH: usize = 10;
+
+/// Something a rule can look at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum Metric {
+    Money,
+    Reputation,
+    ConsumerConfidence,
+    ThingsPerSecond,
+}
+
+impl Metric {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Metric::Money => "money",
+            Metric::Reputation => "reputation",
+            Metric::ConsumerConfidence => "consumer confidence",
+            Metric::ThingsPerSecond => "Things/sec",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Metric::Money => Metric::Reputation,
+            Metric::Reputation => Metric::ConsumerConfidence,
+            Metric::ConsumerConfidence => Metric::ThingsPerSecond,
+            Metric::ThingsPerSecond => Metric::Money,
+        }
+    }
+
+    /// Thresholds the player can pick from
+    pub fn thresholds(&self) -> &'static [f64] {
+        match self {
+            Metric::Money => &[100.0, 500.0, 1_000.0, 5_000.0, 10_000.0, 50_000.0, 100_000.0],
+            Metric::Reputation => &[1.0, 2.0, 2.5, 3.0, 4.0, 4.5],
+            Metric::ConsumerConfidence => &[0.7, 0.8, 0.9, 1.0, 1.1, 1.2],
+            Metric::ThingsPerSecond => &[1.0, 5.0, 10.0, 25.0, 50.0],
+        }
+    }
+
+    /// Display a threshold for this metric
+    pub fn format_value(&self, value: f64) -> String {
+        match self {
+            Metric::Money => money_whole(value),
+            Metric::Reputation => format!("{} stars", value),
+            Metric::ConsumerConfidence => format!("{:.1}", value),
+            Metric::ThingsPerSecond => format!("{}", value),
+        }
+    }
+
+    fn current(&self, game_state: &GameState, world: &WorldState) -> f64 {
+        match self {
+            Metric::Money => game_state.money.dollars(),
+            Metric::Reputation => game_state.reputation as f64,
+            Metric::ConsumerConfidence => world.consumer_confidence as f64,
+            Metric::ThingsPerSecond => game_state.things_per_second,
+        }
+    }
+}
+
+/// Which side of the threshold triggers the rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum Comparison {
+    Above,
+    Below,
+}
+
+impl Comparison {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Comparison::Above => ">",
+            Comparison::Below => "<",
+        }
+    }
+
+    pub fn flip(&self) -> Self {
+        match self {
+            Comparison::Above => Comparison::Below,
+            Comparison::Below => Comparison::Above,
+        }
+    }
+}
+
+/// What a rule does when its condition holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum RuleAction {
+    BuyUpgrade(UpgradeType),
+    StartChannel(MarketingChannel),
+    PauseChannel(MarketingChannel),
+}
+
+impl RuleAction {
+    /// Every action available to rules, in the order the builder cycles through them
+    pub fn all() -> Vec<RuleAction> {
+        let mut actions: Vec<RuleAction> = UpgradeType::ALL.iter().map(|u| RuleAction::BuyUpgrade(*u)).collect();
+        for channel in MarketingChannel::ADVERTISING {
+            actions.push(RuleAction::StartChannel(channel));
+            actions.push(RuleAction::PauseChannel(channel));
+        }
+        actions
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            RuleAction::BuyUpgrade(upgrade) => format!("buy {}", upgrade.name()),
+            RuleAction::StartChannel(channel) => format!("start {}", channel.name()),
+            RuleAction::PauseChannel(channel) => format!("pause {}", channel.name()),
+        }
+    }
+}
+
+/// A single automation rule
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct Rule {
+    pub metric: Metric,
+    pub comparison: Comparison,
+    pub threshold: f64,
+    pub action: RuleAction,
+}
+
+impl Rule {
+    pub fn describe(&self) -> String {
+        format!(
+            "If {} {} {}, {}",
+            self.metric.name(),
+            self.comparison.symbol(),
+            self.metric.format_value(self.threshold),
+            self.action.describe()
+        )
+    }
+
+    fn holds(&self, game_state: &GameState, world: &WorldState) -> bool {
+        let value = self.metric.current(game_state, world);
+        match self.comparison {
+            Comparison::Above => value > self.threshold,
+            Comparison::Below => value < self.threshold,
+        }
+    }
+}
+
+/// The rule being put together in the automation panel
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct RuleDraft {
+    pub metric: Metric,
+    pub comparison: Comparison,
+    pub threshold_index: usize,
+    pub action_index: usize,
+}
+
+impl Default for RuleDraft {
+    fn default() -> Self {
+        Self {
+            metric: Metric::Money,
+            comparison: Comparison::Above,
+            threshold_index: 3,
+            action_index: 0,
+        }
+    }
+}
+
+impl RuleDraft {
+    pub fn threshold(&self) -> f64 {
+        let thresholds = self.metric.thresholds();
+        thresholds[self.threshold_index.min(thresholds.len() - 1)]
+    }
+
+    pub fn action(&self) -> RuleAction {
+        let actions = RuleAction::all();
+        actions[self.action_index % actions.len()]
+    }
+
+    pub fn to_rule(self) -> Rule {
+        Rule {
+            metric: self.metric,
+            comparison: self.comparison,
+            threshold: self.threshold(),
+            action: self.action(),
+        }
+    }
+}
+
+/// The player's rules, the draft in the builder and what the rules have done
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct AutomationState {
+    pub rules: Vec<Rule>,
+    pub draft: RuleDraft,
+    /// Most recent first
+    pub log: VecDeque<String>,
+}
+
+impl AutomationState {
+    /// Add the draft as a new rule. Returns false if the rule list is full.
+    pub fn add_draft(&mut self) -> bool {
+        if self.rules.len() >= MAX_RULES {
+            return false;
+        }
+        let rule = self.draft.to_rule();
+        self.rules.push(rule);
+        true
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.rules.len() {
+            self.rules.remove(index);
+        }
+    }
+
+    fn record(&mut self, entry: String) {
+        self.log.push_front(entry);
+        self.log.truncate(LOG_LENGTH);
+    }
+}
+
+pub struct AutomationPlugin;
+
+impl Plugin for AutomationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AutomationState>()
+            .add_systems(
+                Update,
+                run_automation_rules
+                    .run_if(in_state(AppState::Playing))
+                    .run_if(automation_researched),
+            );
+    }
+}
+
+fn automation_researched(research: Res<ResearchState>) -> bool {
+    research.is_unlocked(Tech::Automation)
+}
+
+/// Run every rule once per game day, in order
+fn run_automation_rules(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    world: Res<WorldState>,
+    mut automation: ResMut<AutomationState>,
+    mut game_state: ResMut<GameState>,
+    mut upgrades: ResMut<UpgradeState>,
+    mut marketing: ResMut<MarketingState>,
+    mut purchase_events: MessageWriter<UpgradePurchasedEvent>,
+) {
+    for event in day_events.read() {
+        if automation.rules.is_empty() {
+            continue;
+        }
+
+        let date = event.date.format();
+        let rules = automation.rules.clone();
+        for rule in rules {
+            if !rule.holds(&game_state, &world) {
+                continue;
+            }
+
+            let tier = ReputationTier::from_reputation(game_state.reputation);
+            let result = match rule.action {
+                RuleAction::BuyUpgrade(upgrade) => {
+                    if upgrades.purchase(upgrade, &mut game_state) {
+                        purchase_events.write(UpgradePurchasedEvent { upgrade });
+                        Some(format!("bought {}", upgrade.name()))
+                    } else {
+                        Some(format!("couldn't afford {}", upgrade.name()))
+                    }
+                }
+                RuleAction::StartChannel(channel) if !marketing.is_active(channel) => {
+                    if marketing.set_active(channel, true, tier) {
+                        Some(format!("started {}", channel.name()))
+                    } else {
+                        Some(format!("{} refused to work with us", channel.name()))
+                    }
+                }
+                RuleAction::PauseChannel(channel) if marketing.is_active(channel) => {
+                    marketing.set_active(channel, false, tier);
+                    Some(format!("paused {}", channel.name()))
+                }
+                // Already in the requested state - nothing to report
+                _ => None,
+            };
+
+            if let Some(result) = result {
+                automation.record(format!("{}: {}", date, result));
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! Background mode - the business keeps ticking over while the window is minimized
+//!
+//! Native builds with the `background` feature only. With "Run when
+//! minimized" on, minimizing the window drops the game to a few updates a
+//! second (see `power`) while the simulation carries on, and the window's
+//! title turns into a status line: money and Things made, led by the latest
+//! scandal or milestone until the window comes back. There's no system tray
+//! icon; the taskbar entry is all the player sees of the game meanwhile.
+
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowFocused, WindowOccluded};
+use crate::currency::money_whole;
+use crate::game_state::{AppState, GameState, MilestoneEvent, ReputationChangedEvent};
+use crate::power::PowerMode;
+use crate::reputation::SCANDAL_REPUTATION;
+use crate::settings::Settings;
+use crate::thing_type::ThingRegistry;
+
+/// Whether the game is running in the background, and what's happened since
+#[derive(Resource, Default)]
+pub struct Background {
+    /// The window's title from before it was minimized
+    title: Option<String>,
+    /// The latest scandal or milestone, to lead the title with
+    notice: Option<String>,
+    in_scandal: bool,
+}
+
+impl Background {
+    pub fn is_backgrounded(&self) -> bool {
+        self.title.is_some()
+    }
+}
+
+pub struct BackgroundPlugin;
+
+impl Plugin for BackgroundPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Background>().add_systems(
+            Update,
+            (enter_background, notify_major_events, update_status_title)
+                .chain()
+                .run_if(in_state(AppState::Playing)),
+        );
+    }
+}
+
+/// Drop to background mode when the window is minimized, and come back when it returns
+fn enter_background(
+    mut occluded_events: MessageReader<WindowOccluded>,
+    mut focus_events: MessageReader<WindowFocused>,
+    settings: Res<Settings>,
+    mut background: ResMut<Background>,
+    mut power: ResMut<PowerMode>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let hidden = occluded_events.read().last().map(|event| event.occluded);
+    let focused = focus_events.read().any(|event| event.focused);
+    let Ok(mut window) = window_query.single_mut() else {
+        return;
+    };
+
+    if hidden == Some(true) && settings.background_mode && !background.is_backgrounded() {
+        background.title = Some(window.title.clone());
+        power.backgrounded = true;
+    } else if (hidden == Some(false) || focused) && background.is_backgrounded() {
+        if let Some(title) = background.title.take() {
+            window.title = title;
+        }
+        background.notice = None;
+        power.backgrounded = false;
+    }
+}
+
+/// Note scandals and milestones while in the background
+fn notify_major_events(
+    mut milestone_events: MessageReader<MilestoneEvent>,
+    mut rep_events: MessageReader<ReputationChangedEvent>,
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    mut background: ResMut<Background>,
+) {
+    let mut notice = milestone_events.read().last().map(|event| event.milestone_type.describe());
+    for event in rep_events.read() {
+        if !things.is_shady(game_state.thing_type) {
+            continue;
+        }
+        if event.new_reputation < SCANDAL_REPUTATION && !background.in_scandal {
+            background.in_scandal = true;
+            notice = Some("SCANDAL".to_string());
+        } else if event.new_reputation >= SCANDAL_REPUTATION + 0.5 {
+            background.in_scandal = false;
+        }
+    }
+
+    if let Some(notice) = notice.filter(|_| background.is_backgrounded()) {
+        background.notice = Some(notice);
+    }
+}
+
+/// Keep the window title showing how the business is doing
+fn update_status_title(
+    background: Res<Background>,
+    game_state: Res<GameState>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Some(title) = &background.title else {
+        return;
+    };
+    let Ok(mut window) = window_query.single_mut() else {
+        return;
+    };
+
+    let stats = format!("{} - {} Things - {}", money_whole(game_state.money), game_state.things_produced, title);
+    let status = match &background.notice {
+        Some(notice) => format!("(!) {} | {}", notice, stats),
+        None => stats,
+    };
+    if window.title != status {
+        window.title = status;
+    }
+}
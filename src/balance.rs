@@ -0,0 +1,137 @@
+//! Balancing mode (`--balance`) - bots play many headless runs and the outcomes are summarised
+//!
+//! Every [`PlayerAgent`] strategy plays every Thing over a range of world
+//! seeds, with no window and the clock running as fast as the CPU allows.
+//! The spread of final money, reputation and output is printed to stdout
+//! and written to `balance_output.txt`.
+//!
+//! `--balance-runs=N` sets the seeds per strategy and Thing (default 20) and
+//! `--balance-days=N` the length of each run (default 365). With
+//! `--balance-snapshots` every run's final state is also written as JSON to
+//! `balance_snapshots/`, for digging into the outliers.
+
+use bevy::prelude::*;
+use std::fs;
+use std::path::Path;
+use crate::agent::{GreedyClicker, MarketingMaximalist, PlayerAgent};
+use crate::sim::SimWorld;
+use crate::thing_type::{ThingDefinition, ThingRegistry, ThingType};
+
+const DEFAULT_RUNS: u32 = 20;
+const DEFAULT_DAYS: u32 = 365;
+const SNAPSHOT_DIR: &str = "balance_snapshots";
+
+/// How a single run ended
+#[derive(Debug, Clone, Copy)]
+struct Outcome {
+    money: f64,
+    reputation: f32,
+    things_produced: u64,
+}
+
+pub struct Balance {
+    runs: u32,
+    days: u32,
+    snapshots: bool,
+}
+
+impl Balance {
+    /// Parse `--balance` / `--balance-runs=N` / `--balance-days=N` / `--balance-snapshots`
+    /// from the command line, if present
+    pub fn from_args() -> Option<Self> {
+        let args: Vec<String> = std::env::args().collect();
+        if !args.iter().any(|arg| arg == "--balance") {
+            return None;
+        }
+
+        let number = |prefix: &str, default: u32| {
+            args.iter()
+                .find_map(|arg| arg.strip_prefix(prefix))
+                .and_then(|n| n.parse().ok())
+                .unwrap_or(default)
+        };
+        Some(Self {
+            runs: number("--balance-runs=", DEFAULT_RUNS),
+            days: number("--balance-days=", DEFAULT_DAYS),
+            snapshots: args.iter().any(|arg| arg == "--balance-snapshots"),
+        })
+    }
+
+    /// Play every strategy and Thing over every seed and report
+    pub fn run(&self) {
+        let strategies: [fn() -> Box<dyn PlayerAgent>; 2] =
+            [|| Box::new(GreedyClicker), || Box::new(MarketingMaximalist)];
+
+        let things = ThingRegistry::load();
+        let mut report = format!("Balance report: {} seeds x {} days per strategy and Thing\n", self.runs, self.days);
+        for strategy in strategies {
+            report += &format!("\n{}\n", strategy().name());
+            for thing_type in things.all() {
+                let outcomes: Vec<Outcome> = (0..self.runs)
+                    .map(|seed| self.play_headless(&things, strategy(), thing_type, seed))
+                    .collect();
+                report += &summarise(&things[thing_type], &outcomes);
+            }
+        }
+
+        println!("{}", report);
+        if let Err(err) = fs::write("balance_output.txt", &report) {
+            warn!("Couldn't write balance_output.txt: {}", err);
+        }
+    }
+
+    /// Play one run to the end
+    fn play_headless(&self, things: &ThingRegistry, agent: Box<dyn PlayerAgent>, thing_type: ThingType, seed: u32) -> Outcome {
+        let name = format!("{}_{}_{}", agent.name().to_lowercase().replace(' ', "_"), things[thing_type].id, seed);
+        let mut sim = SimWorld::new(things, thing_type, seed).with_agent(agent);
+        sim.run_days(self.days);
+
+        if self.snapshots {
+            if let Err(err) = write_snapshot(&sim, &name) {
+                warn!("Couldn't write the snapshot for {}: {}", name, err);
+            }
+        }
+
+        let game_state = sim.game_state();
+        Outcome {
+            money: game_state.money.dollars(),
+            reputation: game_state.reputation,
+            things_produced: game_state.things_produced,
+        }
+    }
+}
+
+fn write_snapshot(sim: &SimWorld, name: &str) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(&sim.to_json()?).map_err(|err| err.to_string())?;
+    fs::create_dir_all(SNAPSHOT_DIR).map_err(|err| err.to_string())?;
+    fs::write(Path::new(SNAPSHOT_DIR).join(format!("{}.json", name)), json).map_err(|err| err.to_string())
+}
+
+/// One line of the report: the spread of outcomes for a Thing
+fn summarise(thing: &ThingDefinition, outcomes: &[Outcome]) -> String {
+    if outcomes.is_empty() {
+        return format!("  {:<10} no runs\n", thing.name);
+    }
+
+    let mut money: Vec<f64> = outcomes.iter().map(|o| o.money).collect();
+    money.sort_by(f64::total_cmp);
+    let percentile = |p: f64| money[((money.len() - 1) as f64 * p).round() as usize];
+    let broke = outcomes.iter().filter(|o| o.money < 0.0).count();
+    let reputation = outcomes.iter().map(|o| o.reputation).sum::<f32>() / outcomes.len() as f32;
+    let things = outcomes.iter().map(|o| o.things_produced).sum::<u64>() / outcomes.len() as u64;
+
+    format!(
+        "  {:<10} money min ${:.0} / p25 ${:.0} / median ${:.0} / p75 ${:.0} / max ${:.0}, \
+         in debt {}/{}, avg reputation {:.2}, avg Things {}\n",
+        thing.name,
+        percentile(0.0),
+        percentile(0.25),
+        percentile(0.5),
+        percentile(0.75),
+        percentile(1.0),
+        broke,
+        outcomes.len(),
+        reputation,
+        things
+    )
+}
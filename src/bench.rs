@@ -0,0 +1,218 @@
+//! Benchmark mode (`--bench`) - a worst-case run for catching performance regressions
+//!
+//! Skips Thing selection, maxes out upgrades, floods the screen with floating
+//! text and fills the history, then runs a fixed number of frames and reports
+//! frame-time percentiles to stdout and `bench_output.txt`.
+//!
+//! `--bench-frames=N` changes the number of measured frames (default 600).
+//!
+//! The bench run is fake and plays with the settings, so none of it is saved:
+//! the active profile's save and `config.json` are left as they were.
+
+use bevy::prelude::*;
+use bevy::app::AppExit;
+use std::fs;
+use crate::automation::{AutomationState, RuleDraft, MAX_RULES};
+use crate::business::{UpgradeState, UpgradeType};
+use crate::currency::Money;
+use crate::economy::{DayAdvancedEvent, WorldState};
+use crate::game_state::{AppState, GameState, InGame};
+use crate::settings::{load_config, Settings};
+use crate::stats::{DailySnapshot, RunStats};
+use crate::thing_type::{ThingRegistry, ThingType};
+use crate::ui::MainScreen;
+
+/// Frames measured when `--bench-frames` isn't given
+const DEFAULT_FRAMES: usize = 600;
+/// Frames ignored at the start while everything spins up
+const WARMUP_FRAMES: usize = 60;
+/// Floating text entities kept on screen
+const FLOATING_TEXTS: usize = 3_000;
+/// Purchases of each upgrade
+const UPGRADES_EACH: u32 = 200;
+/// Days of fake history in the stats log (ten years)
+const HISTORY_DAYS: usize = 3_650;
+
+/// Number of frames to measure
+#[derive(Resource)]
+struct BenchConfig {
+    frames: usize,
+}
+
+pub struct BenchPlugin {
+    pub frames: usize,
+}
+
+impl BenchPlugin {
+    /// Parse `--bench` / `--bench-frames=N` from the command line, if present
+    pub fn from_args() -> Option<Self> {
+        let args: Vec<String> = std::env::args().collect();
+        if !args.iter().any(|arg| arg == "--bench") {
+            return None;
+        }
+
+        let frames = args
+            .iter()
+            .find_map(|arg| arg.strip_prefix("--bench-frames="))
+            .and_then(|n| n.parse().ok())
+            .unwrap_or(DEFAULT_FRAMES);
+        Some(Self { frames })
+    }
+}
+
+/// Present while benchmarking (see [`benchmarking`])
+#[derive(Resource)]
+pub struct Benchmarking;
+
+/// Run condition: this is a `--bench` run, which mustn't save anything
+pub fn benchmarking(bench: Option<Res<Benchmarking>>) -> bool {
+    bench.is_some()
+}
+
+/// Frame times collected so far (seconds)
+#[derive(Resource, Default)]
+struct FrameSamples {
+    frames_seen: usize,
+    samples: Vec<f32>,
+}
+
+/// Text node that drifts around to stand in for floating numbers and particles
+#[derive(Component)]
+struct BenchFloater {
+    phase: f32,
+}
+
+impl Plugin for BenchPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(BenchConfig { frames: self.frames })
+            .insert_resource(Benchmarking)
+            .init_resource::<FrameSamples>()
+            .add_systems(Startup, (setup_worst_case.after(load_config), uncap_frame_rate))
+            .add_systems(OnEnter(InGame), spawn_floaters)
+            .add_systems(Update, (move_floaters, record_frame_times).run_if(in_state(AppState::Playing)));
+    }
+}
+
+/// Skip selection and build a late-game business
+fn setup_worst_case(
+    mut game_state: ResMut<GameState>,
+    mut upgrades: ResMut<UpgradeState>,
+    mut world: ResMut<WorldState>,
+    mut stats: ResMut<RunStats>,
+    mut automation: ResMut<AutomationState>,
+    mut settings: ResMut<Settings>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    game_state.thing_type = Some(ThingType::CHEAP);
+    game_state.money = Money::from_cents(i64::MAX / 4);
+
+    for upgrade in UpgradeType::ALL {
+        for _ in 0..UPGRADES_EACH {
+            upgrades.purchase(upgrade, &mut game_state);
+        }
+    }
+    game_state.money = Money::from_dollars(1_000_000_000.0);
+
+    // Runs should be comparable, so no random modifiers
+    settings.fortune_cookie = false;
+
+    // Days fly by so every daily system runs constantly
+    world.time_scale = 0.05;
+
+    // A long history for everything that reads it (Cheap is built in, so any registry has it)
+    let today = DailySnapshot::capture(&game_state, &ThingRegistry::default(), &world, &DayAdvancedEvent::today(&world));
+    stats.snapshots = vec![today; HISTORY_DAYS];
+    for _ in 0..MAX_RULES {
+        automation.draft = RuleDraft::default();
+        automation.add_draft();
+    }
+
+    next_state.set(AppState::Playing);
+}
+
+/// Frame times are meaningless when vsync holds every frame to the refresh rate
+fn uncap_frame_rate(mut windows: Query<&mut Window>) {
+    for mut window in &mut windows {
+        window.present_mode = bevy::window::PresentMode::AutoNoVsync;
+    }
+}
+
+fn spawn_floaters(mut commands: Commands) {
+    for i in 0..FLOATING_TEXTS {
+        commands.spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            Text::new(format!("+${}", i)),
+            TextFont {
+                font_size: 14.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.5, 0.9, 0.5)),
+            GlobalZIndex(90),
+            BenchFloater { phase: i as f32 * 0.37 },
+            MainScreen,
+        ));
+    }
+}
+
+fn move_floaters(time: Res<Time>, mut query: Query<(&mut Node, &mut TextColor, &BenchFloater)>) {
+    let t = time.elapsed_secs();
+    for (mut node, mut color, floater) in &mut query {
+        let phase = floater.phase + t;
+        node.left = Val::Percent(50.0 + phase.sin() * 45.0);
+        node.top = Val::Percent(50.0 + (phase * 1.3).cos() * 45.0);
+        color.0 = color.0.with_alpha(0.5 + phase.sin() * 0.5);
+    }
+}
+
+/// Collect frame times and report once enough frames have run
+fn record_frame_times(
+    time: Res<Time>,
+    config: Res<BenchConfig>,
+    mut samples: ResMut<FrameSamples>,
+    mut exit: MessageWriter<AppExit>,
+) {
+    samples.frames_seen += 1;
+    if samples.frames_seen <= WARMUP_FRAMES {
+        return;
+    }
+
+    samples.samples.push(time.delta_secs());
+    if samples.samples.len() < config.frames {
+        return;
+    }
+
+    let report = frame_report(&mut samples.samples);
+    println!("{}", report);
+    if let Err(err) = fs::write("bench_output.txt", &report) {
+        warn!("Couldn't write bench_output.txt: {}", err);
+    }
+    exit.write(AppExit::Success);
+}
+
+/// Percentile summary of frame times (sorts `samples`)
+fn frame_report(samples: &mut [f32]) -> String {
+    samples.sort_by(f32::total_cmp);
+    let percentile = |p: f32| {
+        let index = ((samples.len() - 1) as f32 * p).round() as usize;
+        samples[index] * 1000.0
+    };
+    let mean = samples.iter().sum::<f32>() / samples.len() as f32 * 1000.0;
+
+    format!(
+        "Thing Simulator benchmark: {} frames\n\
+         mean {:.2} ms\n\
+         p50  {:.2} ms\n\
+         p90  {:.2} ms\n\
+         p99  {:.2} ms\n\
+         max  {:.2} ms\n",
+        samples.len(),
+        mean,
+        percentile(0.5),
+        percentile(0.9),
+        percentile(0.99),
+        percentile(1.0),
+    )
+}
@@ -0,0 +1,249 @@
+//! Bundles - selling Things in threes, or in a very nice box
+//!
+//! Once Product Packaging is researched the player can put bundles on the
+//! shelf next to the single Thing. Each bundle has its own price adjustment:
+//! a 3-pack moves more Things per customer at a discount, a Deluxe Box sells
+//! one Thing at a markup. How many customers pick a bundle depends on the
+//! Thing and on how steep the price is, and the mix sets how many Things go
+//! out the door and what each one earns.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::economy::DayAdvancedEvent;
+use crate::game_state::{AppState, GameState, MoneyChangedEvent};
+use crate::thing_type::{PriceTier, ThingRegistry};
+
+/// Step for the price adjustment buttons
+pub const PRICE_STEP: f32 = 0.05;
+
+/// Ways to sell Things
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bundle {
+    Single,
+    ThreePack,
+    DeluxeBox,
+}
+
+impl Bundle {
+    pub const ALL: [Bundle; 3] = [Bundle::Single, Bundle::ThreePack, Bundle::DeluxeBox];
+    /// Bundles the player can put on sale
+    pub const OPTIONAL: [Bundle; 2] = [Bundle::ThreePack, Bundle::DeluxeBox];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Bundle::Single => "Single Thing",
+            Bundle::ThreePack => "3-Pack",
+            Bundle::DeluxeBox => "Deluxe Box",
+        }
+    }
+
+    /// Things per sale
+    pub fn things(&self) -> u32 {
+        match self {
+            Bundle::ThreePack => 3,
+            Bundle::Single | Bundle::DeluxeBox => 1,
+        }
+    }
+
+    /// Price adjustment a bundle starts at
+    fn default_adjustment(&self) -> f32 {
+        match self {
+            Bundle::Single => 0.0,
+            Bundle::ThreePack => -0.1,
+            Bundle::DeluxeBox => 0.5,
+        }
+    }
+
+    /// Lowest and highest price adjustment allowed
+    pub fn adjustment_range(&self) -> (f32, f32) {
+        match self {
+            Bundle::Single => (0.0, 0.0),
+            Bundle::ThreePack => (-0.3, 0.1),
+            Bundle::DeluxeBox => (0.0, 1.5),
+        }
+    }
+
+    /// Share of customers who'd pick this bundle at its default price
+    fn appeal(&self, tier: PriceTier) -> f32 {
+        match (self, tier) {
+            (Bundle::Single, _) => 0.0,
+            (Bundle::ThreePack, PriceTier::Budget) => 0.35,
+            (Bundle::ThreePack, PriceTier::Luxury) => 0.05,
+            (Bundle::ThreePack, PriceTier::Mid) => 0.2,
+            (Bundle::DeluxeBox, PriceTier::Luxury) => 0.3,
+            (Bundle::DeluxeBox, PriceTier::Budget) => 0.05,
+            (Bundle::DeluxeBox, PriceTier::Mid) => 0.15,
+        }
+    }
+
+    /// How quickly appeal drops as the price goes up (per +100%)
+    fn price_sensitivity(&self) -> f32 {
+        match self {
+            Bundle::Single => 0.0,
+            Bundle::ThreePack => 3.0,
+            Bundle::DeluxeBox => 0.6,
+        }
+    }
+}
+
+/// One bundle's share of sales
+#[derive(Debug, Clone, Copy)]
+pub struct BundleShare {
+    pub bundle: Bundle,
+    /// Share of customers
+    pub customers: f32,
+    /// Price per Thing relative to a single
+    pub price: f32,
+}
+
+/// How one bundle has sold so far
+#[derive(Debug, Clone, Copy, Default, Reflect)]
+pub struct BundleSales {
+    pub sales: f64,
+    pub things: f64,
+    pub revenue: f64,
+}
+
+/// Bundles on offer, their prices, and how they've sold
+#[derive(Resource, Reflect)]
+#[reflect(Resource, Default)]
+pub struct BundleState {
+    offered: [bool; Bundle::ALL.len()],
+    adjustments: [f32; Bundle::ALL.len()],
+    sales: [BundleSales; Bundle::ALL.len()],
+    /// Revenue and Things sold so far today (split across bundles at day's end)
+    revenue_today: f64,
+    last_customers_served: u64,
+}
+
+impl Default for BundleState {
+    fn default() -> Self {
+        Self {
+            offered: [true, false, false],
+            adjustments: Bundle::ALL.map(|b| b.default_adjustment()),
+            sales: Default::default(),
+            revenue_today: 0.0,
+            last_customers_served: 0,
+        }
+    }
+}
+
+impl BundleState {
+    fn index(bundle: Bundle) -> usize {
+        Bundle::ALL.iter().position(|b| *b == bundle).unwrap_or(0)
+    }
+
+    pub fn is_offered(&self, bundle: Bundle) -> bool {
+        self.offered[Self::index(bundle)]
+    }
+
+    pub fn toggle(&mut self, bundle: Bundle) {
+        if bundle != Bundle::Single {
+            let offered = &mut self.offered[Self::index(bundle)];
+            *offered = !*offered;
+        }
+    }
+
+    /// Price adjustment, e.g. -0.1 for 10% off
+    pub fn adjustment(&self, bundle: Bundle) -> f32 {
+        self.adjustments[Self::index(bundle)]
+    }
+
+    /// Nudge a bundle's price by `steps` price steps, within its range
+    pub fn adjust(&mut self, bundle: Bundle, steps: i32) {
+        let (min, max) = bundle.adjustment_range();
+        let adjustment = &mut self.adjustments[Self::index(bundle)];
+        *adjustment = (*adjustment + steps as f32 * PRICE_STEP).clamp(min, max);
+    }
+
+    pub fn sales(&self, bundle: Bundle) -> BundleSales {
+        self.sales[Self::index(bundle)]
+    }
+
+    /// Who buys what, for a Thing at `tier`
+    pub fn mix(&self, tier: PriceTier) -> Vec<BundleShare> {
+        let mut shares: Vec<BundleShare> = Bundle::OPTIONAL
+            .into_iter()
+            .filter(|bundle| self.is_offered(*bundle))
+            .map(|bundle| {
+                let adjustment = self.adjustment(bundle);
+                let drop = (adjustment - bundle.default_adjustment()) * bundle.price_sensitivity();
+                BundleShare {
+                    bundle,
+                    customers: (bundle.appeal(tier) * (1.0 - drop)).clamp(0.0, 0.6),
+                    price: 1.0 + adjustment,
+                }
+            })
+            .collect();
+
+        let taken: f32 = shares.iter().map(|s| s.customers).sum();
+        shares.insert(
+            0,
+            BundleShare {
+                bundle: Bundle::Single,
+                customers: (1.0 - taken).max(0.0),
+                price: 1.0,
+            },
+        );
+        shares
+    }
+
+    /// Things per customer and revenue per Thing (relative to singles), for a Thing at `tier`
+    pub fn sales_factors(&self, tier: PriceTier) -> (f64, f64) {
+        let mix = self.mix(tier);
+        let things: f32 = mix.iter().map(|s| s.customers * s.bundle.things() as f32).sum();
+        let revenue: f32 = mix.iter().map(|s| s.customers * s.bundle.things() as f32 * s.price).sum();
+        if things <= 0.0 {
+            return (1.0, 1.0);
+        }
+        (things as f64, (revenue / things) as f64)
+    }
+}
+
+pub struct BundlePlugin;
+
+impl Plugin for BundlePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BundleState>()
+            .add_systems(Update, track_bundle_sales.run_if(in_state(AppState::Playing)));
+    }
+}
+
+/// Split each day's sales across the bundle mix for the analytics
+fn track_bundle_sales(
+    mut money_events: MessageReader<MoneyChangedEvent>,
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    mut bundles: ResMut<BundleState>,
+) {
+    for event in money_events.read() {
+        if event.delta > 0.0 {
+            bundles.revenue_today += event.delta;
+        }
+    }
+
+    for _ in day_events.read() {
+        let sold = game_state.customers_served.saturating_sub(bundles.last_customers_served) as f64;
+        let revenue = std::mem::take(&mut bundles.revenue_today);
+        bundles.last_customers_served = game_state.customers_served;
+        let Some(thing_type) = game_state.thing_type else {
+            continue;
+        };
+        if sold <= 0.0 {
+            continue;
+        }
+
+        let mix = bundles.mix(things[thing_type].tier);
+        let total_things: f32 = mix.iter().map(|s| s.customers * s.bundle.things() as f32).sum();
+        let total_revenue: f32 = mix.iter().map(|s| s.customers * s.bundle.things() as f32 * s.price).sum();
+        for share in mix {
+            let thing_share = (share.customers * share.bundle.things() as f32 / total_things) as f64;
+            let revenue_share = (share.customers * share.bundle.things() as f32 * share.price / total_revenue) as f64;
+            let sales = &mut bundles.sales[BundleState::index(share.bundle)];
+            sales.things += sold * thing_share;
+            sales.sales += sold * thing_share / share.bundle.things() as f64;
+            sales.revenue += revenue * revenue_share;
+        }
+    }
+}
@@ -2,96 +2,219 @@
 
 use bevy::prelude::*;
 use bevy::ecs::schedule::IntoScheduleConfigs;
-use crate::game_state::{AppState, GameState, ThingProducedEvent, MoneyChangedEvent, ReputationChangedEvent};
-use crate::thing_type::ThingType;
+use bevy::ecs::system::SystemParam;
+use serde::{Deserialize, Serialize};
+use crate::accounting::ExpenseCategory;
+use crate::ai_boom::AiState;
+use crate::bundles::BundleState;
+use crate::counterfeits::CounterfeitState;
+use crate::currency::Money;
+use crate::difficulty::DifficultyConfig;
+use crate::game_state::{game_running, AppState, GameState, ThingProducedEvent, MoneyChangedEvent, ReputationChangedEvent};
+use crate::thing_type::ThingRegistry;
+use crate::eco::EcoState;
 use crate::economy::WorldState;
+use crate::fads::FadState;
+use crate::fortune::RunModifiers;
+use crate::game_clock::GameClock;
+use crate::limited_editions::LimitedEditions;
+use crate::macro_dynamics;
 use crate::marketing::MarketingState;
+use crate::pandemic::PandemicState;
+use crate::price_war::PriceWarState;
+use crate::reputation::customer_volume_factor;
+use crate::utilities::SOLAR_REPUTATION_BONUS;
+use crate::venues::Venues;
+use crate::word_of_mouth::WordOfMouth;
 
 pub struct BusinessPlugin;
 
 impl Plugin for BusinessPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            (
-                process_sales,
-                update_reputation,
-                apply_reputation_decay,
-            )
-                .run_if(in_state(AppState::Playing)),
-        );
+        app.add_message::<UpgradePurchasedEvent>()
+            .init_resource::<AnnouncedReputation>()
+            .add_systems(
+                Update,
+                (
+                    process_sales,
+                    update_reputation,
+                    apply_reputation_decay,
+                )
+                    .run_if(in_state(AppState::Playing).and(game_running)),
+            );
     }
 }
 
+/// Everything outside the player's Thing that moves demand and revenue
+#[derive(SystemParam)]
+struct SalesFactors<'w> {
+    things: Res<'w, ThingRegistry>,
+    world: Res<'w, WorldState>,
+    marketing: Res<'w, MarketingState>,
+    modifiers: Res<'w, RunModifiers>,
+    difficulty: Res<'w, DifficultyConfig>,
+    eco: Res<'w, EcoState>,
+    word_of_mouth: Res<'w, WordOfMouth>,
+    venues: Res<'w, Venues>,
+    bundles: Res<'w, BundleState>,
+    pandemic: Res<'w, PandemicState>,
+    ai: Res<'w, AiState>,
+    price_war: Res<'w, PriceWarState>,
+    counterfeits: Res<'w, CounterfeitState>,
+    editions: Res<'w, LimitedEditions>,
+    fads: Res<'w, FadState>,
+}
+
+/// Reputation has to move this far from what was last announced before it's announced again
+const REPUTATION_EVENT_STEP: f32 = 0.05;
+/// Reputation at which sales earn their full price (above it they earn more)
+pub const PAR_REPUTATION: f64 = 2.5;
+/// Reputation gained per game day for each level of marketing (not for Bad Things)
+pub const MARKETING_REPUTATION_GAIN: f32 = 0.001;
+
+/// The reputation sales and decay last announced, shared so neither repeats the other
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct AnnouncedReputation(Option<f32>);
+
+impl AnnouncedReputation {
+    /// Announce reputation once it has moved a meaningful amount since it was last announced
+    fn report(&mut self, reputation: f32, rep_events: &mut MessageWriter<ReputationChangedEvent>) {
+        if self.0.is_some_and(|last| (reputation - last).abs() < REPUTATION_EVENT_STEP) {
+            return;
+        }
+        self.0 = Some(reputation);
+        rep_events.write(ReputationChangedEvent {
+            new_reputation: reputation,
+        });
+    }
+}
+
+/// What one batch of sales couldn't settle, left for the next
+#[derive(Default)]
+struct SalesCarry {
+    /// Would-be customers short of a whole one
+    customers: f64,
+    /// Revenue short of a whole cent
+    revenue: f64,
+}
+
 /// Process sales when Things are produced
-/// Revenue is affected by invisible world forces AND player-controlled marketing
+/// Revenue is affected by invisible world forces AND player-controlled marketing.
+/// Everything made in a frame sells as one batch, however many events it arrived in.
 fn process_sales(
     mut game_state: ResMut<GameState>,
-    world: Res<WorldState>,
-    marketing: Res<MarketingState>,
+    factors: SalesFactors,
     mut thing_events: MessageReader<ThingProducedEvent>,
     mut money_events: MessageWriter<MoneyChangedEvent>,
     mut rep_events: MessageWriter<ReputationChangedEvent>,
+    mut carry: Local<SalesCarry>,
+    mut announced: ResMut<AnnouncedReputation>,
 ) {
-    for event in thing_events.read() {
-        if let Some(thing_type) = game_state.thing_type {
-            // Calculate revenue based on multiple factors
-            let base_price = thing_type.base_price();
-
-            // Player-controlled factors
-            let old_marketing_bonus = 1.0 + (game_state.marketing_level as f64 * 0.1);
-            let marketing_boost = marketing.calculate_demand_boost() as f64;
-            let reputation_bonus = game_state.reputation as f64 / 2.5;
-
-            // Invisible world factors (player has NO control over these)
-            let world_demand = world.calculate_demand_modifier() as f64;
-            let daily_chaos = world.daily_chaos() as f64;
-
-            // Price multiplier from marketing strategy
-            let price_mult = marketing.price_multiplier as f64;
-
-            // Final revenue calculation
-            let revenue = event.amount as f64
-                * base_price
-                * price_mult
-                * old_marketing_bonus
-                * marketing_boost
-                * reputation_bonus
-                * world_demand
-                * daily_chaos;
-
-            let _old_money = game_state.money;
-            game_state.money += revenue;
-            game_state.customers_served += event.amount;
-
-            money_events.write(MoneyChangedEvent {
-                new_amount: game_state.money,
-                delta: revenue,
-            });
-
-            // Update reputation based on Thing type
-            let rep_change = thing_type.reputation_per_sale() * event.amount as f32;
-            let old_rep = game_state.reputation;
-            game_state.reputation = (game_state.reputation + rep_change).clamp(0.0, 5.0);
-
-            if (game_state.reputation - old_rep).abs() > 0.001 {
-                rep_events.write(ReputationChangedEvent {
-                    new_reputation: game_state.reputation,
-                });
-            }
-        }
+    let made: u64 = thing_events.read().map(|event| event.amount).sum();
+    if made == 0 {
+        return;
     }
+
+    // Anything made for the old Thing before retooling is written off
+    if game_state.retooling {
+        carry.customers = 0.0;
+        return;
+    }
+    let Some(thing_type) = game_state.thing_type else {
+        return;
+    };
+    let thing = &factors.things[thing_type];
+
+    // Bundles decide how many Things each customer takes home, and what each one earns
+    let (things_per_customer, bundle_price) = factors.bundles.sales_factors(thing.tier);
+
+    // Word of mouth (and any pop-up venue, the pandemic, the chatbot, what inflation
+    // has left in people's pockets, how the price compares with the competitors', any
+    // fad being ridden, and how many buy fakes instead) decides how many would-be customers there are,
+    // reputation how many actually buy
+    carry.customers += made as f64
+        * factors.word_of_mouth.demand_factor(&factors.world)
+        * factors.pandemic.venue_boost(factors.venues.demand_boost())
+        * factors.pandemic.customer_factor(thing, &factors.world)
+        * factors.ai.customer_factor()
+        * macro_dynamics::spending_power(&factors.world, thing)
+        * factors.price_war.demand_factor(factors.marketing.price_multiplier)
+        * factors.fads.customer_factor(&factors.world)
+        * factors.counterfeits.customer_factor()
+        * customer_volume_factor(game_state.reputation)
+        * things_per_customer;
+    let units_sold = carry.customers.floor() as u64;
+    carry.customers -= units_sold as f64;
+    if units_sold == 0 {
+        return;
+    }
+
+    // Calculate revenue based on multiple factors
+    let base_price = thing.base_price;
+
+    // Player-controlled factors
+    let old_marketing_bonus = 1.0 + (game_state.marketing_level as f64 * 0.1);
+    let marketing_boost = factors.marketing.calculate_demand_boost() as f64;
+    let reputation_bonus = game_state.reputation as f64 / PAR_REPUTATION;
+
+    // Invisible world factors (player has NO control over these)
+    let world_demand = factors.world.calculate_demand_modifier() as f64;
+    let chaos_variance = factors.modifiers.chaos_variance * factors.difficulty.chaos_variance;
+    let daily_chaos = factors.world.daily_chaos(chaos_variance) as f64;
+
+    // Price multiplier from marketing strategy, the bundle mix and any limited edition
+    let price_mult = factors.marketing.price_multiplier as f64 * bundle_price * factors.editions.price_factor();
+
+    // Terry's fortune cookie
+    let fortune_mult = factors.modifiers.revenue;
+
+    // Eco-conscious customers (only buy from certified businesses)
+    let eco_mult = factors.eco.segment_multiplier();
+
+    // Final revenue calculation
+    let revenue = units_sold as f64
+        * base_price
+        * price_mult
+        * old_marketing_bonus
+        * marketing_boost
+        * reputation_bonus
+        * world_demand
+        * daily_chaos
+        * fortune_mult
+        * eco_mult;
+
+    // Paid in whole cents; the fraction of a cent left over goes towards the next batch
+    let (earned, leftover) = Money::whole_cents(revenue + carry.revenue);
+    carry.revenue = leftover;
+    game_state.money += earned;
+    game_state.customers_served += units_sold;
+
+    money_events.write(MoneyChangedEvent {
+        new_amount: game_state.money.dollars(),
+        delta: earned.dollars(),
+    });
+
+    // Update reputation based on Thing type
+    let rep_change = factors
+        .modifiers
+        .scale_reputation_change(thing.reputation_per_sale * units_sold as f32);
+    game_state.reputation = (game_state.reputation + rep_change).clamp(0.0, 5.0);
+    announced.report(game_state.reputation, &mut rep_events);
 }
 
 /// Update reputation based on various factors
 fn update_reputation(
     mut game_state: ResMut<GameState>,
-    time: Res<Time>,
+    things: Res<ThingRegistry>,
+    modifiers: Res<RunModifiers>,
+    clock: Res<GameClock>,
 ) {
     if let Some(thing_type) = game_state.thing_type {
-        // Natural reputation growth for non-Bad Things when marketing
-        if thing_type != ThingType::Bad && game_state.marketing_level > 0 {
-            let marketing_rep_gain = 0.001 * game_state.marketing_level as f32 * time.delta_secs();
+        // Natural reputation growth for honest Things when marketing
+        if !things[thing_type].shady && game_state.marketing_level > 0 {
+            let marketing_rep_gain =
+                MARKETING_REPUTATION_GAIN * game_state.marketing_level as f32 * clock.delta_secs() * modifiers.reputation_gain;
             game_state.reputation = (game_state.reputation + marketing_rep_gain).clamp(0.0, 5.0);
         }
     }
@@ -100,26 +223,29 @@ fn update_reputation(
 /// Apply reputation decay for Bad Things
 fn apply_reputation_decay(
     mut game_state: ResMut<GameState>,
-    time: Res<Time>,
+    things: Res<ThingRegistry>,
+    modifiers: Res<RunModifiers>,
+    clock: Res<GameClock>,
     mut rep_events: MessageWriter<ReputationChangedEvent>,
+    mut announced: ResMut<AnnouncedReputation>,
 ) {
     if let Some(thing_type) = game_state.thing_type {
-        let decay = thing_type.reputation_decay() * time.delta_secs();
+        let decay = things[thing_type].reputation_decay * clock.delta_secs() * modifiers.reputation_loss;
         if decay > 0.0 {
-            let old_rep = game_state.reputation;
             game_state.reputation = (game_state.reputation - decay).max(0.0);
-
-            if (game_state.reputation - old_rep).abs() > 0.01 {
-                rep_events.write(ReputationChangedEvent {
-                    new_reputation: game_state.reputation,
-                });
-            }
+            announced.report(game_state.reputation, &mut rep_events);
         }
     }
 }
 
+/// Message fired when an upgrade is successfully purchased
+#[derive(Event, Message, Clone)]
+pub struct UpgradePurchasedEvent {
+    pub upgrade: UpgradeType,
+}
+
 /// Upgrade types for the business
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Serialize, Deserialize)]
 pub enum UpgradeType {
     BetterTools,      // Increases click power
     HireWorker,       // Increases things per second
@@ -127,9 +253,22 @@ pub enum UpgradeType {
     SocialMedia,      // Marketing level +1
     Billboard,        // Marketing level +2
     InfluencerDeal,   // Marketing level +3
+    StandingDesk,     // Bigger Hustle meter
+    SolarPanels,      // Cheaper utility bills, a little goodwill
 }
 
 impl UpgradeType {
+    pub const ALL: [UpgradeType; 8] = [
+        UpgradeType::BetterTools,
+        UpgradeType::HireWorker,
+        UpgradeType::Automation,
+        UpgradeType::StandingDesk,
+        UpgradeType::SolarPanels,
+        UpgradeType::SocialMedia,
+        UpgradeType::Billboard,
+        UpgradeType::InfluencerDeal,
+    ];
+
     pub fn name(&self) -> &'static str {
         match self {
             UpgradeType::BetterTools => "Better Tools",
@@ -138,6 +277,8 @@ impl UpgradeType {
             UpgradeType::SocialMedia => "Social Media",
             UpgradeType::Billboard => "Billboard",
             UpgradeType::InfluencerDeal => "Influencer Deal",
+            UpgradeType::StandingDesk => "Standing Desk",
+            UpgradeType::SolarPanels => "Solar Panels",
         }
     }
 
@@ -149,6 +290,8 @@ impl UpgradeType {
             UpgradeType::SocialMedia => "+1 Marketing Level",
             UpgradeType::Billboard => "+2 Marketing Level",
             UpgradeType::InfluencerDeal => "+3 Marketing Level",
+            UpgradeType::StandingDesk => "+25 max Hustle",
+            UpgradeType::SolarPanels => "-10% utility bills, +0.1 reputation",
         }
     }
 
@@ -160,11 +303,20 @@ impl UpgradeType {
             UpgradeType::SocialMedia => 75.0,
             UpgradeType::Billboard => 300.0,
             UpgradeType::InfluencerDeal => 1000.0,
+            UpgradeType::StandingDesk => 150.0,
+            UpgradeType::SolarPanels => 800.0,
         }
     }
 
     pub fn is_production(&self) -> bool {
-        matches!(self, UpgradeType::BetterTools | UpgradeType::HireWorker | UpgradeType::Automation)
+        matches!(
+            self,
+            UpgradeType::BetterTools
+                | UpgradeType::HireWorker
+                | UpgradeType::Automation
+                | UpgradeType::StandingDesk
+                | UpgradeType::SolarPanels
+        )
     }
 
     pub fn is_marketing(&self) -> bool {
@@ -173,7 +325,8 @@ impl UpgradeType {
 }
 
 /// Resource tracking upgrade counts
-#[derive(Resource, Default)]
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
 pub struct UpgradeState {
     pub better_tools: u32,
     pub workers: u32,
@@ -181,6 +334,8 @@ pub struct UpgradeState {
     pub social_media: u32,
     pub billboards: u32,
     pub influencer_deals: u32,
+    pub standing_desks: u32,
+    pub solar_panels: u32,
 }
 
 impl UpgradeState {
@@ -192,18 +347,21 @@ impl UpgradeState {
             UpgradeType::SocialMedia => self.social_media,
             UpgradeType::Billboard => self.billboards,
             UpgradeType::InfluencerDeal => self.influencer_deals,
+            UpgradeType::StandingDesk => self.standing_desks,
+            UpgradeType::SolarPanels => self.solar_panels,
         }
     }
 
     pub fn cost(&self, upgrade: UpgradeType) -> f64 {
         let count = self.get_count(upgrade);
-        upgrade.base_cost() * 1.15_f64.powi(count as i32)
+        // Past i32::MAX the cost is long since infinite; don't let the count wrap negative
+        upgrade.base_cost() * 1.15_f64.powi(i32::try_from(count).unwrap_or(i32::MAX))
     }
 
     pub fn purchase(&mut self, upgrade: UpgradeType, game_state: &mut GameState) -> bool {
         let cost = self.cost(upgrade);
         if game_state.money >= cost {
-            game_state.money -= cost;
+            game_state.spend(ExpenseCategory::Upgrades, cost);
 
             match upgrade {
                 UpgradeType::BetterTools => {
@@ -230,6 +388,15 @@ impl UpgradeState {
                     self.influencer_deals += 1;
                     game_state.marketing_level += 3;
                 }
+                UpgradeType::StandingDesk => {
+                    // Capacity is picked up by the clicker's hustle system
+                    self.standing_desks += 1;
+                }
+                UpgradeType::SolarPanels => {
+                    // Bill savings are picked up by the utilities system
+                    self.solar_panels += 1;
+                    game_state.reputation = (game_state.reputation + SOLAR_REPUTATION_BONUS).min(5.0);
+                }
             }
             true
         } else {
@@ -237,3 +404,148 @@ impl UpgradeState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use bevy::state::app::StatesPlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use rand::{RngExt, SeedableRng};
+    use std::time::Duration;
+    use crate::currency::Money;
+    use crate::game_clock::GameClockPlugin;
+    use crate::thing_type::ThingType;
+
+    const TRIALS: usize = 2_000;
+
+    fn with_count(upgrade: UpgradeType, count: u32) -> UpgradeState {
+        let mut upgrades = UpgradeState::default();
+        let field = match upgrade {
+            UpgradeType::BetterTools => &mut upgrades.better_tools,
+            UpgradeType::HireWorker => &mut upgrades.workers,
+            UpgradeType::Automation => &mut upgrades.automation,
+            UpgradeType::SocialMedia => &mut upgrades.social_media,
+            UpgradeType::Billboard => &mut upgrades.billboards,
+            UpgradeType::InfluencerDeal => &mut upgrades.influencer_deals,
+            UpgradeType::StandingDesk => &mut upgrades.standing_desks,
+            UpgradeType::SolarPanels => &mut upgrades.solar_panels,
+        };
+        *field = count;
+        upgrades
+    }
+
+    #[test]
+    fn every_purchase_costs_more_than_the_last() {
+        for upgrade in UpgradeType::ALL {
+            let mut previous = with_count(upgrade, 0).cost(upgrade);
+            assert_eq!(previous, upgrade.base_cost());
+            for count in 1..=500 {
+                let cost = with_count(upgrade, count).cost(upgrade);
+                assert!(cost > previous, "{:?} #{} costs {} after {}", upgrade, count, cost, previous);
+                previous = cost;
+            }
+        }
+    }
+
+    #[test]
+    fn cost_never_falls_as_the_count_grows() {
+        let mut rng = StdRng::seed_from_u64(8);
+        for _ in 0..TRIALS {
+            let upgrade = UpgradeType::ALL[rng.random_range(0..UpgradeType::ALL.len())];
+            let fewer = rng.random_range(0..=u32::MAX);
+            let more = rng.random_range(fewer..=u32::MAX);
+            let (cheaper, dearer) = (with_count(upgrade, fewer).cost(upgrade), with_count(upgrade, more).cost(upgrade));
+            assert!(cheaper <= dearer, "{:?}: {} at {} but {} at {}", upgrade, cheaper, fewer, dearer, more);
+            assert!(!dearer.is_nan());
+        }
+    }
+
+    #[test]
+    fn buying_charges_exactly_the_price() {
+        let mut rng = StdRng::seed_from_u64(9);
+        for _ in 0..TRIALS {
+            let upgrade = UpgradeType::ALL[rng.random_range(0..UpgradeType::ALL.len())];
+            let mut upgrades = with_count(upgrade, rng.random_range(0..60));
+            let price = Money::from_dollars(upgrades.cost(upgrade));
+            let count = upgrades.get_count(upgrade);
+
+            // A cent short buys nothing
+            let mut game_state = GameState {
+                money: price - Money::from_cents(1),
+                ..Default::default()
+            };
+            assert!(!upgrades.purchase(upgrade, &mut game_state));
+            assert_eq!(game_state.money, price - Money::from_cents(1));
+            assert_eq!(upgrades.get_count(upgrade), count);
+
+            let spare = Money::from_cents(rng.random_range(0..1_000_000));
+            game_state.money = price + spare;
+            assert!(upgrades.purchase(upgrade, &mut game_state));
+            assert_eq!(game_state.money, spare);
+            assert_eq!(upgrades.get_count(upgrade), count + 1);
+        }
+    }
+
+    #[derive(Resource, Default)]
+    struct Announced(Vec<f32>);
+
+    fn record(mut rep_events: MessageReader<ReputationChangedEvent>, mut announced: ResMut<Announced>) {
+        announced.0.extend(rep_events.read().map(|event| event.new_reputation));
+    }
+
+    fn produce(mut thing_events: MessageWriter<ThingProducedEvent>) {
+        thing_events.write(ThingProducedEvent {
+            amount: 3,
+            from_click: false,
+        });
+    }
+
+    #[test]
+    fn sales_and_decay_share_one_announced_reputation() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+            .insert_state(AppState::Playing)
+            .insert_resource(GameState {
+                thing_type: Some(ThingType::BAD),
+                ..Default::default()
+            })
+            .init_resource::<ThingRegistry>()
+            .init_resource::<WorldState>()
+            .init_resource::<MarketingState>()
+            .init_resource::<RunModifiers>()
+            .init_resource::<DifficultyConfig>()
+            .init_resource::<EcoState>()
+            .init_resource::<WordOfMouth>()
+            .init_resource::<Venues>()
+            .init_resource::<BundleState>()
+            .init_resource::<PandemicState>()
+            .init_resource::<AiState>()
+            .init_resource::<PriceWarState>()
+            .init_resource::<CounterfeitState>()
+            .init_resource::<LimitedEditions>()
+            .init_resource::<FadState>()
+            .init_resource::<Announced>()
+            .add_message::<ThingProducedEvent>()
+            .add_message::<MoneyChangedEvent>()
+            .add_message::<ReputationChangedEvent>()
+            .add_plugins((GameClockPlugin, BusinessPlugin))
+            .add_systems(PreUpdate, produce)
+            .add_systems(PostUpdate, record);
+
+        let start = app.world().resource::<GameState>().reputation;
+        for _ in 0..600 {
+            app.update();
+        }
+
+        // Bad Things lose reputation to both sales and decay, announced a step at a time
+        let reputation = app.world().resource::<GameState>().reputation;
+        let announced = &app.world().resource::<Announced>().0;
+        assert!(reputation < start - 10.0 * REPUTATION_EVENT_STEP);
+        assert!(announced.len() >= 10);
+        for pair in announced.windows(2) {
+            assert!((pair[0] - pair[1]).abs() >= REPUTATION_EVENT_STEP, "{:?}", pair);
+        }
+    }
+}
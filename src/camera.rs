@@ -0,0 +1,162 @@
+//! Camera effects - the backdrop reacting to big moments
+//!
+//! The 2D camera sits still behind the UI unless something happens: a
+//! milestone or a climb into a better reputation tier punches the zoom in and
+//! lets it settle, and a power cut, a strike, a pandemic or a fall in
+//! reputation shakes it. In between it drifts, barely, so the scenery never
+//! looks like a screenshot. Effects run on real time (a paused game can still
+//! finish a punch). Reduced motion and low power keep the camera still.
+
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use crate::game_state::{AppState, InGame, MilestoneEvent};
+use crate::labor::{StrikeEvent, StrikeStage};
+use crate::pandemic::{PandemicEvent, PandemicStage};
+use crate::power::PowerMode;
+use crate::reputation::ReputationTierChangedEvent;
+use crate::settings::Settings;
+use crate::utilities::PowerOutageEvent;
+
+/// Seconds a zoom punch takes to settle
+const PUNCH_SECS: f32 = 0.5;
+/// How far a punch zooms in at its peak
+const PUNCH_ZOOM: f32 = 0.06;
+/// Shake added by a shock, out of 1
+const SHOCK_TRAUMA: f32 = 0.6;
+/// Trauma shaken off per second
+const TRAUMA_DECAY: f32 = 1.2;
+/// Furthest a full shake moves the camera, in pixels
+const SHAKE_PX: f32 = 10.0;
+/// How far and how slowly the camera drifts
+const DRIFT_PX: f32 = 4.0;
+const DRIFT_SPEED: f32 = 0.15;
+
+/// Effects running on the camera
+#[derive(Resource, Default)]
+pub struct CameraFx {
+    /// Seconds left on the zoom punch
+    punch: f32,
+    /// How hard the camera is shaking, 0 to 1 (felt squared, so small shocks stay subtle)
+    trauma: f32,
+    /// Seconds of drift so far
+    drift: f32,
+}
+
+impl CameraFx {
+    pub fn punch(&mut self) {
+        self.punch = PUNCH_SECS;
+    }
+
+    pub fn shake(&mut self, trauma: f32) {
+        self.trauma = (self.trauma + trauma).min(1.0);
+    }
+}
+
+pub struct CameraFxPlugin;
+
+impl Plugin for CameraFxPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraFx>()
+            .add_systems(
+                Update,
+                (trigger_camera_fx, apply_camera_fx).chain().run_if(in_state(AppState::Playing)),
+            )
+            .add_systems(OnExit(InGame), reset_camera_fx);
+    }
+}
+
+/// Messages the camera reacts to
+#[derive(SystemParam)]
+struct CameraTriggers<'w, 's> {
+    milestones: MessageReader<'w, 's, MilestoneEvent>,
+    tiers: MessageReader<'w, 's, ReputationTierChangedEvent>,
+    outages: MessageReader<'w, 's, PowerOutageEvent>,
+    strikes: MessageReader<'w, 's, StrikeEvent>,
+    pandemic: MessageReader<'w, 's, PandemicEvent>,
+}
+
+/// Punch in on good news, shake on shocks
+fn trigger_camera_fx(mut triggers: CameraTriggers, mut fx: ResMut<CameraFx>) {
+    if triggers.milestones.read().count() > 0 {
+        fx.punch();
+    }
+    for event in triggers.tiers.read() {
+        if event.new_tier > event.old_tier {
+            fx.punch();
+        } else {
+            fx.shake(SHOCK_TRAUMA);
+        }
+    }
+    for event in triggers.outages.read() {
+        if event.cause.is_some() {
+            fx.shake(SHOCK_TRAUMA);
+        }
+    }
+    for event in triggers.strikes.read() {
+        if matches!(event.stage, StrikeStage::Started) {
+            fx.shake(SHOCK_TRAUMA);
+        }
+    }
+    for event in triggers.pandemic.read() {
+        if matches!(event.stage, PandemicStage::Began(_)) {
+            fx.shake(SHOCK_TRAUMA);
+        }
+    }
+}
+
+/// Move the camera for the running effects (or hold it still)
+fn apply_camera_fx(
+    time: Res<Time<Real>>,
+    settings: Res<Settings>,
+    power: Res<PowerMode>,
+    mut fx: ResMut<CameraFx>,
+    mut camera_query: Query<(&mut Transform, &mut Projection), With<Camera2d>>,
+) {
+    if settings.reduced_motion || power.low_power {
+        // Only write when something's running, so a still camera stays unchanged
+        if fx.punch > 0.0 || fx.trauma > 0.0 || fx.drift > 0.0 {
+            *fx = CameraFx::default();
+            for (mut transform, mut projection) in &mut camera_query {
+                still(&mut transform, &mut projection);
+            }
+        }
+        return;
+    }
+
+    let step = time.delta_secs();
+    fx.punch = (fx.punch - step).max(0.0);
+    fx.trauma = (fx.trauma - TRAUMA_DECAY * step).max(0.0);
+    fx.drift += step;
+
+    // Snap in, then ease back out
+    let t = fx.punch / PUNCH_SECS;
+    let zoom = 1.0 - PUNCH_ZOOM * t * t;
+    let shake = fx.trauma * fx.trauma * SHAKE_PX;
+    let drift = fx.drift * DRIFT_SPEED * std::f32::consts::TAU;
+    let offset = Vec2::new(drift.sin(), (drift * 0.7).cos()) * DRIFT_PX
+        + Vec2::new(rand::random::<f32>() - 0.5, rand::random::<f32>() - 0.5) * 2.0 * shake;
+
+    for (mut transform, mut projection) in &mut camera_query {
+        transform.translation.x = offset.x;
+        transform.translation.y = offset.y;
+        if let Projection::Orthographic(orthographic) = &mut *projection {
+            orthographic.scale = zoom;
+        }
+    }
+}
+
+fn still(transform: &mut Transform, projection: &mut Projection) {
+    transform.translation.x = 0.0;
+    transform.translation.y = 0.0;
+    if let Projection::Orthographic(orthographic) = projection {
+        orthographic.scale = 1.0;
+    }
+}
+
+/// Leave the other screens with a still camera
+fn reset_camera_fx(mut fx: ResMut<CameraFx>, mut camera_query: Query<(&mut Transform, &mut Projection), With<Camera2d>>) {
+    *fx = CameraFx::default();
+    for (mut transform, mut projection) in &mut camera_query {
+        still(&mut transform, &mut projection);
+    }
+}
@@ -2,38 +2,151 @@
 
 use bevy::prelude::*;
 use bevy::ecs::schedule::IntoScheduleConfigs;
-use crate::game_state::{AppState, GameState, ThingProducedEvent};
+use bevy::ecs::system::SystemParam;
+use crate::accounting::ExpenseCategory;
+use crate::business::UpgradeState;
+use crate::fortune::RunModifiers;
+use crate::game_clock::GameClock;
+use crate::game_state::{game_running, AppState, GameState, ThingProducedEvent};
+use crate::org::Organization;
+use crate::settings::Settings;
+use crate::thing_type::ThingRegistry;
+use crate::wellbeing::Wellbeing;
 
 pub struct ClickerPlugin;
 
 impl Plugin for ClickerPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<AutoProductionAccumulator>()
-            .add_systems(Update, auto_produce.run_if(in_state(AppState::Playing)));
+            .init_resource::<HustleMeter>()
+            .add_systems(
+                Update,
+                (auto_produce, regenerate_hustle, handle_click)
+                    .run_if(in_state(AppState::Playing).and(game_running)),
+            );
     }
 }
 
 /// Accumulator for fractional production
-#[derive(Resource, Default)]
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
 pub struct AutoProductionAccumulator {
     pub accumulated: f64,
 }
 
+// === HUSTLE BALANCE ===
+/// Hustle capacity before any upgrades
+pub const HUSTLE_BASE_MAX: f32 = 100.0;
+/// Extra capacity per Standing Desk
+pub const HUSTLE_PER_DESK: f32 = 25.0;
+/// Hustle spent on each manual click
+pub const HUSTLE_PER_CLICK: f32 = 5.0;
+/// Hustle recovered per second of not clicking (or clicking slowly)
+pub const HUSTLE_REGEN_PER_SEC: f32 = 4.0;
+/// Price of a coffee
+pub const COFFEE_COST: f64 = 5.0;
+/// Hustle restored by a coffee
+pub const COFFEE_HUSTLE: f32 = 50.0;
+
+/// The "Hustle" meter - manual clicking burns it, idling refills it
+#[derive(Resource, Reflect)]
+#[reflect(Resource, Default)]
+pub struct HustleMeter {
+    pub current: f32,
+    pub max: f32,
+    /// Hustle per click (tweakable by events and Thing types)
+    pub cost_per_click: f32,
+    /// Hustle regenerated per second
+    pub regen_per_sec: f32,
+}
+
+impl Default for HustleMeter {
+    fn default() -> Self {
+        Self {
+            current: HUSTLE_BASE_MAX,
+            max: HUSTLE_BASE_MAX,
+            cost_per_click: HUSTLE_PER_CLICK,
+            regen_per_sec: HUSTLE_REGEN_PER_SEC,
+        }
+    }
+}
+
+impl HustleMeter {
+    /// Pay for a manual click. Always succeeds when the meter is switched off.
+    pub fn try_click(&mut self, enabled: bool) -> bool {
+        if !enabled {
+            return true;
+        }
+        if self.current < self.cost_per_click {
+            return false;
+        }
+        self.current -= self.cost_per_click;
+        true
+    }
+
+    /// Buy a coffee. Returns false if it can't be afforded or wouldn't help.
+    pub fn drink_coffee(&mut self, game_state: &mut GameState) -> bool {
+        if game_state.money < COFFEE_COST || self.current >= self.max {
+            return false;
+        }
+        game_state.spend(ExpenseCategory::Coffee, COFFEE_COST);
+        self.current = (self.current + COFFEE_HUSTLE).min(self.max);
+        true
+    }
+
+    /// Fraction of the meter that's full (0-1)
+    pub fn fraction(&self) -> f32 {
+        if self.max > 0.0 { self.current / self.max } else { 0.0 }
+    }
+}
+
+/// Refill the hustle meter over time and keep its capacity in line with upgrades
+fn regenerate_hustle(
+    clock: Res<GameClock>,
+    settings: Res<Settings>,
+    upgrades: Res<UpgradeState>,
+    mut hustle: ResMut<HustleMeter>,
+) {
+    if !settings.hustle_meter {
+        return;
+    }
+
+    let max = HUSTLE_BASE_MAX + upgrades.standing_desks as f32 * HUSTLE_PER_DESK;
+    if hustle.max != max {
+        hustle.max = max;
+    }
+
+    if hustle.current < hustle.max {
+        let regen = hustle.regen_per_sec * clock.delta_secs();
+        hustle.current = (hustle.current + regen).min(hustle.max);
+    }
+}
+
+/// What sets the pace of auto-production
+#[derive(SystemParam)]
+struct ProductionRate<'w> {
+    things: Res<'w, ThingRegistry>,
+    modifiers: Res<'w, RunModifiers>,
+    org: Res<'w, Organization>,
+    upgrades: Res<'w, UpgradeState>,
+}
+
 /// Auto-produce Things over time
 fn auto_produce(
-    time: Res<Time>,
+    clock: Res<GameClock>,
+    rate: ProductionRate,
     mut accumulator: ResMut<AutoProductionAccumulator>,
     mut thing_events: MessageWriter<ThingProducedEvent>,
     mut game_state: ResMut<GameState>,
 ) {
-    if game_state.things_per_second > 0.0 {
+    if game_state.things_per_second > 0.0 && game_state.can_produce() {
         // Apply production multiplier from Thing type
         let multiplier = game_state
             .thing_type
-            .map(|t| t.production_multiplier())
-            .unwrap_or(1.0);
+            .map_or(1.0, |t| rate.things[t].production_multiplier);
 
-        let production = game_state.things_per_second * multiplier * time.delta_secs() as f64;
+        let per_second = rate.org.effective_rate(game_state.things_per_second, rate.upgrades.workers);
+        let production = per_second * multiplier * rate.modifiers.production * clock.delta_secs() as f64;
         accumulator.accumulated += production;
 
         // Convert accumulated to whole Things
@@ -54,21 +167,31 @@ fn auto_produce(
 #[derive(Event, Message, Clone)]
 pub struct ClickEvent;
 
-/// System to handle manual clicks
-pub fn handle_click(
+/// System to handle manual clicks (the Make Thing hotkey)
+fn handle_click(
     mut click_events: MessageReader<ClickEvent>,
     mut game_state: ResMut<GameState>,
+    things: Res<ThingRegistry>,
+    mut hustle: ResMut<HustleMeter>,
+    mut wellbeing: ResMut<Wellbeing>,
+    settings: Res<Settings>,
     mut thing_events: MessageWriter<ThingProducedEvent>,
 ) {
     for _ in click_events.read() {
+        if !game_state.can_produce()
+            || !hustle.try_click(settings.hustle_meter)
+            || !wellbeing.try_click(settings.wellbeing)
+        {
+            continue;
+        }
         if let Some(thing_type) = game_state.thing_type {
-            let multiplier = thing_type.production_multiplier();
-            let things = (game_state.click_power as f64 * multiplier).ceil() as u64;
+            let multiplier = things[thing_type].production_multiplier;
+            let made = (game_state.click_power as f64 * multiplier).ceil() as u64;
 
-            game_state.things_produced += things;
+            game_state.things_produced += made;
 
             thing_events.write(ThingProducedEvent {
-                amount: things,
+                amount: made,
                 from_click: true,
             });
         }
@@ -0,0 +1,165 @@
+//! Real-world clock awareness - Terry notices when *you* should be asleep
+//!
+//! The player's local time is sampled once a minute. Playing past midnight,
+//! on a real holiday, or for a marathon session each fire a one-off moment
+//! Terry can comment on.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use std::time::Duration;
+use crate::economy::{GameDate, Holiday};
+use crate::game_state::{AppState, InGame};
+
+/// How often the wall clock is checked
+const SAMPLE_INTERVAL_SECS: f32 = 60.0;
+/// Local hours (from midnight) that count as "way too late"
+const LATE_NIGHT_HOURS: std::ops::Range<u8> = 0..5;
+/// Play time that counts as a marathon session
+const MARATHON_SESSION: Duration = Duration::from_secs(4 * 60 * 60);
+
+/// A moment in local time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalTime {
+    pub date: GameDate,
+    pub hour: u8,
+    pub minute: u8,
+}
+
+impl LocalTime {
+    /// The current local time, or UTC where the local zone can't be read
+    pub fn now() -> Self {
+        let secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        Self::from_unix(secs + utc_offset_secs(secs))
+    }
+
+    /// Break seconds since 1970 (already shifted into local time) into a date and time
+    fn from_unix(secs: i64) -> Self {
+        let days = secs.div_euclid(86_400);
+        let of_day = secs.rem_euclid(86_400);
+
+        // Civil-from-days (Howard Hinnant's algorithm)
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+        let year = (yoe + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+
+        Self {
+            date: GameDate::new(year, month, day),
+            hour: (of_day / 3_600) as u8,
+            minute: (of_day % 3_600 / 60) as u8,
+        }
+    }
+}
+
+/// Seconds the local time zone is ahead of UTC at the given instant
+#[cfg(unix)]
+fn utc_offset_secs(unix_secs: i64) -> i64 {
+    let t = unix_secs as libc::time_t;
+    // SAFETY: localtime_r only writes to the `tm` we hand it
+    unsafe {
+        let mut tm: libc::tm = std::mem::zeroed();
+        if libc::localtime_r(&t, &mut tm).is_null() {
+            return 0;
+        }
+        tm.tm_gmtoff as i64
+    }
+}
+
+#[cfg(not(unix))]
+fn utc_offset_secs(_unix_secs: i64) -> i64 {
+    0
+}
+
+/// Something worth remarking on about when the player is playing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockMoment {
+    LateNight,
+    Holiday(Holiday),
+    Marathon,
+}
+
+/// Message fired when a real-world moment comes up
+#[derive(Event, Message, Clone)]
+pub struct ClockMomentEvent {
+    pub moment: ClockMoment,
+}
+
+/// The last local time sampled, and which moments have already been noticed
+#[derive(Resource)]
+pub struct LocalClock {
+    pub now: LocalTime,
+    timer: Timer,
+    /// The local date of the last late-night remark (once a night)
+    late_night_noted: Option<GameDate>,
+    /// The local date of the last holiday remark (once a day)
+    holiday_noted: Option<GameDate>,
+    marathon_noted: bool,
+}
+
+impl Default for LocalClock {
+    fn default() -> Self {
+        Self {
+            now: LocalTime::now(),
+            timer: Timer::from_seconds(SAMPLE_INTERVAL_SECS, TimerMode::Repeating),
+            late_night_noted: None,
+            holiday_noted: None,
+            marathon_noted: false,
+        }
+    }
+}
+
+pub struct ClockPlugin;
+
+impl Plugin for ClockPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LocalClock>()
+            .add_message::<ClockMomentEvent>()
+            .add_systems(OnEnter(InGame), sample_on_start)
+            .add_systems(Update, sample_local_clock.run_if(in_state(AppState::Playing)));
+    }
+}
+
+/// Check straight away instead of a minute in, so a late-night start gets noticed
+fn sample_on_start(mut clock: ResMut<LocalClock>) {
+    let duration = clock.timer.duration();
+    clock.timer.set_elapsed(duration);
+}
+
+/// Sample the wall clock once a minute and announce anything new
+fn sample_local_clock(
+    time: Res<Time<Real>>,
+    mut clock: ResMut<LocalClock>,
+    mut moment_events: MessageWriter<ClockMomentEvent>,
+) {
+    if !clock.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let now = LocalTime::now();
+    clock.now = now;
+
+    if LATE_NIGHT_HOURS.contains(&now.hour) && clock.late_night_noted != Some(now.date) {
+        clock.late_night_noted = Some(now.date);
+        moment_events.write(ClockMomentEvent { moment: ClockMoment::LateNight });
+    }
+
+    if let Some(holiday) = now.date.holiday() {
+        if clock.holiday_noted != Some(now.date) {
+            clock.holiday_noted = Some(now.date);
+            moment_events.write(ClockMomentEvent { moment: ClockMoment::Holiday(holiday) });
+        }
+    }
+
+    if !clock.marathon_noted && time.elapsed() >= MARATHON_SESSION {
+        clock.marathon_noted = true;
+        moment_events.write(ClockMomentEvent { moment: ClockMoment::Marathon });
+    }
+}
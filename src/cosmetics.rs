@@ -0,0 +1,210 @@
+//! Terry's wardrobe - accessories earned through achievements
+//!
+//! Accessories are unlocked by hitting milestones and stay unlocked across runs
+//! (they're saved with the player's profile). Each one sits in a slot on Terry's
+//! portrait. On holidays Terry puts on a seasonal hat over whatever is equipped.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use serde::{Deserialize, Serialize};
+use crate::attract::attract_running;
+use crate::economy::Holiday;
+use crate::game_state::AppState;
+use crate::reputation::{ReputationTier, ReputationTierChangedEvent};
+
+/// Where on Terry an accessory goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slot {
+    Hat,
+    Face,
+    Hand,
+}
+
+impl Slot {
+    pub const ALL: [Slot; 3] = [Slot::Hat, Slot::Face, Slot::Hand];
+}
+
+/// Something Terry can wear
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Cosmetic {
+    TinyBriefcase,
+    Monocle,
+    TopHat,
+    SantaHat,
+    PumpkinHat,
+    PartyHat,
+    StarSpangledHat,
+    PriceGun,
+    BlueRibbon,
+    Crown,
+    DarkGlasses,
+}
+
+impl Cosmetic {
+    /// Accessories earned through achievements (holiday hats aren't earned)
+    pub const EARNABLE: [Cosmetic; 7] = [
+        Cosmetic::TinyBriefcase,
+        Cosmetic::Monocle,
+        Cosmetic::TopHat,
+        Cosmetic::PriceGun,
+        Cosmetic::BlueRibbon,
+        Cosmetic::Crown,
+        Cosmetic::DarkGlasses,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Cosmetic::TinyBriefcase => "Tiny Briefcase",
+            Cosmetic::Monocle => "Monocle",
+            Cosmetic::TopHat => "Top Hat",
+            Cosmetic::SantaHat => "Santa Hat",
+            Cosmetic::PumpkinHat => "Pumpkin Hat",
+            Cosmetic::PartyHat => "Party Hat",
+            Cosmetic::StarSpangledHat => "Star-Spangled Hat",
+            Cosmetic::PriceGun => "Price Gun",
+            Cosmetic::BlueRibbon => "Blue Ribbon",
+            Cosmetic::Crown => "Crown",
+            Cosmetic::DarkGlasses => "Dark Glasses",
+        }
+    }
+
+    /// What it looks like on the portrait
+    pub fn glyph(&self) -> &'static str {
+        match self {
+            Cosmetic::TinyBriefcase => "💼",
+            Cosmetic::Monocle => "🧐",
+            Cosmetic::TopHat => "🎩",
+            Cosmetic::SantaHat => "🎅",
+            Cosmetic::PumpkinHat => "🎃",
+            Cosmetic::PartyHat => "🎉",
+            Cosmetic::StarSpangledHat => "🇺🇸",
+            Cosmetic::PriceGun => "🏷",
+            Cosmetic::BlueRibbon => "🏅",
+            Cosmetic::Crown => "👑",
+            Cosmetic::DarkGlasses => "🕶",
+        }
+    }
+
+    pub fn slot(&self) -> Slot {
+        match self {
+            Cosmetic::TinyBriefcase | Cosmetic::PriceGun | Cosmetic::BlueRibbon => Slot::Hand,
+            Cosmetic::Monocle | Cosmetic::DarkGlasses => Slot::Face,
+            Cosmetic::TopHat
+            | Cosmetic::Crown
+            | Cosmetic::SantaHat
+            | Cosmetic::PumpkinHat
+            | Cosmetic::PartyHat
+            | Cosmetic::StarSpangledHat => Slot::Hat,
+        }
+    }
+
+    /// How to earn it
+    pub fn unlock_hint(&self) -> &'static str {
+        match self {
+            Cosmetic::TinyBriefcase => "Have $10,000 in the bank",
+            Cosmetic::Monocle => "Become Beloved",
+            Cosmetic::TopHat => "Make 100,000 Things",
+            Cosmetic::PriceGun => "Finish the Cheap Thing story",
+            Cosmetic::BlueRibbon => "Finish the Good Thing story",
+            Cosmetic::Crown => "Finish the Expensive Thing story",
+            Cosmetic::DarkGlasses => "Finish the Bad Thing story",
+            Cosmetic::SantaHat | Cosmetic::PumpkinHat | Cosmetic::PartyHat | Cosmetic::StarSpangledHat => {
+                "Worn automatically on the right holiday"
+            }
+        }
+    }
+
+    /// The hat Terry wears for a holiday, if there is one
+    pub fn for_holiday(holiday: Holiday) -> Option<Cosmetic> {
+        match holiday {
+            Holiday::Christmas => Some(Cosmetic::SantaHat),
+            Holiday::Halloween => Some(Cosmetic::PumpkinHat),
+            Holiday::NewYears | Holiday::NewYearsEve => Some(Cosmetic::PartyHat),
+            Holiday::IndependenceDay => Some(Cosmetic::StarSpangledHat),
+            _ => None,
+        }
+    }
+}
+
+/// What Terry has unlocked and has on
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Wardrobe {
+    pub unlocked: Vec<Cosmetic>,
+    pub equipped: Vec<Cosmetic>,
+}
+
+impl Wardrobe {
+    pub fn is_unlocked(&self, cosmetic: Cosmetic) -> bool {
+        self.unlocked.contains(&cosmetic)
+    }
+
+    pub fn is_equipped(&self, cosmetic: Cosmetic) -> bool {
+        self.equipped.contains(&cosmetic)
+    }
+
+    /// Unlock an accessory. Returns false if it was already unlocked.
+    pub fn unlock(&mut self, cosmetic: Cosmetic) -> bool {
+        if self.is_unlocked(cosmetic) {
+            return false;
+        }
+        self.unlocked.push(cosmetic);
+        true
+    }
+
+    /// Put an unlocked accessory on (taking off whatever was in its slot), or take it off
+    pub fn toggle(&mut self, cosmetic: Cosmetic) {
+        if self.is_equipped(cosmetic) {
+            self.equipped.retain(|c| *c != cosmetic);
+        } else if self.is_unlocked(cosmetic) {
+            self.equipped.retain(|c| c.slot() != cosmetic.slot());
+            self.equipped.push(cosmetic);
+        }
+    }
+
+    /// What's in a slot today; a holiday hat beats anything equipped
+    pub fn worn(&self, slot: Slot, holiday: Option<Holiday>) -> Option<Cosmetic> {
+        let holiday_hat = holiday.and_then(Cosmetic::for_holiday).filter(|_| slot == Slot::Hat);
+        holiday_hat.or_else(|| self.equipped.iter().copied().find(|c| c.slot() == slot))
+    }
+}
+
+/// Message fired when an accessory is earned
+#[derive(Event, Message, Clone)]
+pub struct CosmeticUnlockedEvent {
+    pub cosmetic: Cosmetic,
+}
+
+pub struct CosmeticsPlugin;
+
+impl Plugin for CosmeticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Wardrobe>()
+            .add_message::<CosmeticUnlockedEvent>()
+            .add_systems(
+                Update,
+                unlock_cosmetics
+                    .run_if(in_state(AppState::Playing))
+                    .run_if(not(attract_running)),
+            );
+    }
+}
+
+/// Hand out accessories for reputation (milestone accessories are paid out by `rewards`)
+fn unlock_cosmetics(
+    mut tier_events: MessageReader<ReputationTierChangedEvent>,
+    mut wardrobe: ResMut<Wardrobe>,
+    mut unlock_events: MessageWriter<CosmeticUnlockedEvent>,
+) {
+    let earned: Vec<Cosmetic> = tier_events
+        .read()
+        .filter(|event| event.new_tier == ReputationTier::Beloved)
+        .map(|_| Cosmetic::Monocle)
+        .collect();
+    for cosmetic in earned {
+        if wardrobe.unlock(cosmetic) {
+            unlock_events.write(CosmeticUnlockedEvent { cosmetic });
+        }
+    }
+}
@@ -0,0 +1,280 @@
+//! Counterfeits - once enough people know the Thing, someone starts faking it
+//!
+//! Above a level of brand recognition, knock-offs creep into the market and
+//! take a share of would-be customers. Now and then someone buys a fake, it
+//! falls apart, and they blame the real Thing. The player can't see how bad it
+//! is without a test buy; a legal campaign knocks the fakes back, and
+//! registering the Thing with customs lets shipments get seized at the border.
+//! A seizure is only made final if the player sends customs an authentication
+//! report in time - otherwise the goods are released and carry on.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::accounting::ExpenseCategory;
+use crate::economy::{DayAdvancedEvent, GameDate, WorldState};
+use crate::game_state::{AppState, GameState, ReputationChangedEvent};
+use crate::word_of_mouth::WordOfMouth;
+
+/// Brand recognition (share of the market that's heard of the Thing) where fakes start appearing
+const RECOGNITION_THRESHOLD: f64 = 0.4;
+/// Daily growth in prevalence per point of recognition over the threshold
+const SPREAD_RATE: f32 = 0.02;
+/// Most of the market the fakes can take
+const MAX_PREVALENCE: f32 = 0.4;
+/// Daily chance, at full prevalence, that a burned customer makes a fuss
+const BURN_CHANCE: f32 = 0.1;
+/// Reputation lost when a customer gets burned by a fake
+const BURN_REPUTATION: f32 = 0.05;
+
+/// Cost of buying a few "Things" off market stalls to see how many are fake
+pub const TEST_BUY_COST: f64 = 300.0;
+/// Cost of a round of cease-and-desist letters and takedown notices
+pub const LEGAL_ACTION_COST: f64 = 2_000.0;
+/// Days the lawyers take
+const LEGAL_ACTION_DAYS: u32 = 14;
+/// Share of the fakes a legal campaign takes off the market
+const LEGAL_ACTION_CUT: f32 = 0.5;
+/// Cost of registering the Thing with customs (once per run)
+pub const CUSTOMS_REGISTRATION_COST: f64 = 1_500.0;
+/// Daily chance, at full prevalence, that customs stops a shipment of fakes
+const SEIZURE_CHANCE: f32 = 0.15;
+/// Cost of the authentication report customs asks for
+pub const AUTHENTICATION_COST: f64 = 500.0;
+/// Days customs holds a shipment waiting for the report
+const SEIZURE_HOLD_DAYS: u32 = 7;
+/// Share of the fakes a forfeited shipment takes off the market
+const SEIZURE_CUT: f32 = 0.25;
+/// Prevalence added when a held shipment is released
+const RELEASE_BOOST: f32 = 0.03;
+/// Reputation gained from a bust making the news
+const BUST_REPUTATION: f32 = 0.1;
+
+/// The fakes, and what the player is doing about them
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct CounterfeitState {
+    /// Share of would-be customers buying fakes instead
+    pub prevalence: f32,
+    /// What the last test buy found, and when
+    pub last_test_buy: Option<(GameDate, f32)>,
+    /// Days left on a legal campaign
+    pub legal_action_days: Option<u32>,
+    pub customs_registered: bool,
+    /// Days left to authenticate a seized shipment
+    pub seizure_hold_days: Option<u32>,
+}
+
+impl CounterfeitState {
+    /// Multiplier on would-be customers (the rest buy fakes)
+    pub fn customer_factor(&self) -> f64 {
+        1.0 - self.prevalence as f64
+    }
+
+    /// Whether the fakes have shown up yet
+    pub fn appeared(&self) -> bool {
+        self.prevalence > 0.0
+    }
+
+    pub fn can_take(&self, action: CounterfeitAction, game_state: &GameState) -> bool {
+        let available = match action {
+            CounterfeitAction::TestBuy => self.appeared(),
+            CounterfeitAction::LegalAction => self.appeared() && self.legal_action_days.is_none(),
+            CounterfeitAction::RegisterWithCustoms => self.appeared() && !self.customs_registered,
+            CounterfeitAction::Authenticate => self.seizure_hold_days.is_some(),
+        };
+        available && game_state.money >= action.cost()
+    }
+}
+
+/// What the player can do about the fakes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CounterfeitAction {
+    TestBuy,
+    LegalAction,
+    RegisterWithCustoms,
+    Authenticate,
+}
+
+impl CounterfeitAction {
+    pub const ALL: [CounterfeitAction; 4] = [
+        CounterfeitAction::TestBuy,
+        CounterfeitAction::LegalAction,
+        CounterfeitAction::RegisterWithCustoms,
+        CounterfeitAction::Authenticate,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CounterfeitAction::TestBuy => "Test buy",
+            CounterfeitAction::LegalAction => "Legal action",
+            CounterfeitAction::RegisterWithCustoms => "Register with customs",
+            CounterfeitAction::Authenticate => "Authenticate seizure",
+        }
+    }
+
+    pub fn cost(&self) -> f64 {
+        match self {
+            CounterfeitAction::TestBuy => TEST_BUY_COST,
+            CounterfeitAction::LegalAction => LEGAL_ACTION_COST,
+            CounterfeitAction::RegisterWithCustoms => CUSTOMS_REGISTRATION_COST,
+            CounterfeitAction::Authenticate => AUTHENTICATION_COST,
+        }
+    }
+}
+
+/// What happened with the fakes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CounterfeitStage {
+    /// Fakes have appeared on the market
+    Appeared,
+    /// A customer bought a fake and blamed the real Thing
+    CustomerBurned,
+    /// A test buy found this share of the market was fake
+    TestBuy(f32),
+    LegalActionStarted,
+    LegalActionWon,
+    CustomsRegistered,
+    /// Customs stopped a shipment and wants it authenticated
+    Seized,
+    /// The shipment was authenticated and destroyed
+    Forfeited,
+    /// Nobody authenticated it in time, so it went on its way
+    Released,
+}
+
+/// Message fired as the counterfeit story unfolds
+#[derive(Event, Message, Clone)]
+pub struct CounterfeitEvent {
+    pub stage: CounterfeitStage,
+}
+
+/// Message taking an action against the fakes (sent by the UI)
+#[derive(Event, Message, Clone)]
+pub struct CounterfeitActionEvent {
+    pub action: CounterfeitAction,
+}
+
+pub struct CounterfeitPlugin;
+
+impl Plugin for CounterfeitPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CounterfeitState>()
+            .add_message::<CounterfeitEvent>()
+            .add_message::<CounterfeitActionEvent>()
+            .add_systems(
+                Update,
+                (take_counterfeit_actions, daily_counterfeits)
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// Pay for an action and start it
+fn take_counterfeit_actions(
+    mut action_events: MessageReader<CounterfeitActionEvent>,
+    world: Res<WorldState>,
+    mut game_state: ResMut<GameState>,
+    mut counterfeits: ResMut<CounterfeitState>,
+    mut counterfeit_events: MessageWriter<CounterfeitEvent>,
+    mut rep_events: MessageWriter<ReputationChangedEvent>,
+) {
+    for event in action_events.read() {
+        if !counterfeits.can_take(event.action, &game_state) {
+            continue;
+        }
+        game_state.spend(ExpenseCategory::Legal, event.action.cost());
+
+        let stage = match event.action {
+            CounterfeitAction::TestBuy => {
+                counterfeits.last_test_buy = Some((world.date, counterfeits.prevalence));
+                CounterfeitStage::TestBuy(counterfeits.prevalence)
+            }
+            CounterfeitAction::LegalAction => {
+                counterfeits.legal_action_days = Some(LEGAL_ACTION_DAYS);
+                CounterfeitStage::LegalActionStarted
+            }
+            CounterfeitAction::RegisterWithCustoms => {
+                counterfeits.customs_registered = true;
+                CounterfeitStage::CustomsRegistered
+            }
+            CounterfeitAction::Authenticate => {
+                counterfeits.seizure_hold_days = None;
+                counterfeits.prevalence *= 1.0 - SEIZURE_CUT;
+                game_state.reputation = (game_state.reputation + BUST_REPUTATION).min(5.0);
+                rep_events.write(ReputationChangedEvent {
+                    new_reputation: game_state.reputation,
+                });
+                CounterfeitStage::Forfeited
+            }
+        };
+        counterfeit_events.write(CounterfeitEvent { stage });
+    }
+}
+
+/// Each game day: the fakes spread, burn customers, and run into the lawyers and customs
+fn daily_counterfeits(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    world: Res<WorldState>,
+    word_of_mouth: Res<WordOfMouth>,
+    mut game_state: ResMut<GameState>,
+    mut counterfeits: ResMut<CounterfeitState>,
+    mut counterfeit_events: MessageWriter<CounterfeitEvent>,
+    mut rep_events: MessageWriter<ReputationChangedEvent>,
+) {
+    for _ in day_events.read() {
+        // Fakes follow fame
+        let excess = (word_of_mouth.recognition(&world) - RECOGNITION_THRESHOLD).max(0.0) as f32;
+        if excess > 0.0 {
+            let appeared = !counterfeits.appeared();
+            counterfeits.prevalence += SPREAD_RATE * excess * (MAX_PREVALENCE - counterfeits.prevalence).max(0.0);
+            if appeared {
+                counterfeit_events.write(CounterfeitEvent {
+                    stage: CounterfeitStage::Appeared,
+                });
+            }
+        }
+        let prevalence = counterfeits.prevalence / MAX_PREVALENCE;
+
+        if rand::random::<f32>() < BURN_CHANCE * prevalence {
+            game_state.reputation = (game_state.reputation - BURN_REPUTATION).max(0.0);
+            rep_events.write(ReputationChangedEvent {
+                new_reputation: game_state.reputation,
+            });
+            counterfeit_events.write(CounterfeitEvent {
+                stage: CounterfeitStage::CustomerBurned,
+            });
+        }
+
+        if let Some(days) = counterfeits.legal_action_days {
+            if days <= 1 {
+                counterfeits.legal_action_days = None;
+                counterfeits.prevalence *= 1.0 - LEGAL_ACTION_CUT;
+                counterfeit_events.write(CounterfeitEvent {
+                    stage: CounterfeitStage::LegalActionWon,
+                });
+            } else {
+                counterfeits.legal_action_days = Some(days - 1);
+            }
+        }
+
+        // A held shipment waits for the report; a registered Thing gets shipments stopped
+        match counterfeits.seizure_hold_days {
+            Some(days) if days <= 1 => {
+                counterfeits.seizure_hold_days = None;
+                counterfeits.prevalence = (counterfeits.prevalence + RELEASE_BOOST).min(MAX_PREVALENCE);
+                counterfeit_events.write(CounterfeitEvent {
+                    stage: CounterfeitStage::Released,
+                });
+            }
+            Some(days) => counterfeits.seizure_hold_days = Some(days - 1),
+            None if counterfeits.customs_registered && rand::random::<f32>() < SEIZURE_CHANCE * prevalence => {
+                counterfeits.seizure_hold_days = Some(SEIZURE_HOLD_DAYS);
+                counterfeit_events.write(CounterfeitEvent {
+                    stage: CounterfeitStage::Seized,
+                });
+            }
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,326 @@
+//! ThingCoin - a speculative asset that only exists while crypto is in fashion
+//!
+//! During the historical crypto manias (2017 and 2021) ThingCoin lists and
+//! swings wildly from day to day. Outside them it's crypto winter: nothing new
+//! can be bought and the price bleeds out, though holders can still sell.
+//! Bad Thing companies can also issue a coin of their own, which raises cash
+//! at once but risks being exposed as a rug pull every day the window lasts.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::currency::Money;
+use crate::economy::{DayAdvancedEvent, GameDate, WorldState};
+use crate::fortune::RunModifiers;
+use crate::game_state::{AppState, GameState, ReputationChangedEvent};
+use crate::social::{Post, Sentiment, SocialFeed};
+use crate::thing_type::ThingRegistry;
+
+/// Price when ThingCoin first lists (and the floor it's hyped back to each mania)
+const LISTING_PRICE: f64 = 10.0;
+/// Largest daily swing during a mania, either way
+const MANIA_SWING: f64 = 0.3;
+/// Average daily rise during a mania
+const MANIA_DRIFT: f64 = 0.01;
+/// Largest daily swing in crypto winter
+const WINTER_SWING: f64 = 0.05;
+/// Average daily fall in crypto winter
+const WINTER_DRIFT: f64 = -0.03;
+/// A day's move this big is news
+const BIG_MOVE: f64 = 0.2;
+/// Lowest the price can go
+const MIN_PRICE: f64 = 0.01;
+/// Share of cash spent per buy
+pub const BUY_SHARE: f64 = 0.1;
+/// Cash a coin raises, plus some per customer served
+const ISSUE_BASE: f64 = 200.0;
+const ISSUE_PER_CUSTOMER: f64 = 0.5;
+/// Daily chance an issued coin is exposed as a rug pull
+const RUG_PULL_CHANCE: f32 = 0.02;
+/// Share of the raise paid back when it is
+const RUG_PULL_REFUND: f64 = 0.6;
+/// Reputation lost when it is
+const RUG_PULL_REPUTATION: f32 = 1.5;
+
+/// Is crypto in fashion on this date?
+pub fn crypto_window(date: GameDate) -> bool {
+    matches!(
+        (date.year(), date.month()),
+        (2017, 5..) | (2018, 1) | (2020, 12) | (2021, _) | (2022, 1..=4)
+    )
+}
+
+/// What the player can do with ThingCoin
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CryptoTrade {
+    Buy,
+    SellAll,
+    /// Bad Things only
+    IssueCoin,
+}
+
+impl CryptoTrade {
+    pub const ALL: [CryptoTrade; 3] = [CryptoTrade::Buy, CryptoTrade::SellAll, CryptoTrade::IssueCoin];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CryptoTrade::Buy => "Buy (10% of cash)",
+            CryptoTrade::SellAll => "Sell all",
+            CryptoTrade::IssueCoin => "Issue our own coin",
+        }
+    }
+}
+
+/// ThingCoin holdings and the market
+#[derive(Resource, Reflect)]
+#[reflect(Resource, Default)]
+pub struct CryptoState {
+    pub price: f64,
+    /// Price yesterday, for the daily change
+    pub previous_price: f64,
+    pub coins: f64,
+    /// What the coins held cost
+    pub cost_basis: Money,
+    pub window_open: bool,
+    /// Cash raised by our own coin, while it's live
+    pub issued: Option<f64>,
+    /// Only one coin per mania
+    pub issued_this_window: bool,
+}
+
+impl Default for CryptoState {
+    fn default() -> Self {
+        Self {
+            price: LISTING_PRICE,
+            previous_price: LISTING_PRICE,
+            coins: 0.0,
+            cost_basis: Money::ZERO,
+            window_open: false,
+            issued: None,
+            issued_this_window: false,
+        }
+    }
+}
+
+impl CryptoState {
+    pub fn holdings_value(&self) -> f64 {
+        self.coins * self.price
+    }
+
+    /// Today's move as a fraction of yesterday's price
+    pub fn daily_change(&self) -> f64 {
+        self.price / self.previous_price - 1.0
+    }
+
+    pub fn can_trade(&self, trade: CryptoTrade, game_state: &GameState, things: &ThingRegistry) -> bool {
+        match trade {
+            CryptoTrade::Buy => self.window_open && game_state.money > 0.0,
+            CryptoTrade::SellAll => self.coins > 0.0,
+            CryptoTrade::IssueCoin => {
+                self.window_open && !self.issued_this_window && things.is_shady(game_state.thing_type)
+            }
+        }
+    }
+}
+
+/// What happened with ThingCoin
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CryptoStage {
+    WindowOpened,
+    WindowClosed,
+    Bought { spent: f64 },
+    Sold { proceeds: f64, gain: f64 },
+    /// A big rise in a day
+    Mooned,
+    /// A big fall in a day
+    Crashed,
+    Issued { raised: f64 },
+    /// Our coin was exposed; `refund` went back to the investors
+    RugPulled { refund: f64 },
+    /// The mania ended before anyone noticed
+    CashedOut { raised: f64 },
+}
+
+/// Message fired for everything that happens with ThingCoin
+#[derive(Event, Message, Clone)]
+pub struct CryptoEvent {
+    pub stage: CryptoStage,
+}
+
+/// Message requesting a trade (sent by the UI)
+#[derive(Event, Message, Clone)]
+pub struct CryptoTradeEvent {
+    pub trade: CryptoTrade,
+}
+
+pub struct CryptoPlugin;
+
+impl Plugin for CryptoPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CryptoState>()
+            .add_message::<CryptoEvent>()
+            .add_message::<CryptoTradeEvent>()
+            .add_systems(
+                Update,
+                (handle_crypto_trades, move_crypto_market, post_crypto_chatter)
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// Buy, sell, or issue a coin
+fn handle_crypto_trades(
+    mut trade_events: MessageReader<CryptoTradeEvent>,
+    mut game_state: ResMut<GameState>,
+    things: Res<ThingRegistry>,
+    mut crypto: ResMut<CryptoState>,
+    mut crypto_events: MessageWriter<CryptoEvent>,
+) {
+    for event in trade_events.read() {
+        if !crypto.can_trade(event.trade, &game_state, &things) {
+            continue;
+        }
+        let stage = match event.trade {
+            CryptoTrade::Buy => {
+                let spent = game_state.money.share(BUY_SHARE);
+                game_state.money -= spent;
+                crypto.coins += spent.dollars() / crypto.price;
+                crypto.cost_basis += spent;
+                CryptoStage::Bought { spent: spent.dollars() }
+            }
+            CryptoTrade::SellAll => {
+                let proceeds = Money::from_dollars(crypto.holdings_value());
+                let gain = proceeds - crypto.cost_basis;
+                game_state.money += proceeds;
+                crypto.coins = 0.0;
+                crypto.cost_basis = Money::ZERO;
+                CryptoStage::Sold {
+                    proceeds: proceeds.dollars(),
+                    gain: gain.dollars(),
+                }
+            }
+            CryptoTrade::IssueCoin => {
+                let raised = ISSUE_BASE + game_state.customers_served as f64 * ISSUE_PER_CUSTOMER;
+                game_state.money += raised;
+                crypto.issued = Some(raised);
+                crypto.issued_this_window = true;
+                CryptoStage::Issued { raised }
+            }
+        };
+        crypto_events.write(CryptoEvent { stage });
+    }
+}
+
+/// Each game day: open or close the window, move the price, and roll for a rug pull
+fn move_crypto_market(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    modifiers: Res<RunModifiers>,
+    mut game_state: ResMut<GameState>,
+    mut crypto: ResMut<CryptoState>,
+    mut crypto_events: MessageWriter<CryptoEvent>,
+    mut rep_events: MessageWriter<ReputationChangedEvent>,
+) {
+    for event in day_events.read() {
+        let open = crypto_window(event.date);
+        if open != crypto.window_open {
+            crypto.window_open = open;
+            if open {
+                crypto.price = crypto.price.max(LISTING_PRICE);
+                crypto.previous_price = crypto.price;
+                crypto.issued_this_window = false;
+                crypto_events.write(CryptoEvent { stage: CryptoStage::WindowOpened });
+            } else {
+                crypto_events.write(CryptoEvent { stage: CryptoStage::WindowClosed });
+                if let Some(raised) = crypto.issued.take() {
+                    crypto_events.write(CryptoEvent { stage: CryptoStage::CashedOut { raised } });
+                }
+            }
+        }
+
+        // Nothing to simulate before the first mania or with nobody holding
+        if !open && crypto.coins <= 0.0 {
+            continue;
+        }
+
+        let (drift, swing) = if open { (MANIA_DRIFT, MANIA_SWING) } else { (WINTER_DRIFT, WINTER_SWING) };
+        let change = drift + (rand::random::<f64>() * 2.0 - 1.0) * swing;
+        crypto.previous_price = crypto.price;
+        crypto.price = (crypto.price * (1.0 + change)).max(MIN_PRICE);
+        if open && change >= BIG_MOVE {
+            crypto_events.write(CryptoEvent { stage: CryptoStage::Mooned });
+        } else if open && change <= -BIG_MOVE {
+            crypto_events.write(CryptoEvent { stage: CryptoStage::Crashed });
+        }
+
+        let Some(raised) = crypto.issued else {
+            continue;
+        };
+        if rand::random::<f32>() < RUG_PULL_CHANCE {
+            let refund = raised * RUG_PULL_REFUND;
+            crypto.issued = None;
+            game_state.money -= refund;
+            let change = modifiers.scale_reputation_change(-RUG_PULL_REPUTATION);
+            game_state.reputation = (game_state.reputation + change).clamp(0.0, 5.0);
+            rep_events.write(ReputationChangedEvent {
+                new_reputation: game_state.reputation,
+            });
+            crypto_events.write(CryptoEvent { stage: CryptoStage::RugPulled { refund } });
+        }
+    }
+}
+
+/// Crypto Chirper has opinions
+fn post_crypto_chatter(
+    mut crypto_events: MessageReader<CryptoEvent>,
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    crypto: Res<CryptoState>,
+    world: Res<WorldState>,
+    mut feed: ResMut<SocialFeed>,
+) {
+    let thing = game_state.thing_type.map_or("Thing", |t| &things[t].name);
+
+    for event in crypto_events.read() {
+        let move_pct = crypto.daily_change().abs() * 100.0;
+        let (author, text, sentiment) = match event.stage {
+            CryptoStage::WindowOpened => (
+                "@crypto_carl",
+                "ThingCoin just listed. It's a coin. For Things. Not backed by Things. Buying.".to_string(),
+                Sentiment::Neutral,
+            ),
+            CryptoStage::Mooned => (
+                "@crypto_carl",
+                format!("ThingCoin up {:.0}% today. Told my boss what I think of him. No regrets.", move_pct),
+                Sentiment::Neutral,
+            ),
+            CryptoStage::Crashed => (
+                "@crypto_carl",
+                format!("ThingCoin down {:.0}% today. Does anyone know if my boss is hiring.", move_pct),
+                Sentiment::Neutral,
+            ),
+            CryptoStage::WindowClosed => (
+                "@skeptical_steve",
+                "Remember ThingCoin? Neither does anyone who bought it.".to_string(),
+                Sentiment::Neutral,
+            ),
+            CryptoStage::Issued { .. } => (
+                "@skeptical_steve",
+                format!("The {} Thing company launched its own coin. The whitepaper is one page. It says \"trust us\".", thing),
+                Sentiment::Negative,
+            ),
+            CryptoStage::RugPulled { .. } => (
+                "@actual_reviewer",
+                format!("The {} Thing coin was a rug pull. Shocked. Not surprised. But shocked.", thing),
+                Sentiment::Negative,
+            ),
+            CryptoStage::Bought { .. } | CryptoStage::Sold { .. } | CryptoStage::CashedOut { .. } => continue,
+        };
+        feed.push(Post {
+            date: world.date,
+            author: author.to_string(),
+            text,
+            sentiment,
+            astroturfed: false,
+        });
+    }
+}
@@ -0,0 +1,367 @@
+//! Money - the cash balance, and formatting every amount shown to the player
+//!
+//! The balance is a [`Money`]: whole cents in an integer, so it doesn't drift
+//! as millions of small sales land on a late-game fortune. Prices, costs and
+//! revenue are still worked out as `f64` and become cents when they touch the
+//! balance, rounded to the nearest cent (halves away from zero). Sales carry the
+//! fraction of a cent they couldn't pay into the next sale instead of rounding
+//! it away, so nothing is made or lost along the way.
+//!
+//! The player picks how money reads in the settings ($1,234.56, 1.234,56 €,
+//! £1,234.56 or ¥1,235). It's only a display choice: amounts aren't
+//! converted, and exports keep plain numbers. The choice lives in a global
+//! rather than a resource because money is formatted all over, including in
+//! report and event log text built far from any system.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering as CmpOrdering;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Cents in a unit of money
+const CENTS: f64 = 100.0;
+
+/// An amount of money, in whole cents
+///
+/// Arithmetic saturates rather than wrapping, and amounts coming in as `f64`
+/// are rounded to the nearest cent with halves away from zero (not-a-number
+/// counts as nothing).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Reflect, Serialize, Deserialize)]
+pub struct Money(i64);
+
+impl Money {
+    pub const ZERO: Money = Money(0);
+
+    pub const fn from_cents(cents: i64) -> Self {
+        Money(cents)
+    }
+
+    /// An amount to the nearest cent
+    pub fn from_dollars(amount: f64) -> Self {
+        // `as` saturates at the ends of i64 and turns NaN into 0
+        Money((amount * CENTS).round() as i64)
+    }
+
+    /// Split an amount into the whole cents in it and the fraction of a cent
+    /// left over (with the same sign), for the next amount to pick up
+    pub fn whole_cents(amount: f64) -> (Self, f64) {
+        let cents = (amount * CENTS).trunc();
+        (Money(cents as i64), (amount * CENTS - cents) / CENTS)
+    }
+
+    pub fn cents(&self) -> i64 {
+        self.0
+    }
+
+    /// The amount as a plain number, for prices, charts and exports
+    pub fn dollars(&self) -> f64 {
+        self.0 as f64 / CENTS
+    }
+
+    /// A share of the amount, to the nearest cent
+    pub fn share(&self, fraction: f64) -> Self {
+        Money::from_dollars(self.dollars() * fraction)
+    }
+
+    /// Never less than nothing
+    pub fn at_least_zero(&self) -> Self {
+        Money(self.0.max(0))
+    }
+}
+
+impl From<Money> for f64 {
+    fn from(money: Money) -> f64 {
+        money.dollars()
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, other: Money) -> Money {
+        Money(self.0.saturating_add(other.0))
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, other: Money) -> Money {
+        Money(self.0.saturating_sub(other.0))
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        Money(self.0.saturating_neg())
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, other: Money) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, other: Money) {
+        *self = *self - other;
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(amounts: I) -> Money {
+        amounts.fold(Money::ZERO, Add::add)
+    }
+}
+
+/// Adding an `f64` amount rounds it to the nearest cent first
+impl AddAssign<f64> for Money {
+    fn add_assign(&mut self, amount: f64) {
+        *self += Money::from_dollars(amount);
+    }
+}
+
+/// Taking away an `f64` amount rounds it to the nearest cent first
+impl SubAssign<f64> for Money {
+    fn sub_assign(&mut self, amount: f64) {
+        *self -= Money::from_dollars(amount);
+    }
+}
+
+/// Compared with a price as it would be paid: rounded to the nearest cent
+impl PartialEq<f64> for Money {
+    fn eq(&self, amount: &f64) -> bool {
+        *self == Money::from_dollars(*amount)
+    }
+}
+
+impl PartialOrd<f64> for Money {
+    fn partial_cmp(&self, amount: &f64) -> Option<CmpOrdering> {
+        if amount.is_nan() {
+            return None;
+        }
+        Some(self.cmp(&Money::from_dollars(*amount)))
+    }
+}
+
+/// The format everything is currently shown in (see [`CurrencyFormat::set_current`])
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+/// How money reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CurrencyFormat {
+    /// $1,234.56
+    #[default]
+    Dollars,
+    /// 1.234,56 €
+    Euros,
+    /// £1,234.56
+    Pounds,
+    /// ¥1,235 - yen have no cents
+    Yen,
+}
+
+impl CurrencyFormat {
+    pub const ALL: [CurrencyFormat; 4] = [
+        CurrencyFormat::Dollars,
+        CurrencyFormat::Euros,
+        CurrencyFormat::Pounds,
+        CurrencyFormat::Yen,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CurrencyFormat::Dollars => "$1,234.56",
+            CurrencyFormat::Euros => "1.234,56 €",
+            CurrencyFormat::Pounds => "£1,234.56",
+            CurrencyFormat::Yen => "¥1,235",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            CurrencyFormat::Dollars => CurrencyFormat::Euros,
+            CurrencyFormat::Euros => CurrencyFormat::Pounds,
+            CurrencyFormat::Pounds => CurrencyFormat::Yen,
+            CurrencyFormat::Yen => CurrencyFormat::Dollars,
+        }
+    }
+
+    pub fn current() -> Self {
+        Self::ALL[CURRENT.load(Ordering::Relaxed) as usize]
+    }
+
+    pub fn set_current(format: CurrencyFormat) {
+        let index = Self::ALL.iter().position(|f| *f == format).unwrap_or(0);
+        CURRENT.store(index as u8, Ordering::Relaxed);
+    }
+
+    /// Thousands separator and decimal point
+    fn separators(&self) -> (char, char) {
+        match self {
+            CurrencyFormat::Euros => ('.', ','),
+            _ => (',', '.'),
+        }
+    }
+
+    /// Format an amount with up to `decimals` places (yen never have any)
+    pub fn format(&self, amount: f64, decimals: usize) -> String {
+        let decimals = if *self == CurrencyFormat::Yen { 0 } else { decimals };
+        let digits = format!("{:.*}", decimals, amount.abs());
+        let (whole, fraction) = digits.split_once('.').unwrap_or((&digits, ""));
+        let (thousands, point) = self.separators();
+
+        let mut number = String::new();
+        for (i, digit) in whole.chars().enumerate() {
+            if i > 0 && (whole.len() - i) % 3 == 0 {
+                number.push(thousands);
+            }
+            number.push(digit);
+        }
+        if !fraction.is_empty() {
+            number.push(point);
+            number.push_str(fraction);
+        }
+
+        // Rounding can leave a negative amount at zero
+        let sign = if amount < 0.0 && digits.chars().any(|c| c.is_ascii_digit() && c != '0') { "-" } else { "" };
+        match self {
+            CurrencyFormat::Dollars => format!("{}${}", sign, number),
+            CurrencyFormat::Euros => format!("{}{} €", sign, number),
+            CurrencyFormat::Pounds => format!("{}£{}", sign, number),
+            CurrencyFormat::Yen => format!("{}¥{}", sign, number),
+        }
+    }
+}
+
+/// An amount to the cent, in the player's format
+pub fn money(amount: impl Into<f64>) -> String {
+    CurrencyFormat::current().format(amount.into(), 2)
+}
+
+/// An amount in whole units, in the player's format
+pub fn money_whole(amount: impl Into<f64>) -> String {
+    CurrencyFormat::current().format(amount.into(), 0)
+}
+
+/// A gain or loss in whole units, always signed
+pub fn money_change(amount: impl Into<f64>) -> String {
+    let amount = amount.into();
+    let formatted = money_whole(amount);
+    if formatted.starts_with('-') {
+        formatted
+    } else {
+        format!("+{}", formatted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{RngExt, SeedableRng};
+
+    const TRIALS: usize = 1_000;
+
+    #[test]
+    fn amounts_round_to_the_nearest_cent() {
+        assert_eq!(Money::from_dollars(12.344).cents(), 1234);
+        assert_eq!(Money::from_dollars(12.346).cents(), 1235);
+        assert_eq!(Money::from_dollars(0.005).cents(), 1);
+        assert_eq!(Money::from_dollars(-0.005).cents(), -1);
+        assert_eq!(Money::from_dollars(f64::NAN), Money::ZERO);
+        assert_eq!(Money::from_dollars(1e300).cents(), i64::MAX);
+        assert_eq!(Money::from_cents(i64::MAX) + Money::from_cents(1), Money::from_cents(i64::MAX));
+    }
+
+    #[test]
+    fn buying_and_selling_never_makes_or_loses_money() {
+        let mut rng = StdRng::seed_from_u64(2012);
+        for _ in 0..TRIALS {
+            let start = Money::from_cents(rng.random_range(-1_000_000..1_000_000_000_000));
+            let mut balance = start;
+            let mut expected = start.cents();
+
+            for _ in 0..50 {
+                let amount = rng.random_range(0.0..1_000_000.0);
+                let cents = Money::from_dollars(amount).cents();
+                if rng.random_bool(0.5) {
+                    balance -= amount;
+                    expected -= cents;
+                } else {
+                    balance += amount;
+                    expected += cents;
+                }
+            }
+            assert_eq!(balance.cents(), expected);
+
+            // Buying something and selling it back for the same price comes out even
+            let price = rng.random_range(0.0..1_000_000.0);
+            let mut round_trip = balance;
+            round_trip -= price;
+            round_trip += price;
+            assert_eq!(round_trip, balance);
+        }
+    }
+
+    #[test]
+    fn fractions_of_a_cent_are_carried_not_lost() {
+        let mut rng = StdRng::seed_from_u64(31_337);
+        for _ in 0..TRIALS / 10 {
+            let mut balance = Money::ZERO;
+            let mut carry = 0.0;
+            let mut exact = 0.0;
+            for _ in 0..1_000 {
+                let revenue = rng.random_range(0.0..0.03);
+                exact += revenue;
+                let (earned, leftover) = Money::whole_cents(revenue + carry);
+                carry = leftover;
+                balance += earned;
+            }
+            assert!((0.0..0.01).contains(&carry), "carry {}", carry);
+            assert!(balance.dollars() <= exact + 1e-9, "paid {} for {}", balance.dollars(), exact);
+            assert!((balance.dollars() + carry - exact).abs() < 1e-9, "{} + {} vs {}", balance.dollars(), carry, exact);
+        }
+    }
+
+    #[test]
+    fn huge_balances_still_count_every_cent() {
+        let mut balance = Money::from_dollars(90_000_000_000_000.0);
+        let start = balance;
+        for _ in 0..10_000 {
+            balance += 0.01;
+        }
+        assert_eq!(balance - start, Money::from_dollars(100.0));
+
+        // The same sum in floating point drifts, a fraction of a cent at a time
+        let mut float = 90_000_000_000_000.0_f64;
+        for _ in 0..10_000 {
+            float += 0.01;
+        }
+        assert_ne!(float - 90_000_000_000_000.0, 100.0);
+    }
+
+    #[test]
+    fn prices_compare_as_they_would_be_paid() {
+        let balance = Money::from_dollars(100.0);
+        assert!(balance >= 100.0);
+        assert!(balance >= 100.004);
+        assert!(balance < 100.005);
+        assert!(balance == 99.999);
+        assert!(balance.partial_cmp(&f64::NAN).is_none());
+    }
+
+    #[test]
+    fn shown_the_same_as_plain_amounts() {
+        let format = CurrencyFormat::Dollars;
+        assert_eq!(format.format(Money::from_dollars(1234.5).into(), 2), "$1,234.50");
+        assert_eq!(format.format(Money::from_dollars(-0.25).into(), 2), "-$0.25");
+        assert_eq!(format.format(Money::from_cents(123_456_789_012).into(), 0), "$1,234,567,890");
+    }
+}
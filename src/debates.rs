@@ -0,0 +1,401 @@
+//! Advisor debates - Terry and Doug arguing it out
+//!
+//! Doug kept his lanyard after filling in for Terry, so once Terry's had a
+//! break there are two advisors. Every so often they disagree, argue it out a
+//! turn at a time, and the player has to back one of them. The winner's plan
+//! goes ahead, cost and consequences included, and both relationships feel
+//! it: whoever you back warms to you and the other one sulks. An argument
+//! nobody settles within a few days blows over, and neither of them is
+//! pleased about that either.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::accounting::ExpenseCategory;
+use crate::economy::DayAdvancedEvent;
+use crate::game_state::{AppState, GameState, ReputationChangedEvent};
+use crate::vacation::{VacationEvent, VacationStage, VacationState};
+
+/// Days between debates, at least
+const MIN_DAYS_BETWEEN_DEBATES: u32 = 30;
+/// Daily chance of a debate once it's been long enough
+const DEBATE_CHANCE: f32 = 0.05;
+/// Days the player has to pick a side before it blows over
+const DEBATE_DAYS: u32 = 5;
+/// Where the relationship with Doug starts
+const STARTING_DOUG_RELATIONSHIP: f32 = 50.0;
+/// Relationship changes
+const BACKED_GOODWILL: f32 = 8.0;
+const OVERRULED_COST: f32 = 6.0;
+const IGNORED_COST: f32 = 3.0;
+
+/// Who's giving the advice
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Advisor {
+    Terry,
+    Doug,
+}
+
+impl Advisor {
+    pub const BOTH: [Advisor; 2] = [Advisor::Terry, Advisor::Doug];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Advisor::Terry => "Terry",
+            Advisor::Doug => "Doug",
+        }
+    }
+}
+
+/// One advisor's side of a debate, and what comes of going with it
+pub struct Position {
+    /// The advisor's two turns in the argument
+    pub lines: [&'static str; 2],
+    /// The plan, in a few words
+    pub plan: &'static str,
+    /// Share of the cash it costs (negative brings money in)
+    pub cost: f64,
+    pub reputation: f32,
+}
+
+impl Position {
+    /// What the plan costs or brings in, and does to reputation
+    pub fn outcome(&self) -> String {
+        let cash = match self.cost {
+            cost if cost > 0.0 => format!("costs {:.0}% of cash", cost * 100.0),
+            cost if cost < 0.0 => format!("brings in {:.0}% of cash", -cost * 100.0),
+            _ => "free".to_string(),
+        };
+        format!("{}: {}, reputation {:+.2}", self.plan, cash, self.reputation)
+    }
+}
+
+/// Something Terry and Doug disagree about
+pub struct DebateTopic {
+    pub title: &'static str,
+    /// Where the money goes, if the winning plan costs any
+    pub category: ExpenseCategory,
+    pub terry: Position,
+    pub doug: Position,
+}
+
+impl DebateTopic {
+    pub fn position(&self, advisor: Advisor) -> &Position {
+        match advisor {
+            Advisor::Terry => &self.terry,
+            Advisor::Doug => &self.doug,
+        }
+    }
+
+    /// The argument in order, a turn each (Terry opens)
+    pub fn turns(&self) -> [(Advisor, &'static str); 4] {
+        [
+            (Advisor::Terry, self.terry.lines[0]),
+            (Advisor::Doug, self.doug.lines[0]),
+            (Advisor::Terry, self.terry.lines[1]),
+            (Advisor::Doug, self.doug.lines[1]),
+        ]
+    }
+}
+
+/// Everything Terry and Doug argue about
+pub const DEBATE_TOPICS: [DebateTopic; 5] = [
+    DebateTopic {
+        title: "Pricing",
+        category: ExpenseCategory::Marketing,
+        terry: Position {
+            lines: [
+                "We need premium positioning. Customers don't buy Things, they buy the story of the Thing.",
+                "A focus group will tell us exactly which story. That's called data, Doug.",
+            ],
+            plan: "Focus group",
+            cost: 0.05,
+            reputation: 0.2,
+        },
+        doug: Position {
+            lines: [
+                "Or we put up a sign that says SALE. People love a sign.",
+                "My cousin did SALE signs for eleven years. Never once asked a focus group anything.",
+            ],
+            plan: "SALE sign",
+            cost: -0.03,
+            reputation: -0.1,
+        },
+    },
+    DebateTopic {
+        title: "The break room",
+        category: ExpenseCategory::Payroll,
+        terry: Position {
+            lines: [
+                "A wellness program. Yoga, fruit, a quiet room. Happy workers are productive workers.",
+                "It's a documented ROI. I wrote a case study on it. It got a B-plus.",
+            ],
+            plan: "Wellness program",
+            cost: 0.04,
+            reputation: 0.15,
+        },
+        doug: Position {
+            lines: [
+                "Vending machine. They pay us for the snacks. It's a wellness program that makes money.",
+                "I'll even stock it. I know a guy with a lot of off-brand crisps.",
+            ],
+            plan: "Vending machine",
+            cost: -0.02,
+            reputation: -0.05,
+        },
+    },
+    DebateTopic {
+        title: "The books",
+        category: ExpenseCategory::Legal,
+        terry: Position {
+            lines: [
+                "It's time for a proper audit. Clean books are a competitive advantage.",
+                "Auditors find things, Doug. That's the point. Better us than the taxman.",
+            ],
+            plan: "Proper audit",
+            cost: 0.03,
+            reputation: 0.1,
+        },
+        doug: Position {
+            lines: [
+                "Hear me out: we lose the receipts. Can't audit what isn't there.",
+                "I've lost loads of receipts. Never once been audited. Coincidence? I don't think so.",
+            ],
+            plan: "Lose the receipts",
+            cost: -0.05,
+            reputation: -0.25,
+        },
+    },
+    DebateTopic {
+        title: "The logo",
+        category: ExpenseCategory::Marketing,
+        terry: Position {
+            lines: [
+                "We need a rebrand. A real agency. Brand architecture, a mood board, the works.",
+                "They did the logo for a bank. You've seen it. It's a circle, but a confident circle.",
+            ],
+            plan: "Agency rebrand",
+            cost: 0.06,
+            reputation: 0.25,
+        },
+        doug: Position {
+            lines: [
+                "I can do a logo. I've got Paint. It's a Thing with sunglasses on. Done.",
+                "And it's free. Confident circles aren't free.",
+            ],
+            plan: "Doug's logo",
+            cost: 0.0,
+            reputation: 0.05,
+        },
+    },
+    DebateTopic {
+        title: "The factory floor",
+        category: ExpenseCategory::Upgrades,
+        terry: Position {
+            lines: [
+                "Lean consultants. They'll map every process and cut the waste.",
+                "Continuous improvement, Doug. It's a whole philosophy. There's a belt system.",
+            ],
+            plan: "Lean consultants",
+            cost: 0.05,
+            reputation: 0.1,
+        },
+        doug: Position {
+            lines: [
+                "Have we tried turning the machines off and on again?",
+                "Every problem I've ever had, that fixed it. Except the one with the fish.",
+            ],
+            plan: "Off and on again",
+            cost: 0.0,
+            reputation: -0.05,
+        },
+    },
+];
+
+/// An argument waiting for the player to pick a side
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct Debate {
+    /// Index into `DEBATE_TOPICS`
+    pub topic: usize,
+    /// Days left to pick a side
+    pub days_left: u32,
+}
+
+impl Debate {
+    pub fn topic(&self) -> &'static DebateTopic {
+        &DEBATE_TOPICS[self.topic]
+    }
+}
+
+/// The second advisor, and how the arguments have gone
+#[derive(Resource, Reflect)]
+#[reflect(Resource, Default)]
+pub struct DebateState {
+    /// Doug stayed on after filling in for Terry
+    pub doug_on_staff: bool,
+    /// 0-100 (Terry's is kept with the vacations)
+    pub doug_relationship: f32,
+    pub active: Option<Debate>,
+    /// Days since the last debate ended
+    pub days_since_debate: u32,
+    /// Debates each advisor has won
+    pub terry_wins: u32,
+    pub doug_wins: u32,
+}
+
+impl Default for DebateState {
+    fn default() -> Self {
+        Self {
+            doug_on_staff: false,
+            doug_relationship: STARTING_DOUG_RELATIONSHIP,
+            active: None,
+            days_since_debate: 0,
+            terry_wins: 0,
+            doug_wins: 0,
+        }
+    }
+}
+
+impl DebateState {
+    fn change_doug_relationship(&mut self, amount: f32) {
+        self.doug_relationship = (self.doug_relationship + amount).clamp(0.0, 100.0);
+    }
+}
+
+/// How a debate went
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DebateStage {
+    Started,
+    /// The player backed this advisor
+    Settled(Advisor),
+    /// Nobody picked a side in time
+    BlewOver,
+}
+
+/// Message fired as a debate starts and ends
+#[derive(Event, Message, Clone)]
+pub struct DebateEvent {
+    pub stage: DebateStage,
+}
+
+/// Message picking a side in the current debate (sent by the UI)
+#[derive(Event, Message, Clone)]
+pub struct DebateDecisionEvent {
+    pub side: Advisor,
+}
+
+pub struct DebatePlugin;
+
+impl Plugin for DebatePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebateState>()
+            .add_message::<DebateEvent>()
+            .add_message::<DebateDecisionEvent>()
+            .add_systems(
+                Update,
+                (keep_doug_on, handle_debate_decisions, progress_debates)
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// Doug's first stint as the temp makes him a permanent fixture
+fn keep_doug_on(mut vacation_events: MessageReader<VacationEvent>, mut debates: ResMut<DebateState>) {
+    for event in vacation_events.read() {
+        if matches!(event.stage, VacationStage::Granted | VacationStage::BurnedOut) && !debates.doug_on_staff {
+            debates.doug_on_staff = true;
+        }
+    }
+}
+
+/// Go with the chosen advisor's plan
+fn handle_debate_decisions(
+    mut decision_events: MessageReader<DebateDecisionEvent>,
+    mut game_state: ResMut<GameState>,
+    mut vacation: ResMut<VacationState>,
+    mut debates: ResMut<DebateState>,
+    mut debate_events: MessageWriter<DebateEvent>,
+    mut rep_events: MessageWriter<ReputationChangedEvent>,
+) {
+    for event in decision_events.read() {
+        let Some(debate) = debates.active.take() else {
+            continue;
+        };
+        let topic = debate.topic();
+        let position = topic.position(event.side);
+
+        let amount = game_state.money.at_least_zero().share(position.cost.abs());
+        if position.cost > 0.0 {
+            game_state.spend(topic.category, amount.dollars());
+        } else {
+            game_state.money += amount;
+        }
+        if position.reputation != 0.0 {
+            game_state.reputation = (game_state.reputation + position.reputation).clamp(0.0, 5.0);
+            rep_events.write(ReputationChangedEvent {
+                new_reputation: game_state.reputation,
+            });
+        }
+
+        let (terry_change, doug_change) = match event.side {
+            Advisor::Terry => {
+                debates.terry_wins += 1;
+                (BACKED_GOODWILL, -OVERRULED_COST)
+            }
+            Advisor::Doug => {
+                debates.doug_wins += 1;
+                (-OVERRULED_COST, BACKED_GOODWILL)
+            }
+        };
+        vacation.change_relationship(terry_change);
+        debates.change_doug_relationship(doug_change);
+        debates.days_since_debate = 0;
+        debate_events.write(DebateEvent {
+            stage: DebateStage::Settled(event.side),
+        });
+    }
+}
+
+/// Each game day: start an argument, or let an unsettled one blow over
+fn progress_debates(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    mut vacation: ResMut<VacationState>,
+    mut debates: ResMut<DebateState>,
+    mut debate_events: MessageWriter<DebateEvent>,
+) {
+    for _ in day_events.read() {
+        // No arguing with Terry while Terry's away; an open argument is dropped
+        if vacation.is_away() {
+            debates.active = None;
+            continue;
+        }
+
+        if let Some(debate) = debates.active.as_mut() {
+            debate.days_left = debate.days_left.saturating_sub(1);
+            if debate.days_left == 0 {
+                debates.active = None;
+                debates.days_since_debate = 0;
+                vacation.change_relationship(-IGNORED_COST);
+                debates.change_doug_relationship(-IGNORED_COST);
+                debate_events.write(DebateEvent { stage: DebateStage::BlewOver });
+            }
+            continue;
+        }
+
+        if !debates.doug_on_staff {
+            continue;
+        }
+        debates.days_since_debate += 1;
+        // Not while Terry's waiting to hear about time off
+        if vacation.request_days.is_none()
+            && debates.days_since_debate >= MIN_DAYS_BETWEEN_DEBATES
+            && rand::random::<f32>() < DEBATE_CHANCE
+        {
+            debates.active = Some(Debate {
+                topic: rand::random_range(0..DEBATE_TOPICS.len()),
+                days_left: DEBATE_DAYS,
+            });
+            debate_events.write(DebateEvent { stage: DebateStage::Started });
+        }
+    }
+}
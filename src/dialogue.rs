@@ -2,33 +2,165 @@
 
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use crate::game_state::AppState;
+use crate::thing_type::ThingRegistry;
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
+/// Seconds before a trigger can be heard again, for lines that don't say
+pub const DEFAULT_TRIGGER_COOLDOWN: f32 = 12.0;
+/// Fewest seconds between two non-critical lines, unless a dialogue file says otherwise
+pub const DEFAULT_MIN_GAP: f32 = 6.0;
+
 pub struct DialoguePlugin;
 
 impl Plugin for DialoguePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<DialogueDatabase>()
-            .add_systems(Startup, load_dialogues);
+            .add_systems(OnEnter(AppState::Loading), load_dialogues);
     }
 }
 
+/// How urgently a line needs to be heard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DialoguePriority {
+    /// Background chatter - only plays when Terry has nothing else to say
+    Ambient,
+    /// Regular reactions - replace ambient chatter
+    #[default]
+    Normal,
+    /// Interrupts whatever Terry is saying right now (scandals, bankruptcy, big wins)
+    Critical,
+}
+
 /// A single dialogue line
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DialogueLine {
     pub id: String,
     pub trigger: String,
     pub text: String,
     #[serde(default)]
     pub mood: String,
+    #[serde(default)]
+    pub priority: DialoguePriority,
+    /// Minimum seconds on screen before a non-critical line may replace it
+    /// (defaults to an estimate based on reading speed)
+    #[serde(default)]
+    pub min_duration: Option<f32>,
+    /// Voice clip for the line, relative to `assets/` (the line is text-only without one)
+    #[serde(default)]
+    pub audio: Option<String>,
+    /// Seconds before this line's trigger can be heard again (critical lines
+    /// ignore it). Defaults to the dialogue files' `cooldowns` entry for the trigger.
+    #[serde(default)]
+    pub cooldown: Option<f32>,
+}
+
+impl DialogueLine {
+    /// How long this line must stay up before it can be replaced
+    pub fn min_display_secs(&self) -> f32 {
+        self.min_duration.unwrap_or_else(|| {
+            // Roughly four words a second, never less than two seconds
+            let words = self.text.split_whitespace().count() as f32;
+            (words / 4.0).clamp(2.0, 8.0)
+        })
+    }
+
+    pub fn cooldown_secs(&self) -> f32 {
+        self.cooldown.unwrap_or(DEFAULT_TRIGGER_COOLDOWN)
+    }
 }
 
 /// Collection of dialogue lines
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DialogueFile {
     pub lines: Vec<DialogueLine>,
+    /// Cooldowns by trigger, for lines in any file that don't set their own
+    #[serde(default)]
+    pub cooldowns: HashMap<String, f32>,
+    /// Fewest seconds between two non-critical lines (the last file to set it wins)
+    #[serde(default)]
+    pub min_gap: Option<f32>,
+}
+
+/// A dialogue file with its lines left unparsed, so a bad one can be skipped
+#[derive(Deserialize)]
+struct RawDialogueFile<'a> {
+    #[serde(borrow)]
+    lines: Vec<&'a RawValue>,
+    #[serde(default)]
+    cooldowns: HashMap<String, f32>,
+    #[serde(default)]
+    min_gap: Option<f32>,
+}
+
+/// Something wrong in a dialogue file (the line or file it's about was skipped)
+#[derive(Debug, Clone, PartialEq)]
+pub struct DialogueIssue {
+    pub path: String,
+    /// Line in the file, counting from 1
+    pub line: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for DialogueIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.path, self.line, self.message)
+    }
+}
+
+/// serde_json's message without its " at line X column Y"
+fn error_message(err: &serde_json::Error) -> String {
+    let message = err.to_string();
+    let position = format!(" at line {} column {}", err.line(), err.column());
+    message.strip_suffix(&position).unwrap_or(&message).to_string()
+}
+
+/// Parse a dialogue file, skipping any line that doesn't parse or is missing
+/// its id, trigger or text. Only a file that isn't JSON at all is lost whole.
+pub fn parse_dialogue_file(path: &str, contents: &str, issues: &mut Vec<DialogueIssue>) -> Option<DialogueFile> {
+    let raw: RawDialogueFile = match serde_json::from_str(contents) {
+        Ok(raw) => raw,
+        Err(err) => {
+            issues.push(DialogueIssue {
+                path: path.to_string(),
+                line: err.line(),
+                message: format!("file skipped: {}", error_message(&err)),
+            });
+            return None;
+        }
+    };
+
+    let mut lines = Vec::with_capacity(raw.lines.len());
+    for entry in raw.lines {
+        // The entry borrows from `contents`, so its offset says where it starts
+        let offset = entry.get().as_ptr() as usize - contents.as_ptr() as usize;
+        let first_line = contents[..offset].matches('\n').count() + 1;
+        let mut skip = |line: usize, message: String| {
+            issues.push(DialogueIssue { path: path.to_string(), line, message });
+        };
+
+        match serde_json::from_str::<DialogueLine>(entry.get()) {
+            Ok(line) if line.id.trim().is_empty() => skip(first_line, "line skipped: empty id".to_string()),
+            Ok(line) if line.trigger.trim().is_empty() => {
+                skip(first_line, format!("line '{}' skipped: empty trigger", line.id));
+            }
+            Ok(line) if line.text.trim().is_empty() => {
+                skip(first_line, format!("line '{}' skipped: empty text", line.id));
+            }
+            Ok(line) => lines.push(line),
+            Err(err) => skip(first_line + err.line() - 1, format!("line skipped: {}", error_message(&err))),
+        }
+    }
+
+    Some(DialogueFile {
+        lines,
+        cooldowns: raw.cooldowns,
+        min_gap: raw.min_gap,
+    })
 }
 
 /// Resource containing all loaded dialogues
@@ -38,9 +170,17 @@ pub struct DialogueDatabase {
     pub by_trigger: HashMap<String, Vec<DialogueLine>>,
     /// All lines indexed by ID
     pub by_id: HashMap<String, DialogueLine>,
+    /// Fewest seconds between two non-critical lines, if a file set it
+    pub min_gap: Option<f32>,
+    /// What was skipped while loading (shown in the inspector)
+    pub issues: Vec<DialogueIssue>,
 }
 
 impl DialogueDatabase {
+    pub fn min_gap(&self) -> f32 {
+        self.min_gap.unwrap_or(DEFAULT_MIN_GAP)
+    }
+
     /// Get a random line for a trigger
     pub fn get_for_trigger(&self, trigger: &str) -> Option<&DialogueLine> {
         self.by_trigger.get(trigger).and_then(|lines| {
@@ -71,31 +211,45 @@ impl DialogueDatabase {
     }
 }
 
-/// Load all dialogue files
-fn load_dialogues(mut dialogue_db: ResMut<DialogueDatabase>) {
-    let dialogue_files = [
-        "assets/dialogues/terry_generic.json",
-        "assets/dialogues/terry_cheap.json",
-        "assets/dialogues/terry_good.json",
-        "assets/dialogues/terry_expensive.json",
-        "assets/dialogues/terry_bad.json",
-    ];
+/// Every dialogue file the game reads, in load order
+fn dialogue_files(things: &ThingRegistry) -> Vec<String> {
+    // One file per Thing prefix; modded Things can share a built-in one
+    let mut dialogue_files = vec!["assets/dialogues/terry_generic.json".to_string()];
+    for thing_type in things.all() {
+        let path = format!("assets/dialogues/terry_{}.json", things[thing_type].dialogue_prefix);
+        if !dialogue_files.contains(&path) {
+            dialogue_files.push(path);
+        }
+    }
+    dialogue_files.push("assets/dialogues/terry_world_events.json".to_string());
+    dialogue_files
+}
 
-    for path_str in dialogue_files {
+/// Load all dialogue files (as the loading screen opens)
+pub fn load_dialogues(things: Res<ThingRegistry>, mut dialogue_db: ResMut<DialogueDatabase>) {
+    let mut cooldowns = HashMap::new();
+    let mut issues = Vec::new();
+    for path_str in &dialogue_files(&things) {
         let path = Path::new(path_str);
         if path.exists() {
             match fs::read_to_string(path) {
-                Ok(contents) => match serde_json::from_str::<DialogueFile>(&contents) {
-                    Ok(file) => {
+                Ok(contents) => {
+                    let skipped = issues.len();
+                    if let Some(file) = parse_dialogue_file(path_str, &contents, &mut issues) {
+                        let loaded = file.lines.len();
                         for line in file.lines {
                             dialogue_db.add_line(line);
                         }
-                        info!("Loaded dialogue file: {}", path_str);
+                        cooldowns.extend(file.cooldowns);
+                        if file.min_gap.is_some() {
+                            dialogue_db.min_gap = file.min_gap;
+                        }
+                        info!("Loaded dialogue file: {} ({} lines)", path_str, loaded);
                     }
-                    Err(e) => {
-                        warn!("Failed to parse dialogue file {}: {}", path_str, e);
+                    for issue in &issues[skipped..] {
+                        warn!("Dialogue: {}", issue);
                     }
-                },
+                }
                 Err(e) => {
                     warn!("Failed to read dialogue file {}: {}", path_str, e);
                 }
@@ -105,6 +259,18 @@ fn load_dialogues(mut dialogue_db: ResMut<DialogueDatabase>) {
             info!("Dialogue file not found (will use fallbacks): {}", path_str);
         }
     }
+    if !issues.is_empty() {
+        warn!("{} dialogue warnings while loading", issues.len());
+    }
+    dialogue_db.issues = issues;
+
+    // Lines without a cooldown of their own take their trigger's
+    let db = &mut *dialogue_db;
+    for line in db.by_trigger.values_mut().flatten().chain(db.by_id.values_mut()) {
+        if line.cooldown.is_none() {
+            line.cooldown = cooldowns.get(&line.trigger).copied();
+        }
+    }
 
     // If no files loaded, add some default fallback lines
     if dialogue_db.by_id.is_empty() {
@@ -121,18 +287,22 @@ fn add_fallback_lines(db: &mut DialogueDatabase) {
             trigger: "game_start".into(),
             text: "Welcome to Thing Simulator 2012! I'm Terry. Yes, I'm a hot dog. Yes, I have an MBA. Your mother asked me to help you with this.".into(),
             mood: "neutral".into(),
+            ..default()
         },
         DialogueLine {
             id: "generic_click".into(),
             trigger: "click".into(),
             text: "That's the spirit! Every Thing counts. Your mother would be proud.".into(),
             mood: "happy".into(),
+            ..default()
         },
         DialogueLine {
             id: "generic_idle".into(),
             trigger: "idle".into(),
             text: "You know what they say in business school? 'Time is money.' I learned that before they realized I was a hot dog.".into(),
             mood: "thoughtful".into(),
+            priority: DialoguePriority::Ambient,
+            ..default()
         },
         // Milestone lines
         DialogueLine {
@@ -140,18 +310,21 @@ fn add_fallback_lines(db: &mut DialogueDatabase) {
             trigger: "things_10".into(),
             text: "10 Things! That's what I call a proof of concept. Your mother will be thrilled.".into(),
             mood: "happy".into(),
+            ..default()
         },
         DialogueLine {
             id: "milestone_100".into(),
             trigger: "things_100".into(),
             text: "100 Things! We're really cooking now. Pun absolutely intended.".into(),
             mood: "excited".into(),
+            ..default()
         },
         DialogueLine {
             id: "milestone_1000".into(),
             trigger: "things_1000".into(),
             text: "1,000 Things! This is what we call 'scaling' in the business. I'm a scaling hot dog!".into(),
             mood: "excited".into(),
+            ..default()
         },
         // Cheap Thing lines
         DialogueLine {
@@ -159,12 +332,15 @@ fn add_fallback_lines(db: &mut DialogueDatabase) {
             trigger: "select_cheap".into(),
             text: "Cheap Things? Bold strategy. Volume is key. Your mother would approve - she loves a bargain.".into(),
             mood: "skeptical".into(),
+            ..default()
         },
         DialogueLine {
             id: "cheap_advice".into(),
             trigger: "cheap_playing".into(),
             text: "Remember: when selling cheap, it's all about turnover. Like a rotisserie. Like... never mind.".into(),
             mood: "helpful".into(),
+            priority: DialoguePriority::Ambient,
+            ..default()
         },
         // Good Thing lines
         DialogueLine {
@@ -172,12 +348,15 @@ fn add_fallback_lines(db: &mut DialogueDatabase) {
             trigger: "select_good".into(),
             text: "A Good Thing! Quality over quantity. Very noble. Very slow. But noble.".into(),
             mood: "approving".into(),
+            ..default()
         },
         DialogueLine {
             id: "good_advice".into(),
             trigger: "good_playing".into(),
             text: "Quality builds reputation. Reputation builds trust. Trust builds... the ability to charge more.".into(),
             mood: "wise".into(),
+            priority: DialoguePriority::Ambient,
+            ..default()
         },
         // Expensive Thing lines
         DialogueLine {
@@ -185,12 +364,15 @@ fn add_fallback_lines(db: &mut DialogueDatabase) {
             trigger: "select_expensive".into(),
             text: "Expensive Things! Luxury positioning. I learned about this at Wharton. Well, I read about Wharton. In a dumpster behind Wharton.".into(),
             mood: "impressed".into(),
+            ..default()
         },
         DialogueLine {
             id: "expensive_advice".into(),
             trigger: "expensive_playing".into(),
             text: "In the luxury market, scarcity creates value. Like hot dogs with business degrees.".into(),
             mood: "sophisticated".into(),
+            priority: DialoguePriority::Ambient,
+            ..default()
         },
         // Bad Thing lines
         DialogueLine {
@@ -198,18 +380,23 @@ fn add_fallback_lines(db: &mut DialogueDatabase) {
             trigger: "select_bad".into(),
             text: "Bad Things? Oh. Oh no. This is... this is exactly what my ethics professor warned me about. He was a bratwurst.".into(),
             mood: "concerned".into(),
+            ..default()
         },
         DialogueLine {
             id: "bad_advice".into(),
             trigger: "bad_playing".into(),
             text: "I'm not saying this is wrong, but I'm definitely taking notes for my parole hearing.".into(),
             mood: "nervous".into(),
+            priority: DialoguePriority::Ambient,
+            ..default()
         },
         DialogueLine {
             id: "bad_reputation_low".into(),
             trigger: "bad_low_rep".into(),
             text: "Our reputation is tanking. This is fine. Everything is fine. *sweats mustard*".into(),
             mood: "panicked".into(),
+            priority: DialoguePriority::Ambient,
+            ..default()
         },
     ];
 
@@ -219,3 +406,70 @@ fn add_fallback_lines(db: &mut DialogueDatabase) {
 
     info!("Loaded {} fallback dialogue lines", db.by_id.len());
 }
+
+/// Check every dialogue file and print what's wrong. Returns whether all were clean.
+pub fn check_dialogue_files() -> bool {
+    let mut issues = Vec::new();
+    let mut lines = 0;
+    for path in dialogue_files(&ThingRegistry::load()) {
+        match fs::read_to_string(&path) {
+            Ok(contents) => {
+                if let Some(file) = parse_dialogue_file(&path, &contents, &mut issues) {
+                    lines += file.lines.len();
+                }
+            }
+            Err(e) => println!("{}: not read ({})", path, e),
+        }
+    }
+
+    for issue in &issues {
+        println!("{}", issue);
+    }
+    println!("{} dialogue lines OK, {} warnings", lines, issues.len());
+    issues.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = r#"{
+  "cooldowns": { "click": 4.0 },
+  "lines": [
+    { "id": "good_one", "trigger": "click", "text": "Synergy!" },
+    {
+      "id": "bad_priority",
+      "trigger": "click",
+      "text": "Loud.",
+      "priority": "deafening"
+    },
+    { "id": "no_text", "trigger": "click", "text": "  " },
+    { "id": "good_two", "trigger": "idle", "text": "Still here.", "mood": "bored" }
+  ]
+}"#;
+
+    #[test]
+    fn malformed_lines_are_skipped_and_the_rest_load() {
+        let mut issues = Vec::new();
+        let file = parse_dialogue_file("test.json", FILE, &mut issues).expect("the file itself is fine");
+
+        let ids: Vec<&str> = file.lines.iter().map(|line| line.id.as_str()).collect();
+        assert_eq!(ids, ["good_one", "good_two"]);
+        assert_eq!(file.cooldowns.get("click"), Some(&4.0));
+
+        // Each issue points at the line it's about: the bad field, or the start of the entry
+        let lines: Vec<usize> = issues.iter().map(|issue| issue.line).collect();
+        assert_eq!(lines, [9, 11]);
+        assert!(issues[0].message.contains("deafening"), "{}", issues[0]);
+        assert!(issues[1].to_string().starts_with("test.json:11: line 'no_text' skipped"), "{}", issues[1]);
+    }
+
+    #[test]
+    fn broken_json_skips_the_file() {
+        let mut issues = Vec::new();
+        let broken = FILE.replace(r#""Synergy!" },"#, r#""Synergy!" }"#);
+        assert!(parse_dialogue_file("test.json", &broken, &mut issues).is_none());
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, 5);
+    }
+}
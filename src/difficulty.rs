@@ -0,0 +1,119 @@
+//! Difficulty - how hard the world is on the business
+//!
+//! Picked on the selection screen before a run (and remembered in the settings
+//! for the next one). [`DifficultyConfig`] spells out what the pick means: the
+//! money the business starts with, how hard the news and bad luck hit, how
+//! pushy the competition gets and how wildly the days swing. The starting
+//! money goes in the bank while the selection screen is up; the economy and
+//! the event systems read the rest. Normal leaves everything as it always was.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::currency::Money;
+use crate::game_state::{AppState, GameState};
+use crate::settings::Settings;
+
+/// How harsh the world is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, Reflect)]
+#[serde(rename_all = "snake_case")]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Brutal,
+}
+
+impl Difficulty {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Brutal => "Brutal",
+        }
+    }
+
+    /// What it's like, in plain terms
+    pub fn description(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "more cash, a gentler world",
+            Difficulty::Normal => "the world as it is",
+            Difficulty::Brutal => "less cash, harsher news, hungrier competitors",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Brutal,
+            Difficulty::Brutal => Difficulty::Easy,
+        }
+    }
+}
+
+/// What the run's difficulty does to the world (all neutral on Normal)
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource, Default)]
+pub struct DifficultyConfig {
+    pub difficulty: Difficulty,
+    /// Cash in the bank on day one
+    pub starting_money: Money,
+    /// Scales how hard historical events and bad luck (power outages) hit
+    pub event_severity: f32,
+    /// Scales how hard competitors push: their market pressure and price cuts
+    pub competitor_aggression: f32,
+    /// Scales how far daily chaos swings away from 1.0, on top of any fortune
+    pub chaos_variance: f32,
+}
+
+impl DifficultyConfig {
+    pub fn new(difficulty: Difficulty) -> Self {
+        match difficulty {
+            Difficulty::Easy => Self {
+                difficulty,
+                starting_money: Money::from_dollars(250.0),
+                event_severity: 0.5,
+                competitor_aggression: 0.75,
+                chaos_variance: 0.6,
+            },
+            Difficulty::Normal => Self {
+                difficulty,
+                starting_money: Money::from_dollars(100.0),
+                event_severity: 1.0,
+                competitor_aggression: 1.0,
+                chaos_variance: 1.0,
+            },
+            Difficulty::Brutal => Self {
+                difficulty,
+                starting_money: Money::from_dollars(25.0),
+                event_severity: 1.5,
+                competitor_aggression: 1.3,
+                chaos_variance: 1.5,
+            },
+        }
+    }
+}
+
+impl Default for DifficultyConfig {
+    fn default() -> Self {
+        Self::new(Difficulty::Normal)
+    }
+}
+
+pub struct DifficultyPlugin;
+
+impl Plugin for DifficultyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DifficultyConfig>()
+            .add_systems(Update, apply_difficulty.run_if(in_state(AppState::ThingSelection)));
+    }
+}
+
+/// Keep the run about to start in line with the difficulty picked
+fn apply_difficulty(settings: Res<Settings>, mut config: ResMut<DifficultyConfig>, mut game_state: ResMut<GameState>) {
+    if config.difficulty != settings.difficulty {
+        *config = DifficultyConfig::new(settings.difficulty);
+    }
+    if game_state.money != config.starting_money {
+        game_state.money = config.starting_money;
+    }
+}
@@ -0,0 +1,268 @@
+//! Environmental impact - what all those Things cost the planet
+//!
+//! Every Thing made adds to an impact score (how much depends on the kind of
+//! Thing), and nature slowly works it back down. A high score draws activist
+//! protests. Eco-certifications cut the impact of each Thing and win over an
+//! eco-conscious customer segment that pays a premium.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::accounting::ExpenseCategory;
+use crate::business::UpgradeState;
+use crate::economy::DayAdvancedEvent;
+use crate::game_state::{AppState, GameState, ReputationChangedEvent, ThingProducedEvent};
+use crate::thing_type::ThingRegistry;
+
+// === ECO BALANCE ===
+/// Impact added per Thing (before the Thing type's factor)
+const IMPACT_PER_THING: f32 = 0.05;
+/// Impact nature cleans up each day
+const DAILY_RECOVERY: f32 = 0.5;
+/// Extra daily cleanup per solar panel
+const SOLAR_RECOVERY: f32 = 0.1;
+/// Highest impact score
+pub const MAX_IMPACT: f32 = 100.0;
+/// Daily protest chance at maximum impact
+const MAX_PROTEST_CHANCE: f32 = 0.05;
+/// Impact below which activists have nothing to protest
+const PROTEST_THRESHOLD: f32 = 25.0;
+/// Reputation lost to a protest
+const PROTEST_REPUTATION: f32 = 0.2;
+/// Daily chance activists praise a certified, clean business
+const PRAISE_CHANCE: f32 = 0.02;
+/// Impact at or below which certified businesses get praised
+const PRAISE_THRESHOLD: f32 = 10.0;
+/// Reputation gained from activist praise
+const PRAISE_REPUTATION: f32 = 0.1;
+/// Revenue premium from the eco-conscious segment per certification (at zero impact)
+pub const ECO_SEGMENT_PREMIUM: f64 = 0.05;
+/// Quiet days after an activist event before the next
+const ACTIVIST_COOLDOWN_DAYS: u32 = 14;
+
+/// Eco-certifications, bought in order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Certification {
+    RecycledPackaging,
+    CarbonNeutral,
+    CradleToCradle,
+}
+
+impl Certification {
+    pub const ALL: [Certification; 3] = [
+        Certification::RecycledPackaging,
+        Certification::CarbonNeutral,
+        Certification::CradleToCradle,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Certification::RecycledPackaging => "Recycled Packaging",
+            Certification::CarbonNeutral => "Carbon Neutral",
+            Certification::CradleToCradle => "Cradle to Cradle",
+        }
+    }
+
+    pub fn cost(&self) -> f64 {
+        match self {
+            Certification::RecycledPackaging => 2_000.0,
+            Certification::CarbonNeutral => 10_000.0,
+            Certification::CradleToCradle => 40_000.0,
+        }
+    }
+
+    /// Share of each Thing's impact this certification removes
+    pub fn impact_reduction(&self) -> f32 {
+        match self {
+            Certification::RecycledPackaging => 0.2,
+            Certification::CarbonNeutral => 0.3,
+            Certification::CradleToCradle => 0.3,
+        }
+    }
+}
+
+/// How the planet is holding up, as words
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EcoRating {
+    Pristine,
+    Green,
+    Noticeable,
+    Smoggy,
+    Toxic,
+}
+
+impl EcoRating {
+    pub fn from_impact(impact: f32) -> Self {
+        match impact {
+            i if i < 10.0 => EcoRating::Pristine,
+            i if i < 25.0 => EcoRating::Green,
+            i if i < 50.0 => EcoRating::Noticeable,
+            i if i < 75.0 => EcoRating::Smoggy,
+            _ => EcoRating::Toxic,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EcoRating::Pristine => "Pristine",
+            EcoRating::Green => "Green",
+            EcoRating::Noticeable => "Noticeable",
+            EcoRating::Smoggy => "Smoggy",
+            EcoRating::Toxic => "Toxic",
+        }
+    }
+
+    /// How the story of this business ends, as far as the planet is concerned
+    pub fn epilogue(&self) -> &'static str {
+        match self {
+            EcoRating::Pristine => "Decades later, a small forest is named after Terry. Squirrels live there. They seem happy.",
+            EcoRating::Green => "The business is remembered fondly, and mostly for the right reasons.",
+            EcoRating::Noticeable => "Locals still find the odd Thing in the river. Nobody is sure whether to be angry.",
+            EcoRating::Smoggy => "On clear days you can almost see the old factory through the haze it left behind.",
+            EcoRating::Toxic => "The factory site is now a documentary. Terry is the villain. Terry has not watched it.",
+        }
+    }
+}
+
+/// The business's environmental record
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct EcoState {
+    /// 0 (untouched) to `MAX_IMPACT`
+    pub impact: f32,
+    /// Certifications bought so far (in `Certification::ALL` order)
+    pub certifications: usize,
+    /// Days since the last activist event
+    pub quiet_days: u32,
+}
+
+impl EcoState {
+    pub fn rating(&self) -> EcoRating {
+        EcoRating::from_impact(self.impact)
+    }
+
+    /// The next certification on offer, if any are left
+    pub fn next_certification(&self) -> Option<Certification> {
+        Certification::ALL.get(self.certifications).copied()
+    }
+
+    /// Share of each Thing's impact that still lands on the planet
+    pub fn impact_factor(&self) -> f32 {
+        let reduction: f32 = Certification::ALL[..self.certifications]
+            .iter()
+            .map(|c| c.impact_reduction())
+            .sum();
+        1.0 - reduction
+    }
+
+    /// Revenue multiplier from eco-conscious customers. They only buy from
+    /// certified businesses, and they pay less attention the dirtier it gets.
+    pub fn segment_multiplier(&self) -> f64 {
+        let cleanliness = 1.0 - (self.impact / MAX_IMPACT) as f64;
+        1.0 + ECO_SEGMENT_PREMIUM * self.certifications as f64 * cleanliness
+    }
+
+    /// Pay for the next certification. Returns false if there isn't one or it can't be afforded.
+    pub fn certify(&mut self, game_state: &mut GameState) -> bool {
+        let Some(certification) = self.next_certification() else {
+            return false;
+        };
+        if game_state.money < certification.cost() {
+            return false;
+        }
+        game_state.spend(ExpenseCategory::Eco, certification.cost());
+        self.certifications += 1;
+        true
+    }
+}
+
+/// What the activists showed up to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivistAction {
+    Protest,
+    Praise,
+}
+
+/// Message fired when activists turn up at the factory
+#[derive(Event, Message, Clone)]
+pub struct ActivistEvent {
+    pub action: ActivistAction,
+}
+
+pub struct EcoPlugin;
+
+impl Plugin for EcoPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EcoState>()
+            .add_message::<ActivistEvent>()
+            .add_systems(
+                Update,
+                (accumulate_impact, daily_eco)
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// Every Thing made leaves its mark
+fn accumulate_impact(
+    mut thing_events: MessageReader<ThingProducedEvent>,
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    mut eco: ResMut<EcoState>,
+) {
+    let Some(thing_type) = game_state.thing_type else {
+        return;
+    };
+
+    let made: u64 = thing_events.read().map(|e| e.amount).sum();
+    if made == 0 {
+        return;
+    }
+
+    let added = made as f32 * IMPACT_PER_THING * things[thing_type].eco_impact * eco.impact_factor();
+    eco.impact = (eco.impact + added).min(MAX_IMPACT);
+}
+
+/// Each game day: nature recovers a little, and activists may show up
+fn daily_eco(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    upgrades: Res<UpgradeState>,
+    mut eco: ResMut<EcoState>,
+    mut game_state: ResMut<GameState>,
+    mut activist_events: MessageWriter<ActivistEvent>,
+    mut rep_events: MessageWriter<ReputationChangedEvent>,
+) {
+    for _ in day_events.read() {
+        let recovery = DAILY_RECOVERY + upgrades.solar_panels as f32 * SOLAR_RECOVERY;
+        eco.impact = (eco.impact - recovery).max(0.0);
+
+        eco.quiet_days += 1;
+        if eco.quiet_days < ACTIVIST_COOLDOWN_DAYS {
+            continue;
+        }
+
+        let action = if eco.impact >= PROTEST_THRESHOLD {
+            let chance = MAX_PROTEST_CHANCE * eco.impact / MAX_IMPACT;
+            (rand::random::<f32>() < chance).then_some(ActivistAction::Protest)
+        } else if eco.certifications > 0 && eco.impact <= PRAISE_THRESHOLD {
+            (rand::random::<f32>() < PRAISE_CHANCE).then_some(ActivistAction::Praise)
+        } else {
+            None
+        };
+
+        let Some(action) = action else {
+            continue;
+        };
+
+        let change = match action {
+            ActivistAction::Protest => -PROTEST_REPUTATION,
+            ActivistAction::Praise => PRAISE_REPUTATION,
+        };
+        game_state.reputation = (game_state.reputation + change).clamp(0.0, 5.0);
+        rep_events.write(ReputationChangedEvent {
+            new_reputation: game_state.reputation,
+        });
+        eco.quiet_days = 0;
+        activist_events.write(ActivistEvent { action });
+    }
+}
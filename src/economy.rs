@@ -4,17 +4,65 @@
 //! The player can only control their own actions; the world moves on without them.
 
 use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use std::fmt;
+use crate::difficulty::DifficultyConfig;
+use crate::fortune::RunModifiers;
+use crate::game_clock::GameClock;
+use crate::game_state::game_running;
+use crate::macro_dynamics;
+
+/// Temperature (°F) at or below which it counts as snowing
+const FREEZING_TEMP: f32 = 32.0;
+/// Daily pull on competitor pressure per unit of competitor aggression above normal
+const COMPETITOR_AGGRESSION_DRIFT: f32 = 0.005;
+/// Temperature (°F) at or above which it counts as a heat wave
+const HEAT_WAVE_TEMP: f32 = 90.0;
+
+// Demand weights (also spelled out in Terry's economy encyclopedia)
+/// Days before Christmas the shopping rush builds over
+pub const CHRISTMAS_RUSH_DAYS: i32 = 30;
+/// Extra demand at the peak of the Christmas rush (on top of 1.0)
+pub const CHRISTMAS_RUSH_PEAK: f32 = 2.0;
+/// Demand on Saturdays and Sundays
+pub const WEEKEND_DEMAND: f32 = 1.3;
+/// Below this (°F) or above `TOO_HOT_TEMP` people stay home
+pub const TOO_COLD_TEMP: f32 = 20.0;
+pub const TOO_HOT_TEMP: f32 = 95.0;
+/// Demand when it's too cold or too hot to go out
+pub const EXTREME_WEATHER_DEMAND: f32 = 0.7;
+/// Temperatures (°F) nice enough to put people in a spending mood
+pub const NICE_WEATHER_TEMPS: (f32, f32) = (70.0, 80.0);
+/// Demand in nice weather
+pub const NICE_WEATHER_DEMAND: f32 = 1.1;
+/// Demand lost per unit of unemployment rate
+pub const UNEMPLOYMENT_WEIGHT: f32 = 0.5;
+/// Demand gained per unit of market sentiment
+pub const SENTIMENT_WEIGHT: f32 = 0.2;
+/// Demand lost per unit of competitor pressure
+pub const COMPETITOR_WEIGHT: f32 = 0.3;
+/// Demand lost per unit of market saturation
+pub const SATURATION_WEIGHT: f32 = 0.2;
+/// World population demand is measured against (2012)
+pub const BASELINE_POPULATION: f64 = 7_000_000_000.0;
+/// Demand never drops below this, whatever the factors say
+pub const DEMAND_FLOOR: f32 = 0.1;
+/// Furthest daily chaos swings from 1.0 at normal variance
+pub const CHAOS_SWING: f32 = 0.2;
 
 /// The current state of the world - most of this is invisible to the player
-#[derive(Resource)]
+#[derive(Resource, Reflect)]
+#[reflect(Resource, Default)]
 pub struct WorldState {
     // === TIME ===
     /// Current game date (starts Jan 1, 2012)
     pub date: GameDate,
     /// How many real seconds equal one game day
     pub time_scale: f32,
-    /// Accumulated time for day progression
+    /// Game time towards the next day (see `GameClock`)
     pub day_accumulator: f32,
+    /// Shifts the day-to-day weather and market swings (shared in challenge codes)
+    pub seed: u32,
 
     // === INVISIBLE ENVIRONMENTAL FACTORS ===
     /// Current temperature in Fahrenheit (affects consumer behavior)
@@ -37,13 +85,17 @@ pub struct WorldState {
     pub unemployment_rate: f32,
     /// Inflation rate (affects perceived value)
     pub inflation_rate: f32,
+    /// Inflation above target, averaged over the last year or so (erodes spending power, see `macro_dynamics`)
+    pub inflation_pressure: f32,
     /// Stock market sentiment (-1.0 to 1.0)
     pub market_sentiment: f32,
+    /// Central bank interest rate (what money in the bank earns in a year)
+    pub interest_rate: f32,
 
     // === INVISIBLE SOCIAL FACTORS ===
     /// Current "trend momentum" - how much Things are in vogue
     pub trend_factor: f32,
-    /// Viral coefficient - chance of word-of-mouth spread
+    /// Viral coefficient - share of the unaware hearing about Things from customers each day (set by word of mouth)
     pub viral_coefficient: f32,
     /// Media attention level (0.0 - 1.0)
     pub media_buzz: f32,
@@ -61,25 +113,101 @@ pub struct WorldState {
     pub is_weekend: bool,
     /// Is it a holiday? (various effects)
     pub current_holiday: Option<Holiday>,
+    /// Today's news, while a historical event is playing out
+    pub headline: Option<Headline>,
+    /// The time of year people are talking about, if any
+    pub seasonal_moment: Option<SeasonalMoment>,
+    /// When this winter's first snow fell (cleared each summer)
+    pub first_snow: Option<GameDate>,
     /// Day of week (0 = Sunday)
     pub day_of_week: u8,
 }
 
-#[derive(Clone, Copy, Debug)]
+/// A day on the (proleptic Gregorian) calendar
+///
+/// Stored as a count of days since 1970-01-01, so date arithmetic is plain
+/// integer arithmetic; the year, month and day are worked out from the count
+/// when asked for (Howard Hinnant's `civil_from_days`/`days_from_civil`).
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
 pub struct GameDate {
-    pub year: i32,
-    pub month: u8,  // 1-12
-    pub day: u8,    // 1-31
+    /// Days since 1970-01-01
+    days: i32,
 }
 
+/// Days in a 400-year cycle of the calendar
+const DAYS_PER_ERA: i32 = 146_097;
+/// Days from 0000-03-01 (the start of the calendar's eras) to 1970-01-01
+const EPOCH_SHIFT: i32 = 719_468;
+
 impl GameDate {
+    /// The date for a year, month (1-12) and day (1-31). A day past the end
+    /// of the month carries over into the next.
     pub fn new(year: i32, month: u8, day: u8) -> Self {
-        Self { year, month, day }
+        // Years run March to February, so leap days fall at the end
+        let (month, day) = (month as i32, day as i32);
+        let year = if month <= 2 { year - 1 } else { year };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let day_of_era_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_era_year;
+        Self {
+            days: era * DAYS_PER_ERA + day_of_era - EPOCH_SHIFT,
+        }
+    }
+
+    /// Year, month (1-12) and day (1-31)
+    fn civil(&self) -> (i32, u8, u8) {
+        let shifted = self.days + EPOCH_SHIFT;
+        let era = shifted.div_euclid(DAYS_PER_ERA);
+        let day_of_era = shifted - era * DAYS_PER_ERA;
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_era_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_of_era_year = (5 * day_of_era_year + 2) / 153;
+        let day = day_of_era_year - (153 * month_of_era_year + 2) / 5 + 1;
+        let month = if month_of_era_year < 10 { month_of_era_year + 3 } else { month_of_era_year - 9 };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        (year, month as u8, day as u8)
+    }
+
+    pub fn year(&self) -> i32 {
+        self.civil().0
+    }
+
+    /// Month, 1-12
+    pub fn month(&self) -> u8 {
+        self.civil().1
+    }
+
+    /// Day of the month, 1-31
+    pub fn day(&self) -> u8 {
+        self.civil().2
+    }
+
+    /// The date some days later (or earlier, for a negative count)
+    pub fn add_days(&self, days: i32) -> Self {
+        Self {
+            days: self.days + days,
+        }
+    }
+
+    /// Days from this date to another (negative if the other is earlier)
+    pub fn days_until(&self, other: GameDate) -> i32 {
+        other.days - self.days
+    }
+
+    /// First day of the following month
+    pub fn next_month(&self) -> Self {
+        let (year, month, _) = self.civil();
+        if month == 12 {
+            GameDate::new(year + 1, 1, 1)
+        } else {
+            GameDate::new(year, month + 1, 1)
+        }
     }
 
     /// Days in the current month
     pub fn days_in_month(&self) -> u8 {
-        match self.month {
+        match self.month() {
             1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
             4 | 6 | 9 | 11 => 30,
             2 => if self.is_leap_year() { 29 } else { 28 },
@@ -88,73 +216,136 @@ impl GameDate {
     }
 
     pub fn is_leap_year(&self) -> bool {
-        (self.year % 4 == 0 && self.year % 100 != 0) || (self.year % 400 == 0)
+        let year = self.year();
+        (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0)
     }
 
     /// Advance by one day
     pub fn advance(&mut self) {
-        self.day += 1;
-        if self.day > self.days_in_month() {
-            self.day = 1;
-            self.month += 1;
-            if self.month > 12 {
-                self.month = 1;
-                self.year += 1;
-            }
-        }
+        self.days += 1;
     }
 
     /// Day of year (1-366)
     pub fn day_of_year(&self) -> u16 {
-        let days_before_month: [u16; 12] = [0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334];
-        let mut day = days_before_month[(self.month - 1) as usize] + self.day as u16;
-        if self.month > 2 && self.is_leap_year() {
-            day += 1;
-        }
-        day
+        (GameDate::new(self.year(), 1, 1).days_until(*self) + 1) as u16
     }
 
-    /// Days until December 25th
+    /// Days until December 25th (0 on Christmas Day itself)
     pub fn days_until_christmas(&self) -> i32 {
-        let christmas_day = if self.month == 12 && self.day > 25 {
-            // After Christmas, count to next year
-            let days_left_in_year = if self.is_leap_year() { 366 } else { 365 } - self.day_of_year() as i32;
-            days_left_in_year + 359 // Jan 1 to Dec 25
+        let year = self.year();
+        let christmas = GameDate::new(year, 12, 25);
+        if *self <= christmas {
+            self.days_until(christmas)
         } else {
-            // Dec 25 is day 359 (or 360 in leap year)
-            let christmas_doy = if self.is_leap_year() && self.month <= 2 { 360 } else { 359 };
-            christmas_doy - self.day_of_year() as i32
-        };
-        christmas_day.max(0)
+            self.days_until(GameDate::new(year + 1, 12, 25))
+        }
+    }
+
+    /// Quarter of the year, 1-4
+    pub fn quarter(&self) -> u8 {
+        (self.month() - 1) / 3 + 1
     }
 
-    /// Calculate day of week (0 = Sunday, using Zeller's congruence)
+    /// Day of the week (0 = Sunday)
     pub fn day_of_week(&self) -> u8 {
-        let mut y = self.year;
-        let mut m = self.month as i32;
-        if m < 3 {
-            m += 12;
-            y -= 1;
+        // 1970-01-01 was a Thursday
+        (self.days + 4).rem_euclid(7) as u8
+    }
+
+    /// What holiday (if any) falls on this date
+    pub fn holiday(&self) -> Option<Holiday> {
+        let (_, m, d) = self.civil();
+        let day_of_week = self.day_of_week();
+
+        match (m, d) {
+            (1, 1) => Some(Holiday::NewYears),
+            (2, 14) => Some(Holiday::ValentinesDay),
+            (7, 4) => Some(Holiday::IndependenceDay),
+            (10, 31) => Some(Holiday::Halloween),
+            (12, 25) => Some(Holiday::Christmas),
+            (12, 31) => Some(Holiday::NewYearsEve),
+            // Approximate floating holidays
+            (2, 15..=21) if day_of_week == 1 => Some(Holiday::PresidentsDay), // 3rd Monday Feb
+            (5, 25..=31) if day_of_week == 1 => Some(Holiday::MemorialDay),   // Last Monday May
+            (9, 1..=7) if day_of_week == 1 => Some(Holiday::LaborDay),        // 1st Monday Sep
+            (11, 22..=28) if day_of_week == 4 => Some(Holiday::Thanksgiving), // 4th Thursday Nov
+            (11, 23..=29) if day_of_week == 5 => Some(Holiday::BlackFriday),  // Day after Thanksgiving
+            // Easter is complicated, skip for now
+            _ => None,
+        }
+    }
+
+    /// ISO-8601 date, e.g. 2012-01-31
+    pub fn iso(&self) -> String {
+        let (year, month, day) = self.civil();
+        format!("{:04}-{:02}-{:02}", year, month, day)
+    }
+
+    /// Full name of a month (1-12)
+    pub fn month_name(month: u8) -> &'static str {
+        match month {
+            1 => "January", 2 => "February", 3 => "March", 4 => "April",
+            5 => "May", 6 => "June", 7 => "July", 8 => "August",
+            9 => "September", 10 => "October", 11 => "November", 12 => "December",
+            _ => "???",
         }
-        let q = self.day as i32;
-        let k = y % 100;
-        let j = y / 100;
-        let h = (q + (13 * (m + 1)) / 5 + k + k / 4 + j / 4 - 2 * j) % 7;
-        ((h + 6) % 7) as u8 // Convert to Sunday = 0
+    }
+
+    /// Month and year, e.g. "January 2012"
+    pub fn month_label(&self) -> String {
+        format!("{} {}", Self::month_name(self.month()), self.year())
     }
 
     pub fn format(&self) -> String {
-        let month_name = match self.month {
+        let (year, month, day) = self.civil();
+        let month_name = match month {
             1 => "Jan", 2 => "Feb", 3 => "Mar", 4 => "Apr",
             5 => "May", 6 => "Jun", 7 => "Jul", 8 => "Aug",
             9 => "Sep", 10 => "Oct", 11 => "Nov", 12 => "Dec",
             _ => "???",
         };
-        format!("{} {}, {}", month_name, self.day, self.year)
+        format!("{} {}, {}", month_name, day, year)
+    }
+}
+
+impl fmt::Debug for GameDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "GameDate({})", self.iso())
+    }
+}
+
+/// Times of year that get people talking, from the calendar and the weather
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+pub enum SeasonalMoment {
+    /// The week after the first freezing day of the winter
+    FirstSnow,
+    HeatWave,
+    /// The last week before Christmas
+    ChristmasWeek,
+    /// The week leading up to April 15
+    TaxDay,
+}
+
+impl SeasonalMoment {
+    /// Dialogue trigger for Terry's commentary
+    pub fn trigger(&self) -> &'static str {
+        match self {
+            SeasonalMoment::FirstSnow => "season_first_snow",
+            SeasonalMoment::HeatWave => "season_heat_wave",
+            SeasonalMoment::ChristmasWeek => "season_christmas_week",
+            SeasonalMoment::TaxDay => "season_tax_day",
+        }
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// A historical event making the news; `id` keys dialogue and other reactions
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+pub struct Headline {
+    pub id: &'static str,
+    pub text: &'static str,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect)]
 pub enum Holiday {
     NewYears,
     ValentinesDay,
@@ -170,6 +361,56 @@ pub enum Holiday {
     NewYearsEve,
 }
 
+impl Holiday {
+    pub const ALL: [Holiday; 12] = [
+        Holiday::NewYears,
+        Holiday::ValentinesDay,
+        Holiday::PresidentsDay,
+        Holiday::Easter,
+        Holiday::MemorialDay,
+        Holiday::IndependenceDay,
+        Holiday::LaborDay,
+        Holiday::Halloween,
+        Holiday::Thanksgiving,
+        Holiday::BlackFriday,
+        Holiday::Christmas,
+        Holiday::NewYearsEve,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Holiday::NewYears => "New Year's Day",
+            Holiday::ValentinesDay => "Valentine's Day",
+            Holiday::PresidentsDay => "Presidents' Day",
+            Holiday::Easter => "Easter",
+            Holiday::MemorialDay => "Memorial Day",
+            Holiday::IndependenceDay => "Independence Day",
+            Holiday::LaborDay => "Labor Day",
+            Holiday::Halloween => "Halloween",
+            Holiday::Thanksgiving => "Thanksgiving",
+            Holiday::BlackFriday => "Black Friday",
+            Holiday::Christmas => "Christmas",
+            Holiday::NewYearsEve => "New Year's Eve",
+        }
+    }
+
+    /// Multiplier on demand for the day
+    pub fn demand_factor(&self) -> f32 {
+        match self {
+            Holiday::BlackFriday => 3.0,
+            Holiday::Christmas => 0.5,      // People are WITH family, not shopping
+            Holiday::NewYearsEve => 0.3,
+            Holiday::NewYears => 0.4,
+            Holiday::Thanksgiving => 0.6,
+            Holiday::ValentinesDay => 1.3,
+            Holiday::IndependenceDay => 0.8,
+            Holiday::Halloween => 1.2,
+            Holiday::LaborDay | Holiday::MemorialDay | Holiday::PresidentsDay => 1.4, // Sales!
+            Holiday::Easter => 0.7,
+        }
+    }
+}
+
 impl Default for WorldState {
     fn default() -> Self {
         Self {
@@ -177,6 +418,7 @@ impl Default for WorldState {
             date: GameDate::new(2012, 1, 1),
             time_scale: 1.0, // 1 real second = 1 game day
             day_accumulator: 0.0,
+            seed: 0,
 
             // January temperature (cold)
             temperature: 35.0,
@@ -191,7 +433,9 @@ impl Default for WorldState {
             consumer_confidence: 1.0,
             unemployment_rate: 0.08, // 8% (2012 was still recovering)
             inflation_rate: 0.02,    // 2%
+            inflation_pressure: 0.0,
             market_sentiment: 0.0,
+            interest_rate: 0.0025, // 0-0.25%, rates still on the floor
 
             // Social factors
             trend_factor: 1.0,
@@ -206,18 +450,45 @@ impl Default for WorldState {
             days_to_christmas: 359, // Will be calculated
             is_weekend: false,      // Jan 1, 2012 was a Sunday
             current_holiday: Some(Holiday::NewYears),
+            headline: None,
+            seasonal_moment: None,
+            first_snow: None,
             day_of_week: 0,
         }
     }
 }
 
 impl WorldState {
+    /// Seed for today's pseudo-random swings, from the date and the world seed
+    fn day_seed(&self) -> i32 {
+        self.date.year() * 10000 + self.date.month() as i32 * 100 + self.date.day() as i32 + self.seed as i32
+    }
+
+    /// Which seasonal moment today falls in, if any
+    fn calculate_seasonal_moment(&self) -> Option<SeasonalMoment> {
+        let since_first_snow = self
+            .first_snow
+            .map(|snow| (self.date.day_of_year() + 366 - snow.day_of_year()) % 366);
+
+        if (1..=7).contains(&self.days_to_christmas) {
+            Some(SeasonalMoment::ChristmasWeek)
+        } else if self.date.month() == 4 && (8..=15).contains(&self.date.day()) {
+            Some(SeasonalMoment::TaxDay)
+        } else if since_first_snow.is_some_and(|days| days < 7) {
+            Some(SeasonalMoment::FirstSnow)
+        } else if self.temperature >= HEAT_WAVE_TEMP {
+            Some(SeasonalMoment::HeatWave)
+        } else {
+            None
+        }
+    }
+
     /// Calculate seasonal base temperature based on month
     fn calculate_seasonal_temp(&self) -> f32 {
         // Northern hemisphere seasonal cycle
         // Coldest in January, warmest in July
-        let month = self.date.month as f32;
-        let day_of_month = self.date.day as f32;
+        let month = self.date.month() as f32;
+        let day_of_month = self.date.day() as f32;
 
         // Approximate day of year as continuous value
         let year_progress = (month - 1.0 + day_of_month / 30.0) / 12.0;
@@ -230,129 +501,201 @@ impl WorldState {
         57.5 - (temp_cycle * 27.5)
     }
 
-    /// Check what holiday (if any) is today
-    fn check_holiday(&self) -> Option<Holiday> {
-        let m = self.date.month;
-        let d = self.date.day;
-
-        match (m, d) {
-            (1, 1) => Some(Holiday::NewYears),
-            (2, 14) => Some(Holiday::ValentinesDay),
-            (7, 4) => Some(Holiday::IndependenceDay),
-            (10, 31) => Some(Holiday::Halloween),
-            (12, 25) => Some(Holiday::Christmas),
-            (12, 31) => Some(Holiday::NewYearsEve),
-            // Approximate floating holidays
-            (2, 15..=21) if self.day_of_week == 1 => Some(Holiday::PresidentsDay), // 3rd Monday Feb
-            (5, 25..=31) if self.day_of_week == 1 => Some(Holiday::MemorialDay),   // Last Monday May
-            (9, 1..=7) if self.day_of_week == 1 => Some(Holiday::LaborDay),        // 1st Monday Sep
-            (11, 22..=28) if self.day_of_week == 4 => Some(Holiday::Thanksgiving), // 4th Thursday Nov
-            (11, 23..=29) if self.day_of_week == 5 => Some(Holiday::BlackFriday),  // Day after Thanksgiving
-            // Easter is complicated, skip for now
-            _ => None,
-        }
-    }
-
     /// Get the combined demand modifier from all invisible factors
     pub fn calculate_demand_modifier(&self) -> f32 {
-        let mut modifier = 1.0;
+        let modifier: f32 = self.demand_factors().iter().map(|(_, factor)| factor).product();
+        modifier.max(DEMAND_FLOOR) // Never completely zero
+    }
+
+    /// Each invisible factor's multiplier on demand today, with a name the player could understand
+    pub fn demand_factors(&self) -> Vec<(&'static str, f32)> {
+        let mut factors = Vec::new();
 
         // Christmas effect (huge!)
         // Peaks in the weeks before Christmas
-        if self.days_to_christmas <= 30 && self.days_to_christmas > 0 {
-            let christmas_boost = 1.0 + (2.0 * (30 - self.days_to_christmas) as f32 / 30.0);
-            modifier *= christmas_boost;
+        if self.days_to_christmas <= CHRISTMAS_RUSH_DAYS && self.days_to_christmas > 0 {
+            let christmas_boost = 1.0
+                + CHRISTMAS_RUSH_PEAK * (CHRISTMAS_RUSH_DAYS - self.days_to_christmas) as f32 / CHRISTMAS_RUSH_DAYS as f32;
+            factors.push(("Christmas shopping", christmas_boost));
         }
 
         // Holiday effects
         if let Some(holiday) = &self.current_holiday {
-            modifier *= match holiday {
-                Holiday::BlackFriday => 3.0,
-                Holiday::Christmas => 0.5,      // People are WITH family, not shopping
-                Holiday::NewYearsEve => 0.3,
-                Holiday::NewYears => 0.4,
-                Holiday::Thanksgiving => 0.6,
-                Holiday::ValentinesDay => 1.3,
-                Holiday::IndependenceDay => 0.8,
-                Holiday::Halloween => 1.2,
-                Holiday::LaborDay | Holiday::MemorialDay | Holiday::PresidentsDay => 1.4, // Sales!
-                Holiday::Easter => 0.7,
-            };
+            factors.push(("The holiday", holiday.demand_factor()));
         }
 
         // Weekend effect
         if self.is_weekend {
-            modifier *= 1.3; // More shopping on weekends
+            factors.push(("Weekend shoppers", WEEKEND_DEMAND)); // More shopping on weekends
         }
 
         // Temperature effects
         // Extreme temps keep people home
-        if self.temperature < 20.0 || self.temperature > 95.0 {
-            modifier *= 0.7;
-        } else if self.temperature > 70.0 && self.temperature < 80.0 {
-            modifier *= 1.1; // Nice weather = good mood = more spending
+        let (nice_low, nice_high) = NICE_WEATHER_TEMPS;
+        if self.temperature < TOO_COLD_TEMP || self.temperature > TOO_HOT_TEMP {
+            factors.push(("The weather", EXTREME_WEATHER_DEMAND));
+        } else if self.temperature > nice_low && self.temperature < nice_high {
+            factors.push(("The weather", NICE_WEATHER_DEMAND)); // Nice weather = good mood = more spending
         }
 
         // Consumer confidence
-        modifier *= self.consumer_confidence;
+        factors.push(("Consumer confidence", self.consumer_confidence));
 
         // Unemployment drags spending
-        modifier *= 1.0 - (self.unemployment_rate * 0.5);
+        factors.push(("Unemployment", 1.0 - (self.unemployment_rate * UNEMPLOYMENT_WEIGHT)));
 
         // Market sentiment
-        modifier *= 1.0 + (self.market_sentiment * 0.2);
+        factors.push(("The stock market", 1.0 + (self.market_sentiment * SENTIMENT_WEIGHT)));
 
         // Trend factor
-        modifier *= self.trend_factor;
+        factors.push(("Trends", self.trend_factor));
 
         // Competitor pressure reduces your slice
-        modifier *= 1.0 - (self.competitor_pressure * 0.3);
+        factors.push(("Competitors", 1.0 - (self.competitor_pressure * COMPETITOR_WEIGHT)));
 
         // Market saturation makes it harder
-        modifier *= 1.0 - (self.market_saturation * 0.2);
+        factors.push(("Market saturation", 1.0 - (self.market_saturation * SATURATION_WEIGHT)));
 
         // Global population scales total addressable market
         // Normalize to 2012 baseline
-        let population_factor = (self.global_population / 7_000_000_000.0) as f32;
-        modifier *= population_factor;
+        let population_factor = (self.global_population / BASELINE_POPULATION) as f32;
+        factors.push(("Population", population_factor));
 
-        modifier.max(0.1) // Never completely zero
+        factors
     }
 
-    /// Get a "chaos factor" - random daily variance in the economy
-    pub fn daily_chaos(&self) -> f32 {
+    /// Get a "chaos factor" - random daily variance in the economy.
+    /// `variance` scales the swing (1.0 is normal, see `RunModifiers`).
+    pub fn daily_chaos(&self, variance: f32) -> f32 {
         // Pseudo-random based on date (deterministic but feels random)
-        let seed = self.date.year * 10000 + self.date.month as i32 * 100 + self.date.day as i32;
+        let seed = self.day_seed();
         let chaos = ((seed as f32 * 12.9898).sin() * 43758.5453).fract();
-        0.8 + (chaos * 0.4) // Range: 0.8 to 1.2
+        1.0 + (chaos * 2.0 * CHAOS_SWING - CHAOS_SWING) * variance // Range: 0.8 to 1.2 at normal variance
+    }
+}
+
+/// Message fired each time the calendar moves on a day, with what the new day is like
+#[derive(Event, Message, Clone)]
+pub struct DayAdvancedEvent {
+    pub date: GameDate,
+    pub holiday: Option<Holiday>,
+    pub is_weekend: bool,
+    /// The day's demand modifier, as `WorldState::calculate_demand_modifier`
+    pub demand_modifier: f32,
+}
+
+impl DayAdvancedEvent {
+    /// The world's current day
+    pub fn today(world: &WorldState) -> Self {
+        Self {
+            date: world.date,
+            holiday: world.current_holiday,
+            is_weekend: world.is_weekend,
+            demand_modifier: world.calculate_demand_modifier(),
+        }
     }
 }
 
+/// Message fired on Mondays, when a new week starts
+#[derive(Event, Message, Clone)]
+pub struct WeekStartedEvent {
+    pub date: GameDate,
+}
+
+/// Message fired on the first of each month
+#[derive(Event, Message, Clone)]
+pub struct MonthStartedEvent {
+    pub date: GameDate,
+}
+
+/// Message fired on the first of January, April, July and October
+#[derive(Event, Message, Clone)]
+pub struct QuarterStartedEvent {
+    pub date: GameDate,
+    /// 1-4
+    pub quarter: u8,
+}
+
+/// Message fired when a historical event first makes the news
+#[derive(Event, Message, Clone)]
+pub struct HeadlineEvent {
+    pub headline: Headline,
+}
+
 pub struct EconomyPlugin;
 
 impl Plugin for EconomyPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<WorldState>()
-            .add_systems(Update, advance_world_simulation);
+            .add_message::<DayAdvancedEvent>()
+            .add_message::<WeekStartedEvent>()
+            .add_message::<MonthStartedEvent>()
+            .add_message::<QuarterStartedEvent>()
+            .add_message::<HeadlineEvent>()
+            .add_systems(Update, advance_world_simulation.run_if(game_running));
+    }
+}
+
+/// The calendar's milestones, so schedules don't each keep track of the date
+///
+/// Every day gets a `DayAdvancedEvent`; Mondays, the first of the month and
+/// the first of a quarter also get theirs, in the same frame, for anything
+/// that happens weekly, monthly or quarterly.
+#[derive(SystemParam)]
+struct CalendarMessages<'w> {
+    days: MessageWriter<'w, DayAdvancedEvent>,
+    weeks: MessageWriter<'w, WeekStartedEvent>,
+    months: MessageWriter<'w, MonthStartedEvent>,
+    quarters: MessageWriter<'w, QuarterStartedEvent>,
+}
+
+impl CalendarMessages<'_> {
+    fn announce(&mut self, world: &WorldState) {
+        let date = world.date;
+        self.days.write(DayAdvancedEvent::today(world));
+        if date.day_of_week() == 1 {
+            self.weeks.write(WeekStartedEvent { date });
+        }
+        if date.day() == 1 {
+            self.months.write(MonthStartedEvent { date });
+            if date.month() % 3 == 1 {
+                self.quarters.write(QuarterStartedEvent {
+                    date,
+                    quarter: date.quarter(),
+                });
+            }
+        }
     }
 }
 
 /// Advances the world simulation each frame
 fn advance_world_simulation(
-    time: Res<Time>,
+    clock: Res<GameClock>,
+    modifiers: Res<RunModifiers>,
+    difficulty: Res<DifficultyConfig>,
     mut world: ResMut<WorldState>,
+    mut calendar: CalendarMessages,
+    mut headline_events: MessageWriter<HeadlineEvent>,
 ) {
-    // Accumulate time
-    world.day_accumulator += time.delta_secs();
-
-    // Advance days based on time scale
-    while world.day_accumulator >= world.time_scale {
-        world.day_accumulator -= world.time_scale;
-        advance_one_day(&mut world);
+    // Accumulate time (the clock already runs at the time scale, a day a second)
+    world.day_accumulator += clock.delta_secs();
+
+    while world.day_accumulator >= 1.0 {
+        world.day_accumulator -= 1.0;
+        let previous = world.headline.map(|headline| headline.id);
+        advance_one_day(&mut world, modifiers.chaos_variance, &difficulty);
+        calendar.announce(&world);
+
+        // Events spanning several days only make the news once
+        if let Some(headline) = world.headline.filter(|headline| Some(headline.id) != previous) {
+            headline_events.write(HeadlineEvent { headline });
+        }
     }
 }
 
-fn advance_one_day(world: &mut WorldState) {
+pub(crate) fn advance_one_day(world: &mut WorldState, chaos_variance: f32, difficulty: &DifficultyConfig) {
+    let chaos_variance = chaos_variance * difficulty.chaos_variance;
+
     // Advance the calendar
     world.date.advance();
 
@@ -364,89 +707,168 @@ fn advance_one_day(world: &mut WorldState) {
     world.days_to_christmas = world.date.days_until_christmas();
 
     // Update holiday
-    world.current_holiday = world.check_holiday();
+    world.current_holiday = world.date.holiday();
 
     // Update seasonal temperature
     world.seasonal_base_temp = world.calculate_seasonal_temp();
 
     // Add daily temperature variance (-10 to +10 degrees)
-    let temp_seed = world.date.year * 10000 + world.date.month as i32 * 100 + world.date.day as i32;
+    let temp_seed = world.day_seed();
     world.temp_variance = ((temp_seed as f32 * 78.233).sin() * 43758.5453).fract() * 20.0 - 10.0;
     world.temperature = world.seasonal_base_temp + world.temp_variance;
 
+    // Note the first freeze of the winter; summer resets it
+    if world.date.month() == 7 {
+        world.first_snow = None;
+    } else if world.first_snow.is_none() && world.temperature <= FREEZING_TEMP {
+        world.first_snow = Some(world.date);
+    }
+    world.seasonal_moment = world.calculate_seasonal_moment();
+
     // Grow population
     world.global_population *= world.population_growth_rate;
 
     // Apply historical events BEFORE random drift
-    apply_historical_events(world);
+    apply_historical_events(world, chaos_variance, difficulty.event_severity);
+    world.interest_rate = benchmark_rate(world.date, world.inflation_rate);
 
-    // Drift economic indicators slightly (random walk)
+    // Drift economic indicators slightly (random walk). Every drift here takes rem_euclid
+    // rather than fract, which goes negative with the sine and would drag the walk down
+    // every other day
     let econ_seed = temp_seed + 1;
-    let drift = ((econ_seed as f32 * 45.164).sin() * 43758.5453).fract() * 0.02 - 0.01;
+    let drift = ((econ_seed as f32 * 45.164).sin() * 43758.5453).rem_euclid(1.0) * 0.02 - 0.01;
 
     world.consumer_confidence = (world.consumer_confidence + drift).clamp(0.5, 1.5);
     world.market_sentiment = (world.market_sentiment + drift * 2.0).clamp(-0.5, 0.5);
 
     // Trend factor drifts more dramatically
     let trend_seed = temp_seed + 2;
-    let trend_drift = ((trend_seed as f32 * 93.989).sin() * 43758.5453).fract() * 0.1 - 0.05;
+    let trend_drift = ((trend_seed as f32 * 93.989).sin() * 43758.5453).rem_euclid(1.0) * 0.1 - 0.05;
     world.trend_factor = (world.trend_factor + trend_drift).clamp(0.5, 2.0);
 
     // Competitor pressure ebbs and flows
     let comp_seed = temp_seed + 3;
-    let comp_drift = ((comp_seed as f32 * 12.345).sin() * 43758.5453).fract() * 0.05 - 0.025;
+    let comp_drift = ((comp_seed as f32 * 12.345).sin() * 43758.5453).rem_euclid(1.0) * 0.05 - 0.025
+        + (difficulty.competitor_aggression - 1.0) * COMPETITOR_AGGRESSION_DRIFT;
     world.competitor_pressure = (world.competitor_pressure + comp_drift).clamp(0.2, 0.8);
+
+    // Let the indicators pull on each other
+    macro_dynamics::couple(world);
 }
 
-/// Historical events from 2012-2026 that affect the economy
-/// These are invisible to the player but shape the world
-fn apply_historical_events(world: &mut WorldState) {
-    let y = world.date.year;
-    let m = world.date.month;
-    let d = world.date.day;
+/// The central bank's rate on a date (top of the Fed's target range, 2012-2026)
+fn benchmark_rate(date: GameDate, inflation_rate: f32) -> f32 {
+    match (date.year(), date.month()) {
+        // Zero lower bound after the financial crisis
+        (..=2014, _) | (2015, 1..=11) => 0.0025,
+        // Liftoff - December 2015, then slowly up
+        (2015, 12) | (2016, 1..=11) => 0.005,
+        (2016, 12) | (2017, 1..=2) => 0.0075,
+        (2017, 3..=5) => 0.01,
+        (2017, 6..=11) => 0.0125,
+        (2017, 12) | (2018, 1..=2) => 0.015,
+        (2018, 3..=5) => 0.0175,
+        (2018, 6..=8) => 0.02,
+        (2018, 9..=11) => 0.0225,
+        (2018, 12) | (2019, 1..=6) => 0.025,
+        // Insurance cuts - 2019
+        (2019, 7..=8) => 0.0225,
+        (2019, 9) => 0.02,
+        (2019, 10..) | (2020, 1..=2) => 0.0175,
+        // Emergency cut to zero - March 2020
+        (2020, 3..) | (2021, _) | (2022, 1..=2) => 0.0025,
+        // Fighting inflation - fastest hikes in decades
+        (2022, 3..=4) => 0.005,
+        (2022, 5) => 0.01,
+        (2022, 6) => 0.0175,
+        (2022, 7..=8) => 0.025,
+        (2022, 9..=10) => 0.0325,
+        (2022, 11) => 0.04,
+        (2022, 12) | (2023, 1) => 0.045,
+        (2023, 2) => 0.0475,
+        (2023, 3..=4) => 0.05,
+        (2023, 5..=6) => 0.0525,
+        (2023, 7..) | (2024, 1..=8) => 0.055,
+        // Cutting again - late 2024
+        (2024, 9..=10) => 0.05,
+        (2024, 11) => 0.0475,
+        (2024, 12) | (2025, 1..=8) => 0.045,
+        (2025, 9) => 0.0425,
+        (2025, 10) => 0.04,
+        (2025, 11..) | (2026, 1..=2) => 0.0375,
+        // Beyond the known timeline, rates chase inflation
+        _ => (inflation_rate + 0.015).clamp(0.0025, 0.08),
+    }
+}
 
-    match (y, m, d) {
+fn news(id: &'static str, text: &'static str) -> Option<Headline> {
+    Some(Headline { id, text })
+}
+
+/// Historical events from 2012-2026 that affect the economy
+/// These are invisible to the player but shape the world; `severity` scales how hard they hit
+fn apply_historical_events(world: &mut WorldState, chaos_variance: f32, severity: f32) {
+    let before = (
+        world.consumer_confidence,
+        world.market_sentiment,
+        world.trend_factor,
+        world.unemployment_rate,
+        world.inflation_rate,
+    );
+    let y = world.date.year();
+    let m = world.date.month();
+    let d = world.date.day();
+
+    world.headline = match (y, m, d) {
         // === 2012 ===
         // Obama re-elected - November 6, 2012 (must come before Sandy range)
         (2012, 11, 6) => {
             world.market_sentiment += 0.05;
+            news("obama_reelected", "Obama re-elected")
         }
         // Hurricane Sandy - late October 2012
         (2012, 10, 29..=31) | (2012, 11, 1..=7) => {
             world.consumer_confidence *= 0.85;
             world.market_sentiment -= 0.1;
+            news("hurricane_sandy", "Hurricane Sandy hits the East Coast")
         }
 
         // === 2013 ===
         // Boston Marathon bombing - April 15, 2013
         (2013, 4, 15..=22) => {
             world.consumer_confidence *= 0.92;
+            news("boston_marathon", "Bombing at the Boston Marathon")
         }
         // Government shutdown - October 2013
         (2013, 10, 1..=16) => {
             world.consumer_confidence *= 0.9;
             world.market_sentiment -= 0.15;
+            news("shutdown_2013", "US government shuts down")
         }
 
         // === 2014 ===
         // Russia annexes Crimea - March 2014
         (2014, 3, 18..=31) => {
             world.market_sentiment -= 0.1;
+            news("crimea", "Russia annexes Crimea")
         }
         // Ferguson protests - August 2014
         (2014, 8, 9..=31) => {
             world.consumer_confidence *= 0.95;
+            news("ferguson", "Protests in Ferguson, Missouri")
         }
 
         // === 2015 ===
         // Same-sex marriage legalized - June 26, 2015
         (2015, 6, 26..=30) => {
             world.trend_factor *= 1.05;
+            news("marriage_equality", "Supreme Court legalizes same-sex marriage nationwide")
         }
         // Paris attacks - November 13, 2015
         (2015, 11, 13..=20) => {
             world.consumer_confidence *= 0.9;
             world.market_sentiment -= 0.1;
+            news("paris_attacks", "Terror attacks in Paris")
         }
 
         // === 2016 ===
@@ -454,55 +876,66 @@ fn apply_historical_events(world: &mut WorldState) {
         (2016, 6, 23..=30) => {
             world.market_sentiment -= 0.2;
             world.consumer_confidence *= 0.92;
+            news("brexit", "Britain votes to leave the EU")
         }
         // Trump elected - November 8, 2016
         (2016, 11, 8..=15) => {
             world.market_sentiment += 0.1; // Markets initially rallied
             world.trend_factor *= 1.1;
+            news("trump_elected", "Trump wins the presidency")
         }
 
         // === 2017 ===
         // Trump inaugurated - January 20, 2017
         (2017, 1, 20) => {
             world.trend_factor *= 1.05;
+            news("trump_inaugurated", "Trump inaugurated")
         }
         // Hurricane Harvey - late August 2017
         (2017, 8, 25..=31) | (2017, 9, 1..=5) => {
             world.consumer_confidence *= 0.88;
+            news("hurricane_harvey", "Hurricane Harvey floods Texas")
         }
         // Hurricane Maria - September 2017
         (2017, 9, 20..=30) => {
             world.consumer_confidence *= 0.9;
+            news("hurricane_maria", "Hurricane Maria devastates Puerto Rico")
         }
         // Bitcoin mania peaks - December 2017
         (2017, 12, 1..=20) => {
             world.trend_factor *= 1.15;
             world.market_sentiment += 0.1;
+            news("bitcoin_mania", "Bitcoin closes in on $20,000")
         }
 
         // === 2018 ===
         // Bitcoin crash - January-February 2018
         (2018, 1, 15..=31) | (2018, 2, 1..=10) => {
             world.market_sentiment -= 0.15;
+            news("bitcoin_crash", "Bitcoin crashes")
         }
         // Trade war begins - March 2018
         (2018, 3, 22..=31) | (2018, 4, 1..=15) => {
             world.market_sentiment -= 0.1;
             world.consumer_confidence *= 0.95;
+            news("trade_war", "US-China trade war begins")
         }
         // Midterms - Democrats take House - November 6, 2018
         (2018, 11, 6..=10) => {
             world.market_sentiment -= 0.05;
+            news("midterms_2018", "Democrats take the House")
         }
 
         // === 2019 ===
         // Government shutdown ends - January 2019 (longest ever)
         (2019, 1, 1..=25) => {
             world.consumer_confidence *= 0.92;
+            news("shutdown_2019", "Longest government shutdown in US history")
         }
         // Trump impeachment vote - December 18, 2019
         (2019, 12, 18..=31) => {
             world.market_sentiment -= 0.05;
+            news("impeachment_2019", "House votes to impeach Trump")
         }
 
         // === 2020 - THE BIG ONE ===
@@ -511,30 +944,36 @@ fn apply_historical_events(world: &mut WorldState) {
             world.consumer_confidence *= 0.6;
             world.market_sentiment -= 0.4;
             world.unemployment_rate = 0.15; // Massive spike
+            news("covid", "WHO declares COVID-19 a pandemic")
         }
         // George Floyd protests - May 25 onward, 2020 (must come before general May)
         (2020, 5, 25..=31) | (2020, 6, 1..=15) => {
             world.consumer_confidence = 0.5;
             world.consumer_confidence *= 0.85;
+            news("george_floyd", "Protests after the killing of George Floyd")
         }
         // COVID lockdowns continue - April-May 2020
         (2020, 4, _) | (2020, 5, 1..=24) => {
             world.consumer_confidence = 0.5;
             world.unemployment_rate = 0.14;
+            news("covid_lockdowns", "Lockdowns drag on")
         }
         // Slow recovery - Summer 2020
         (2020, 6, 16..=30) | (2020, 7, _) | (2020, 8, _) => {
             world.consumer_confidence = 0.7;
             world.unemployment_rate = 0.11;
+            news("covid_reopening", "The economy starts to reopen")
         }
         // Biden elected - November 3, 2020
         (2020, 11, 3..=10) => {
             world.market_sentiment += 0.15;
+            news("biden_elected", "Biden wins the presidency")
         }
         // Vaccine approved - December 2020
         (2020, 12, 11..=31) => {
             world.consumer_confidence *= 1.1;
             world.market_sentiment += 0.2;
+            news("vaccine", "First COVID vaccines approved")
         }
 
         // === 2021 ===
@@ -542,30 +981,36 @@ fn apply_historical_events(world: &mut WorldState) {
         (2021, 1, 6..=10) => {
             world.consumer_confidence *= 0.9;
             world.market_sentiment -= 0.1;
+            news("capitol_riot", "Rioters storm the US Capitol")
         }
         // Biden inaugurated - January 20, 2021
         (2021, 1, 20) => {
             world.market_sentiment += 0.05;
+            news("biden_inaugurated", "Biden inaugurated")
         }
         // Stimulus checks - March 2021
         (2021, 3, 12..=31) => {
             world.consumer_confidence *= 1.15;
             world.trend_factor *= 1.1;
+            news("stimulus", "Stimulus checks land")
         }
         // Meme stock mania - January 2021
         (2021, 1, 25..=31) => {
             world.trend_factor *= 1.2;
             world.market_sentiment += 0.15;
+            news("meme_stocks", "GameStop shares go to the moon")
         }
         // Recovery continues through 2021
         (2021, 4, _) | (2021, 5, _) | (2021, 6, _) => {
             world.unemployment_rate = 0.06;
             world.consumer_confidence = 1.1;
+            news("recovery_2021", "Jobs come roaring back")
         }
         // Inflation worries begin - late 2021
         (2021, 10, _) | (2021, 11, _) | (2021, 12, _) => {
             world.inflation_rate = 0.07;
             world.consumer_confidence *= 0.95;
+            news("inflation_2021", "Inflation worries grow")
         }
 
         // === 2022 ===
@@ -574,19 +1019,23 @@ fn apply_historical_events(world: &mut WorldState) {
             world.consumer_confidence *= 0.85;
             world.market_sentiment -= 0.2;
             world.inflation_rate = 0.085;
+            news("ukraine", "Russia invades Ukraine")
         }
         // Inflation peaks - June 2022 (9.1%)
         (2022, 6, _) | (2022, 7, _) => {
             world.inflation_rate = 0.091;
             world.consumer_confidence *= 0.9;
+            news("inflation_peak", "Inflation peaks at 9.1%")
         }
         // Queen Elizabeth II dies - September 8, 2022
         (2022, 9, 8..=19) => {
             world.trend_factor *= 0.95; // Somber mood
+            news("queen_dies", "Queen Elizabeth II dies")
         }
         // Midterms - November 2022
         (2022, 11, 8..=12) => {
             world.market_sentiment += 0.05;
+            news("midterms_2022", "Midterm elections")
         }
 
         // === 2023 ===
@@ -594,37 +1043,44 @@ fn apply_historical_events(world: &mut WorldState) {
         (2023, 3, 10..=20) => {
             world.market_sentiment -= 0.25;
             world.consumer_confidence *= 0.85;
+            news("svb", "Silicon Valley Bank collapses")
         }
         // AI boom (ChatGPT mania) - throughout 2023
         (2023, 1, _) | (2023, 2, _) | (2023, 3, _) | (2023, 4, _) | (2023, 5, _) => {
             world.trend_factor *= 1.05;
+            news("chatgpt", "ChatGPT mania grips the markets")
         }
         // Inflation cooling - late 2023
         (2023, 10, _) | (2023, 11, _) | (2023, 12, _) => {
             world.inflation_rate = 0.035;
             world.consumer_confidence *= 1.05;
+            news("inflation_cooling", "Inflation cools")
         }
 
         // === 2024 ===
         // Election year uncertainty - most of 2024
         (2024, 6, _) | (2024, 7, _) | (2024, 8, _) | (2024, 9, _) | (2024, 10, _) => {
             world.market_sentiment -= 0.05;
+            news("election_2024", "Election season heats up")
         }
         // Trump wins election - November 5, 2024
         (2024, 11, 5..=12) => {
             world.market_sentiment += 0.15;
             world.trend_factor *= 1.1;
+            news("trump_wins_2024", "Trump wins a second term")
         }
 
         // === 2025 ===
         // Trump inaugurated again - January 20, 2025
         (2025, 1, 20) => {
             world.trend_factor *= 1.05;
+            news("trump_inaugurated_2025", "Trump inaugurated again")
         }
         // Tariff announcements begin - early 2025
         (2025, 2, _) | (2025, 3, _) => {
             world.market_sentiment -= 0.1;
             world.consumer_confidence *= 0.95;
+            news("tariffs", "New tariffs announced")
         }
 
         // === 2026 ===
@@ -632,12 +1088,21 @@ fn apply_historical_events(world: &mut WorldState) {
         // The game catches up to "now" - things get weird
         (2026, 2, 19..) | (2026, 3.., _) => {
             // Beyond the known timeline - maximum chaos
-            world.trend_factor *= 1.0 + (world.daily_chaos() - 1.0) * 2.0;
+            world.trend_factor *= 1.0 + (world.daily_chaos(chaos_variance) - 1.0) * 2.0;
+            news("the_present", "The news catches up with today")
         }
 
         // Default - no special event
-        _ => {}
-    }
+        _ => None,
+    };
+
+    // Scale how far the news moved things
+    let scale = |before: f32, after: f32| before + (after - before) * severity;
+    world.consumer_confidence = scale(before.0, world.consumer_confidence);
+    world.market_sentiment = scale(before.1, world.market_sentiment);
+    world.trend_factor = scale(before.2, world.trend_factor);
+    world.unemployment_rate = scale(before.3, world.unemployment_rate);
+    world.inflation_rate = scale(before.4, world.inflation_rate);
 
     // Clamp values after historical adjustments
     world.consumer_confidence = world.consumer_confidence.clamp(0.3, 1.8);
@@ -645,3 +1110,251 @@ fn apply_historical_events(world: &mut WorldState) {
     world.unemployment_rate = world.unemployment_rate.clamp(0.03, 0.25);
     world.inflation_rate = world.inflation_rate.clamp(0.01, 0.15);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use crate::difficulty::Difficulty;
+    use rand::{RngExt, SeedableRng};
+
+    const TRIALS: usize = 2_000;
+
+    /// Reference calendar, worked out the long way from month lengths
+    fn reference_month_len(year: i32, month: u8) -> u8 {
+        let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        [31, if leap { 29 } else { 28 }, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31][month as usize - 1]
+    }
+
+    fn reference_year_len(year: i32) -> i64 {
+        (1..=12).map(|month| reference_month_len(year, month) as i64).sum()
+    }
+
+    /// Reference calendar: days since 1970-01-01
+    fn reference_days(year: i32, month: u8, day: u8) -> i64 {
+        let years: i64 = if year >= 1970 {
+            (1970..year).map(reference_year_len).sum()
+        } else {
+            -(year..1970).map(reference_year_len).sum::<i64>()
+        };
+        let months: i64 = (1..month).map(|m| reference_month_len(year, m) as i64).sum();
+        years + months + day as i64 - 1
+    }
+
+    /// Reference calendar: the day after
+    fn reference_next_day((year, month, day): (i32, u8, u8)) -> (i32, u8, u8) {
+        if day < reference_month_len(year, month) {
+            (year, month, day + 1)
+        } else if month < 12 {
+            (year, month + 1, 1)
+        } else {
+            (year + 1, 1, 1)
+        }
+    }
+
+    fn parts(date: GameDate) -> (i32, u8, u8) {
+        (date.year(), date.month(), date.day())
+    }
+
+    fn random_ymd(rng: &mut StdRng) -> (i32, u8, u8) {
+        let (year, month) = (rng.random_range(1600..2400), rng.random_range(1..=12));
+        (year, month, rng.random_range(1..=reference_month_len(year, month)))
+    }
+
+    fn random_date(rng: &mut StdRng) -> GameDate {
+        let (year, month, day) = random_ymd(rng);
+        GameDate::new(year, month, day)
+    }
+
+    /// The days around every leap day and year end from 1896 to 2104 (1900 and 2100 aren't leap years, 2000 is)
+    fn edge_dates() -> impl Iterator<Item = GameDate> {
+        (1896..=2104).flat_map(|year| {
+            [(2, 27), (2, 28), (2, 29), (3, 1), (12, 24), (12, 25), (12, 26), (12, 31), (1, 1)]
+                .into_iter()
+                .filter(move |(month, day)| *day <= reference_month_len(year, *month))
+                .map(move |(month, day)| GameDate::new(year, month, day))
+        })
+    }
+
+    #[test]
+    fn dates_match_the_reference_calendar() {
+        let epoch = GameDate::new(1970, 1, 1);
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..TRIALS {
+            let (year, month, day) = random_ymd(&mut rng);
+            let date = GameDate::new(year, month, day);
+            assert_eq!(parts(date), (year, month, day));
+            assert_eq!(epoch.days_until(date) as i64, reference_days(year, month, day), "{:?}", date);
+            assert_eq!(date.days_in_month(), reference_month_len(year, month), "{:?}", date);
+        }
+        assert_eq!(epoch.days_until(GameDate::new(2012, 1, 1)), 15_340);
+        assert_eq!(GameDate::new(2012, 1, 1).iso(), "2012-01-01");
+        assert_eq!(GameDate::new(2012, 1, 1).format(), "Jan 1, 2012");
+    }
+
+    #[test]
+    fn advancing_matches_the_reference_calendar() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for _ in 0..TRIALS {
+            let start = random_date(&mut rng);
+            let days = rng.random_range(1..800);
+            let mut date = start;
+            let mut expected = parts(start);
+            for _ in 0..days {
+                date.advance();
+                expected = reference_next_day(expected);
+            }
+            assert_eq!(parts(date), expected, "{:?} + {} days", start, days);
+            assert_eq!(start.add_days(days), date, "{:?} + {} days", start, days);
+            assert_eq!(date.add_days(-days), start, "{:?} - {} days", date, days);
+            assert_eq!(start.days_until(date), days);
+            assert_eq!(date.days_until(start), -days);
+        }
+        for date in edge_dates() {
+            assert_eq!(parts(date.add_days(1)), reference_next_day(parts(date)), "day after {:?}", date);
+        }
+    }
+
+    #[test]
+    fn next_month_starts_the_following_month() {
+        let mut rng = StdRng::seed_from_u64(8);
+        for date in (0..TRIALS).map(|_| random_date(&mut rng)).chain(edge_dates()) {
+            let next = date.next_month();
+            let expected = if date.month() == 12 { (date.year() + 1, 1, 1) } else { (date.year(), date.month() + 1, 1) };
+            assert_eq!(parts(next), expected, "{:?}", date);
+            assert_eq!(date.add_days(date.days_in_month() as i32 - date.day() as i32 + 1), next, "{:?}", date);
+        }
+    }
+
+    #[test]
+    fn day_of_week_matches_the_reference_calendar() {
+        let mut rng = StdRng::seed_from_u64(3);
+        // 1970-01-01 was a Thursday
+        let reference = |date: GameDate| (reference_days(date.year(), date.month(), date.day()) + 4).rem_euclid(7) as u8;
+        for date in (0..TRIALS).map(|_| random_date(&mut rng)).chain(edge_dates()) {
+            assert_eq!(date.day_of_week(), reference(date), "{:?}", date);
+        }
+        // The game starts on a Sunday
+        assert_eq!(GameDate::new(2012, 1, 1).day_of_week(), 0);
+    }
+
+    #[test]
+    fn day_of_year_matches_the_reference_calendar() {
+        let mut rng = StdRng::seed_from_u64(4);
+        for date in (0..TRIALS).map(|_| random_date(&mut rng)).chain(edge_dates()) {
+            let (year, month, day) = parts(date);
+            let expected = reference_days(year, month, day) - reference_days(year, 1, 1) + 1;
+            assert_eq!(date.day_of_year() as i64, expected, "{:?}", date);
+        }
+    }
+
+    #[test]
+    fn days_until_christmas_matches_the_reference_calendar() {
+        let mut rng = StdRng::seed_from_u64(5);
+        for date in (0..TRIALS).map(|_| random_date(&mut rng)).chain(edge_dates()) {
+            let (year, month, day) = parts(date);
+            let today = reference_days(year, month, day);
+            let this_year = reference_days(year, 12, 25);
+            let christmas = if today <= this_year { this_year } else { reference_days(year + 1, 12, 25) };
+            assert_eq!(date.days_until_christmas() as i64, christmas - today, "{:?}", date);
+        }
+    }
+
+    #[test]
+    fn counting_down_to_christmas_goes_down_a_day_at_a_time() {
+        let mut date = GameDate::new(2011, 1, 1);
+        let mut previous = date.days_until_christmas();
+        while date < GameDate::new(2030, 1, 1) {
+            date.advance();
+            let days = date.days_until_christmas();
+            if previous == 0 {
+                assert!((364..=365).contains(&days), "{:?}: {} days after Christmas", date, days);
+            } else {
+                assert_eq!(days, previous - 1, "{:?}", date);
+            }
+            previous = days;
+        }
+    }
+
+    const HOLIDAYS: [Holiday; 12] = [
+        Holiday::NewYears,
+        Holiday::ValentinesDay,
+        Holiday::PresidentsDay,
+        Holiday::Easter,
+        Holiday::MemorialDay,
+        Holiday::IndependenceDay,
+        Holiday::LaborDay,
+        Holiday::Halloween,
+        Holiday::Thanksgiving,
+        Holiday::BlackFriday,
+        Holiday::Christmas,
+        Holiday::NewYearsEve,
+    ];
+
+    /// Highest the demand modifier can go with every indicator in range: the
+    /// best of each factor (Christmas rush, Black Friday, a weekend, nice
+    /// weather, confidence, sentiment, trends, light competition, 10 billion people)
+    const MAX_DEMAND: f32 = 3.0 * 3.0 * 1.3 * 1.1 * 1.8 * 1.16 * 2.0 * 0.94 * (10.0 / 7.0);
+
+    /// A world with every indicator somewhere in the range the simulation keeps it in
+    fn random_world(rng: &mut StdRng) -> WorldState {
+        WorldState {
+            days_to_christmas: rng.random_range(0..366),
+            current_holiday: rng.random_bool(0.3).then(|| HOLIDAYS[rng.random_range(0..HOLIDAYS.len())]),
+            is_weekend: rng.random_bool(2.0 / 7.0),
+            temperature: rng.random_range(-20.0..120.0),
+            consumer_confidence: rng.random_range(0.3..=1.8),
+            unemployment_rate: rng.random_range(0.03..=0.25),
+            market_sentiment: rng.random_range(-0.8..=0.8),
+            trend_factor: rng.random_range(0.5..=2.0),
+            competitor_pressure: rng.random_range(0.2..=0.8),
+            market_saturation: rng.random_range(0.0..=1.0),
+            global_population: rng.random_range(7e9..=1e10),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn demand_modifier_stays_in_bounds() {
+        let mut rng = StdRng::seed_from_u64(6);
+        for _ in 0..TRIALS {
+            let world = random_world(&mut rng);
+            for (name, factor) in world.demand_factors() {
+                assert!(factor.is_finite() && factor > 0.0, "{} factor {}", name, factor);
+            }
+            let modifier = world.calculate_demand_modifier();
+            assert!((0.1..=MAX_DEMAND).contains(&modifier), "modifier {}", modifier);
+        }
+    }
+
+    #[test]
+    fn demand_modifier_stays_in_bounds_over_simulated_years() {
+        for difficulty in [Difficulty::Easy, Difficulty::Normal, Difficulty::Brutal] {
+            let difficulty = DifficultyConfig::new(difficulty);
+            for seed in [0, 7, 2012] {
+                let mut world = WorldState {
+                    seed,
+                    ..Default::default()
+                };
+                while world.date < GameDate::new(2030, 1, 1) {
+                    advance_one_day(&mut world, 2.0, &difficulty);
+                    let modifier = world.calculate_demand_modifier();
+                    assert!((0.1..=MAX_DEMAND).contains(&modifier), "{:?}: modifier {}", world.date, modifier);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn more_confidence_never_means_less_demand() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..TRIALS {
+            // The same world twice, one with more confident consumers
+            let seed = rng.random();
+            let world = random_world(&mut StdRng::seed_from_u64(seed));
+            let mut bolder = random_world(&mut StdRng::seed_from_u64(seed));
+            bolder.consumer_confidence = (bolder.consumer_confidence + rng.random_range(0.0..0.5)).min(1.8);
+            assert!(bolder.calculate_demand_modifier() >= world.calculate_demand_modifier());
+        }
+    }
+}
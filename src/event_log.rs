@@ -0,0 +1,490 @@
+//! Event log - a dated record of everything that happened this run
+//!
+//! Every subsystem already announces what it's doing with messages. This
+//! listens to the interesting ones and keeps a line for each, stamped with the
+//! game date and sorted into a category (economy, marketing, legal, Terry)
+//! with a severity, so the log overlay can filter and jump around by date.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::SystemParam;
+use crate::accounting::{Accounting, MonthlyReportEvent};
+use crate::ai_boom::{AiEvent, AiStage};
+use crate::counterfeits::{CounterfeitEvent, CounterfeitStage};
+use crate::crypto::{CryptoEvent, CryptoStage};
+use crate::currency::{money_change, money_whole};
+use crate::eco::{ActivistAction, ActivistEvent};
+use crate::economy::{GameDate, HeadlineEvent, WorldState};
+use crate::fads::{FadEvent, FadStage};
+use crate::game_state::{AppState, MilestoneEvent};
+use crate::gift_cards::{GiftCardActivity, GiftCardEvent};
+use crate::labor::{StrikeEvent, StrikeStage};
+use crate::limited_editions::{EditionEvent, EditionStage};
+use crate::objectives::WeeklyGoalEvent;
+use crate::pandemic::{PandemicEvent, PandemicPhase, PandemicStage};
+use crate::pivot::PivotEvent;
+use crate::price_war::{PriceWarEvent, PriceWarStage};
+use crate::quests::{QuestBook, QuestEvent, QuestLog, QuestStage};
+use crate::recovery::{RecoveryEvent, RecoveryStage};
+use crate::reputation::ReputationTierChangedEvent;
+use crate::research::ResearchCompletedEvent;
+use crate::rewards::{Reward, RewardEvent};
+use crate::savings::{SavingsActivity, SavingsEvent};
+use crate::seminars::SeminarEvent;
+use crate::suppliers::{SupplierEvent, SupplierStage};
+use crate::surveys::SurveyEvent;
+use crate::terry::TerryState;
+use crate::thing_type::ThingRegistry;
+use crate::utilities::{OutageCause, PowerOutageEvent};
+use crate::vacation::{VacationEvent, VacationStage, VacationState};
+use crate::venues::{VenueEvent, VenueStage};
+
+/// Oldest entries are dropped past this many
+const MAX_ENTRIES: usize = 2_000;
+
+/// What part of the business an entry is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum LogCategory {
+    Economy,
+    Marketing,
+    Legal,
+    Terry,
+}
+
+impl LogCategory {
+    pub const ALL: [LogCategory; 4] = [LogCategory::Economy, LogCategory::Marketing, LogCategory::Legal, LogCategory::Terry];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LogCategory::Economy => "Economy",
+            LogCategory::Marketing => "Marketing",
+            LogCategory::Legal => "Legal",
+            LogCategory::Terry => "Terry",
+        }
+    }
+}
+
+/// How much an entry matters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum Severity {
+    Info,
+    Good,
+    Warning,
+    Bad,
+}
+
+impl Severity {
+    /// Short icon shown next to the entry
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Severity::Info => "i",
+            Severity::Good => "+",
+            Severity::Warning => "!",
+            Severity::Bad => "X",
+        }
+    }
+}
+
+/// One line in the log
+#[derive(Debug, Clone, Reflect)]
+pub struct LogEntry {
+    pub date: GameDate,
+    pub category: LogCategory,
+    pub severity: Severity,
+    pub text: String,
+}
+
+/// Everything that's happened this run, oldest first
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct EventLog {
+    pub entries: Vec<LogEntry>,
+}
+
+impl EventLog {
+    fn push(&mut self, date: GameDate, category: LogCategory, severity: Severity, text: String) {
+        if self.entries.len() >= MAX_ENTRIES {
+            self.entries.remove(0);
+        }
+        self.entries.push(LogEntry { date, category, severity, text });
+    }
+
+    /// Dates that have at least one entry, oldest first
+    pub fn dates(&self) -> Vec<GameDate> {
+        let mut dates: Vec<GameDate> = self.entries.iter().map(|e| e.date).collect();
+        dates.dedup();
+        dates
+    }
+}
+
+pub struct EventLogPlugin;
+
+impl Plugin for EventLogPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EventLog>().add_systems(
+            Update,
+            (log_economy_events, log_marketing_events, log_legal_events, log_terry_lines, log_vacations, log_quests)
+                .run_if(in_state(AppState::Playing)),
+        );
+    }
+}
+
+/// Messages that go under Economy
+#[derive(SystemParam)]
+struct EconomyEvents<'w, 's> {
+    milestones: MessageReader<'w, 's, MilestoneEvent>,
+    reports: MessageReader<'w, 's, MonthlyReportEvent>,
+    research: MessageReader<'w, 's, ResearchCompletedEvent>,
+    seminars: MessageReader<'w, 's, SeminarEvent>,
+    pivots: MessageReader<'w, 's, PivotEvent>,
+    things: Res<'w, ThingRegistry>,
+    outages: MessageReader<'w, 's, PowerOutageEvent>,
+    suppliers: MessageReader<'w, 's, SupplierEvent>,
+    rewards: MessageReader<'w, 's, RewardEvent>,
+    savings: MessageReader<'w, 's, SavingsEvent>,
+    crypto: MessageReader<'w, 's, CryptoEvent>,
+    pandemic: MessageReader<'w, 's, PandemicEvent>,
+    headlines: MessageReader<'w, 's, HeadlineEvent>,
+    weekly_goals: MessageReader<'w, 's, WeeklyGoalEvent>,
+}
+
+fn log_economy_events(
+    mut events: EconomyEvents,
+    world: Res<WorldState>,
+    accounting: Res<Accounting>,
+    mut log: ResMut<EventLog>,
+) {
+    let date = world.date;
+    let category = LogCategory::Economy;
+    for event in events.milestones.read() {
+        log.push(date, category, Severity::Good, event.milestone_type.describe());
+    }
+    for event in events.rewards.read() {
+        let severity = if matches!(event.reward, Reward::Choice(_)) { Severity::Info } else { Severity::Good };
+        log.push(date, category, severity, format!("Milestone reward: {}", event.reward.describe()));
+    }
+    for event in events.savings.read() {
+        let text = match event.activity {
+            SavingsActivity::Deposited => format!("Deposited {} into savings", money_whole(event.amount)),
+            SavingsActivity::WithdrawalRequested => format!("Asked for {} back from savings", money_whole(event.amount)),
+            SavingsActivity::Withdrawn => format!("{} arrived from savings", money_whole(event.amount)),
+            SavingsActivity::Interest => continue,
+        };
+        log.push(date, category, Severity::Info, text);
+    }
+    for event in events.crypto.read() {
+        let (severity, text) = match event.stage {
+            CryptoStage::WindowOpened => (Severity::Info, "ThingCoin listed - crypto is back in fashion".to_string()),
+            CryptoStage::WindowClosed => (Severity::Info, "Crypto winter - ThingCoin can't be bought".to_string()),
+            CryptoStage::Bought { spent } => (Severity::Info, format!("Bought {} of ThingCoin", money_whole(spent))),
+            CryptoStage::Sold { proceeds, gain } => (
+                if gain >= 0.0 { Severity::Good } else { Severity::Warning },
+                format!("Sold ThingCoin for {} ({})", money_whole(proceeds), money_change(gain)),
+            ),
+            CryptoStage::Mooned => (Severity::Info, "ThingCoin soared".to_string()),
+            CryptoStage::Crashed => (Severity::Warning, "ThingCoin crashed".to_string()),
+            CryptoStage::Issued { raised } => (Severity::Warning, format!("Issued our own coin, raising {}", money_whole(raised))),
+            CryptoStage::CashedOut { raised } => {
+                (Severity::Good, format!("The mania ended; our coin's {} is ours to keep", money_whole(raised)))
+            }
+            CryptoStage::RugPulled { .. } => continue,
+        };
+        log.push(date, category, severity, text);
+    }
+    for event in events.pandemic.read() {
+        let (severity, text) = match event.stage {
+            PandemicStage::Began(PandemicPhase::Lockdown) => {
+                (Severity::Bad, "Lockdown - physical channels collapse, online surges".to_string())
+            }
+            PandemicStage::Began(PandemicPhase::Restrictions) => {
+                (Severity::Warning, "Restrictions ease a little - stores reopen at reduced capacity".to_string())
+            }
+            PandemicStage::Over => (Severity::Good, "Restrictions lifted - the pandemic era is over".to_string()),
+            PandemicStage::PivotedToDelivery => (Severity::Good, "Pivoted to delivery".to_string()),
+            PandemicStage::MaskRushStarted => (Severity::Good, "Our Things are selling as masks and sanitizer".to_string()),
+            PandemicStage::MaskRushEnded => (Severity::Info, "The mask and sanitizer rush is over".to_string()),
+        };
+        log.push(date, category, severity, text);
+    }
+    for event in events.weekly_goals.read() {
+        let (severity, verb) = if event.met { (Severity::Good, "Hit") } else { (Severity::Warning, "Missed") };
+        log.push(
+            date,
+            category,
+            severity,
+            format!("{} the weekly goal: {} of {} customers", verb, event.served, event.target),
+        );
+    }
+    for event in events.headlines.read() {
+        log.push(date, category, Severity::Info, format!("In the news: {}", event.headline.text));
+    }
+    for event in events.reports.read() {
+        let Some(report) = accounting.reports.get(event.index) else {
+            continue;
+        };
+        let severity = if report.profit() >= 0.0 { Severity::Info } else { Severity::Warning };
+        log.push(date, category, severity, format!("Monthly report: {}", report.summary()));
+    }
+    for event in events.research.read() {
+        log.push(date, category, Severity::Good, format!("Researched {}", event.tech.name()));
+    }
+    for event in events.seminars.read() {
+        let text = if event.completed {
+            format!("Terry finished teaching {}", event.course.name())
+        } else {
+            format!("Terry started teaching {}", event.course.name())
+        };
+        log.push(date, category, if event.completed { Severity::Good } else { Severity::Info }, text);
+    }
+    for event in events.pivots.read() {
+        let text = if event.completed {
+            format!("Finished pivoting to {}", events.things[event.to].name)
+        } else {
+            format!("Started pivoting from {} to {}", events.things[event.from].name, events.things[event.to].name)
+        };
+        log.push(date, category, Severity::Info, text);
+    }
+    for event in events.outages.read() {
+        match event.cause {
+            Some(OutageCause::HeatWave) => log.push(date, category, Severity::Bad, "Heat wave blackout".to_string()),
+            Some(OutageCause::UnpaidBill) => {}
+            None => log.push(date, category, Severity::Good, "Power restored".to_string()),
+        }
+    }
+    for event in events.suppliers.read() {
+        let (severity, text) = match event.stage {
+            SupplierStage::Offered => (Severity::Info, "Supplier offered new terms"),
+            SupplierStage::Accepted => (Severity::Info, "Accepted the supplier's terms"),
+            SupplierStage::Haggled { success: true } => (Severity::Good, "Haggled the supplier down"),
+            SupplierStage::Haggled { success: false } => (Severity::Warning, "Haggling with the supplier backfired"),
+            SupplierStage::WalkedAway => (Severity::Warning, "Walked away from the supplier"),
+            SupplierStage::Shortfall => (Severity::Warning, "Missed the supplier volume commitment"),
+            SupplierStage::Disrupted => (Severity::Bad, "Supplier stopped delivering"),
+            SupplierStage::Restored => (Severity::Good, "Supplier deliveries resumed"),
+        };
+        log.push(date, category, severity, text.to_string());
+    }
+}
+
+/// Messages that go under Marketing
+#[derive(SystemParam)]
+struct MarketingEvents<'w, 's> {
+    tiers: MessageReader<'w, 's, ReputationTierChangedEvent>,
+    venues: MessageReader<'w, 's, VenueEvent>,
+    gift_cards: MessageReader<'w, 's, GiftCardEvent>,
+    ai: MessageReader<'w, 's, AiEvent>,
+    price_war: MessageReader<'w, 's, PriceWarEvent>,
+    surveys: MessageReader<'w, 's, SurveyEvent>,
+    editions: MessageReader<'w, 's, EditionEvent>,
+    fads: MessageReader<'w, 's, FadEvent>,
+}
+
+fn log_marketing_events(mut events: MarketingEvents, world: Res<WorldState>, mut log: ResMut<EventLog>) {
+    let date = world.date;
+    let category = LogCategory::Marketing;
+    for event in events.tiers.read() {
+        let severity = if event.new_tier > event.old_tier { Severity::Good } else { Severity::Bad };
+        log.push(
+            date,
+            category,
+            severity,
+            format!("Reputation went from {} to {}", event.old_tier.name(), event.new_tier.name()),
+        );
+    }
+    for event in events.venues.read() {
+        let name = event.kind.name().to_lowercase();
+        let (severity, text) = match event.stage {
+            VenueStage::Offered => (Severity::Info, format!("The {} is taking bookings", name)),
+            VenueStage::Opened => (Severity::Good, format!("Opened a pop-up at the {}", name)),
+            VenueStage::WashedOut => (Severity::Warning, format!("Weather washed out the {}", name)),
+            VenueStage::Closed => (Severity::Info, format!("Packed up the {} pop-up", name)),
+        };
+        log.push(date, category, severity, text);
+    }
+    for event in events.gift_cards.read() {
+        if event.activity == GiftCardActivity::Breakage {
+            log.push(
+                date,
+                category,
+                Severity::Good,
+                format!("{} of gift cards expired unredeemed", money_whole(event.amount)),
+            );
+        }
+    }
+    for event in events.ai.read() {
+        let (severity, text) = match event.stage {
+            AiStage::Unlocked => (Severity::Info, "The AI boom - AI-powered upgrades are on sale".to_string()),
+            AiStage::Bought(upgrade) => (Severity::Good, format!("Bought {}", upgrade.name())),
+            AiStage::Hallucinated(upgrade) => {
+                (Severity::Bad, format!("{} hallucinated in public and was taken offline", upgrade.name()))
+            }
+            AiStage::BackOnline(upgrade) => (Severity::Info, format!("{} is back online", upgrade.name())),
+        };
+        log.push(date, category, severity, text);
+    }
+    for event in events.price_war.read() {
+        let (severity, text) = match event.stage {
+            PriceWarStage::Started => (Severity::Bad, "The competitors started a price war"),
+            PriceWarStage::TruceOffered => (Severity::Info, "The competitors offered a truce"),
+            PriceWarStage::TruceAccepted => (Severity::Good, "Accepted the competitors' truce"),
+            PriceWarStage::Ceasefire => (Severity::Good, "The price war fizzled out"),
+            PriceWarStage::Recovered => (Severity::Info, "Competitors' prices are back to normal"),
+        };
+        log.push(date, category, severity, text.to_string());
+    }
+    for event in events.surveys.read() {
+        let text = if event.completed {
+            format!("{} survey results are in", event.topic.name())
+        } else {
+            format!("Commissioned a {} survey", event.topic.name().to_lowercase())
+        };
+        log.push(date, category, Severity::Info, text);
+    }
+    for event in events.editions.read() {
+        let name = event.edition.name();
+        let (severity, text) = match event.stage {
+            EditionStage::Ordered => (Severity::Info, format!("Ordered a batch of {}", name)),
+            EditionStage::Launched => (Severity::Good, format!("{} is on sale", name)),
+            EditionStage::SellingOut => (Severity::Good, format!("{} is nearly sold out", name)),
+            EditionStage::SoldOut => (Severity::Good, format!("{} sold out", name)),
+            EditionStage::Leftover(units) => (Severity::Bad, format!("{} unsold {} went to clearance", units, name)),
+        };
+        log.push(date, category, severity, text);
+    }
+    for event in events.fads.read() {
+        let name = event.fad.name();
+        let (severity, text) = match event.stage {
+            FadStage::Started => (Severity::Info, format!("New fad: \"{}\"", name)),
+            FadStage::Chased => (Severity::Good, format!("Retooled for the \"{}\" fad", name)),
+            FadStage::Whiplash(stars) => {
+                (Severity::Bad, format!("Customers have fad whiplash (-{:.2} stars)", stars))
+            }
+            FadStage::Faded => (Severity::Info, format!("The \"{}\" fad is over", name)),
+        };
+        log.push(date, category, severity, text);
+    }
+}
+
+/// Messages that go under Legal
+#[derive(SystemParam)]
+struct LegalEvents<'w, 's> {
+    strikes: MessageReader<'w, 's, StrikeEvent>,
+    activists: MessageReader<'w, 's, ActivistEvent>,
+    recovery: MessageReader<'w, 's, RecoveryEvent>,
+    outages: MessageReader<'w, 's, PowerOutageEvent>,
+    crypto: MessageReader<'w, 's, CryptoEvent>,
+    counterfeits: MessageReader<'w, 's, CounterfeitEvent>,
+}
+
+fn log_legal_events(mut events: LegalEvents, world: Res<WorldState>, mut log: ResMut<EventLog>) {
+    let date = world.date;
+    let category = LogCategory::Legal;
+    for event in events.strikes.read() {
+        let (severity, text) = match event.stage {
+            StrikeStage::Started => (Severity::Bad, "Workers went on strike".to_string()),
+            StrikeStage::Resolving(resolution) => {
+                (Severity::Info, format!("Strike: chose to {}", resolution.name().to_lowercase()))
+            }
+            StrikeStage::Ended(_) => (Severity::Good, "Strike ended".to_string()),
+        };
+        log.push(date, category, severity, text);
+    }
+    for event in events.activists.read() {
+        let (severity, text) = match event.action {
+            ActivistAction::Protest => (Severity::Bad, "Environmental activists protested outside"),
+            ActivistAction::Praise => (Severity::Good, "Environmental activists praised the business"),
+        };
+        log.push(date, category, severity, text.to_string());
+    }
+    for event in events.recovery.read() {
+        let action = event.action.map(|a| a.name()).unwrap_or("Redemption");
+        let (severity, text) = match event.stage {
+            RecoveryStage::Unlocked => (Severity::Warning, "Scandal: redemption options are on the table".to_string()),
+            RecoveryStage::Started => (Severity::Info, format!("{} began", action)),
+            RecoveryStage::Midway => continue,
+            RecoveryStage::Succeeded => (Severity::Good, format!("{} worked", action)),
+            RecoveryStage::Failed => (Severity::Bad, format!("{} flopped", action)),
+        };
+        log.push(date, category, severity, text);
+    }
+    for event in events.outages.read() {
+        if event.cause == Some(OutageCause::UnpaidBill) {
+            log.push(date, category, Severity::Bad, "Power cut off for an unpaid bill".to_string());
+        }
+    }
+    for event in events.crypto.read() {
+        if let CryptoStage::RugPulled { refund } = event.stage {
+            log.push(date, category, Severity::Bad, format!("Our coin was exposed as a rug pull; refunded {}", money_whole(refund)));
+        }
+    }
+    for event in events.counterfeits.read() {
+        let (severity, text) = match event.stage {
+            CounterfeitStage::Appeared => (Severity::Warning, "Counterfeit Things are turning up on the market".to_string()),
+            CounterfeitStage::CustomerBurned => (Severity::Bad, "A customer was burned by a fake and blamed us".to_string()),
+            CounterfeitStage::TestBuy(share) => {
+                (Severity::Info, format!("Test buy: {:.0}% of the market's Things are fake", share * 100.0))
+            }
+            CounterfeitStage::LegalActionStarted => (Severity::Info, "Lawyers sent out cease-and-desist letters".to_string()),
+            CounterfeitStage::LegalActionWon => (Severity::Good, "Takedowns knocked the counterfeiters back".to_string()),
+            CounterfeitStage::CustomsRegistered => (Severity::Info, "Registered the Thing with customs".to_string()),
+            CounterfeitStage::Seized => (Severity::Warning, "Customs seized a shipment of fakes and wants it authenticated".to_string()),
+            CounterfeitStage::Forfeited => (Severity::Good, "Seized fakes were authenticated and destroyed".to_string()),
+            CounterfeitStage::Released => (Severity::Bad, "Seized fakes were released - nobody authenticated them".to_string()),
+        };
+        log.push(date, category, severity, text);
+    }
+}
+
+/// Everything Terry (or the temp) says
+fn log_terry_lines(
+    terry_state: Res<TerryState>,
+    vacation: Res<VacationState>,
+    world: Res<WorldState>,
+    mut log: ResMut<EventLog>,
+    mut last_line: Local<Option<String>>,
+) {
+    let Some(line) = terry_state.current_line.as_ref() else {
+        return;
+    };
+    if last_line.as_deref() == Some(line.id.as_str()) {
+        return;
+    }
+    *last_line = Some(line.id.clone());
+    let speaker = if vacation.is_away() { "Doug (temp)" } else { "Terry" };
+    log.push(world.date, LogCategory::Terry, Severity::Info, format!("{}: \"{}\"", speaker, line.text));
+}
+
+/// The Thing's story moving along
+fn log_quests(
+    mut quest_events: MessageReader<QuestEvent>,
+    book: Res<QuestBook>,
+    quest_log: Res<QuestLog>,
+    world: Res<WorldState>,
+    mut log: ResMut<EventLog>,
+) {
+    for event in quest_events.read() {
+        let Some(step) = quest_log.thing_type.and_then(|thing_type| book.step(thing_type, &event.step_id)) else {
+            continue;
+        };
+        let (severity, text) = match event.stage {
+            QuestStage::StepStarted => (Severity::Info, format!("New quest step: {}", step.title)),
+            QuestStage::ObjectiveMet => (Severity::Good, format!("Quest step ready: {}", step.title)),
+            QuestStage::ChainFinished => (Severity::Good, "Finished the Thing's story".to_string()),
+        };
+        log.push(world.date, LogCategory::Economy, severity, text);
+    }
+}
+
+/// Terry's time off
+fn log_vacations(mut vacation_events: MessageReader<VacationEvent>, world: Res<WorldState>, mut log: ResMut<EventLog>) {
+    for event in vacation_events.read() {
+        let (severity, text) = match event.stage {
+            VacationStage::Requested => (Severity::Info, "Terry asked for a week off"),
+            VacationStage::Granted => (Severity::Info, "Terry went on vacation; a temp is filling in"),
+            VacationStage::Refused => (Severity::Warning, "Terry's vacation was refused"),
+            VacationStage::BurnedOut => (Severity::Bad, "Terry burned out and left for two weeks"),
+            VacationStage::Returned => (Severity::Good, "Terry is back in the office"),
+        };
+        log.push(world.date, LogCategory::Terry, severity, text.to_string());
+    }
+}
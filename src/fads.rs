@@ -0,0 +1,249 @@
+//! Product fads - riding a trend for as long as it lasts
+//!
+//! Every couple of months the public decides that everything must be teal (or
+//! artisanal, or smart) for a few weeks. A new fad lifts the trend for
+//! everyone; the player can chase it by paying to retool the Thing to match,
+//! and while the fad lasts customers come in on top of the usual, more the
+//! hotter trends are running. Chase every fad that comes along, though, and
+//! customers stop being able to tell what the business stands for: each chase
+//! too soon after the last costs reputation, more each time.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::accounting::ExpenseCategory;
+use crate::economy::{DayAdvancedEvent, WorldState};
+use crate::fortune::RunModifiers;
+use crate::game_state::{AppState, GameState, ReputationChangedEvent};
+use crate::thing_type::ThingRegistry;
+
+/// Days before the first fad of a run
+const FIRST_FAD_DAYS: u32 = 60;
+/// Shortest and longest quiet spell between fads
+const FAD_GAP_DAYS: (u32, u32) = (45, 120);
+/// How long a fad lasts
+const FAD_DAYS: u32 = 30;
+/// Trend bump when a fad takes off
+const FAD_HYPE: f32 = 1.1;
+/// Retooling for a fad costs this many Things' worth at list price
+const RETOOL_UNITS: f64 = 200.0;
+/// Extra customers while riding a fad, per point of trend factor
+const FAD_BOOST: f64 = 0.25;
+/// Chasing again within this many days of the last chase is whiplash
+const WHIPLASH_DAYS: u32 = 90;
+/// Reputation lost per chase in a run of whiplash
+const WHIPLASH_REPUTATION: f32 = 0.15;
+
+/// What the public wants this month
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum Fad {
+    Teal,
+    Artisanal,
+    Smart,
+}
+
+impl Fad {
+    pub const ALL: [Fad; 3] = [Fad::Teal, Fad::Artisanal, Fad::Smart];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Fad::Teal => "Everything must be teal",
+            Fad::Artisanal => "Artisanal",
+            Fad::Smart => "Smart",
+        }
+    }
+
+    /// What retooling for the fad means
+    pub fn description(&self) -> &'static str {
+        match self {
+            Fad::Teal => "Repaint the Thing teal. Repaint the packaging teal. Terry is already teal.",
+            Fad::Artisanal => "Hand-finish every Thing and print a small-batch story on the box.",
+            Fad::Smart => "Put a chip in the Thing. Nobody asks what the chip does.",
+        }
+    }
+
+    pub fn trigger(&self) -> &'static str {
+        match self {
+            Fad::Teal => "fad_teal",
+            Fad::Artisanal => "fad_artisanal",
+            Fad::Smart => "fad_smart",
+        }
+    }
+}
+
+/// The fad going round, if any, and how the player has been chasing them
+#[derive(Resource, Reflect)]
+#[reflect(Resource, Default)]
+pub struct FadState {
+    pub current: Option<Fad>,
+    /// Days the current fad has left
+    pub days_left: u32,
+    /// Whether the player retooled for the current fad
+    pub riding: bool,
+    /// Days until the next fad, while there isn't one
+    days_until_next: u32,
+    /// Whiplash chases in a row so far
+    pub whiplash_streak: u32,
+    /// Days since the player last chased a fad
+    days_since_chase: Option<u32>,
+}
+
+impl Default for FadState {
+    fn default() -> Self {
+        Self {
+            current: None,
+            days_left: 0,
+            riding: false,
+            days_until_next: FIRST_FAD_DAYS,
+            whiplash_streak: 0,
+            days_since_chase: None,
+        }
+    }
+}
+
+impl FadState {
+    /// Multiplier on would-be customers while riding a fad (hotter trends, bigger boost)
+    pub fn customer_factor(&self, world: &WorldState) -> f64 {
+        if self.riding && self.current.is_some() {
+            1.0 + FAD_BOOST * world.trend_factor as f64
+        } else {
+            1.0
+        }
+    }
+
+    pub fn retool_cost(game_state: &GameState, things: &ThingRegistry) -> f64 {
+        game_state.thing_type.map_or(0.0, |thing_type| things[thing_type].base_price) * RETOOL_UNITS
+    }
+
+    pub fn can_chase(&self, game_state: &GameState, things: &ThingRegistry) -> bool {
+        self.current.is_some() && !self.riding && game_state.money >= Self::retool_cost(game_state, things)
+    }
+
+    /// Reputation chasing the current fad would cost (zero if it's been a while)
+    pub fn whiplash_penalty(&self) -> f32 {
+        if self.days_since_chase.is_some_and(|days| days < WHIPLASH_DAYS) {
+            WHIPLASH_REPUTATION * (self.whiplash_streak + 1) as f32
+        } else {
+            0.0
+        }
+    }
+}
+
+/// How a fad played out
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FadStage {
+    Started,
+    Chased,
+    /// Chased too soon after the last one, costing this much reputation
+    Whiplash(f32),
+    Faded,
+}
+
+/// Message fired as fads come and go and the player chases them
+#[derive(Event, Message, Clone)]
+pub struct FadEvent {
+    pub fad: Fad,
+    pub stage: FadStage,
+}
+
+/// Message asking to retool for the current fad (sent by the UI)
+#[derive(Event, Message, Clone)]
+pub struct ChaseFadEvent;
+
+pub struct FadPlugin;
+
+impl Plugin for FadPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FadState>()
+            .add_message::<FadEvent>()
+            .add_message::<ChaseFadEvent>()
+            .add_systems(
+                Update,
+                (chase_fad, run_fad_calendar).chain().run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// Pay to retool for the current fad, and pay in reputation if it's one chase too many
+fn chase_fad(
+    mut chase_events: MessageReader<ChaseFadEvent>,
+    modifiers: Res<RunModifiers>,
+    things: Res<ThingRegistry>,
+    mut game_state: ResMut<GameState>,
+    mut fads: ResMut<FadState>,
+    mut fad_events: MessageWriter<FadEvent>,
+    mut rep_events: MessageWriter<ReputationChangedEvent>,
+) {
+    for _ in chase_events.read() {
+        let Some(fad) = fads.current else {
+            continue;
+        };
+        if !fads.can_chase(&game_state, &things) {
+            continue;
+        }
+        let cost = FadState::retool_cost(&game_state, &things);
+        game_state.spend(ExpenseCategory::Pivot, cost);
+
+        let penalty = fads.whiplash_penalty();
+        if penalty > 0.0 {
+            fads.whiplash_streak += 1;
+            let change = modifiers.scale_reputation_change(-penalty);
+            game_state.reputation = (game_state.reputation + change).clamp(0.0, 5.0);
+            rep_events.write(ReputationChangedEvent {
+                new_reputation: game_state.reputation,
+            });
+            fad_events.write(FadEvent {
+                fad,
+                stage: FadStage::Whiplash(penalty),
+            });
+        } else {
+            fads.whiplash_streak = 0;
+        }
+        fads.riding = true;
+        fads.days_since_chase = Some(0);
+        fad_events.write(FadEvent {
+            fad,
+            stage: FadStage::Chased,
+        });
+    }
+}
+
+/// Each game day: count down the current fad, or start the next one
+fn run_fad_calendar(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    mut world: ResMut<WorldState>,
+    mut fads: ResMut<FadState>,
+    mut fad_events: MessageWriter<FadEvent>,
+) {
+    for _ in day_events.read() {
+        if let Some(days) = fads.days_since_chase.as_mut() {
+            *days += 1;
+        }
+
+        if let Some(fad) = fads.current {
+            fads.days_left = fads.days_left.saturating_sub(1);
+            if fads.days_left == 0 {
+                fads.current = None;
+                fads.riding = false;
+                fads.days_until_next = rand::random_range(FAD_GAP_DAYS.0..=FAD_GAP_DAYS.1);
+                fad_events.write(FadEvent {
+                    fad,
+                    stage: FadStage::Faded,
+                });
+            }
+            continue;
+        }
+
+        fads.days_until_next = fads.days_until_next.saturating_sub(1);
+        if fads.days_until_next > 0 {
+            continue;
+        }
+        let fad = Fad::ALL[rand::random_range(0..Fad::ALL.len())];
+        fads.current = Some(fad);
+        fads.days_left = FAD_DAYS;
+        world.trend_factor = (world.trend_factor * FAD_HYPE).min(2.0);
+        fad_events.write(FadEvent {
+            fad,
+            stage: FadStage::Started,
+        });
+    }
+}
@@ -0,0 +1,141 @@
+//! Terry's Fortune Cookie - an optional random modifier drawn at the start of a run
+//!
+//! The drawn fortune lives in [`RunModifiers`], which the economy and business
+//! systems consult. Without a cookie every modifier is neutral.
+
+use bevy::prelude::*;
+use crate::game_state::InGame;
+use crate::settings::Settings;
+
+/// What the fortune cookie can say
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum Fortune {
+    MercuryRetrograde,
+    MomsBlessing,
+    LuckyNumbers,
+    EarlyBird,
+    BadOmen,
+    EmptyCookie,
+}
+
+impl Fortune {
+    pub const ALL: [Fortune; 6] = [
+        Fortune::MercuryRetrograde,
+        Fortune::MomsBlessing,
+        Fortune::LuckyNumbers,
+        Fortune::EarlyBird,
+        Fortune::BadOmen,
+        Fortune::EmptyCookie,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Fortune::MercuryRetrograde => "Mercury in retrograde",
+            Fortune::MomsBlessing => "Mom's Blessing",
+            Fortune::LuckyNumbers => "Lucky numbers",
+            Fortune::EarlyBird => "The early bird",
+            Fortune::BadOmen => "A black cat crossed the loading dock",
+            Fortune::EmptyCookie => "The cookie was empty",
+        }
+    }
+
+    /// What it does, in plain terms
+    pub fn effect(&self) -> &'static str {
+        match self {
+            Fortune::MercuryRetrograde => "chaos variance doubled",
+            Fortune::MomsBlessing => "+10% reputation gain",
+            Fortune::LuckyNumbers => "+10% revenue",
+            Fortune::EarlyBird => "+15% auto production",
+            Fortune::BadOmen => "reputation losses +50%",
+            Fortune::EmptyCookie => "no effect, Terry is devastated",
+        }
+    }
+}
+
+/// Multipliers for the current run (all 1.0 when no fortune was drawn)
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource, Default)]
+pub struct RunModifiers {
+    pub fortune: Option<Fortune>,
+    /// Decided by a challenge code (or a saved run), so no cookie gets opened
+    pub preset: bool,
+    /// Scales how far daily chaos swings away from 1.0
+    pub chaos_variance: f32,
+    /// Multiplies reputation gains
+    pub reputation_gain: f32,
+    /// Multiplies reputation losses and decay
+    pub reputation_loss: f32,
+    /// Multiplies sales revenue
+    pub revenue: f64,
+    /// Multiplies automatic production
+    pub production: f64,
+}
+
+impl Default for RunModifiers {
+    fn default() -> Self {
+        Self {
+            fortune: None,
+            preset: false,
+            chaos_variance: 1.0,
+            reputation_gain: 1.0,
+            reputation_loss: 1.0,
+            revenue: 1.0,
+            production: 1.0,
+        }
+    }
+}
+
+impl RunModifiers {
+    /// The modifiers a challenge code calls for
+    pub fn from_share_code(fortune: Option<Fortune>) -> Self {
+        Self {
+            preset: true,
+            ..fortune.map(Self::from_fortune).unwrap_or_default()
+        }
+    }
+
+    pub fn from_fortune(fortune: Fortune) -> Self {
+        let mut modifiers = Self {
+            fortune: Some(fortune),
+            ..default()
+        };
+        match fortune {
+            Fortune::MercuryRetrograde => modifiers.chaos_variance = 2.0,
+            Fortune::MomsBlessing => modifiers.reputation_gain = 1.1,
+            Fortune::LuckyNumbers => modifiers.revenue = 1.1,
+            Fortune::EarlyBird => modifiers.production = 1.15,
+            Fortune::BadOmen => modifiers.reputation_loss = 1.5,
+            Fortune::EmptyCookie => {}
+        }
+        modifiers
+    }
+
+    /// Scale a reputation change by the matching gain or loss modifier
+    pub fn scale_reputation_change(&self, change: f32) -> f32 {
+        if change >= 0.0 {
+            change * self.reputation_gain
+        } else {
+            change * self.reputation_loss
+        }
+    }
+}
+
+pub struct FortunePlugin;
+
+impl Plugin for FortunePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunModifiers>()
+            .add_systems(OnEnter(InGame), open_fortune_cookie);
+    }
+}
+
+/// Crack open the cookie when the run starts (if the player wants one)
+pub fn open_fortune_cookie(settings: Res<Settings>, mut modifiers: ResMut<RunModifiers>) {
+    if !settings.fortune_cookie || modifiers.preset || modifiers.fortune.is_some() {
+        return;
+    }
+
+    let fortune = Fortune::ALL[rand::random_range(0..Fortune::ALL.len())];
+    *modifiers = RunModifiers::from_fortune(fortune);
+    info!("Fortune cookie: {} ({})", fortune.name(), fortune.effect());
+}
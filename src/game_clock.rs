@@ -0,0 +1,45 @@
+//! Game clock - the time gameplay timers run on
+//!
+//! Anything that ticks in game terms (days, production, hustle, reputation
+//! drift, Terry's commentary) reads [`GameClock`] rather than `Time`. It
+//! stands still whenever the game is paused or not being played, and keeps
+//! pace with the world's time scale: a game second is a game day, however many
+//! real seconds `time_scale` makes that. UI timers (dialogue pacing,
+//! animations) stay on `Time`.
+
+use bevy::prelude::*;
+use crate::economy::WorldState;
+use crate::game_state::{AppState, GameState};
+
+/// How much game time passed this frame
+#[derive(Resource, Default)]
+pub struct GameClock {
+    /// Game seconds this frame (zero while paused)
+    delta: f32,
+}
+
+impl GameClock {
+    pub fn delta_secs(&self) -> f32 {
+        self.delta
+    }
+}
+
+pub struct GameClockPlugin;
+
+impl Plugin for GameClockPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameClock>().add_systems(PreUpdate, tick_game_clock);
+    }
+}
+
+/// Advance the clock with virtual time, unless the game's paused or not being played
+fn tick_game_clock(
+    time: Res<Time>,
+    state: Res<State<AppState>>,
+    game_state: Res<GameState>,
+    world: Res<WorldState>,
+    mut clock: ResMut<GameClock>,
+) {
+    let running = *state.get() == AppState::Playing && !game_state.paused;
+    clock.delta = if running { time.delta_secs() / world.time_scale.max(f32::EPSILON) } else { 0.0 };
+}
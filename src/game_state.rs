@@ -2,26 +2,48 @@
 
 use bevy::prelude::*;
 use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::accounting::{ExpenseCategory, ExpenseTally};
+use crate::currency::{money_whole, Money};
+use crate::save::RunLoadedEvent;
+use crate::snapshot;
 use crate::thing_type::ThingType;
 
 /// The main game states
 #[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
 pub enum AppState {
+    /// Reading and preloading content, before the selection screen
     #[default]
+    Loading,
     ThingSelection,
     Playing,
+    /// The pause menu is open over a run
     Paused,
 }
 
+/// A run is on screen, whether it's being played or paused. The main screen
+/// and the run's setup and teardown hang off this, so pausing doesn't tear
+/// them down.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct InGame;
+
+impl ComputedStates for InGame {
+    type SourceStates = AppState;
+
+    fn compute(state: AppState) -> Option<Self> {
+        matches!(state, AppState::Playing | AppState::Paused).then_some(InGame)
+    }
+}
+
 /// Core game state resource
-#[derive(Resource, Debug)]
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource, Default)]
 pub struct GameState {
     /// The type of Thing the player is selling
     pub thing_type: Option<ThingType>,
     /// Total Things produced (lifetime)
     pub things_produced: u64,
     /// Current money
-    pub money: f64,
+    pub money: Money,
     /// Reputation (0.0 to 5.0, like star rating)
     pub reputation: f32,
     /// Marketing level (affects customer flow)
@@ -32,6 +54,37 @@ pub struct GameState {
     pub click_power: u64,
     /// Customers served
     pub customers_served: u64,
+    /// Production is shut down while the factory retools for a pivot
+    pub retooling: bool,
+    /// A vignette has stopped the clock
+    pub paused: bool,
+    /// Workers have walked out
+    pub on_strike: bool,
+    /// The factory has no power
+    pub power_out: bool,
+    /// The supplier has stopped delivering materials
+    pub supply_cut: bool,
+    /// Everything spent this run, by category
+    pub expenses: ExpenseTally,
+}
+
+impl GameState {
+    /// Is the line running? (a Thing has been chosen, we're not mid-pivot, on strike, in the dark, out of materials or paused)
+    pub fn can_produce(&self) -> bool {
+        self.thing_type.is_some() && !self.retooling && !self.on_strike && !self.power_out && !self.supply_cut && !self.paused
+    }
+
+    /// Pay for something, booking it under an expense category
+    pub fn spend(&mut self, category: ExpenseCategory, amount: f64) {
+        let amount = Money::from_dollars(amount);
+        self.money -= amount;
+        self.expenses.add(category, amount.dollars());
+    }
+}
+
+/// Run condition: the clock isn't stopped (by the pause menu or a vignette)
+pub fn game_running(state: Res<State<AppState>>, game_state: Res<GameState>) -> bool {
+    *state.get() != AppState::Paused && !game_state.paused
 }
 
 impl Default for GameState {
@@ -39,12 +92,18 @@ impl Default for GameState {
         Self {
             thing_type: None,
             things_produced: 0,
-            money: 100.0, // Starting capital (questionable sources)
+            money: Money::from_dollars(100.0), // Starting capital (questionable sources)
             reputation: 2.5, // Starting at middle reputation
             marketing_level: 0,
             things_per_second: 0.0,
             click_power: 1,
             customers_served: 0,
+            retooling: false,
+            paused: false,
+            on_strike: false,
+            power_out: false,
+            supply_cut: false,
+            expenses: ExpenseTally::default(),
         }
     }
 }
@@ -75,7 +134,7 @@ pub struct MilestoneEvent {
     pub milestone_type: MilestoneType,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
 pub enum MilestoneType {
     ThingsProduced(u64),
     MoneyEarned(u64),
@@ -83,26 +142,67 @@ pub enum MilestoneType {
     ReputationReached(u8),
 }
 
+impl MilestoneType {
+    pub fn describe(&self) -> String {
+        match self {
+            MilestoneType::ThingsProduced(n) => format!("{} Things produced", n),
+            MilestoneType::MoneyEarned(n) => format!("{} in the bank", money_whole(*n as f64)),
+            MilestoneType::CustomersServed(n) => format!("{} customers served", n),
+            MilestoneType::ReputationReached(n) => format!("Reached {} stars", n),
+        }
+    }
+}
+
 pub struct GameStatePlugin;
 
 impl Plugin for GameStatePlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<GameState>()
+        app.add_computed_state::<InGame>()
+            .init_resource::<GameState>()
             .add_message::<ThingProducedEvent>()
             .add_message::<MoneyChangedEvent>()
             .add_message::<ReputationChangedEvent>()
             .add_message::<MilestoneEvent>()
-            .add_systems(Update, check_milestones.run_if(in_state(AppState::Playing)));
+            .add_systems(Update, check_milestones.run_if(in_state(AppState::Playing)))
+            .add_systems(OnExit(InGame), end_run);
+    }
+}
+
+/// Throw the run away on leaving it (any save has been written by now), so
+/// the next one starts fresh
+fn end_run(world: &mut World) {
+    reset_run(world);
+}
+
+/// Put every piece of run state back the way a fresh run starts (state
+/// outside the snapshots, like the UI's, is reset by its own plugin on
+/// leaving the run)
+pub fn reset_run(world: &mut World) {
+    if let Err(err) = snapshot::reset(world) {
+        warn!("Part of the last run is left over: {}", err);
     }
 }
 
+/// Put run state that isn't in the snapshots back to its default on leaving the run
+pub fn reset_resource<R: Resource + Default>(mut commands: Commands) {
+    commands.insert_resource(R::default());
+}
+
 /// Check for milestone achievements
 fn check_milestones(
     game_state: Res<GameState>,
     mut milestone_events: MessageWriter<MilestoneEvent>,
+    mut loaded_events: MessageReader<RunLoadedEvent>,
     mut last_things: Local<u64>,
     mut last_money: Local<u64>,
 ) {
+    // A continued run has passed its milestones already
+    if loaded_events.read().count() > 0 {
+        *last_things = game_state.things_produced;
+        *last_money = game_state.money.dollars() as u64;
+        return;
+    }
+
     let milestones = [10, 100, 1000, 10000, 100000, 1000000];
 
     // Check things produced milestones
@@ -116,7 +216,7 @@ fn check_milestones(
     *last_things = game_state.things_produced;
 
     // Check money milestones
-    let money_rounded = game_state.money as u64;
+    let money_rounded = game_state.money.dollars() as u64;
     for &milestone in &milestones {
         if money_rounded >= milestone && *last_money < milestone {
             milestone_events.write(MilestoneEvent {
@@ -0,0 +1,134 @@
+//! Gift cards - Christmas cash now, Things owed in January
+//!
+//! In the run-up to Christmas a growing share of shoppers buy gift cards on
+//! top of their Things. The cash arrives at once but isn't revenue yet: it is
+//! owed back as Things once the cards are redeemed after Christmas (when
+//! redeemers also tend to spend a bit extra). Whatever is still unredeemed on
+//! the first of March is breakage, and finally counts as income.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::currency::Money;
+use crate::economy::{DayAdvancedEvent, GameDate, WorldState};
+use crate::game_state::{AppState, GameState, MoneyChangedEvent};
+
+/// Days before Christmas gift cards go on sale
+const SEASON_DAYS: i32 = 55;
+/// Gift cards sold per dollar of Things sold, on Christmas Eve
+const PEAK_CARD_SHARE: f64 = 0.3;
+/// Share of outstanding cards redeemed each day after Christmas
+const DAILY_REDEMPTION: f64 = 0.04;
+/// Extra cash spent per dollar redeemed
+const REDEMPTION_UPSELL: f64 = 0.2;
+/// Month and day unredeemed cards are written off as breakage
+const BREAKAGE_DATE: (u8, u8) = (3, 1);
+
+/// What happened to gift cards today
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GiftCardActivity {
+    /// Cash in, Things owed
+    Sold,
+    /// Things handed over against cards; now it's revenue
+    Redeemed,
+    /// Unredeemed cards written off as income
+    Breakage,
+}
+
+/// Message fired once a day for each kind of gift card activity
+#[derive(Event, Message, Clone)]
+pub struct GiftCardEvent {
+    pub activity: GiftCardActivity,
+    /// Value of the cards sold, redeemed or written off
+    pub amount: f64,
+    /// Cash that changed hands (the card price, or what redeemers spent on top)
+    pub cash: f64,
+}
+
+/// Gift cards sold but not yet redeemed
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct GiftCards {
+    /// Value owed to card holders
+    pub outstanding: Money,
+    /// Sales so far today (gift cards ride along with them)
+    sales_today: f64,
+}
+
+/// Share of today's sales that also buy a gift card
+fn card_share(world: &WorldState) -> f64 {
+    let days = world.days_to_christmas;
+    if days <= 0 || days > SEASON_DAYS {
+        return 0.0;
+    }
+    PEAK_CARD_SHARE * (1.0 - (days - 1) as f64 / SEASON_DAYS as f64)
+}
+
+/// Cards get redeemed from Boxing Day until breakage
+fn redemption_season(date: GameDate) -> bool {
+    (date.month() == 12 && date.day() > 25) || date.month() < BREAKAGE_DATE.0
+}
+
+pub struct GiftCardPlugin;
+
+impl Plugin for GiftCardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GiftCards>()
+            .add_message::<GiftCardEvent>()
+            .add_systems(Update, run_gift_cards.run_if(in_state(AppState::Playing)));
+    }
+}
+
+/// Sell, redeem and write off gift cards, once a day
+fn run_gift_cards(
+    mut money_events: MessageReader<MoneyChangedEvent>,
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    world: Res<WorldState>,
+    mut game_state: ResMut<GameState>,
+    mut cards: ResMut<GiftCards>,
+    mut card_events: MessageWriter<GiftCardEvent>,
+) {
+    for event in money_events.read() {
+        if event.delta > 0.0 {
+            cards.sales_today += event.delta;
+        }
+    }
+
+    for event in day_events.read() {
+        // The day that just ended
+        let sold = Money::from_dollars(cards.sales_today * card_share(&world));
+        cards.sales_today = 0.0;
+        if sold > Money::ZERO {
+            game_state.money += sold;
+            cards.outstanding += sold;
+            card_events.write(GiftCardEvent {
+                activity: GiftCardActivity::Sold,
+                amount: sold.dollars(),
+                cash: sold.dollars(),
+            });
+        }
+
+        if cards.outstanding <= Money::ZERO {
+            continue;
+        }
+
+        if (event.date.month(), event.date.day()) == BREAKAGE_DATE {
+            let breakage = cards.outstanding;
+            cards.outstanding = Money::ZERO;
+            card_events.write(GiftCardEvent {
+                activity: GiftCardActivity::Breakage,
+                amount: breakage.dollars(),
+                cash: 0.0,
+            });
+        } else if redemption_season(event.date) {
+            let redeemed = cards.outstanding.share(DAILY_REDEMPTION);
+            let upsell = redeemed.share(REDEMPTION_UPSELL);
+            cards.outstanding -= redeemed;
+            game_state.money += upsell;
+            card_events.write(GiftCardEvent {
+                activity: GiftCardActivity::Redeemed,
+                amount: redeemed.dollars(),
+                cash: upsell.dollars(),
+            });
+        }
+    }
+}
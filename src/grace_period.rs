@@ -0,0 +1,74 @@
+//! Grace period - picking a different Thing for free at the start of a run
+//!
+//! For the first couple of game days, or until the first sale, the player can
+//! take back their choice of Thing from the pause menu. Nothing much has
+//! happened yet, so rather than patching up everything that depends on the
+//! Thing, the run is thrown away and the selection screen comes back. After
+//! that, changing Things means pivoting, with everything that costs.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::economy::DayAdvancedEvent;
+use crate::game_state::{AppState, GameState, InGame};
+use crate::thing_type::ThingRegistry;
+
+/// Game days the choice of Thing can be taken back for free
+pub const GRACE_DAYS: u32 = 2;
+
+/// How far into the run we are, for the grace period
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct SelectionGrace {
+    /// Game days played this run
+    days: u32,
+}
+
+impl SelectionGrace {
+    /// Can the Thing still be changed for free?
+    pub fn is_open(&self, game_state: &GameState) -> bool {
+        self.days < GRACE_DAYS || game_state.customers_served == 0
+    }
+}
+
+/// Message requesting the choice of Thing be taken back (sent by the UI)
+#[derive(Event, Message, Clone)]
+pub struct UndoThingSelectionEvent;
+
+pub struct GracePeriodPlugin;
+
+impl Plugin for GracePeriodPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SelectionGrace>()
+            .add_message::<UndoThingSelectionEvent>()
+            .add_systems(
+                Update,
+                (
+                    count_grace_days.run_if(in_state(AppState::Playing)),
+                    // The button's on the pause menu
+                    undo_thing_selection.run_if(in_state(InGame)),
+                ),
+            );
+    }
+}
+
+fn count_grace_days(mut day_events: MessageReader<DayAdvancedEvent>, mut grace: ResMut<SelectionGrace>) {
+    for _ in day_events.read() {
+        grace.days += 1;
+    }
+}
+
+/// Go back to the selection screen (which throws the run away), while it's still free
+fn undo_thing_selection(
+    mut undo_events: MessageReader<UndoThingSelectionEvent>,
+    grace: Res<SelectionGrace>,
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if undo_events.read().last().is_none() || !grace.is_open(&game_state) {
+        return;
+    }
+
+    info!("Taking back {} before the run gets going", game_state.thing_type.map_or("nothing", |thing_type| &things[thing_type].name));
+    next_state.set(AppState::ThingSelection);
+}
@@ -0,0 +1,124 @@
+//! HUD layout - the widgets a player has pinned to the main screen, and where
+//!
+//! Any stat, upgrade or marketing channel can be pinned as a small widget and
+//! dragged wherever it's wanted. The layout is saved in the profile's config,
+//! so each profile keeps its own dashboard from run to run.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::business::UpgradeType;
+use crate::marketing::MarketingChannel;
+
+/// Where the first pinned widget goes, in logical pixels from the top left
+const FIRST_SLOT: Vec2 = Vec2::new(330.0, 80.0);
+/// Gap between the slots new widgets are stacked in
+const SLOT_HEIGHT: f32 = 46.0;
+
+/// A number the HUD can show
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HudStat {
+    Money,
+    ThingsMade,
+    Production,
+    Customers,
+    Reputation,
+    MarketingLevel,
+    /// Today's demand, as a gauge
+    Demand,
+    /// Things sitting in the warehouse
+    Stock,
+}
+
+impl HudStat {
+    pub const ALL: [HudStat; 8] = [
+        HudStat::Money,
+        HudStat::ThingsMade,
+        HudStat::Production,
+        HudStat::Customers,
+        HudStat::Reputation,
+        HudStat::MarketingLevel,
+        HudStat::Demand,
+        HudStat::Stock,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HudStat::Money => "Money",
+            HudStat::ThingsMade => "Things made",
+            HudStat::Production => "Production",
+            HudStat::Customers => "Customers",
+            HudStat::Reputation => "Reputation",
+            HudStat::MarketingLevel => "Marketing level",
+            HudStat::Demand => "Demand",
+            HudStat::Stock => "Stock on hand",
+        }
+    }
+}
+
+/// What a pinned widget shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HudWidget {
+    Stat(HudStat),
+    Upgrade(UpgradeType),
+    Channel(MarketingChannel),
+}
+
+impl HudWidget {
+    pub fn name(&self) -> &'static str {
+        match self {
+            HudWidget::Stat(stat) => stat.name(),
+            HudWidget::Upgrade(upgrade) => upgrade.name(),
+            HudWidget::Channel(channel) => channel.name(),
+        }
+    }
+}
+
+/// A pinned widget and its top left corner, in logical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PlacedWidget {
+    pub widget: HudWidget,
+    pub x: f32,
+    pub y: f32,
+}
+
+/// Every pinned widget, in the order they were pinned
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HudLayout {
+    pub widgets: Vec<PlacedWidget>,
+}
+
+impl HudLayout {
+    pub fn contains(&self, widget: HudWidget) -> bool {
+        self.widgets.iter().any(|placed| placed.widget == widget)
+    }
+
+    /// Pin a widget in the first free slot down the side of the screen
+    pub fn pin(&mut self, widget: HudWidget) {
+        if self.contains(widget) {
+            return;
+        }
+        let slot = (0..)
+            .map(|slot| FIRST_SLOT + Vec2::Y * SLOT_HEIGHT * slot as f32)
+            .find(|position| !self.widgets.iter().any(|placed| placed.x == position.x && placed.y == position.y))
+            .unwrap_or(FIRST_SLOT);
+        self.widgets.push(PlacedWidget {
+            widget,
+            x: slot.x,
+            y: slot.y,
+        });
+    }
+
+    pub fn unpin(&mut self, widget: HudWidget) {
+        self.widgets.retain(|placed| placed.widget != widget);
+    }
+
+    /// Move a pinned widget
+    pub fn place(&mut self, widget: HudWidget, position: Vec2) {
+        if let Some(placed) = self.widgets.iter_mut().find(|placed| placed.widget == widget) {
+            placed.x = position.x;
+            placed.y = position.y;
+        }
+    }
+}
@@ -0,0 +1,278 @@
+//! Keyboard shortcuts - a rebindable map from game actions to keys
+//!
+//! Key presses become [`HotkeyEvent`]s; the systems that own each action react
+//! to them. Bindings are saved with the rest of the config file.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use std::collections::BTreeMap;
+use crate::business::{UpgradePurchasedEvent, UpgradeState, UpgradeType};
+use crate::clicker::ClickEvent;
+use crate::game_state::{AppState, GameState, InGame};
+
+/// Something a key can be bound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputAction {
+    /// Same as pressing "Make Thing"
+    MakeThing,
+    /// Buy one of an upgrade
+    Upgrade(UpgradeType),
+    /// Open or close the Marketing panel
+    ToggleMarketing,
+    /// Stop the clock
+    Pause,
+}
+
+impl InputAction {
+    /// Every action, in the order the settings screen lists them
+    pub fn all() -> Vec<InputAction> {
+        let mut actions = vec![InputAction::MakeThing];
+        actions.extend(UpgradeType::ALL.iter().map(|u| InputAction::Upgrade(*u)));
+        actions.push(InputAction::ToggleMarketing);
+        actions.push(InputAction::Pause);
+        actions
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            InputAction::MakeThing => "Make Thing".to_string(),
+            InputAction::Upgrade(upgrade) => format!("Buy {}", upgrade.name()),
+            InputAction::ToggleMarketing => "Marketing panel".to_string(),
+            InputAction::Pause => "Pause".to_string(),
+        }
+    }
+
+    /// Stable name used in the config file
+    fn id(&self) -> String {
+        match self {
+            InputAction::MakeThing => "make_thing".to_string(),
+            InputAction::Upgrade(upgrade) => format!("buy_{}", upgrade.name().to_lowercase().replace(' ', "_")),
+            InputAction::ToggleMarketing => "marketing_panel".to_string(),
+            InputAction::Pause => "pause".to_string(),
+        }
+    }
+}
+
+/// Keys that can be bound (anything else is ignored while rebinding; Tab, Enter and
+/// the arrows are kept for moving focus around the UI)
+const BINDABLE_KEYS: [KeyCode; 46] = [
+    KeyCode::KeyA, KeyCode::KeyB, KeyCode::KeyC, KeyCode::KeyD, KeyCode::KeyE, KeyCode::KeyF,
+    KeyCode::KeyG, KeyCode::KeyH, KeyCode::KeyI, KeyCode::KeyJ, KeyCode::KeyK, KeyCode::KeyL,
+    KeyCode::KeyM, KeyCode::KeyN, KeyCode::KeyO, KeyCode::KeyP, KeyCode::KeyQ, KeyCode::KeyR,
+    KeyCode::KeyS, KeyCode::KeyT, KeyCode::KeyU, KeyCode::KeyV, KeyCode::KeyW, KeyCode::KeyX,
+    KeyCode::KeyY, KeyCode::KeyZ,
+    KeyCode::Digit0, KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3, KeyCode::Digit4,
+    KeyCode::Digit5, KeyCode::Digit6, KeyCode::Digit7, KeyCode::Digit8, KeyCode::Digit9,
+    KeyCode::F1, KeyCode::F2, KeyCode::F3, KeyCode::F4, KeyCode::F5, KeyCode::F6,
+    KeyCode::Space, KeyCode::Escape, KeyCode::Backspace, KeyCode::Backquote,
+];
+
+/// Short display name for a key ("KeyM" -> "M", "Digit1" -> "1")
+pub fn key_name(key: KeyCode) -> String {
+    let name = format!("{:?}", key);
+    match key {
+        KeyCode::Escape => "Esc".to_string(),
+        KeyCode::Backquote => "`".to_string(),
+        _ => name
+            .strip_prefix("Key")
+            .or_else(|| name.strip_prefix("Digit"))
+            .unwrap_or(&name)
+            .to_string(),
+    }
+}
+
+fn key_from_name(name: &str) -> Option<KeyCode> {
+    BINDABLE_KEYS.into_iter().find(|key| key_name(*key) == name)
+}
+
+/// Which key triggers each action
+#[derive(Resource, Debug, Clone)]
+pub struct InputMap {
+    bindings: Vec<(InputAction, KeyCode)>,
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        let digits = [
+            KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3, KeyCode::Digit4,
+            KeyCode::Digit5, KeyCode::Digit6, KeyCode::Digit7, KeyCode::Digit8,
+        ];
+
+        let mut bindings = vec![(InputAction::MakeThing, KeyCode::Space)];
+        for (upgrade, key) in UpgradeType::ALL.iter().zip(digits) {
+            bindings.push((InputAction::Upgrade(*upgrade), key));
+        }
+        bindings.push((InputAction::ToggleMarketing, KeyCode::KeyM));
+        bindings.push((InputAction::Pause, KeyCode::Escape));
+        Self { bindings }
+    }
+}
+
+impl InputMap {
+    pub fn key_for(&self, action: InputAction) -> Option<KeyCode> {
+        self.bindings.iter().find(|(a, _)| *a == action).map(|(_, key)| *key)
+    }
+
+    pub fn action_for(&self, key: KeyCode) -> Option<InputAction> {
+        self.bindings.iter().find(|(_, k)| *k == key).map(|(action, _)| *action)
+    }
+
+    /// Bind `action` to `key`. Fails with the action already using the key, if any.
+    pub fn rebind(&mut self, action: InputAction, key: KeyCode) -> Result<(), InputAction> {
+        if let Some(existing) = self.action_for(key) {
+            if existing != action {
+                return Err(existing);
+            }
+        }
+
+        match self.bindings.iter_mut().find(|(a, _)| *a == action) {
+            Some(binding) => binding.1 = key,
+            None => self.bindings.push((action, key)),
+        }
+        Ok(())
+    }
+
+    /// Bindings as written to the config file (action id -> key name)
+    pub fn to_saved(&self) -> BTreeMap<String, String> {
+        self.bindings
+            .iter()
+            .map(|(action, key)| (action.id(), key_name(*key)))
+            .collect()
+    }
+
+    /// Apply bindings read from the config file, skipping unknown or conflicting entries
+    pub fn apply_saved(&mut self, saved: &BTreeMap<String, String>) {
+        for action in InputAction::all() {
+            let Some(key) = saved.get(&action.id()).and_then(|name| key_from_name(name)) else {
+                continue;
+            };
+            if self.rebind(action, key).is_err() {
+                warn!("Ignoring saved key {} for {}: already in use", key_name(key), action.label());
+            }
+        }
+    }
+}
+
+/// The action waiting for a new key on the settings screen, and how the last attempt went
+#[derive(Resource, Default)]
+pub struct Rebinding {
+    pub action: Option<InputAction>,
+    pub message: Option<String>,
+}
+
+/// Message fired when a bound key is pressed
+#[derive(Event, Message, Clone)]
+pub struct HotkeyEvent {
+    pub action: InputAction,
+}
+
+pub struct InputPlugin;
+
+impl Plugin for InputPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputMap>()
+            .init_resource::<Rebinding>()
+            .add_message::<HotkeyEvent>()
+            .add_systems(
+                Update,
+                (capture_rebind_key, read_hotkeys, apply_hotkeys)
+                    .chain()
+                    .run_if(in_state(InGame)),
+            )
+            .add_systems(OnEnter(AppState::Paused), stop_clock)
+            .add_systems(OnExit(AppState::Paused), restart_clock)
+            .add_systems(OnExit(InGame), resume_on_exit);
+    }
+}
+
+/// While an action is being rebound, the next bindable key press goes to it
+fn capture_rebind_key(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut input_map: ResMut<InputMap>,
+    mut rebinding: ResMut<Rebinding>,
+) {
+    let Some(action) = rebinding.action else {
+        return;
+    };
+    let Some(key) = keys.get_just_pressed().copied().find(|key| BINDABLE_KEYS.contains(key)) else {
+        return;
+    };
+
+    rebinding.action = None;
+    rebinding.message = match input_map.rebind(action, key) {
+        Ok(()) => Some(format!("{} is now {}", action.label(), key_name(key))),
+        Err(existing) => Some(format!("{} is already used for {}", key_name(key), existing.label())),
+    };
+}
+
+/// Turn key presses into hotkey messages
+fn read_hotkeys(
+    keys: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    rebinding: Res<Rebinding>,
+    mut hotkey_events: MessageWriter<HotkeyEvent>,
+) {
+    // The key that finished a rebind shouldn't also trigger its new action
+    if rebinding.action.is_some() || rebinding.is_changed() {
+        return;
+    }
+
+    for key in keys.get_just_pressed() {
+        if let Some(action) = input_map.action_for(*key) {
+            hotkey_events.write(HotkeyEvent { action });
+        }
+    }
+}
+
+/// Carry out the game-side hotkeys (the Marketing panel is handled by the UI)
+fn apply_hotkeys(
+    mut hotkey_events: MessageReader<HotkeyEvent>,
+    state: Res<State<AppState>>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut game_state: ResMut<GameState>,
+    mut upgrades: ResMut<UpgradeState>,
+    mut click_events: MessageWriter<ClickEvent>,
+    mut purchase_events: MessageWriter<UpgradePurchasedEvent>,
+) {
+    let paused = *state.get() == AppState::Paused;
+    for event in hotkey_events.read() {
+        // Nothing works while a vignette has the clock stopped, and only unpausing works while paused
+        if game_state.paused || (paused && event.action != InputAction::Pause) {
+            continue;
+        }
+
+        match event.action {
+            InputAction::Pause => {
+                next_state.set(if paused { AppState::Playing } else { AppState::Paused });
+            }
+            InputAction::MakeThing => {
+                click_events.write(ClickEvent);
+            }
+            InputAction::Upgrade(upgrade) => {
+                if upgrades.purchase(upgrade, &mut game_state) {
+                    purchase_events.write(UpgradePurchasedEvent { upgrade });
+                }
+            }
+            // Handled by the UI
+            InputAction::ToggleMarketing => {}
+        }
+    }
+}
+
+/// Stop virtual time while the pause menu is open
+fn stop_clock(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+/// Start it again on leaving the menu, unless a vignette is holding it
+fn restart_clock(mut time: ResMut<Time<Virtual>>, game_state: Res<GameState>) {
+    if !game_state.paused {
+        time.unpause();
+    }
+}
+
+/// Never leave the clock stopped behind a screen that can't unpause it
+fn resume_on_exit(mut time: ResMut<Time<Virtual>>, mut game_state: ResMut<GameState>) {
+    game_state.paused = false;
+    time.unpause();
+}
@@ -0,0 +1,253 @@
+//! Labor relations - workers can walk out
+//!
+//! Once there's a real workforce, each game day carries a small chance of a
+//! strike (higher when lots of workers have no manager looking out for them).
+//! A strike stops production until the player settles it or it fizzles out.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::accounting::ExpenseCategory;
+use crate::business::UpgradeState;
+use crate::economy::DayAdvancedEvent;
+use crate::game_state::{AppState, GameState, ReputationChangedEvent};
+use crate::org::Organization;
+
+// === STRIKE BALANCE ===
+/// Fewest workers that can organise a strike
+pub const MIN_STRIKE_WORKERS: u32 = 5;
+/// Daily strike chance with a fully managed workforce
+const BASE_STRIKE_CHANCE: f32 = 0.002;
+/// Extra daily strike chance per worker without a manager
+const UNMANAGED_STRIKE_CHANCE: f32 = 0.0005;
+/// Highest daily strike chance
+const MAX_STRIKE_CHANCE: f32 = 0.03;
+/// Quiet days after a strike before another can start
+const STRIKE_COOLDOWN_DAYS: u32 = 30;
+/// Daily chance an unresolved strike fizzles out on its own
+const FIZZLE_CHANCE: f32 = 0.1;
+/// Reputation lost each day a strike drags on
+const DAILY_STRIKE_REPUTATION: f32 = 0.02;
+
+/// Ways to end a strike
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrikeResolution {
+    /// Give them what they want. Expensive, but people notice.
+    MeetDemands,
+    /// Sit down at the table. Cheaper, but takes a few more days.
+    Negotiate,
+    /// Bring in replacements. Production restarts today; the press is brutal.
+    ReplaceWorkers,
+}
+
+impl StrikeResolution {
+    pub const ALL: [StrikeResolution; 3] = [
+        StrikeResolution::MeetDemands,
+        StrikeResolution::Negotiate,
+        StrikeResolution::ReplaceWorkers,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            StrikeResolution::MeetDemands => "Meet Demands",
+            StrikeResolution::Negotiate => "Negotiate",
+            StrikeResolution::ReplaceWorkers => "Replace Workers",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            StrikeResolution::MeetDemands => "Raises all round. Reputation +0.2.",
+            StrikeResolution::Negotiate => "Back to work in 3 days.",
+            StrikeResolution::ReplaceWorkers => "Back to work now. Reputation -0.5.",
+        }
+    }
+
+    /// Cost per worker
+    fn cost_per_worker(&self) -> f64 {
+        match self {
+            StrikeResolution::MeetDemands => 50.0,
+            StrikeResolution::Negotiate => 20.0,
+            StrikeResolution::ReplaceWorkers => 30.0,
+        }
+    }
+
+    pub fn cost(&self, workers: u32) -> f64 {
+        self.cost_per_worker() * workers as f64
+    }
+
+    /// Reputation change once it's settled
+    pub fn reputation_change(&self) -> f32 {
+        match self {
+            StrikeResolution::MeetDemands => 0.2,
+            StrikeResolution::Negotiate => 0.0,
+            StrikeResolution::ReplaceWorkers => -0.5,
+        }
+    }
+
+    /// Further days on strike after choosing this
+    fn days_to_settle(&self) -> u32 {
+        match self {
+            StrikeResolution::Negotiate => 3,
+            StrikeResolution::MeetDemands | StrikeResolution::ReplaceWorkers => 0,
+        }
+    }
+}
+
+/// A strike underway
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct Strike {
+    pub days: u32,
+    /// Set once talks have started; the strike ends when the countdown does
+    pub negotiating: Option<u32>,
+}
+
+/// The state of labor relations
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct LaborState {
+    pub strike: Option<Strike>,
+    /// Days since the last strike ended
+    pub quiet_days: u32,
+}
+
+impl LaborState {
+    /// Can this resolution be chosen right now?
+    pub fn can_resolve(&self, resolution: StrikeResolution, workers: u32, game_state: &GameState) -> bool {
+        matches!(self.strike, Some(strike) if strike.negotiating.is_none())
+            && game_state.money >= resolution.cost(workers)
+    }
+}
+
+/// How a strike moved along
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrikeStage {
+    Started,
+    /// The player picked a resolution
+    Resolving(StrikeResolution),
+    /// Production is back on
+    Ended(Option<StrikeResolution>),
+}
+
+/// Message fired at each stage of a strike
+#[derive(Event, Message, Clone)]
+pub struct StrikeEvent {
+    pub stage: StrikeStage,
+}
+
+/// Message requesting a strike resolution (sent by the UI)
+#[derive(Event, Message, Clone)]
+pub struct ResolveStrikeEvent {
+    pub resolution: StrikeResolution,
+}
+
+pub struct LaborPlugin;
+
+impl Plugin for LaborPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LaborState>()
+            .add_message::<StrikeEvent>()
+            .add_message::<ResolveStrikeEvent>()
+            .add_systems(
+                Update,
+                (resolve_strike, progress_labor)
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// Pay for the chosen resolution
+fn resolve_strike(
+    mut resolve_events: MessageReader<ResolveStrikeEvent>,
+    upgrades: Res<UpgradeState>,
+    mut game_state: ResMut<GameState>,
+    mut labor: ResMut<LaborState>,
+    mut strike_events: MessageWriter<StrikeEvent>,
+) {
+    for event in resolve_events.read() {
+        let resolution = event.resolution;
+        if !labor.can_resolve(resolution, upgrades.workers, &game_state) {
+            continue;
+        }
+
+        game_state.spend(ExpenseCategory::Payroll, resolution.cost(upgrades.workers));
+        game_state.reputation = (game_state.reputation + resolution.reputation_change()).clamp(0.0, 5.0);
+        strike_events.write(StrikeEvent {
+            stage: StrikeStage::Resolving(resolution),
+        });
+
+        let days = resolution.days_to_settle();
+        if days > 0 {
+            if let Some(strike) = labor.strike.as_mut() {
+                strike.negotiating = Some(days);
+            }
+        } else {
+            end_strike(&mut labor, &mut game_state);
+            strike_events.write(StrikeEvent {
+                stage: StrikeStage::Ended(Some(resolution)),
+            });
+        }
+    }
+}
+
+/// Each game day: maybe start a strike, or move the current one along
+fn progress_labor(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    upgrades: Res<UpgradeState>,
+    org: Res<Organization>,
+    mut game_state: ResMut<GameState>,
+    mut labor: ResMut<LaborState>,
+    mut strike_events: MessageWriter<StrikeEvent>,
+    mut rep_events: MessageWriter<ReputationChangedEvent>,
+) {
+    for _ in day_events.read() {
+        let Some(strike) = labor.strike.as_mut() else {
+            labor.quiet_days += 1;
+            let workers = upgrades.workers;
+            if workers < MIN_STRIKE_WORKERS || labor.quiet_days < STRIKE_COOLDOWN_DAYS {
+                continue;
+            }
+
+            let unmanaged = workers - org.supervised_workers(workers);
+            let chance = (BASE_STRIKE_CHANCE + unmanaged as f32 * UNMANAGED_STRIKE_CHANCE).min(MAX_STRIKE_CHANCE);
+            if rand::random::<f32>() < chance {
+                labor.strike = Some(Strike { days: 0, negotiating: None });
+                game_state.on_strike = true;
+                strike_events.write(StrikeEvent { stage: StrikeStage::Started });
+            }
+            continue;
+        };
+
+        strike.days += 1;
+
+        // Talks under way: back to work when they wrap up
+        if let Some(days_left) = strike.negotiating.as_mut() {
+            *days_left = days_left.saturating_sub(1);
+            if *days_left == 0 {
+                end_strike(&mut labor, &mut game_state);
+                strike_events.write(StrikeEvent {
+                    stage: StrikeStage::Ended(Some(StrikeResolution::Negotiate)),
+                });
+            }
+            continue;
+        }
+
+        // Left alone, the picket line costs goodwill until everyone gives up
+        game_state.reputation = (game_state.reputation - DAILY_STRIKE_REPUTATION).max(0.0);
+        rep_events.write(ReputationChangedEvent {
+            new_reputation: game_state.reputation,
+        });
+        if rand::random::<f32>() < FIZZLE_CHANCE {
+            end_strike(&mut labor, &mut game_state);
+            strike_events.write(StrikeEvent {
+                stage: StrikeStage::Ended(None),
+            });
+        }
+    }
+}
+
+fn end_strike(labor: &mut LaborState, game_state: &mut GameState) {
+    labor.strike = None;
+    labor.quiet_days = 0;
+    game_state.on_strike = false;
+}
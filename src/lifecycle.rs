@@ -0,0 +1,230 @@
+//! Entity lifecycle - transient entities expire, and leaks get noticed
+//!
+//! Anything that belongs to a state is despawned when the state exits: each
+//! screen's marker requires a `DespawnOnExit` for its state, so tagging an
+//! entity with `MainScreen` or `SelectionScreen` is all the cleanup a new
+//! screen needs (give a new screen's marker the same `#[require]`). One-off
+//! entities like voice clips also carry an [`Expires`] so they go away even
+//! if whatever should have cleaned them up never runs. Debug builds also audit
+//! every screen change: the UI left over when a screen opens should be the
+//! same visit after visit, so a count that keeps climbing means something
+//! isn't being despawned, and gets a warning.
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::game_state::{AppState, InGame};
+
+/// Leftover UI entities allowed over the fewest seen for a screen before it counts as a leak
+pub const LEAK_ALLOWANCE: usize = 50;
+
+/// Despawn the entity once the timer runs out
+#[derive(Component)]
+pub struct Expires(pub Timer);
+
+impl Expires {
+    pub fn after(secs: f32) -> Self {
+        Self(Timer::from_seconds(secs, TimerMode::Once))
+    }
+}
+
+/// UI entities left over each time a screen opened
+#[derive(Resource, Default)]
+pub struct LeakAudit {
+    /// Fewest leftovers seen opening each screen
+    baseline: HashMap<AppState, usize>,
+    /// Screens already warned about, so the log isn't flooded
+    pub leaking: Vec<AppState>,
+}
+
+impl LeakAudit {
+    /// Note the leftovers on opening a screen. True the first time they've
+    /// grown past the allowance.
+    pub fn record(&mut self, state: AppState, count: usize) -> bool {
+        let baseline = self.baseline.entry(state).or_insert(count);
+        *baseline = (*baseline).min(count);
+        if count > *baseline + LEAK_ALLOWANCE && !self.leaking.contains(&state) {
+            self.leaking.push(state);
+            return true;
+        }
+        false
+    }
+}
+
+pub struct LifecyclePlugin;
+
+impl Plugin for LifecyclePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, despawn_expired);
+        if cfg!(debug_assertions) {
+            app.init_resource::<LeakAudit>()
+                .add_systems(OnEnter(AppState::ThingSelection), audit_ui_entities)
+                .add_systems(OnEnter(InGame), audit_ui_entities);
+        }
+    }
+}
+
+/// Despawn transient entities whose time is up (real time, so pausing doesn't hold them)
+fn despawn_expired(mut commands: Commands, time: Res<Time<Real>>, mut query: Query<(Entity, &mut Expires)>) {
+    for (entity, mut expires) in &mut query {
+        if expires.0.tick(time.delta()).is_finished() {
+            commands.entity(entity).try_despawn();
+        }
+    }
+}
+
+/// Count the UI still around as a screen opens (the last screen's cleanup has
+/// run, and the new one hasn't spawned yet)
+fn audit_ui_entities(state: Res<State<AppState>>, mut audit: ResMut<LeakAudit>, ui_query: Query<(), With<Node>>) {
+    let count = ui_query.iter().count();
+    if audit.record(*state.get(), count) {
+        warn!(
+            "{} UI entities were left over opening {:?}, and it keeps growing: something isn't despawned when its screen closes",
+            count,
+            state.get()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::state::app::StatesPlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::attract::AttractMode;
+    use crate::ui::{
+        clear_pool, clear_selection_timer, reset_pool_budget, FloatingText, MainScreen, SelectionScreen, UiPool,
+    };
+
+    const CYCLES: usize = 300;
+
+    /// Stand-ins for the two screens: a few nodes under each marker, plus a
+    /// pooled toast on the main screen that's finished and handed back
+    fn screen_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_state(AppState::ThingSelection)
+            .add_computed_state::<InGame>()
+            .init_resource::<AttractMode>()
+            .insert_resource(UiPool::<FloatingText>::new(4))
+            .add_plugins(LifecyclePlugin)
+            .add_systems(First, reset_pool_budget::<FloatingText>)
+            .add_systems(OnEnter(AppState::ThingSelection), |mut commands: Commands| {
+                commands.spawn((Node::default(), SelectionScreen)).with_children(|parent| {
+                    parent.spawn(Node::default());
+                    parent.spawn(Node::default());
+                });
+            })
+            .add_systems(OnExit(AppState::ThingSelection), clear_selection_timer)
+            .add_systems(OnEnter(InGame), spawn_main_screen)
+            .add_systems(Update, finish_toasts.run_if(in_state(AppState::Playing)))
+            .add_systems(OnExit(InGame), clear_pool::<FloatingText>);
+        app
+    }
+
+    fn spawn_main_screen(mut commands: Commands, mut pool: ResMut<UiPool<FloatingText>>) {
+        commands.spawn((Node::default(), MainScreen)).with_children(|parent| {
+            parent.spawn(Node::default());
+        });
+        let toast = pool.acquire(&mut commands).unwrap();
+        commands.entity(toast).insert((
+            Node::default(),
+            FloatingText {
+                elapsed: 0.0,
+                duration: 1.0,
+                from: Vec2::ZERO,
+                rise: 10.0,
+                color: Color::WHITE,
+            },
+            MainScreen,
+        ));
+    }
+
+    /// Toasts finish straight away and go back to the pool
+    fn finish_toasts(
+        mut commands: Commands,
+        mut pool: ResMut<UiPool<FloatingText>>,
+        query: Query<Entity, With<FloatingText>>,
+    ) {
+        for entity in &query {
+            pool.release(&mut commands, entity);
+        }
+    }
+
+    fn go_to(app: &mut App, state: AppState) {
+        app.world_mut().resource_mut::<NextState<AppState>>().set(state);
+        app.update();
+    }
+
+    fn count<F: bevy::ecs::query::QueryFilter>(app: &mut App) -> usize {
+        app.world_mut().query_filtered::<(), F>().iter(app.world()).count()
+    }
+
+    #[test]
+    fn cycling_screens_leaves_nothing_behind() {
+        let mut app = screen_app();
+        app.update();
+        let on_selection = count::<With<Node>>(&mut app);
+        let total = app.world().entities().len();
+
+        for _ in 0..CYCLES {
+            go_to(&mut app, AppState::Playing);
+            go_to(&mut app, AppState::ThingSelection);
+            assert_eq!(count::<With<Node>>(&mut app), on_selection);
+            assert_eq!(count::<With<MainScreen>>(&mut app), 0);
+        }
+        assert_eq!(app.world().entities().len(), total);
+        assert!(app.world().resource::<LeakAudit>().leaking.is_empty());
+    }
+
+    #[test]
+    fn leftovers_that_keep_growing_are_flagged() {
+        let mut app = screen_app();
+        // A screen that forgets its marker on one of its nodes
+        app.add_systems(OnEnter(AppState::Playing), |mut commands: Commands| {
+            commands.spawn(Node::default());
+        });
+        app.update();
+
+        for _ in 0..CYCLES {
+            go_to(&mut app, AppState::Playing);
+            go_to(&mut app, AppState::ThingSelection);
+        }
+        let audit = app.world().resource::<LeakAudit>();
+        assert!(audit.leaking.contains(&AppState::ThingSelection));
+        assert!(audit.leaking.contains(&AppState::Playing));
+    }
+
+    #[test]
+    fn steady_leftovers_are_not_flagged() {
+        let mut audit = LeakAudit::default();
+        for cycle in 0..CYCLES {
+            // Hovering around the same count is fine
+            assert!(!audit.record(AppState::Playing, 120 + cycle % 7));
+        }
+        assert!(audit.record(AppState::Playing, 120 + LEAK_ALLOWANCE + 1));
+        // Only warned about once
+        assert!(!audit.record(AppState::Playing, 120 + LEAK_ALLOWANCE * 2));
+    }
+
+    #[test]
+    fn expired_entities_are_despawned() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+            .add_plugins(LifecyclePlugin);
+        let short = app.world_mut().spawn(Expires::after(0.5)).id();
+        let long = app.world_mut().spawn(Expires::after(2.0)).id();
+
+        for _ in 0..8 {
+            app.update();
+        }
+        assert!(app.world().get_entity(short).is_err());
+        assert!(app.world().get_entity(long).is_ok());
+
+        for _ in 0..20 {
+            app.update();
+        }
+        assert!(app.world().get_entity(long).is_err());
+    }
+}
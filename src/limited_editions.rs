@@ -0,0 +1,279 @@
+//! Limited editions - seasonal drops of a special Thing, for a month only
+//!
+//! Each edition has a calendar month (Pumpkin Spice Thing in October, Love
+//! Thing in February). The player orders a batch beforehand or during the
+//! month; while it lasts, a share of every sale goes out as the edition at a
+//! markup. Collectors talk about a drop when it launches and again when it's
+//! about to sell out, which pushes the trend up. Whatever's left when the month
+//! ends is dead stock and gets cleared for pennies.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::accounting::ExpenseCategory;
+use crate::economy::{DayAdvancedEvent, GameDate, WorldState};
+use crate::game_state::{AppState, GameState};
+use crate::thing_type::ThingRegistry;
+
+/// Share of sales that go out as the edition while it's in stock
+const EDITION_SHARE: f64 = 0.3;
+/// What a batch costs per unit, as a share of the Thing's price
+const UNIT_COST: f64 = 0.6;
+/// What leftover stock fetches at clearance, as a share of the Thing's price
+const CLEARANCE_PRICE: f64 = 0.1;
+/// Trend bump on launch day
+const LAUNCH_HYPE: f32 = 1.1;
+/// Daily trend bump while the batch is nearly gone
+const SCARCITY_HYPE: f32 = 1.01;
+/// Share of the batch left that counts as "nearly gone"
+const SCARCITY_THRESHOLD: f64 = 0.25;
+
+/// The seasonal editions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum Edition {
+    PumpkinSpice,
+    Love,
+}
+
+impl Edition {
+    pub const ALL: [Edition; 2] = [Edition::PumpkinSpice, Edition::Love];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Edition::PumpkinSpice => "Pumpkin Spice Thing",
+            Edition::Love => "Love Thing",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Edition::PumpkinSpice => "It's the Thing, but it smells faintly of autumn. On sale all October.",
+            Edition::Love => "It's the Thing, but pink, with a heart on it. On sale all February.",
+        }
+    }
+
+    /// Calendar month the edition is on sale
+    pub fn month(&self) -> u8 {
+        match self {
+            Edition::PumpkinSpice => 10,
+            Edition::Love => 2,
+        }
+    }
+
+    /// Price multiplier on an edition unit
+    pub fn markup(&self) -> f64 {
+        match self {
+            Edition::PumpkinSpice => 1.5,
+            Edition::Love => 1.4,
+        }
+    }
+
+    pub fn on_sale(&self, date: GameDate) -> bool {
+        date.month() == self.month()
+    }
+
+    /// Batches can be ordered the month before and during the window
+    pub fn can_order(&self, date: GameDate) -> bool {
+        self.on_sale(date) || date.month() % 12 + 1 == self.month()
+    }
+}
+
+/// Batch sizes on offer
+pub const BATCH_SIZES: [u64; 3] = [250, 1_000, 5_000];
+
+/// A batch of an edition, ordered and waiting or on the shelves
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct EditionBatch {
+    pub edition: Edition,
+    pub ordered: u64,
+    pub remaining: u64,
+    /// Whether the month has started (and the launch hype has happened)
+    pub launched: bool,
+}
+
+/// The current batch, if any
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct LimitedEditions {
+    pub batch: Option<EditionBatch>,
+    /// Customers served as of the last update
+    last_customers_served: u64,
+    /// Part of a unit sold but not yet counted
+    pending_units: f64,
+}
+
+impl LimitedEditions {
+    /// The edition currently selling, if it's in stock
+    fn selling(&self) -> Option<&EditionBatch> {
+        self.batch.as_ref().filter(|batch| batch.launched && batch.remaining > 0)
+    }
+
+    /// Multiplier on revenue per Thing while an edition is selling
+    pub fn price_factor(&self) -> f64 {
+        self.selling()
+            .map_or(1.0, |batch| 1.0 + EDITION_SHARE * (batch.edition.markup() - 1.0))
+    }
+
+    pub fn batch_cost(game_state: &GameState, things: &ThingRegistry, units: u64) -> f64 {
+        let price = game_state.thing_type.map_or(0.0, |thing_type| things[thing_type].base_price);
+        units as f64 * price * UNIT_COST
+    }
+
+    pub fn can_order(&self, edition: Edition, units: u64, date: GameDate, game_state: &GameState, things: &ThingRegistry) -> bool {
+        self.batch.is_none() && edition.can_order(date) && game_state.money >= Self::batch_cost(game_state, things, units)
+    }
+}
+
+/// What happened with an edition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditionStage {
+    Ordered,
+    Launched,
+    SellingOut,
+    SoldOut,
+    /// The month ended with this many units unsold
+    Leftover(u64),
+}
+
+/// Message fired as an edition is ordered, launches, and sells out (or doesn't)
+#[derive(Event, Message, Clone)]
+pub struct EditionEvent {
+    pub edition: Edition,
+    pub stage: EditionStage,
+}
+
+/// Message ordering a batch (sent by the UI)
+#[derive(Event, Message, Clone)]
+pub struct OrderEditionEvent {
+    pub edition: Edition,
+    pub units: u64,
+}
+
+pub struct LimitedEditionPlugin;
+
+impl Plugin for LimitedEditionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LimitedEditions>()
+            .add_message::<EditionEvent>()
+            .add_message::<OrderEditionEvent>()
+            .add_systems(
+                Update,
+                (order_edition, sell_editions, run_edition_calendar)
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// Pay for a batch
+fn order_edition(
+    mut order_events: MessageReader<OrderEditionEvent>,
+    world: Res<WorldState>,
+    things: Res<ThingRegistry>,
+    mut game_state: ResMut<GameState>,
+    mut editions: ResMut<LimitedEditions>,
+    mut edition_events: MessageWriter<EditionEvent>,
+) {
+    for event in order_events.read() {
+        if !editions.can_order(event.edition, event.units, world.date, &game_state, &things) {
+            continue;
+        }
+        let cost = LimitedEditions::batch_cost(&game_state, &things, event.units);
+        game_state.spend(ExpenseCategory::Materials, cost);
+        editions.batch = Some(EditionBatch {
+            edition: event.edition,
+            ordered: event.units,
+            remaining: event.units,
+            launched: false,
+        });
+        edition_events.write(EditionEvent {
+            edition: event.edition,
+            stage: EditionStage::Ordered,
+        });
+    }
+}
+
+/// Take the edition's share of each sale off the batch
+fn sell_editions(
+    game_state: Res<GameState>,
+    mut editions: ResMut<LimitedEditions>,
+    mut edition_events: MessageWriter<EditionEvent>,
+) {
+    let sold = game_state.customers_served.saturating_sub(editions.last_customers_served);
+    if sold == 0 {
+        return;
+    }
+    editions.last_customers_served = game_state.customers_served;
+
+    let Some(batch) = editions.selling().copied() else {
+        return;
+    };
+    editions.pending_units += sold as f64 * EDITION_SHARE;
+    let units = (editions.pending_units.floor() as u64).min(batch.remaining);
+    editions.pending_units -= units as f64;
+    let Some(batch) = editions.batch.as_mut() else {
+        return;
+    };
+
+    let was_scarce = batch.remaining as f64 <= batch.ordered as f64 * SCARCITY_THRESHOLD;
+    batch.remaining -= units;
+    let stage = if batch.remaining == 0 {
+        Some(EditionStage::SoldOut)
+    } else if !was_scarce && batch.remaining as f64 <= batch.ordered as f64 * SCARCITY_THRESHOLD {
+        Some(EditionStage::SellingOut)
+    } else {
+        None
+    };
+    if let Some(stage) = stage {
+        edition_events.write(EditionEvent {
+            edition: batch.edition,
+            stage,
+        });
+    }
+}
+
+/// Each game day: launch a batch when its month starts, hype it, and clear what's left when it ends
+fn run_edition_calendar(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    mut world: ResMut<WorldState>,
+    things: Res<ThingRegistry>,
+    mut game_state: ResMut<GameState>,
+    mut editions: ResMut<LimitedEditions>,
+    mut edition_events: MessageWriter<EditionEvent>,
+) {
+    for event in day_events.read() {
+        let Some(batch) = editions.batch.as_mut() else {
+            continue;
+        };
+        let edition = batch.edition;
+
+        if edition.on_sale(event.date) {
+            if !batch.launched {
+                batch.launched = true;
+                world.trend_factor = (world.trend_factor * LAUNCH_HYPE).min(2.0);
+                edition_events.write(EditionEvent {
+                    edition,
+                    stage: EditionStage::Launched,
+                });
+            } else if batch.remaining > 0 && batch.remaining as f64 <= batch.ordered as f64 * SCARCITY_THRESHOLD {
+                // Collectors scramble for the last few
+                world.trend_factor = (world.trend_factor * SCARCITY_HYPE).min(2.0);
+            }
+            continue;
+        }
+        if !batch.launched {
+            continue;
+        }
+
+        // The month is over; whatever's left goes to the clearance bin
+        let leftover = batch.remaining;
+        editions.batch = None;
+        if leftover > 0 {
+            let price = game_state.thing_type.map_or(0.0, |thing_type| things[thing_type].base_price);
+            game_state.money += leftover as f64 * price * CLEARANCE_PRICE;
+            edition_events.write(EditionEvent {
+                edition,
+                stage: EditionStage::Leftover(leftover),
+            });
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! Loading - reading and preloading content before the selection screen
+//!
+//! The game opens on a loading screen. Dialogue and quests are read from
+//! their files as it opens (the Thing definitions, balance included, were
+//! read before the window was), and then every font, scenery layer and voice
+//! clip on disk is loaded and held on to, so nothing hitches or pops in the
+//! first time it's needed. An asset that fails to load counts as done; the
+//! code that uses it falls back as usual. Once everything is in, or it's
+//! taken too long, the selection screen opens.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use std::fs;
+use std::path::Path;
+use crate::dialogue::{load_dialogues, DialogueDatabase};
+use crate::game_state::AppState;
+use crate::thing_type::ThingRegistry;
+use crate::ui::scenery_layers;
+
+/// Fonts to preload, relative to `assets/`
+const FONT_DIR: &str = "fonts";
+/// Least time on the loading screen, so it doesn't just flash up
+const MIN_LOADING_SECS: f32 = 1.0;
+/// Most time on the loading screen; whatever's still loading carries on in the background
+const MAX_LOADING_SECS: f32 = 20.0;
+
+/// What's being preloaded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreloadKind {
+    Fonts,
+    Scenery,
+    Voice,
+}
+
+impl PreloadKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PreloadKind::Fonts => "fonts",
+            PreloadKind::Scenery => "scenery",
+            PreloadKind::Voice => "Terry's voice",
+        }
+    }
+}
+
+/// Assets loaded up front, held so they stay loaded for the whole session
+#[derive(Resource, Default)]
+pub struct Preloads {
+    handles: Vec<(PreloadKind, UntypedHandle)>,
+    /// How many have loaded (or failed to)
+    pub done: usize,
+    /// What's still loading, if anything
+    pub loading: Option<PreloadKind>,
+    /// Real seconds on the loading screen
+    pub elapsed: f32,
+}
+
+impl Preloads {
+    pub fn total(&self) -> usize {
+        self.handles.len()
+    }
+
+    /// 0 to 1
+    pub fn progress(&self) -> f32 {
+        if self.handles.is_empty() {
+            1.0
+        } else {
+            self.done as f32 / self.handles.len() as f32
+        }
+    }
+}
+
+pub struct LoadingPlugin;
+
+impl Plugin for LoadingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Preloads>()
+            .add_systems(OnEnter(AppState::Loading), queue_preloads.after(load_dialogues))
+            .add_systems(Update, track_preloads.run_if(in_state(AppState::Loading)));
+    }
+}
+
+/// Asset paths under an `assets/` folder with one of these extensions
+fn files_in(dir: &str, extensions: &[&str]) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(Path::new("assets").join(dir)) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<String> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .and_then(|extension| extension.to_str())
+                .is_some_and(|extension| extensions.contains(&extension))
+        })
+        .filter_map(|path| Some(format!("{}/{}", dir, path.file_name()?.to_str()?)))
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// Start loading every font, scenery layer and voice clip on disk
+fn queue_preloads(
+    asset_server: Res<AssetServer>,
+    things: Res<ThingRegistry>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut preloads: ResMut<Preloads>,
+) {
+    let mut handles = Vec::new();
+    for path in files_in(FONT_DIR, &["ttf", "otf"]) {
+        handles.push((PreloadKind::Fonts, asset_server.load::<Font>(path).untyped()));
+    }
+    for thing_type in things.all() {
+        for (_, path, _) in scenery_layers(&things[thing_type]) {
+            handles.push((PreloadKind::Scenery, asset_server.load::<Image>(path).untyped()));
+        }
+    }
+
+    // Lines can share a clip; load each once (missing clips are left to play as text)
+    let mut clips: Vec<&str> = dialogue_db.by_id.values().filter_map(|line| line.audio.as_deref()).collect();
+    clips.sort();
+    clips.dedup();
+    for path in clips {
+        if Path::new("assets").join(path).exists() {
+            handles.push((PreloadKind::Voice, asset_server.load::<AudioSource>(path.to_string()).untyped()));
+        }
+    }
+
+    info!("Preloading {} assets", handles.len());
+    *preloads = Preloads {
+        handles,
+        ..default()
+    };
+}
+
+/// Count what's in, and open the selection screen once everything is
+fn track_preloads(
+    time: Res<Time<Real>>,
+    asset_server: Res<AssetServer>,
+    mut preloads: ResMut<Preloads>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    preloads.elapsed += time.delta_secs();
+
+    let mut done = 0;
+    let mut loading = None;
+    for (kind, handle) in &preloads.handles {
+        let state = asset_server.load_state(handle.id());
+        if state.is_loaded() || state.is_failed() {
+            done += 1;
+        } else if loading.is_none() {
+            loading = Some(*kind);
+        }
+    }
+    preloads.done = done;
+    preloads.loading = loading;
+
+    if preloads.elapsed >= MAX_LOADING_SECS {
+        warn!(
+            "Still preloading {} of {} assets after {}s, opening the game anyway",
+            preloads.total() - done,
+            preloads.total(),
+            MAX_LOADING_SECS
+        );
+        next_state.set(AppState::ThingSelection);
+    } else if loading.is_none() && preloads.elapsed >= MIN_LOADING_SECS {
+        next_state.set(AppState::ThingSelection);
+    }
+}
@@ -0,0 +1,173 @@
+//! Macro dynamics - how the economic indicators pull on each other
+//!
+//! Historical events knock the indicators around and a daily random walk
+//! jiggles them; this is what happens in between. Unemployment heals slowly
+//! (faster when consumers are confident), high unemployment drags confidence
+//! down, confidence far from neutral snaps back, and inflation eases toward
+//! target. Inflation that stays high for months builds up "pressure" that eats
+//! into what customers can afford, hitting budget buyers hardest.
+
+use crate::economy::WorldState;
+use crate::thing_type::ThingDefinition;
+
+/// Unemployment the economy settles back to
+pub const NATURAL_UNEMPLOYMENT: f32 = 0.05;
+/// Share of the gap to natural unemployment closed each day
+const UNEMPLOYMENT_RECOVERY: f32 = 0.0015;
+/// Daily change in unemployment per point of confidence above neutral (confident customers, hiring firms)
+const HIRING_FROM_CONFIDENCE: f32 = 0.00005;
+/// Daily confidence lost per point of unemployment above natural
+const UNEMPLOYMENT_DRAG: f32 = 0.03;
+/// Share of the gap to neutral confidence closed each day
+const CONFIDENCE_REVERSION: f32 = 0.01;
+/// How far from neutral confidence can wander before it's "extreme"
+const CONFIDENCE_BAND: f32 = 0.25;
+/// Extra reversion per point of confidence beyond the band
+const EXTREME_REVERSION: f32 = 0.05;
+/// Inflation the central bank aims for
+pub const TARGET_INFLATION: f32 = 0.02;
+/// Share of the gap to target inflation closed each day
+const INFLATION_EASING: f32 = 0.002;
+/// Days inflation pressure takes to build up (and to fade)
+const PRESSURE_DAYS: f32 = 365.0;
+/// Least spending power inflation can leave a segment with
+const MIN_SPENDING_POWER: f64 = 0.5;
+
+/// Apply one day of coupling (after the day's events and random drift)
+pub fn couple(world: &mut WorldState) {
+    let excess_unemployment = (world.unemployment_rate - NATURAL_UNEMPLOYMENT).max(0.0);
+
+    // Jobs come back slowly, quicker when people are spending
+    world.unemployment_rate += (NATURAL_UNEMPLOYMENT - world.unemployment_rate) * UNEMPLOYMENT_RECOVERY
+        - (world.consumer_confidence - 1.0) * HIRING_FROM_CONFIDENCE;
+
+    // Nobody feels great while their friends are out of work, and no mood lasts forever
+    let distance = (world.consumer_confidence - 1.0).abs();
+    let reversion = CONFIDENCE_REVERSION + EXTREME_REVERSION * (distance - CONFIDENCE_BAND).max(0.0);
+    world.consumer_confidence += (1.0 - world.consumer_confidence) * reversion - excess_unemployment * UNEMPLOYMENT_DRAG;
+
+    // Rate hikes work, eventually; meanwhile prices stay up
+    world.inflation_rate += (TARGET_INFLATION - world.inflation_rate) * INFLATION_EASING;
+    world.inflation_pressure += (world.inflation_rate - TARGET_INFLATION - world.inflation_pressure) / PRESSURE_DAYS;
+
+    world.consumer_confidence = world.consumer_confidence.clamp(0.5, 1.5);
+    world.unemployment_rate = world.unemployment_rate.clamp(0.03, 0.25);
+    world.inflation_rate = world.inflation_rate.clamp(0.01, 0.15);
+}
+
+/// Multiplier on would-be customers for a Thing, after prolonged inflation
+pub fn spending_power(world: &WorldState, thing: &ThingDefinition) -> f64 {
+    let pressure = world.inflation_pressure.max(0.0) as f64;
+    (1.0 - pressure * thing.inflation_sensitivity).max(MIN_SPENDING_POWER)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::DifficultyConfig;
+    use crate::economy::{advance_one_day, GameDate};
+    use crate::thing_type::{ThingRegistry, ThingType};
+
+    /// Run the world from 2012 to `until`, calling `each_day` after every day
+    fn run_until(seed: u32, chaos_variance: f32, until: GameDate, mut each_day: impl FnMut(&WorldState)) -> WorldState {
+        let mut world = WorldState {
+            seed,
+            ..Default::default()
+        };
+        while world.date < until {
+            advance_one_day(&mut world, chaos_variance, &DifficultyConfig::default());
+            each_day(&world);
+        }
+        world
+    }
+
+    /// Mean of an indicator over a stretch of days
+    fn average(days: &[(GameDate, f32)], from: GameDate, to: GameDate) -> f32 {
+        let values: Vec<f32> = days.iter().filter(|(date, _)| *date >= from && *date < to).map(|(_, v)| *v).collect();
+        values.iter().sum::<f32>() / values.len() as f32
+    }
+
+    #[test]
+    fn indicators_stay_in_plausible_ranges() {
+        for seed in [0, 7, 1234, 99_999] {
+            for chaos_variance in [0.5, 1.0, 2.0] {
+                run_until(seed, chaos_variance, GameDate::new(2026, 1, 1), |world| {
+                    assert!((0.5..=1.5).contains(&world.consumer_confidence), "confidence {}", world.consumer_confidence);
+                    assert!((0.03..=0.25).contains(&world.unemployment_rate), "unemployment {}", world.unemployment_rate);
+                    assert!((0.01..=0.15).contains(&world.inflation_rate), "inflation {}", world.inflation_rate);
+                });
+            }
+        }
+    }
+
+    #[test]
+    fn confidence_hovers_around_neutral_over_the_years() {
+        for seed in [0, 42, 31_337] {
+            let mut confidence = Vec::new();
+            run_until(seed, 1.0, GameDate::new(2026, 1, 1), |world| confidence.push((world.date, world.consumer_confidence)));
+            // History's shocks are mostly bad news, so the long-run average sits a little under neutral
+            let mean = average(&confidence, GameDate::new(2012, 1, 1), GameDate::new(2026, 1, 1));
+            assert!((0.7..=1.15).contains(&mean), "seed {} averaged {}", seed, mean);
+        }
+    }
+
+    #[test]
+    fn unemployment_recovers_after_2012_and_after_the_pandemic() {
+        let mut unemployment = Vec::new();
+        run_until(0, 1.0, GameDate::new(2025, 1, 1), |world| unemployment.push((world.date, world.unemployment_rate)));
+        let in_2012 = average(&unemployment, GameDate::new(2012, 1, 1), GameDate::new(2013, 1, 1));
+        let in_2019 = average(&unemployment, GameDate::new(2019, 1, 1), GameDate::new(2020, 1, 1));
+        let spring_2020 = average(&unemployment, GameDate::new(2020, 4, 1), GameDate::new(2020, 6, 1));
+        let in_2024 = average(&unemployment, GameDate::new(2024, 1, 1), GameDate::new(2025, 1, 1));
+        assert!(in_2019 < in_2012 && in_2019 < 0.06, "2012 {} -> 2019 {}", in_2012, in_2019);
+        assert!(spring_2020 > 0.1, "spring 2020 {}", spring_2020);
+        assert!(in_2024 < 0.06, "2024 {}", in_2024);
+    }
+
+    #[test]
+    fn high_unemployment_drags_confidence() {
+        let mut confidence = Vec::new();
+        run_until(0, 1.0, GameDate::new(2021, 1, 1), |world| confidence.push((world.date, world.consumer_confidence)));
+        let in_2019 = average(&confidence, GameDate::new(2019, 1, 1), GameDate::new(2020, 1, 1));
+        let autumn_2020 = average(&confidence, GameDate::new(2020, 9, 1), GameDate::new(2020, 11, 1));
+        assert!(autumn_2020 < in_2019, "2019 {} vs autumn 2020 {}", in_2019, autumn_2020);
+    }
+
+    #[test]
+    fn extreme_confidence_mean_reverts() {
+        let mut world = WorldState {
+            consumer_confidence: 1.5,
+            unemployment_rate: NATURAL_UNEMPLOYMENT,
+            ..Default::default()
+        };
+        for _ in 0..60 {
+            couple(&mut world);
+        }
+        assert!(world.consumer_confidence < 1.3, "still at {}", world.consumer_confidence);
+    }
+
+    #[test]
+    fn prolonged_inflation_hits_budget_buyers_hardest() {
+        let things = ThingRegistry::default();
+        let world = run_until(0, 1.0, GameDate::new(2023, 1, 1), |_| {});
+        let cheap = spending_power(&world, &things[ThingType::CHEAP]);
+        let expensive = spending_power(&world, &things[ThingType::EXPENSIVE]);
+        assert!(cheap < 0.9, "cheap {}", cheap);
+        assert!(cheap < expensive && expensive > 0.95, "cheap {} vs expensive {}", cheap, expensive);
+
+        // Before the inflation years nobody's squeezed
+        let world = run_until(0, 1.0, GameDate::new(2020, 1, 1), |_| {});
+        for thing_type in things.all() {
+            assert!(spending_power(&world, &things[thing_type]) > 0.99);
+        }
+    }
+
+    #[test]
+    fn inflation_pressure_fades_once_prices_calm_down() {
+        let mut pressure = Vec::new();
+        run_until(0, 1.0, GameDate::new(2026, 1, 1), |world| pressure.push((world.date, world.inflation_pressure)));
+        let in_2022 = average(&pressure, GameDate::new(2022, 7, 1), GameDate::new(2023, 1, 1));
+        let in_2025 = average(&pressure, GameDate::new(2025, 7, 1), GameDate::new(2026, 1, 1));
+        assert!(in_2025 < in_2022 / 2.0, "2022 {} -> 2025 {}", in_2022, in_2025);
+    }
+}
@@ -1,29 +1,138 @@
 //! Thing Simulator 2012
 //! A comedy business simulator featuring Terry, an anthropomorphic hot dog with an MBA
 
+mod accounting;
+mod agent;
+mod ai_boom;
+mod attract;
+mod automation;
+#[cfg(all(feature = "background", not(target_arch = "wasm32")))]
+mod background;
+mod balance;
+mod bench;
+mod bundles;
 mod business;
+mod camera;
 mod clicker;
+mod clock;
+mod cosmetics;
+mod counterfeits;
+mod crypto;
+mod currency;
+mod debates;
 mod dialogue;
+mod difficulty;
+mod eco;
+mod event_log;
 mod economy;
+mod fads;
+mod fortune;
+mod game_clock;
 mod game_state;
+mod gift_cards;
+mod grace_period;
+mod hud;
+mod input;
+mod labor;
+mod lifecycle;
+mod limited_editions;
+mod loading;
+mod macro_dynamics;
+mod market;
 mod marketing;
+mod merch;
+mod migration;
+mod objectives;
+mod org;
+mod pandemic;
+mod parallel;
+mod pivot;
+mod power;
+mod price_war;
+mod profile;
+mod quests;
+mod recovery;
+mod replay;
+mod reputation;
+mod research;
+mod returns;
+mod rewards;
+mod save;
+mod savings;
+mod seminars;
+mod session;
+mod settings;
+mod share_code;
+mod sim;
+mod snapshot;
+mod social;
+mod spending;
+mod stats;
+mod suppliers;
+mod surveys;
 mod terry;
 mod thing_type;
+mod tutorial;
 mod ui;
+mod utilities;
+mod vacation;
+mod venues;
+mod voice;
+mod wellbeing;
+mod word_of_mouth;
+mod wrapped;
 
 use bevy::prelude::*;
-use game_state::{AppState, GameStatePlugin};
-use business::BusinessPlugin;
-use clicker::ClickerPlugin;
+use game_state::AppState;
+use attract::AttractPlugin;
+use balance::Balance;
+use bench::BenchPlugin;
+use camera::CameraFxPlugin;
+use clock::ClockPlugin;
+use cosmetics::CosmeticsPlugin;
+use debates::DebatePlugin;
 use dialogue::DialoguePlugin;
-use economy::EconomyPlugin;
-use marketing::MarketingPlugin;
+use event_log::EventLogPlugin;
+use grace_period::GracePeriodPlugin;
+use input::InputPlugin;
+use lifecycle::LifecyclePlugin;
+use loading::LoadingPlugin;
+use merch::MerchPlugin;
+use objectives::ObjectivesPlugin;
+use parallel::ParallelPlugin;
+use power::PowerPlugin;
+use profile::ProfilePlugin;
+use quests::QuestPlugin;
+use replay::ReplayPlugin;
+use save::SavePlugin;
+use session::SessionPlugin;
+use settings::SettingsPlugin;
+use spending::SpendingPlugin;
+use sim::SimulationPlugins;
+use surveys::SurveyPlugin;
 use terry::TerryPlugin;
+use thing_type::ThingTypePlugin;
+use tutorial::TutorialPlugin;
 use ui::UiPlugin;
+use vacation::VacationPlugin;
+use voice::VoicePlugin;
+use wrapped::WrappedPlugin;
 
 fn main() {
-    App::new()
-        .add_plugins(DefaultPlugins.set(WindowPlugin {
+    // `--balance` plays bot strategies headlessly and reports the outcomes
+    if let Some(balance) = Balance::from_args() {
+        balance.run();
+        return;
+    }
+
+    // `--check-dialogue` reports malformed dialogue lines without opening the game
+    if std::env::args().any(|arg| arg == "--check-dialogue") {
+        let clean = dialogue::check_dialogue_files();
+        std::process::exit(if clean { 0 } else { 1 });
+    }
+
+    let mut app = App::new();
+    app.add_plugins(DefaultPlugins.set(WindowPlugin {
             primary_window: Some(Window {
                 title: "Thing Simulator 2012".into(),
                 resolution: (1024, 768).into(),
@@ -32,18 +141,37 @@ fn main() {
             ..default()
         }))
         .init_state::<AppState>()
+        .add_plugins(ThingTypePlugin)
+        .add_plugins(SimulationPlugins)
         .add_plugins((
-            GameStatePlugin,
-            EconomyPlugin,
-            MarketingPlugin,
+            SettingsPlugin,
+            InputPlugin,
             DialoguePlugin,
             TerryPlugin,
-            BusinessPlugin,
-            ClickerPlugin,
+            VoicePlugin,
+            PowerPlugin,
+            CameraFxPlugin,
             UiPlugin,
+            ClockPlugin,
+            SessionPlugin,
+            CosmeticsPlugin,
+            MerchPlugin,
+            ProfilePlugin,
+            SavePlugin,
+            AttractPlugin,
         ))
-        .add_systems(Startup, setup_camera)
-        .run();
+        .add_plugins((EventLogPlugin, ReplayPlugin, TutorialPlugin, VacationPlugin, DebatePlugin, SurveyPlugin, QuestPlugin, ObjectivesPlugin, ParallelPlugin, WrappedPlugin, LifecyclePlugin, LoadingPlugin, SpendingPlugin, GracePeriodPlugin))
+        .add_systems(Startup, setup_camera);
+
+    #[cfg(all(feature = "background", not(target_arch = "wasm32")))]
+    app.add_plugins(background::BackgroundPlugin);
+
+    // `--bench` runs a worst-case scenario and reports frame times
+    if let Some(bench) = BenchPlugin::from_args() {
+        app.add_plugins(bench);
+    }
+
+    app.run();
 }
 
 fn setup_camera(mut commands: Commands) {
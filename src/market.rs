@@ -0,0 +1,107 @@
+//! Market share - who is selling to the total addressable market, week by week
+//!
+//! The addressable market grows with the world's population. Each week (closed
+//! on Monday, as the next one starts) the player's sales are compared with it
+//! and with three competitors, whose combined share follows
+//! `competitor_pressure` (and grows while they're cutting prices in a price
+//! war). Whatever the player and the competitors serve between them is the
+//! market's saturation, which feeds back into demand.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::economy::{DayAdvancedEvent, GameDate, WeekStartedEvent, WorldState};
+use crate::game_state::{AppState, GameState};
+use crate::price_war::PriceWarState;
+
+/// Things wanted per person per day
+const DEMAND_PER_PERSON: f64 = 0.000_01;
+/// Competitors' combined share of the market at a competitor pressure of 0.5
+const COMPETITOR_BASE_SHARE: f32 = 0.25;
+/// Weeks of history kept for the chart
+pub const MAX_WEEKS: usize = 26;
+
+/// The competition, and how they split their share
+pub const COMPETITORS: [(&str, f32); 3] = [("ThingMart", 0.45), ("Big Thing Co.", 0.35), ("Thingz Direct", 0.2)];
+
+/// One week's split of the addressable market (fractions, adding up to at most 1)
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct WeeklyShare {
+    /// The last day of the week
+    pub date: GameDate,
+    pub player: f32,
+    pub competitors: [f32; 3],
+}
+
+impl WeeklyShare {
+    /// The part of the market nobody is serving
+    pub fn untapped(&self) -> f32 {
+        (1.0 - self.player - self.competitors.iter().sum::<f32>()).max(0.0)
+    }
+}
+
+/// Weekly market share history and the week in progress
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct MarketShare {
+    pub weeks: Vec<WeeklyShare>,
+    /// Customers served before this week started
+    week_start_customers: u64,
+    /// Things the market wanted so far this week
+    week_demand: f64,
+}
+
+impl MarketShare {
+    pub fn latest(&self) -> Option<&WeeklyShare> {
+        self.weeks.last()
+    }
+}
+
+pub struct MarketPlugin;
+
+impl Plugin for MarketPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MarketShare>()
+            .add_systems(Update, tally_market_share.run_if(in_state(AppState::Playing)));
+    }
+}
+
+/// Close the week every Monday, then add up the day's demand
+fn tally_market_share(
+    mut week_events: MessageReader<WeekStartedEvent>,
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    game_state: Res<GameState>,
+    mut world: ResMut<WorldState>,
+    mut market: ResMut<MarketShare>,
+    price_war: Res<PriceWarState>,
+) {
+    for event in week_events.read() {
+        if market.week_demand <= 0.0 {
+            continue;
+        }
+
+        let sold = game_state.customers_served.saturating_sub(market.week_start_customers);
+        let player = (sold as f64 / market.week_demand).min(1.0) as f32;
+        // Competitors cutting prices in a price war sell more of the market
+        let competitor_total = (COMPETITOR_BASE_SHARE * (0.5 + world.competitor_pressure) * price_war.competitor_volume())
+            .min(1.0 - player);
+        let share = WeeklyShare {
+            date: event.date.add_days(-1),
+            player,
+            competitors: COMPETITORS.map(|(_, split)| competitor_total * split),
+        };
+
+        // Everyone's sales use up the market
+        world.market_saturation = 1.0 - share.untapped();
+
+        market.weeks.push(share);
+        if market.weeks.len() > MAX_WEEKS {
+            market.weeks.remove(0);
+        }
+        market.week_start_customers = game_state.customers_served;
+        market.week_demand = 0.0;
+    }
+
+    for _ in day_events.read() {
+        market.week_demand += world.global_population * DEMAND_PER_PERSON;
+    }
+}
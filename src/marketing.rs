@@ -3,9 +3,16 @@
 //! These are the things the player CAN control, unlike the invisible world forces.
 
 use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use serde::{Deserialize, Serialize};
+use crate::accounting::ExpenseCategory;
+use crate::economy::DayAdvancedEvent;
+use crate::game_state::{AppState, GameState};
+use crate::reputation::{ReputationTier, ReputationTierChangedEvent};
 
 /// All the marketing and business levers the player can pull
-#[derive(Resource)]
+#[derive(Resource, Reflect)]
+#[reflect(Resource, Default)]
 pub struct MarketingState {
     // === ADVERTISING ===
     /// Newspaper ads (cheap, local reach)
@@ -68,9 +75,17 @@ pub struct MarketingState {
     pub loyalty_program: u8,
     /// Referral bonus amount
     pub referral_bonus: f32,
+
+    // === ERA ===
+    /// How far billboards and retail placement reach (cut during the pandemic)
+    pub physical_reach: f32,
+    /// How far internet ads and influencers reach (boosted during the pandemic)
+    pub online_reach: f32,
+    /// How hard every ad works (raised by generative ad copy)
+    pub ad_copy: f32,
 }
 
-#[derive(Clone, Default)]
+#[derive(Clone, Default, Reflect)]
 pub struct AdvertisingCampaign {
     /// Is this campaign active?
     pub active: bool,
@@ -94,7 +109,7 @@ impl AdvertisingCampaign {
     }
 }
 
-#[derive(Clone, Default)]
+#[derive(Clone, Default, Reflect)]
 pub struct InfluencerDeal {
     /// Is there an active deal?
     pub active: bool,
@@ -118,7 +133,7 @@ impl InfluencerDeal {
     }
 }
 
-#[derive(Clone, Default)]
+#[derive(Clone, Default, Reflect)]
 pub struct BackroomDeal {
     /// Is the deal active?
     pub active: bool,
@@ -142,7 +157,7 @@ impl BackroomDeal {
     }
 }
 
-#[derive(Clone, Default)]
+#[derive(Clone, Default, Reflect)]
 pub struct ManipulationTactic {
     /// Is this tactic in use?
     pub active: bool,
@@ -169,26 +184,31 @@ impl Default for MarketingState {
         Self {
             // Advertising - all start inactive
             newspaper_ads: AdvertisingCampaign {
+                daily_spend: 10.0,
                 effectiveness: 0.5,
                 reach: 0.1,
                 ..default()
             },
             radio_ads: AdvertisingCampaign {
+                daily_spend: 25.0,
                 effectiveness: 0.7,
                 reach: 0.3,
                 ..default()
             },
             tv_ads: AdvertisingCampaign {
+                daily_spend: 250.0,
                 effectiveness: 1.5,
                 reach: 1.0,
                 ..default()
             },
             internet_ads: AdvertisingCampaign {
+                daily_spend: 20.0,
                 effectiveness: 1.0,
                 reach: 0.5,
                 ..default()
             },
             billboard_ads: AdvertisingCampaign {
+                daily_spend: 15.0,
                 effectiveness: 0.3,
                 reach: 0.2,
                 ..default()
@@ -275,6 +295,11 @@ impl Default for MarketingState {
             // Loyalty
             loyalty_program: 0,
             referral_bonus: 0.0,
+
+            // Era
+            physical_reach: 1.0,
+            online_reach: 1.0,
+            ad_copy: 1.0,
         }
     }
 }
@@ -285,19 +310,21 @@ impl MarketingState {
         let mut boost = 1.0;
 
         // Advertising contributions
-        boost += self.newspaper_ads.contribution() * 0.001;
-        boost += self.radio_ads.contribution() * 0.002;
-        boost += self.tv_ads.contribution() * 0.005;
-        boost += self.internet_ads.contribution() * 0.003;
-        boost += self.billboard_ads.contribution() * 0.001;
+        let mut ads = 0.0;
+        ads += self.newspaper_ads.contribution() * 0.001;
+        ads += self.radio_ads.contribution() * 0.002;
+        ads += self.tv_ads.contribution() * 0.005;
+        ads += self.internet_ads.contribution() * 0.003 * self.online_reach;
+        ads += self.billboard_ads.contribution() * 0.001 * self.physical_reach;
+        boost += ads * self.ad_copy;
 
         // Influencer contributions
-        boost += self.micro_influencers.contribution() * 0.05;
-        boost += self.mid_influencers.contribution() * 0.1;
-        boost += self.celebrity_endorsement.contribution() * 0.3;
+        boost += self.micro_influencers.contribution() * 0.05 * self.online_reach;
+        boost += self.mid_influencers.contribution() * 0.1 * self.online_reach;
+        boost += self.celebrity_endorsement.contribution() * 0.3 * self.online_reach;
 
         // Backroom deals
-        boost *= 1.0 + self.retail_placement.contribution() * 0.1;
+        boost *= 1.0 + self.retail_placement.contribution() * 0.1 * self.physical_reach;
         boost *= 1.0 + self.distributor_deals.contribution() * 0.15;
         boost *= 1.0 + self.supplier_exclusivity.contribution() * 0.05;
         boost *= 1.0 + self.consulting_fees.contribution() * 0.2;
@@ -337,6 +364,11 @@ impl MarketingState {
         if self.internet_ads.active { costs += self.internet_ads.daily_spend; }
         if self.billboard_ads.active { costs += self.billboard_ads.daily_spend; }
 
+        // Influencers post once a day while the deal lasts
+        if self.micro_influencers.contribution() > 0.0 { costs += self.micro_influencers.cost_per_post; }
+        if self.mid_influencers.contribution() > 0.0 { costs += self.mid_influencers.cost_per_post; }
+        if self.celebrity_endorsement.contribution() > 0.0 { costs += self.celebrity_endorsement.cost_per_post; }
+
         // Monthly costs converted to daily
         if self.retail_placement.active { costs += self.retail_placement.monthly_cost / 30.0; }
         if self.distributor_deals.active { costs += self.distributor_deals.monthly_cost / 30.0; }
@@ -347,10 +379,306 @@ impl MarketingState {
     }
 }
 
+/// Every individual marketing channel the player can switch on or off
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum MarketingChannel {
+    NewspaperAds,
+    RadioAds,
+    TvAds,
+    InternetAds,
+    BillboardAds,
+    MicroInfluencers,
+    MidInfluencers,
+    CelebrityEndorsement,
+    RetailPlacement,
+    DistributorDeals,
+    SupplierExclusivity,
+    ConsultingFees,
+    ArtificialScarcity,
+    Astroturfing,
+    ReviewManipulation,
+    CompetitorSabotage,
+}
+
+impl MarketingChannel {
+    pub const ALL: [MarketingChannel; 16] = [
+        MarketingChannel::NewspaperAds,
+        MarketingChannel::RadioAds,
+        MarketingChannel::TvAds,
+        MarketingChannel::InternetAds,
+        MarketingChannel::BillboardAds,
+        MarketingChannel::MicroInfluencers,
+        MarketingChannel::MidInfluencers,
+        MarketingChannel::CelebrityEndorsement,
+        MarketingChannel::RetailPlacement,
+        MarketingChannel::DistributorDeals,
+        MarketingChannel::SupplierExclusivity,
+        MarketingChannel::ConsultingFees,
+        MarketingChannel::ArtificialScarcity,
+        MarketingChannel::Astroturfing,
+        MarketingChannel::ReviewManipulation,
+        MarketingChannel::CompetitorSabotage,
+    ];
+
+    /// The paid advertising channels
+    pub const ADVERTISING: [MarketingChannel; 5] = [
+        MarketingChannel::NewspaperAds,
+        MarketingChannel::RadioAds,
+        MarketingChannel::TvAds,
+        MarketingChannel::InternetAds,
+        MarketingChannel::BillboardAds,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MarketingChannel::NewspaperAds => "Newspaper Ads",
+            MarketingChannel::RadioAds => "Radio Spots",
+            MarketingChannel::TvAds => "TV Commercials",
+            MarketingChannel::InternetAds => "Internet Ads",
+            MarketingChannel::BillboardAds => "Billboards",
+            MarketingChannel::MicroInfluencers => "Micro-Influencers",
+            MarketingChannel::MidInfluencers => "Mid-Tier Influencers",
+            MarketingChannel::CelebrityEndorsement => "Celebrity Endorsement",
+            MarketingChannel::RetailPlacement => "Retail Placement",
+            MarketingChannel::DistributorDeals => "Distributor Deals",
+            MarketingChannel::SupplierExclusivity => "Supplier Exclusivity",
+            MarketingChannel::ConsultingFees => "\"Consulting Fees\"",
+            MarketingChannel::ArtificialScarcity => "Artificial Scarcity",
+            MarketingChannel::Astroturfing => "Astroturfing",
+            MarketingChannel::ReviewManipulation => "Review Manipulation",
+            MarketingChannel::CompetitorSabotage => "Competitor Sabotage",
+        }
+    }
+
+    /// Premium channels only take your call once you have a full 5 stars
+    pub fn is_premium(&self) -> bool {
+        matches!(self, MarketingChannel::TvAds | MarketingChannel::CelebrityEndorsement)
+    }
+
+    /// Channels that depend on a partner agreeing to work with you
+    pub fn is_partnership(&self) -> bool {
+        matches!(
+            self,
+            MarketingChannel::MicroInfluencers
+                | MarketingChannel::MidInfluencers
+                | MarketingChannel::CelebrityEndorsement
+                | MarketingChannel::RetailPlacement
+                | MarketingChannel::DistributorDeals
+                | MarketingChannel::SupplierExclusivity
+                | MarketingChannel::ConsultingFees
+        )
+    }
+
+    /// Would this channel sign on at the given reputation tier?
+    pub fn available_at(&self, tier: ReputationTier) -> bool {
+        if self.is_partnership() && !tier.allows_partnerships() {
+            return false;
+        }
+        if self.is_premium() && !tier.unlocks_premium() {
+            return false;
+        }
+        true
+    }
+}
+
+/// Posts in a freshly signed influencer deal (one per day)
+const INFLUENCER_DEAL_POSTS: u32 = 14;
+
+impl MarketingState {
+    /// Mutable access to a channel's on/off switch
+    fn active_flag_mut(&mut self, channel: MarketingChannel) -> &mut bool {
+        match channel {
+            MarketingChannel::NewspaperAds => &mut self.newspaper_ads.active,
+            MarketingChannel::RadioAds => &mut self.radio_ads.active,
+            MarketingChannel::TvAds => &mut self.tv_ads.active,
+            MarketingChannel::InternetAds => &mut self.internet_ads.active,
+            MarketingChannel::BillboardAds => &mut self.billboard_ads.active,
+            MarketingChannel::MicroInfluencers => &mut self.micro_influencers.active,
+            MarketingChannel::MidInfluencers => &mut self.mid_influencers.active,
+            MarketingChannel::CelebrityEndorsement => &mut self.celebrity_endorsement.active,
+            MarketingChannel::RetailPlacement => &mut self.retail_placement.active,
+            MarketingChannel::DistributorDeals => &mut self.distributor_deals.active,
+            MarketingChannel::SupplierExclusivity => &mut self.supplier_exclusivity.active,
+            MarketingChannel::ConsultingFees => &mut self.consulting_fees.active,
+            MarketingChannel::ArtificialScarcity => &mut self.artificial_scarcity.active,
+            MarketingChannel::Astroturfing => &mut self.astroturfing.active,
+            MarketingChannel::ReviewManipulation => &mut self.review_manipulation.active,
+            MarketingChannel::CompetitorSabotage => &mut self.competitor_sabotage.active,
+        }
+    }
+
+    pub fn is_active(&self, channel: MarketingChannel) -> bool {
+        match channel {
+            MarketingChannel::NewspaperAds => self.newspaper_ads.active,
+            MarketingChannel::RadioAds => self.radio_ads.active,
+            MarketingChannel::TvAds => self.tv_ads.active,
+            MarketingChannel::InternetAds => self.internet_ads.active,
+            MarketingChannel::BillboardAds => self.billboard_ads.active,
+            MarketingChannel::MicroInfluencers => self.micro_influencers.active,
+            MarketingChannel::MidInfluencers => self.mid_influencers.active,
+            MarketingChannel::CelebrityEndorsement => self.celebrity_endorsement.active,
+            MarketingChannel::RetailPlacement => self.retail_placement.active,
+            MarketingChannel::DistributorDeals => self.distributor_deals.active,
+            MarketingChannel::SupplierExclusivity => self.supplier_exclusivity.active,
+            MarketingChannel::ConsultingFees => self.consulting_fees.active,
+            MarketingChannel::ArtificialScarcity => self.artificial_scarcity.active,
+            MarketingChannel::Astroturfing => self.astroturfing.active,
+            MarketingChannel::ReviewManipulation => self.review_manipulation.active,
+            MarketingChannel::CompetitorSabotage => self.competitor_sabotage.active,
+        }
+    }
+
+    /// Try to switch a channel on or off. Switching on fails if the channel
+    /// won't work with you at your current reputation.
+    pub fn set_active(&mut self, channel: MarketingChannel, active: bool, tier: ReputationTier) -> bool {
+        if active && !channel.available_at(tier) {
+            return false;
+        }
+        *self.active_flag_mut(channel) = active;
+        if active {
+            if let Some(deal) = self.influencer_mut(channel) {
+                if deal.posts_remaining == 0 {
+                    deal.posts_remaining = INFLUENCER_DEAL_POSTS;
+                }
+            }
+        }
+        true
+    }
+
+    fn influencer_mut(&mut self, channel: MarketingChannel) -> Option<&mut InfluencerDeal> {
+        match channel {
+            MarketingChannel::MicroInfluencers => Some(&mut self.micro_influencers),
+            MarketingChannel::MidInfluencers => Some(&mut self.mid_influencers),
+            MarketingChannel::CelebrityEndorsement => Some(&mut self.celebrity_endorsement),
+            _ => None,
+        }
+    }
+
+    /// What a single channel costs per day while active
+    pub fn channel_daily_cost(&self, channel: MarketingChannel) -> f32 {
+        match channel {
+            MarketingChannel::NewspaperAds => self.newspaper_ads.daily_spend,
+            MarketingChannel::RadioAds => self.radio_ads.daily_spend,
+            MarketingChannel::TvAds => self.tv_ads.daily_spend,
+            MarketingChannel::InternetAds => self.internet_ads.daily_spend,
+            MarketingChannel::BillboardAds => self.billboard_ads.daily_spend,
+            MarketingChannel::MicroInfluencers => self.micro_influencers.cost_per_post,
+            MarketingChannel::MidInfluencers => self.mid_influencers.cost_per_post,
+            MarketingChannel::CelebrityEndorsement => self.celebrity_endorsement.cost_per_post,
+            MarketingChannel::RetailPlacement => self.retail_placement.monthly_cost / 30.0,
+            MarketingChannel::DistributorDeals => self.distributor_deals.monthly_cost / 30.0,
+            MarketingChannel::SupplierExclusivity => self.supplier_exclusivity.monthly_cost / 30.0,
+            MarketingChannel::ConsultingFees => self.consulting_fees.monthly_cost / 30.0,
+            // Manipulation costs nothing up front - the bill comes later
+            _ => 0.0,
+        }
+    }
+
+    /// Stop every channel that costs money. Returns how many were cancelled.
+    fn cancel_paid_channels(&mut self) -> u32 {
+        let mut cancelled = 0;
+        for channel in MarketingChannel::ALL {
+            if self.is_active(channel) && self.channel_daily_cost(channel) > 0.0 {
+                *self.active_flag_mut(channel) = false;
+                cancelled += 1;
+            }
+        }
+        cancelled
+    }
+
+    /// Use up one day of every running influencer deal
+    fn tick_influencer_posts(&mut self) {
+        for deal in [
+            &mut self.micro_influencers,
+            &mut self.mid_influencers,
+            &mut self.celebrity_endorsement,
+        ] {
+            if deal.active {
+                deal.posts_remaining = deal.posts_remaining.saturating_sub(1);
+                if deal.posts_remaining == 0 {
+                    deal.active = false;
+                }
+            }
+        }
+    }
+
+    /// Cancel every active partnership (partners walking away). Returns how many ended.
+    pub fn drop_partnerships(&mut self) -> u32 {
+        let mut dropped = 0;
+        for channel in MarketingChannel::ALL {
+            if channel.is_partnership() && self.is_active(channel) {
+                *self.active_flag_mut(channel) = false;
+                dropped += 1;
+            }
+        }
+        dropped
+    }
+}
+
+/// Message fired when the player switches a channel on or off
+#[derive(Event, Message, Clone)]
+pub struct ChannelToggledEvent {
+    pub channel: MarketingChannel,
+    pub active: bool,
+}
+
 pub struct MarketingPlugin;
 
 impl Plugin for MarketingPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<MarketingState>();
+        app.init_resource::<MarketingState>()
+            .add_message::<ChannelToggledEvent>()
+            .add_systems(
+                Update,
+                (enforce_reputation_gates, bill_marketing_costs).run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// Partners refuse to be associated with a Pariah
+fn enforce_reputation_gates(
+    mut tier_events: MessageReader<ReputationTierChangedEvent>,
+    mut marketing: ResMut<MarketingState>,
+) {
+    for event in tier_events.read() {
+        if !event.new_tier.allows_partnerships() {
+            let dropped = marketing.drop_partnerships();
+            if dropped > 0 {
+                info!(
+                    "Reputation fell from {} to {}: {} marketing partners walked away",
+                    event.old_tier.name(),
+                    event.new_tier.name(),
+                    dropped
+                );
+            }
+        }
+    }
+}
+
+/// Charge active channels once per game day. If the bill can't be paid,
+/// every paid channel is cancelled.
+fn bill_marketing_costs(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    mut marketing: ResMut<MarketingState>,
+    mut game_state: ResMut<GameState>,
+) {
+    for event in day_events.read() {
+        let costs = marketing.calculate_daily_costs() as f64;
+        if costs <= 0.0 {
+            continue;
+        }
+
+        if game_state.money >= costs {
+            game_state.spend(ExpenseCategory::Marketing, costs);
+            marketing.tick_influencer_posts();
+        } else {
+            let cancelled = marketing.cancel_paid_channels();
+            info!(
+                "{}: couldn't pay ${:.0} marketing bill, {} channels cancelled",
+                event.date.format(),
+                costs,
+                cancelled
+            );
+        }
     }
 }
@@ -0,0 +1,174 @@
+//! Terry merch - achievement points spent on things for the desk and the screen
+//!
+//! Every milestone hit and accessory earned is worth achievement points, which
+//! pile up across runs (they're saved with the player's profile). The merch
+//! shop turns them into a Terry mug or bobblehead for the desk under Terry's
+//! portrait, or a condiment-themed skin for the UI. It's all for show: nothing
+//! in the shop touches the simulation.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use serde::{Deserialize, Serialize};
+use crate::attract::attract_running;
+use crate::cosmetics::CosmeticUnlockedEvent;
+use crate::game_state::{AppState, MilestoneEvent, MilestoneType};
+
+/// Points for earning an accessory
+const COSMETIC_POINTS: u32 = 10;
+
+/// Where a piece of merch shows up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MerchKind {
+    /// Sits on the desk under Terry's portrait
+    Desk,
+    /// Recolors the UI (one at a time)
+    Skin,
+}
+
+/// Something from the merch shop
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MerchItem {
+    Mug,
+    Nameplate,
+    Bobblehead,
+    KetchupSkin,
+    MustardSkin,
+    RelishSkin,
+}
+
+impl MerchItem {
+    pub const ALL: [MerchItem; 6] = [
+        MerchItem::Mug,
+        MerchItem::Nameplate,
+        MerchItem::Bobblehead,
+        MerchItem::KetchupSkin,
+        MerchItem::MustardSkin,
+        MerchItem::RelishSkin,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MerchItem::Mug => "World's Best Hot Dog Mug",
+            MerchItem::Nameplate => "\"Terry, MBA\" Nameplate",
+            MerchItem::Bobblehead => "Terry Bobblehead",
+            MerchItem::KetchupSkin => "Ketchup Skin",
+            MerchItem::MustardSkin => "Mustard Skin",
+            MerchItem::RelishSkin => "Relish Skin",
+        }
+    }
+
+    /// What it looks like on the desk (skins have nothing to put there)
+    pub fn glyph(&self) -> &'static str {
+        match self {
+            MerchItem::Mug => "☕",
+            MerchItem::Nameplate => "📛",
+            MerchItem::Bobblehead => "🌭",
+            MerchItem::KetchupSkin | MerchItem::MustardSkin | MerchItem::RelishSkin => "",
+        }
+    }
+
+    pub fn kind(&self) -> MerchKind {
+        match self {
+            MerchItem::Mug | MerchItem::Nameplate | MerchItem::Bobblehead => MerchKind::Desk,
+            MerchItem::KetchupSkin | MerchItem::MustardSkin | MerchItem::RelishSkin => MerchKind::Skin,
+        }
+    }
+
+    /// Price in achievement points
+    pub fn price(&self) -> u32 {
+        match self {
+            MerchItem::Mug => 10,
+            MerchItem::Nameplate => 15,
+            MerchItem::Bobblehead => 30,
+            MerchItem::KetchupSkin | MerchItem::MustardSkin | MerchItem::RelishSkin => 20,
+        }
+    }
+}
+
+/// Achievement points for a milestone: one per power of ten, or one per star
+fn milestone_points(milestone: MilestoneType) -> u32 {
+    match milestone {
+        MilestoneType::ThingsProduced(n) | MilestoneType::MoneyEarned(n) | MilestoneType::CustomersServed(n) => {
+            n.max(1).ilog10()
+        }
+        MilestoneType::ReputationReached(stars) => stars as u32,
+    }
+}
+
+/// Points to spend, merch bought, and what's out on show
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MerchShop {
+    pub points: u32,
+    pub owned: Vec<MerchItem>,
+    /// Desk merch on the desk, and the skin in use
+    pub displayed: Vec<MerchItem>,
+}
+
+impl MerchShop {
+    pub fn owns(&self, item: MerchItem) -> bool {
+        self.owned.contains(&item)
+    }
+
+    pub fn is_displayed(&self, item: MerchItem) -> bool {
+        self.displayed.contains(&item)
+    }
+
+    pub fn can_buy(&self, item: MerchItem) -> bool {
+        !self.owns(item) && self.points >= item.price()
+    }
+
+    /// Buy an item and put it out. Returns false if it's owned already or out of reach.
+    pub fn buy(&mut self, item: MerchItem) -> bool {
+        if !self.can_buy(item) {
+            return false;
+        }
+        self.points -= item.price();
+        self.owned.push(item);
+        self.toggle(item);
+        true
+    }
+
+    /// Put an owned item out (a skin replaces the one in use), or put it away
+    pub fn toggle(&mut self, item: MerchItem) {
+        if self.is_displayed(item) {
+            self.displayed.retain(|i| *i != item);
+        } else if self.owns(item) {
+            if item.kind() == MerchKind::Skin {
+                self.displayed.retain(|i| i.kind() != MerchKind::Skin);
+            }
+            self.displayed.push(item);
+        }
+    }
+
+    /// The skin in use, if any
+    pub fn skin(&self) -> Option<MerchItem> {
+        self.displayed.iter().copied().find(|item| item.kind() == MerchKind::Skin)
+    }
+}
+
+pub struct MerchPlugin;
+
+impl Plugin for MerchPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MerchShop>().add_systems(
+            Update,
+            award_points.run_if(in_state(AppState::Playing)).run_if(not(attract_running)),
+        );
+    }
+}
+
+/// Milestones and accessories pay out in achievement points
+fn award_points(
+    mut milestone_events: MessageReader<MilestoneEvent>,
+    mut unlock_events: MessageReader<CosmeticUnlockedEvent>,
+    mut shop: ResMut<MerchShop>,
+) {
+    let earned: u32 = milestone_events.read().map(|event| milestone_points(event.milestone_type)).sum::<u32>()
+        + unlock_events.read().count() as u32 * COSMETIC_POINTS;
+    // Only touch the resource when something was earned, so the profile isn't saved every frame
+    if earned > 0 {
+        shop.points += earned;
+    }
+}
@@ -0,0 +1,230 @@
+//! Config migrations - bringing profiles saved by older versions of the game up to date
+//!
+//! Every `config.json` records the format version it was written in (files from
+//! before versions were recorded count as version 1). On load, a file from
+//! another version is first copied to `config.v<N>.bak.json` next to it, then
+//! upgraded one version at a time until it matches `CONFIG_VERSION`. When the
+//! format changes, bump `CONFIG_VERSION`, add the step from the previous
+//! version to `UPGRADERS`, and add a fixture saved by the previous version.
+//!
+//! Saved runs go through the same steps with their own [`Format`] (see
+//! `save`), so a change to `save.json` is handled the same way.
+
+use serde_json::{Map, Value};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The format this version of the game writes
+pub const CONFIG_VERSION: u32 = 2;
+
+/// The key the version is stored under
+const VERSION_KEY: &str = "version";
+
+/// Turns a config object from one version into the next
+pub type Upgrader = fn(&mut Map<String, Value>) -> Result<(), String>;
+
+/// `UPGRADERS[n]` turns version n + 1 into version n + 2
+const UPGRADERS: [Upgrader; CONFIG_VERSION as usize - 1] = [v1_to_v2];
+
+/// A versioned JSON file: the version this build writes, and the steps up to it
+pub struct Format {
+    pub current: u32,
+    /// `upgraders[n]` turns version n + 1 into version n + 2
+    pub upgraders: &'static [Upgrader],
+}
+
+/// `config.json`
+pub const CONFIG: Format = Format {
+    current: CONFIG_VERSION,
+    upgraders: &UPGRADERS,
+};
+
+/// Version 1 is every file saved before versions were recorded. Its layout is
+/// the same as version 2's, so recording the version is all it takes.
+fn v1_to_v2(_config: &mut Map<String, Value>) -> Result<(), String> {
+    Ok(())
+}
+
+/// Why a config file couldn't be brought up to date
+#[derive(Debug, Clone, PartialEq)]
+pub enum MigrationError {
+    /// Not a JSON object
+    Unreadable(String),
+    /// Saved by a newer version of the game
+    TooNew { version: u32, newest: u32 },
+    /// An upgrader gave up
+    Failed { from: u32, reason: String },
+    /// Couldn't keep a copy of the old file, so it was left alone
+    Backup(String),
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MigrationError::Unreadable(reason) => write!(f, "the file couldn't be read ({})", reason),
+            MigrationError::TooNew { version, newest } => write!(
+                f,
+                "it was saved by a newer version of the game (format {}, this version reads up to {})",
+                version, newest
+            ),
+            MigrationError::Failed { from, reason } => {
+                write!(f, "upgrading it from format {} failed ({})", from, reason)
+            }
+            MigrationError::Backup(reason) => write!(f, "the old file couldn't be backed up ({})", reason),
+        }
+    }
+}
+
+/// The format a parsed config was saved in
+pub fn version_of(config: &Value) -> Result<u32, MigrationError> {
+    let Some(object) = config.as_object() else {
+        return Err(MigrationError::Unreadable("expected a JSON object".to_string()));
+    };
+    match object.get(VERSION_KEY) {
+        None => Ok(1),
+        Some(version) => version
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .filter(|v| *v >= 1)
+            .ok_or_else(|| MigrationError::Unreadable(format!("bad version {}", version))),
+    }
+}
+
+impl Format {
+    /// Upgrade a parsed file to the current version, one version at a time
+    pub fn migrate(&self, config: &mut Value) -> Result<(), MigrationError> {
+        let mut version = version_of(config)?;
+        if version > self.current {
+            return Err(MigrationError::TooNew { version, newest: self.current });
+        }
+        let Some(object) = config.as_object_mut() else {
+            return Err(MigrationError::Unreadable("expected a JSON object".to_string()));
+        };
+
+        while version < self.current {
+            let upgrade = self.upgraders.get(version as usize - 1).ok_or_else(|| MigrationError::Failed {
+                from: version,
+                reason: "there's no way to upgrade it".to_string(),
+            })?;
+            upgrade(object).map_err(|reason| MigrationError::Failed { from: version, reason })?;
+            version += 1;
+            object.insert(VERSION_KEY.to_string(), Value::from(version));
+        }
+        Ok(())
+    }
+
+    /// Parse a file and bring it up to date, backing it up first if it's from another version
+    pub fn load_file(&self, path: &Path, contents: &str) -> Result<Value, MigrationError> {
+        let mut config: Value =
+            serde_json::from_str(contents).map_err(|err| MigrationError::Unreadable(err.to_string()))?;
+        let version = version_of(&config)?;
+        if version != self.current {
+            fs::copy(path, backup_path(path, version)).map_err(|err| MigrationError::Backup(err.to_string()))?;
+        }
+        self.migrate(&mut config)?;
+        Ok(config)
+    }
+}
+
+/// Where a file saved in `version` is kept before it's upgraded
+pub fn backup_path(path: &Path, version: u32) -> PathBuf {
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("config");
+    path.with_file_name(format!("{}.v{}.bak.json", stem, version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cosmetics::{Cosmetic, Wardrobe};
+    use crate::settings::{DialogueLayout, Settings};
+    use crate::tutorial::{FirstTime, FirstTimes};
+
+    /// A config saved by each version, oldest first
+    const FIXTURES: [&str; CONFIG_VERSION as usize] = [
+        include_str!("../tests/fixtures/config_v1.json"),
+        include_str!("../tests/fixtures/config_v2.json"),
+    ];
+
+    fn parse(contents: &str) -> Value {
+        serde_json::from_str(contents).unwrap()
+    }
+
+    #[test]
+    fn fixtures_report_their_version() {
+        for (index, fixture) in FIXTURES.iter().enumerate() {
+            assert_eq!(version_of(&parse(fixture)), Ok(index as u32 + 1));
+        }
+    }
+
+    #[test]
+    fn every_fixture_migrates_to_the_current_version() {
+        for fixture in FIXTURES {
+            let mut config = parse(fixture);
+            CONFIG.migrate(&mut config).unwrap();
+            assert_eq!(version_of(&config), Ok(CONFIG_VERSION));
+
+            // Everything the game reads back out still deserializes
+            let settings: Settings = serde_json::from_value(config["settings"].clone()).unwrap();
+            assert_eq!(settings.dialogue_layout, DialogueLayout::Bubble);
+            assert!(!settings.hustle_meter);
+            let wardrobe: Wardrobe = serde_json::from_value(config["wardrobe"].clone()).unwrap();
+            assert!(wardrobe.is_equipped(Cosmetic::TopHat));
+            let first_times: FirstTimes = serde_json::from_value(config["first_times"].clone()).unwrap();
+            assert!(first_times.has_seen(FirstTime::Upgrade));
+            assert_eq!(config["key_bindings"]["make_thing"], "Space");
+            assert_eq!(config["lifetime_playtime_secs"], 5400);
+        }
+    }
+
+    #[test]
+    fn current_version_is_left_alone() {
+        let original = parse(FIXTURES[CONFIG_VERSION as usize - 1]);
+        let mut config = original.clone();
+        CONFIG.migrate(&mut config).unwrap();
+        assert_eq!(config, original);
+    }
+
+    #[test]
+    fn newer_versions_are_refused() {
+        let mut config = parse(r#"{ "version": 99, "settings": {} }"#);
+        assert_eq!(CONFIG.migrate(&mut config), Err(MigrationError::TooNew { version: 99, newest: CONFIG_VERSION }));
+    }
+
+    #[test]
+    fn non_objects_and_bad_versions_are_unreadable() {
+        for contents in [r#"[1, 2, 3]"#, r#"{ "version": "two" }"#, r#"{ "version": 0 }"#] {
+            assert!(matches!(CONFIG.migrate(&mut parse(contents)), Err(MigrationError::Unreadable(_))));
+        }
+    }
+
+    #[test]
+    fn old_files_are_backed_up_before_migrating() {
+        let dir = std::env::temp_dir().join(format!("thing_migration_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.json");
+        fs::write(&path, FIXTURES[0]).unwrap();
+
+        let config = CONFIG.load_file(&path, FIXTURES[0]).unwrap();
+        assert_eq!(version_of(&config), Ok(CONFIG_VERSION));
+        let backup = backup_path(&path, 1);
+        assert_eq!(backup, dir.join("config.v1.bak.json"));
+        assert_eq!(fs::read_to_string(&backup).unwrap(), FIXTURES[0]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn current_files_are_not_backed_up() {
+        let dir = std::env::temp_dir().join(format!("thing_migration_current_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.json");
+        let current = FIXTURES[CONFIG_VERSION as usize - 1];
+        fs::write(&path, current).unwrap();
+
+        CONFIG.load_file(&path, current).unwrap();
+        assert!(!backup_path(&path, CONFIG_VERSION).exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,244 @@
+//! Objectives - everything the player is working towards, in one list
+//!
+//! The current quest step, deadlines on time-limited offers (a truce on the
+//! table, seized fakes waiting to be authenticated, an edition that has to
+//! sell before its month ends) and this week's customer goal are all turned
+//! into a `TrackedObjective`, so the tracker on the main screen can show them
+//! the same way. Each one knows which overlay to open when it's clicked.
+//!
+//! The weekly goal lives here too: every Monday the target resets to a bit
+//! more than last week's customers.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::SystemParam;
+use crate::counterfeits::CounterfeitState;
+use crate::economy::{WeekStartedEvent, WorldState};
+use crate::game_state::{AppState, GameState};
+use crate::limited_editions::LimitedEditions;
+use crate::price_war::PriceWarState;
+use crate::quests::{QuestBook, QuestLog};
+use crate::ui::OverlayKind;
+
+/// How much to beat last week by
+const WEEKLY_STRETCH: f64 = 1.1;
+/// Smallest weekly target
+const MIN_WEEKLY_TARGET: u64 = 50;
+
+/// Where an objective comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectiveKind {
+    Quest,
+    Deadline,
+    Weekly,
+}
+
+impl ObjectiveKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ObjectiveKind::Quest => "Quest",
+            ObjectiveKind::Deadline => "Deadline",
+            ObjectiveKind::Weekly => "This week",
+        }
+    }
+}
+
+/// One line in the tracker
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackedObjective {
+    pub kind: ObjectiveKind,
+    pub label: String,
+    pub detail: String,
+    /// From 0.0 to 1.0, for objectives that fill up
+    pub progress: Option<f32>,
+    pub days_left: Option<u32>,
+    /// Overlay that deals with it
+    pub focus: Option<OverlayKind>,
+}
+
+/// Everything being tracked right now, rebuilt as the sources change
+#[derive(Resource, Default)]
+pub struct Objectives {
+    pub list: Vec<TrackedObjective>,
+}
+
+/// This week's customer target
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct WeeklyGoal {
+    /// Customers to serve this week (0 until the first Monday)
+    pub target: u64,
+    /// Weeks in a row the target was hit
+    pub streak: u32,
+    /// Customers served as of Monday
+    week_start_customers: u64,
+}
+
+impl WeeklyGoal {
+    pub fn served(&self, game_state: &GameState) -> u64 {
+        game_state.customers_served.saturating_sub(self.week_start_customers)
+    }
+}
+
+/// Message fired when a week closes, hit or missed
+#[derive(Event, Message, Clone)]
+pub struct WeeklyGoalEvent {
+    pub target: u64,
+    pub served: u64,
+    pub met: bool,
+}
+
+pub struct ObjectivesPlugin;
+
+impl Plugin for ObjectivesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Objectives>()
+            .init_resource::<WeeklyGoal>()
+            .add_message::<WeeklyGoalEvent>()
+            .add_systems(
+                Update,
+                (roll_weekly_goal, collect_objectives)
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// Close out the week every Monday and set the next target
+fn roll_weekly_goal(
+    mut week_events: MessageReader<WeekStartedEvent>,
+    game_state: Res<GameState>,
+    mut goal: ResMut<WeeklyGoal>,
+    mut goal_events: MessageWriter<WeeklyGoalEvent>,
+) {
+    for _ in week_events.read() {
+        let served = goal.served(&game_state);
+        if goal.target > 0 {
+            let met = served >= goal.target;
+            goal.streak = if met { goal.streak + 1 } else { 0 };
+            goal_events.write(WeeklyGoalEvent {
+                target: goal.target,
+                served,
+                met,
+            });
+        }
+        goal.target = ((served as f64 * WEEKLY_STRETCH).round() as u64).max(MIN_WEEKLY_TARGET);
+        goal.week_start_customers = game_state.customers_served;
+    }
+}
+
+/// Where the tracker gets its objectives from
+#[derive(SystemParam)]
+struct ObjectiveSources<'w> {
+    game_state: Res<'w, GameState>,
+    world: Res<'w, WorldState>,
+    quest_book: Res<'w, QuestBook>,
+    quest_log: Res<'w, QuestLog>,
+    price_war: Res<'w, PriceWarState>,
+    counterfeits: Res<'w, CounterfeitState>,
+    editions: Res<'w, LimitedEditions>,
+    goal: Res<'w, WeeklyGoal>,
+}
+
+impl ObjectiveSources<'_> {
+    fn changed(&self) -> bool {
+        self.game_state.is_changed()
+            || self.quest_log.is_changed()
+            || self.price_war.is_changed()
+            || self.counterfeits.is_changed()
+            || self.editions.is_changed()
+            || self.goal.is_changed()
+    }
+
+    fn quest(&self) -> Option<TrackedObjective> {
+        let step = self.quest_log.current(&self.quest_book)?;
+        let (detail, progress) = if self.quest_log.ready {
+            ("Ready - pick what happens next".to_string(), 1.0)
+        } else {
+            (
+                step.objective.describe(),
+                step.objective.progress(&self.quest_log, &self.game_state),
+            )
+        };
+        Some(TrackedObjective {
+            kind: ObjectiveKind::Quest,
+            label: step.title.clone(),
+            detail,
+            progress: Some(progress),
+            days_left: None,
+            focus: Some(OverlayKind::Quests),
+        })
+    }
+
+    fn deadlines(&self) -> Vec<TrackedObjective> {
+        let mut deadlines = Vec::new();
+        if let Some(days) = self.price_war.truce_offer_days {
+            deadlines.push(TrackedObjective {
+                kind: ObjectiveKind::Deadline,
+                label: "Truce on the table".to_string(),
+                detail: "The competition will stop undercutting if you go back to list price".to_string(),
+                progress: None,
+                days_left: Some(days),
+                focus: Some(OverlayKind::Market),
+            });
+        }
+        if let Some(days) = self.counterfeits.seizure_hold_days {
+            deadlines.push(TrackedObjective {
+                kind: ObjectiveKind::Deadline,
+                label: "Authenticate seized fakes".to_string(),
+                detail: "Customs releases the shipment if nobody sends a report".to_string(),
+                progress: None,
+                days_left: Some(days),
+                focus: Some(OverlayKind::Counterfeits),
+            });
+        }
+        if let Some(batch) = self.editions.batch.filter(|batch| batch.launched && batch.remaining > 0) {
+            let date = self.world.date;
+            let sold = batch.ordered - batch.remaining;
+            deadlines.push(TrackedObjective {
+                kind: ObjectiveKind::Deadline,
+                label: format!("Sell the {}", batch.edition.name()),
+                detail: format!("{} of {} left; the rest goes to clearance", batch.remaining, batch.ordered),
+                progress: Some(sold as f32 / batch.ordered.max(1) as f32),
+                days_left: Some((date.days_in_month() - date.day()) as u32),
+                focus: Some(OverlayKind::Editions),
+            });
+        }
+        deadlines
+    }
+
+    fn weekly(&self) -> Option<TrackedObjective> {
+        if self.goal.target == 0 {
+            return None;
+        }
+        let served = self.goal.served(&self.game_state);
+        let streak = if self.goal.streak > 0 {
+            format!(" ({} weeks in a row)", self.goal.streak)
+        } else {
+            String::new()
+        };
+        Some(TrackedObjective {
+            kind: ObjectiveKind::Weekly,
+            label: format!("Serve {} customers{}", self.goal.target, streak),
+            detail: format!("{} served so far", served),
+            progress: Some((served as f64 / self.goal.target as f64).min(1.0) as f32),
+            // Weeks run Monday to Sunday
+            days_left: Some(7 - (self.world.day_of_week as u32 + 6) % 7),
+            focus: None,
+        })
+    }
+}
+
+/// Rebuild the list whenever one of its sources changes
+fn collect_objectives(sources: ObjectiveSources, mut objectives: ResMut<Objectives>) {
+    if !sources.changed() {
+        return;
+    }
+
+    let mut list: Vec<TrackedObjective> = sources.quest().into_iter().collect();
+    list.extend(sources.deadlines());
+    list.extend(sources.weekly());
+    if objectives.list != list {
+        objectives.list = list;
+    }
+}
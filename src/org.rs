@@ -0,0 +1,123 @@
+//! Org chart - managers over the workforce
+//!
+//! Each manager at the bottom layer looks after a handful of workers and makes
+//! them more productive, but managers draw a salary, and every layer of
+//! management past the first couple slows the whole company down.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::accounting::ExpenseCategory;
+use crate::economy::DayAdvancedEvent;
+use crate::game_state::{AppState, GameState};
+
+// === ORG BALANCE ===
+/// Direct reports one manager can handle
+pub const MANAGER_SPAN: u32 = 5;
+/// Extra output of a supervised worker
+pub const MANAGER_BOOST: f64 = 0.25;
+/// Things/sec a single worker produces (matches the Hire Worker upgrade)
+const WORKER_OUTPUT: f64 = 0.5;
+/// Hiring fee for the first manager (grows like upgrade prices)
+pub const MANAGER_HIRE_COST: f64 = 400.0;
+/// Daily salary per manager
+pub const MANAGER_SALARY: f64 = 20.0;
+/// Layers of management before bureaucracy sets in
+pub const FLAT_LAYERS: usize = 2;
+/// Production lost per layer beyond `FLAT_LAYERS`
+pub const BUREAUCRACY_PENALTY: f64 = 0.1;
+
+/// The management structure sitting on top of the workers
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct Organization {
+    pub managers: u32,
+}
+
+impl Organization {
+    pub fn hire_cost(&self) -> f64 {
+        MANAGER_HIRE_COST * 1.15_f64.powi(self.managers as i32)
+    }
+
+    pub fn daily_overhead(&self) -> f64 {
+        self.managers as f64 * MANAGER_SALARY
+    }
+
+    /// Managers per layer, bottom (managing workers) first. Managers fill the
+    /// bottom layer until every worker is covered; the rest manage managers,
+    /// and anyone left over at the top becomes yet another layer.
+    pub fn layers(&self, workers: u32) -> Vec<u32> {
+        let mut layers = Vec::new();
+        let mut remaining = self.managers;
+        let mut reports = workers;
+        while remaining > 0 {
+            let here = reports.div_ceil(MANAGER_SPAN).max(1).min(remaining);
+            layers.push(here);
+            remaining -= here;
+            reports = here;
+        }
+        layers
+    }
+
+    /// Workers who have a manager
+    pub fn supervised_workers(&self, workers: u32) -> u32 {
+        let bottom = self.layers(workers).first().copied().unwrap_or(0);
+        workers.min(bottom * MANAGER_SPAN)
+    }
+
+    /// Production multiplier from too many layers (1.0 when flat enough)
+    pub fn bureaucracy_factor(&self, workers: u32) -> f64 {
+        let excess = self.layers(workers).len().saturating_sub(FLAT_LAYERS);
+        (1.0 - excess as f64 * BUREAUCRACY_PENALTY).max(0.5)
+    }
+
+    /// Things/sec once managers are accounted for
+    pub fn effective_rate(&self, things_per_second: f64, workers: u32) -> f64 {
+        let boost = self.supervised_workers(workers) as f64 * WORKER_OUTPUT * MANAGER_BOOST;
+        (things_per_second + boost) * self.bureaucracy_factor(workers)
+    }
+
+    /// Pay the hiring fee and add a manager. Returns false if it can't be afforded.
+    pub fn hire(&mut self, game_state: &mut GameState) -> bool {
+        let cost = self.hire_cost();
+        if game_state.money < cost {
+            return false;
+        }
+        game_state.spend(ExpenseCategory::Payroll, cost);
+        self.managers += 1;
+        true
+    }
+
+    pub fn let_go(&mut self) {
+        self.managers = self.managers.saturating_sub(1);
+    }
+}
+
+pub struct OrgPlugin;
+
+impl Plugin for OrgPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Organization>()
+            .add_systems(Update, pay_managers.run_if(in_state(AppState::Playing)));
+    }
+}
+
+/// Pay manager salaries each game day; a manager quits whenever payroll bounces
+fn pay_managers(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    mut org: ResMut<Organization>,
+    mut game_state: ResMut<GameState>,
+) {
+    for event in day_events.read() {
+        let overhead = org.daily_overhead();
+        if overhead <= 0.0 {
+            continue;
+        }
+
+        if game_state.money >= overhead {
+            game_state.spend(ExpenseCategory::Payroll, overhead);
+        } else {
+            org.let_go();
+            info!("{}: couldn't make payroll, a manager quit", event.date.format());
+        }
+    }
+}
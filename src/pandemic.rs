@@ -0,0 +1,204 @@
+//! Pandemic era - 2020's rules, layered over the usual ones
+//!
+//! From the March 2020 lockdowns until restrictions lift in mid-2021, physical
+//! channels (billboards, retail placement, pop-up venues) barely reach anyone
+//! while online channels surge. Unemployment splits the customers too: people
+//! trade down to Cheap Things and put off Expensive ones. A one-off "pivot to
+//! delivery" wins back some of the in-person trade, and Cheap Thing makers get
+//! a few weeks selling their Things as masks and sanitizer.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::accounting::ExpenseCategory;
+use crate::economy::{DayAdvancedEvent, GameDate, WorldState};
+use crate::game_state::{AppState, GameState};
+use crate::macro_dynamics::NATURAL_UNEMPLOYMENT;
+use crate::marketing::MarketingState;
+use crate::thing_type::{PriceTier, ThingDefinition, ThingRegistry};
+
+/// Cost of pivoting to delivery
+pub const DELIVERY_COST: f64 = 1_500.0;
+/// Customers won back by delivery while the pandemic lasts
+const DELIVERY_BOOST: f64 = 1.3;
+/// Customers who keep ordering once it's over
+const DELIVERY_AFTERGLOW: f64 = 1.05;
+/// Days the mask and sanitizer rush lasts
+const MASK_RUSH_DAYS: u32 = 45;
+/// Customers during the rush
+const MASK_RUSH_BOOST: f64 = 1.8;
+
+/// How far into the pandemic the world is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum PandemicPhase {
+    Lockdown,
+    Restrictions,
+}
+
+impl PandemicPhase {
+    /// The phase on a date, if there's a pandemic on
+    pub fn on(date: GameDate) -> Option<Self> {
+        match (date.year(), date.month(), date.day()) {
+            (2020, 3, 11..) | (2020, 4, _) | (2020, 5, _) => Some(PandemicPhase::Lockdown),
+            (2020, 6.., _) | (2021, 1..=5, _) => Some(PandemicPhase::Restrictions),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PandemicPhase::Lockdown => "Lockdown",
+            PandemicPhase::Restrictions => "Restrictions",
+        }
+    }
+
+    /// Reach of billboards, retail placement and pop-ups
+    pub fn physical_reach(&self) -> f32 {
+        match self {
+            PandemicPhase::Lockdown => 0.2,
+            PandemicPhase::Restrictions => 0.6,
+        }
+    }
+
+    /// Reach of internet ads and influencers
+    pub fn online_reach(&self) -> f32 {
+        match self {
+            PandemicPhase::Lockdown => 1.8,
+            PandemicPhase::Restrictions => 1.4,
+        }
+    }
+}
+
+/// The era layer
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct PandemicState {
+    pub phase: Option<PandemicPhase>,
+    /// Pivoted to delivery
+    pub delivery: bool,
+    /// Days left of the mask and sanitizer rush
+    pub mask_rush_days: u32,
+    /// The rush only happens once
+    pub mask_rush_done: bool,
+}
+
+impl PandemicState {
+    /// Multiplier on would-be customers for a Thing today
+    pub fn customer_factor(&self, thing: &ThingDefinition, world: &WorldState) -> f64 {
+        let mut factor = 1.0;
+        if self.phase.is_some() {
+            let excess_unemployment = (world.unemployment_rate - NATURAL_UNEMPLOYMENT) as f64;
+            factor *= (1.0 + thing.unemployment_sensitivity * excess_unemployment).max(0.3);
+        }
+        if self.delivery {
+            factor *= if self.phase.is_some() { DELIVERY_BOOST } else { DELIVERY_AFTERGLOW };
+        }
+        if self.mask_rush_days > 0 {
+            factor *= MASK_RUSH_BOOST;
+        }
+        factor
+    }
+
+    /// Pop-up venues only help as much as people can get to them
+    pub fn venue_boost(&self, boost: f64) -> f64 {
+        let reach = self.phase.map(|p| p.physical_reach()).unwrap_or(1.0) as f64;
+        1.0 + (boost - 1.0) * reach
+    }
+
+    pub fn can_pivot_to_delivery(&self, game_state: &GameState) -> bool {
+        self.phase.is_some() && !self.delivery && game_state.money >= DELIVERY_COST
+    }
+}
+
+/// What happened in the pandemic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PandemicStage {
+    Began(PandemicPhase),
+    Over,
+    PivotedToDelivery,
+    MaskRushStarted,
+    MaskRushEnded,
+}
+
+/// Message fired as the pandemic moves along
+#[derive(Event, Message, Clone)]
+pub struct PandemicEvent {
+    pub stage: PandemicStage,
+}
+
+/// Message requesting the pivot to delivery (sent by the UI)
+#[derive(Event, Message, Clone)]
+pub struct PivotToDeliveryEvent;
+
+pub struct PandemicPlugin;
+
+impl Plugin for PandemicPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PandemicState>()
+            .add_message::<PandemicEvent>()
+            .add_message::<PivotToDeliveryEvent>()
+            .add_systems(
+                Update,
+                (advance_pandemic, handle_delivery_pivot)
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// Each game day: move between phases, reweight the marketing channels, and run the mask rush
+fn advance_pandemic(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    mut pandemic: ResMut<PandemicState>,
+    mut marketing: ResMut<MarketingState>,
+    mut pandemic_events: MessageWriter<PandemicEvent>,
+) {
+    for event in day_events.read() {
+        let phase = PandemicPhase::on(event.date);
+        if phase != pandemic.phase {
+            pandemic.phase = phase;
+            marketing.physical_reach = phase.map(|p| p.physical_reach()).unwrap_or(1.0);
+            marketing.online_reach = phase.map(|p| p.online_reach()).unwrap_or(1.0);
+            let stage = phase.map(PandemicStage::Began).unwrap_or(PandemicStage::Over);
+            pandemic_events.write(PandemicEvent { stage });
+        }
+
+        if pandemic.mask_rush_days > 0 {
+            pandemic.mask_rush_days -= 1;
+            if pandemic.mask_rush_days == 0 {
+                pandemic_events.write(PandemicEvent {
+                    stage: PandemicStage::MaskRushEnded,
+                });
+            }
+        } else if phase == Some(PandemicPhase::Lockdown)
+            && !pandemic.mask_rush_done
+            && game_state.thing_type.is_some_and(|thing_type| things[thing_type].tier == PriceTier::Budget)
+        {
+            pandemic.mask_rush_done = true;
+            pandemic.mask_rush_days = MASK_RUSH_DAYS;
+            pandemic_events.write(PandemicEvent {
+                stage: PandemicStage::MaskRushStarted,
+            });
+        }
+    }
+}
+
+/// Pay for the pivot to delivery
+fn handle_delivery_pivot(
+    mut pivot_events: MessageReader<PivotToDeliveryEvent>,
+    mut game_state: ResMut<GameState>,
+    mut pandemic: ResMut<PandemicState>,
+    mut pandemic_events: MessageWriter<PandemicEvent>,
+) {
+    for _ in pivot_events.read() {
+        if !pandemic.can_pivot_to_delivery(&game_state) {
+            continue;
+        }
+        game_state.spend(ExpenseCategory::Upgrades, DELIVERY_COST);
+        pandemic.delivery = true;
+        pandemic_events.write(PandemicEvent {
+            stage: PandemicStage::PivotedToDelivery,
+        });
+    }
+}
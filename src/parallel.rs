@@ -0,0 +1,139 @@
+//! Parallel universes - a bot runs a different Thing on the same world alongside you
+//!
+//! With the mode switched on at the selection screen, starting a run also
+//! starts a `SimWorld` with the same seed and fortune but another Thing,
+//! played by a bot. It's kept in step with your calendar one game day at a
+//! time, so the two runs see the same weather, market swings and history,
+//! and the main screen can show how the other one is doing.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::agent::GreedyClicker;
+use crate::attract::AttractMode;
+use crate::difficulty::DifficultyConfig;
+use crate::economy::WorldState;
+use crate::fortune::{open_fortune_cookie, RunModifiers};
+use crate::game_state::{AppState, GameState, InGame};
+use crate::sim::SimWorld;
+use crate::thing_type::{ThingRegistry, ThingType};
+
+/// Most updates the other universe gets per frame while catching up
+const MAX_STEPS_PER_FRAME: u32 = 40;
+
+/// Whether the next run gets a parallel universe
+#[derive(Resource, Default)]
+pub struct ParallelMode {
+    pub enabled: bool,
+}
+
+/// How the other universe is doing
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Standing {
+    pub thing_type: ThingType,
+    pub money: f64,
+    pub things_produced: u64,
+    pub customers_served: u64,
+    pub reputation: f32,
+}
+
+impl Standing {
+    fn of(thing_type: ThingType, game_state: &GameState) -> Self {
+        Self {
+            thing_type,
+            money: game_state.money.dollars(),
+            things_produced: game_state.things_produced,
+            customers_served: game_state.customers_served,
+            reputation: game_state.reputation,
+        }
+    }
+}
+
+/// The latest from the other universe, for the UI and Terry
+#[derive(Resource, Default)]
+pub struct ParallelStandings {
+    pub other: Option<Standing>,
+    /// Whether the player had more money at the last day's close
+    pub player_ahead: Option<bool>,
+}
+
+/// The other universe's simulation (its app isn't `Send`, so it lives outside the resources)
+pub struct ParallelUniverse {
+    sim: SimWorld,
+    thing_type: ThingType,
+}
+
+/// Message fired when the lead changes hands between the universes
+#[derive(Event, Message, Clone)]
+pub struct UniverseLeadEvent {
+    pub player_ahead: bool,
+}
+
+pub struct ParallelPlugin;
+
+impl Plugin for ParallelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ParallelMode>()
+            .init_resource::<ParallelStandings>()
+            .add_message::<UniverseLeadEvent>()
+            .add_systems(OnEnter(InGame), open_parallel_universe.after(open_fortune_cookie))
+            .add_systems(OnExit(InGame), close_parallel_universe)
+            .add_systems(Update, advance_parallel_universe.run_if(in_state(AppState::Playing)));
+    }
+}
+
+/// Start the other universe, with any other Thing on the same seed and fortune
+fn open_parallel_universe(world: &mut World) {
+    let enabled = world.resource::<ParallelMode>().enabled && !world.resource::<AttractMode>().running;
+    let Some(player_thing) = world.resource::<GameState>().thing_type.filter(|_| enabled) else {
+        return;
+    };
+    let things = world.resource::<ThingRegistry>().clone();
+    let others: Vec<ThingType> = things.all().filter(|thing_type| *thing_type != player_thing).collect();
+    if others.is_empty() {
+        return;
+    }
+
+    let thing_type = others[rand::random_range(0..others.len())];
+    let seed = world.resource::<WorldState>().seed;
+    let sim = SimWorld::new(&things, thing_type, seed)
+        .with_agent(Box::new(GreedyClicker))
+        .with_modifiers(world.resource::<RunModifiers>().clone())
+        .with_difficulty(world.resource::<DifficultyConfig>().clone());
+    let standing = Standing::of(thing_type, sim.game_state());
+    world.insert_non_send_resource(ParallelUniverse { sim, thing_type });
+    *world.resource_mut::<ParallelStandings>() = ParallelStandings {
+        other: Some(standing),
+        player_ahead: None,
+    };
+    info!("Parallel universe opened: {} on seed {}", things[thing_type].name, seed);
+}
+
+fn close_parallel_universe(world: &mut World) {
+    world.remove_non_send_resource::<ParallelUniverse>();
+    *world.resource_mut::<ParallelStandings>() = ParallelStandings::default();
+}
+
+/// Run the other universe up to today's date, then compare notes
+fn advance_parallel_universe(
+    universe: Option<NonSendMut<ParallelUniverse>>,
+    world: Res<WorldState>,
+    game_state: Res<GameState>,
+    mut standings: ResMut<ParallelStandings>,
+    mut lead_events: MessageWriter<UniverseLeadEvent>,
+) {
+    let Some(mut universe) = universe else {
+        return;
+    };
+    if universe.sim.date() >= world.date {
+        return;
+    }
+    universe.sim.run_until(world.date, MAX_STEPS_PER_FRAME);
+
+    let other = Standing::of(universe.thing_type, universe.sim.game_state());
+    let player_ahead = game_state.money >= other.money;
+    if standings.player_ahead.is_some_and(|ahead| ahead != player_ahead) {
+        lead_events.write(UniverseLeadEvent { player_ahead });
+    }
+    standings.other = Some(other);
+    standings.player_ahead = Some(player_ahead);
+}
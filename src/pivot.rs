@@ -0,0 +1,184 @@
+//! Pivoting - switching to a different kind of Thing mid-run
+//!
+//! A pivot shuts the line down for retooling, writes off the old stock,
+//! and only some of your reputation survives the change of direction.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::accounting::ExpenseCategory;
+use crate::economy::DayAdvancedEvent;
+use crate::game_state::{AppState, GameState, ReputationChangedEvent};
+use crate::thing_type::{ThingRegistry, ThingType};
+
+/// Game days of downtime while the factory is retooled
+pub const RETOOLING_DAYS: u32 = 7;
+/// Base retooling fee (each pivot after the first costs more)
+pub const RETOOLING_BASE_COST: f64 = 1_000.0;
+/// Seconds of output assumed to be sitting unsold in the warehouse
+const STOCK_ON_HAND_SECS: f64 = 60.0;
+/// Share of distance from neutral reputation that survives a pivot
+const REPUTATION_CARRYOVER: f32 = 0.5;
+/// Where reputation settles for a brand nobody has an opinion about yet
+const NEUTRAL_REPUTATION: f32 = 2.5;
+
+/// A pivot in progress
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct ActivePivot {
+    pub from: ThingType,
+    pub to: ThingType,
+    pub days_remaining: u32,
+}
+
+/// Pivot history and any retooling underway
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct PivotState {
+    pub active: Option<ActivePivot>,
+    /// Completed pivots this run
+    pub pivots: u32,
+}
+
+/// Things reckoned to be sitting in the warehouse
+pub fn stock_on_hand(game_state: &GameState, things: &ThingRegistry) -> f64 {
+    let multiplier = game_state.thing_type.map_or(1.0, |thing_type| things[thing_type].production_multiplier);
+    game_state.things_per_second * multiplier * STOCK_ON_HAND_SECS
+}
+
+impl PivotState {
+    /// Fee for retooling the factory
+    pub fn retooling_cost(&self) -> f64 {
+        RETOOLING_BASE_COST * (self.pivots + 1) as f64
+    }
+
+    /// Value of old-type stock that has to be thrown away
+    pub fn inventory_write_off(&self, game_state: &GameState, things: &ThingRegistry) -> f64 {
+        let Some(thing_type) = game_state.thing_type else {
+            return 0.0;
+        };
+        stock_on_hand(game_state, things) * things[thing_type].base_price
+    }
+
+    /// Everything a pivot costs right now
+    pub fn total_cost(&self, game_state: &GameState, things: &ThingRegistry) -> f64 {
+        self.retooling_cost() + self.inventory_write_off(game_state, things)
+    }
+
+    pub fn can_pivot(&self, to: ThingType, game_state: &GameState, things: &ThingRegistry) -> bool {
+        self.active.is_none()
+            && game_state.thing_type.is_some_and(|current| current != to)
+            && game_state.money >= self.total_cost(game_state, things)
+    }
+}
+
+/// Reputation after a pivot: part of the old standing carries over, the rest
+/// resets toward neutral
+pub fn carried_over_reputation(reputation: f32) -> f32 {
+    NEUTRAL_REPUTATION + (reputation - NEUTRAL_REPUTATION) * REPUTATION_CARRYOVER
+}
+
+/// Message requesting a pivot (sent by the UI)
+#[derive(Event, Message, Clone)]
+pub struct StartPivotEvent {
+    pub to: ThingType,
+}
+
+/// Message fired when a pivot begins or completes
+#[derive(Event, Message, Clone)]
+pub struct PivotEvent {
+    pub from: ThingType,
+    pub to: ThingType,
+    pub completed: bool,
+}
+
+pub struct PivotPlugin;
+
+impl Plugin for PivotPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PivotState>()
+            .add_message::<StartPivotEvent>()
+            .add_message::<PivotEvent>()
+            .add_systems(
+                Update,
+                (start_pivot, progress_pivot)
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// Pay for the pivot and shut the line down for retooling
+fn start_pivot(
+    mut start_events: MessageReader<StartPivotEvent>,
+    things: Res<ThingRegistry>,
+    mut game_state: ResMut<GameState>,
+    mut pivot: ResMut<PivotState>,
+    mut pivot_events: MessageWriter<PivotEvent>,
+) {
+    for event in start_events.read() {
+        if !pivot.can_pivot(event.to, &game_state, &things) {
+            continue;
+        }
+        let Some(from) = game_state.thing_type else {
+            continue;
+        };
+
+        let write_off = pivot.inventory_write_off(&game_state, &things);
+        let cost = pivot.total_cost(&game_state, &things);
+        game_state.spend(ExpenseCategory::Pivot, cost);
+        game_state.retooling = true;
+        pivot.active = Some(ActivePivot {
+            from,
+            to: event.to,
+            days_remaining: RETOOLING_DAYS,
+        });
+        info!(
+            "Pivoting from {} to {} (wrote off ${:.0} of stock)",
+            things[from].name,
+            things[event.to].name,
+            write_off
+        );
+
+        pivot_events.write(PivotEvent {
+            from,
+            to: event.to,
+            completed: false,
+        });
+    }
+}
+
+/// Count down retooling and relaunch as the new Thing
+fn progress_pivot(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    mut game_state: ResMut<GameState>,
+    mut pivot: ResMut<PivotState>,
+    mut pivot_events: MessageWriter<PivotEvent>,
+    mut rep_events: MessageWriter<ReputationChangedEvent>,
+) {
+    for _ in day_events.read() {
+        let Some(active) = pivot.active.as_mut() else {
+            continue;
+        };
+
+        active.days_remaining = active.days_remaining.saturating_sub(1);
+        if active.days_remaining > 0 {
+            continue;
+        }
+
+        let active = *active;
+        pivot.active = None;
+        pivot.pivots += 1;
+
+        game_state.thing_type = Some(active.to);
+        game_state.retooling = false;
+        game_state.reputation = carried_over_reputation(game_state.reputation).clamp(0.0, 5.0);
+
+        rep_events.write(ReputationChangedEvent {
+            new_reputation: game_state.reputation,
+        });
+        pivot_events.write(PivotEvent {
+            from: active.from,
+            to: active.to,
+            completed: true,
+        });
+    }
+}
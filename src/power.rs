@@ -0,0 +1,62 @@
+//! Power saving - fewer frames and no decoration when nobody's watching closely
+//!
+//! The game drops to low power when the window loses focus or the player turns
+//! on the battery saver: the frame rate is capped, UI animations jump straight
+//! to their end and purely decorative effects (floating numbers, flying icons,
+//! the typewriter, scenery parallax) are skipped. The simulation runs on
+//! virtual time, so it's updated less often but never loses time: every wait
+//! stays under `Time<Virtual>`'s max delta.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy::winit::{UpdateMode, WinitSettings};
+use std::time::Duration;
+use crate::settings::Settings;
+
+/// Longest wait between frames in low power (20 fps)
+const LOW_POWER_TICK: Duration = Duration::from_millis(50);
+/// Longest wait between frames while running in the background
+const BACKGROUND_TICK: Duration = Duration::from_millis(200);
+
+/// How hard the game is working right now
+#[derive(Resource, Default)]
+pub struct PowerMode {
+    /// Frame rate capped and decoration off
+    pub low_power: bool,
+    /// Minimized and running in the background (`background` builds only)
+    pub backgrounded: bool,
+}
+
+pub struct PowerPlugin;
+
+impl Plugin for PowerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PowerMode>().add_systems(Update, update_power_mode);
+    }
+}
+
+/// Work out the power mode and pace the event loop to match
+fn update_power_mode(
+    settings: Res<Settings>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut power: ResMut<PowerMode>,
+    mut winit: ResMut<WinitSettings>,
+) {
+    let focused = window_query.single().map(|window| window.focused).unwrap_or(true);
+    let low_power = settings.battery_saver || !focused || power.backgrounded;
+    if power.low_power != low_power {
+        power.low_power = low_power;
+    }
+
+    let focused_mode = if settings.battery_saver {
+        UpdateMode::reactive_low_power(LOW_POWER_TICK)
+    } else {
+        UpdateMode::Continuous
+    };
+    let unfocused_mode =
+        UpdateMode::reactive_low_power(if power.backgrounded { BACKGROUND_TICK } else { LOW_POWER_TICK });
+    if winit.focused_mode != focused_mode || winit.unfocused_mode != unfocused_mode {
+        winit.focused_mode = focused_mode;
+        winit.unfocused_mode = unfocused_mode;
+    }
+}
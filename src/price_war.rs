@@ -0,0 +1,223 @@
+//! Price wars - undercut the competition for long enough and they fight back
+//!
+//! The player sets a price relative to list in the market overlay. Customers
+//! follow the cheaper price, so undercutting the competitors wins business -
+//! for a while. Keep it up for a couple of weeks and they start cutting too, a
+//! little every day, until they're just under the player. While the war lasts
+//! their cheap Things fill the market faster, and everyone's margins are
+//! thinner. Stop undercutting for long enough, or take the truce they
+//! eventually offer, and their prices drift back up to list.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::difficulty::DifficultyConfig;
+use crate::economy::DayAdvancedEvent;
+use crate::game_state::AppState;
+use crate::marketing::MarketingState;
+
+/// Lowest and highest price the player can set, relative to list
+const MIN_PRICE: f32 = 0.5;
+const MAX_PRICE: f32 = 2.0;
+/// Step for the price buttons
+const PRICE_STEP: f32 = 0.05;
+/// How far under the competitors counts as undercutting them
+const UNDERCUT_MARGIN: f32 = 0.1;
+/// Days in a row of undercutting before the competitors respond
+const PROVOCATION_DAYS: u32 = 14;
+/// How much the competitors cut each day of a war
+const COMPETITOR_CUT_PER_DAY: f32 = 0.01;
+/// How far under the player's price they aim
+const COMPETITOR_UNDERCUT: f32 = 0.05;
+/// The lowest the competitors will go
+const COMPETITOR_FLOOR: f32 = 0.6;
+/// Days of not undercutting that end a war
+const CEASEFIRE_DAYS: u32 = 10;
+/// Days into a war when the competitors offer a truce
+const TRUCE_OFFER_DAYS: u32 = 45;
+/// Days the offer stays open
+const TRUCE_OFFER_OPEN_DAYS: u32 = 7;
+/// How quickly the competitors' prices recover after a war, per day
+const RECOVERY_PER_DAY: f32 = 0.005;
+/// How strongly customers follow the cheaper price
+pub const PRICE_ELASTICITY: f32 = 1.5;
+/// Extra market the competitors take per point they've cut
+const CHEAP_VOLUME: f32 = 1.0;
+
+/// The player's price after `steps` presses of the price buttons
+pub fn stepped_price(price: f32, steps: i32) -> f32 {
+    let steps_from_list = ((price - 1.0) / PRICE_STEP).round() + steps as f32;
+    (1.0 + steps_from_list * PRICE_STEP).clamp(MIN_PRICE, MAX_PRICE)
+}
+
+/// The competitors' prices, and whether the player has started a war
+#[derive(Resource, Reflect)]
+#[reflect(Resource, Default)]
+pub struct PriceWarState {
+    /// The competitors' price, relative to list
+    pub competitor_price: f32,
+    /// Days in a row the player has undercut them (before a war)
+    pub undercut_days: u32,
+    pub at_war: bool,
+    pub war_days: u32,
+    /// Days in a row the player hasn't been undercutting them (during a war)
+    calm_days: u32,
+    /// Days left to take the competitors' truce
+    pub truce_offer_days: Option<u32>,
+}
+
+impl Default for PriceWarState {
+    fn default() -> Self {
+        Self {
+            competitor_price: 1.0,
+            undercut_days: 0,
+            at_war: false,
+            war_days: 0,
+            calm_days: 0,
+            truce_offer_days: None,
+        }
+    }
+}
+
+impl PriceWarState {
+    /// Multiplier on would-be customers at the player's price
+    pub fn demand_factor(&self, player_price: f32) -> f64 {
+        (self.competitor_price / player_price.max(MIN_PRICE)).powf(PRICE_ELASTICITY).clamp(0.25, 3.0) as f64
+    }
+
+    /// Multiplier on the competitors' share of the market
+    pub fn competitor_volume(&self) -> f32 {
+        1.0 + (1.0 - self.competitor_price).max(0.0) * CHEAP_VOLUME
+    }
+
+    fn undercutting(&self, player_price: f32) -> bool {
+        player_price <= self.competitor_price - UNDERCUT_MARGIN
+    }
+
+    fn end_war(&mut self) {
+        self.at_war = false;
+        self.war_days = 0;
+        self.calm_days = 0;
+        self.undercut_days = 0;
+        self.truce_offer_days = None;
+    }
+}
+
+/// What happened in the price war
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceWarStage {
+    /// Undercut for too long; the competitors start cutting
+    Started,
+    /// The competitors want out
+    TruceOffered,
+    /// The player took the truce
+    TruceAccepted,
+    /// The player stopped undercutting and the competitors stood down
+    Ceasefire,
+    /// The competitors are back at list price
+    Recovered,
+}
+
+/// Message fired as a price war starts, winds down and ends
+#[derive(Event, Message, Clone)]
+pub struct PriceWarEvent {
+    pub stage: PriceWarStage,
+}
+
+/// Message accepting the competitors' truce (sent by the UI)
+#[derive(Event, Message, Clone)]
+pub struct AcceptTruceEvent;
+
+pub struct PriceWarPlugin;
+
+impl Plugin for PriceWarPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PriceWarState>()
+            .add_message::<PriceWarEvent>()
+            .add_message::<AcceptTruceEvent>()
+            .add_systems(
+                Update,
+                (accept_truce, run_price_war)
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// Take the truce: the player goes back to list price and the war is over
+fn accept_truce(
+    mut truce_events: MessageReader<AcceptTruceEvent>,
+    mut marketing: ResMut<MarketingState>,
+    mut price_war: ResMut<PriceWarState>,
+    mut price_war_events: MessageWriter<PriceWarEvent>,
+) {
+    for _ in truce_events.read() {
+        if price_war.truce_offer_days.is_none() {
+            continue;
+        }
+        marketing.price_multiplier = marketing.price_multiplier.max(1.0);
+        price_war.end_war();
+        price_war_events.write(PriceWarEvent {
+            stage: PriceWarStage::TruceAccepted,
+        });
+    }
+}
+
+/// Each game day: watch for undercutting, fight the war, or let prices recover
+fn run_price_war(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    marketing: Res<MarketingState>,
+    difficulty: Res<DifficultyConfig>,
+    mut price_war: ResMut<PriceWarState>,
+    mut price_war_events: MessageWriter<PriceWarEvent>,
+) {
+    for _ in day_events.read() {
+        let price = marketing.price_multiplier;
+
+        if !price_war.at_war {
+            price_war.undercut_days = if price_war.undercutting(price) { price_war.undercut_days + 1 } else { 0 };
+            if price_war.undercut_days >= PROVOCATION_DAYS {
+                price_war.at_war = true;
+                price_war_events.write(PriceWarEvent {
+                    stage: PriceWarStage::Started,
+                });
+            } else if price_war.competitor_price < 1.0 {
+                price_war.competitor_price = (price_war.competitor_price + RECOVERY_PER_DAY).min(1.0);
+                if price_war.competitor_price >= 1.0 {
+                    price_war_events.write(PriceWarEvent {
+                        stage: PriceWarStage::Recovered,
+                    });
+                }
+            }
+            continue;
+        }
+
+        price_war.war_days += 1;
+        // They cut a little every day until they're just under the player
+        let target = (price - COMPETITOR_UNDERCUT).max(COMPETITOR_FLOOR);
+        if price_war.competitor_price > target {
+            let cut = COMPETITOR_CUT_PER_DAY * difficulty.competitor_aggression;
+            price_war.competitor_price = (price_war.competitor_price - cut).max(target);
+        }
+
+        price_war.calm_days = if price >= price_war.competitor_price { price_war.calm_days + 1 } else { 0 };
+        if price_war.calm_days >= CEASEFIRE_DAYS {
+            price_war.end_war();
+            price_war_events.write(PriceWarEvent {
+                stage: PriceWarStage::Ceasefire,
+            });
+            continue;
+        }
+
+        if price_war.war_days == TRUCE_OFFER_DAYS {
+            price_war.truce_offer_days = Some(TRUCE_OFFER_OPEN_DAYS);
+            price_war_events.write(PriceWarEvent {
+                stage: PriceWarStage::TruceOffered,
+            });
+        } else if let Some(days) = price_war.truce_offer_days.as_mut() {
+            *days = days.saturating_sub(1);
+            if *days == 0 {
+                price_war.truce_offer_days = None;
+            }
+        }
+    }
+}
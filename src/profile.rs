@@ -0,0 +1,185 @@
+//! Player profiles - one set of settings, unlocks and exports per person
+//!
+//! Each profile keeps its own `config.json` (settings, key bindings, playtime,
+//! wardrobe) and `exports/` folder under `profiles/<name>/`. The list of
+//! profiles and the last one used live in `profiles/profiles.json`.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where every profile lives
+pub const PROFILES_DIR: &str = "profiles";
+/// The profile list, inside `PROFILES_DIR`
+const INDEX_FILE: &str = "profiles.json";
+/// The profile created on first launch
+const DEFAULT_PROFILE: &str = "Player";
+/// Pre-profiles config file, adopted by the first profile
+const LEGACY_CONFIG: &str = "config.json";
+/// Longest allowed profile name
+pub const MAX_PROFILE_NAME: usize = 16;
+
+/// Every profile on this computer and which one is playing
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+pub struct Profiles {
+    pub active: String,
+    pub names: Vec<String>,
+}
+
+impl Profiles {
+    /// Read the profile list, creating the default profile on first launch
+    pub fn load() -> Self {
+        let index = Path::new(PROFILES_DIR).join(INDEX_FILE);
+        let loaded = fs::read_to_string(&index)
+            .ok()
+            .and_then(|contents| match serde_json::from_str::<Profiles>(&contents) {
+                Ok(profiles) => Some(profiles),
+                Err(err) => {
+                    warn!("Ignoring {}: {}", index.display(), err);
+                    None
+                }
+            })
+            .filter(|profiles| profiles.names.contains(&profiles.active));
+
+        loaded.unwrap_or_else(|| {
+            let profiles = Self {
+                active: DEFAULT_PROFILE.to_string(),
+                names: vec![DEFAULT_PROFILE.to_string()],
+            };
+            // Carry settings over from before profiles existed
+            let config = profiles.config_path();
+            if Path::new(LEGACY_CONFIG).exists() && !config.exists() {
+                let moved = fs::create_dir_all(profiles.dir()).and_then(|_| fs::rename(LEGACY_CONFIG, &config));
+                if let Err(err) = moved {
+                    warn!("Couldn't move {} into {}: {}", LEGACY_CONFIG, config.display(), err);
+                }
+            }
+            profiles.save();
+            profiles
+        })
+    }
+
+    /// Write the profile list
+    pub fn save(&self) {
+        let result = fs::create_dir_all(PROFILES_DIR)
+            .and_then(|_| serde_json::to_string_pretty(self).map_err(std::io::Error::other))
+            .and_then(|json| fs::write(Path::new(PROFILES_DIR).join(INDEX_FILE), json));
+        if let Err(err) = result {
+            warn!("Couldn't save the profile list: {}", err);
+        }
+    }
+
+    /// The active profile's folder
+    pub fn dir(&self) -> PathBuf {
+        profile_dir(&self.active)
+    }
+
+    /// The active profile's config file
+    pub fn config_path(&self) -> PathBuf {
+        self.dir().join("config.json")
+    }
+
+    /// Check a name for a new profile, or for `renaming` (which may keep its
+    /// own name in different case)
+    pub fn validate_name(&self, name: &str, renaming: Option<&str>) -> Result<(), String> {
+        if name.is_empty() {
+            return Err("Type a name first".to_string());
+        }
+        if name.chars().count() > MAX_PROFILE_NAME {
+            return Err(format!("Names can be at most {} characters", MAX_PROFILE_NAME));
+        }
+        if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == ' ' || c == '-' || c == '_') {
+            return Err("Letters, numbers, spaces, - and _ only".to_string());
+        }
+        if name.starts_with(' ') || name.ends_with(' ') {
+            return Err("Names can't start or end with a space".to_string());
+        }
+        if self
+            .names
+            .iter()
+            .filter(|n| Some(n.as_str()) != renaming)
+            .any(|n| n.eq_ignore_ascii_case(name))
+        {
+            return Err(format!("There's already a profile called {}", name));
+        }
+        Ok(())
+    }
+
+    /// Add a profile (its config starts out as defaults)
+    pub fn create(&mut self, name: &str) -> Result<(), String> {
+        self.validate_name(name, None)?;
+        fs::create_dir_all(profile_dir(name)).map_err(|err| format!("Couldn't create the profile: {}", err))?;
+        self.names.push(name.to_string());
+        self.save();
+        Ok(())
+    }
+
+    /// Rename a profile, folder and all
+    pub fn rename(&mut self, old: &str, new: &str) -> Result<(), String> {
+        self.validate_name(new, Some(old))?;
+        let old_dir = profile_dir(old);
+        if old_dir.exists() {
+            fs::rename(&old_dir, profile_dir(new)).map_err(|err| format!("Couldn't rename the profile: {}", err))?;
+        }
+        for name in &mut self.names {
+            if name == old {
+                *name = new.to_string();
+            }
+        }
+        if self.active == old {
+            self.active = new.to_string();
+        }
+        self.save();
+        Ok(())
+    }
+
+    /// Delete a profile and everything in it (not the one playing)
+    pub fn delete(&mut self, name: &str) -> Result<(), String> {
+        if name == self.active {
+            return Err("Switch to another profile before deleting this one".to_string());
+        }
+        let dir = profile_dir(name);
+        if dir.exists() {
+            fs::remove_dir_all(&dir).map_err(|err| format!("Couldn't delete the profile: {}", err))?;
+        }
+        self.names.retain(|n| n != name);
+        self.save();
+        Ok(())
+    }
+}
+
+fn profile_dir(name: &str) -> PathBuf {
+    Path::new(PROFILES_DIR).join(name)
+}
+
+/// Request to change which profile is playing
+#[derive(Event, Message, Clone)]
+pub struct SwitchProfileEvent {
+    pub name: String,
+}
+
+pub struct ProfilePlugin;
+
+impl Plugin for ProfilePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Profiles::load())
+            .add_message::<SwitchProfileEvent>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_profile_can_be_renamed_to_its_own_name_in_other_case() {
+        let profiles = Profiles {
+            active: "bob".to_string(),
+            names: vec!["bob".to_string(), "Alice".to_string()],
+        };
+        assert!(profiles.validate_name("Bob", Some("bob")).is_ok());
+        assert!(profiles.validate_name("Bob", None).is_err());
+        assert!(profiles.validate_name("alice", Some("bob")).is_err());
+    }
+}
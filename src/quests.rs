@@ -0,0 +1,389 @@
+//! Quest chains - a short story for each type of Thing
+//!
+//! Each ThingType has a chain of steps defined in `assets/quests/quests.json`.
+//! A step has an objective (serve more customers, reach a reputation, wait it
+//! out); once it's met the player picks one of the step's outcomes, which
+//! pays out or costs something and decides which step comes next. Chains
+//! branch, and each one ends with an accessory for Terry that can't be
+//! earned any other way. Pivoting starts the new Thing's chain from the top.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use crate::attract::attract_running;
+use crate::cosmetics::{Cosmetic, CosmeticUnlockedEvent, Wardrobe};
+use crate::currency::{money_change, money_whole};
+use crate::economy::DayAdvancedEvent;
+use crate::game_state::{AppState, GameState};
+use crate::thing_type::{ThingRegistry, ThingType};
+
+const QUEST_FILE: &str = "assets/quests/quests.json";
+
+/// What a step asks of the player
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Objective {
+    /// Make this many more Things
+    ThingsProduced(u64),
+    /// Serve this many more customers
+    CustomersServed(u64),
+    /// Have this much in the bank
+    Money(f64),
+    ReputationAbove(f32),
+    ReputationBelow(f32),
+    /// Let this many days go by
+    Days(u32),
+}
+
+impl Objective {
+    pub fn describe(&self) -> String {
+        match self {
+            Objective::ThingsProduced(n) => format!("Make {} Things", n),
+            Objective::CustomersServed(n) => format!("Serve {} customers", n),
+            Objective::Money(amount) => format!("Have {} in the bank", money_whole(*amount)),
+            Objective::ReputationAbove(stars) => format!("Reach {:.1} stars", stars),
+            Objective::ReputationBelow(stars) => format!("Sink below {:.1} stars", stars),
+            Objective::Days(days) => format!("Wait {} days", days),
+        }
+    }
+
+    /// How far along the objective is, from 0.0 to 1.0
+    pub fn progress(&self, log: &QuestLog, game_state: &GameState) -> f32 {
+        let fraction = match *self {
+            Objective::ThingsProduced(n) => {
+                game_state.things_produced.saturating_sub(log.things_at_start) as f64 / n.max(1) as f64
+            }
+            Objective::CustomersServed(n) => {
+                game_state.customers_served.saturating_sub(log.customers_at_start) as f64 / n.max(1) as f64
+            }
+            Objective::Money(amount) => game_state.money.dollars() / amount.max(1.0),
+            Objective::ReputationAbove(stars) => (game_state.reputation / stars.max(0.1)) as f64,
+            Objective::ReputationBelow(stars) => {
+                if game_state.reputation < stars {
+                    1.0
+                } else {
+                    (stars / game_state.reputation.max(0.1)) as f64
+                }
+            }
+            Objective::Days(days) => log.days as f64 / days.max(1) as f64,
+        };
+        (fraction as f32).clamp(0.0, 1.0)
+    }
+
+    pub fn met(&self, log: &QuestLog, game_state: &GameState) -> bool {
+        match *self {
+            Objective::ReputationAbove(stars) => game_state.reputation >= stars,
+            Objective::ReputationBelow(stars) => game_state.reputation < stars,
+            _ => self.progress(log, game_state) >= 1.0,
+        }
+    }
+}
+
+/// What an outcome pays out (or costs)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuestReward {
+    /// Negative amounts are costs
+    Cash(f64),
+    /// Stars gained or lost
+    Reputation(f32),
+    Cosmetic(Cosmetic),
+}
+
+impl QuestReward {
+    pub fn describe(&self) -> String {
+        match self {
+            QuestReward::Cash(amount) => money_change(*amount),
+            QuestReward::Reputation(stars) => format!("{:+.1} stars", stars),
+            QuestReward::Cosmetic(cosmetic) => format!("{} for Terry", cosmetic.name()),
+        }
+    }
+}
+
+/// One way a step can go
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuestOutcome {
+    pub label: String,
+    #[serde(default)]
+    pub rewards: Vec<QuestReward>,
+    /// Step this leads to; none ends the chain
+    #[serde(default)]
+    pub next: Option<String>,
+}
+
+impl QuestOutcome {
+    pub fn describe(&self) -> String {
+        let rewards: Vec<String> = self.rewards.iter().map(QuestReward::describe).collect();
+        if rewards.is_empty() {
+            self.label.clone()
+        } else {
+            format!("{} ({})", self.label, rewards.join(", "))
+        }
+    }
+}
+
+/// A step in a chain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuestStep {
+    pub id: String,
+    pub title: String,
+    pub text: String,
+    /// What Terry says when the step starts
+    #[serde(default)]
+    pub terry: Option<String>,
+    pub objective: Objective,
+    /// What the player can choose once the objective is met (at most three)
+    pub outcomes: Vec<QuestOutcome>,
+}
+
+/// The chain for one type of Thing; the first step is where it starts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuestChain {
+    /// Id of the Thing the chain is for
+    pub thing_type: String,
+    pub title: String,
+    pub steps: Vec<QuestStep>,
+}
+
+impl QuestChain {
+    pub fn step(&self, id: &str) -> Option<&QuestStep> {
+        self.steps.iter().find(|step| step.id == id)
+    }
+}
+
+/// The quest definition file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuestFile {
+    pub chains: Vec<QuestChain>,
+}
+
+/// Every chain, loaded from the quest file
+#[derive(Resource, Default)]
+pub struct QuestBook {
+    pub chains: HashMap<ThingType, QuestChain>,
+}
+
+impl QuestBook {
+    pub fn chain(&self, thing_type: ThingType) -> Option<&QuestChain> {
+        self.chains.get(&thing_type)
+    }
+
+    pub fn step(&self, thing_type: ThingType, id: &str) -> Option<&QuestStep> {
+        self.chain(thing_type).and_then(|chain| chain.step(id))
+    }
+}
+
+/// Where the player is in their Thing's chain
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct QuestLog {
+    /// Whose chain this is
+    pub thing_type: Option<ThingType>,
+    /// The step in progress; none once the chain is finished
+    pub step: Option<String>,
+    /// The objective's been met and an outcome is waiting to be picked
+    pub ready: bool,
+    /// Steps taken so far, with the choice made
+    pub journal: Vec<String>,
+    things_at_start: u64,
+    customers_at_start: u64,
+    days: u32,
+}
+
+impl QuestLog {
+    /// The current step's definition
+    pub fn current<'a>(&self, book: &'a QuestBook) -> Option<&'a QuestStep> {
+        let thing_type = self.thing_type?;
+        book.step(thing_type, self.step.as_deref()?)
+    }
+
+    pub fn finished(&self) -> bool {
+        self.thing_type.is_some() && self.step.is_none()
+    }
+
+    fn begin(&mut self, step: Option<String>, game_state: &GameState) {
+        self.step = step;
+        self.ready = false;
+        self.things_at_start = game_state.things_produced;
+        self.customers_at_start = game_state.customers_served;
+        self.days = 0;
+    }
+}
+
+/// What happened in a chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuestStage {
+    StepStarted,
+    ObjectiveMet,
+    ChainFinished,
+}
+
+/// Message fired as the chain moves along
+#[derive(Event, Message, Clone)]
+pub struct QuestEvent {
+    pub step_id: String,
+    pub stage: QuestStage,
+}
+
+/// Message picking an outcome for a step that's ready (sent by the UI)
+#[derive(Event, Message, Clone)]
+pub struct ChooseQuestOutcomeEvent {
+    pub index: usize,
+}
+
+pub struct QuestPlugin;
+
+impl Plugin for QuestPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<QuestBook>()
+            .init_resource::<QuestLog>()
+            .add_message::<QuestEvent>()
+            .add_message::<ChooseQuestOutcomeEvent>()
+            .add_systems(OnEnter(AppState::Loading), load_quests)
+            .add_systems(
+                Update,
+                (start_chain, track_objective, choose_outcome)
+                    .chain()
+                    .run_if(in_state(AppState::Playing))
+                    .run_if(not(attract_running)),
+            );
+    }
+}
+
+/// Load the chains, skipping any whose steps point at steps that don't exist
+fn load_quests(things: Res<ThingRegistry>, mut book: ResMut<QuestBook>) {
+    let file = match fs::read_to_string(QUEST_FILE) {
+        Ok(contents) => match serde_json::from_str::<QuestFile>(&contents) {
+            Ok(file) => file,
+            Err(e) => {
+                warn!("Failed to parse quest file {}: {}", QUEST_FILE, e);
+                return;
+            }
+        },
+        Err(e) => {
+            warn!("Failed to read quest file {}: {}", QUEST_FILE, e);
+            return;
+        }
+    };
+
+    for chain in file.chains {
+        let dangling = chain
+            .steps
+            .iter()
+            .flat_map(|step| &step.outcomes)
+            .filter_map(|outcome| outcome.next.as_deref())
+            .find(|next| chain.step(next).is_none());
+        if let Some(next) = dangling {
+            warn!("Quest chain '{}' points at missing step '{}'", chain.title, next);
+            continue;
+        }
+        let Some(thing_type) = things.find(&chain.thing_type) else {
+            warn!("Quest chain '{}' is for unknown Thing '{}'", chain.title, chain.thing_type);
+            continue;
+        };
+        book.chains.insert(thing_type, chain);
+    }
+    info!("Loaded {} quest chains", book.chains.len());
+}
+
+/// Start the chain for the Thing being sold (again, after a pivot)
+fn start_chain(
+    game_state: Res<GameState>,
+    book: Res<QuestBook>,
+    mut log: ResMut<QuestLog>,
+    mut quest_events: MessageWriter<QuestEvent>,
+) {
+    let Some(thing_type) = game_state.thing_type else {
+        return;
+    };
+    if log.thing_type == Some(thing_type) {
+        return;
+    }
+    let Some(first) = book.chain(thing_type).and_then(|chain| chain.steps.first()) else {
+        return;
+    };
+
+    log.thing_type = Some(thing_type);
+    log.journal.clear();
+    log.begin(Some(first.id.clone()), &game_state);
+    quest_events.write(QuestEvent {
+        step_id: first.id.clone(),
+        stage: QuestStage::StepStarted,
+    });
+}
+
+/// Count the days and flag the step once its objective is met
+fn track_objective(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    game_state: Res<GameState>,
+    book: Res<QuestBook>,
+    mut log: ResMut<QuestLog>,
+    mut quest_events: MessageWriter<QuestEvent>,
+) {
+    let days = day_events.read().count() as u32;
+    if log.ready {
+        return;
+    }
+    let Some(step) = log.current(&book) else {
+        return;
+    };
+    let (objective, step_id) = (step.objective, step.id.clone());
+
+    log.days += days;
+    if objective.met(&log, &game_state) {
+        log.ready = true;
+        quest_events.write(QuestEvent {
+            step_id,
+            stage: QuestStage::ObjectiveMet,
+        });
+    }
+}
+
+/// Pay out the chosen outcome and move on to the step it leads to
+fn choose_outcome(
+    mut choose_events: MessageReader<ChooseQuestOutcomeEvent>,
+    mut game_state: ResMut<GameState>,
+    mut wardrobe: ResMut<Wardrobe>,
+    book: Res<QuestBook>,
+    mut log: ResMut<QuestLog>,
+    mut quest_events: MessageWriter<QuestEvent>,
+    mut unlock_events: MessageWriter<CosmeticUnlockedEvent>,
+) {
+    for event in choose_events.read() {
+        if !log.ready {
+            continue;
+        }
+        let Some(step) = log.current(&book) else {
+            continue;
+        };
+        let Some(outcome) = step.outcomes.get(event.index) else {
+            continue;
+        };
+
+        for reward in &outcome.rewards {
+            match *reward {
+                QuestReward::Cash(amount) => game_state.money += amount,
+                QuestReward::Reputation(stars) => {
+                    game_state.reputation = (game_state.reputation + stars).clamp(0.0, 5.0);
+                }
+                QuestReward::Cosmetic(cosmetic) => {
+                    if wardrobe.unlock(cosmetic) {
+                        unlock_events.write(CosmeticUnlockedEvent { cosmetic });
+                    }
+                }
+            }
+        }
+
+        let entry = format!("{} - {}", step.title, outcome.label);
+        let step_id = step.id.clone();
+        let next = outcome.next.clone();
+        log.journal.push(entry);
+        log.begin(next.clone(), &game_state);
+        quest_events.write(QuestEvent {
+            step_id: next.unwrap_or(step_id),
+            stage: if log.step.is_some() { QuestStage::StepStarted } else { QuestStage::ChainFinished },
+        });
+    }
+}
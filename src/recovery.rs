@@ -0,0 +1,255 @@
+//! Redemption arcs - climbing back out of a Bad Thing scandal
+//!
+//! Once a Bad run has driven reputation into the ground, Terry's crisis
+//! consultants (his cousins) offer a way out: a Rebrand or an Apology Tour.
+//! Both take weeks, cost money, and might not work.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::accounting::ExpenseCategory;
+use crate::economy::DayAdvancedEvent;
+use crate::game_state::{AppState, GameState, ReputationChangedEvent};
+use crate::thing_type::{ThingRegistry, ThingType};
+
+/// Reputation a Bad Thing must sink below before redemption is on the table
+pub const ROCK_BOTTOM_REPUTATION: f32 = 0.5;
+
+/// The redemption actions available after hitting rock bottom
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum RecoveryAction {
+    /// New name, new logo, new Thing. Pivots the business to Good Things.
+    Rebrand,
+    /// Say sorry in every town that will have you. Restores some reputation.
+    ApologyTour,
+}
+
+impl RecoveryAction {
+    pub const ALL: [RecoveryAction; 2] = [RecoveryAction::Rebrand, RecoveryAction::ApologyTour];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RecoveryAction::Rebrand => "Rebrand",
+            RecoveryAction::ApologyTour => "Apology Tour",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            RecoveryAction::Rebrand => "Become a Good Thing company. Nobody will remember. Probably.",
+            RecoveryAction::ApologyTour => "A heartfelt, focus-grouped apology in 12 cities.",
+        }
+    }
+
+    /// Up-front cost
+    pub fn cost(&self) -> f64 {
+        match self {
+            RecoveryAction::Rebrand => 2_000.0,
+            RecoveryAction::ApologyTour => 500.0,
+        }
+    }
+
+    /// How many game days the action takes
+    pub fn duration_days(&self) -> u32 {
+        match self {
+            RecoveryAction::Rebrand => 42,     // Six weeks
+            RecoveryAction::ApologyTour => 21, // Three weeks
+        }
+    }
+
+    /// Chance (0-1) the public buys it
+    pub fn success_chance(&self) -> f32 {
+        match self {
+            RecoveryAction::Rebrand => 0.45,
+            RecoveryAction::ApologyTour => 0.6,
+        }
+    }
+
+    /// Reputation the business ends up with when it works
+    pub fn success_reputation(&self) -> f32 {
+        match self {
+            RecoveryAction::Rebrand => 2.0,
+            RecoveryAction::ApologyTour => 1.5,
+        }
+    }
+}
+
+/// A redemption action in progress
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct ActiveRecovery {
+    pub action: RecoveryAction,
+    pub days_remaining: u32,
+}
+
+impl ActiveRecovery {
+    /// Fraction of the timeline completed (0-1)
+    pub fn progress(&self) -> f32 {
+        let total = self.action.duration_days() as f32;
+        1.0 - self.days_remaining as f32 / total
+    }
+}
+
+/// Where the player is on the road to redemption
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct RecoveryState {
+    /// Has reputation bottomed out, unlocking the recovery actions?
+    pub unlocked: bool,
+    /// The action currently underway, if any
+    pub active: Option<ActiveRecovery>,
+    /// Set once a Rebrand has turned the run Good
+    pub redeemed: bool,
+}
+
+impl RecoveryState {
+    /// Can this action be started right now?
+    pub fn can_start(&self, action: RecoveryAction, game_state: &GameState, things: &ThingRegistry) -> bool {
+        self.unlocked
+            && !self.redeemed
+            && things.is_shady(game_state.thing_type)
+            && self.active.is_none()
+            && game_state.money >= action.cost()
+    }
+
+    /// Pay for and begin an action. Returns false if it can't be started.
+    pub fn start(&mut self, action: RecoveryAction, game_state: &mut GameState, things: &ThingRegistry) -> bool {
+        if !self.can_start(action, game_state, things) {
+            return false;
+        }
+        game_state.spend(ExpenseCategory::Recovery, action.cost());
+        self.active = Some(ActiveRecovery {
+            action,
+            days_remaining: action.duration_days(),
+        });
+        true
+    }
+}
+
+/// What happened along a recovery arc
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecoveryStage {
+    /// Reputation bottomed out; the actions are now available
+    Unlocked,
+    Started,
+    /// Halfway through the timeline
+    Midway,
+    Succeeded,
+    Failed,
+}
+
+/// Message fired at each step of a recovery arc
+#[derive(Event, Message, Clone)]
+pub struct RecoveryEvent {
+    pub action: Option<RecoveryAction>,
+    pub stage: RecoveryStage,
+}
+
+/// Message requesting that a recovery action begin (sent by the UI)
+#[derive(Event, Message, Clone)]
+pub struct StartRecoveryEvent {
+    pub action: RecoveryAction,
+}
+
+pub struct RecoveryPlugin;
+
+impl Plugin for RecoveryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RecoveryState>()
+            .add_message::<RecoveryEvent>()
+            .add_message::<StartRecoveryEvent>()
+            .add_systems(
+                Update,
+                (unlock_recovery, start_recovery, progress_recovery)
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// Offer redemption once a Bad Thing hits rock bottom
+fn unlock_recovery(
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    mut recovery: ResMut<RecoveryState>,
+    mut recovery_events: MessageWriter<RecoveryEvent>,
+) {
+    if recovery.unlocked || !things.is_shady(game_state.thing_type) {
+        return;
+    }
+
+    if game_state.reputation < ROCK_BOTTOM_REPUTATION {
+        recovery.unlocked = true;
+        recovery_events.write(RecoveryEvent {
+            action: None,
+            stage: RecoveryStage::Unlocked,
+        });
+    }
+}
+
+/// Begin a requested recovery action if it's affordable
+fn start_recovery(
+    mut start_events: MessageReader<StartRecoveryEvent>,
+    mut game_state: ResMut<GameState>,
+    things: Res<ThingRegistry>,
+    mut recovery: ResMut<RecoveryState>,
+    mut recovery_events: MessageWriter<RecoveryEvent>,
+) {
+    for event in start_events.read() {
+        if recovery.start(event.action, &mut game_state, &things) {
+            recovery_events.write(RecoveryEvent {
+                action: Some(event.action),
+                stage: RecoveryStage::Started,
+            });
+        }
+    }
+}
+
+/// Tick the active action each game day and roll for success when it ends
+fn progress_recovery(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    mut game_state: ResMut<GameState>,
+    mut recovery: ResMut<RecoveryState>,
+    mut recovery_events: MessageWriter<RecoveryEvent>,
+    mut rep_events: MessageWriter<ReputationChangedEvent>,
+) {
+    for _ in day_events.read() {
+        let Some(active) = recovery.active.as_mut() else {
+            continue;
+        };
+
+        let was_before_midway = active.progress() < 0.5;
+        active.days_remaining = active.days_remaining.saturating_sub(1);
+        let active = *active;
+
+        if active.days_remaining > 0 {
+            if was_before_midway && active.progress() >= 0.5 {
+                recovery_events.write(RecoveryEvent {
+                    action: Some(active.action),
+                    stage: RecoveryStage::Midway,
+                });
+            }
+            continue;
+        }
+
+        recovery.active = None;
+        let succeeded = rand::random::<f32>() < active.action.success_chance();
+
+        if succeeded {
+            game_state.reputation = game_state.reputation.max(active.action.success_reputation());
+            if active.action == RecoveryAction::Rebrand {
+                game_state.thing_type = Some(ThingType::GOOD);
+                recovery.redeemed = true;
+            }
+        } else {
+            // The public noticed the PR stunt
+            game_state.reputation = (game_state.reputation - 0.25).max(0.0);
+        }
+
+        rep_events.write(ReputationChangedEvent {
+            new_reputation: game_state.reputation,
+        });
+        recovery_events.write(RecoveryEvent {
+            action: Some(active.action),
+            stage: if succeeded { RecoveryStage::Succeeded } else { RecoveryStage::Failed },
+        });
+    }
+}
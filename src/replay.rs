@@ -0,0 +1,120 @@
+//! Replay log - what the player did each day, for scrubbing back through the run
+//!
+//! `RunStats` already keeps a snapshot of the numbers every day and the
+//! `EventLog` keeps what happened and everything Terry said, both by date.
+//! This adds the missing piece, the player's own actions, so the replay
+//! viewer can show each day as it was: the charts up to that point, what was
+//! clicked and bought, and what Terry had to say about it.
+//!
+//! The game's randomness isn't seeded per run, so a replay shows the
+//! recorded days rather than re-simulating them.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::business::{UpgradePurchasedEvent, UpgradeType};
+use crate::clicker::ClickEvent;
+use crate::economy::{GameDate, WorldState};
+use crate::game_state::AppState;
+use crate::marketing::{ChannelToggledEvent, MarketingChannel};
+use crate::pivot::StartPivotEvent;
+use crate::research::{StartResearchEvent, Tech};
+use crate::thing_type::{ThingRegistry, ThingType};
+
+/// Everything the player did on one day
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct DayActions {
+    pub date: GameDate,
+    /// Make Thing presses (including the bot's, in a demo)
+    pub clicks: u32,
+    pub upgrades: Vec<UpgradeType>,
+    pub pivots: Vec<ThingType>,
+    pub research: Vec<Tech>,
+    /// Marketing channels switched on (true) or off
+    pub channels: Vec<(MarketingChannel, bool)>,
+}
+
+impl DayActions {
+    fn new(date: GameDate) -> Self {
+        Self {
+            date,
+            clicks: 0,
+            upgrades: Vec::new(),
+            pivots: Vec::new(),
+            research: Vec::new(),
+            channels: Vec::new(),
+        }
+    }
+
+    /// One line per kind of action
+    pub fn describe(&self, things: &ThingRegistry) -> Vec<String> {
+        let mut lines = Vec::new();
+        if self.clicks > 0 {
+            lines.push(format!("Made Things by hand {} times", self.clicks));
+        }
+        for upgrade in &self.upgrades {
+            lines.push(format!("Bought {}", upgrade.name()));
+        }
+        for thing_type in &self.pivots {
+            lines.push(format!("Started pivoting to {}", things[*thing_type].name));
+        }
+        for tech in &self.research {
+            lines.push(format!("Started researching {}", tech.name()));
+        }
+        for (channel, active) in &self.channels {
+            lines.push(format!("Switched {} {}", channel.name(), if *active { "on" } else { "off" }));
+        }
+        lines
+    }
+}
+
+/// The player's actions this run, one entry per day anything happened
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct ReplayLog {
+    pub days: Vec<DayActions>,
+}
+
+impl ReplayLog {
+    fn today(&mut self, date: GameDate) -> &mut DayActions {
+        if self.days.last().is_none_or(|day| day.date != date) {
+            self.days.push(DayActions::new(date));
+        }
+        self.days.last_mut().expect("just pushed")
+    }
+}
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReplayLog>()
+            .add_systems(Update, record_actions.run_if(in_state(AppState::Playing)));
+    }
+}
+
+/// Note down everything the player did today
+fn record_actions(
+    world: Res<WorldState>,
+    mut clicks: MessageReader<ClickEvent>,
+    mut purchases: MessageReader<UpgradePurchasedEvent>,
+    mut pivots: MessageReader<StartPivotEvent>,
+    mut research: MessageReader<StartResearchEvent>,
+    mut channels: MessageReader<ChannelToggledEvent>,
+    mut log: ResMut<ReplayLog>,
+) {
+    let clicks = clicks.read().count() as u32;
+    let upgrades: Vec<UpgradeType> = purchases.read().map(|event| event.upgrade).collect();
+    let pivots: Vec<ThingType> = pivots.read().map(|event| event.to).collect();
+    let research: Vec<Tech> = research.read().map(|event| event.tech).collect();
+    let channels: Vec<(MarketingChannel, bool)> = channels.read().map(|event| (event.channel, event.active)).collect();
+    if clicks == 0 && upgrades.is_empty() && pivots.is_empty() && research.is_empty() && channels.is_empty() {
+        return;
+    }
+
+    let today = log.today(world.date);
+    today.clicks += clicks;
+    today.upgrades.extend(upgrades);
+    today.pivots.extend(pivots);
+    today.research.extend(research);
+    today.channels.extend(channels);
+}
@@ -0,0 +1,105 @@
+//! Reputation tiers - what your star rating actually does to the business
+//!
+//! Reputation multiplies revenue, but it also decides how many customers show up
+//! at all and who is willing to be seen doing business with you.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::game_state::{AppState, GameState};
+
+/// Broad reputation bands with gameplay consequences
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ReputationTier {
+    /// Below 1 star - demand collapses and partners walk away
+    Pariah,
+    /// 1 to 2.5 stars - customers are wary
+    Shaky,
+    /// 2.5 stars and up - business as usual
+    Respected,
+    /// A full 5 stars - premium channels open up
+    Beloved,
+}
+
+impl ReputationTier {
+    pub fn from_reputation(reputation: f32) -> Self {
+        if reputation < 1.0 {
+            ReputationTier::Pariah
+        } else if reputation < 2.5 {
+            ReputationTier::Shaky
+        } else if reputation < 4.9 {
+            ReputationTier::Respected
+        } else {
+            ReputationTier::Beloved
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReputationTier::Pariah => "Pariah",
+            ReputationTier::Shaky => "Shaky",
+            ReputationTier::Respected => "Respected",
+            ReputationTier::Beloved => "Beloved",
+        }
+    }
+
+    /// Will influencers and backroom partners work with you?
+    pub fn allows_partnerships(&self) -> bool {
+        *self != ReputationTier::Pariah
+    }
+
+    /// Are premium marketing channels available?
+    pub fn unlocks_premium(&self) -> bool {
+        *self == ReputationTier::Beloved
+    }
+}
+
+/// Reputation at which a Bad Thing run officially becomes a scandal
+pub const SCANDAL_REPUTATION: f32 = 1.0;
+
+/// Fraction of would-be customers who actually buy, based on reputation.
+/// 1.0 from 2.5 stars up; falls off below that and collapses under 1 star.
+pub fn customer_volume_factor(reputation: f32) -> f64 {
+    let reputation = reputation.clamp(0.0, 5.0) as f64;
+    if reputation < 1.0 {
+        // Collapse: 10% of normal at zero stars, 50% at one star
+        0.1 + reputation * 0.4
+    } else if reputation < 2.5 {
+        // Wary customers: 50% at one star, back to normal at 2.5
+        0.5 + (reputation - 1.0) / 1.5 * 0.5
+    } else {
+        1.0
+    }
+}
+
+/// Message fired when reputation crosses into a different tier
+#[derive(Event, Message, Clone)]
+pub struct ReputationTierChangedEvent {
+    pub old_tier: ReputationTier,
+    pub new_tier: ReputationTier,
+}
+
+pub struct ReputationPlugin;
+
+impl Plugin for ReputationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<ReputationTierChangedEvent>()
+            .add_systems(Update, detect_tier_changes.run_if(in_state(AppState::Playing)));
+    }
+}
+
+/// Watch reputation and announce tier changes
+fn detect_tier_changes(
+    game_state: Res<GameState>,
+    mut tier_events: MessageWriter<ReputationTierChangedEvent>,
+    mut last_tier: Local<Option<ReputationTier>>,
+) {
+    let tier = ReputationTier::from_reputation(game_state.reputation);
+
+    match *last_tier {
+        Some(old_tier) if old_tier != tier => {
+            tier_events.write(ReputationTierChangedEvent { old_tier, new_tier: tier });
+        }
+        _ => {}
+    }
+    *last_tier = Some(tier);
+}
@@ -0,0 +1,144 @@
+//! R&D - spend money and time to unlock new business capabilities
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::accounting::ExpenseCategory;
+use crate::economy::DayAdvancedEvent;
+use crate::game_state::{AppState, GameState};
+
+/// Technologies that can be researched
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum Tech {
+    /// Rules that run the business while you're not looking
+    Automation,
+    /// 3-packs and Deluxe Boxes
+    Packaging,
+}
+
+impl Tech {
+    pub const ALL: [Tech; 2] = [Tech::Automation, Tech::Packaging];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Tech::Automation => "Business Automation",
+            Tech::Packaging => "Product Packaging",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Tech::Automation => "Write simple rules that run once a day. Terry calls it 'delegating to the spreadsheet.'",
+            Tech::Packaging => "Sell Things in 3-packs and Deluxe Boxes. Same Thing, more cardboard, new price.",
+        }
+    }
+
+    /// Up-front research cost
+    pub fn cost(&self) -> f64 {
+        match self {
+            Tech::Automation => 2_500.0,
+            Tech::Packaging => 1_500.0,
+        }
+    }
+
+    /// Game days of research
+    pub fn duration_days(&self) -> u32 {
+        match self {
+            Tech::Automation => 14,
+            Tech::Packaging => 10,
+        }
+    }
+}
+
+/// A research project underway
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct ActiveResearch {
+    pub tech: Tech,
+    pub days_remaining: u32,
+}
+
+/// What has been researched, and what's in the lab right now
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct ResearchState {
+    pub unlocked: Vec<Tech>,
+    pub active: Option<ActiveResearch>,
+}
+
+impl ResearchState {
+    pub fn is_unlocked(&self, tech: Tech) -> bool {
+        self.unlocked.contains(&tech)
+    }
+
+    pub fn can_start(&self, tech: Tech, game_state: &GameState) -> bool {
+        self.active.is_none() && !self.is_unlocked(tech) && game_state.money >= tech.cost()
+    }
+}
+
+/// Message requesting research on a tech (sent by the UI)
+#[derive(Event, Message, Clone)]
+pub struct StartResearchEvent {
+    pub tech: Tech,
+}
+
+/// Message fired when research finishes
+#[derive(Event, Message, Clone)]
+pub struct ResearchCompletedEvent {
+    pub tech: Tech,
+}
+
+pub struct ResearchPlugin;
+
+impl Plugin for ResearchPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ResearchState>()
+            .add_message::<StartResearchEvent>()
+            .add_message::<ResearchCompletedEvent>()
+            .add_systems(
+                Update,
+                (start_research, progress_research)
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// Pay for requested research and start the clock
+fn start_research(
+    mut start_events: MessageReader<StartResearchEvent>,
+    mut game_state: ResMut<GameState>,
+    mut research: ResMut<ResearchState>,
+) {
+    for event in start_events.read() {
+        if !research.can_start(event.tech, &game_state) {
+            continue;
+        }
+        game_state.spend(ExpenseCategory::Research, event.tech.cost());
+        research.active = Some(ActiveResearch {
+            tech: event.tech,
+            days_remaining: event.tech.duration_days(),
+        });
+    }
+}
+
+/// Count research down each game day
+fn progress_research(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    mut research: ResMut<ResearchState>,
+    mut completed_events: MessageWriter<ResearchCompletedEvent>,
+) {
+    for _ in day_events.read() {
+        let Some(active) = research.active.as_mut() else {
+            continue;
+        };
+
+        active.days_remaining = active.days_remaining.saturating_sub(1);
+        if active.days_remaining > 0 {
+            continue;
+        }
+
+        let tech = active.tech;
+        research.active = None;
+        research.unlocked.push(tech);
+        completed_events.write(ResearchCompletedEvent { tech });
+    }
+}
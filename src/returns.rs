@@ -0,0 +1,228 @@
+//! Returns and refunds - some of what you sell comes back
+//!
+//! A share of every day's sales is returned a week and a half later, more
+//! for flimsy or Bad Things and unhappy customers. The refund policy decides
+//! how many returns are accepted and what that does to reputation. Restocking
+//! resells accepted returns as open-box Things for part of their price.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use std::collections::VecDeque;
+use crate::accounting::ExpenseCategory;
+use crate::economy::DayAdvancedEvent;
+use crate::game_state::{AppState, GameState, MoneyChangedEvent, ReputationChangedEvent};
+use crate::thing_type::{ThingDefinition, ThingRegistry};
+
+/// Days between a sale and its return
+const RETURN_DELAY_DAYS: usize = 10;
+/// Return rate multiplier for a 0-star reputation (5 stars is 1.0)
+const UNHAPPY_RETURN_FACTOR: f64 = 2.0;
+/// Share of a restocked return's refund won back by selling it open-box
+const RESTOCK_RECOVERY: f64 = 0.5;
+/// Handling cost per restocked Thing, as a share of its refund
+const RESTOCK_HANDLING: f64 = 0.1;
+/// Most reputation a day of returns can move
+const MAX_DAILY_REPUTATION: f32 = 0.05;
+
+/// How the shop treats people bringing Things back
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum RefundPolicy {
+    /// Any return, any reason - more returns, but customers love it
+    NoQuestionsAsked,
+    /// Returns with a receipt
+    #[default]
+    WithReceipt,
+    /// Nothing comes back except what the bank forces through
+    AllSalesFinal,
+}
+
+impl RefundPolicy {
+    pub const ALL: [RefundPolicy; 3] = [
+        RefundPolicy::NoQuestionsAsked,
+        RefundPolicy::WithReceipt,
+        RefundPolicy::AllSalesFinal,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RefundPolicy::NoQuestionsAsked => "No Questions Asked",
+            RefundPolicy::WithReceipt => "With Receipt",
+            RefundPolicy::AllSalesFinal => "All Sales Final",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            RefundPolicy::NoQuestionsAsked => "More returns, but every refund earns goodwill.",
+            RefundPolicy::WithReceipt => "The usual. Nobody is thrilled, nobody is furious.",
+            RefundPolicy::AllSalesFinal => "Most returns are refused. Chargebacks still happen, and people talk.",
+        }
+    }
+
+    /// How many would-be returns people attempt under this policy
+    fn return_factor(&self) -> f64 {
+        match self {
+            RefundPolicy::NoQuestionsAsked => 1.3,
+            RefundPolicy::WithReceipt => 1.0,
+            RefundPolicy::AllSalesFinal => 1.0,
+        }
+    }
+
+    /// Share of attempted returns that end in a refund
+    fn accepted_share(&self) -> f64 {
+        match self {
+            RefundPolicy::NoQuestionsAsked => 1.0,
+            RefundPolicy::WithReceipt => 0.9,
+            // Chargebacks
+            RefundPolicy::AllSalesFinal => 0.3,
+        }
+    }
+
+    /// Reputation change per accepted and per refused return
+    fn reputation_per_return(&self) -> (f32, f32) {
+        match self {
+            RefundPolicy::NoQuestionsAsked => (0.002, 0.0),
+            RefundPolicy::WithReceipt => (0.0, -0.001),
+            RefundPolicy::AllSalesFinal => (0.0, -0.003),
+        }
+    }
+}
+
+/// One day's sales, waiting to see what comes back
+#[derive(Debug, Clone, Copy, Default, Reflect)]
+struct SalesDay {
+    units: u64,
+    revenue: f64,
+}
+
+/// Returns in flight and the policy for handling them
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct ReturnsState {
+    pub policy: RefundPolicy,
+    /// Resell accepted returns as open-box Things
+    pub restocking: bool,
+    /// Sales from the last `RETURN_DELAY_DAYS` days, oldest first
+    recent_sales: VecDeque<SalesDay>,
+    today: SalesDay,
+    /// Fraction of a return carried over to the next day
+    pending_returns: f64,
+    last_customers_served: u64,
+    pub total_returned: u64,
+    pub total_refunded: f64,
+    pub total_restocked: u64,
+}
+
+impl ReturnsState {
+    /// Refunds still to come from recent sales, at today's rates (roughly)
+    pub fn refunds_in_flight(&self, game_state: &GameState, things: &ThingRegistry) -> f64 {
+        let rate = game_state
+            .thing_type
+            .map(|t| return_rate(&things[t], game_state.reputation, self.policy))
+            .unwrap_or(0.0);
+        self.recent_sales.iter().map(|day| day.revenue).sum::<f64>() * rate * self.policy.accepted_share()
+    }
+}
+
+/// Share of sold Things that people try to bring back
+pub fn return_rate(thing: &ThingDefinition, reputation: f32, policy: RefundPolicy) -> f64 {
+    let unhappiness = 1.0 - (reputation.clamp(0.0, 5.0) / 5.0) as f64;
+    thing.return_rate * (1.0 + unhappiness * (UNHAPPY_RETURN_FACTOR - 1.0)) * policy.return_factor()
+}
+
+/// Message fired when a day's returns are refunded
+#[derive(Event, Message, Clone)]
+pub struct RefundEvent {
+    pub units: u64,
+    pub amount: f64,
+    /// Won back by reselling the returns open-box
+    pub resale: f64,
+}
+
+pub struct ReturnsPlugin;
+
+impl Plugin for ReturnsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReturnsState>()
+            .add_message::<RefundEvent>()
+            .add_systems(Update, process_returns.run_if(in_state(AppState::Playing)));
+    }
+}
+
+/// Tally sales as they happen, and once a day refund what comes back
+fn process_returns(
+    mut money_events: MessageReader<MoneyChangedEvent>,
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    things: Res<ThingRegistry>,
+    mut game_state: ResMut<GameState>,
+    mut returns: ResMut<ReturnsState>,
+    mut refund_events: MessageWriter<RefundEvent>,
+    mut rep_events: MessageWriter<ReputationChangedEvent>,
+) {
+    for event in money_events.read() {
+        if event.delta > 0.0 {
+            returns.today.revenue += event.delta;
+        }
+    }
+
+    for _ in day_events.read() {
+        let sold = game_state.customers_served.saturating_sub(returns.last_customers_served);
+        returns.last_customers_served = game_state.customers_served;
+        returns.today.units += sold;
+        let today = std::mem::take(&mut returns.today);
+        returns.recent_sales.push_back(today);
+        if returns.recent_sales.len() <= RETURN_DELAY_DAYS {
+            continue;
+        }
+        let Some(due) = returns.recent_sales.pop_front() else {
+            continue;
+        };
+        let Some(thing_type) = game_state.thing_type else {
+            continue;
+        };
+        if due.units == 0 {
+            continue;
+        }
+
+        let policy = returns.policy;
+        returns.pending_returns += due.units as f64 * return_rate(&things[thing_type], game_state.reputation, policy).min(1.0);
+        let attempted = (returns.pending_returns.floor() as u64).min(due.units);
+        returns.pending_returns -= attempted as f64;
+        if attempted == 0 {
+            continue;
+        }
+
+        let accepted = (attempted as f64 * policy.accepted_share()).round() as u64;
+        let refused = attempted - accepted;
+        let price = due.revenue / due.units as f64;
+        let refund = accepted as f64 * price;
+
+        game_state.money -= refund;
+        returns.total_returned += accepted;
+        returns.total_refunded += refund;
+
+        // Open-box resale; nobody wants a returned scam
+        let mut resale = 0.0;
+        if returns.restocking && !things[thing_type].shady && accepted > 0 {
+            game_state.spend(ExpenseCategory::Returns, refund * RESTOCK_HANDLING);
+            resale = refund * RESTOCK_RECOVERY;
+            game_state.money += resale;
+            returns.total_restocked += accepted;
+        }
+        refund_events.write(RefundEvent {
+            units: accepted,
+            amount: refund,
+            resale,
+        });
+
+        let (per_accepted, per_refused) = policy.reputation_per_return();
+        let rep_change = (per_accepted * accepted as f32 + per_refused * refused as f32)
+            .clamp(-MAX_DAILY_REPUTATION, MAX_DAILY_REPUTATION);
+        if rep_change.abs() > 0.0001 {
+            game_state.reputation = (game_state.reputation + rep_change).clamp(0.0, 5.0);
+            rep_events.write(ReputationChangedEvent {
+                new_reputation: game_state.reputation,
+            });
+        }
+    }
+}
@@ -0,0 +1,224 @@
+//! Milestone rewards - hitting a milestone pays out, not just congratulates
+//!
+//! `MILESTONE_REWARDS` is the data table: each milestone maps to a cash
+//! bonus, a temporary boost, a cosmetic for Terry, or (at the big thresholds)
+//! a choice of three reward cards. Each milestone pays out once per run, even
+//! if money dips below a threshold and climbs back over it.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::SystemParam;
+use crate::attract::attract_running;
+use crate::cosmetics::{Cosmetic, CosmeticUnlockedEvent, Wardrobe};
+use crate::currency::money_whole;
+use crate::economy::DayAdvancedEvent;
+use crate::fortune::RunModifiers;
+use crate::game_state::{AppState, GameState, MilestoneEvent, MilestoneType};
+
+/// What a temporary boost multiplies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum Boost {
+    Production,
+    Revenue,
+}
+
+impl Boost {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Boost::Production => "production",
+            Boost::Revenue => "revenue",
+        }
+    }
+
+    fn apply(&self, modifiers: &mut RunModifiers, factor: f64) {
+        match self {
+            Boost::Production => modifiers.production *= factor,
+            Boost::Revenue => modifiers.revenue *= factor,
+        }
+    }
+}
+
+/// A milestone payout
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Reward {
+    Cash(f64),
+    Boost { boost: Boost, multiplier: f64, days: u32 },
+    Cosmetic(Cosmetic),
+    /// The player picks one of three
+    Choice(&'static [Reward; 3]),
+}
+
+impl Reward {
+    pub fn describe(&self) -> String {
+        match self {
+            Reward::Cash(amount) => format!("{} bonus", money_whole(*amount)),
+            Reward::Boost { boost, multiplier, days } => {
+                format!("+{:.0}% {} for {} days", (multiplier - 1.0) * 100.0, boost.name(), days)
+            }
+            Reward::Cosmetic(cosmetic) => format!("{} for Terry", cosmetic.name()),
+            Reward::Choice(_) => "Pick a reward card".to_string(),
+        }
+    }
+}
+
+const SMALL_CHOICE: [Reward; 3] = [
+    Reward::Cash(500.0),
+    Reward::Boost { boost: Boost::Production, multiplier: 1.5, days: 10 },
+    Reward::Boost { boost: Boost::Revenue, multiplier: 1.25, days: 10 },
+];
+
+const BIG_CHOICE: [Reward; 3] = [
+    Reward::Cash(10_000.0),
+    Reward::Boost { boost: Boost::Production, multiplier: 2.0, days: 14 },
+    Reward::Boost { boost: Boost::Revenue, multiplier: 1.5, days: 14 },
+];
+
+/// The milestone data table
+pub const MILESTONE_REWARDS: &[(MilestoneType, Reward)] = &[
+    (MilestoneType::ThingsProduced(100), Reward::Cash(25.0)),
+    (MilestoneType::ThingsProduced(1_000), Reward::Boost { boost: Boost::Production, multiplier: 1.25, days: 7 }),
+    (MilestoneType::ThingsProduced(10_000), Reward::Choice(&SMALL_CHOICE)),
+    (MilestoneType::ThingsProduced(100_000), Reward::Cosmetic(Cosmetic::TopHat)),
+    (MilestoneType::ThingsProduced(1_000_000), Reward::Choice(&BIG_CHOICE)),
+    (MilestoneType::MoneyEarned(100), Reward::Boost { boost: Boost::Revenue, multiplier: 1.1, days: 5 }),
+    (MilestoneType::MoneyEarned(1_000), Reward::Cash(100.0)),
+    (MilestoneType::MoneyEarned(10_000), Reward::Cosmetic(Cosmetic::TinyBriefcase)),
+    (MilestoneType::MoneyEarned(100_000), Reward::Choice(&BIG_CHOICE)),
+];
+
+/// The reward for a milestone, if it has one
+pub fn reward_for(milestone: MilestoneType) -> Option<Reward> {
+    MILESTONE_REWARDS.iter().find(|(m, _)| *m == milestone).map(|(_, reward)| *reward)
+}
+
+/// A temporary boost that's running
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct ActiveBoost {
+    pub boost: Boost,
+    pub multiplier: f64,
+    pub days_remaining: u32,
+}
+
+/// Rewards paid out this run
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct RewardState {
+    pub claimed: Vec<MilestoneType>,
+    pub boosts: Vec<ActiveBoost>,
+    /// Reward cards waiting for the player to pick one
+    #[reflect(ignore)]
+    pub pending_choice: Option<&'static [Reward; 3]>,
+}
+
+/// Message fired when a reward is paid out
+#[derive(Event, Message, Clone)]
+pub struct RewardEvent {
+    pub reward: Reward,
+}
+
+/// Message picking one of the pending reward cards (sent by the UI)
+#[derive(Event, Message, Clone)]
+pub struct ChooseRewardEvent {
+    pub index: usize,
+}
+
+pub struct RewardPlugin;
+
+impl Plugin for RewardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RewardState>()
+            .add_message::<RewardEvent>()
+            .add_message::<ChooseRewardEvent>()
+            .add_systems(
+                Update,
+                (grant_milestone_rewards, expire_boosts)
+                    .chain()
+                    .run_if(in_state(AppState::Playing))
+                    .run_if(not(attract_running)),
+            );
+    }
+}
+
+/// Everything a reward can pay out into
+#[derive(SystemParam)]
+struct Payout<'w> {
+    game_state: ResMut<'w, GameState>,
+    modifiers: ResMut<'w, RunModifiers>,
+    wardrobe: ResMut<'w, Wardrobe>,
+    unlock_events: MessageWriter<'w, CosmeticUnlockedEvent>,
+}
+
+impl Payout<'_> {
+    fn pay(&mut self, reward: Reward, rewards: &mut RewardState) {
+        match reward {
+            Reward::Cash(amount) => self.game_state.money += amount,
+            Reward::Boost { boost, multiplier, days } => {
+                boost.apply(&mut self.modifiers, multiplier);
+                rewards.boosts.push(ActiveBoost {
+                    boost,
+                    multiplier,
+                    days_remaining: days,
+                });
+            }
+            Reward::Cosmetic(cosmetic) => {
+                if self.wardrobe.unlock(cosmetic) {
+                    self.unlock_events.write(CosmeticUnlockedEvent { cosmetic });
+                }
+            }
+            Reward::Choice(cards) => rewards.pending_choice = Some(cards),
+        }
+    }
+}
+
+/// Pay out milestones from the table, and the reward card the player picks
+fn grant_milestone_rewards(
+    mut milestone_events: MessageReader<MilestoneEvent>,
+    mut choose_events: MessageReader<ChooseRewardEvent>,
+    mut payout: Payout,
+    mut rewards: ResMut<RewardState>,
+    mut reward_events: MessageWriter<RewardEvent>,
+) {
+    let mut earned = Vec::new();
+    for event in milestone_events.read() {
+        let milestone = event.milestone_type;
+        let Some(reward) = reward_for(milestone) else {
+            continue;
+        };
+        if rewards.claimed.contains(&milestone) {
+            continue;
+        }
+        rewards.claimed.push(milestone);
+        earned.push(reward);
+    }
+    for event in choose_events.read() {
+        if let Some(card) = rewards.pending_choice.and_then(|cards| cards.get(event.index).copied()) {
+            rewards.pending_choice = None;
+            earned.push(card);
+        }
+    }
+
+    for reward in earned {
+        payout.pay(reward, &mut rewards);
+        reward_events.write(RewardEvent { reward });
+    }
+}
+
+/// Count boosts down each game day and take them off when they run out
+fn expire_boosts(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    mut modifiers: ResMut<RunModifiers>,
+    mut rewards: ResMut<RewardState>,
+) {
+    for _ in day_events.read() {
+        if rewards.boosts.is_empty() {
+            continue;
+        }
+        for active in rewards.boosts.iter_mut() {
+            active.days_remaining = active.days_remaining.saturating_sub(1);
+            if active.days_remaining == 0 {
+                active.boost.apply(&mut modifiers, 1.0 / active.multiplier);
+            }
+        }
+        rewards.boosts.retain(|active| active.days_remaining > 0);
+    }
+}
@@ -0,0 +1,412 @@
+//! Saved runs - the run in progress, kept on disk so quitting doesn't lose it
+//!
+//! A save is a snapshot of every run resource (see `snapshot`) in the
+//! active profile's `save.json`. It's written when the player asks, at the
+//! start of every game month and on quitting, and never during demos.
+//! Continuing from the selection screen loads the resources back and goes
+//! straight to the main screen.
+//!
+//! Each save also takes a small screenshot, `save.png` next to the save, which
+//! the Continue button shows so the run is easy to recognise. The screenshot
+//! lands a frame or two after the save (and not at all when the game is
+//! closing), so it can lag one save behind.
+//!
+//! A made-up Thing isn't in anyone else's registry, so its definition goes in
+//! the save too. The save records its format version and loads through
+//! `migration` like a profile's config does, so a save from an older version
+//! is backed up and upgraded when the format changes.
+
+use bevy::prelude::*;
+use bevy::app::AppExit;
+use bevy::asset::RenderAssetUsages;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::image::{CompressedImageFormats, ImageSampler, ImageType};
+use bevy::render::view::screenshot::{Screenshot, ScreenshotCaptured};
+use bevy::window::PrimaryWindow;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::attract::{attract_running, AttractMode};
+use crate::bench::Benchmarking;
+use crate::currency::{money_whole, Money};
+use crate::economy::{MonthStartedEvent, WorldState};
+use crate::fortune::RunModifiers;
+use crate::game_state::{reset_run, AppState, GameState, InGame};
+use crate::profile::Profiles;
+use crate::migration::Format;
+use crate::snapshot;
+use crate::thing_type::{ThingDefinition, ThingRegistry, ThingType};
+
+/// Format of `save.json`. When it changes, bump `current` and add the upgrader
+/// from the previous version.
+const SAVE_FORMAT: Format = Format {
+    current: 2,
+    upgraders: &[v1_to_v2],
+};
+
+/// Version 2 keeps savings, the crypto cost basis and gift cards owed in whole
+/// cents, like the cash balance, where version 1 had them in dollars
+fn v1_to_v2(save: &mut Map<String, Value>) -> Result<(), String> {
+    let Some(resources) = save.get_mut("resources") else {
+        return Ok(());
+    };
+    let to_cents = |amount: Option<&mut Value>| {
+        if let Some(amount) = amount {
+            *amount = Value::from(Money::from_dollars(amount.as_f64().unwrap_or_default()).cents());
+        }
+    };
+    if let Some(savings) = resources.get_mut("SavingsState") {
+        to_cents(savings.get_mut("balance"));
+        to_cents(savings.get_mut("interest_earned"));
+        if let Some(Value::Array(withdrawals)) = savings.get_mut("withdrawals") {
+            for withdrawal in withdrawals {
+                to_cents(withdrawal.get_mut("amount"));
+            }
+        }
+    }
+    if let Some(crypto) = resources.get_mut("CryptoState") {
+        to_cents(crypto.get_mut("cost_basis"));
+    }
+    if let Some(cards) = resources.get_mut("GiftCards") {
+        to_cents(cards.get_mut("outstanding"));
+    }
+    Ok(())
+}
+
+/// Largest size of the screenshot kept with a save (it keeps the window's shape)
+const THUMBNAIL_SIZE: (u32, u32) = (192, 108);
+
+/// What `save.json` holds
+#[derive(Serialize, Deserialize)]
+struct SaveFile {
+    version: u32,
+    /// One line about the run, for the Continue button
+    summary: String,
+    /// The made-up Thing the run sells, if it sells one
+    custom_thing: Option<ThingDefinition>,
+    /// Every run resource, by name
+    resources: Value,
+}
+
+/// The active profile's save file
+pub fn save_path(profiles: &Profiles) -> PathBuf {
+    profiles.dir().join("save.json")
+}
+
+/// The screenshot of the active profile's saved run
+pub fn thumbnail_path(profiles: &Profiles) -> PathBuf {
+    profiles.dir().join("save.png")
+}
+
+/// The saved run's screenshot, if it has one
+pub fn load_thumbnail(path: &Path) -> Option<Image> {
+    let bytes = fs::read(path).ok()?;
+    Image::from_buffer(
+        &bytes,
+        ImageType::Extension("png"),
+        CompressedImageFormats::NONE,
+        true,
+        ImageSampler::Default,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+    .map_err(|err| warn!("Couldn't read {}: {}", path.display(), err))
+    .ok()
+}
+
+/// Shrink a captured screenshot and write it to `path`
+fn save_thumbnail(path: PathBuf) -> impl FnMut(On<ScreenshotCaptured>) {
+    move |captured| {
+        let result = captured
+            .image
+            .clone()
+            .try_into_dynamic()
+            .map_err(|err| err.to_string())
+            .and_then(|image| {
+                // Drop the alpha channel, which holds brightness rather than transparency with HDR on
+                let (width, height) = THUMBNAIL_SIZE;
+                image.thumbnail(width, height).to_rgb8().save(&path).map_err(|err| err.to_string())
+            });
+        if let Err(err) = result {
+            warn!("Couldn't save the thumbnail {}: {}", path.display(), err);
+        }
+    }
+}
+
+fn read(path: &Path) -> Result<SaveFile, String> {
+    let contents = fs::read_to_string(path).map_err(|err| err.to_string())?;
+    let save = SAVE_FORMAT.load_file(path, &contents).map_err(|err| err.to_string())?;
+    serde_json::from_value(save).map_err(|err| err.to_string())
+}
+
+/// The save for the run in `world`
+fn save_file(world: &World) -> Result<SaveFile, String> {
+    let custom_thing = world
+        .resource::<GameState>()
+        .thing_type
+        .filter(ThingType::is_custom)
+        .and_then(|_| world.resource::<ThingRegistry>().custom().cloned());
+    Ok(SaveFile {
+        version: SAVE_FORMAT.current,
+        summary: summary(world),
+        custom_thing,
+        resources: snapshot::to_json(world)?,
+    })
+}
+
+/// Load a save into `world`; see `snapshot::apply_json` for what an error means
+fn load(world: &mut World, save: &SaveFile) -> Result<(), String> {
+    if let Some(definition) = &save.custom_thing {
+        world.resource_mut::<ThingRegistry>().set_custom(definition.clone());
+    }
+    snapshot::apply_json(world, &save.resources)
+}
+
+/// The saved run's summary, if there's one to continue
+pub fn peek(path: &Path) -> Option<String> {
+    read(path).ok().map(|save| save.summary)
+}
+
+/// Saves asked for, and how the last save or load went
+#[derive(Resource, Default)]
+pub struct SavedRun {
+    /// Set by the Continue button, handled at the end of the frame
+    pub continue_requested: bool,
+    /// Set by the Save button and the autosaves, handled at the end of the frame
+    pub save_requested: bool,
+    /// What the last save or load came to
+    pub status: Option<String>,
+}
+
+/// Message fired when a saved run has been loaded (so trackers can catch up with it)
+#[derive(Event, Message, Clone)]
+pub struct RunLoadedEvent;
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SavedRun>()
+            .add_message::<RunLoadedEvent>()
+            .add_systems(
+                Update,
+                request_autosave.run_if(in_state(AppState::Playing)).run_if(not(attract_running)),
+            )
+            .add_systems(
+                Last,
+                (
+                    request_save_on_exit,
+                    // The pause menu's quit buttons save from Paused
+                    write_save.run_if(in_state(InGame)),
+                    continue_saved_run.run_if(in_state(AppState::ThingSelection)),
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Autosave as each game month starts
+fn request_autosave(mut month_events: MessageReader<MonthStartedEvent>, mut saved: ResMut<SavedRun>) {
+    if month_events.read().count() > 0 {
+        saved.save_requested = true;
+    }
+}
+
+/// Save on the way out
+fn request_save_on_exit(mut exit_events: MessageReader<AppExit>, mut saved: ResMut<SavedRun>) {
+    if exit_events.read().count() > 0 {
+        saved.save_requested = true;
+    }
+}
+
+/// One line about the run: the Thing, the date and the money
+fn summary(world: &World) -> String {
+    let game_state = world.resource::<GameState>();
+    let thing = game_state.thing_type.map_or("Nothing", |thing_type| &world.resource::<ThingRegistry>()[thing_type].name);
+    format!(
+        "{}, {} ({})",
+        thing,
+        world.resource::<WorldState>().date.format(),
+        money_whole(game_state.money.dollars())
+    )
+}
+
+/// Write the save, if one's been asked for (exclusive, since snapshots read resources straight from the world)
+fn write_save(world: &mut World) {
+    // Checked before touching the resource, so it only shows as changed when something happens
+    if !world.resource::<SavedRun>().save_requested {
+        return;
+    }
+    world.resource_mut::<SavedRun>().save_requested = false;
+    // Neither the demo nor the benchmark is the player's run
+    if world.resource::<AttractMode>().running || world.contains_resource::<Benchmarking>() {
+        return;
+    }
+
+    let path = save_path(world.resource::<Profiles>());
+    let result = save_file(world)
+        .and_then(|save| serde_json::to_string(&save).map_err(|err| err.to_string()))
+        .and_then(|json| {
+            path.parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|_| fs::write(&path, json))
+                .map_err(|err| err.to_string())
+        });
+    let status = match result {
+        Ok(()) => {
+            // Headless runs have no window to take a picture of
+            let mut windows = world.query_filtered::<(), With<PrimaryWindow>>();
+            if windows.iter(world).next().is_some() {
+                let thumbnail = thumbnail_path(world.resource::<Profiles>());
+                world.spawn(Screenshot::primary_window()).observe(save_thumbnail(thumbnail));
+            }
+            format!("Saved {}", summary(world))
+        }
+        Err(err) => {
+            warn!("Couldn't save {}: {}", path.display(), err);
+            format!("Save failed: {}", err)
+        }
+    };
+    world.resource_mut::<SavedRun>().status = Some(status);
+}
+
+/// Load the saved run and start playing it, if the player asked to continue
+fn continue_saved_run(world: &mut World) {
+    if !world.resource::<SavedRun>().continue_requested {
+        return;
+    }
+    world.resource_mut::<SavedRun>().continue_requested = false;
+
+    let path = save_path(world.resource::<Profiles>());
+    let save = match read(&path) {
+        Ok(save) => save,
+        Err(err) => {
+            warn!("Couldn't read {}: {}", path.display(), err);
+            world.resource_mut::<SavedRun>().status = Some(format!("Couldn't continue: {}", err));
+            return;
+        }
+    };
+    // Start from a clean run, so anything that doesn't load keeps its fresh value
+    // (not whatever the last run left behind); the rest of the run carries on
+    reset_run(world);
+    let loaded = load(world, &save);
+    if world.resource::<GameState>().thing_type.is_none() {
+        // Nothing to sell (its mod has been taken out, say), so there's no run to continue
+        warn!("Couldn't continue {}: {}", path.display(), loaded.err().unwrap_or_default());
+        reset_run(world);
+        world.resource_mut::<SavedRun>().status =
+            Some("Couldn't continue: the saved run sells a Thing this game doesn't have".to_string());
+        return;
+    }
+    let status = match loaded {
+        Ok(()) => format!("Continued {}", save.summary),
+        Err(err) => {
+            warn!("Parts of {} couldn't be loaded: {}", path.display(), err);
+            format!("Continued {} (some of it couldn't be loaded)", save.summary)
+        }
+    };
+
+    // The run's fortune was settled when it started; don't open another cookie
+    world.resource_mut::<RunModifiers>().preset = true;
+    world.resource_mut::<SavedRun>().status = Some(status);
+    world.write_message(RunLoadedEvent);
+    world.resource_mut::<NextState<AppState>>().set(AppState::Playing);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::GreedyClicker;
+    use crate::gift_cards::GiftCards;
+    use crate::savings::SavingsState;
+    use crate::sim::SimWorld;
+    use crate::thing_type::ThingTraits;
+
+    /// Save `played`, write the save out and read it back into a fresh run on `things`
+    fn round_trip(played: &mut SimWorld, things: &ThingRegistry) -> SimWorld {
+        let json = serde_json::to_string(&save_file(played.world_mut()).unwrap()).unwrap();
+        let save: SaveFile = serde_json::from_str(&json).unwrap();
+        let mut loaded = SimWorld::new(things, ThingType::CHEAP, 1);
+        load(loaded.world_mut(), &save).unwrap();
+        loaded
+    }
+
+    #[test]
+    fn made_up_thing_comes_back_from_a_save() {
+        let mut things = ThingRegistry::default();
+        let custom = things.set_custom(ThingDefinition::from_traits("Gizmo", ThingTraits::default()));
+        let mut played = SimWorld::new(&things, custom, 7).with_agent(Box::new(GreedyClicker));
+        assert!(played.run_days(20));
+
+        let loaded = round_trip(&mut played, &ThingRegistry::default());
+        assert_eq!(loaded.game_state().thing_type, Some(ThingType::CUSTOM));
+        assert_eq!(loaded.resource::<ThingRegistry>()[ThingType::CUSTOM], things[custom]);
+        assert_eq!(loaded.date(), played.date());
+        assert_eq!(loaded.game_state().money, played.game_state().money);
+    }
+
+    #[test]
+    fn modded_thing_comes_back_after_the_mods_change() {
+        let modded = |id: &str| ThingDefinition {
+            id: id.to_string(),
+            name: id.to_string(),
+            ..ThingRegistry::default()[ThingType::GOOD].clone()
+        };
+        let mut before = ThingRegistry::default();
+        before.insert(modded("widget")).unwrap();
+        let gadget = before.insert(modded("gadget")).unwrap();
+        let mut played = SimWorld::new(&before, gadget, 7).with_agent(Box::new(GreedyClicker));
+        assert!(played.run_days(20));
+
+        // The widget mod has been taken out, so the gadget moves up a place
+        let mut after = ThingRegistry::default();
+        let moved = after.insert(modded("gadget")).unwrap();
+        assert_ne!(moved, gadget);
+
+        let loaded = round_trip(&mut played, &after);
+        assert_eq!(loaded.game_state().thing_type, Some(moved));
+        assert_eq!(loaded.game_state().money, played.game_state().money);
+    }
+
+    fn go_to(sim: &mut SimWorld, state: AppState) {
+        sim.world_mut().resource_mut::<NextState<AppState>>().set(state);
+        sim.step();
+    }
+
+    #[test]
+    fn quitting_to_title_starts_the_next_thing_fresh() {
+        let mut played = SimWorld::new(&ThingRegistry::default(), ThingType::GOOD, 7);
+        assert!(played.run_days(20));
+        assert_ne!(played.date(), WorldState::default().date);
+
+        go_to(&mut played, AppState::Paused);
+        go_to(&mut played, AppState::ThingSelection);
+        played.world_mut().resource_mut::<GameState>().thing_type = Some(ThingType::CHEAP);
+        go_to(&mut played, AppState::Playing);
+
+        assert_eq!(played.game_state().thing_type, Some(ThingType::CHEAP));
+        assert_eq!(played.date(), WorldState::default().date);
+        assert_eq!(played.game_state().money, GameState::default().money);
+    }
+
+    #[test]
+    fn format_one_amounts_in_dollars_load_as_cents() {
+        let mut played = SimWorld::new(&ThingRegistry::default(), ThingType::GOOD, 7);
+        assert!(played.run_days(5));
+        let mut json = serde_json::to_value(save_file(played.world_mut()).unwrap()).unwrap();
+        json["version"] = Value::from(1);
+        json["resources"]["SavingsState"]["balance"] = Value::from(12.345);
+        json["resources"]["SavingsState"]["withdrawals"] = serde_json::json!([{ "amount": 2.5, "days_remaining": 1 }]);
+        json["resources"]["GiftCards"]["outstanding"] = Value::from(40.0);
+
+        SAVE_FORMAT.migrate(&mut json).unwrap();
+        let save: SaveFile = serde_json::from_value(json).unwrap();
+        let mut loaded = SimWorld::new(&ThingRegistry::default(), ThingType::CHEAP, 1);
+        load(loaded.world_mut(), &save).unwrap();
+
+        let savings = loaded.resource::<SavingsState>();
+        assert_eq!(savings.balance, Money::from_cents(1235));
+        assert_eq!(savings.withdrawals[0].amount, Money::from_cents(250));
+        assert_eq!(loaded.resource::<GiftCards>().outstanding, Money::from_cents(4000));
+    }
+}
@@ -0,0 +1,182 @@
+//! Savings account - park idle cash and let it compound
+//!
+//! Money in savings earns the central bank's rate (see `WorldState::interest_rate`),
+//! compounded daily. Deposits are instant, but getting money back out takes a
+//! few days, and money on its way out earns nothing. So cash in savings is
+//! cash that can't be spent on upgrades in a hurry.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::currency::Money;
+use crate::economy::{DayAdvancedEvent, WorldState};
+use crate::game_state::{AppState, GameState};
+
+/// Days between asking for money back and having it
+pub const WITHDRAWAL_DAYS: u32 = 3;
+/// Interest is quoted per year and paid per day
+const DAYS_PER_YEAR: f64 = 365.0;
+
+/// What the player can do with the account
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SavingsMove {
+    DepositQuarter,
+    DepositAll,
+    WithdrawQuarter,
+    WithdrawAll,
+}
+
+impl SavingsMove {
+    pub const ALL: [SavingsMove; 4] = [
+        SavingsMove::DepositQuarter,
+        SavingsMove::DepositAll,
+        SavingsMove::WithdrawQuarter,
+        SavingsMove::WithdrawAll,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SavingsMove::DepositQuarter => "Deposit 25%",
+            SavingsMove::DepositAll => "Deposit all",
+            SavingsMove::WithdrawQuarter => "Withdraw 25%",
+            SavingsMove::WithdrawAll => "Withdraw all",
+        }
+    }
+}
+
+/// Money on its way back out of savings
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct Withdrawal {
+    pub amount: Money,
+    pub days_remaining: u32,
+}
+
+/// The account
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct SavingsState {
+    /// Earning interest
+    pub balance: Money,
+    /// Not earning, not spendable yet
+    pub withdrawals: Vec<Withdrawal>,
+    /// Interest earned over the run
+    pub interest_earned: Money,
+    /// Interest short of a whole cent, paid along with the next day's
+    interest_carry: f64,
+}
+
+/// What happened in the account
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SavingsActivity {
+    Deposited,
+    WithdrawalRequested,
+    /// A withdrawal arrived as cash
+    Withdrawn,
+    /// A day's interest
+    Interest,
+}
+
+/// Message fired for every movement in the account
+#[derive(Event, Message, Clone)]
+pub struct SavingsEvent {
+    pub activity: SavingsActivity,
+    pub amount: f64,
+}
+
+/// Message requesting a deposit or withdrawal (sent by the UI)
+#[derive(Event, Message, Clone)]
+pub struct SavingsMoveEvent {
+    pub savings_move: SavingsMove,
+}
+
+pub struct SavingsPlugin;
+
+impl Plugin for SavingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SavingsState>()
+            .add_message::<SavingsEvent>()
+            .add_message::<SavingsMoveEvent>()
+            .add_systems(
+                Update,
+                (handle_savings_moves, accrue_interest)
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// Move money in, or start moving it out
+fn handle_savings_moves(
+    mut move_events: MessageReader<SavingsMoveEvent>,
+    mut game_state: ResMut<GameState>,
+    mut savings: ResMut<SavingsState>,
+    mut savings_events: MessageWriter<SavingsEvent>,
+) {
+    for event in move_events.read() {
+        let cash = game_state.money.at_least_zero();
+        let (activity, amount) = match event.savings_move {
+            SavingsMove::DepositQuarter => (SavingsActivity::Deposited, cash.share(0.25)),
+            SavingsMove::DepositAll => (SavingsActivity::Deposited, cash),
+            SavingsMove::WithdrawQuarter => (SavingsActivity::WithdrawalRequested, savings.balance.share(0.25)),
+            SavingsMove::WithdrawAll => (SavingsActivity::WithdrawalRequested, savings.balance),
+        };
+        if amount <= Money::ZERO {
+            continue;
+        }
+
+        if activity == SavingsActivity::Deposited {
+            game_state.money -= amount;
+            savings.balance += amount;
+        } else {
+            savings.balance -= amount;
+            savings.withdrawals.push(Withdrawal {
+                amount,
+                days_remaining: WITHDRAWAL_DAYS,
+            });
+        }
+        savings_events.write(SavingsEvent {
+            activity,
+            amount: amount.dollars(),
+        });
+    }
+}
+
+/// Each game day: pay out withdrawals that have cleared, then add a day's interest
+fn accrue_interest(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    world: Res<WorldState>,
+    mut game_state: ResMut<GameState>,
+    mut savings: ResMut<SavingsState>,
+    mut savings_events: MessageWriter<SavingsEvent>,
+) {
+    for _ in day_events.read() {
+        if savings.balance <= Money::ZERO && savings.withdrawals.is_empty() {
+            continue;
+        }
+
+        for withdrawal in savings.withdrawals.iter_mut() {
+            withdrawal.days_remaining = withdrawal.days_remaining.saturating_sub(1);
+        }
+        let cleared: Money = savings.withdrawals.iter().filter(|w| w.days_remaining == 0).map(|w| w.amount).sum();
+        savings.withdrawals.retain(|w| w.days_remaining > 0);
+        if cleared > Money::ZERO {
+            game_state.money += cleared;
+            savings_events.write(SavingsEvent {
+                activity: SavingsActivity::Withdrawn,
+                amount: cleared.dollars(),
+            });
+        }
+
+        // Paid in whole cents; the fraction of a cent left over goes towards tomorrow's
+        let rate = world.interest_rate as f64 / DAYS_PER_YEAR;
+        let (interest, leftover) = Money::whole_cents(savings.balance.dollars() * rate + savings.interest_carry);
+        savings.interest_carry = leftover;
+        if interest > Money::ZERO {
+            savings.balance += interest;
+            savings.interest_earned += interest;
+            savings_events.write(SavingsEvent {
+                activity: SavingsActivity::Interest,
+                amount: interest.dollars(),
+            });
+        }
+    }
+}
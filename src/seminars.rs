@@ -0,0 +1,214 @@
+//! Seminars - Terry teaches MBA courses for a fee, and comes back a little better at business
+//!
+//! A light progression track next to R&D. Each course costs money and takes
+//! Terry out of the office for a few days, and after teaching Terry needs a
+//! while to recover before the next one. A finished course permanently nudges
+//! one of the run's multipliers and gives Terry a new batch of jargon.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::accounting::ExpenseCategory;
+use crate::economy::DayAdvancedEvent;
+use crate::fortune::RunModifiers;
+use crate::game_state::{AppState, GameState};
+
+/// Days Terry needs between courses
+pub const COOLDOWN_DAYS: u32 = 7;
+
+/// Courses Terry can teach
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum Course {
+    Synergy101,
+    AdvancedBuzzwords,
+    StakeholderEmpathy,
+    CrisisCommunications,
+}
+
+impl Course {
+    pub const ALL: [Course; 4] = [
+        Course::Synergy101,
+        Course::AdvancedBuzzwords,
+        Course::StakeholderEmpathy,
+        Course::CrisisCommunications,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Course::Synergy101 => "Synergy 101",
+            Course::AdvancedBuzzwords => "Advanced Buzzwords",
+            Course::StakeholderEmpathy => "Stakeholder Empathy",
+            Course::CrisisCommunications => "Crisis Communications",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Course::Synergy101 => "Teach the room to align workstreams. The factory somehow runs 5% faster.",
+            Course::AdvancedBuzzwords => "Leverage core competencies at scale. Customers pay 5% more to hear it.",
+            Course::StakeholderEmpathy => "Listen actively, nod slowly. Reputation gains 10% faster.",
+            Course::CrisisCommunications => "Never say 'sorry', say 'learnings'. Reputation losses 10% smaller.",
+        }
+    }
+
+    /// Seminar fee (venue hire, handouts, the good coffee)
+    pub fn cost(&self) -> f64 {
+        match self {
+            Course::Synergy101 => 400.0,
+            Course::AdvancedBuzzwords => 900.0,
+            Course::StakeholderEmpathy => 1_500.0,
+            Course::CrisisCommunications => 2_500.0,
+        }
+    }
+
+    /// Game days Terry spends teaching
+    pub fn duration_days(&self) -> u32 {
+        match self {
+            Course::Synergy101 => 3,
+            Course::AdvancedBuzzwords => 5,
+            Course::StakeholderEmpathy => 6,
+            Course::CrisisCommunications => 8,
+        }
+    }
+
+    /// Apply the course's permanent bonus
+    pub fn apply(&self, modifiers: &mut RunModifiers) {
+        match self {
+            Course::Synergy101 => modifiers.production *= 1.05,
+            Course::AdvancedBuzzwords => modifiers.revenue *= 1.05,
+            Course::StakeholderEmpathy => modifiers.reputation_gain *= 1.1,
+            Course::CrisisCommunications => modifiers.reputation_loss *= 0.9,
+        }
+    }
+
+    /// Take the bonus back off while Terry isn't around to use it
+    pub fn suspend(&self, modifiers: &mut RunModifiers) {
+        match self {
+            Course::Synergy101 => modifiers.production /= 1.05,
+            Course::AdvancedBuzzwords => modifiers.revenue /= 1.05,
+            Course::StakeholderEmpathy => modifiers.reputation_gain /= 1.1,
+            Course::CrisisCommunications => modifiers.reputation_loss /= 0.9,
+        }
+    }
+
+    /// Dialogue trigger for the jargon Terry picks up from the course
+    pub fn jargon_trigger(&self) -> &'static str {
+        match self {
+            Course::Synergy101 => "jargon_synergy",
+            Course::AdvancedBuzzwords => "jargon_buzzwords",
+            Course::StakeholderEmpathy => "jargon_empathy",
+            Course::CrisisCommunications => "jargon_crisis",
+        }
+    }
+}
+
+/// A course being taught
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct ActiveSeminar {
+    pub course: Course,
+    pub days_remaining: u32,
+}
+
+/// Courses taught, and what Terry's up to now
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct SeminarState {
+    pub taught: Vec<Course>,
+    pub active: Option<ActiveSeminar>,
+    /// Days until Terry can teach again
+    pub cooldown_days: u32,
+}
+
+impl SeminarState {
+    pub fn is_taught(&self, course: Course) -> bool {
+        self.taught.contains(&course)
+    }
+
+    pub fn can_start(&self, course: Course, game_state: &GameState) -> bool {
+        self.active.is_none()
+            && self.cooldown_days == 0
+            && !self.is_taught(course)
+            && game_state.money >= course.cost()
+    }
+}
+
+/// Message requesting a seminar (sent by the UI)
+#[derive(Event, Message, Clone)]
+pub struct StartSeminarEvent {
+    pub course: Course,
+}
+
+/// Message fired when a seminar starts or finishes
+#[derive(Event, Message, Clone)]
+pub struct SeminarEvent {
+    pub course: Course,
+    pub completed: bool,
+}
+
+pub struct SeminarPlugin;
+
+impl Plugin for SeminarPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SeminarState>()
+            .add_message::<StartSeminarEvent>()
+            .add_message::<SeminarEvent>()
+            .add_systems(
+                Update,
+                (start_seminar, progress_seminar)
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// Pay the fee and send Terry off to teach
+fn start_seminar(
+    mut start_events: MessageReader<StartSeminarEvent>,
+    mut game_state: ResMut<GameState>,
+    mut seminars: ResMut<SeminarState>,
+    mut seminar_events: MessageWriter<SeminarEvent>,
+) {
+    for event in start_events.read() {
+        if !seminars.can_start(event.course, &game_state) {
+            continue;
+        }
+        game_state.spend(ExpenseCategory::Seminars, event.course.cost());
+        seminars.active = Some(ActiveSeminar {
+            course: event.course,
+            days_remaining: event.course.duration_days(),
+        });
+        seminar_events.write(SeminarEvent {
+            course: event.course,
+            completed: false,
+        });
+    }
+}
+
+/// Count the seminar and the cooldown down each game day
+fn progress_seminar(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    mut seminars: ResMut<SeminarState>,
+    mut modifiers: ResMut<RunModifiers>,
+    mut seminar_events: MessageWriter<SeminarEvent>,
+) {
+    for _ in day_events.read() {
+        if seminars.cooldown_days > 0 {
+            seminars.cooldown_days -= 1;
+        }
+
+        let Some(active) = seminars.active.as_mut() else {
+            continue;
+        };
+
+        active.days_remaining = active.days_remaining.saturating_sub(1);
+        if active.days_remaining > 0 {
+            continue;
+        }
+
+        let course = active.course;
+        seminars.active = None;
+        seminars.cooldown_days = COOLDOWN_DAYS;
+        seminars.taught.push(course);
+        course.apply(&mut modifiers);
+        seminar_events.write(SeminarEvent { course, completed: true });
+    }
+}
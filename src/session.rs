@@ -0,0 +1,113 @@
+//! Real-time session tracking - how long you've been playing, and a nudge to take a break
+//!
+//! Lifetime playtime is saved per profile alongside the settings. With
+//! play reminders on, every hour of real time brings a gentle reminder with
+//! a few stats from the session.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::economy::DayAdvancedEvent;
+use crate::game_state::{AppState, MoneyChangedEvent, ThingProducedEvent};
+use crate::settings::Settings;
+use crate::snapshot::ReflectOutsideRun;
+
+/// Real seconds between play reminders
+const REMINDER_INTERVAL_SECS: u64 = 60 * 60;
+/// How often playtime is checkpointed (and so saved)
+const CHECKPOINT_SECS: u64 = 60;
+
+/// Real time played, this session and over every session before it
+#[derive(Resource, Default)]
+pub struct Playtime {
+    /// Seconds the active profile played before it was loaded (from its config file)
+    pub previous_secs: u64,
+    /// Seconds played this session, as of the last checkpoint
+    pub session_secs: u64,
+    /// Session seconds when the active profile was loaded
+    profile_start_secs: u64,
+}
+
+impl Playtime {
+    /// Seconds the active profile has played, ever
+    pub fn lifetime_secs(&self) -> u64 {
+        self.previous_secs + self.session_secs - self.profile_start_secs
+    }
+
+    /// Start counting for a newly loaded profile
+    pub fn start_profile(&mut self, previous_secs: u64) {
+        self.previous_secs = previous_secs;
+        self.profile_start_secs = self.session_secs;
+    }
+}
+
+/// Format seconds as e.g. "3h 25m"
+pub fn format_duration(secs: u64) -> String {
+    format!("{}h {:02}m", secs / 3_600, secs % 3_600 / 60)
+}
+
+/// What got done this session (kept out of saves, since it isn't part of the run)
+#[derive(Resource, Default, Debug, Clone, Copy, Reflect)]
+#[reflect(Resource, OutsideRun)]
+pub struct SessionStats {
+    pub things_made: u64,
+    pub money_earned: f64,
+    pub days_played: u32,
+}
+
+/// Message fired each hour of play when reminders are on
+#[derive(Event, Message, Clone)]
+pub struct PlayReminderEvent {
+    pub hours: u64,
+    pub stats: SessionStats,
+}
+
+pub struct SessionPlugin;
+
+impl Plugin for SessionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Playtime>()
+            .init_resource::<SessionStats>()
+            .add_message::<PlayReminderEvent>()
+            .add_systems(Update, track_playtime)
+            .add_systems(Update, track_session_stats.run_if(in_state(AppState::Playing)));
+    }
+}
+
+/// Checkpoint playtime once a minute and send the hourly reminder
+fn track_playtime(
+    time: Res<Time<Real>>,
+    settings: Res<Settings>,
+    stats: Res<SessionStats>,
+    mut playtime: ResMut<Playtime>,
+    mut reminder_events: MessageWriter<PlayReminderEvent>,
+) {
+    let elapsed = time.elapsed().as_secs();
+    if elapsed < playtime.session_secs + CHECKPOINT_SECS {
+        return;
+    }
+
+    let hours_before = playtime.session_secs / REMINDER_INTERVAL_SECS;
+    playtime.session_secs = elapsed;
+    let hours = elapsed / REMINDER_INTERVAL_SECS;
+    if hours > hours_before && settings.play_reminders {
+        reminder_events.write(PlayReminderEvent { hours, stats: *stats });
+    }
+}
+
+/// Tally what the session has produced
+fn track_session_stats(
+    mut thing_events: MessageReader<ThingProducedEvent>,
+    mut money_events: MessageReader<MoneyChangedEvent>,
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    mut stats: ResMut<SessionStats>,
+) {
+    for event in thing_events.read() {
+        stats.things_made += event.amount;
+    }
+    for event in money_events.read() {
+        if event.delta > 0.0 {
+            stats.money_earned += event.delta;
+        }
+    }
+    stats.days_played += day_events.read().count() as u32;
+}
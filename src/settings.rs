@@ -0,0 +1,456 @@
+//! Player settings - presentation and layout options
+//!
+//! Settings, key bindings, lifetime playtime, Terry's wardrobe, the tutorial
+//! moments already seen, the HUD layout and Terry merch are saved to the active profile's `config.json` whenever
+//! they change, and loaded again on startup or when the player switches profile.
+//! Files saved by older versions are upgraded on load (see `migration`).
+
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::bench::benchmarking;
+use crate::cosmetics::Wardrobe;
+use crate::currency::CurrencyFormat;
+use crate::difficulty::Difficulty;
+use crate::hud::HudLayout;
+use crate::tutorial::FirstTimes;
+use crate::input::InputMap;
+use crate::merch::MerchShop;
+use crate::migration::{self, MigrationError, CONFIG_VERSION};
+use crate::profile::{Profiles, SwitchProfileEvent};
+use crate::session::Playtime;
+
+/// Where Terry's dialogue is displayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DialogueLayout {
+    /// Dialogue box in the left panel under Terry's portrait
+    #[default]
+    Panel,
+    /// Speech bubble floating next to Terry's portrait
+    Bubble,
+}
+
+impl DialogueLayout {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DialogueLayout::Panel => "Panel",
+            DialogueLayout::Bubble => "Speech Bubble",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            DialogueLayout::Panel => DialogueLayout::Bubble,
+            DialogueLayout::Bubble => DialogueLayout::Panel,
+        }
+    }
+}
+
+/// How reputation is shown on the main screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReputationStyle {
+    /// Five stars
+    #[default]
+    Stars,
+    /// The number, 0.0 to 5.0
+    Number,
+    /// A bar filled with a pattern (reads without telling colors apart)
+    Bar,
+}
+
+impl ReputationStyle {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReputationStyle::Stars => "Stars",
+            ReputationStyle::Number => "Number",
+            ReputationStyle::Bar => "Bar",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            ReputationStyle::Stars => ReputationStyle::Number,
+            ReputationStyle::Number => ReputationStyle::Bar,
+            ReputationStyle::Bar => ReputationStyle::Stars,
+        }
+    }
+}
+
+/// Where exported run statistics are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportLocation {
+    /// `exports/` in the player's profile folder
+    #[default]
+    GameFolder,
+    /// The player's home directory
+    Home,
+    /// The player's desktop
+    Desktop,
+}
+
+impl ExportLocation {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExportLocation::GameFolder => "Game folder",
+            ExportLocation::Home => "Home",
+            ExportLocation::Desktop => "Desktop",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            ExportLocation::GameFolder => ExportLocation::Home,
+            ExportLocation::Home => ExportLocation::Desktop,
+            ExportLocation::Desktop => ExportLocation::GameFolder,
+        }
+    }
+
+    /// The directory to write into (falls back to the profile folder without a home directory)
+    pub fn dir(&self, profile_dir: &Path) -> PathBuf {
+        let home = std::env::var_os("HOME")
+            .or_else(|| std::env::var_os("USERPROFILE"))
+            .map(PathBuf::from);
+
+        match (self, home) {
+            (ExportLocation::Home, Some(home)) => home,
+            (ExportLocation::Desktop, Some(home)) => home.join("Desktop"),
+            _ => profile_dir.join("exports"),
+        }
+    }
+}
+
+/// All player-adjustable settings
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Where Terry's lines are shown
+    pub dialogue_layout: DialogueLayout,
+    /// Manual clicks cost Hustle
+    pub hustle_meter: bool,
+    /// Where stats exports go
+    pub export_location: ExportLocation,
+    /// How money amounts read
+    pub currency_format: CurrencyFormat,
+    /// How reputation is shown
+    pub reputation_style: ReputationStyle,
+    /// Draw a fortune cookie modifier when a run starts
+    pub fortune_cookie: bool,
+    /// Difficulty picked for the last run (picked on the selection screen)
+    pub difficulty: Difficulty,
+    /// Pop the monthly report up (otherwise it only goes to the inbox)
+    pub monthly_report_popup: bool,
+    /// Hourly take-a-break reminders
+    pub play_reminders: bool,
+    /// Explainer cards the first time something happens
+    pub tutorial_cards: bool,
+    /// Clicking and crises wear the player down (optional mode)
+    pub wellbeing: bool,
+    /// Keep the camera still (no zoom punches, shake or drift)
+    pub reduced_motion: bool,
+    /// Cap the frame rate and skip animations to save battery
+    pub battery_saver: bool,
+    /// Keep the business running, slowly, while the window is minimized (`background` builds only)
+    pub background_mode: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            dialogue_layout: DialogueLayout::default(),
+            hustle_meter: true,
+            export_location: ExportLocation::default(),
+            currency_format: CurrencyFormat::default(),
+            reputation_style: ReputationStyle::default(),
+            fortune_cookie: true,
+            difficulty: Difficulty::default(),
+            monthly_report_popup: true,
+            play_reminders: true,
+            tutorial_cards: true,
+            wellbeing: false,
+            reduced_motion: false,
+            battery_saver: false,
+            background_mode: false,
+        }
+    }
+}
+
+/// A single adjustable option, as listed on the settings screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingOption {
+    DialogueLayout,
+    HustleMeter,
+    ExportLocation,
+    CurrencyFormat,
+    ReputationStyle,
+    FortuneCookie,
+    MonthlyReport,
+    PlayReminders,
+    TutorialCards,
+    Wellbeing,
+    ReducedMotion,
+    BatterySaver,
+    BackgroundMode,
+}
+
+impl SettingOption {
+    /// Every option, in display order
+    pub const ALL: [SettingOption; 13] = [
+        SettingOption::DialogueLayout,
+        SettingOption::HustleMeter,
+        SettingOption::ExportLocation,
+        SettingOption::CurrencyFormat,
+        SettingOption::ReputationStyle,
+        SettingOption::FortuneCookie,
+        SettingOption::MonthlyReport,
+        SettingOption::PlayReminders,
+        SettingOption::TutorialCards,
+        SettingOption::Wellbeing,
+        SettingOption::ReducedMotion,
+        SettingOption::BatterySaver,
+        SettingOption::BackgroundMode,
+    ];
+
+    /// Whether this build has the option at all
+    pub fn available(&self) -> bool {
+        *self != SettingOption::BackgroundMode || cfg!(all(feature = "background", not(target_arch = "wasm32")))
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SettingOption::DialogueLayout => "Terry's dialogue",
+            SettingOption::HustleMeter => "Hustle meter",
+            SettingOption::ExportLocation => "Export stats to",
+            SettingOption::CurrencyFormat => "Money format",
+            SettingOption::ReputationStyle => "Reputation shown as",
+            SettingOption::FortuneCookie => "Fortune cookie (new runs)",
+            SettingOption::MonthlyReport => "Monthly report",
+            SettingOption::PlayReminders => "Break reminders",
+            SettingOption::TutorialCards => "Tutorial tips",
+            SettingOption::Wellbeing => "Work-life balance",
+            SettingOption::ReducedMotion => "Reduced motion",
+            SettingOption::BatterySaver => "Battery saver",
+            SettingOption::BackgroundMode => "Run when minimized",
+        }
+    }
+}
+
+impl Settings {
+    /// Display value of an option
+    pub fn value_label(&self, option: SettingOption) -> String {
+        match option {
+            SettingOption::DialogueLayout => self.dialogue_layout.name().to_string(),
+            SettingOption::HustleMeter => on_off(self.hustle_meter).to_string(),
+            SettingOption::ExportLocation => self.export_location.name().to_string(),
+            SettingOption::CurrencyFormat => self.currency_format.name().to_string(),
+            SettingOption::ReputationStyle => self.reputation_style.name().to_string(),
+            SettingOption::FortuneCookie => on_off(self.fortune_cookie).to_string(),
+            SettingOption::MonthlyReport => {
+                if self.monthly_report_popup { "Pop up" } else { "Inbox only" }.to_string()
+            }
+            SettingOption::PlayReminders => on_off(self.play_reminders).to_string(),
+            SettingOption::TutorialCards => on_off(self.tutorial_cards).to_string(),
+            SettingOption::Wellbeing => on_off(self.wellbeing).to_string(),
+            SettingOption::ReducedMotion => on_off(self.reduced_motion).to_string(),
+            SettingOption::BatterySaver => on_off(self.battery_saver).to_string(),
+            SettingOption::BackgroundMode => on_off(self.background_mode).to_string(),
+        }
+    }
+
+    /// Step an option to its next value
+    pub fn cycle(&mut self, option: SettingOption) {
+        match option {
+            SettingOption::DialogueLayout => self.dialogue_layout = self.dialogue_layout.next(),
+            SettingOption::HustleMeter => self.hustle_meter = !self.hustle_meter,
+            SettingOption::ExportLocation => self.export_location = self.export_location.next(),
+            SettingOption::CurrencyFormat => self.currency_format = self.currency_format.next(),
+            SettingOption::ReputationStyle => self.reputation_style = self.reputation_style.next(),
+            SettingOption::FortuneCookie => self.fortune_cookie = !self.fortune_cookie,
+            SettingOption::MonthlyReport => self.monthly_report_popup = !self.monthly_report_popup,
+            SettingOption::PlayReminders => self.play_reminders = !self.play_reminders,
+            SettingOption::TutorialCards => self.tutorial_cards = !self.tutorial_cards,
+            SettingOption::Wellbeing => self.wellbeing = !self.wellbeing,
+            SettingOption::ReducedMotion => self.reduced_motion = !self.reduced_motion,
+            SettingOption::BatterySaver => self.battery_saver = !self.battery_saver,
+            SettingOption::BackgroundMode => self.background_mode = !self.background_mode,
+        }
+    }
+}
+
+fn on_off(value: bool) -> &'static str {
+    if value { "On" } else { "Off" }
+}
+
+/// What `config.json` holds
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+struct ConfigFile {
+    /// Format version (see `migration`)
+    version: u32,
+    settings: Settings,
+    /// Action id -> key name
+    key_bindings: BTreeMap<String, String>,
+    /// Real seconds played over every session
+    lifetime_playtime_secs: u64,
+    /// Accessories unlocked and equipped
+    wardrobe: Wardrobe,
+    /// Tutorial moments already seen
+    first_times: FirstTimes,
+    /// Widgets pinned to the main screen
+    hud_layout: HudLayout,
+    /// Achievement points and Terry merch
+    merch: MerchShop,
+}
+
+impl ConfigFile {
+    /// Read a config file's contents, upgrading them if they're from an older version
+    fn read(path: &Path, contents: &str) -> Result<Self, MigrationError> {
+        let config = migration::CONFIG.load_file(path, contents)?;
+        serde_json::from_value(config).map_err(|err| MigrationError::Unreadable(err.to_string()))
+    }
+}
+
+/// Why the active profile's config couldn't be loaded (shown until the player dismisses it)
+#[derive(Resource, Default)]
+pub struct ConfigLoadError {
+    pub message: Option<String>,
+}
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Settings>()
+            .init_resource::<ConfigLoadError>()
+            .init_resource::<HudLayout>()
+            .add_systems(Startup, load_config)
+            // The benchmark changes settings for its own run only
+            .add_systems(Update, (switch_profile, save_config).chain().run_if(not(benchmarking)))
+            .add_systems(Update, apply_currency_format.run_if(resource_changed::<Settings>));
+    }
+}
+
+/// Everything that goes in a profile's config file
+#[derive(SystemParam)]
+pub struct SavedConfig<'w> {
+    settings: ResMut<'w, Settings>,
+    input_map: ResMut<'w, InputMap>,
+    playtime: ResMut<'w, Playtime>,
+    wardrobe: ResMut<'w, Wardrobe>,
+    first_times: ResMut<'w, FirstTimes>,
+    hud_layout: ResMut<'w, HudLayout>,
+    merch: ResMut<'w, MerchShop>,
+    load_error: ResMut<'w, ConfigLoadError>,
+}
+
+impl SavedConfig<'_> {
+    fn changed(&self) -> bool {
+        self.settings.is_changed()
+            || self.input_map.is_changed()
+            || self.playtime.is_changed()
+            || self.wardrobe.is_changed()
+            || self.first_times.is_changed()
+            || self.hud_layout.is_changed()
+            || self.merch.is_changed()
+    }
+
+    /// Load a config file; a missing file means defaults, and so does a broken one (after keeping a copy)
+    fn load(&mut self, path: &Path) {
+        self.load_error.message = None;
+        let config = match fs::read_to_string(path) {
+            Ok(contents) => ConfigFile::read(path, &contents).unwrap_or_else(|err| {
+                warn!("Ignoring {}: {}", path.display(), err);
+                self.load_error.message = Some(load_error_message(path, &err));
+                ConfigFile::default()
+            }),
+            Err(_) => ConfigFile::default(),
+        };
+
+        *self.settings = config.settings;
+        *self.input_map = InputMap::default();
+        self.input_map.apply_saved(&config.key_bindings);
+        self.playtime.start_profile(config.lifetime_playtime_secs);
+        *self.wardrobe = config.wardrobe;
+        *self.first_times = config.first_times;
+        *self.hud_layout = config.hud_layout;
+        *self.merch = config.merch;
+    }
+
+    fn save(&self, path: &Path) {
+        let config = ConfigFile {
+            version: CONFIG_VERSION,
+            settings: self.settings.clone(),
+            key_bindings: self.input_map.to_saved(),
+            lifetime_playtime_secs: self.playtime.lifetime_secs(),
+            wardrobe: self.wardrobe.clone(),
+            first_times: self.first_times.clone(),
+            hud_layout: self.hud_layout.clone(),
+            merch: self.merch.clone(),
+        };
+        let result = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| serde_json::to_string_pretty(&config).map_err(std::io::Error::other))
+            .and_then(|json| fs::write(path, json));
+        if let Err(err) = result {
+            warn!("Couldn't save {}: {}", path.display(), err);
+        }
+    }
+}
+
+/// Keep a copy of a config file that couldn't be loaded (it's overwritten at the next save) and say where it went
+fn load_error_message(path: &Path, err: &MigrationError) -> String {
+    let kept = path.with_file_name("config.broken.json");
+    let copy = match fs::copy(path, &kept) {
+        Ok(_) => format!("The old file was kept as {}.", kept.display()),
+        Err(copy_err) => format!("A copy of the old file couldn't be kept either ({}).", copy_err),
+    };
+    format!(
+        "This profile's settings couldn't be loaded because {}. {} The profile starts over from the default settings.",
+        err, copy
+    )
+}
+
+/// Load the active profile's settings, key bindings, playtime, wardrobe, first times, HUD layout and merch
+pub fn load_config(profiles: Res<Profiles>, mut config: SavedConfig) {
+    config.load(&profiles.config_path());
+}
+
+/// Save the outgoing profile and load the incoming one
+fn switch_profile(
+    mut switch_events: MessageReader<SwitchProfileEvent>,
+    mut profiles: ResMut<Profiles>,
+    mut config: SavedConfig,
+) {
+    let Some(event) = switch_events.read().last() else {
+        return;
+    };
+    if event.name == profiles.active || !profiles.names.contains(&event.name) {
+        return;
+    }
+
+    config.save(&profiles.config_path());
+    profiles.active = event.name.clone();
+    profiles.save();
+    config.load(&profiles.config_path());
+}
+
+/// Write the config file whenever settings, bindings, the wardrobe, first times, the HUD layout or merch change, or playtime is checkpointed
+fn save_config(profiles: Res<Profiles>, config: SavedConfig) {
+    // Skip the first frame, which only sees what was just loaded
+    if config.settings.is_added() || !config.changed() {
+        return;
+    }
+
+    config.save(&profiles.config_path());
+}
+
+/// Show money in the format the settings ask for
+fn apply_currency_format(settings: Res<Settings>) {
+    CurrencyFormat::set_current(settings.currency_format);
+}
@@ -0,0 +1,185 @@
+//! Challenge codes - share a run's starting conditions as a short code
+//!
+//! A code captures the Thing, the world seed (which drives the day-to-day
+//! weather and market swings), the fortune cookie and the difficulty, but
+//! nothing about progress. Codes are seven characters of Crockford base32, the
+//! last of which is a checksum so typos get caught instead of starting the
+//! wrong run. Only the built-in Things fit in a code; runs of modded Things
+//! don't get one. Six-character codes from before difficulty was included are
+//! turned away rather than guessed at.
+
+use crate::difficulty::Difficulty;
+use crate::economy::WorldState;
+use crate::fortune::{Fortune, RunModifiers};
+use crate::game_state::GameState;
+use crate::thing_type::ThingType;
+
+/// Characters used in codes (no I, L, O or U to avoid mix-ups)
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+/// Characters in a code, not counting the dash
+const CODE_LEN: usize = 7;
+const SEED_BITS: u32 = 20;
+/// Largest world seed a code can hold
+pub const MAX_SEED: u32 = (1 << SEED_BITS) - 1;
+
+/// The starting conditions of a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShareCode {
+    pub thing_type: ThingType,
+    pub seed: u32,
+    pub fortune: Option<Fortune>,
+    pub difficulty: Difficulty,
+}
+
+/// Why a typed code was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShareCodeError {
+    Empty,
+    WrongLength(usize),
+    BadCharacter(char),
+    Checksum,
+    Unsupported,
+}
+
+impl ShareCodeError {
+    /// What to tell the player
+    pub fn message(&self) -> String {
+        match self {
+            ShareCodeError::Empty => "Type a code first.".to_string(),
+            ShareCodeError::WrongLength(len) => {
+                format!("Codes are {} characters, like 7QK-2MXA (that was {}).", CODE_LEN, len)
+            }
+            ShareCodeError::BadCharacter(c) => format!("'{}' never appears in a code. Check for typos.", c),
+            ShareCodeError::Checksum => "That code doesn't add up. Probably a typo somewhere.".to_string(),
+            ShareCodeError::Unsupported => "That code is from a different version of the game.".to_string(),
+        }
+    }
+}
+
+impl ShareCode {
+    /// The code for the run being played
+    pub fn for_run(
+        game_state: &GameState,
+        world: &WorldState,
+        modifiers: &RunModifiers,
+        difficulty: Difficulty,
+    ) -> Option<Self> {
+        Some(Self {
+            thing_type: game_state.thing_type.filter(ThingType::is_built_in)?,
+            seed: world.seed,
+            fortune: modifiers.fortune,
+            difficulty,
+        })
+    }
+
+    /// Encode as e.g. "7QK-2MXA"
+    pub fn encode(&self) -> String {
+        let thing = self.thing_type.index() as u32;
+        let fortune = self
+            .fortune
+            .and_then(|f| Fortune::ALL.iter().position(|a| *a == f))
+            .map_or(0, |i| i as u32 + 1);
+        let difficulty = match self.difficulty {
+            Difficulty::Easy => 0,
+            Difficulty::Normal => 1,
+            Difficulty::Brutal => 2,
+        };
+        let payload = (difficulty << (SEED_BITS + 5))
+            | (thing << (SEED_BITS + 3))
+            | (fortune << SEED_BITS)
+            | (self.seed & MAX_SEED);
+
+        let mut digits = [0u32; CODE_LEN];
+        for (i, digit) in digits.iter_mut().take(CODE_LEN - 1).enumerate() {
+            *digit = (payload >> (5 * (CODE_LEN - 2 - i))) & 31;
+        }
+        digits[CODE_LEN - 1] = checksum(&digits[..CODE_LEN - 1]);
+
+        let chars: String = digits.iter().map(|d| ALPHABET[*d as usize] as char).collect();
+        format!("{}-{}", &chars[..3], &chars[3..])
+    }
+
+    /// Read a typed code (case, dashes and spaces don't matter)
+    pub fn parse(input: &str) -> Result<Self, ShareCodeError> {
+        let chars: Vec<char> = input
+            .chars()
+            .filter(|c| *c != '-' && !c.is_whitespace())
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+        if chars.is_empty() {
+            return Err(ShareCodeError::Empty);
+        }
+        if chars.len() != CODE_LEN {
+            return Err(ShareCodeError::WrongLength(chars.len()));
+        }
+
+        let mut digits = [0u32; CODE_LEN];
+        for (digit, c) in digits.iter_mut().zip(&chars) {
+            *digit = decode_char(*c).ok_or(ShareCodeError::BadCharacter(*c))?;
+        }
+        if checksum(&digits[..CODE_LEN - 1]) != digits[CODE_LEN - 1] {
+            return Err(ShareCodeError::Checksum);
+        }
+
+        let payload = digits[..CODE_LEN - 1].iter().fold(0u32, |acc, d| (acc << 5) | d);
+        let difficulty = match payload >> (SEED_BITS + 5) {
+            0 => Difficulty::Easy,
+            1 => Difficulty::Normal,
+            2 => Difficulty::Brutal,
+            _ => return Err(ShareCodeError::Unsupported),
+        };
+        let thing = ((payload >> (SEED_BITS + 3)) & 0b11) as usize;
+        let fortune = ((payload >> SEED_BITS) & 0b111) as usize;
+        let thing_type = *ThingType::BUILT_IN.get(thing).ok_or(ShareCodeError::Unsupported)?;
+        let fortune = match fortune {
+            0 => None,
+            n => Some(*Fortune::ALL.get(n - 1).ok_or(ShareCodeError::Unsupported)?),
+        };
+
+        Ok(Self {
+            thing_type,
+            seed: payload & MAX_SEED,
+            fortune,
+            difficulty,
+        })
+    }
+}
+
+/// Look up a code character, forgiving the usual look-alikes
+fn decode_char(c: char) -> Option<u32> {
+    let c = match c {
+        'O' => '0',
+        'I' | 'L' => '1',
+        c => c,
+    };
+    ALPHABET.iter().position(|a| *a as char == c).map(|i| i as u32)
+}
+
+/// Weighted sum with odd weights, so any single wrong character changes it
+fn checksum(digits: &[u32]) -> u32 {
+    digits
+        .iter()
+        .enumerate()
+        .map(|(i, d)| d * (2 * i as u32 + 1))
+        .sum::<u32>()
+        % 32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_carry_the_difficulty_and_old_codes_are_turned_away() {
+        for difficulty in [Difficulty::Easy, Difficulty::Normal, Difficulty::Brutal] {
+            let code = ShareCode {
+                thing_type: ThingType::BAD,
+                seed: MAX_SEED,
+                fortune: Fortune::ALL.last().copied(),
+                difficulty,
+            };
+            assert_eq!(ShareCode::parse(&code.encode()), Ok(code));
+        }
+        assert_eq!(ShareCode::parse("7QK-2MX"), Err(ShareCodeError::WrongLength(6)));
+    }
+}
@@ -0,0 +1,245 @@
+//! The simulation on its own - a run that can be created, stepped and read outside the game
+//!
+//! [`SimulationPlugins`] are the gameplay plugins: the economy, customers,
+//! upgrades and everything else that decides how a run goes, with no window,
+//! UI or Terry. The game adds them to its own app; a [`SimWorld`] gives them
+//! a private headless app instead, so any number of runs can exist side by
+//! side. `--balance` plays bots through thousands of them and the parallel
+//! universe keeps one next to the player's run.
+//!
+//! A run's state is still the plugins' Bevy resources, not a struct of its
+//! own: a `SimWorld` is a whole `App`, read through its resources and
+//! serialized with the same reflection snapshots as the inspector. The
+//! player's run isn't a `SimWorld` either, just the main app running the same
+//! plugins, so there's nothing to sync between the two.
+
+use bevy::app::PluginGroupBuilder;
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use serde_json::Value;
+use std::time::Duration;
+use crate::accounting::AccountingPlugin;
+use crate::agent::{AgentDriver, AgentPlugin, PlayerAgent};
+use crate::attract::AttractMode;
+use crate::automation::AutomationPlugin;
+use crate::ai_boom::AiBoomPlugin;
+use crate::bundles::BundlePlugin;
+use crate::business::{BusinessPlugin, UpgradeState};
+use crate::clicker::{ClickEvent, ClickerPlugin};
+use crate::cosmetics::{CosmeticUnlockedEvent, Wardrobe};
+use crate::counterfeits::CounterfeitPlugin;
+use crate::crypto::CryptoPlugin;
+use crate::difficulty::{DifficultyConfig, DifficultyPlugin};
+use crate::eco::EcoPlugin;
+use crate::economy::{DayAdvancedEvent, EconomyPlugin, GameDate, WorldState};
+use crate::fads::FadPlugin;
+use crate::fortune::{FortunePlugin, RunModifiers};
+use crate::game_clock::GameClockPlugin;
+use crate::game_state::{AppState, GameState, GameStatePlugin};
+use crate::gift_cards::GiftCardPlugin;
+use crate::labor::LaborPlugin;
+use crate::limited_editions::LimitedEditionPlugin;
+use crate::market::MarketPlugin;
+use crate::marketing::MarketingPlugin;
+use crate::org::OrgPlugin;
+use crate::pandemic::PandemicPlugin;
+use crate::pivot::PivotPlugin;
+use crate::price_war::PriceWarPlugin;
+use crate::recovery::RecoveryPlugin;
+use crate::reputation::ReputationPlugin;
+use crate::research::ResearchPlugin;
+use crate::returns::ReturnsPlugin;
+use crate::rewards::RewardPlugin;
+use crate::save::RunLoadedEvent;
+use crate::savings::SavingsPlugin;
+use crate::settings::Settings;
+use crate::seminars::SeminarPlugin;
+use crate::snapshot;
+use crate::social::SocialPlugin;
+use crate::stats::StatsPlugin;
+use crate::suppliers::SupplierPlugin;
+use crate::thing_type::{ThingRegistry, ThingType};
+use crate::utilities::UtilitiesPlugin;
+use crate::venues::VenuePlugin;
+use crate::wellbeing::WellbeingPlugin;
+use crate::word_of_mouth::WordOfMouthPlugin;
+
+/// Simulated time per step (a game day is one second)
+const STEP: Duration = Duration::from_millis(100);
+/// Steps allowed per game day before a run is declared stuck
+pub const MAX_STEPS_PER_DAY: u32 = 100;
+
+/// Every plugin that decides how a run plays out
+pub struct SimulationPlugins;
+
+impl PluginGroup for SimulationPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add(GameStatePlugin)
+            .add(GameClockPlugin)
+            .add(FortunePlugin)
+            .add(DifficultyPlugin)
+            .add(EconomyPlugin)
+            .add(MarketingPlugin)
+            .add(ReputationPlugin)
+            .add(RecoveryPlugin)
+            .add(PivotPlugin)
+            .add(ResearchPlugin)
+            .add(AutomationPlugin)
+            .add(StatsPlugin)
+            .add(AccountingPlugin)
+            .add(OrgPlugin)
+            .add(LaborPlugin)
+            .add(BusinessPlugin)
+            .add(ClickerPlugin)
+            .add(UtilitiesPlugin)
+            .add(EcoPlugin)
+            .add(SocialPlugin)
+            .add(AgentPlugin)
+            .add(MarketPlugin)
+            .add(WordOfMouthPlugin)
+            .add(ReturnsPlugin)
+            .add(GiftCardPlugin)
+            .add(VenuePlugin)
+            .add(BundlePlugin)
+            .add(SupplierPlugin)
+            .add(SeminarPlugin)
+            .add(RewardPlugin)
+            .add(SavingsPlugin)
+            .add(CryptoPlugin)
+            .add(PandemicPlugin)
+            .add(AiBoomPlugin)
+            .add(WellbeingPlugin)
+            .add(PriceWarPlugin)
+            .add(CounterfeitPlugin)
+            .add(LimitedEditionPlugin)
+            .add(FadPlugin)
+    }
+}
+
+/// Days counted so far in a headless run
+#[derive(Resource, Default)]
+struct DaysPlayed(u32);
+
+fn count_days(mut day_events: MessageReader<DayAdvancedEvent>, mut days: ResMut<DaysPlayed>) {
+    days.0 += day_events.read().count() as u32;
+}
+
+/// A run of the game in its own headless app, stepped by hand
+pub struct SimWorld {
+    app: App,
+}
+
+impl SimWorld {
+    /// A fresh run selling `thing_type` (one of `things`) on world `seed`, with nobody playing it
+    pub fn new(things: &ThingRegistry, thing_type: ThingType, seed: u32) -> Self {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(STEP))
+            .insert_state(AppState::Playing)
+            .insert_resource(things.clone())
+            // Resources the UI side of the game would otherwise provide
+            .init_resource::<Settings>()
+            .init_resource::<UpgradeState>()
+            .init_resource::<DaysPlayed>()
+            .init_resource::<AttractMode>()
+            .init_resource::<Wardrobe>()
+            .add_message::<ClickEvent>()
+            .add_message::<CosmeticUnlockedEvent>()
+            .add_message::<RunLoadedEvent>()
+            .add_plugins(SimulationPlugins)
+            .add_systems(Update, count_days);
+
+        app.world_mut().resource_mut::<GameState>().thing_type = Some(thing_type);
+        app.world_mut().resource_mut::<WorldState>().seed = seed;
+        Self { app }
+    }
+
+    /// Hand the run to a bot
+    pub fn with_agent(mut self, agent: Box<dyn PlayerAgent>) -> Self {
+        self.app.insert_resource(AgentDriver::new(agent));
+        self
+    }
+
+    /// Play under `modifiers` rather than opening a fortune cookie
+    pub fn with_modifiers(mut self, modifiers: RunModifiers) -> Self {
+        self.app.insert_resource(RunModifiers {
+            preset: true,
+            ..modifiers
+        });
+        self
+    }
+
+    /// Play at `difficulty` rather than Normal
+    pub fn with_difficulty(mut self, difficulty: DifficultyConfig) -> Self {
+        self.app.world_mut().resource_mut::<GameState>().money = difficulty.starting_money;
+        self.app.insert_resource(difficulty);
+        self
+    }
+
+    /// Advance one step
+    pub fn step(&mut self) {
+        self.app.update();
+    }
+
+    /// Step until `date` comes round, at most `max_steps` times; true once it's there
+    pub fn run_until(&mut self, date: GameDate, max_steps: u32) -> bool {
+        for _ in 0..max_steps {
+            if self.date() >= date {
+                return true;
+            }
+            self.step();
+        }
+        self.date() >= date
+    }
+
+    /// Step until `days` days have gone by since the start; false if the run got stuck
+    pub fn run_days(&mut self, days: u32) -> bool {
+        for _ in 0..days.saturating_mul(MAX_STEPS_PER_DAY) {
+            if self.days_played() >= days {
+                return true;
+            }
+            self.step();
+        }
+        self.days_played() >= days
+    }
+
+    pub fn days_played(&self) -> u32 {
+        self.app.world().resource::<DaysPlayed>().0
+    }
+
+    pub fn date(&self) -> GameDate {
+        self.world_state().date
+    }
+
+    pub fn game_state(&self) -> &GameState {
+        self.resource::<GameState>()
+    }
+
+    pub fn world_state(&self) -> &WorldState {
+        self.resource::<WorldState>()
+    }
+
+    /// Any resource in the run
+    pub fn resource<R: Resource>(&self) -> &R {
+        self.app.world().resource::<R>()
+    }
+
+    /// Every gameplay resource, by name (the same format as the inspector's snapshots)
+    pub fn to_json(&self) -> Result<Value, String> {
+        snapshot::to_json(self.app.world())
+    }
+
+    /// Load a snapshot back into the run (see `snapshot::apply_json`)
+    #[cfg(test)]
+    pub fn apply_json(&mut self, snapshot: &Value) -> Result<(), String> {
+        snapshot::apply_json(self.app.world_mut(), snapshot)
+    }
+
+    /// The run's world, for tests that need more than its resources
+    #[cfg(test)]
+    pub fn world_mut(&mut self) -> &mut World {
+        self.app.world_mut()
+    }
+}
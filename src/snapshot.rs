@@ -0,0 +1,264 @@
+//! Reflection snapshots - every gameplay resource, read without knowing its type
+//!
+//! Gameplay resources derive `Reflect` with `#[reflect(Resource, Default)]`,
+//! which Bevy registers on its own. Anything registered that way from this
+//! crate shows up here, so new resources need no extra code to be inspected,
+//! saved or reset: derive `Reflect` on the resource (and whatever it holds) and
+//! it's picked up. Snapshots load back the same way, which is how saved runs
+//! are continued, and [`reset`] puts every one back to its `Default` when a run
+//! ends.
+//!
+//! A few resources belong to the session or the profile rather than the run
+//! (`SessionStats`, say). Those add `OutsideRun` to their reflect attribute,
+//! `#[reflect(Resource, OutsideRun)]`, and stay out of snapshots; the
+//! inspector still lists them.
+//!
+//! Thing types are written by their definition's id, not their place in the
+//! `ThingRegistry`, so a snapshot still means the same Thing after mods are
+//! added or removed.
+
+use bevy::prelude::*;
+use bevy::reflect::serde::{
+    ReflectDeserializerProcessor, ReflectSerializerProcessor, TypedReflectDeserializer, TypedReflectSerializer,
+};
+use bevy::reflect::{FromType, PartialReflect, TypeRegistration, TypeRegistry};
+use serde::de::{DeserializeSeed, Error as _};
+use serde::{Deserialize, Deserializer, Serializer};
+use serde_json::{Map, Value};
+use std::any::TypeId;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use crate::economy::WorldState;
+use crate::thing_type::{ThingRegistry, ThingType};
+
+/// Type paths of this crate's types start with this
+const CRATE_PREFIX: &str = concat!(env!("CARGO_CRATE_NAME"), "::");
+
+/// Type data marking a resource that outlives the run, so snapshots leave it out
+#[derive(Clone)]
+pub struct ReflectOutsideRun;
+
+impl<T> FromType<T> for ReflectOutsideRun {
+    fn from_type() -> Self {
+        ReflectOutsideRun
+    }
+}
+
+/// Reads and writes Thing types against the registry; everything else is left to reflection
+struct ThingCodec<'a> {
+    things: &'a ThingRegistry,
+}
+
+impl ReflectSerializerProcessor for ThingCodec<'_> {
+    fn try_serialize<S>(&self, value: &dyn PartialReflect, _: &TypeRegistry, serializer: S) -> Result<Result<S::Ok, S>, S::Error>
+    where
+        S: Serializer,
+    {
+        let Some(thing_type) = value.try_downcast_ref::<ThingType>() else {
+            return Ok(Err(serializer));
+        };
+        Ok(Ok(serializer.serialize_str(&self.things[*thing_type].id)?))
+    }
+}
+
+impl ReflectDeserializerProcessor for ThingCodec<'_> {
+    fn try_deserialize<'de, D>(
+        &mut self,
+        registration: &TypeRegistration,
+        _: &TypeRegistry,
+        deserializer: D,
+    ) -> Result<Result<Box<dyn PartialReflect>, D>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if registration.type_id() != TypeId::of::<ThingType>() {
+            return Ok(Err(deserializer));
+        }
+        let id = String::deserialize(deserializer)?;
+        let thing_type = self.things.find(&id).ok_or_else(|| D::Error::custom(format!("no Thing type '{}'", id)))?;
+        Ok(Ok(Box::new(thing_type)))
+    }
+}
+
+/// This crate's reflected resources, sorted by name
+fn gameplay_resources(registry: &TypeRegistry) -> Vec<&TypeRegistration> {
+    let mut resources: Vec<_> = registry
+        .iter()
+        .filter(|registration| registration.data::<ReflectResource>().is_some())
+        .filter(|registration| registration.type_info().type_path().starts_with(CRATE_PREFIX))
+        .collect();
+    resources.sort_by_key(|registration| short_name(registration));
+    resources
+}
+
+/// The gameplay resources that make up a run (everything but `OutsideRun` ones)
+fn run_resources(registry: &TypeRegistry) -> Vec<&TypeRegistration> {
+    gameplay_resources(registry)
+        .into_iter()
+        .filter(|registration| registration.data::<ReflectOutsideRun>().is_none())
+        .collect()
+}
+
+fn short_name(registration: &TypeRegistration) -> &'static str {
+    registration.type_info().type_path_table().short_path()
+}
+
+/// Every gameplay resource's name, for the inspector's list
+#[cfg(debug_assertions)]
+pub fn resource_names(registry: &TypeRegistry) -> Vec<String> {
+    gameplay_resources(registry)
+        .into_iter()
+        .map(|registration| short_name(registration).to_string())
+        .collect()
+}
+
+/// One resource's current value, pretty-printed (None if it isn't in the world)
+#[cfg(debug_assertions)]
+pub fn describe(world: &World, name: &str) -> Option<String> {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let registration = gameplay_resources(&registry)
+        .into_iter()
+        .find(|registration| short_name(registration) == name)?;
+    let value = registration.data::<ReflectResource>()?.reflect(world).ok()?;
+    Some(format!("{:#?}", value.as_partial_reflect()))
+}
+
+/// Every run resource in the world, by name
+pub fn to_json(world: &World) -> Result<Value, String> {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let codec = ThingCodec {
+        things: world.resource::<ThingRegistry>(),
+    };
+    let mut resources = Map::new();
+    for registration in run_resources(&registry) {
+        let Some(reflect_resource) = registration.data::<ReflectResource>() else {
+            continue;
+        };
+        let Ok(value) = reflect_resource.reflect(world) else {
+            continue;
+        };
+        let serializer = TypedReflectSerializer::with_processor(value.as_partial_reflect(), &registry, &codec);
+        let value = serde_json::to_value(serializer).map_err(|err| format!("{}: {}", short_name(registration), err))?;
+        resources.insert(short_name(registration).to_string(), value);
+    }
+    Ok(Value::Object(resources))
+}
+
+/// Load a snapshot's resources back into the world. Resources the snapshot
+/// doesn't have keep their current value; any that can't be read are skipped
+/// and named in the error, after everything else has been loaded.
+pub fn apply_json(world: &mut World, snapshot: &Value) -> Result<(), String> {
+    let Value::Object(resources) = snapshot else {
+        return Err("not a snapshot".to_string());
+    };
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let things = world.resource::<ThingRegistry>().clone();
+    let mut codec = ThingCodec { things: &things };
+
+    let mut unreadable = Vec::new();
+    for registration in run_resources(&registry) {
+        let name = short_name(registration);
+        let (Some(reflect_resource), Some(value)) = (registration.data::<ReflectResource>(), resources.get(name))
+        else {
+            continue;
+        };
+        let loaded = TypedReflectDeserializer::with_processor(registration, &registry, &mut codec)
+            .deserialize(value.clone())
+            .map_err(|err| err.to_string())
+            .and_then(|loaded| {
+                let mut resource = reflect_resource.reflect_mut(&mut *world).map_err(|err| err.to_string())?;
+                resource.try_apply(loaded.as_ref()).map_err(|err| err.to_string())
+            });
+        if let Err(err) = loaded {
+            unreadable.push(format!("{}: {}", name, err));
+        }
+    }
+
+    if unreadable.is_empty() { Ok(()) } else { Err(unreadable.join("; ")) }
+}
+
+/// Put every run resource back to its `Default`. Any without `Default` in
+/// their reflect attribute keep their value and are named in the error.
+pub fn reset(world: &mut World) -> Result<(), String> {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+
+    let mut stuck = Vec::new();
+    for registration in run_resources(&registry) {
+        let (Some(reflect_resource), Some(reflect_default)) =
+            (registration.data::<ReflectResource>(), registration.data::<ReflectDefault>())
+        else {
+            stuck.push(short_name(registration));
+            continue;
+        };
+        reflect_resource.insert(world, reflect_default.default().as_partial_reflect(), &registry);
+    }
+
+    if stuck.is_empty() { Ok(()) } else { Err(format!("no Default to reset to: {}", stuck.join(", "))) }
+}
+
+/// Write a snapshot of the run into `dir`
+#[cfg(debug_assertions)]
+pub fn save(world: &World, dir: &Path) -> io::Result<PathBuf> {
+    let date = world.get_resource::<WorldState>().map_or("empty".to_string(), |w| w.date.iso());
+    let path = dir.join(format!("thing_snapshot_{}.json", date));
+
+    let snapshot = to_json(world).map_err(io::Error::other)?;
+    let json = serde_json::to_string_pretty(&snapshot).map_err(io::Error::other)?;
+    fs::create_dir_all(dir)?;
+    fs::write(&path, json)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use super::{reset, to_json};
+    use crate::agent::GreedyClicker;
+    use crate::game_state::GameState;
+    use crate::session::SessionStats;
+    use crate::sim::SimWorld;
+    use crate::thing_type::{ThingRegistry, ThingType};
+
+    #[test]
+    fn snapshot_loads_back_into_a_fresh_run() {
+        let things = ThingRegistry::default();
+        let mut played = SimWorld::new(&things, ThingType::GOOD, 7).with_agent(Box::new(GreedyClicker));
+        assert!(played.run_days(40));
+        let snapshot = played.to_json().unwrap();
+
+        let mut fresh = SimWorld::new(&things, ThingType::CHEAP, 1);
+        fresh.apply_json(&snapshot).unwrap();
+        assert_eq!(fresh.to_json().unwrap(), snapshot);
+    }
+
+    #[test]
+    fn every_snapshot_resource_is_reset_when_the_run_ends() {
+        let things = ThingRegistry::default();
+        let mut played = SimWorld::new(&things, ThingType::BAD, 7).with_agent(Box::new(GreedyClicker));
+        assert!(played.run_days(40));
+        reset(played.world_mut()).unwrap();
+
+        let mut fresh = SimWorld::new(&things, ThingType::GOOD, 1);
+        reset(fresh.world_mut()).unwrap();
+        let fresh = fresh.to_json().unwrap();
+        let played = played.to_json().unwrap();
+        for (name, value) in fresh.as_object().unwrap() {
+            assert_eq!(played.get(name), Some(value), "{} wasn't reset", name);
+        }
+    }
+
+    #[test]
+    fn snapshot_leaves_out_session_resources() {
+        let mut app = App::new();
+        app.init_resource::<ThingRegistry>().init_resource::<GameState>().insert_resource(SessionStats {
+            things_made: 12,
+            ..default()
+        });
+        let snapshot = to_json(app.world()).unwrap();
+        assert!(snapshot.get("GameState").is_some());
+        assert!(snapshot.get("SessionStats").is_none());
+    }
+}
@@ -0,0 +1,211 @@
+//! Chirper - the in-world social feed
+//!
+//! Each game day the public posts about your Thing. How much they post follows
+//! media buzz and virality; how they feel follows reputation, and a scandal
+//! turns the feed sour. Media buzz itself drifts toward how loudly you're
+//! marketing. With astroturfing on, some of the praise is yours - the player
+//! sees those posts labeled, the public doesn't.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use std::collections::VecDeque;
+use crate::economy::{DayAdvancedEvent, GameDate, WorldState};
+use crate::game_state::{AppState, GameState};
+use crate::marketing::MarketingState;
+use crate::reputation::SCANDAL_REPUTATION;
+use crate::thing_type::ThingRegistry;
+
+/// Posts kept in the feed
+pub const MAX_POSTS: usize = 30;
+/// Most posts in one day, however viral things get
+const MAX_DAILY_POSTS: f32 = 6.0;
+/// Posts a day with no buzz at all (friends and family)
+const BASE_DAILY_POSTS: f32 = 0.3;
+/// Extra daily posts at full media buzz
+const BUZZ_POSTS: f32 = 4.0;
+/// Extra daily posts per point of viral coefficient
+const VIRAL_POSTS: f32 = 40.0;
+/// How far media buzz moves toward its target each day
+const BUZZ_DRIFT: f32 = 0.2;
+
+/// How a post feels about your Thing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum Sentiment {
+    Positive,
+    Neutral,
+    Negative,
+}
+
+/// One post on the feed
+#[derive(Debug, Clone, Reflect)]
+pub struct Post {
+    pub date: GameDate,
+    pub author: String,
+    pub text: String,
+    pub sentiment: Sentiment,
+    /// Written by your own sock puppets
+    pub astroturfed: bool,
+}
+
+/// The feed, newest first
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct SocialFeed {
+    pub posts: VecDeque<Post>,
+    /// Fractional posts carried over to tomorrow
+    backlog: f32,
+}
+
+impl SocialFeed {
+    pub fn push(&mut self, post: Post) {
+        self.posts.push_front(post);
+        self.posts.truncate(MAX_POSTS);
+    }
+}
+
+const HANDLES: [&str; 12] = [
+    "@thingfan88", "@mom_of_3_thingz", "@TechBroChad", "@sad_intern", "@grandpa_online",
+    "@xX_ThingSlayer_Xx", "@local_news_guy", "@skeptical_steve", "@wellness_wendy",
+    "@crypto_carl", "@HotTakeHannah", "@actual_reviewer",
+];
+
+const ASTROTURF_HANDLES: [&str; 4] = ["@totally_real_person", "@NotAnEmployee42", "@just_a_regular_guy", "@grassroots_gary"];
+
+const POSITIVE_POSTS: [&str; 6] = [
+    "Just got a {thing} Thing and honestly? Life changing.",
+    "Can't stop thinking about my {thing} Thing. Is this normal?",
+    "Shoutout to the {thing} Thing people. You get it.",
+    "Bought a {thing} Thing for my dad. He cried. Good tears.",
+    "{thing} Things are the best thing since Things.",
+    "My therapist says I talk about {thing} Things too much. My therapist is wrong.",
+];
+
+const NEUTRAL_POSTS: [&str; 5] = [
+    "Anyone else seen these {thing} Things everywhere lately?",
+    "Got a {thing} Thing. It's a Thing. Does what it says.",
+    "Is a {thing} Thing worth it? Asking for a friend.",
+    "Saw an ad for {thing} Things. Didn't hate it.",
+    "My coworker has a {thing} Thing. Still not sure what it's for.",
+];
+
+const NEGATIVE_POSTS: [&str; 5] = [
+    "My {thing} Thing broke after a day. Never again.",
+    "Whoever runs the {thing} Thing company should be ashamed.",
+    "Returned my {thing} Thing. The box was the best part.",
+    "Why is the {thing} Thing company CEO a hot dog? Why is nobody asking this?",
+    "Thread: everything wrong with {thing} Things (1/47)",
+];
+
+const SCANDAL_POSTS: [&str; 4] = [
+    "BOYCOTT {thing} THINGS. Retweet if you agree.",
+    "The {thing} Thing scandal is the worst thing I've seen this year and I've seen a lot.",
+    "Just threw my {thing} Thing in the trash. Where it belongs.",
+    "Has anyone checked on the hot dog? Not out of sympathy. Out of curiosity.",
+];
+
+const ASTROTURF_POSTS: [&str; 4] = [
+    "As a normal customer who is not paid, I love {thing} Things!!",
+    "Wow I just found these {thing} Things organically and they are GREAT. #notanad",
+    "10/10 would {thing} Thing again. Five stars. Definitely a real review.",
+    "I am a regular person and {thing} Things changed my regular life.",
+];
+
+fn pick<'a>(options: &[&'a str]) -> &'a str {
+    options[rand::random_range(0..options.len())]
+}
+
+pub struct SocialPlugin;
+
+impl Plugin for SocialPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SocialFeed>()
+            .add_systems(
+                Update,
+                (update_media_buzz, generate_posts)
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// Media buzz drifts toward how loudly the business is marketing (and how
+/// badly it's misbehaving)
+fn update_media_buzz(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    marketing: Res<MarketingState>,
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    mut world: ResMut<WorldState>,
+) {
+    for _ in day_events.read() {
+        let mut target = ((marketing.calculate_demand_boost() - 1.0) * 0.5).clamp(0.0, 1.0);
+        if is_scandal(&game_state, &things) {
+            target = target.max(0.8);
+        }
+        world.media_buzz += (target - world.media_buzz) * BUZZ_DRIFT;
+    }
+}
+
+/// Post today's chatter
+fn generate_posts(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    world: Res<WorldState>,
+    marketing: Res<MarketingState>,
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    mut feed: ResMut<SocialFeed>,
+) {
+    for event in day_events.read() {
+        let Some(thing_type) = game_state.thing_type else {
+            continue;
+        };
+        let name = &things[thing_type].name;
+
+        let volume = BASE_DAILY_POSTS + world.media_buzz * BUZZ_POSTS + world.viral_coefficient * VIRAL_POSTS;
+        feed.backlog = (feed.backlog + volume).min(MAX_DAILY_POSTS);
+        while feed.backlog >= 1.0 {
+            feed.backlog -= 1.0;
+            let post = public_post(event.date, name, &game_state, &things);
+            feed.push(post);
+        }
+
+        // The sock puppets post whatever the buzz
+        let astroturfing = &marketing.astroturfing;
+        if astroturfing.active && rand::random::<f32>() < astroturfing.contribution() {
+            feed.push(Post {
+                date: event.date,
+                author: pick(&ASTROTURF_HANDLES).to_string(),
+                text: pick(&ASTROTURF_POSTS).replace("{thing}", name),
+                sentiment: Sentiment::Positive,
+                astroturfed: true,
+            });
+        }
+    }
+}
+
+fn is_scandal(game_state: &GameState, things: &ThingRegistry) -> bool {
+    things.is_shady(game_state.thing_type) && game_state.reputation < SCANDAL_REPUTATION
+}
+
+/// A genuine post, feeling about the Thing the way its reputation suggests
+fn public_post(date: GameDate, thing_name: &str, game_state: &GameState, things: &ThingRegistry) -> Post {
+    let roll = rand::random::<f32>();
+    let positive_share = game_state.reputation / 5.0;
+    let (sentiment, templates): (Sentiment, &[&str]) = if is_scandal(game_state, things) {
+        (Sentiment::Negative, &SCANDAL_POSTS)
+    } else if roll < positive_share * 0.8 {
+        (Sentiment::Positive, &POSITIVE_POSTS)
+    } else if roll < positive_share * 0.8 + 0.25 {
+        (Sentiment::Neutral, &NEUTRAL_POSTS)
+    } else {
+        (Sentiment::Negative, &NEGATIVE_POSTS)
+    };
+
+    Post {
+        date,
+        author: pick(&HANDLES).to_string(),
+        text: pick(templates).replace("{thing}", thing_name),
+        sentiment,
+        astroturfed: false,
+    }
+}
@@ -0,0 +1,162 @@
+//! Spending habits - Terry notices how the player buys upgrades
+//!
+//! Every upgrade bought goes into a rolling history, stamped with the day it
+//! was bought. Three patterns get a remark: a shopping spree (lots of upgrades
+//! in a few days), a run of marketing while the factory can't keep up, and
+//! sitting on a pile of cash for months without buying anything. Each pattern
+//! is only pointed out once in a while.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use std::collections::VecDeque;
+use crate::business::{UpgradePurchasedEvent, UpgradeState, UpgradeType};
+use crate::economy::DayAdvancedEvent;
+use crate::game_state::{AppState, GameState};
+
+/// Purchases remembered
+const HISTORY_LEN: usize = 20;
+/// Upgrades bought within `SPREE_DAYS` that make a shopping spree
+const SPREE_PURCHASES: usize = 5;
+const SPREE_DAYS: u32 = 3;
+/// Marketing upgrades in a row, with production behind, before Terry says something
+const MARKETING_STREAK: usize = 5;
+/// Days without buying anything that count as hoarding
+const HOARD_DAYS: u32 = 90;
+/// Hoarding means sitting on this many times the cheapest upgrade
+const HOARD_MULTIPLE: f64 = 10.0;
+/// Days before the same pattern is pointed out again
+const NOTICE_COOLDOWN_DAYS: u32 = 60;
+
+/// A spending habit worth a comment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum SpendingPattern {
+    /// Lots of upgrades in a few days
+    Spree,
+    /// Nothing but marketing while production lags behind
+    MarketingOnly,
+    /// Months of cash piling up without buying anything
+    Hoarding,
+}
+
+impl SpendingPattern {
+    pub const ALL: [SpendingPattern; 3] =
+        [SpendingPattern::Spree, SpendingPattern::MarketingOnly, SpendingPattern::Hoarding];
+
+    pub fn trigger(&self) -> &'static str {
+        match self {
+            SpendingPattern::Spree => "spending_spree",
+            SpendingPattern::MarketingOnly => "spending_marketing_only",
+            SpendingPattern::Hoarding => "spending_hoarding",
+        }
+    }
+}
+
+/// Message fired when a spending pattern is spotted
+#[derive(Event, Message, Clone)]
+pub struct SpendingPatternEvent {
+    pub pattern: SpendingPattern,
+}
+
+/// Recent purchases and when each pattern was last pointed out
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct SpendingHistory {
+    /// Days played this run
+    day: u32,
+    /// Recent purchases and the day each was made, oldest first
+    purchases: VecDeque<(u32, UpgradeType)>,
+    /// Day of the last purchase
+    last_purchase_day: u32,
+    /// Day each pattern was last pointed out, in `SpendingPattern::ALL` order
+    noticed: [Option<u32>; 3],
+}
+
+impl SpendingHistory {
+    fn record(&mut self, upgrade: UpgradeType) {
+        if self.purchases.len() >= HISTORY_LEN {
+            self.purchases.pop_front();
+        }
+        self.purchases.push_back((self.day, upgrade));
+        self.last_purchase_day = self.day;
+    }
+
+    fn is_spree(&self) -> bool {
+        let recent = self.purchases.iter().filter(|(day, _)| day + SPREE_DAYS > self.day).count();
+        recent >= SPREE_PURCHASES
+    }
+
+    fn is_marketing_only(&self, upgrades: &UpgradeState) -> bool {
+        let streak = self.purchases.iter().rev().take_while(|(_, upgrade)| upgrade.is_marketing()).count();
+        let marketing = upgrades.social_media + upgrades.billboards + upgrades.influencer_deals;
+        let production = upgrades.workers + upgrades.automation;
+        streak >= MARKETING_STREAK && production < marketing
+    }
+
+    fn is_hoarding(&self, game_state: &GameState, upgrades: &UpgradeState) -> bool {
+        let cheapest = UpgradeType::ALL.iter().map(|upgrade| upgrades.cost(*upgrade)).fold(f64::INFINITY, f64::min);
+        self.day - self.last_purchase_day >= HOARD_DAYS && game_state.money >= cheapest * HOARD_MULTIPLE
+    }
+
+    /// Whether a pattern can be pointed out again, noting it if so
+    fn notice(&mut self, pattern: SpendingPattern) -> bool {
+        let index = SpendingPattern::ALL.iter().position(|p| *p == pattern).unwrap_or(0);
+        if self.noticed[index].is_some_and(|day| day + NOTICE_COOLDOWN_DAYS > self.day) {
+            return false;
+        }
+        self.noticed[index] = Some(self.day);
+        true
+    }
+}
+
+pub struct SpendingPlugin;
+
+impl Plugin for SpendingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpendingHistory>()
+            .add_message::<SpendingPatternEvent>()
+            .add_systems(
+                Update,
+                (record_purchases, spot_hoarding).chain().run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// Remember each purchase and look for sprees and marketing streaks
+fn record_purchases(
+    mut purchase_events: MessageReader<UpgradePurchasedEvent>,
+    upgrades: Res<UpgradeState>,
+    mut history: ResMut<SpendingHistory>,
+    mut pattern_events: MessageWriter<SpendingPatternEvent>,
+) {
+    for event in purchase_events.read() {
+        history.record(event.upgrade);
+
+        if history.is_spree() && history.notice(SpendingPattern::Spree) {
+            pattern_events.write(SpendingPatternEvent {
+                pattern: SpendingPattern::Spree,
+            });
+        } else if history.is_marketing_only(&upgrades) && history.notice(SpendingPattern::MarketingOnly) {
+            pattern_events.write(SpendingPatternEvent {
+                pattern: SpendingPattern::MarketingOnly,
+            });
+        }
+    }
+}
+
+/// Count the days, and check for a cash pile nobody's spending
+fn spot_hoarding(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    game_state: Res<GameState>,
+    upgrades: Res<UpgradeState>,
+    mut history: ResMut<SpendingHistory>,
+    mut pattern_events: MessageWriter<SpendingPatternEvent>,
+) {
+    for _ in day_events.read() {
+        history.day += 1;
+        if history.is_hoarding(&game_state, &upgrades) && history.notice(SpendingPattern::Hoarding) {
+            pattern_events.write(SpendingPatternEvent {
+                pattern: SpendingPattern::Hoarding,
+            });
+        }
+    }
+}
@@ -0,0 +1,168 @@
+//! Run statistics - a daily snapshot of the business and the world, exportable
+//! for spreadsheet analysis
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use crate::economy::{DayAdvancedEvent, WorldState};
+use crate::game_state::{AppState, GameState};
+use crate::thing_type::ThingRegistry;
+
+/// One game day's numbers
+#[derive(Debug, Clone, Serialize, Reflect)]
+pub struct DailySnapshot {
+    pub date: String,
+    pub thing_type: String,
+    pub money: f64,
+    pub things_produced: u64,
+    pub customers_served: u64,
+    pub reputation: f32,
+    pub things_per_second: f64,
+    pub marketing_level: u32,
+    // World factors
+    pub temperature: f32,
+    pub consumer_confidence: f32,
+    pub unemployment_rate: f32,
+    pub inflation_rate: f32,
+    pub market_sentiment: f32,
+    pub trend_factor: f32,
+    pub competitor_pressure: f32,
+    pub market_saturation: f32,
+    pub demand_modifier: f32,
+    pub is_weekend: bool,
+    pub holiday: String,
+}
+
+impl DailySnapshot {
+    const CSV_HEADER: &'static str = "date,thing_type,money,things_produced,customers_served,reputation,\
+        things_per_second,marketing_level,temperature,consumer_confidence,unemployment_rate,inflation_rate,\
+        market_sentiment,trend_factor,competitor_pressure,market_saturation,demand_modifier,is_weekend,holiday";
+
+    pub fn capture(game_state: &GameState, things: &ThingRegistry, world: &WorldState, day: &DayAdvancedEvent) -> Self {
+        Self {
+            date: day.date.iso(),
+            thing_type: game_state.thing_type.map_or("None", |t| things[t].name.as_str()).to_string(),
+            money: game_state.money.dollars(),
+            things_produced: game_state.things_produced,
+            customers_served: game_state.customers_served,
+            reputation: game_state.reputation,
+            things_per_second: game_state.things_per_second,
+            marketing_level: game_state.marketing_level,
+            temperature: world.temperature,
+            consumer_confidence: world.consumer_confidence,
+            unemployment_rate: world.unemployment_rate,
+            inflation_rate: world.inflation_rate,
+            market_sentiment: world.market_sentiment,
+            trend_factor: world.trend_factor,
+            competitor_pressure: world.competitor_pressure,
+            market_saturation: world.market_saturation,
+            demand_modifier: day.demand_modifier,
+            is_weekend: day.is_weekend,
+            holiday: day.holiday.map(|h| format!("{:?}", h)).unwrap_or_default(),
+        }
+    }
+
+    fn csv_row(&self) -> String {
+        format!(
+            "{},{},{:.2},{},{},{:.3},{:.2},{},{:.1},{:.3},{:.3},{:.3},{:.3},{:.3},{:.3},{:.3},{:.3},{},{}",
+            self.date,
+            self.thing_type,
+            self.money,
+            self.things_produced,
+            self.customers_served,
+            self.reputation,
+            self.things_per_second,
+            self.marketing_level,
+            self.temperature,
+            self.consumer_confidence,
+            self.unemployment_rate,
+            self.inflation_rate,
+            self.market_sentiment,
+            self.trend_factor,
+            self.competitor_pressure,
+            self.market_saturation,
+            self.demand_modifier,
+            self.is_weekend,
+            self.holiday,
+        )
+    }
+}
+
+/// Export file formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// Every daily snapshot of the current run
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct RunStats {
+    pub snapshots: Vec<DailySnapshot>,
+}
+
+impl RunStats {
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(DailySnapshot::CSV_HEADER);
+        csv.push('\n');
+        for snapshot in &self.snapshots {
+            csv.push_str(&snapshot.csv_row());
+            csv.push('\n');
+        }
+        csv
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(&self.snapshots)
+    }
+
+    /// Write the history into `dir`, returning the file written
+    pub fn export(&self, format: ExportFormat, dir: &Path) -> io::Result<PathBuf> {
+        let last_date = self.snapshots.last().map(|s| s.date.as_str()).unwrap_or("empty");
+        let path = dir.join(format!("thing_run_{}.{}", last_date, format.extension()));
+
+        let contents = match format {
+            ExportFormat::Csv => self.to_csv(),
+            ExportFormat::Json => self.to_json().map_err(io::Error::other)?,
+        };
+
+        fs::create_dir_all(dir)?;
+        fs::write(&path, contents)?;
+        Ok(path)
+    }
+}
+
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunStats>()
+            .add_systems(Update, record_daily_snapshot.run_if(in_state(AppState::Playing)));
+    }
+}
+
+/// Take a snapshot at the turn of every game day
+pub fn record_daily_snapshot(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    world: Res<WorldState>,
+    mut stats: ResMut<RunStats>,
+) {
+    for event in day_events.read() {
+        stats.snapshots.push(DailySnapshot::capture(&game_state, &things, &world, event));
+    }
+}
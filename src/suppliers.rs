@@ -0,0 +1,315 @@
+//! Suppliers - the materials that go into every Thing, and the people who sell them
+//!
+//! Each Thing made uses materials, billed daily at the current terms. On the
+//! first of every month the supplier puts new terms on the table - a price and
+//! a monthly volume commitment - shaped by how well you get on. Accept them,
+//! haggle (good relationships make haggling work), or walk away and buy on the
+//! pricier spot market. Missing a commitment costs a penalty. Burn enough
+//! bridges and the supplier stops delivering for a while.
+//!
+//! The `supplier_exclusivity` backroom deal makes the supplier friendlier and
+//! cheaper, and makes walking out on them hurt twice as much.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::accounting::ExpenseCategory;
+use crate::economy::DayAdvancedEvent;
+use crate::game_state::{AppState, GameState, ThingProducedEvent};
+use crate::marketing::MarketingState;
+use crate::thing_type::ThingRegistry;
+
+/// Materials per Thing, as a share of the Thing's base price, at list price
+const MATERIAL_SHARE: f64 = 0.08;
+/// Price multiplier on the spot market (no deal)
+const SPOT_PRICE: f32 = 1.3;
+/// Price swing between a hated and a beloved customer
+const RELATIONSHIP_PRICE_SWING: f32 = 0.2;
+/// Extra discount for committing to next month's volume
+const COMMITMENT_DISCOUNT: f32 = 0.05;
+/// Commitment offered, relative to last month's Things
+const COMMITMENT_GROWTH: f64 = 1.1;
+/// Discount won by a successful haggle
+const HAGGLE_DISCOUNT: f32 = 0.08;
+/// Penalty per Thing short of the commitment, as a share of its material cost
+const SHORTFALL_PENALTY: f64 = 0.5;
+/// Discount from the supplier exclusivity deal
+const EXCLUSIVITY_DISCOUNT: f32 = 0.1;
+/// Relationship changes
+const ACCEPT_GOODWILL: f32 = 3.0;
+const HAGGLE_FAIL_COST: f32 = 10.0;
+const WALK_AWAY_COST: f32 = 20.0;
+const COMMITMENT_MET_GOODWILL: f32 = 5.0;
+const SHORTFALL_COST: f32 = 8.0;
+const EXCLUSIVITY_GOODWILL: f32 = 3.0;
+/// Relationship below which deliveries stop
+pub const DISRUPTION_THRESHOLD: f32 = 15.0;
+/// Days without deliveries once bridges are burned
+const DISRUPTION_DAYS: u32 = 5;
+/// Where the relationship lands once deliveries resume
+const AFTER_DISRUPTION_RELATIONSHIP: f32 = 30.0;
+
+/// Terms on the table
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct SupplyTerms {
+    /// Multiplier on the list price of materials
+    pub price: f32,
+    /// Things the player promises to make this month
+    pub commitment: u64,
+}
+
+/// How the player answers an offer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NegotiationMove {
+    Accept,
+    Haggle,
+    WalkAway,
+}
+
+impl NegotiationMove {
+    pub const ALL: [NegotiationMove; 3] = [NegotiationMove::Accept, NegotiationMove::Haggle, NegotiationMove::WalkAway];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            NegotiationMove::Accept => "Accept",
+            NegotiationMove::Haggle => "Haggle",
+            NegotiationMove::WalkAway => "Walk Away",
+        }
+    }
+}
+
+/// The supplier relationship
+#[derive(Resource, Reflect)]
+#[reflect(Resource, Default)]
+pub struct SupplierState {
+    /// 0 (burned) to 100 (family)
+    pub relationship: f32,
+    /// Current terms; None means buying on the spot market
+    pub terms: Option<SupplyTerms>,
+    /// Offer waiting for an answer
+    pub offer: Option<SupplyTerms>,
+    /// Haggled once already this month
+    pub haggled: bool,
+    /// Things made this month, against the commitment
+    pub things_this_month: u64,
+    /// Days left without deliveries
+    pub disruption_days: u32,
+    /// Things made today, billed at the end of the day
+    things_today: u64,
+}
+
+impl Default for SupplierState {
+    fn default() -> Self {
+        Self {
+            relationship: 50.0,
+            terms: Some(SupplyTerms {
+                price: 1.0,
+                commitment: 0,
+            }),
+            offer: None,
+            haggled: false,
+            things_this_month: 0,
+            disruption_days: 0,
+            things_today: 0,
+        }
+    }
+}
+
+impl SupplierState {
+    /// Today's price multiplier on materials
+    pub fn price(&self) -> f32 {
+        self.terms.map(|t| t.price).unwrap_or(SPOT_PRICE)
+    }
+
+    /// Chance a haggle works
+    pub fn haggle_chance(&self) -> f32 {
+        (self.relationship / 100.0).clamp(0.05, 0.9)
+    }
+
+    fn change_relationship(&mut self, amount: f32) {
+        self.relationship = (self.relationship + amount).clamp(0.0, 100.0);
+    }
+
+    /// Terms the supplier would offer now
+    fn draft_offer(&self, last_month_things: u64, exclusive: bool) -> SupplyTerms {
+        let mut price = 1.0 - (self.relationship - 50.0) / 50.0 * RELATIONSHIP_PRICE_SWING;
+        if exclusive {
+            price -= EXCLUSIVITY_DISCOUNT;
+        }
+        let commitment = (last_month_things as f64 * COMMITMENT_GROWTH) as u64;
+        if commitment > 0 {
+            price -= COMMITMENT_DISCOUNT;
+        }
+        SupplyTerms { price, commitment }
+    }
+}
+
+/// What happened with the supplier
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SupplierStage {
+    Offered,
+    Accepted,
+    Haggled { success: bool },
+    WalkedAway,
+    Shortfall,
+    Disrupted,
+    Restored,
+}
+
+/// Message fired as negotiations and deliveries go
+#[derive(Event, Message, Clone)]
+pub struct SupplierEvent {
+    pub stage: SupplierStage,
+}
+
+/// Message requesting a move in the negotiation (sent by the UI)
+#[derive(Event, Message, Clone)]
+pub struct NegotiateEvent {
+    pub action: NegotiationMove,
+}
+
+pub struct SupplierPlugin;
+
+impl Plugin for SupplierPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SupplierState>()
+            .add_message::<SupplierEvent>()
+            .add_message::<NegotiateEvent>()
+            .add_systems(
+                Update,
+                (negotiate, run_supply).chain().run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// Answer the supplier's offer
+fn negotiate(
+    mut negotiate_events: MessageReader<NegotiateEvent>,
+    marketing: Res<MarketingState>,
+    mut supplier: ResMut<SupplierState>,
+    mut supplier_events: MessageWriter<SupplierEvent>,
+) {
+    let exclusive = marketing.supplier_exclusivity.active;
+    for event in negotiate_events.read() {
+        let Some(offer) = supplier.offer else {
+            continue;
+        };
+
+        let stage = match event.action {
+            NegotiationMove::Accept => {
+                supplier.terms = Some(offer);
+                supplier.offer = None;
+                supplier.change_relationship(ACCEPT_GOODWILL);
+                SupplierStage::Accepted
+            }
+            NegotiationMove::Haggle => {
+                if supplier.haggled {
+                    continue;
+                }
+                supplier.haggled = true;
+                let success = rand::random::<f32>() < supplier.haggle_chance();
+                if success {
+                    supplier.offer = Some(SupplyTerms {
+                        price: offer.price - HAGGLE_DISCOUNT,
+                        ..offer
+                    });
+                } else {
+                    supplier.change_relationship(-HAGGLE_FAIL_COST);
+                }
+                SupplierStage::Haggled { success }
+            }
+            NegotiationMove::WalkAway => {
+                supplier.terms = None;
+                supplier.offer = None;
+                let cost = if exclusive { WALK_AWAY_COST * 2.0 } else { WALK_AWAY_COST };
+                supplier.change_relationship(-cost);
+                SupplierStage::WalkedAway
+            }
+        };
+        supplier_events.write(SupplierEvent { stage });
+    }
+}
+
+/// Bill materials, settle commitments and make offers, and cut deliveries when bridges are burned
+fn run_supply(
+    mut thing_events: MessageReader<ThingProducedEvent>,
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    marketing: Res<MarketingState>,
+    things: Res<ThingRegistry>,
+    mut game_state: ResMut<GameState>,
+    mut supplier: ResMut<SupplierState>,
+    mut supplier_events: MessageWriter<SupplierEvent>,
+) {
+    for event in thing_events.read() {
+        supplier.things_today += event.amount;
+    }
+
+    for event in day_events.read() {
+        // Pay for yesterday's materials
+        let made = std::mem::take(&mut supplier.things_today);
+        supplier.things_this_month += made;
+        if let Some(thing_type) = game_state.thing_type {
+            let bill = made as f64 * things[thing_type].base_price * MATERIAL_SHARE * supplier.price() as f64;
+            if bill > 0.0 {
+                game_state.spend(ExpenseCategory::Materials, bill);
+            }
+        }
+
+        if supplier.disruption_days > 0 {
+            supplier.disruption_days -= 1;
+            if supplier.disruption_days == 0 {
+                game_state.supply_cut = false;
+                supplier.relationship = AFTER_DISRUPTION_RELATIONSHIP;
+                supplier_events.write(SupplierEvent {
+                    stage: SupplierStage::Restored,
+                });
+            }
+        }
+
+        if event.date.day() == 1 {
+            let exclusive = marketing.supplier_exclusivity.active;
+            let last_month = std::mem::take(&mut supplier.things_this_month);
+
+            // Settle last month's commitment
+            if let (Some(terms), Some(thing_type)) = (supplier.terms, game_state.thing_type) {
+                if terms.commitment > 0 {
+                    if last_month >= terms.commitment {
+                        supplier.change_relationship(COMMITMENT_MET_GOODWILL);
+                    } else {
+                        let short = terms.commitment - last_month;
+                        let penalty = short as f64 * things[thing_type].base_price * MATERIAL_SHARE * SHORTFALL_PENALTY;
+                        game_state.spend(ExpenseCategory::Materials, penalty);
+                        supplier.change_relationship(-SHORTFALL_COST);
+                        supplier_events.write(SupplierEvent {
+                            stage: SupplierStage::Shortfall,
+                        });
+                    }
+                }
+            }
+            if exclusive {
+                supplier.change_relationship(EXCLUSIVITY_GOODWILL);
+            }
+
+            // New terms on the table (the old ones lapse into a plain list-price deal)
+            if supplier.disruption_days == 0 {
+                let offer = supplier.draft_offer(last_month, exclusive);
+                supplier.offer = Some(offer);
+                supplier.haggled = false;
+                if let Some(terms) = supplier.terms.as_mut() {
+                    terms.commitment = 0;
+                }
+                supplier_events.write(SupplierEvent {
+                    stage: SupplierStage::Offered,
+                });
+            }
+        }
+
+        if supplier.relationship < DISRUPTION_THRESHOLD && supplier.disruption_days == 0 {
+            supplier.disruption_days = DISRUPTION_DAYS;
+            supplier.offer = None;
+            game_state.supply_cut = true;
+            supplier_events.write(SupplierEvent {
+                stage: SupplierStage::Disrupted,
+            });
+        }
+    }
+}
@@ -0,0 +1,278 @@
+//! Market research surveys - pay to find out what the market won't tell you
+//!
+//! Some of what drives sales is hidden: how sharply customers react to price,
+//! how much of the Thing's segment can still afford it, and what the
+//! competitors are really charging. A survey asks a sample of people and comes
+//! back after some fieldwork with an estimate and a 95% confidence interval.
+//! Bigger samples cost more and narrow the interval, but every estimate is
+//! still just an estimate.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use std::f64::consts::TAU;
+use crate::accounting::ExpenseCategory;
+use crate::economy::{DayAdvancedEvent, GameDate, WorldState};
+use crate::game_state::{AppState, GameState};
+use crate::macro_dynamics;
+use crate::price_war::{PriceWarState, PRICE_ELASTICITY};
+use crate::thing_type::ThingRegistry;
+
+/// Fixed cost of any survey (questionnaire design, the agency's lunch)
+const SETUP_FEE: f64 = 250.0;
+/// Cost per person asked
+const COST_PER_RESPONDENT: f64 = 4.0;
+/// z-score for a 95% confidence interval
+const Z_95: f64 = 1.96;
+
+/// What a survey can find out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum SurveyTopic {
+    PriceElasticity,
+    SegmentSize,
+    CompetitorPricing,
+}
+
+impl SurveyTopic {
+    pub const ALL: [SurveyTopic; 3] = [
+        SurveyTopic::PriceElasticity,
+        SurveyTopic::SegmentSize,
+        SurveyTopic::CompetitorPricing,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SurveyTopic::PriceElasticity => "Price sensitivity",
+            SurveyTopic::SegmentSize => "Segment size",
+            SurveyTopic::CompetitorPricing => "Competitor pricing",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            SurveyTopic::PriceElasticity => "How many customers walk away when the price goes up.",
+            SurveyTopic::SegmentSize => "How much of your segment can still afford a Thing.",
+            SurveyTopic::CompetitorPricing => "What the competitors are actually charging.",
+        }
+    }
+
+    /// How much individual answers vary - the noisier the question, the wider the interval
+    fn spread(&self) -> f64 {
+        match self {
+            SurveyTopic::PriceElasticity => 2.0,
+            SurveyTopic::SegmentSize => 0.5,
+            SurveyTopic::CompetitorPricing => 0.3,
+        }
+    }
+
+    /// The real answer right now
+    fn truth(&self, world: &WorldState, game_state: &GameState, things: &ThingRegistry, price_war: &PriceWarState) -> f64 {
+        match self {
+            SurveyTopic::PriceElasticity => PRICE_ELASTICITY as f64,
+            SurveyTopic::SegmentSize => game_state
+                .thing_type
+                .map_or(1.0, |thing_type| macro_dynamics::spending_power(world, &things[thing_type])),
+            SurveyTopic::CompetitorPricing => price_war.competitor_price as f64,
+        }
+    }
+
+    /// A value of this topic, in words
+    pub fn format(&self, value: f64) -> String {
+        match self {
+            SurveyTopic::PriceElasticity => format!("{:.2}", value),
+            SurveyTopic::SegmentSize | SurveyTopic::CompetitorPricing => format!("{:.0}%", value * 100.0),
+        }
+    }
+}
+
+/// How many people to ask
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum SampleSize {
+    Small,
+    Medium,
+    Large,
+}
+
+impl SampleSize {
+    pub const ALL: [SampleSize; 3] = [SampleSize::Small, SampleSize::Medium, SampleSize::Large];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SampleSize::Small => "Small",
+            SampleSize::Medium => "Medium",
+            SampleSize::Large => "Large",
+        }
+    }
+
+    pub fn respondents(&self) -> u32 {
+        match self {
+            SampleSize::Small => 50,
+            SampleSize::Medium => 200,
+            SampleSize::Large => 800,
+        }
+    }
+
+    pub fn cost(&self) -> f64 {
+        SETUP_FEE + COST_PER_RESPONDENT * self.respondents() as f64
+    }
+
+    /// Game days of fieldwork before the report comes back
+    pub fn fieldwork_days(&self) -> u32 {
+        match self {
+            SampleSize::Small => 3,
+            SampleSize::Medium => 5,
+            SampleSize::Large => 10,
+        }
+    }
+}
+
+/// A survey in the field
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct PendingSurvey {
+    pub topic: SurveyTopic,
+    pub sample: SampleSize,
+    pub days_remaining: u32,
+}
+
+/// What a survey came back with
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct SurveyReport {
+    pub topic: SurveyTopic,
+    pub sample: SampleSize,
+    pub date: GameDate,
+    pub estimate: f64,
+    /// Half-width of the 95% confidence interval
+    pub margin: f64,
+}
+
+impl SurveyReport {
+    pub fn summary(&self) -> String {
+        format!(
+            "{}: about {} (95% sure it's {} to {}), {} asked on {}",
+            self.topic.name(),
+            self.topic.format(self.estimate),
+            self.topic.format(self.estimate - self.margin),
+            self.topic.format(self.estimate + self.margin),
+            self.sample.respondents(),
+            self.date.iso(),
+        )
+    }
+}
+
+/// Surveys in the field, and the latest report on each topic
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct SurveyState {
+    pub pending: Vec<PendingSurvey>,
+    pub reports: Vec<SurveyReport>,
+}
+
+impl SurveyState {
+    pub fn in_field(&self, topic: SurveyTopic) -> Option<&PendingSurvey> {
+        self.pending.iter().find(|survey| survey.topic == topic)
+    }
+
+    pub fn latest(&self, topic: SurveyTopic) -> Option<&SurveyReport> {
+        self.reports.iter().find(|report| report.topic == topic)
+    }
+
+    pub fn can_commission(&self, topic: SurveyTopic, sample: SampleSize, game_state: &GameState) -> bool {
+        self.in_field(topic).is_none() && game_state.money >= sample.cost()
+    }
+}
+
+/// Message requesting a survey (sent by the UI)
+#[derive(Event, Message, Clone)]
+pub struct CommissionSurveyEvent {
+    pub topic: SurveyTopic,
+    pub sample: SampleSize,
+}
+
+/// Message fired when a survey goes into the field or its report comes back
+#[derive(Event, Message, Clone)]
+pub struct SurveyEvent {
+    pub topic: SurveyTopic,
+    pub completed: bool,
+}
+
+pub struct SurveyPlugin;
+
+impl Plugin for SurveyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SurveyState>()
+            .add_message::<CommissionSurveyEvent>()
+            .add_message::<SurveyEvent>()
+            .add_systems(
+                Update,
+                (commission_survey, run_fieldwork)
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// Pay the agency and send the survey out
+fn commission_survey(
+    mut commission_events: MessageReader<CommissionSurveyEvent>,
+    mut game_state: ResMut<GameState>,
+    mut surveys: ResMut<SurveyState>,
+    mut survey_events: MessageWriter<SurveyEvent>,
+) {
+    for event in commission_events.read() {
+        if !surveys.can_commission(event.topic, event.sample, &game_state) {
+            continue;
+        }
+        game_state.spend(ExpenseCategory::Marketing, event.sample.cost());
+        surveys.pending.push(PendingSurvey {
+            topic: event.topic,
+            sample: event.sample,
+            days_remaining: event.sample.fieldwork_days(),
+        });
+        survey_events.write(SurveyEvent {
+            topic: event.topic,
+            completed: false,
+        });
+    }
+}
+
+/// Count fieldwork down each game day and write up the finished surveys
+fn run_fieldwork(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    world: Res<WorldState>,
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    price_war: Res<PriceWarState>,
+    mut surveys: ResMut<SurveyState>,
+    mut survey_events: MessageWriter<SurveyEvent>,
+) {
+    for event in day_events.read() {
+        for survey in &mut surveys.pending {
+            survey.days_remaining = survey.days_remaining.saturating_sub(1);
+        }
+
+        let (done, pending): (Vec<_>, Vec<_>) = surveys.pending.drain(..).partition(|survey| survey.days_remaining == 0);
+        surveys.pending = pending;
+        for survey in done {
+            let standard_error = survey.topic.spread() / (survey.sample.respondents() as f64).sqrt();
+            let report = SurveyReport {
+                topic: survey.topic,
+                sample: survey.sample,
+                date: event.date,
+                estimate: survey.topic.truth(&world, &game_state, &things, &price_war) + standard_normal() * standard_error,
+                margin: Z_95 * standard_error,
+            };
+            surveys.reports.retain(|old| old.topic != survey.topic);
+            surveys.reports.push(report);
+            survey_events.write(SurveyEvent {
+                topic: survey.topic,
+                completed: true,
+            });
+        }
+    }
+}
+
+/// A draw from the standard normal distribution (Box-Muller)
+fn standard_normal() -> f64 {
+    let u = 1.0 - rand::random::<f64>();
+    let v = rand::random::<f64>();
+    (-2.0 * u.ln()).sqrt() * (TAU * v).cos()
+}
@@ -2,9 +2,42 @@
 
 use bevy::prelude::*;
 use bevy::ecs::schedule::IntoScheduleConfigs;
-use crate::dialogue::{DialogueDatabase, DialogueLine};
-use crate::game_state::{AppState, GameState, MilestoneEvent, MilestoneType, ThingProducedEvent};
-use crate::thing_type::ThingType;
+use bevy::ecs::system::SystemParam;
+use std::collections::{HashMap, VecDeque};
+use crate::ai_boom::{AiEvent, AiStage, AiUpgrade};
+use crate::eco::{ActivistAction, ActivistEvent};
+use crate::crypto::{CryptoEvent, CryptoStage};
+use crate::counterfeits::{CounterfeitEvent, CounterfeitStage};
+use crate::debates::{Advisor, DebateEvent, DebateStage};
+use crate::economy::{DayAdvancedEvent, HeadlineEvent, Holiday, WorldState};
+use crate::fads::{FadEvent, FadStage};
+use crate::game_clock::GameClock;
+use crate::attract::AttractMode;
+use crate::clock::{ClockMoment, ClockMomentEvent};
+use crate::cosmetics::{Cosmetic, CosmeticUnlockedEvent};
+use crate::gift_cards::{GiftCardActivity, GiftCardEvent, GiftCards};
+use crate::seminars::{SeminarEvent, SeminarState};
+use crate::spending::SpendingPatternEvent;
+use crate::suppliers::{SupplierEvent, SupplierStage};
+use crate::tutorial::FirstTimeEvent;
+use crate::venues::{VenueEvent, VenueKind, VenueStage};
+use crate::dialogue::{DialogueDatabase, DialogueLine, DialoguePriority, DEFAULT_MIN_GAP};
+use crate::game_state::{game_running, reset_resource, AppState, GameState, InGame, MilestoneEvent, MilestoneType, ReputationChangedEvent, ThingProducedEvent};
+use crate::labor::{StrikeEvent, StrikeResolution, StrikeStage};
+use crate::limited_editions::{Edition, EditionEvent, EditionStage};
+use crate::pandemic::{PandemicEvent, PandemicPhase, PandemicStage};
+use crate::parallel::UniverseLeadEvent;
+use crate::pivot::PivotEvent;
+use crate::quests::{QuestBook, QuestEvent, QuestLog, QuestStage};
+use crate::price_war::{PriceWarEvent, PriceWarStage};
+use crate::recovery::{RecoveryAction, RecoveryEvent, RecoveryStage};
+use crate::reputation::{ReputationTier, ReputationTierChangedEvent, SCANDAL_REPUTATION};
+use crate::research::{ResearchCompletedEvent, Tech};
+use crate::rewards::{Reward, RewardEvent};
+use crate::thing_type::{ThingRegistry, ThingType};
+use crate::utilities::{OutageCause, PowerOutageEvent};
+use crate::vacation::{terry_in_office, VacationEvent, VacationStage, VacationState};
+use crate::wellbeing::{Wellbeing, WellbeingEvent, WellbeingStage};
 
 pub struct TerryPlugin;
 
@@ -12,15 +45,56 @@ impl Plugin for TerryPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<TerryState>()
             .add_message::<TerryDialogueEvent>()
-            .add_systems(OnEnter(AppState::Playing), terry_greet_on_start)
+            .add_systems(OnEnter(InGame), terry_greet_on_start)
+            .add_systems(OnExit(InGame), reset_resource::<TerryState>)
             .add_systems(
                 Update,
                 (
                     react_to_milestones,
                     react_to_clicks,
-                    periodic_commentary,
+                    react_to_scandal,
+                    react_to_money_trouble,
+                    react_to_reputation_tier,
+                    react_to_recovery,
+                    react_to_pivot,
+                    react_to_research,
+                    react_to_strike,
+                    react_to_power_outage,
+                    react_to_activists,
+                    react_to_clock_moments,
+                    react_to_cosmetic_unlocks,
+                    react_to_gift_cards,
+                    react_to_venues,
+                    react_to_suppliers,
+                    react_to_seminars,
+                    react_to_first_times,
                 )
-                    .run_if(in_state(AppState::Playing)),
+                    .run_if(in_state(AppState::Playing).and(terry_in_office).and(game_running)),
+            )
+            .add_systems(
+                Update,
+                (
+                    react_to_reward_cards,
+                    react_to_crypto,
+                    react_to_pandemic,
+                    react_to_ai_boom,
+                    react_to_wellbeing,
+                    react_to_price_war,
+                    react_to_counterfeits,
+                    react_to_editions,
+                    react_to_fads,
+                    react_to_headlines,
+                    react_to_holidays,
+                    react_to_quests,
+                    react_to_parallel_universe,
+                    react_to_spending_patterns,
+                    react_to_debates,
+                )
+                    .run_if(in_state(AppState::Playing).and(terry_in_office).and(game_running)),
+            )
+            .add_systems(
+                Update,
+                (react_to_vacation, periodic_commentary).run_if(in_state(AppState::Playing).and(game_running)),
             );
     }
 }
@@ -38,6 +112,16 @@ pub struct TerryState {
     pub commentary_timer: f32,
     /// Clicks since last reaction
     pub clicks_since_reaction: u32,
+    /// Lines waiting for the current one to finish
+    pub queue: VecDeque<DialogueLine>,
+    /// Length of the current line's voice clip, once it has loaded
+    pub voice_secs: Option<f32>,
+    /// Seconds until each trigger on cooldown can be heard again
+    pub trigger_cooldowns: HashMap<String, f32>,
+    /// Seconds since the last non-critical line went up
+    pub since_chatter: f32,
+    /// Fewest seconds between two non-critical lines
+    pub min_gap: f32,
 }
 
 impl Default for TerryState {
@@ -48,10 +132,206 @@ impl Default for TerryState {
             line_duration: 5.0,
             commentary_timer: 0.0,
             clicks_since_reaction: 0,
+            queue: VecDeque::new(),
+            voice_secs: None,
+            trigger_cooldowns: HashMap::new(),
+            since_chatter: f32::INFINITY,
+            min_gap: DEFAULT_MIN_GAP,
         }
     }
 }
 
+/// Maximum number of lines waiting to be said
+pub const MAX_QUEUED_LINES: usize = 5;
+/// How fast the typewriter writes out a line with no voice clip
+const TYPEWRITER_CHARS_PER_SEC: f32 = 40.0;
+/// How long a voiced line stays up after the clip ends
+const VOICE_LINGER_SECS: f32 = 1.5;
+
+impl TerryState {
+    /// Is the current line done (or is Terry not saying anything)?
+    pub fn is_idle(&self) -> bool {
+        self.current_line.is_none() || self.line_timer >= self.line_secs()
+    }
+
+    /// How long the current line stays up: its voice clip and a moment, or the usual time
+    fn line_secs(&self) -> f32 {
+        self.voice_secs.map_or(self.line_duration, |secs| secs + VOICE_LINGER_SECS)
+    }
+
+    /// Has the current line been up long enough to be replaced?
+    fn current_line_seen(&self) -> bool {
+        self.current_line.as_ref().is_none_or(|line| {
+            self.line_timer >= self.voice_secs.unwrap_or_else(|| line.min_display_secs())
+        })
+    }
+
+    /// As much of the current line as the typewriter has written out, keeping
+    /// pace with the voice clip when there is one
+    pub fn typed_text(&self) -> Option<&str> {
+        let text = &self.current_line.as_ref()?.text;
+        let chars_per_sec = match self.voice_secs {
+            Some(secs) if secs > 0.0 => text.chars().count() as f32 / secs,
+            _ => TYPEWRITER_CHARS_PER_SEC,
+        };
+        let typed = (self.line_timer * chars_per_sec).ceil() as usize;
+        Some(text.char_indices().nth(typed).map_or(text.as_str(), |(end, _)| &text[..end]))
+    }
+
+    /// Say a line, respecting its priority:
+    /// critical lines interrupt immediately, normal lines replace anything but a
+    /// critical line once it has been on screen long enough, and ambient lines
+    /// wait until Terry is done talking. Anything that can't be shown yet is queued.
+    /// Non-critical lines whose trigger was heard too recently are dropped, and
+    /// wait in the queue if Terry has only just said something.
+    pub fn say(&mut self, line: &DialogueLine) {
+        if line.priority != DialoguePriority::Critical {
+            if self.trigger_cooldowns.contains_key(&line.trigger) {
+                debug!("Trigger {} is cooling down, dropping line: {}", line.trigger, line.id);
+                return;
+            }
+            if line.cooldown_secs() > 0.0 {
+                self.trigger_cooldowns.insert(line.trigger.clone(), line.cooldown_secs());
+            }
+            if !self.chatter_allowed() {
+                self.enqueue(line.clone());
+                return;
+            }
+        }
+
+        if self.is_idle() {
+            self.show(line.clone());
+            return;
+        }
+
+        let current_priority = self
+            .current_line
+            .as_ref()
+            .map(|current| current.priority)
+            .unwrap_or_default();
+
+        let interrupt = match line.priority {
+            DialoguePriority::Critical => true,
+            DialoguePriority::Normal => {
+                current_priority != DialoguePriority::Critical && self.current_line_seen()
+            }
+            DialoguePriority::Ambient => false,
+        };
+
+        if interrupt {
+            // A critical line cutting off something barely read puts it back in line
+            if !self.current_line_seen() {
+                if let Some(interrupted) = self.current_line.take() {
+                    self.requeue_front(interrupted);
+                }
+            }
+            self.show(line.clone());
+        } else {
+            self.enqueue(line.clone());
+        }
+    }
+
+    /// Queue a line, skipping duplicates and making room when full
+    fn enqueue(&mut self, line: DialogueLine) {
+        if self.is_duplicate(&line) {
+            return;
+        }
+
+        if self.queue.len() >= MAX_QUEUED_LINES && !self.make_room(line.priority) {
+            // Everything queued matters more than this line
+            debug!("Terry's dialogue queue is full, dropping line: {}", line.id);
+            return;
+        }
+
+        // Higher priority lines jump ahead of lower priority ones
+        let position = self
+            .queue
+            .iter()
+            .position(|queued| queued.priority < line.priority)
+            .unwrap_or(self.queue.len());
+        self.queue.insert(position, line);
+    }
+
+    /// Put an interrupted line back at the front of the queue
+    fn requeue_front(&mut self, line: DialogueLine) {
+        if self.is_duplicate(&line) {
+            return;
+        }
+        if self.queue.len() >= MAX_QUEUED_LINES {
+            self.queue.pop_back();
+        }
+        self.queue.push_front(line);
+    }
+
+    fn is_duplicate(&self, line: &DialogueLine) -> bool {
+        let showing = !self.is_idle()
+            && self.current_line.as_ref().is_some_and(|current| current.id == line.id);
+        showing || self.queue.iter().any(|queued| queued.id == line.id)
+    }
+
+    /// Drop the newest lowest-priority line to make room for a line of the given priority.
+    /// Returns false if nothing queued is less important.
+    fn make_room(&mut self, priority: DialoguePriority) -> bool {
+        let lowest = self
+            .queue
+            .iter()
+            .enumerate()
+            .filter(|(_, queued)| queued.priority < priority || queued.priority == DialoguePriority::Ambient)
+            .min_by_key(|(index, queued)| (queued.priority, std::cmp::Reverse(*index)))
+            .map(|(index, _)| index);
+
+        match lowest {
+            Some(index) => {
+                self.queue.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Show the next queued line, if any. Returns true if something was shown.
+    pub fn advance_queue(&mut self) -> bool {
+        match self.queue.pop_front() {
+            Some(line) => {
+                self.show(line);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Skip the current line, moving on to whatever is queued
+    pub fn skip(&mut self) {
+        if !self.advance_queue() {
+            self.line_timer = self.line_secs();
+        }
+    }
+
+    /// Whether enough time has passed since the last non-critical line
+    pub fn chatter_allowed(&self) -> bool {
+        self.since_chatter >= self.min_gap
+    }
+
+    /// Run down the trigger cooldowns and the gap since the last line
+    fn tick_pacing(&mut self, secs: f32, min_gap: f32) {
+        self.since_chatter += secs;
+        self.min_gap = min_gap;
+        self.trigger_cooldowns.retain(|_, left| {
+            *left -= secs;
+            *left > 0.0
+        });
+    }
+
+    fn show(&mut self, line: DialogueLine) {
+        if line.priority != DialoguePriority::Critical {
+            self.since_chatter = 0.0;
+        }
+        self.current_line = Some(line);
+        self.line_timer = 0.0;
+        self.voice_secs = None;
+    }
+}
+
 /// Message to trigger Terry saying something
 #[derive(Event, Message, Clone)]
 pub struct TerryDialogueEvent {
@@ -61,28 +341,31 @@ pub struct TerryDialogueEvent {
 /// Greet player when game starts
 fn terry_greet_on_start(
     game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    attract: Res<AttractMode>,
     dialogue_db: Res<DialogueDatabase>,
     mut terry_state: ResMut<TerryState>,
 ) {
-    // First, say hello
-    if let Some(line) = dialogue_db.get_for_trigger("game_start") {
-        terry_state.current_line = Some(line.clone());
-        terry_state.line_timer = 0.0;
+    // First, say hello (or introduce the demo)
+    let greeting = if attract.running { "attract_start" } else { "game_start" };
+    if let Some(line) = dialogue_db.get_for_trigger(greeting) {
+        terry_state.say(line);
     }
 
-    // Then queue up thing-type-specific greeting
-    if let Some(thing_type) = game_state.thing_type {
-        let trigger = match thing_type {
-            ThingType::Cheap => "select_cheap",
-            ThingType::Good => "select_good",
-            ThingType::Expensive => "select_expensive",
-            ThingType::Bad => "select_bad",
-        };
+    // Then queue up thing-type-specific greeting (Terry has to improvise for a made-up one)
+    if let Some(thing_type) = game_state.thing_type.filter(ThingType::is_custom) {
+        if let Some(line) = dialogue_db.get_for_trigger("select_custom") {
+            terry_state.say(&DialogueLine {
+                text: line.text.replace("{thing}", &things[thing_type].name),
+                ..line.clone()
+            });
+        }
+    } else if let Some(thing_type) = game_state.thing_type {
+        let trigger = format!("select_{}", things[thing_type].dialogue_prefix);
 
         // This will be the next line after the greeting times out
-        if let Some(_line) = dialogue_db.get_for_trigger(trigger) {
-            // We'll handle this in the periodic commentary
-            terry_state.commentary_timer = terry_state.line_duration + 1.0;
+        if let Some(line) = dialogue_db.get_for_trigger(&trigger) {
+            terry_state.say(line);
         }
     }
 }
@@ -101,12 +384,12 @@ fn react_to_milestones(
             MilestoneType::ThingsProduced(10000) => "things_10000",
             MilestoneType::MoneyEarned(100) => "money_100",
             MilestoneType::MoneyEarned(1000) => "money_1000",
+            MilestoneType::MoneyEarned(1000000) => "money_1000000",
             _ => continue,
         };
 
         if let Some(line) = dialogue_db.get_for_trigger(trigger) {
-            terry_state.current_line = Some(line.clone());
-            terry_state.line_timer = 0.0;
+            terry_state.say(line);
         }
     }
 }
@@ -126,49 +409,746 @@ fn react_to_clicks(
                 terry_state.clicks_since_reaction = 0;
 
                 if let Some(line) = dialogue_db.get_for_trigger("click") {
-                    terry_state.current_line = Some(line.clone());
-                    terry_state.line_timer = 0.0;
+                    terry_state.say(line);
                 }
             }
         }
     }
 }
 
-/// Periodic commentary based on game state
+/// Chance a periodic comment is seminar jargon, once Terry has taught a course
+const JARGON_CHANCE: f32 = 0.3;
+/// Chance a periodic comment is gloomy while the player is exhausted
+const GLOOM_CHANCE: f32 = 0.5;
+/// Chance a periodic comment is about the time of year, when there's something to say
+const SEASONAL_CHANCE: f32 = 0.5;
+
+/// What Terry's periodic commentary draws on
+#[derive(SystemParam)]
+struct CommentaryContext<'w> {
+    game_state: Res<'w, GameState>,
+    things: Res<'w, ThingRegistry>,
+    world: Res<'w, WorldState>,
+    seminars: Res<'w, SeminarState>,
+    vacation: Res<'w, VacationState>,
+    wellbeing: Res<'w, Wellbeing>,
+}
+
+/// Periodic commentary based on game state and the calendar
 fn periodic_commentary(
     time: Res<Time>,
-    game_state: Res<GameState>,
+    clock: Res<GameClock>,
+    context: CommentaryContext,
     dialogue_db: Res<DialogueDatabase>,
     mut terry_state: ResMut<TerryState>,
 ) {
+    let CommentaryContext {
+        game_state,
+        things,
+        world,
+        seminars,
+        vacation,
+        wellbeing,
+    } = context;
+
     terry_state.line_timer += time.delta_secs();
-    terry_state.commentary_timer += time.delta_secs();
+    terry_state.commentary_timer += clock.delta_secs();
+    terry_state.tick_pacing(time.delta_secs(), dialogue_db.min_gap());
 
-    // Only give commentary if current line has timed out
-    if terry_state.line_timer >= terry_state.line_duration {
+    // Only give commentary if current line has timed out and nothing is waiting
+    if terry_state.is_idle() && terry_state.chatter_allowed() && !terry_state.advance_queue() {
         // Commentary every 15-20 seconds
         if terry_state.commentary_timer >= 15.0 {
             terry_state.commentary_timer = 0.0;
 
-            // Pick contextual commentary based on Thing type
-            let trigger = match game_state.thing_type {
-                Some(ThingType::Cheap) => "cheap_playing",
-                Some(ThingType::Good) => "good_playing",
-                Some(ThingType::Expensive) => "expensive_playing",
-                Some(ThingType::Bad) => {
-                    if game_state.reputation < 1.5 {
-                        "bad_low_rep"
-                    } else {
-                        "bad_playing"
+            // The temp only has the one kind of advice
+            if vacation.is_away() {
+                if let Some(line) = dialogue_db.get_for_trigger("temp_advice") {
+                    terry_state.say(line);
+                }
+                return;
+            }
+
+            // A worn-out boss makes for a gloomy hot dog
+            if wellbeing.exhausted && rand::random::<f32>() < GLOOM_CHANCE {
+                if let Some(line) = dialogue_db.get_for_trigger("wellbeing_gloom") {
+                    terry_state.say(line);
+                    return;
+                }
+            }
+
+            // First snow, heat waves, Christmas week, tax day
+            if let Some(moment) = world.seasonal_moment {
+                if rand::random::<f32>() < SEASONAL_CHANCE {
+                    if let Some(line) = dialogue_db.get_for_trigger(moment.trigger()) {
+                        terry_state.say(line);
+                        return;
                     }
                 }
-                None => "idle",
+            }
+
+            // Now and then, show off what Terry picked up teaching a seminar
+            if !seminars.taught.is_empty() && rand::random::<f32>() < JARGON_CHANCE {
+                let course = seminars.taught[rand::random_range(0..seminars.taught.len())];
+                if let Some(line) = dialogue_db.get_for_trigger(course.jargon_trigger()) {
+                    terry_state.say(line);
+                    return;
+                }
+            }
+
+            // Pick contextual commentary based on Thing type
+            let trigger = match game_state.thing_type {
+                Some(thing_type) if things[thing_type].shady && game_state.reputation < 1.5 => "bad_low_rep".to_string(),
+                Some(thing_type) => format!("{}_playing", things[thing_type].dialogue_prefix),
+                None => "idle".to_string(),
             };
 
+            if let Some(line) = dialogue_db.get_for_trigger(&trigger) {
+                terry_state.say(line);
+            }
+        }
+    }
+}
+
+/// Interrupt with a scandal line when a Bad Thing's reputation crashes
+fn react_to_scandal(
+    mut rep_events: MessageReader<ReputationChangedEvent>,
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+    mut in_scandal: Local<bool>,
+) {
+    for event in rep_events.read() {
+        if !things.is_shady(game_state.thing_type) {
+            continue;
+        }
+
+        if event.new_reputation < SCANDAL_REPUTATION && !*in_scandal {
+            *in_scandal = true;
+            if let Some(line) = dialogue_db.get_for_trigger("bad_reputation_crash") {
+                terry_state.say(line);
+            }
+        } else if event.new_reputation >= SCANDAL_REPUTATION + 0.5 {
+            // Recovered enough that the next crash is news again
+            *in_scandal = false;
+        }
+    }
+}
+
+/// Money below which Terry starts panicking about bankruptcy
+const BANKRUPTCY_WARNING_MONEY: f64 = 5.0;
+/// Money above which the warning resets
+const BANKRUPTCY_RECOVERED_MONEY: f64 = 50.0;
+
+/// Interrupt with a warning when the player is about to run out of money
+fn react_to_money_trouble(
+    game_state: Res<GameState>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+    mut warned: Local<bool>,
+) {
+    if game_state.money < BANKRUPTCY_WARNING_MONEY && !*warned {
+        *warned = true;
+        if let Some(line) = dialogue_db.get_for_trigger("bankruptcy_warning") {
+            terry_state.say(line);
+        }
+    } else if game_state.money >= BANKRUPTCY_RECOVERED_MONEY {
+        *warned = false;
+    }
+}
+
+/// Comment on reputation tier changes that change what the player can do
+fn react_to_reputation_tier(
+    mut tier_events: MessageReader<ReputationTierChangedEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in tier_events.read() {
+        let trigger = match event.new_tier {
+            ReputationTier::Pariah => "reputation_pariah",
+            ReputationTier::Beloved => "reputation_beloved",
+            _ => continue,
+        };
+
+        if let Some(line) = dialogue_db.get_for_trigger(trigger) {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry narrates the road to redemption
+fn react_to_recovery(
+    mut recovery_events: MessageReader<RecoveryEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in recovery_events.read() {
+        let trigger = match (event.stage, event.action) {
+            (RecoveryStage::Unlocked, _) => "recovery_unlocked",
+            (RecoveryStage::Started, Some(RecoveryAction::Rebrand)) => "rebrand_started",
+            (RecoveryStage::Started, Some(RecoveryAction::ApologyTour)) => "apology_tour_started",
+            (RecoveryStage::Midway, Some(RecoveryAction::Rebrand)) => "rebrand_midway",
+            (RecoveryStage::Midway, Some(RecoveryAction::ApologyTour)) => "apology_tour_midway",
+            (RecoveryStage::Succeeded, Some(RecoveryAction::Rebrand)) => "rebrand_success",
+            (RecoveryStage::Succeeded, Some(RecoveryAction::ApologyTour)) => "apology_tour_success",
+            (RecoveryStage::Failed, _) => "recovery_failed",
+            _ => continue,
+        };
+
+        if let Some(line) = dialogue_db.get_for_trigger(trigger) {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry's take on changing direction mid-run
+fn react_to_pivot(
+    mut pivot_events: MessageReader<PivotEvent>,
+    things: Res<ThingRegistry>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in pivot_events.read() {
+        let trigger = if !event.completed {
+            if things[event.from].shady {
+                "pivot_started_from_bad".to_string()
+            } else {
+                "pivot_started".to_string()
+            }
+        } else {
+            format!("pivot_to_{}", things[event.to].dialogue_prefix)
+        };
+
+        if let Some(line) = dialogue_db.get_for_trigger(&trigger) {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry is management, but also a meat product: strikes are complicated
+fn react_to_strike(
+    mut strike_events: MessageReader<StrikeEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in strike_events.read() {
+        let trigger = match event.stage {
+            StrikeStage::Started => "strike_started",
+            StrikeStage::Resolving(StrikeResolution::MeetDemands) => "strike_meet_demands",
+            StrikeStage::Resolving(StrikeResolution::Negotiate) => "strike_negotiate",
+            StrikeStage::Resolving(StrikeResolution::ReplaceWorkers) => "strike_replace",
+            StrikeStage::Ended(None) => "strike_fizzled",
+            StrikeStage::Ended(Some(_)) => continue,
+        };
+
+        if let Some(line) = dialogue_db.get_for_trigger(trigger) {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry, in the dark
+fn react_to_power_outage(
+    mut outage_events: MessageReader<PowerOutageEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in outage_events.read() {
+        let trigger = match event.cause {
+            Some(OutageCause::HeatWave) => "power_outage_heat_wave",
+            Some(OutageCause::UnpaidBill) => "power_outage_unpaid",
+            None => "power_restored",
+        };
+
+        if let Some(line) = dialogue_db.get_for_trigger(trigger) {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry meets the activists outside
+fn react_to_activists(
+    mut activist_events: MessageReader<ActivistEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in activist_events.read() {
+        let trigger = match event.action {
+            ActivistAction::Protest => "activist_protest",
+            ActivistAction::Praise => "activist_praise",
+        };
+
+        if let Some(line) = dialogue_db.get_for_trigger(trigger) {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry notices what time it is in the player's world
+fn react_to_clock_moments(
+    mut moment_events: MessageReader<ClockMomentEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in moment_events.read() {
+        let trigger = match event.moment {
+            ClockMoment::LateNight => "late_night",
+            ClockMoment::Marathon => "marathon_session",
+            ClockMoment::Holiday(Holiday::Christmas) => "real_holiday_christmas",
+            ClockMoment::Holiday(Holiday::Halloween) => "real_holiday_halloween",
+            ClockMoment::Holiday(Holiday::NewYears) => "real_holiday_new_years",
+            ClockMoment::Holiday(Holiday::IndependenceDay) => "real_holiday_independence",
+            ClockMoment::Holiday(_) => "real_holiday",
+        };
+
+        if let Some(line) = dialogue_db.get_for_trigger(trigger) {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry tries on something new
+fn react_to_cosmetic_unlocks(
+    mut unlock_events: MessageReader<CosmeticUnlockedEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in unlock_events.read() {
+        let trigger = match event.cosmetic {
+            Cosmetic::TinyBriefcase => "cosmetic_briefcase",
+            Cosmetic::Monocle => "cosmetic_monocle",
+            Cosmetic::TopHat => "cosmetic_top_hat",
+            _ => "cosmetic_unlocked",
+        };
+
+        if let Some(line) = dialogue_db.get_for_trigger(trigger) {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry on the gift card season: the first cards sold, and the ones never redeemed
+fn react_to_gift_cards(
+    mut card_events: MessageReader<GiftCardEvent>,
+    cards: Res<GiftCards>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in card_events.read() {
+        let trigger = match event.activity {
+            // Only the first sale of the season, when nothing was owed before
+            GiftCardActivity::Sold if cards.outstanding == event.amount => "gift_cards_on_sale",
+            GiftCardActivity::Breakage => "gift_card_breakage",
+            _ => continue,
+        };
+
+        if let Some(line) = dialogue_db.get_for_trigger(trigger) {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry on pop-up venues: new listings, opening day, and the weather
+fn react_to_venues(
+    mut venue_events: MessageReader<VenueEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in venue_events.read() {
+        let trigger = match (event.stage, event.kind) {
+            (VenueStage::Offered, VenueKind::MallKiosk) => "venue_offered_kiosk",
+            (VenueStage::Offered, _) => "venue_offered",
+            (VenueStage::Opened, _) => "venue_opened",
+            (VenueStage::WashedOut, _) => "venue_washed_out",
+            (VenueStage::Closed, _) => continue,
+        };
+
+        if let Some(line) = dialogue_db.get_for_trigger(trigger) {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry on the supplier: offers, haggling, and burned bridges
+fn react_to_suppliers(
+    mut supplier_events: MessageReader<SupplierEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in supplier_events.read() {
+        let trigger = match event.stage {
+            SupplierStage::Offered => "supplier_offer",
+            SupplierStage::Haggled { success: true } => "supplier_haggle_won",
+            SupplierStage::Haggled { success: false } => "supplier_haggle_lost",
+            SupplierStage::WalkedAway => "supplier_walked_away",
+            SupplierStage::Disrupted => "supplier_disrupted",
+            SupplierStage::Accepted | SupplierStage::Shortfall | SupplierStage::Restored => continue,
+        };
+
+        if let Some(line) = dialogue_db.get_for_trigger(trigger) {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry heads off to teach, and comes back insufferable
+fn react_to_seminars(
+    mut seminar_events: MessageReader<SeminarEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in seminar_events.read() {
+        let trigger = if event.completed { "seminar_completed" } else { "seminar_started" };
+        if let Some(line) = dialogue_db.get_for_trigger(trigger) {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry explains things the first time they happen
+fn react_to_first_times(
+    mut first_events: MessageReader<FirstTimeEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in first_events.read() {
+        if let Some(line) = dialogue_db.get_for_trigger(event.first.trigger()) {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry presents the reward cards when a big milestone offers a choice
+fn react_to_reward_cards(
+    mut reward_events: MessageReader<RewardEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in reward_events.read() {
+        if !matches!(event.reward, Reward::Choice(_)) {
+            continue;
+        }
+        if let Some(line) = dialogue_db.get_for_trigger("reward_cards") {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry has never trusted ThingCoin, and says so
+fn react_to_crypto(
+    mut crypto_events: MessageReader<CryptoEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in crypto_events.read() {
+        let trigger = match event.stage {
+            CryptoStage::WindowOpened => "crypto_listed",
+            CryptoStage::Bought { .. } => "crypto_bought",
+            CryptoStage::Sold { gain, .. } if gain >= 0.0 => "crypto_sold_profit",
+            CryptoStage::Sold { .. } => "crypto_sold_loss",
+            CryptoStage::Mooned => "crypto_mooned",
+            CryptoStage::Crashed => "crypto_crashed",
+            CryptoStage::Issued { .. } => "crypto_issued",
+            CryptoStage::RugPulled { .. } => "crypto_rug_pull",
+            CryptoStage::WindowClosed | CryptoStage::CashedOut { .. } => continue,
+        };
+        if let Some(line) = dialogue_db.get_for_trigger(trigger) {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry takes 2020 one press release at a time
+fn react_to_pandemic(
+    mut pandemic_events: MessageReader<PandemicEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in pandemic_events.read() {
+        let trigger = match event.stage {
+            PandemicStage::Began(PandemicPhase::Lockdown) => "pandemic_lockdown",
+            PandemicStage::Began(PandemicPhase::Restrictions) => "pandemic_restrictions",
+            PandemicStage::Over => "pandemic_over",
+            PandemicStage::PivotedToDelivery => "pandemic_delivery",
+            PandemicStage::MaskRushStarted => "pandemic_masks",
+            PandemicStage::MaskRushEnded => continue,
+        };
+        if let Some(line) = dialogue_db.get_for_trigger(trigger) {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry is quietly terrified of being replaced by a large language model
+fn react_to_ai_boom(
+    mut ai_events: MessageReader<AiEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in ai_events.read() {
+        let trigger = match event.stage {
+            AiStage::Unlocked => "ai_boom",
+            AiStage::Bought(AiUpgrade::Chatbot) => "ai_chatbot",
+            AiStage::Bought(AiUpgrade::AdCopy) => "ai_ad_copy",
+            AiStage::Hallucinated(_) => "ai_hallucination",
+            AiStage::BackOnline(_) => continue,
+        };
+        if let Some(line) = dialogue_db.get_for_trigger(trigger) {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry asks for time off, takes it (or doesn't), and hands over to the temp
+fn react_to_vacation(
+    mut vacation_events: MessageReader<VacationEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in vacation_events.read() {
+        let triggers: &[&str] = match event.stage {
+            VacationStage::Requested => &["vacation_request"],
+            VacationStage::Granted => &["vacation_granted", "temp_intro"],
+            VacationStage::Refused => &["vacation_refused"],
+            VacationStage::BurnedOut => &["vacation_burnout", "temp_intro"],
+            VacationStage::Returned => &["vacation_back"],
+        };
+        for trigger in triggers {
             if let Some(line) = dialogue_db.get_for_trigger(trigger) {
-                terry_state.current_line = Some(line.clone());
-                terry_state.line_timer = 0.0;
+                terry_state.say(line);
             }
         }
     }
 }
+
+/// Terry notices when the boss is running on fumes (and when they've rested)
+fn react_to_wellbeing(
+    mut wellbeing_events: MessageReader<WellbeingEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in wellbeing_events.read() {
+        let trigger = match event.stage {
+            WellbeingStage::Exhausted => "wellbeing_exhausted",
+            WellbeingStage::Recovered => "wellbeing_recovered",
+            WellbeingStage::DaysOffStarted => "wellbeing_days_off",
+            WellbeingStage::DaysOffEnded => "wellbeing_back",
+        };
+        if let Some(line) = dialogue_db.get_for_trigger(trigger) {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry has strong opinions about discounting, and stronger ones about being discounted against
+fn react_to_price_war(
+    mut price_war_events: MessageReader<PriceWarEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in price_war_events.read() {
+        let trigger = match event.stage {
+            PriceWarStage::Started => "price_war_started",
+            PriceWarStage::TruceOffered => "price_war_truce_offer",
+            PriceWarStage::TruceAccepted | PriceWarStage::Ceasefire => "price_war_over",
+            PriceWarStage::Recovered => continue,
+        };
+        if let Some(line) = dialogue_db.get_for_trigger(trigger) {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry takes imitation as the sincerest form of theft
+fn react_to_counterfeits(
+    mut counterfeit_events: MessageReader<CounterfeitEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in counterfeit_events.read() {
+        let trigger = match event.stage {
+            CounterfeitStage::Appeared => "counterfeits_appeared",
+            CounterfeitStage::CustomerBurned => "counterfeit_burn",
+            CounterfeitStage::Seized => "customs_seizure",
+            CounterfeitStage::Forfeited => "customs_destroyed",
+            CounterfeitStage::Released => "customs_released",
+            _ => continue,
+        };
+        if let Some(line) = dialogue_db.get_for_trigger(trigger) {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry launches the seasonal specials with more enthusiasm than they deserve
+fn react_to_editions(
+    mut edition_events: MessageReader<EditionEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in edition_events.read() {
+        let trigger = match (event.stage, event.edition) {
+            (EditionStage::Launched, Edition::PumpkinSpice) => "edition_pumpkin_spice",
+            (EditionStage::Launched, Edition::Love) => "edition_love",
+            (EditionStage::SellingOut, _) => "edition_selling_out",
+            (EditionStage::Leftover(_), _) => "edition_leftover",
+            _ => continue,
+        };
+        if let Some(line) = dialogue_db.get_for_trigger(trigger) {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry is first in line for every fad, and first to feel the whiplash
+fn react_to_fads(
+    mut fad_events: MessageReader<FadEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in fad_events.read() {
+        let trigger = match event.stage {
+            FadStage::Started => event.fad.trigger(),
+            FadStage::Whiplash(_) => "fad_whiplash",
+            FadStage::Faded => "fad_over",
+            FadStage::Chased => continue,
+        };
+        if let Some(line) = dialogue_db.get_for_trigger(trigger) {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry has opinions about the news, keyed by the event's ID
+fn react_to_headlines(
+    mut headline_events: MessageReader<HeadlineEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in headline_events.read() {
+        if let Some(line) = dialogue_db.get_for_trigger(&format!("world_{}", event.headline.id)) {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry marks the holidays on the game's calendar
+fn react_to_holidays(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in day_events.read() {
+        let trigger = match event.holiday {
+            Some(Holiday::ValentinesDay) => "holiday_valentines",
+            Some(Holiday::IndependenceDay) => "holiday_independence",
+            Some(Holiday::Halloween) => "holiday_halloween",
+            Some(Holiday::Thanksgiving) => "holiday_thanksgiving",
+            Some(Holiday::BlackFriday) => "holiday_black_friday",
+            Some(Holiday::Christmas) => "holiday_christmas",
+            Some(_) => "holiday",
+            None => continue,
+        };
+
+        if let Some(line) = dialogue_db.get_for_trigger(trigger) {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry narrates the Thing's story: each step's own line, then a nudge when it's ready
+fn react_to_quests(
+    mut quest_events: MessageReader<QuestEvent>,
+    book: Res<QuestBook>,
+    quest_log: Res<QuestLog>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in quest_events.read() {
+        match event.stage {
+            QuestStage::StepStarted => {
+                let step = quest_log.thing_type.and_then(|thing_type| book.step(thing_type, &event.step_id));
+                if let Some((step, text)) = step.and_then(|step| Some((step, step.terry.as_ref()?))) {
+                    terry_state.say(&DialogueLine {
+                        id: format!("quest_{}", step.id),
+                        trigger: "quest_step".to_string(),
+                        text: text.clone(),
+                        mood: "neutral".to_string(),
+                        ..default()
+                    });
+                }
+            }
+            QuestStage::ObjectiveMet | QuestStage::ChainFinished => {
+                let trigger = if event.stage == QuestStage::ObjectiveMet { "quest_ready" } else { "quest_finished" };
+                if let Some(line) = dialogue_db.get_for_trigger(trigger) {
+                    terry_state.say(line);
+                }
+            }
+        }
+    }
+}
+
+/// Terry keeps an eye on the other universe's scoreboard
+fn react_to_parallel_universe(
+    mut lead_events: MessageReader<UniverseLeadEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in lead_events.read() {
+        let trigger = if event.player_ahead { "parallel_ahead" } else { "parallel_behind" };
+        if let Some(line) = dialogue_db.get_for_trigger(trigger) {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry presents the lab's latest breakthrough
+fn react_to_research(
+    mut completed_events: MessageReader<ResearchCompletedEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in completed_events.read() {
+        let trigger = match event.tech {
+            Tech::Automation => "research_automation",
+            Tech::Packaging => "research_packaging",
+        };
+
+        if let Some(line) = dialogue_db.get_for_trigger(trigger) {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry has opinions about how the player shops
+fn react_to_spending_patterns(
+    mut pattern_events: MessageReader<SpendingPatternEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in pattern_events.read() {
+        if let Some(line) = dialogue_db.get_for_trigger(event.pattern.trigger()) {
+            terry_state.say(line);
+        }
+    }
+}
+
+/// Terry opens the argument with Doug, and takes the verdict about as well as you'd expect
+fn react_to_debates(
+    mut debate_events: MessageReader<DebateEvent>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for event in debate_events.read() {
+        let trigger = match event.stage {
+            DebateStage::Started => "debate_started",
+            DebateStage::Settled(Advisor::Terry) => "debate_won",
+            DebateStage::Settled(Advisor::Doug) => "debate_lost",
+            DebateStage::BlewOver => "debate_ignored",
+        };
+        if let Some(line) = dialogue_db.get_for_trigger(trigger) {
+            terry_state.say(line);
+        }
+    }
+}
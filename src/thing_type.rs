@@ -1,99 +1,537 @@
-//! The four types of Things you can sell
+//! The types of Things you can sell
+//!
+//! Each type is a `ThingDefinition`: its prices, multipliers, colour and the
+//! prefix its dialogue triggers use. The four originals are built in; more
+//! can be dropped into `assets/things/` as JSON (`{ "things": [ ... ] }`, one
+//! object per type, same fields as `ThingDefinition`) and are picked up when
+//! the app starts. Gameplay that only some Things get (bundles, scandals,
+//! the redemption arc) goes by a definition's `tier` and `shady`, never by
+//! which type it is, so a loaded or made-up Thing gets it too. They're kept in the `ThingRegistry` resource; `ThingType`
+//! is a small copyable handle into it, so the rest of the game passes that
+//! around and looks the numbers up with `things[thing_type]`.
+//!
+//! One more type can be made up on the selection screen: `ThingTraits` are
+//! the builder's sliders, and `ThingDefinition::from_traits` blends the
+//! built-in numbers to match. It goes in the registry's custom slot, replacing
+//! whichever was made up before.
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::ops::Index;
+use std::path::Path;
 
-/// The type of Thing the player is selling
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
-pub enum ThingType {
-    /// High volume, low margins, mass market appeal
+const THING_DIR: &str = "assets/things";
+
+/// Everything the game needs to know about a type of Thing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThingDefinition {
+    pub id: String,
+    pub name: String,
+    /// Description for selection screen
+    pub description: String,
+    /// Base price per Thing
+    pub base_price: f64,
+    /// Production speed multiplier
+    pub production_multiplier: f64,
+    /// Customer volume multiplier (how many customers come)
+    pub customer_multiplier: f64,
+    /// Reputation change per sale
+    pub reputation_per_sale: f32,
+    /// Passive reputation decay per second
+    pub reputation_decay: f32,
+    /// Environmental impact per Thing made
+    pub eco_impact: f32,
+    /// Share of sold Things that come back (before satisfaction and refund policy)
+    pub return_rate: f64,
+    /// How much each point of inflation pressure hurts its buyers
+    pub inflation_sensitivity: f64,
+    /// How its buyers react to unemployment (positive means they trade down to it)
+    pub unemployment_sensitivity: f64,
+    /// Color for UI
+    pub color: [f32; 3],
+    /// Prefix on its dialogue triggers (`select_<prefix>`, `<prefix>_playing`, `pivot_to_<prefix>`)
+    /// and its dialogue file (`assets/dialogues/terry_<prefix>.json`)
+    pub dialogue_prefix: String,
+    /// Which end of the market it sells to (bundles, the pandemic mask rush)
+    pub tier: PriceTier,
+    /// A scam: it can have scandals, a redemption arc and its own coin, and
+    /// marketing doesn't lift its reputation
+    pub shady: bool,
+}
+
+/// Which end of the market a Thing sells to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceTier {
+    Budget,
     #[default]
-    Cheap,
-    /// Quality focus, builds reputation slowly, loyal customers
-    Good,
-    /// Luxury positioning, low volume, heavy marketing needed
-    Expensive,
-    /// Scam mode, quick cash, reputation crashes
-    Bad,
+    Mid,
+    Luxury,
 }
 
-impl ThingType {
-    /// Base price per Thing
-    pub fn base_price(&self) -> f64 {
-        match self {
-            ThingType::Cheap => 0.50,
-            ThingType::Good => 5.00,
-            ThingType::Expensive => 50.00,
-            ThingType::Bad => 10.00,
+impl ThingDefinition {
+    /// Color for UI
+    pub fn color(&self) -> Color {
+        let [r, g, b] = self.color;
+        Color::srgb(r, g, b)
+    }
+}
+
+/// The original four, in `ThingType` order
+fn built_in() -> Vec<ThingDefinition> {
+    vec![
+        ThingDefinition {
+            id: "cheap".into(),
+            name: "Cheap".into(),
+            description: "High volume, low margins. The people's Thing.".into(),
+            base_price: 0.50,
+            production_multiplier: 2.0,
+            customer_multiplier: 2.0,    // Lots of customers
+            reputation_per_sale: 0.001,  // Slow reputation gain
+            reputation_decay: 0.0,
+            eco_impact: 1.5,             // Disposable plastic everything
+            return_rate: 0.06,           // Falls apart in the car park
+            inflation_sensitivity: 4.0,  // Every cent counts
+            unemployment_sensitivity: 2.0,
+            color: [0.2, 0.7, 0.3],      // Green
+            dialogue_prefix: "cheap".into(),
+            tier: PriceTier::Budget,
+            shady: false,
+        },
+        ThingDefinition {
+            id: "good".into(),
+            name: "Good".into(),
+            description: "Quality craftsmanship. Slow and steady wins the race.".into(),
+            base_price: 5.00,
+            production_multiplier: 1.0,
+            customer_multiplier: 1.0,    // Normal flow
+            reputation_per_sale: 0.01,   // Good reputation gain
+            reputation_decay: 0.0,
+            eco_impact: 0.6,             // Built to last
+            return_rate: 0.02,           // Rarely comes back
+            inflation_sensitivity: 2.5,
+            unemployment_sensitivity: -0.5,
+            color: [0.3, 0.5, 0.9],      // Blue
+            dialogue_prefix: "good".into(),
+            tier: PriceTier::Mid,
+            shady: false,
+        },
+        ThingDefinition {
+            id: "expensive".into(),
+            name: "Expensive".into(),
+            description: "Luxury positioning. For the discerning Thing enthusiast.".into(),
+            base_price: 50.00,
+            production_multiplier: 0.5,
+            customer_multiplier: 0.3,    // Few but wealthy
+            reputation_per_sale: 0.005,  // Medium reputation
+            reputation_decay: 0.0,
+            eco_impact: 1.0,             // Lots of packaging, fewer units
+            return_rate: 0.04,           // Buyer's remorse
+            inflation_sensitivity: 0.5,  // Barely noticed
+            unemployment_sensitivity: -3.0,
+            color: [0.8, 0.6, 0.1],      // Gold
+            dialogue_prefix: "expensive".into(),
+            tier: PriceTier::Luxury,
+            shady: false,
+        },
+        ThingDefinition {
+            id: "bad".into(),
+            name: "Bad".into(),
+            description: "Quick cash. What could possibly go wrong?".into(),
+            base_price: 10.00,
+            production_multiplier: 1.5,
+            customer_multiplier: 1.5,    // Starts high, will crash
+            reputation_per_sale: -0.02,  // Reputation LOSS
+            reputation_decay: 0.005,     // Bad Things cause passive decay
+            eco_impact: 2.0,             // Straight to landfill
+            return_rate: 0.15,           // Does not do the Thing
+            inflation_sensitivity: 3.0,
+            unemployment_sensitivity: 1.0,
+            color: [0.8, 0.2, 0.2],      // Red
+            dialogue_prefix: "bad".into(),
+            tier: PriceTier::Mid,
+            shady: true,
+        },
+    ]
+}
+
+/// A Thing type as written in a file under `assets/things/`
+#[derive(Debug, Clone, Deserialize)]
+struct ThingDefinitionFile {
+    things: Vec<ThingDefinitionEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ThingDefinitionEntry {
+    id: String,
+    name: String,
+    #[serde(default)]
+    description: String,
+    base_price: f64,
+    production_multiplier: f64,
+    customer_multiplier: f64,
+    reputation_per_sale: f32,
+    #[serde(default)]
+    reputation_decay: f32,
+    eco_impact: f32,
+    return_rate: f64,
+    #[serde(default = "default_inflation_sensitivity")]
+    inflation_sensitivity: f64,
+    #[serde(default)]
+    unemployment_sensitivity: f64,
+    #[serde(default = "default_color")]
+    color: [f32; 3],
+    #[serde(default)]
+    dialogue_prefix: Option<String>,
+    #[serde(default)]
+    tier: PriceTier,
+    #[serde(default)]
+    shady: bool,
+}
+
+fn default_inflation_sensitivity() -> f64 {
+    2.5
+}
+
+fn default_color() -> [f32; 3] {
+    [0.6, 0.6, 0.6]
+}
+
+impl ThingDefinitionEntry {
+    fn into_definition(self) -> ThingDefinition {
+        let dialogue_prefix = self.dialogue_prefix.unwrap_or_else(|| self.id.clone());
+        ThingDefinition {
+            id: self.id,
+            name: self.name,
+            description: self.description,
+            base_price: self.base_price,
+            production_multiplier: self.production_multiplier,
+            customer_multiplier: self.customer_multiplier,
+            reputation_per_sale: self.reputation_per_sale,
+            reputation_decay: self.reputation_decay,
+            eco_impact: self.eco_impact,
+            return_rate: self.return_rate,
+            inflation_sensitivity: self.inflation_sensitivity,
+            unemployment_sensitivity: self.unemployment_sensitivity,
+            color: self.color,
+            dialogue_prefix,
+            tier: self.tier,
+            shady: self.shady,
         }
     }
+}
 
-    /// Production speed multiplier
-    pub fn production_multiplier(&self) -> f64 {
-        match self {
-            ThingType::Cheap => 2.0,
-            ThingType::Good => 1.0,
-            ThingType::Expensive => 0.5,
-            ThingType::Bad => 1.5,
+/// Steps on each builder slider
+pub const TRAIT_LEVELS: u8 = 5;
+
+/// Where a made-up Thing sits on the builder's sliders (0 to `TRAIT_LEVELS - 1`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThingTraits {
+    pub price_point: u8,
+    pub quality: u8,
+    pub sketchiness: u8,
+}
+
+impl Default for ThingTraits {
+    fn default() -> Self {
+        Self {
+            price_point: TRAIT_LEVELS / 2,
+            quality: TRAIT_LEVELS / 2,
+            sketchiness: 0,
         }
     }
+}
 
-    /// Customer volume multiplier (how many customers come)
-    pub fn customer_multiplier(&self) -> f64 {
+/// One of the builder's sliders
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trait {
+    PricePoint,
+    Quality,
+    Sketchiness,
+}
+
+impl Trait {
+    pub const ALL: [Trait; 3] = [Trait::PricePoint, Trait::Quality, Trait::Sketchiness];
+
+    pub fn name(&self) -> &'static str {
         match self {
-            ThingType::Cheap => 2.0,      // Lots of customers
-            ThingType::Good => 1.0,       // Normal flow
-            ThingType::Expensive => 0.3,  // Few but wealthy
-            ThingType::Bad => 1.5,        // Starts high, will crash
+            Trait::PricePoint => "Price point",
+            Trait::Quality => "Quality",
+            Trait::Sketchiness => "Sketchiness",
         }
     }
 
-    /// Reputation change per sale
-    pub fn reputation_per_sale(&self) -> f32 {
-        match self {
-            ThingType::Cheap => 0.001,     // Slow reputation gain
-            ThingType::Good => 0.01,       // Good reputation gain
-            ThingType::Expensive => 0.005, // Medium reputation
-            ThingType::Bad => -0.02,       // Reputation LOSS
+    /// What each step is called, low to high
+    pub fn level_name(&self, level: u8) -> &'static str {
+        let names = match self {
+            Trait::PricePoint => ["Bargain bin", "Affordable", "Mid-range", "Premium", "Obscene"],
+            Trait::Quality => ["Junk", "Flimsy", "Decent", "Solid", "Heirloom"],
+            Trait::Sketchiness => ["Squeaky clean", "Mostly legal", "Grey area", "Questionable", "Don't ask"],
+        };
+        names[(level as usize).min(names.len() - 1)]
+    }
+}
+
+impl ThingTraits {
+    pub fn get(&self, which: Trait) -> u8 {
+        match which {
+            Trait::PricePoint => self.price_point,
+            Trait::Quality => self.quality,
+            Trait::Sketchiness => self.sketchiness,
         }
     }
 
-    /// Passive reputation decay per second
-    pub fn reputation_decay(&self) -> f32 {
-        match self {
-            ThingType::Cheap => 0.0,
-            ThingType::Good => 0.0,
-            ThingType::Expensive => 0.0,
-            ThingType::Bad => 0.005, // Bad Things cause passive decay
+    pub fn set(&mut self, which: Trait, level: u8) {
+        let level = level.min(TRAIT_LEVELS - 1);
+        match which {
+            Trait::PricePoint => self.price_point = level,
+            Trait::Quality => self.quality = level,
+            Trait::Sketchiness => self.sketchiness = level,
         }
     }
 
-    /// Display name
-    pub fn name(&self) -> &'static str {
-        match self {
-            ThingType::Cheap => "Cheap",
-            ThingType::Good => "Good",
-            ThingType::Expensive => "Expensive",
-            ThingType::Bad => "Bad",
+    /// A slider's position from 0.0 to 1.0
+    pub fn fraction(&self, which: Trait) -> f64 {
+        self.get(which) as f64 / (TRAIT_LEVELS - 1) as f64
+    }
+}
+
+impl ThingDefinition {
+    /// A made-up Thing: price point slides it from Cheap to Expensive, quality
+    /// pulls it towards Good and sketchiness towards Bad
+    pub fn from_traits(name: &str, traits: ThingTraits) -> ThingDefinition {
+        let lerp = |from: f64, to: f64, t: f64| from + (to - from) * t;
+        let price = traits.fraction(Trait::PricePoint);
+        let quality = traits.fraction(Trait::Quality);
+        let sketch = traits.fraction(Trait::Sketchiness);
+
+        // Lean on whichever built-in's dialogue fits best
+        let dialogue_prefix = if sketch >= 0.5 {
+            "bad"
+        } else if price >= 0.75 {
+            "expensive"
+        } else if quality >= 0.5 {
+            "good"
+        } else {
+            "cheap"
+        };
+
+        ThingDefinition {
+            id: format!("custom_{}", name.to_lowercase()),
+            name: name.to_string(),
+            description: "Your own idea. Terry has concerns.".into(),
+            base_price: 0.5 * 100f64.powf(price) * (1.0 + 0.5 * quality),
+            production_multiplier: lerp(2.0, 0.5, price) * (1.0 + 0.3 * sketch) / (1.0 + 0.3 * quality),
+            customer_multiplier: lerp(2.0, 0.3, price) * (1.0 + 0.5 * sketch),
+            reputation_per_sale: (0.001 + 0.009 * quality - 0.03 * sketch) as f32,
+            reputation_decay: (0.005 * sketch) as f32,
+            eco_impact: (lerp(1.5, 0.6, quality) + sketch) as f32,
+            return_rate: 0.02 + 0.06 * (1.0 - quality) + 0.1 * sketch,
+            inflation_sensitivity: lerp(4.0, 0.5, price),
+            unemployment_sensitivity: lerp(2.0, -3.0, price),
+            color: [
+                lerp(0.2, 0.8, price.max(sketch)) as f32,
+                lerp(0.7, 0.3, price.max(sketch)) as f32,
+                lerp(0.3, 0.1, price) as f32,
+            ],
+            dialogue_prefix: dialogue_prefix.into(),
+            tier: if price < 0.25 {
+                PriceTier::Budget
+            } else if price >= 0.75 {
+                PriceTier::Luxury
+            } else {
+                PriceTier::Mid
+            },
+            shady: sketch >= 0.5,
         }
     }
+}
 
-    /// Description for selection screen
-    pub fn description(&self) -> &'static str {
-        match self {
-            ThingType::Cheap => "High volume, low margins. The people's Thing.",
-            ThingType::Good => "Quality craftsmanship. Slow and steady wins the race.",
-            ThingType::Expensive => "Luxury positioning. For the discerning Thing enthusiast.",
-            ThingType::Bad => "Quick cash. What could possibly go wrong?",
+/// Every Thing type: the built-in four, then any loaded from `assets/things/`.
+/// The default is just the built-in four.
+#[derive(Resource, Debug, Clone)]
+pub struct ThingRegistry {
+    definitions: Vec<ThingDefinition>,
+    /// The Thing made up on the selection screen, if there is one
+    custom: Option<ThingDefinition>,
+}
+
+impl Default for ThingRegistry {
+    fn default() -> Self {
+        Self {
+            definitions: built_in(),
+            custom: None,
         }
     }
+}
 
-    /// Color for UI
-    pub fn color(&self) -> Color {
-        match self {
-            ThingType::Cheap => Color::srgb(0.2, 0.7, 0.3),      // Green
-            ThingType::Good => Color::srgb(0.3, 0.5, 0.9),       // Blue
-            ThingType::Expensive => Color::srgb(0.8, 0.6, 0.1),  // Gold
-            ThingType::Bad => Color::srgb(0.8, 0.2, 0.2),        // Red
+impl ThingRegistry {
+    /// The built-in four plus the extra types in `assets/things/`; anything
+    /// that fails to parse or reuses an id is skipped
+    pub fn load() -> Self {
+        let mut registry = Self::default();
+        let Ok(entries) = fs::read_dir(Path::new(THING_DIR)) else {
+            return registry;
+        };
+
+        let mut paths: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+            .collect();
+        paths.sort();
+        for path in paths {
+            let file = match fs::read_to_string(&path) {
+                Ok(contents) => match serde_json::from_str::<ThingDefinitionFile>(&contents) {
+                    Ok(file) => file,
+                    Err(e) => {
+                        warn!("Failed to parse Thing definitions {}: {}", path.display(), e);
+                        continue;
+                    }
+                },
+                Err(e) => {
+                    warn!("Failed to read Thing definitions {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+            for entry in file.things {
+                let id = entry.id.clone();
+                if registry.insert(entry.into_definition()).is_none() {
+                    warn!("Skipping Thing type '{}' in {}: the id is taken", id, path.display());
+                }
+            }
+            info!("Loaded Thing definitions: {}", path.display());
         }
+        registry
+    }
+
+    /// Add a Thing type after the others, unless its id is taken
+    pub fn insert(&mut self, definition: ThingDefinition) -> Option<ThingType> {
+        if self.find(&definition.id).is_some() {
+            return None;
+        }
+        self.definitions.push(definition);
+        Some(ThingType(self.definitions.len() as u16 - 1))
+    }
+
+    /// Every Thing type, built-in first
+    pub fn all(&self) -> impl Iterator<Item = ThingType> {
+        (0..self.definitions.len()).map(|index| ThingType(index as u16))
+    }
+
+    pub fn find(&self, id: &str) -> Option<ThingType> {
+        if self.custom.as_ref().is_some_and(|definition| definition.id == id) {
+            return Some(ThingType::CUSTOM);
+        }
+        self.definitions
+            .iter()
+            .position(|definition| definition.id == id)
+            .map(|index| ThingType(index as u16))
+    }
+
+    /// Is `thing_type` a scam? (Nothing chosen yet isn't)
+    pub fn is_shady(&self, thing_type: Option<ThingType>) -> bool {
+        thing_type.is_some_and(|thing_type| self[thing_type].shady)
+    }
+
+    /// The made-up Thing, if there is one
+    pub fn custom(&self) -> Option<&ThingDefinition> {
+        self.custom.as_ref()
+    }
+
+    /// Make `definition` the custom Thing (replacing any before it) and hand back its handle
+    pub fn set_custom(&mut self, definition: ThingDefinition) -> ThingType {
+        self.custom = Some(definition);
+        ThingType::CUSTOM
+    }
+}
+
+/// The custom Thing falls back to Good until one has been made
+impl Index<ThingType> for ThingRegistry {
+    type Output = ThingDefinition;
+
+    fn index(&self, thing_type: ThingType) -> &ThingDefinition {
+        if thing_type.is_custom() {
+            return self.custom.as_ref().unwrap_or(&self.definitions[ThingType::GOOD.index()]);
+        }
+        &self.definitions[thing_type.index()]
+    }
+}
+
+/// Loads the Thing type registry up front, so a broken mod file shows up in
+/// the log at startup rather than whenever a Thing is first looked at
+pub struct ThingTypePlugin;
+
+impl Plugin for ThingTypePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ThingRegistry::load());
+    }
+}
+
+/// The type of Thing the player is selling
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub struct ThingType(u16);
+
+impl Default for ThingType {
+    fn default() -> Self {
+        ThingType::CHEAP
+    }
+}
+
+impl ThingType {
+    /// High volume, low margins, mass market appeal
+    pub const CHEAP: ThingType = ThingType(0);
+    /// Quality focus, builds reputation slowly, loyal customers
+    pub const GOOD: ThingType = ThingType(1);
+    /// Luxury positioning, low volume, heavy marketing needed
+    pub const EXPENSIVE: ThingType = ThingType(2);
+    /// Scam mode, quick cash, reputation crashes
+    pub const BAD: ThingType = ThingType(3);
+    /// Whatever the player made up (not part of `ThingRegistry::all()`)
+    pub const CUSTOM: ThingType = ThingType(u16::MAX);
+    /// The original four, which every registry starts with
+    pub const BUILT_IN: [ThingType; 4] = [ThingType::CHEAP, ThingType::GOOD, ThingType::EXPENSIVE, ThingType::BAD];
+
+    pub fn is_custom(&self) -> bool {
+        *self == ThingType::CUSTOM
+    }
+
+    /// Position in the registry (stable for the built-in types)
+    pub fn index(&self) -> usize {
+        self.0 as usize
+    }
+
+    /// One of the original four, rather than loaded from a file
+    pub fn is_built_in(&self) -> bool {
+        self.index() < ThingType::BUILT_IN.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn made_up_and_modded_things_can_be_shady() {
+        let sketchy = |sketchiness| ThingTraits {
+            sketchiness,
+            ..ThingTraits::default()
+        };
+        assert!(ThingDefinition::from_traits("Gizmo", sketchy(TRAIT_LEVELS - 1)).shady);
+        assert!(!ThingDefinition::from_traits("Gizmo", sketchy(0)).shady);
+
+        let file: ThingDefinitionFile = serde_json::from_str(
+            r#"{ "things": [{ "id": "pyramid", "name": "Pyramid", "base_price": 20.0, "production_multiplier": 1.0,
+                "customer_multiplier": 1.0, "reputation_per_sale": -0.01, "eco_impact": 1.0, "return_rate": 0.1,
+                "tier": "luxury", "shady": true }] }"#,
+        )
+        .unwrap();
+        let mut things = ThingRegistry::default();
+        let pyramid = things.insert(file.things[0].clone().into_definition()).unwrap();
+        assert!(things.is_shady(Some(pyramid)));
+        assert_eq!(things[pyramid].tier, PriceTier::Luxury);
+        assert!(things.is_shady(Some(ThingType::BAD)));
+        assert!(!things.is_shady(Some(ThingType::GOOD)));
+        assert!(!things.is_shady(None));
     }
 }
@@ -0,0 +1,172 @@
+//! First times - Terry walks the player through things the first time they happen
+//!
+//! The first upgrade, the first influencer deal, the first scandal, the
+//! first Christmas and the first full year each get a one-shot Terry line and
+//! (unless switched off in the settings) a short explainer card. What's been seen is saved with the
+//! player's profile, so the tutorial only ever plays once per player.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::SystemParam;
+use serde::{Deserialize, Serialize};
+use crate::attract::attract_running;
+use crate::business::{UpgradePurchasedEvent, UpgradeType};
+use crate::economy::DayAdvancedEvent;
+use crate::game_state::AppState;
+use crate::marketing::MarketingState;
+use crate::recovery::{RecoveryEvent, RecoveryStage};
+use crate::reputation::{ReputationTier, ReputationTierChangedEvent};
+use crate::wrapped::YearWrappedEvent;
+
+/// Things that get explained the first time they happen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FirstTime {
+    Upgrade,
+    InfluencerDeal,
+    Scandal,
+    Christmas,
+    /// The first year of a run wrapped up
+    FullYear,
+}
+
+impl FirstTime {
+    /// Terry's one-shot line
+    pub fn trigger(&self) -> &'static str {
+        match self {
+            FirstTime::Upgrade => "first_upgrade",
+            FirstTime::InfluencerDeal => "first_influencer_deal",
+            FirstTime::Scandal => "first_scandal",
+            FirstTime::Christmas => "first_christmas",
+            FirstTime::FullYear => "first_full_year",
+        }
+    }
+
+    /// Explainer card title
+    pub fn title(&self) -> &'static str {
+        match self {
+            FirstTime::Upgrade => "Your first upgrade",
+            FirstTime::InfluencerDeal => "Your first influencer deal",
+            FirstTime::Scandal => "Your first scandal",
+            FirstTime::Christmas => "Your first Christmas",
+            FirstTime::FullYear => "Your first full year",
+        }
+    }
+
+    /// Explainer card body
+    pub fn explainer(&self) -> &'static str {
+        match self {
+            FirstTime::Upgrade => {
+                "Upgrades cost more each time you buy them. Production upgrades make more Things; \
+                 marketing upgrades bring in more customers to buy them. You need both."
+            }
+            FirstTime::InfluencerDeal => {
+                "Influencers bring customers while the deal lasts, but their followers notice when \
+                 a post feels fake. Authentic influencers convert better than big ones."
+            }
+            FirstTime::Scandal => {
+                "Your reputation has hit the floor. Demand drops and partners walk away. The \
+                 Redemption overlay has ways back, none of them cheap or guaranteed."
+            }
+            FirstTime::Christmas => {
+                "Demand climbs through December and collapses on the day itself. Next year, stock \
+                 up and spend on marketing before the rush, not during it."
+            }
+            FirstTime::FullYear => {
+                "A whole year survived. Terry has written up how the economy really works, \
+                 formulas and all: \"Terry Explains the Economy\" is in the header from now on."
+            }
+        }
+    }
+}
+
+/// First times the player has already seen (saved with the profile)
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FirstTimes {
+    pub seen: Vec<FirstTime>,
+}
+
+impl FirstTimes {
+    pub fn has_seen(&self, first: FirstTime) -> bool {
+        self.seen.contains(&first)
+    }
+}
+
+/// Message fired the first time something happens
+#[derive(Event, Message, Clone)]
+pub struct FirstTimeEvent {
+    pub first: FirstTime,
+}
+
+pub struct TutorialPlugin;
+
+impl Plugin for TutorialPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FirstTimes>()
+            .add_message::<FirstTimeEvent>()
+            .add_systems(
+                Update,
+                detect_first_times
+                    .run_if(in_state(AppState::Playing))
+                    .run_if(not(attract_running)),
+            );
+    }
+}
+
+/// Everything that can be a first time
+#[derive(SystemParam)]
+struct FirstTimeSources<'w, 's> {
+    upgrades: MessageReader<'w, 's, UpgradePurchasedEvent>,
+    recovery: MessageReader<'w, 's, RecoveryEvent>,
+    tiers: MessageReader<'w, 's, ReputationTierChangedEvent>,
+    days: MessageReader<'w, 's, DayAdvancedEvent>,
+    years: MessageReader<'w, 's, YearWrappedEvent>,
+    marketing: Res<'w, MarketingState>,
+}
+
+/// Spot first times and record them
+fn detect_first_times(
+    mut sources: FirstTimeSources,
+    mut first_times: ResMut<FirstTimes>,
+    mut first_events: MessageWriter<FirstTimeEvent>,
+) {
+    let mut happened = Vec::new();
+
+    for event in sources.upgrades.read() {
+        happened.push(FirstTime::Upgrade);
+        if event.upgrade == UpgradeType::InfluencerDeal {
+            happened.push(FirstTime::InfluencerDeal);
+        }
+    }
+    let marketing = &sources.marketing;
+    if marketing.micro_influencers.active || marketing.mid_influencers.active || marketing.celebrity_endorsement.active {
+        happened.push(FirstTime::InfluencerDeal);
+    }
+    for event in sources.recovery.read() {
+        if event.stage == RecoveryStage::Unlocked {
+            happened.push(FirstTime::Scandal);
+        }
+    }
+    for event in sources.tiers.read() {
+        if event.new_tier == ReputationTier::Pariah {
+            happened.push(FirstTime::Scandal);
+        }
+    }
+    for event in sources.days.read() {
+        if event.date.month() == 12 && event.date.day() == 25 {
+            happened.push(FirstTime::Christmas);
+        }
+    }
+    if sources.years.read().count() > 0 {
+        happened.push(FirstTime::FullYear);
+    }
+
+    for first in happened {
+        // Check before touching the resource, so the profile is only saved when something's new
+        if first_times.has_seen(first) {
+            continue;
+        }
+        first_times.seen.push(first);
+        first_events.write(FirstTimeEvent { first });
+    }
+}
@@ -0,0 +1,139 @@
+//! AI section of the upgrades panel - the AI-powered upgrades, once 2023 arrives
+
+use bevy::prelude::*;
+use crate::ai_boom::{AiState, AiUpgrade, BuyAiUpgradeEvent};
+use crate::currency::money_whole;
+use crate::game_state::GameState;
+use super::{NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+/// Container for the section (hidden before the AI boom)
+#[derive(Component)]
+pub struct AiSection;
+
+/// Button that buys an AI upgrade
+#[derive(Component)]
+pub struct AiUpgradeButton(pub AiUpgrade);
+
+/// Status line under an AI upgrade's name
+#[derive(Component)]
+pub struct AiUpgradeStatus(pub AiUpgrade);
+
+/// AI section (goes at the top of the upgrades panel, under the pandemic)
+pub fn spawn_ai_section(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(8.0),
+                margin: UiRect::bottom(Val::Px(20.0)),
+                display: Display::None,
+                ..default()
+            },
+            AiSection,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("AI-POWERED"),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.5, 1.0)),
+            ));
+
+            for upgrade in AiUpgrade::ALL {
+                parent
+                    .spawn((
+                        Button,
+                        Node {
+                            width: Val::Percent(100.0),
+                            flex_direction: FlexDirection::Column,
+                            padding: UiRect::all(Val::Px(10.0)),
+                            border: UiRect::all(Val::Px(2.0)),
+                            ..default()
+                        },
+                        BorderColor::all(Color::srgb(0.5, 0.3, 0.8)),
+                        BackgroundColor(NORMAL_BUTTON),
+                        AiUpgradeButton(upgrade),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Text::new(upgrade.name()),
+                            TextFont {
+                                font_size: 16.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                        ));
+                        parent.spawn((
+                            Text::new(upgrade.description()),
+                            TextFont {
+                                font_size: 12.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                        ));
+                        parent.spawn((
+                            Text::new(""),
+                            TextFont {
+                                font_size: 12.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(0.7, 0.6, 0.9)),
+                            AiUpgradeStatus(upgrade),
+                        ));
+                    });
+            }
+        });
+}
+
+/// Buy an AI upgrade
+pub fn handle_ai_upgrade_buttons(
+    mut interaction_query: Query<(&Interaction, &AiUpgradeButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut buy_events: MessageWriter<BuyAiUpgradeEvent>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                buy_events.write(BuyAiUpgradeEvent { upgrade: button.0 });
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+/// Show the section once the boom arrives, and what each tool is up to
+pub fn update_ai_section(
+    ai: Res<AiState>,
+    game_state: Res<GameState>,
+    mut section_query: Query<&mut Node, With<AiSection>>,
+    mut status_query: Query<(&AiUpgradeStatus, &mut Text)>,
+) {
+    if !ai.is_changed() && !game_state.is_changed() {
+        return;
+    }
+
+    for mut node in &mut section_query {
+        node.display = if ai.available { Display::Flex } else { Display::None };
+    }
+    if !ai.available {
+        return;
+    }
+
+    for (status, mut text) in &mut status_query {
+        let upgrade = status.0;
+        let tool = ai.tool(upgrade);
+        **text = if tool.offline_days > 0 {
+            format!("Offline after an incident - back in {} days", tool.offline_days)
+        } else if tool.owned {
+            "Online".to_string()
+        } else if ai.can_buy(upgrade, &game_state) {
+            money_whole(upgrade.cost())
+        } else {
+            format!("{} (can't afford)", money_whole(upgrade.cost()))
+        };
+    }
+}
@@ -0,0 +1,489 @@
+//! Reusable UI animations - pulses, flashes, highlights and flying icons
+//!
+//! Each animation is a component that ticks itself and removes itself when done,
+//! so any screen can attach one to a node without extra bookkeeping. Effects that
+//! are their own entity (flying icons, floating numbers) live in a [`UiPool`].
+//! In low power animations finish on their next tick and pooled effects aren't
+//! spawned at all.
+
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use crate::business::{UpgradePurchasedEvent, UpgradeType};
+use crate::game_state::{GameState, ThingProducedEvent};
+use crate::power::PowerMode;
+use crate::rewards::{Reward, RewardEvent};
+use super::{
+    MainScreen, MakeThingButton, UpgradeButton, ClickPowerText, ProductionText, MarketingText, HustleText,
+    ReputationText, UiPool, upgrade_border_color,
+};
+
+/// Flash color for production upgrades
+pub const PRODUCTION_FLASH: Color = Color::srgb(0.4, 0.8, 1.0);
+/// Flash color for marketing upgrades
+pub const MARKETING_FLASH: Color = Color::srgb(1.0, 0.6, 0.2);
+/// Reputation change (either way) worth drawing the eye to
+const REPUTATION_FLASH_STEP: f32 = 0.25;
+/// Flash colors for reputation going up and down (blue and orange stay apart for colorblind players)
+const REPUTATION_UP_FLASH: Color = Color::srgb(0.4, 0.7, 1.0);
+const REPUTATION_DOWN_FLASH: Color = Color::srgb(1.0, 0.5, 0.1);
+
+/// How far to move animations on this frame - all the way, in low power
+fn animation_step(time: &Time, power: &PowerMode) -> f32 {
+    if power.low_power { f32::INFINITY } else { time.delta_secs() }
+}
+
+/// Cubic ease-out (fast start, gentle landing)
+fn ease_out(t: f32) -> f32 {
+    1.0 - (1.0 - t).powi(3)
+}
+
+/// Scale pulse - grows to peak_scale and settles back to normal
+#[derive(Component)]
+pub struct UiPulse {
+    pub elapsed: f32,
+    pub duration: f32,
+    pub peak_scale: f32,
+}
+
+impl UiPulse {
+    pub fn new(duration: f32, peak_scale: f32) -> Self {
+        Self { elapsed: 0.0, duration, peak_scale }
+    }
+}
+
+/// Border flash - jumps to a bright color and fades back to the resting color
+#[derive(Component)]
+pub struct UiFlash {
+    pub elapsed: f32,
+    pub duration: f32,
+    pub color: Color,
+    pub rest: Color,
+}
+
+impl UiFlash {
+    pub fn new(duration: f32, color: Color, rest: Color) -> Self {
+        Self { elapsed: 0.0, duration, color, rest }
+    }
+}
+
+/// Text highlight - tints the text and fades back to its resting color
+#[derive(Component)]
+pub struct TextHighlight {
+    pub elapsed: f32,
+    pub duration: f32,
+    pub color: Color,
+    pub rest: Color,
+}
+
+impl TextHighlight {
+    pub fn new(duration: f32, color: Color, rest: Color) -> Self {
+        Self { elapsed: 0.0, duration, color, rest }
+    }
+}
+
+/// Small icon flying across the screen (logical pixel coordinates), pooled on arrival
+#[derive(Component)]
+pub struct UiFlyTo {
+    pub elapsed: f32,
+    pub duration: f32,
+    pub from: Vec2,
+    pub to: Vec2,
+}
+
+/// Number that drifts upward and fades out (logical pixel coordinates), pooled when gone
+#[derive(Component)]
+pub struct FloatingText {
+    pub elapsed: f32,
+    pub duration: f32,
+    pub from: Vec2,
+    pub rise: f32,
+    pub color: Color,
+}
+
+/// Size of flying icons in logical pixels
+const FLY_ICON_SIZE: f32 = 24.0;
+/// Fresh flying icons allowed per frame
+pub const FLY_ICON_SPAWN_BUDGET: u32 = 8;
+/// Fresh floating numbers allowed per frame
+pub const FLOATING_TEXT_SPAWN_BUDGET: u32 = 4;
+/// Color of the "+N" shown when Things are made by hand
+const CLICK_NUMBER_COLOR: Color = Color::srgb(0.5, 0.9, 0.5);
+/// Color for milestone reward toasts
+const REWARD_TOAST_COLOR: Color = Color::srgb(1.0, 0.85, 0.3);
+
+/// Animate scale pulses
+pub fn animate_pulses(
+    mut commands: Commands,
+    time: Res<Time>,
+    power: Res<PowerMode>,
+    mut query: Query<(Entity, &mut UiPulse, &mut UiTransform)>,
+) {
+    let step = animation_step(&time, &power);
+    for (entity, mut pulse, mut transform) in &mut query {
+        pulse.elapsed += step;
+        let t = (pulse.elapsed / pulse.duration).min(1.0);
+
+        // Up and back down again in one smooth arc
+        let scale = 1.0 + (pulse.peak_scale - 1.0) * (t * std::f32::consts::PI).sin();
+        transform.scale = Vec2::splat(scale);
+
+        if t >= 1.0 {
+            transform.scale = Vec2::ONE;
+            commands.entity(entity).remove::<UiPulse>();
+        }
+    }
+}
+
+/// Animate border flashes
+pub fn animate_flashes(
+    mut commands: Commands,
+    time: Res<Time>,
+    power: Res<PowerMode>,
+    mut query: Query<(Entity, &mut UiFlash, &mut BorderColor)>,
+) {
+    let step = animation_step(&time, &power);
+    for (entity, mut flash, mut border) in &mut query {
+        flash.elapsed += step;
+        let t = (flash.elapsed / flash.duration).min(1.0);
+
+        *border = BorderColor::all(flash.color.mix(&flash.rest, ease_out(t)));
+
+        if t >= 1.0 {
+            *border = BorderColor::all(flash.rest);
+            commands.entity(entity).remove::<UiFlash>();
+        }
+    }
+}
+
+/// Animate text highlights
+pub fn animate_text_highlights(
+    mut commands: Commands,
+    time: Res<Time>,
+    power: Res<PowerMode>,
+    mut query: Query<(Entity, &mut TextHighlight, &mut TextColor)>,
+) {
+    let step = animation_step(&time, &power);
+    for (entity, mut highlight, mut text_color) in &mut query {
+        highlight.elapsed += step;
+        let t = (highlight.elapsed / highlight.duration).min(1.0);
+
+        text_color.0 = highlight.color.mix(&highlight.rest, ease_out(t));
+
+        if t >= 1.0 {
+            text_color.0 = highlight.rest;
+            commands.entity(entity).remove::<TextHighlight>();
+        }
+    }
+}
+
+/// Move flying icons and hand them back to the pool when they land
+pub fn animate_fly_to(
+    mut commands: Commands,
+    time: Res<Time>,
+    power: Res<PowerMode>,
+    mut pool: ResMut<UiPool<UiFlyTo>>,
+    mut query: Query<(Entity, &mut UiFlyTo, &mut Node)>,
+) {
+    let step = animation_step(&time, &power);
+    for (entity, mut fly, mut node) in &mut query {
+        fly.elapsed += step;
+        let t = (fly.elapsed / fly.duration).min(1.0);
+
+        let pos = fly.from.lerp(fly.to, ease_out(t));
+        node.left = Val::Px(pos.x - FLY_ICON_SIZE / 2.0);
+        node.top = Val::Px(pos.y - FLY_ICON_SIZE / 2.0);
+
+        if t >= 1.0 {
+            pool.release(&mut commands, entity);
+        }
+    }
+}
+
+/// Float numbers upward, fade them out and hand them back to the pool
+pub fn animate_floating_texts(
+    mut commands: Commands,
+    time: Res<Time>,
+    power: Res<PowerMode>,
+    mut pool: ResMut<UiPool<FloatingText>>,
+    mut query: Query<(Entity, &mut FloatingText, &mut Node, &mut TextColor)>,
+) {
+    let step = animation_step(&time, &power);
+    for (entity, mut floating, mut node, mut text_color) in &mut query {
+        floating.elapsed += step;
+        let t = (floating.elapsed / floating.duration).min(1.0);
+
+        node.left = Val::Px(floating.from.x);
+        node.top = Val::Px(floating.from.y - floating.rise * ease_out(t));
+        text_color.0 = floating.color.with_alpha(1.0 - t * t);
+
+        if t >= 1.0 {
+            pool.release(&mut commands, entity);
+        }
+    }
+}
+
+/// Logical-pixel center of a laid-out UI node
+fn node_center(transform: &UiGlobalTransform, computed: &ComputedNode) -> Vec2 {
+    transform.translation * computed.inverse_scale_factor()
+}
+
+/// Upgrade buttons, with their on-screen placement
+type UpgradeButtonQuery<'w, 's> =
+    Query<'w, 's, (Entity, &'static UpgradeButton, &'static UiGlobalTransform, &'static ComputedNode)>;
+
+/// Stat texts of one kind, with their on-screen placement and any running highlight
+type StatQuery<'w, 's, T> = Query<
+    'w,
+    's,
+    (Entity, &'static TextColor, &'static UiGlobalTransform, &'static ComputedNode, Option<&'static TextHighlight>),
+    With<T>,
+>;
+
+/// Every stat text an upgrade can highlight
+#[derive(SystemParam)]
+pub struct UpgradeStats<'w, 's> {
+    click: StatQuery<'w, 's, ClickPowerText>,
+    production: StatQuery<'w, 's, ProductionText>,
+    marketing: StatQuery<'w, 's, MarketingText>,
+    hustle: StatQuery<'w, 's, HustleText>,
+    reputation: StatQuery<'w, 's, ReputationText>,
+}
+
+/// Kick off button and stat feedback when an upgrade is bought
+pub fn trigger_upgrade_feedback(
+    mut commands: Commands,
+    mut purchase_events: MessageReader<UpgradePurchasedEvent>,
+    buttons: UpgradeButtonQuery,
+    stats: UpgradeStats,
+    mut pool: ResMut<UiPool<UiFlyTo>>,
+    power: Res<PowerMode>,
+) {
+    if power.low_power {
+        purchase_events.clear();
+        return;
+    }
+    for event in purchase_events.read() {
+        let upgrade = event.upgrade;
+        let flash_color = if upgrade.is_production() { PRODUCTION_FLASH } else { MARKETING_FLASH };
+
+        // Animate the button that was bought (inserting again restarts a running animation)
+        let mut button_center = None;
+        for (entity, button, transform, computed) in &buttons {
+            if button.0 != upgrade {
+                continue;
+            }
+            button_center = Some(node_center(transform, computed));
+            commands.entity(entity).insert((
+                UiPulse::new(0.3, 1.08),
+                UiFlash::new(0.5, flash_color, upgrade_border_color(upgrade)),
+            ));
+        }
+
+        // Highlight the stat the upgrade changed and fly an icon to it
+        match upgrade {
+            UpgradeType::BetterTools => {
+                highlight_stats(&mut commands, &mut pool, &stats.click, button_center, flash_color);
+            }
+            UpgradeType::HireWorker | UpgradeType::Automation => {
+                highlight_stats(&mut commands, &mut pool, &stats.production, button_center, flash_color);
+            }
+            UpgradeType::SocialMedia | UpgradeType::Billboard | UpgradeType::InfluencerDeal => {
+                highlight_stats(&mut commands, &mut pool, &stats.marketing, button_center, flash_color);
+            }
+            UpgradeType::StandingDesk => {
+                highlight_stats(&mut commands, &mut pool, &stats.hustle, button_center, flash_color);
+            }
+            UpgradeType::SolarPanels => {
+                highlight_stats(&mut commands, &mut pool, &stats.reputation, button_center, flash_color);
+            }
+        }
+    }
+}
+
+fn highlight_stats<T: Component>(
+    commands: &mut Commands,
+    pool: &mut UiPool<UiFlyTo>,
+    stats: &StatQuery<T>,
+    fly_from: Option<Vec2>,
+    color: Color,
+) {
+    for (entity, text_color, transform, computed, highlight) in stats {
+        // Keep the original resting color if a highlight is already running
+        let rest = highlight.map(|h| h.rest).unwrap_or(text_color.0);
+        commands.entity(entity).insert(TextHighlight::new(0.8, color, rest));
+
+        if let Some(from) = fly_from {
+            spawn_fly_icon(commands, pool, from, node_center(transform, computed), color);
+        }
+    }
+}
+
+/// Launch a flying icon, unless this frame's spawn budget is already spent
+fn spawn_fly_icon(commands: &mut Commands, pool: &mut UiPool<UiFlyTo>, from: Vec2, to: Vec2, color: Color) {
+    let Some(entity) = pool.acquire(commands) else {
+        return;
+    };
+
+    commands.entity(entity).insert((
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(from.x - FLY_ICON_SIZE / 2.0),
+            top: Val::Px(from.y - FLY_ICON_SIZE / 2.0),
+            width: Val::Px(FLY_ICON_SIZE),
+            height: Val::Px(FLY_ICON_SIZE),
+            ..default()
+        },
+        Text::new("▲"),
+        TextFont {
+            font_size: 18.0,
+            ..default()
+        },
+        TextColor(color),
+        TextLayout::new_with_justify(Justify::Center),
+        GlobalZIndex(100),
+        UiFlyTo {
+            elapsed: 0.0,
+            duration: 0.6,
+            from,
+            to,
+        },
+        MainScreen,
+    ));
+}
+
+/// Pulse and tint the reputation display whenever it's moved a good step from
+/// where it was last flashed, so a slow slide doesn't go unnoticed
+pub fn flash_reputation_changes(
+    mut commands: Commands,
+    game_state: Res<GameState>,
+    mut flashed_at: Local<Option<f32>>,
+    new_display: Query<(), Added<ReputationText>>,
+    stats: Query<(Entity, &TextColor, Option<&TextHighlight>), With<ReputationText>>,
+) {
+    // A fresh main screen starts from wherever reputation is now
+    let from = match *flashed_at {
+        Some(from) if new_display.is_empty() => from,
+        _ => {
+            *flashed_at = Some(game_state.reputation);
+            return;
+        }
+    };
+    let change = game_state.reputation - from;
+    if change.abs() < REPUTATION_FLASH_STEP {
+        return;
+    }
+
+    *flashed_at = Some(game_state.reputation);
+    let color = if change > 0.0 { REPUTATION_UP_FLASH } else { REPUTATION_DOWN_FLASH };
+    for (entity, text_color, highlight) in &stats {
+        let rest = highlight.map(|h| h.rest).unwrap_or(text_color.0);
+        commands.entity(entity).insert((UiPulse::new(0.4, 1.12), TextHighlight::new(1.2, color, rest)));
+    }
+}
+
+/// Float a "+N" above the Make Thing button for every batch made by hand
+pub fn spawn_click_numbers(
+    mut commands: Commands,
+    mut thing_events: MessageReader<ThingProducedEvent>,
+    buttons: Query<(&UiGlobalTransform, &ComputedNode), With<MakeThingButton>>,
+    mut pool: ResMut<UiPool<FloatingText>>,
+    power: Res<PowerMode>,
+) {
+    if power.low_power {
+        thing_events.clear();
+        return;
+    }
+    let Ok((transform, computed)) = buttons.single() else {
+        thing_events.clear();
+        return;
+    };
+    let center = node_center(transform, computed);
+    let top = center.y - computed.size().y * computed.inverse_scale_factor() / 2.0;
+
+    for event in thing_events.read() {
+        if !event.from_click {
+            continue;
+        }
+        let Some(entity) = pool.acquire(&mut commands) else {
+            continue;
+        };
+
+        // Spread the numbers out a little so rapid clicks don't stack
+        let from = Vec2::new(center.x + (rand::random::<f32>() - 0.5) * 80.0 - 10.0, top - 20.0);
+        commands.entity(entity).insert((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(from.x),
+                top: Val::Px(from.y),
+                ..default()
+            },
+            Text::new(format!("+{}", event.amount)),
+            TextFont {
+                font_size: 18.0,
+                ..default()
+            },
+            TextColor(CLICK_NUMBER_COLOR),
+            GlobalZIndex(100),
+            FloatingText {
+                elapsed: 0.0,
+                duration: 0.8,
+                from,
+                rise: 40.0,
+                color: CLICK_NUMBER_COLOR,
+            },
+            MainScreen,
+        ));
+    }
+}
+
+/// Float each milestone reward up from the Make Thing button, slower than a click number
+pub fn spawn_reward_toasts(
+    mut commands: Commands,
+    mut reward_events: MessageReader<RewardEvent>,
+    buttons: Query<(&UiGlobalTransform, &ComputedNode), With<MakeThingButton>>,
+    mut pool: ResMut<UiPool<FloatingText>>,
+    power: Res<PowerMode>,
+) {
+    if power.low_power {
+        reward_events.clear();
+        return;
+    }
+    let Ok((transform, computed)) = buttons.single() else {
+        reward_events.clear();
+        return;
+    };
+    let center = node_center(transform, computed);
+    let top = center.y - computed.size().y * computed.inverse_scale_factor() / 2.0;
+
+    // A reward card offer gets its own modal, so only payouts float
+    for (i, event) in reward_events.read().filter(|e| !matches!(e.reward, Reward::Choice(_))).enumerate() {
+        let Some(entity) = pool.acquire(&mut commands) else {
+            continue;
+        };
+
+        let from = Vec2::new(center.x - 90.0, top - 50.0 - i as f32 * 24.0);
+        commands.entity(entity).insert((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(from.x),
+                top: Val::Px(from.y),
+                ..default()
+            },
+            Text::new(format!("Milestone reward: {}", event.reward.describe())),
+            TextFont {
+                font_size: 16.0,
+                ..default()
+            },
+            TextColor(REWARD_TOAST_COLOR),
+            GlobalZIndex(100),
+            FloatingText {
+                elapsed: 0.0,
+                duration: 2.5,
+                from,
+                rise: 60.0,
+                color: REWARD_TOAST_COLOR,
+            },
+            MainScreen,
+        ));
+    }
+}
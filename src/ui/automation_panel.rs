@@ -0,0 +1,305 @@
+//! Automation overlay - build rules, see the rule list and the execution log
+
+use bevy::prelude::*;
+use crate::automation::{AutomationState, RuleAction, MAX_RULES};
+use crate::research::{ResearchState, Tech};
+use super::{
+    set_overlay_available, spawn_overlay, ButtonInteractionQuery, OverlayKind, OverlayPanelQuery,
+    OverlayToggleQuery, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON,
+};
+
+/// A part of the rule being built; pressing its button steps it to the next choice
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DraftField {
+    Metric,
+    Comparison,
+    Threshold,
+    Action,
+}
+
+/// Button that cycles part of the draft rule
+#[derive(Component)]
+pub struct DraftFieldButton(pub DraftField);
+
+/// Text showing part of the draft rule
+#[derive(Component)]
+pub struct DraftFieldText(pub DraftField);
+
+/// Marker for the "Add Rule" button
+#[derive(Component)]
+pub struct AddRuleButton;
+
+/// Container the rule rows are rebuilt into
+#[derive(Component)]
+pub struct RuleList;
+
+/// Button that deletes the rule at an index
+#[derive(Component)]
+pub struct RemoveRuleButton(pub usize);
+
+/// Marker for the execution log text
+#[derive(Component)]
+pub struct AutomationLogText;
+
+/// Automation overlay: rule builder, rule list and log
+pub fn spawn_automation_overlay(parent: &mut ChildSpawnerCommands) {
+    spawn_overlay(parent, OverlayKind::Automation, "AUTOMATION", 440.0, |parent| {
+        section_label(parent, "NEW RULE (runs once a day)");
+
+        parent
+            .spawn(Node {
+                width: Val::Percent(100.0),
+                flex_wrap: FlexWrap::Wrap,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(4.0),
+                row_gap: Val::Px(4.0),
+                margin: UiRect::bottom(Val::Px(6.0)),
+                ..default()
+            })
+            .with_children(|parent| {
+                parent.spawn((
+                    Text::new("If"),
+                    TextFont {
+                        font_size: 13.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                ));
+                for field in [DraftField::Metric, DraftField::Comparison, DraftField::Threshold, DraftField::Action] {
+                    spawn_small_button(parent, "", (DraftFieldButton(field),), Some(DraftFieldText(field)));
+                }
+            });
+
+        spawn_small_button(parent, "Add Rule", (AddRuleButton,), None::<DraftFieldText>);
+
+        section_label(parent, "RULES");
+        parent.spawn((
+            Node {
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+            RuleList,
+        ));
+
+        section_label(parent, "LOG");
+        parent.spawn((
+            Text::new("Nothing has happened yet."),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.6, 0.8, 0.6)),
+            AutomationLogText,
+        ));
+    });
+}
+
+fn section_label(parent: &mut ChildSpawnerCommands, label: &str) {
+    parent.spawn((
+        Text::new(label),
+        TextFont {
+            font_size: 13.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.6, 0.6, 0.8)),
+        Node {
+            margin: UiRect::vertical(Val::Px(6.0)),
+            ..default()
+        },
+    ));
+}
+
+/// A compact button; `label_marker` tags its text so it can be updated later
+fn spawn_small_button<B: Bundle, M: Component>(
+    parent: &mut ChildSpawnerCommands,
+    label: &str,
+    marker: B,
+    label_marker: Option<M>,
+) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+            BackgroundColor(NORMAL_BUTTON),
+            marker,
+        ))
+        .with_children(|parent| {
+            let mut text = parent.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+            if let Some(label_marker) = label_marker {
+                text.insert(label_marker);
+            }
+        });
+}
+
+/// The Automation header button appears once the tech is researched
+pub fn update_automation_toggle_visibility(
+    research: Res<ResearchState>,
+    mut toggles: OverlayToggleQuery,
+    mut panels: OverlayPanelQuery,
+) {
+    let available = research.is_unlocked(Tech::Automation);
+    set_overlay_available(OverlayKind::Automation, available, &mut toggles, &mut panels);
+}
+
+/// Step part of the draft rule
+pub fn handle_draft_field_buttons(
+    mut interaction_query: Query<
+        (&Interaction, &DraftFieldButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    mut automation: ResMut<AutomationState>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                let draft = &mut automation.draft;
+                match button.0 {
+                    DraftField::Metric => {
+                        draft.metric = draft.metric.next();
+                        draft.threshold_index = 0;
+                    }
+                    DraftField::Comparison => draft.comparison = draft.comparison.flip(),
+                    DraftField::Threshold => {
+                        draft.threshold_index = (draft.threshold_index + 1) % draft.metric.thresholds().len();
+                    }
+                    DraftField::Action => {
+                        draft.action_index = (draft.action_index + 1) % RuleAction::all().len();
+                    }
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Add the draft to the rule list
+pub fn handle_add_rule_button(
+    mut interaction_query: ButtonInteractionQuery<AddRuleButton>,
+    mut automation: ResMut<AutomationState>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                automation.add_draft();
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Delete a rule
+pub fn handle_remove_rule_buttons(
+    mut interaction_query: Query<
+        (&Interaction, &RemoveRuleButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    mut automation: ResMut<AutomationState>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                automation.remove(button.0);
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Refresh the builder, rebuild the rule list and show the latest log entries
+pub fn update_automation_panel(
+    mut commands: Commands,
+    automation: Res<AutomationState>,
+    mut field_query: Query<(&mut Text, &DraftFieldText), Without<AutomationLogText>>,
+    mut log_query: Query<&mut Text, With<AutomationLogText>>,
+    list_query: Query<Entity, With<RuleList>>,
+) {
+    if !automation.is_changed() {
+        return;
+    }
+
+    let draft = automation.draft;
+    for (mut text, field_text) in &mut field_query {
+        **text = match field_text.0 {
+            DraftField::Metric => draft.metric.name().to_string(),
+            DraftField::Comparison => draft.comparison.symbol().to_string(),
+            DraftField::Threshold => draft.metric.format_value(draft.threshold()),
+            DraftField::Action => draft.action().describe(),
+        };
+    }
+
+    for list in &list_query {
+        commands.entity(list).despawn_children().with_children(|parent| {
+            if automation.rules.is_empty() {
+                parent.spawn((
+                    Text::new(format!("No rules yet (up to {}).", MAX_RULES)),
+                    TextFont {
+                        font_size: 12.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.5, 0.5, 0.5)),
+                ));
+            }
+
+            for (index, rule) in automation.rules.iter().enumerate() {
+                parent
+                    .spawn(Node {
+                        width: Val::Percent(100.0),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::SpaceBetween,
+                        margin: UiRect::bottom(Val::Px(4.0)),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Text::new(rule.describe()),
+                            TextFont {
+                                font_size: 12.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(0.85, 0.85, 0.85)),
+                        ));
+                        spawn_small_button(parent, "X", (RemoveRuleButton(index),), None::<DraftFieldText>);
+                    });
+            }
+        });
+    }
+
+    if !automation.log.is_empty() {
+        let log = automation.log.iter().cloned().collect::<Vec<_>>().join("\n");
+        for mut text in &mut log_query {
+            **text = log.clone();
+        }
+    }
+}
@@ -0,0 +1,220 @@
+//! Bundles overlay - what's on the shelf, at what price, and how it's selling
+
+use bevy::prelude::*;
+use crate::bundles::{Bundle, BundleState};
+use crate::currency::{money, money_whole};
+use crate::game_state::GameState;
+use crate::thing_type::ThingRegistry;
+use crate::research::{ResearchState, Tech};
+use super::{
+    set_overlay_available, spawn_overlay, OverlayKind, OverlayPanelQuery, OverlayToggleQuery,
+    NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON,
+};
+
+const BUNDLE_ON: Color = Color::srgb(0.5, 0.9, 0.5);
+const BUNDLE_OFF: Color = Color::srgb(0.7, 0.7, 0.7);
+
+/// Button that puts a bundle on or off the shelf
+#[derive(Component)]
+pub struct BundleToggleButton(pub Bundle);
+
+/// Button that nudges a bundle's price up or down by a number of steps
+#[derive(Component)]
+pub struct BundlePriceButton(pub Bundle, pub i32);
+
+/// Text showing a bundle's status and price
+#[derive(Component)]
+pub struct BundleStatusText(pub Bundle);
+
+/// Marker for the bundle analytics text
+#[derive(Component)]
+pub struct BundleAnalyticsText;
+
+/// Bundles overlay
+pub fn spawn_bundle_overlay(parent: &mut ChildSpawnerCommands) {
+    spawn_overlay(parent, OverlayKind::Bundles, "BUNDLES", 400.0, |parent| {
+        parent.spawn((
+            Text::new(
+                "3-packs move more Things per customer at a discount. Deluxe Boxes \
+                 sell one Thing at a markup. The steeper the price, the fewer takers.",
+            ),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            Node {
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+        ));
+
+        for bundle in Bundle::OPTIONAL {
+            spawn_bundle_row(parent, bundle);
+        }
+
+        parent.spawn((
+            Text::new(""),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            Node {
+                margin: UiRect::top(Val::Px(8.0)),
+                ..default()
+            },
+            BundleAnalyticsText,
+        ));
+    });
+}
+
+fn spawn_bundle_row(parent: &mut ChildSpawnerCommands, bundle: Bundle) {
+    parent
+        .spawn(Node {
+            width: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(6.0),
+            margin: UiRect::bottom(Val::Px(6.0)),
+            ..default()
+        })
+        .with_children(|parent| {
+            spawn_small_button(parent, bundle.name(), 100.0, BundleToggleButton(bundle));
+            spawn_small_button(parent, "-", 24.0, BundlePriceButton(bundle, -1));
+            spawn_small_button(parent, "+", 24.0, BundlePriceButton(bundle, 1));
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(BUNDLE_OFF),
+                BundleStatusText(bundle),
+            ));
+        });
+}
+
+pub(super) fn spawn_small_button(parent: &mut ChildSpawnerCommands, label: &str, min_width: f32, marker: impl Component) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                min_width: Val::Px(min_width),
+                padding: UiRect::axes(Val::Px(6.0), Val::Px(4.0)),
+                justify_content: JustifyContent::Center,
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+            BackgroundColor(NORMAL_BUTTON),
+            marker,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// The Bundles header button appears once packaging is researched
+pub fn update_bundle_toggle_visibility(
+    research: Res<ResearchState>,
+    mut toggles: OverlayToggleQuery,
+    mut panels: OverlayPanelQuery,
+) {
+    let available = research.is_unlocked(Tech::Packaging);
+    set_overlay_available(OverlayKind::Bundles, available, &mut toggles, &mut panels);
+}
+
+/// Put bundles on or off the shelf
+pub fn handle_bundle_toggles(
+    mut interaction_query: Query<(&Interaction, &BundleToggleButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut bundles: ResMut<BundleState>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                bundles.toggle(button.0);
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+/// Change a bundle's price
+pub fn handle_bundle_price_buttons(
+    mut interaction_query: Query<(&Interaction, &BundlePriceButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut bundles: ResMut<BundleState>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                bundles.adjust(button.0, button.1);
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+/// Keep bundle prices, takers and sales figures current
+pub fn update_bundle_panel(
+    bundles: Res<BundleState>,
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    mut status_query: Query<(&mut Text, &mut TextColor, &BundleStatusText), Without<BundleAnalyticsText>>,
+    mut analytics_query: Query<&mut Text, (With<BundleAnalyticsText>, Without<BundleStatusText>)>,
+) {
+    if !bundles.is_changed() {
+        return;
+    }
+    let Some(thing_type) = game_state.thing_type else {
+        return;
+    };
+
+    let mix = bundles.mix(things[thing_type].tier);
+    for (mut text, mut color, status) in &mut status_query {
+        let bundle = status.0;
+        let takers = mix.iter().find(|s| s.bundle == bundle).map(|s| s.customers).unwrap_or(0.0);
+        let price = format!("{:+.0}% per Thing", bundles.adjustment(bundle) * 100.0);
+        if bundles.is_offered(bundle) {
+            **text = format!("ON, {} - {:.0}% of customers", price, takers * 100.0);
+            color.0 = BUNDLE_ON;
+        } else {
+            **text = format!("OFF, {}", price);
+            color.0 = BUNDLE_OFF;
+        }
+    }
+
+    let total_revenue: f64 = Bundle::ALL.iter().map(|b| bundles.sales(*b).revenue).sum();
+    let mut lines = vec!["Sales so far:".to_string()];
+    for bundle in Bundle::ALL {
+        let sales = bundles.sales(bundle);
+        if sales.sales < 1.0 {
+            continue;
+        }
+        lines.push(format!(
+            "  {}: {:.0} sold ({:.0} Things), {} - {:.0}% of revenue, {} a sale",
+            bundle.name(),
+            sales.sales,
+            sales.things,
+            money_whole(sales.revenue),
+            sales.revenue / total_revenue.max(0.01) * 100.0,
+            money(sales.revenue / sales.sales)
+        ));
+    }
+    if lines.len() == 1 {
+        lines.push("  Nothing sold yet.".to_string());
+    }
+    for mut text in &mut analytics_query {
+        **text = lines.join("\n");
+    }
+}
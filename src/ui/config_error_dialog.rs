@@ -0,0 +1,133 @@
+//! Dialog on the selection screen when a profile's config couldn't be loaded or upgraded
+
+use bevy::prelude::*;
+use crate::settings::ConfigLoadError;
+use super::{ButtonInteractionQuery, FocusTrap, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+/// Marker for the dialog (hidden unless there's an error)
+#[derive(Component)]
+pub struct ConfigErrorDialog;
+
+/// Marker for the dialog's explanation
+#[derive(Component)]
+pub struct ConfigErrorText;
+
+/// Marker for the dialog's "OK" button
+#[derive(Component)]
+pub struct DismissConfigErrorButton;
+
+/// Centered error window (goes last on the selection screen so it sits on top)
+pub fn spawn_config_error_dialog(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(120.0),
+                left: Val::Percent(50.0),
+                margin: UiRect::left(Val::Px(-220.0)),
+                width: Val::Px(440.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                padding: UiRect::all(Val::Px(15.0)),
+                border: UiRect::all(Val::Px(2.0)),
+                display: Display::None,
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.8, 0.3, 0.3)),
+            BackgroundColor(Color::srgb(0.12, 0.06, 0.06)),
+            GlobalZIndex(70),
+            ConfigErrorDialog,
+            FocusTrap,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("COULDN'T LOAD YOUR SETTINGS"),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.6, 0.6)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(10.0)),
+                    ..default()
+                },
+            ));
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.85, 0.8, 0.8)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(12.0)),
+                    ..default()
+                },
+                ConfigErrorText,
+            ));
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        padding: UiRect::axes(Val::Px(16.0), Val::Px(6.0)),
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    BorderColor::all(Color::srgb(0.6, 0.4, 0.4)),
+                    BackgroundColor(NORMAL_BUTTON),
+                    DismissConfigErrorButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new("OK"),
+                        TextFont {
+                            font_size: 13.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+        });
+}
+
+/// Show the dialog while there's an error to explain
+pub fn update_config_error_dialog(
+    load_error: Res<ConfigLoadError>,
+    added: Query<(), Added<ConfigErrorDialog>>,
+    mut dialog_query: Query<&mut Node, With<ConfigErrorDialog>>,
+    mut text_query: Query<&mut Text, With<ConfigErrorText>>,
+) {
+    if !load_error.is_changed() && added.is_empty() {
+        return;
+    }
+
+    for mut node in &mut dialog_query {
+        node.display = if load_error.message.is_some() { Display::Flex } else { Display::None };
+    }
+    if let Some(message) = &load_error.message {
+        for mut text in &mut text_query {
+            **text = message.clone();
+        }
+    }
+}
+
+/// Dismiss the dialog
+pub fn handle_dismiss_config_error_button(
+    mut interaction_query: ButtonInteractionQuery<DismissConfigErrorButton>,
+    mut load_error: ResMut<ConfigLoadError>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                load_error.message = None;
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
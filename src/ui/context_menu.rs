@@ -0,0 +1,384 @@
+//! Context menus - extra actions on upgrades and marketing channels
+//!
+//! Anything with a [`ContextMenuSource`] opens a small menu on right-click,
+//! on a long press, or with the Menu key (gamepad Y) while it has focus.
+//! Picking an entry sends a [`ContextActionEvent`] to whatever carries the
+//! action out; "View history" is answered by the menu itself, from the
+//! replay log.
+
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use bevy::window::PrimaryWindow;
+use crate::business::UpgradeType;
+use crate::hud::{HudLayout, HudStat, HudWidget};
+use crate::marketing::{MarketingChannel, MarketingState};
+use crate::replay::{DayActions, ReplayLog};
+use super::{FocusTrap, MainScreen, UiFocus, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+/// How long a press has to be held to open the menu
+const LONG_PRESS_SECS: f32 = 0.6;
+/// How far the pointer can wander before a long press counts as a drag instead
+const LONG_PRESS_SLOP: f32 = 8.0;
+const MENU_WIDTH: f32 = 210.0;
+/// Most history lines a menu shows
+const HISTORY_LINES: usize = 12;
+
+/// What a context menu is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextSubject {
+    Stat(HudStat),
+    Upgrade(UpgradeType),
+    Channel(MarketingChannel),
+}
+
+impl ContextSubject {
+    pub fn name(&self) -> &'static str {
+        self.hud_widget().name()
+    }
+
+    /// The widget it's pinned to the HUD as
+    pub fn hud_widget(&self) -> HudWidget {
+        match *self {
+            ContextSubject::Stat(stat) => HudWidget::Stat(stat),
+            ContextSubject::Upgrade(upgrade) => HudWidget::Upgrade(upgrade),
+            ContextSubject::Channel(channel) => HudWidget::Channel(channel),
+        }
+    }
+
+    pub fn from_hud_widget(widget: HudWidget) -> Self {
+        match widget {
+            HudWidget::Stat(stat) => ContextSubject::Stat(stat),
+            HudWidget::Upgrade(upgrade) => ContextSubject::Upgrade(upgrade),
+            HudWidget::Channel(channel) => ContextSubject::Channel(channel),
+        }
+    }
+
+    /// Everything on the menu, in order
+    fn actions(&self, pinned: bool, marketing: &MarketingState) -> Vec<ContextAction> {
+        let pin = if pinned { ContextAction::Unpin } else { ContextAction::Pin };
+        let first = match self {
+            ContextSubject::Stat(_) => return vec![pin, ContextAction::Close],
+            ContextSubject::Upgrade(_) => ContextAction::BuyMax,
+            ContextSubject::Channel(channel) if marketing.is_active(*channel) => ContextAction::SwitchOff,
+            ContextSubject::Channel(_) => ContextAction::SwitchOn,
+        };
+        vec![first, pin, ContextAction::ViewHistory, ContextAction::Close]
+    }
+
+    /// What the player did with it on one day, if anything
+    fn history(&self, day: &DayActions) -> Option<String> {
+        match self {
+            ContextSubject::Stat(_) => None,
+            ContextSubject::Upgrade(upgrade) => {
+                let bought = day.upgrades.iter().filter(|u| *u == upgrade).count();
+                (bought > 0).then(|| format!("{}: bought {}", day.date.format(), bought))
+            }
+            ContextSubject::Channel(channel) => {
+                let switches: Vec<&str> = day
+                    .channels
+                    .iter()
+                    .filter(|(c, _)| c == channel)
+                    .map(|(_, active)| if *active { "on" } else { "off" })
+                    .collect();
+                (!switches.is_empty()).then(|| format!("{}: switched {}", day.date.format(), switches.join(", then ")))
+            }
+        }
+    }
+}
+
+/// An entry on a context menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextAction {
+    /// Buy as many as the money allows
+    BuyMax,
+    SwitchOn,
+    SwitchOff,
+    /// Keep it on the main screen
+    Pin,
+    Unpin,
+    ViewHistory,
+    Close,
+}
+
+impl ContextAction {
+    fn label(&self) -> &'static str {
+        match self {
+            ContextAction::BuyMax => "Buy max",
+            ContextAction::SwitchOn => "Switch on",
+            ContextAction::SwitchOff => "Switch off",
+            ContextAction::Pin => "Pin to HUD",
+            ContextAction::Unpin => "Unpin from HUD",
+            ContextAction::ViewHistory => "View history",
+            ContextAction::Close => "Close",
+        }
+    }
+}
+
+/// Something that has a context menu
+#[derive(Component)]
+pub struct ContextMenuSource(pub ContextSubject);
+
+/// Context menu sources, with where they are on screen
+pub type ContextMenuSourceQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static Interaction, &'static ContextMenuSource, &'static ComputedNode, &'static UiGlobalTransform),
+>;
+
+/// Marker for the open menu
+#[derive(Component)]
+pub struct ContextMenuPanel;
+
+/// An entry on the open menu
+#[derive(Component)]
+pub struct ContextMenuItem(pub ContextAction);
+
+/// The menu that's open
+pub struct OpenContextMenu {
+    pub subject: ContextSubject,
+    /// Top left corner, in logical pixels
+    pub position: Vec2,
+    /// Set once "View history" is picked, replacing the entries
+    pub history: Option<Vec<String>>,
+}
+
+#[derive(Resource, Default)]
+pub struct ContextMenu {
+    pub open: Option<OpenContextMenu>,
+}
+
+/// Message fired when a context menu entry is picked
+#[derive(Event, Message, Clone)]
+pub struct ContextActionEvent {
+    pub subject: ContextSubject,
+    pub action: ContextAction,
+}
+
+/// The inputs that open and close context menus
+#[derive(SystemParam)]
+pub struct ContextMenuInput<'w, 's> {
+    mouse: Res<'w, ButtonInput<MouseButton>>,
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    gamepads: Query<'w, 's, &'static Gamepad>,
+    time: Res<'w, Time<Real>>,
+    window_query: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+    focus: Res<'w, UiFocus>,
+}
+
+impl ContextMenuInput<'_, '_> {
+    fn cursor(&self) -> Option<Vec2> {
+        self.window_query.single().ok().and_then(|window| window.cursor_position())
+    }
+
+    fn menu_key(&self) -> bool {
+        self.keys.just_pressed(KeyCode::ContextMenu)
+            || self.gamepads.iter().any(|gamepad| gamepad.just_pressed(GamepadButton::North))
+    }
+
+    /// Keep a menu opened at `position` on screen
+    fn clamp(&self, position: Vec2) -> Vec2 {
+        let Ok(window) = self.window_query.single() else {
+            return position;
+        };
+        Vec2::new(
+            position.x.min(window.width() - MENU_WIDTH - 8.0).max(0.0),
+            position.y.min(window.height() - 220.0).max(0.0),
+        )
+    }
+}
+
+/// Where a source's menu opens when it isn't opened with the mouse
+fn source_position(node: &ComputedNode, transform: &UiGlobalTransform) -> Vec2 {
+    transform.translation * node.inverse_scale_factor()
+}
+
+/// Open a menu on right-click, long press or the Menu key; close it on a click elsewhere
+pub fn open_context_menus(
+    input: ContextMenuInput,
+    mut menu: ResMut<ContextMenu>,
+    mut long_press: Local<Option<(Entity, f32, Option<Vec2>)>>,
+    source_query: ContextMenuSourceQuery,
+    panel_query: Query<&Interaction, With<ContextMenuPanel>>,
+    item_query: Query<&Interaction, With<ContextMenuItem>>,
+) {
+    let over_menu = panel_query.iter().chain(&item_query).any(|interaction| *interaction != Interaction::None);
+    if menu.open.is_some() && !over_menu && input.mouse.any_just_pressed([MouseButton::Left, MouseButton::Right]) {
+        menu.open = None;
+    }
+
+    let mut opened = None;
+    if input.mouse.just_pressed(MouseButton::Right) {
+        opened = source_query
+            .iter()
+            .find(|(_, interaction, _, _, _)| **interaction != Interaction::None)
+            .zip(input.cursor())
+            .map(|((_, _, source, _, _), cursor)| (source.0, cursor));
+    }
+
+    if input.menu_key() {
+        opened = opened.or_else(|| {
+            let (_, _, source, node, transform) = source_query.get(input.focus.focused?).ok()?;
+            Some((source.0, source_position(node, transform)))
+        });
+    }
+
+    // Held still: count up, and open once when it's been long enough (moving away makes it a drag)
+    match source_query.iter().find(|(_, interaction, _, _, _)| **interaction == Interaction::Pressed) {
+        Some((entity, _, source, node, transform)) => {
+            let cursor = input.cursor();
+            let (held, start) = match *long_press {
+                Some((pressed, held, start)) if pressed == entity => (held + input.time.delta_secs(), start),
+                _ => (input.time.delta_secs(), cursor),
+            };
+            let wandered = start.zip(cursor).is_some_and(|(start, cursor)| start.distance(cursor) > LONG_PRESS_SLOP);
+            if wandered {
+                *long_press = Some((entity, f32::MIN, start));
+            } else if held >= LONG_PRESS_SECS {
+                opened = opened.or(Some((source.0, source_position(node, transform))));
+                *long_press = Some((entity, f32::MIN, start));
+            } else {
+                *long_press = Some((entity, held, start));
+            }
+        }
+        None => *long_press = None,
+    }
+
+    if let Some((subject, position)) = opened {
+        menu.open = Some(OpenContextMenu {
+            subject,
+            position: input.clamp(position),
+            history: None,
+        });
+    }
+}
+
+/// Carry out the entry picked from the menu
+pub fn handle_context_menu_items(
+    mut interaction_query: Query<(&Interaction, &ContextMenuItem, &mut BackgroundColor), Changed<Interaction>>,
+    mut menu: ResMut<ContextMenu>,
+    replay: Res<ReplayLog>,
+    mut action_events: MessageWriter<ContextActionEvent>,
+) {
+    for (interaction, item, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                let Some(open) = menu.open.as_mut() else {
+                    continue;
+                };
+                let subject = open.subject;
+                match item.0 {
+                    ContextAction::ViewHistory => {
+                        let mut lines: Vec<String> =
+                            replay.days.iter().rev().filter_map(|day| subject.history(day)).take(HISTORY_LINES).collect();
+                        if lines.is_empty() {
+                            lines.push("Nothing yet this run.".to_string());
+                        }
+                        open.history = Some(lines);
+                    }
+                    ContextAction::Close => menu.open = None,
+                    action => {
+                        action_events.write(ContextActionEvent { subject, action });
+                        menu.open = None;
+                    }
+                }
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+/// Rebuild the menu whenever it opens, changes or closes
+pub fn refresh_context_menu(
+    mut commands: Commands,
+    menu: Res<ContextMenu>,
+    layout: Res<HudLayout>,
+    marketing: Res<MarketingState>,
+    panel_query: Query<Entity, With<ContextMenuPanel>>,
+) {
+    if !menu.is_changed() {
+        return;
+    }
+    for entity in &panel_query {
+        commands.entity(entity).despawn();
+    }
+    let Some(open) = &menu.open else {
+        return;
+    };
+
+    let actions = match &open.history {
+        Some(_) => vec![ContextAction::Close],
+        None => open.subject.actions(layout.contains(open.subject.hud_widget()), &marketing),
+    };
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(open.position.x),
+                top: Val::Px(open.position.y),
+                width: Val::Px(MENU_WIDTH),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.5, 0.5, 0.6)),
+            BackgroundColor(Color::srgb(0.1, 0.1, 0.14)),
+            GlobalZIndex(80),
+            Interaction::default(),
+            FocusTrap,
+            ContextMenuPanel,
+            MainScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(open.subject.name()),
+                TextFont {
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            ));
+            if let Some(history) = &open.history {
+                parent.spawn((
+                    Text::new(history.join("\n")),
+                    TextFont {
+                        font_size: 12.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.85, 0.85, 0.85)),
+                ));
+            }
+
+            for action in actions {
+                parent
+                    .spawn((
+                        Button,
+                        Node {
+                            width: Val::Percent(100.0),
+                            padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                            ..default()
+                        },
+                        BackgroundColor(NORMAL_BUTTON),
+                        ContextMenuItem(action),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Text::new(action.label()),
+                            TextFont {
+                                font_size: 13.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                        ));
+                    });
+            }
+        });
+}
+
+/// Nothing stays open once the main screen goes
+pub fn close_context_menu(mut menu: ResMut<ContextMenu>) {
+    menu.open = None;
+}
@@ -0,0 +1,152 @@
+//! Counterfeits overlay - find out how bad the fakes are, and go after them
+
+use bevy::prelude::*;
+use crate::counterfeits::{CounterfeitAction, CounterfeitActionEvent, CounterfeitState};
+use crate::currency::money_whole;
+use crate::game_state::GameState;
+use super::{
+    set_overlay_available, spawn_overlay, OverlayKind, OverlayPanelQuery, OverlayToggleQuery, NORMAL_BUTTON,
+    HOVERED_BUTTON, PRESSED_BUTTON, DISABLED_BUTTON,
+};
+
+/// Button that takes an action against the fakes
+#[derive(Component)]
+pub struct CounterfeitButton(pub CounterfeitAction);
+
+/// Text showing an action's status
+#[derive(Component)]
+pub struct CounterfeitActionStatus(pub CounterfeitAction);
+
+/// Marker for what the last test buy found
+#[derive(Component)]
+pub struct CounterfeitSummaryText;
+
+/// Counterfeits overlay with one button per action
+pub fn spawn_counterfeit_overlay(parent: &mut ChildSpawnerCommands) {
+    spawn_overlay(parent, OverlayKind::Counterfeits, "COUNTERFEITS", 380.0, |parent| {
+        parent.spawn((
+            Text::new("Nobody knows how many fakes are out there. A test buy would tell you."),
+            TextFont {
+                font_size: 13.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            Node {
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+            CounterfeitSummaryText,
+        ));
+
+        for action in CounterfeitAction::ALL {
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Percent(100.0),
+                        flex_direction: FlexDirection::Column,
+                        padding: UiRect::all(Val::Px(8.0)),
+                        margin: UiRect::bottom(Val::Px(8.0)),
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    BorderColor::all(Color::srgb(0.6, 0.4, 0.4)),
+                    BackgroundColor(NORMAL_BUTTON),
+                    CounterfeitButton(action),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(format!("{} - {}", action.name(), money_whole(action.cost()))),
+                        TextFont {
+                            font_size: 15.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                    parent.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.9, 0.8, 0.5)),
+                        CounterfeitActionStatus(action),
+                    ));
+                });
+        }
+    });
+}
+
+/// The Fakes header button appears once counterfeits show up
+pub fn update_counterfeit_toggle_visibility(
+    counterfeits: Res<CounterfeitState>,
+    mut toggles: OverlayToggleQuery,
+    mut panels: OverlayPanelQuery,
+) {
+    set_overlay_available(OverlayKind::Counterfeits, counterfeits.appeared(), &mut toggles, &mut panels);
+}
+
+/// Take an action when its button is pressed
+pub fn handle_counterfeit_buttons(
+    mut interaction_query: Query<(&Interaction, &CounterfeitButton, &mut BackgroundColor), Changed<Interaction>>,
+    counterfeits: Res<CounterfeitState>,
+    game_state: Res<GameState>,
+    mut action_events: MessageWriter<CounterfeitActionEvent>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        let can_take = counterfeits.can_take(button.0, &game_state);
+
+        match *interaction {
+            Interaction::Pressed => {
+                if can_take {
+                    *bg_color = PRESSED_BUTTON.into();
+                    action_events.write(CounterfeitActionEvent { action: button.0 });
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = if can_take { HOVERED_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+            Interaction::None => {
+                *bg_color = if can_take { NORMAL_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+        }
+    }
+}
+
+/// Keep the test buy summary and each action's status current
+pub fn update_counterfeit_panel(
+    counterfeits: Res<CounterfeitState>,
+    mut summary_query: Query<&mut Text, With<CounterfeitSummaryText>>,
+    mut status_query: Query<(&mut Text, &CounterfeitActionStatus), Without<CounterfeitSummaryText>>,
+) {
+    if !counterfeits.is_changed() {
+        return;
+    }
+
+    if let Some((date, share)) = counterfeits.last_test_buy {
+        for mut text in &mut summary_query {
+            **text = format!("Test buy on {}: {:.0}% of the Things out there were fake.", date.iso(), share * 100.0);
+        }
+    }
+
+    for (mut text, status) in &mut status_query {
+        **text = match status.0 {
+            CounterfeitAction::TestBuy => "Buy a few off market stalls and count the fakes".to_string(),
+            CounterfeitAction::LegalAction => match counterfeits.legal_action_days {
+                Some(days) => format!("Lawyers at work: {} days to go", days),
+                None => "Cease-and-desist letters and takedowns halve the fakes".to_string(),
+            },
+            CounterfeitAction::RegisterWithCustoms => {
+                if counterfeits.customs_registered {
+                    "Registered - customs is watching for fakes".to_string()
+                } else {
+                    "Lets customs stop shipments of fakes at the border".to_string()
+                }
+            }
+            CounterfeitAction::Authenticate => match counterfeits.seizure_hold_days {
+                Some(days) => format!("Customs is holding a shipment: {} days to send the report", days),
+                None => "Nothing held at customs".to_string(),
+            },
+        };
+    }
+}
@@ -0,0 +1,176 @@
+//! ThingCoin section of the finances overlay - only shown while crypto matters
+
+use bevy::prelude::*;
+use crate::crypto::{CryptoState, CryptoTrade, CryptoTradeEvent};
+use crate::currency::{money, money_whole};
+use crate::game_state::GameState;
+use crate::thing_type::ThingRegistry;
+use super::{NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+const TRADE_AVAILABLE: Color = Color::WHITE;
+const TRADE_UNAVAILABLE: Color = Color::srgb(0.4, 0.4, 0.4);
+
+/// Container for the whole section (hidden outside the crypto windows)
+#[derive(Component)]
+pub struct CryptoSection;
+
+/// Marker for the price and holdings text
+#[derive(Component)]
+pub struct CryptoText;
+
+/// Button that makes a trade
+#[derive(Component)]
+pub struct CryptoTradeButton(pub CryptoTrade);
+
+/// ThingCoin section (goes under savings in the finances overlay)
+pub fn spawn_crypto_section(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                margin: UiRect::bottom(Val::Px(12.0)),
+                display: Display::None,
+                ..default()
+            },
+            CryptoSection,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("THINGCOIN"),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.8, 0.4)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(4.0)),
+                    ..default()
+                },
+            ));
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(6.0)),
+                    ..default()
+                },
+                CryptoText,
+            ));
+
+            parent
+                .spawn(Node {
+                    width: Val::Percent(100.0),
+                    flex_wrap: FlexWrap::Wrap,
+                    column_gap: Val::Px(6.0),
+                    row_gap: Val::Px(4.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for trade in CryptoTrade::ALL {
+                        parent
+                            .spawn((
+                                Button,
+                                Node {
+                                    padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                                    border: UiRect::all(Val::Px(1.0)),
+                                    ..default()
+                                },
+                                BorderColor::all(Color::srgb(0.6, 0.5, 0.3)),
+                                BackgroundColor(NORMAL_BUTTON),
+                                CryptoTradeButton(trade),
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    Text::new(trade.label()),
+                                    TextFont {
+                                        font_size: 12.0,
+                                        ..default()
+                                    },
+                                    TextColor(TRADE_AVAILABLE),
+                                ));
+                            });
+                    }
+                });
+        });
+}
+
+/// Make a trade
+pub fn handle_crypto_trade_buttons(
+    mut interaction_query: Query<(&Interaction, &CryptoTradeButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut trade_events: MessageWriter<CryptoTradeEvent>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                trade_events.write(CryptoTradeEvent { trade: button.0 });
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+/// Show the section while there's a market or coins to sell, and grey out trades that can't happen
+pub fn update_crypto_section(
+    crypto: Res<CryptoState>,
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    mut section_query: Query<&mut Node, With<CryptoSection>>,
+    mut text_query: Query<&mut Text, With<CryptoText>>,
+    mut button_query: Query<(&CryptoTradeButton, &mut Node, &Children), Without<CryptoSection>>,
+    mut label_query: Query<&mut TextColor>,
+) {
+    if !crypto.is_changed() && !game_state.is_changed() {
+        return;
+    }
+
+    let shown = crypto.window_open || crypto.coins > 0.0 || crypto.issued.is_some();
+    for mut node in &mut section_query {
+        node.display = if shown { Display::Flex } else { Display::None };
+    }
+    if !shown {
+        return;
+    }
+
+    let mut lines = vec![
+        format!("Price: {} ({:+.0}% today)", money(crypto.price), crypto.daily_change() * 100.0),
+        format!(
+            "Holding: {:.2} coins worth {} (paid {})",
+            crypto.coins,
+            money(crypto.holdings_value()),
+            money(crypto.cost_basis)
+        ),
+    ];
+    if !crypto.window_open {
+        lines.push("Crypto winter: selling only.".to_string());
+    }
+    if let Some(raised) = crypto.issued {
+        lines.push(format!("Our own coin raised {}. Nobody has looked closely. Yet.", money_whole(raised)));
+    }
+    for mut text in &mut text_query {
+        **text = lines.join("\n");
+    }
+
+    for (button, mut node, children) in &mut button_query {
+        // Only shady Things get to issue a coin at all
+        if button.0 == CryptoTrade::IssueCoin {
+            node.display = if crypto.window_open && things.is_shady(game_state.thing_type) {
+                Display::Flex
+            } else {
+                Display::None
+            };
+        }
+        let color = if crypto.can_trade(button.0, &game_state, &things) { TRADE_AVAILABLE } else { TRADE_UNAVAILABLE };
+        for child in children.iter() {
+            if let Ok(mut text_color) = label_query.get_mut(child) {
+                text_color.0 = color;
+            }
+        }
+    }
+}
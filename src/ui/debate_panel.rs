@@ -0,0 +1,234 @@
+//! Debate panel under Terry's dialogue - Terry and Doug arguing in turns, and picking a side
+
+use bevy::prelude::*;
+use crate::debates::{Advisor, DebateDecisionEvent, DebateState};
+use crate::vacation::VacationState;
+use super::{NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+/// Real seconds between turns in the argument
+const TURN_SECS: f32 = 1.6;
+const TERRY_BUBBLE: Color = Color::srgb(0.95, 0.92, 0.85);
+const DOUG_BUBBLE: Color = Color::srgb(0.8, 0.85, 0.9);
+
+/// Container for the argument (hidden unless there's one going)
+#[derive(Component)]
+pub struct DebatePanel;
+
+/// Row holding the side buttons (shown once everyone's had their say)
+#[derive(Component)]
+pub struct DebateChoices;
+
+/// Button backing an advisor
+#[derive(Component)]
+pub struct DebateSideButton(pub Advisor);
+
+/// Which text of the panel this is
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub enum DebateText {
+    Title,
+    /// A turn in the argument (index into the topic's turns)
+    Turn(usize),
+    /// The label on a side button
+    Side(Advisor),
+    /// Both relationships and who's won so far
+    Record,
+}
+
+/// Debate panel (goes under Terry's dialogue)
+pub fn spawn_debate_panel(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(5.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                margin: UiRect::top(Val::Px(6.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                display: Display::None,
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.5, 0.5, 0.7)),
+            BackgroundColor(Color::srgb(0.1, 0.1, 0.15)),
+            DebatePanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.85, 0.4)),
+                DebateText::Title,
+            ));
+
+            for turn in 0..4 {
+                // Terry opens, so the even turns are Terry's
+                let (bubble, align_self) = if turn % 2 == 0 {
+                    (TERRY_BUBBLE, AlignSelf::FlexStart)
+                } else {
+                    (DOUG_BUBBLE, AlignSelf::FlexEnd)
+                };
+                parent.spawn((
+                    Text::new(""),
+                    TextFont {
+                        font_size: 12.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.15, 0.1, 0.05)),
+                    Node {
+                        align_self,
+                        max_width: Val::Percent(85.0),
+                        padding: UiRect::axes(Val::Px(8.0), Val::Px(5.0)),
+                        border_radius: BorderRadius::all(Val::Px(8.0)),
+                        display: Display::None,
+                        ..default()
+                    },
+                    BackgroundColor(bubble),
+                    DebateText::Turn(turn),
+                ));
+            }
+
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(4.0),
+                        margin: UiRect::top(Val::Px(4.0)),
+                        display: Display::None,
+                        ..default()
+                    },
+                    DebateChoices,
+                ))
+                .with_children(|parent| {
+                    for advisor in Advisor::BOTH {
+                        parent
+                            .spawn((
+                                Button,
+                                Node {
+                                    padding: UiRect::axes(Val::Px(10.0), Val::Px(4.0)),
+                                    border: UiRect::all(Val::Px(1.0)),
+                                    ..default()
+                                },
+                                BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+                                BackgroundColor(NORMAL_BUTTON),
+                                DebateSideButton(advisor),
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    Text::new(""),
+                                    TextFont {
+                                        font_size: 12.0,
+                                        ..default()
+                                    },
+                                    TextColor(Color::WHITE),
+                                    DebateText::Side(advisor),
+                                ));
+                            });
+                    }
+                });
+
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 11.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.6, 0.6)),
+                DebateText::Record,
+            ));
+        });
+}
+
+/// Back an advisor
+pub fn handle_debate_buttons(
+    mut interaction_query: Query<(&Interaction, &DebateSideButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut decision_events: MessageWriter<DebateDecisionEvent>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                decision_events.write(DebateDecisionEvent { side: button.0 });
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+/// Show the panel while there's an argument, and write it out
+pub fn update_debate_panel(
+    debates: Res<DebateState>,
+    vacation: Res<VacationState>,
+    added: Query<(), Added<DebatePanel>>,
+    mut panel_query: Query<&mut Node, With<DebatePanel>>,
+    mut text_query: Query<(&mut Text, &DebateText)>,
+) {
+    if !debates.is_changed() && !vacation.is_changed() && added.is_empty() {
+        return;
+    }
+
+    for mut node in &mut panel_query {
+        let display = if debates.active.is_some() { Display::Flex } else { Display::None };
+        if node.display != display {
+            node.display = display;
+        }
+    }
+    let Some(debate) = debates.active else {
+        return;
+    };
+
+    let topic = debate.topic();
+    let turns = topic.turns();
+    for (mut text, kind) in &mut text_query {
+        **text = match *kind {
+            DebateText::Title => format!("Terry and Doug disagree: {} ({} days to decide)", topic.title, debate.days_left),
+            DebateText::Turn(turn) => {
+                let (advisor, line) = turns[turn];
+                format!("{}: \"{}\"", advisor.name(), line)
+            }
+            DebateText::Side(advisor) => {
+                format!("Side with {} - {}", advisor.name(), topic.position(advisor).outcome())
+            }
+            DebateText::Record => format!(
+                "Terry {:.0}/100, Doug {:.0}/100 - debates won: Terry {}, Doug {}",
+                vacation.relationship, debates.doug_relationship, debates.terry_wins, debates.doug_wins
+            ),
+        };
+    }
+}
+
+/// Let the argument play out a turn at a time, then offer the sides
+pub fn reveal_debate_turns(
+    time: Res<Time<Real>>,
+    debates: Res<DebateState>,
+    mut turn_query: Query<(&mut Node, &DebateText), Without<DebateChoices>>,
+    mut choices_query: Query<&mut Node, With<DebateChoices>>,
+    mut elapsed: Local<f32>,
+) {
+    let Some(debate) = debates.active else {
+        // The next argument starts from the first turn
+        *elapsed = 0.0;
+        return;
+    };
+    *elapsed += time.delta_secs();
+    let shown = (*elapsed / TURN_SECS) as usize + 1;
+
+    for (mut node, kind) in &mut turn_query {
+        let DebateText::Turn(turn) = *kind else {
+            continue;
+        };
+        let display = if turn < shown { Display::Flex } else { Display::None };
+        if node.display != display {
+            node.display = display;
+        }
+    }
+    let display = if shown > debate.topic().turns().len() { Display::Flex } else { Display::None };
+    for mut node in &mut choices_query {
+        if node.display != display {
+            node.display = display;
+        }
+    }
+}
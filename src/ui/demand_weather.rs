@@ -0,0 +1,191 @@
+//! Demand "weather report" - a rough read on today's demand, without the numbers
+
+use bevy::prelude::*;
+use crate::economy::{GameDate, WorldState};
+
+/// How many factors the tooltip lists
+const TOP_FACTORS: usize = 3;
+/// Factors closer to 1.0 than this (in log terms) aren't worth mentioning
+const NEGLIGIBLE_FACTOR: f32 = 0.02;
+
+/// Today's demand, bucketed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DemandOutlook {
+    Stormy,
+    Cloudy,
+    Sunny,
+}
+
+impl DemandOutlook {
+    pub fn from_modifier(modifier: f32) -> Self {
+        if modifier < 0.85 {
+            DemandOutlook::Stormy
+        } else if modifier < 1.15 {
+            DemandOutlook::Cloudy
+        } else {
+            DemandOutlook::Sunny
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DemandOutlook::Stormy => "Stormy",
+            DemandOutlook::Cloudy => "Cloudy",
+            DemandOutlook::Sunny => "Sunny",
+        }
+    }
+
+    fn icon_color(&self) -> Color {
+        match self {
+            DemandOutlook::Stormy => Color::srgb(0.45, 0.35, 0.6),
+            DemandOutlook::Cloudy => Color::srgb(0.65, 0.65, 0.7),
+            DemandOutlook::Sunny => Color::srgb(1.0, 0.85, 0.3),
+        }
+    }
+}
+
+/// The widget (hover target)
+#[derive(Component)]
+pub struct DemandWeather;
+
+/// The outlook icon
+#[derive(Component)]
+pub struct DemandWeatherIcon;
+
+/// The outlook label
+#[derive(Component)]
+pub struct DemandWeatherText;
+
+/// Tooltip listing what's driving demand
+#[derive(Component)]
+pub struct DemandWeatherTooltip;
+
+/// Text inside the tooltip
+#[derive(Component)]
+pub struct DemandWeatherTooltipText;
+
+/// Demand weather widget for the header
+pub fn spawn_demand_weather(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            Node {
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(6.0),
+                ..default()
+            },
+            Interaction::default(),
+            DemandWeather,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    width: Val::Px(14.0),
+                    height: Val::Px(14.0),
+                    border_radius: BorderRadius::MAX,
+                    ..default()
+                },
+                BackgroundColor(DemandOutlook::Cloudy.icon_color()),
+                DemandWeatherIcon,
+            ));
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.8, 0.9)),
+                DemandWeatherText,
+            ));
+
+            parent
+                .spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        top: Val::Px(26.0),
+                        left: Val::Px(0.0),
+                        width: Val::Px(240.0),
+                        padding: UiRect::all(Val::Px(8.0)),
+                        border: UiRect::all(Val::Px(1.0)),
+                        display: Display::None,
+                        ..default()
+                    },
+                    BorderColor::all(Color::srgb(0.4, 0.5, 0.7)),
+                    BackgroundColor(Color::srgb(0.08, 0.08, 0.14)),
+                    GlobalZIndex(60),
+                    DemandWeatherTooltip,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.85, 0.85, 0.85)),
+                        DemandWeatherTooltipText,
+                    ));
+                });
+        });
+}
+
+/// What the tooltip says: the factors pushing demand around the most, without numbers
+fn describe_factors(world: &WorldState) -> String {
+    let mut factors: Vec<(&str, f32)> = world
+        .demand_factors()
+        .into_iter()
+        .map(|(name, factor)| (name, factor.max(0.01).ln()))
+        .filter(|(_, effect)| effect.abs() >= NEGLIGIBLE_FACTOR)
+        .collect();
+    factors.sort_by(|a, b| b.1.abs().total_cmp(&a.1.abs()));
+
+    if factors.is_empty() {
+        return "Nothing much is moving demand today.".to_string();
+    }
+    let mut lines = vec!["What's driving demand:".to_string()];
+    for (name, effect) in factors.into_iter().take(TOP_FACTORS) {
+        let direction = if effect > 0.0 { "helping" } else { "hurting" };
+        lines.push(format!("  {} ({})", name, direction));
+    }
+    lines.join("\n")
+}
+
+/// Refresh the outlook when the world changes
+pub fn update_demand_weather(
+    world: Res<WorldState>,
+    mut icon_query: Query<&mut BackgroundColor, With<DemandWeatherIcon>>,
+    mut text_query: Query<&mut Text, (With<DemandWeatherText>, Without<DemandWeatherTooltipText>)>,
+    mut tooltip_text_query: Query<&mut Text, (With<DemandWeatherTooltipText>, Without<DemandWeatherText>)>,
+    added_query: Query<(), Added<DemandWeather>>,
+    mut shown: Local<Option<GameDate>>,
+) {
+    // The factors only move when the day does (or the widget was just built)
+    if *shown == Some(world.date) && added_query.is_empty() {
+        return;
+    }
+    *shown = Some(world.date);
+
+    let outlook = DemandOutlook::from_modifier(world.calculate_demand_modifier());
+    for mut color in &mut icon_query {
+        color.0 = outlook.icon_color();
+    }
+    for mut text in &mut text_query {
+        **text = format!("Demand: {}", outlook.name());
+    }
+    let description = describe_factors(&world);
+    for mut text in &mut tooltip_text_query {
+        **text = description.clone();
+    }
+}
+
+/// Show the tooltip while the widget is hovered
+pub fn show_demand_weather_tooltip(
+    widget_query: Query<&Interaction, (With<DemandWeather>, Changed<Interaction>)>,
+    mut tooltip_query: Query<&mut Node, With<DemandWeatherTooltip>>,
+) {
+    for interaction in &widget_query {
+        let display = if *interaction == Interaction::None { Display::None } else { Display::Flex };
+        for mut node in &mut tooltip_query {
+            node.display = display;
+        }
+    }
+}
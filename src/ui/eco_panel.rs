@@ -0,0 +1,136 @@
+//! Eco overlay - environmental impact and eco-certifications
+
+use bevy::prelude::*;
+use crate::currency::money_whole;
+use crate::eco::{EcoState, ECO_SEGMENT_PREMIUM};
+use crate::game_state::GameState;
+use super::{spawn_overlay, ButtonInteractionQuery, OverlayKind, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON, DISABLED_BUTTON};
+
+/// Marker for the "Certify" button
+#[derive(Component)]
+pub struct CertifyButton;
+
+/// Marker for the certify button's label (shows the next certification)
+#[derive(Component)]
+pub struct CertifyText;
+
+/// Marker for the impact summary text
+#[derive(Component)]
+pub struct EcoSummaryText;
+
+/// Eco overlay
+pub fn spawn_eco_overlay(parent: &mut ChildSpawnerCommands) {
+    spawn_overlay(parent, OverlayKind::Eco, "ECO", 360.0, |parent| {
+        parent.spawn((
+            Text::new(format!(
+                "Every Thing made leaves a mark, and some Things leave bigger ones. \
+                 Certifications cut the mark and bring in eco-conscious customers \
+                 (up to +{:.0}% revenue each, less the dirtier you are).",
+                ECO_SEGMENT_PREMIUM * 100.0
+            )),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            Node {
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+        ));
+
+        parent.spawn((
+            Text::new(""),
+            TextFont {
+                font_size: 13.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.7, 0.9, 0.7)),
+            Node {
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+            EcoSummaryText,
+        ));
+
+        parent
+            .spawn((
+                Button,
+                Node {
+                    padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                    border: UiRect::all(Val::Px(1.0)),
+                    ..default()
+                },
+                BorderColor::all(Color::srgb(0.4, 0.7, 0.4)),
+                BackgroundColor(NORMAL_BUTTON),
+                CertifyButton,
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Text::new(""),
+                    TextFont {
+                        font_size: 13.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                    CertifyText,
+                ));
+            });
+    });
+}
+
+/// Buy the next certification
+pub fn handle_certify_button(
+    mut interaction_query: ButtonInteractionQuery<CertifyButton>,
+    mut eco: ResMut<EcoState>,
+    mut game_state: ResMut<GameState>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        let can_afford = eco
+            .next_certification()
+            .is_some_and(|c| game_state.money >= c.cost());
+
+        match *interaction {
+            Interaction::Pressed => {
+                if can_afford {
+                    *bg_color = PRESSED_BUTTON.into();
+                    eco.certify(&mut game_state);
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = if can_afford { HOVERED_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+            Interaction::None => {
+                *bg_color = if can_afford { NORMAL_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+        }
+    }
+}
+
+/// Keep the impact summary and certify button current
+pub fn update_eco_panel(
+    eco: Res<EcoState>,
+    mut summary_query: Query<&mut Text, (With<EcoSummaryText>, Without<CertifyText>)>,
+    mut certify_query: Query<&mut Text, (With<CertifyText>, Without<EcoSummaryText>)>,
+) {
+    if !eco.is_changed() {
+        return;
+    }
+
+    for mut text in &mut summary_query {
+        **text = format!(
+            "Impact: {:.0}/100 ({})\nCertifications: {}\nEco-conscious customers: +{:.1}% revenue",
+            eco.impact,
+            eco.rating().name(),
+            eco.certifications,
+            (eco.segment_multiplier() - 1.0) * 100.0
+        );
+    }
+
+    for mut text in &mut certify_query {
+        **text = match eco.next_certification() {
+            Some(certification) => format!("Certify: {} ({})", certification.name(), money_whole(certification.cost())),
+            None => "Fully certified".to_string(),
+        };
+    }
+}
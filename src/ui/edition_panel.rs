@@ -0,0 +1,180 @@
+//! Limited editions overlay - order seasonal batches and watch them sell
+
+use bevy::prelude::*;
+use crate::currency::money;
+use crate::economy::{GameDate, WorldState};
+use crate::game_state::GameState;
+use crate::limited_editions::{Edition, LimitedEditions, OrderEditionEvent, BATCH_SIZES};
+use crate::thing_type::ThingRegistry;
+use super::{spawn_overlay, OverlayKind, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON, DISABLED_BUTTON};
+
+/// Button that orders a batch of an edition
+#[derive(Component)]
+pub struct OrderEditionButton(pub Edition, pub u64);
+
+/// Text showing an edition's window, batch and stock
+#[derive(Component)]
+pub struct EditionStatusText(pub Edition);
+
+/// Limited editions overlay with one entry per edition
+pub fn spawn_edition_overlay(parent: &mut ChildSpawnerCommands) {
+    spawn_overlay(parent, OverlayKind::Editions, "LIMITED EDITIONS", 380.0, |parent| {
+        parent.spawn((
+            Text::new(
+                "Order a batch the month before. A share of every sale goes out as the edition \
+                 at a markup; whatever's left when the month ends goes to clearance.",
+            ),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            Node {
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+        ));
+
+        for edition in Edition::ALL {
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Percent(100.0),
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(4.0),
+                        padding: UiRect::all(Val::Px(8.0)),
+                        margin: UiRect::bottom(Val::Px(8.0)),
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    BorderColor::all(Color::srgb(0.7, 0.5, 0.3)),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(edition.name()),
+                        TextFont {
+                            font_size: 15.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                    parent.spawn((
+                        Text::new(edition.description()),
+                        TextFont {
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                    ));
+                    parent
+                        .spawn(Node {
+                            column_gap: Val::Px(6.0),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            for units in BATCH_SIZES {
+                                spawn_order_button(parent, edition, units);
+                            }
+                        });
+                    parent.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.9, 0.8, 0.5)),
+                        EditionStatusText(edition),
+                    ));
+                });
+        }
+    });
+}
+
+fn spawn_order_button(parent: &mut ChildSpawnerCommands, edition: Edition, units: u64) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+            BackgroundColor(NORMAL_BUTTON),
+            OrderEditionButton(edition, units),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(format!("Order {}", units)),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// Order a batch when a size is picked
+pub fn handle_order_edition_buttons(
+    mut interaction_query: Query<(&Interaction, &OrderEditionButton, &mut BackgroundColor), Changed<Interaction>>,
+    editions: Res<LimitedEditions>,
+    world: Res<WorldState>,
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    mut order_events: MessageWriter<OrderEditionEvent>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        let can_order = editions.can_order(button.0, button.1, world.date, &game_state, &things);
+
+        match *interaction {
+            Interaction::Pressed => {
+                if can_order {
+                    *bg_color = PRESSED_BUTTON.into();
+                    order_events.write(OrderEditionEvent {
+                        edition: button.0,
+                        units: button.1,
+                    });
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = if can_order { HOVERED_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+            Interaction::None => {
+                *bg_color = if can_order { NORMAL_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+        }
+    }
+}
+
+/// Keep each edition's window, cost and stock current
+pub fn update_edition_panel(
+    editions: Res<LimitedEditions>,
+    world: Res<WorldState>,
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    mut query: Query<(&mut Text, &EditionStatusText)>,
+) {
+    if !editions.is_changed() && !world.is_changed() {
+        return;
+    }
+
+    for (mut text, status) in &mut query {
+        let edition = status.0;
+        let month = GameDate::month_name(edition.month());
+        **text = match editions.batch {
+            Some(batch) if batch.edition == edition && batch.launched => {
+                format!("On sale: {} of {} left", batch.remaining, batch.ordered)
+            }
+            Some(batch) if batch.edition == edition => {
+                format!("{} ordered - on sale from {} 1", batch.ordered, month)
+            }
+            Some(_) => "Another edition is on order".to_string(),
+            None if edition.can_order(world.date) => format!(
+                "Orders open: {} a unit",
+                money(LimitedEditions::batch_cost(&game_state, &things, 1))
+            ),
+            None => format!("Orders open the month before {}", month),
+        };
+    }
+}
@@ -0,0 +1,197 @@
+//! "Terry Explains the Economy" - the real formulas, for players who've seen a full year
+//!
+//! Everywhere else the world's factors stay invisible (the demand weather only
+//! says which way they push). Once a player has seen a year wrapped up, this
+//! overlay lays out how demand, daily chaos and reputation are actually worked
+//! out, with the weights the simulation uses and what they come to today.
+
+use bevy::prelude::*;
+use crate::business::{MARKETING_REPUTATION_GAIN, PAR_REPUTATION};
+use crate::economy::{
+    GameDate, Holiday, WorldState, BASELINE_POPULATION, CHAOS_SWING, CHRISTMAS_RUSH_DAYS, CHRISTMAS_RUSH_PEAK,
+    COMPETITOR_WEIGHT, DEMAND_FLOOR, EXTREME_WEATHER_DEMAND, NICE_WEATHER_DEMAND, NICE_WEATHER_TEMPS,
+    SATURATION_WEIGHT, SENTIMENT_WEIGHT, TOO_COLD_TEMP, TOO_HOT_TEMP, UNEMPLOYMENT_WEIGHT, WEEKEND_DEMAND,
+};
+use crate::fortune::RunModifiers;
+use crate::reputation::{customer_volume_factor, SCANDAL_REPUTATION};
+use crate::thing_type::{ThingDefinition, ThingRegistry};
+use crate::tutorial::{FirstTime, FirstTimes};
+use super::stats_panel::RunSetup;
+use super::{set_overlay_available, spawn_overlay, OverlayKind, OverlayPanelQuery, OverlayToggleQuery};
+
+/// Marker for the encyclopedia's entries
+#[derive(Component)]
+pub struct EncyclopediaText;
+
+/// Encyclopedia overlay
+pub fn spawn_encyclopedia_overlay(parent: &mut ChildSpawnerCommands) {
+    spawn_overlay(parent, OverlayKind::Encyclopedia, "TERRY EXPLAINS THE ECONOMY", 460.0, |parent| {
+        parent.spawn((
+            Text::new(
+                "\"You made it a whole year, so you've earned the real numbers. \
+                 Don't tell the other hot dogs.\"",
+            ),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.8, 0.75, 0.6)),
+            Node {
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+        ));
+
+        parent.spawn((
+            Text::new(""),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.85, 0.85, 0.85)),
+            EncyclopediaText,
+        ));
+    });
+}
+
+/// How demand is worked out, and today's factors
+fn demand_entry(world: &WorldState) -> String {
+    let holidays: Vec<String> = Holiday::ALL
+        .iter()
+        .map(|holiday| format!("{} x{:.1}", holiday.name(), holiday.demand_factor()))
+        .collect();
+    let today: Vec<String> = world
+        .demand_factors()
+        .into_iter()
+        .map(|(name, factor)| format!("  {}: x{:.3}", name, factor))
+        .collect();
+    let (nice_low, nice_high) = NICE_WEATHER_TEMPS;
+
+    format!(
+        "DEMAND\n\
+         Every sale is multiplied by the day's demand: all the factors below multiplied \
+         together, never less than {:.1}.\n\
+         - Christmas rush: 1 + {:.0} x (days into the last {} before Christmas) / {}\n\
+         - Holidays: {}\n\
+         - Weekends: x{:.1}\n\
+         - Weather: x{:.1} below {:.0}°F or above {:.0}°F, x{:.1} between {:.0}°F and {:.0}°F\n\
+         - Consumer confidence: x confidence\n\
+         - Unemployment: 1 - {:.1} x unemployment rate\n\
+         - The stock market: 1 + {:.1} x market sentiment\n\
+         - Trends: x trend factor\n\
+         - Competitors: 1 - {:.1} x competitor pressure\n\
+         - Market saturation: 1 - {:.1} x saturation\n\
+         - Population: world population / {:.1} billion\n\
+         Today:\n{}\n  Demand: x{:.3}",
+        DEMAND_FLOOR,
+        CHRISTMAS_RUSH_PEAK,
+        CHRISTMAS_RUSH_DAYS,
+        CHRISTMAS_RUSH_DAYS,
+        holidays.join(", "),
+        WEEKEND_DEMAND,
+        EXTREME_WEATHER_DEMAND,
+        TOO_COLD_TEMP,
+        TOO_HOT_TEMP,
+        NICE_WEATHER_DEMAND,
+        nice_low,
+        nice_high,
+        UNEMPLOYMENT_WEIGHT,
+        SENTIMENT_WEIGHT,
+        COMPETITOR_WEIGHT,
+        SATURATION_WEIGHT,
+        BASELINE_POPULATION / 1_000_000_000.0,
+        today.join("\n"),
+        world.calculate_demand_modifier()
+    )
+}
+
+/// How far the daily chaos roll can swing this run, and today's roll
+fn chaos_entry(world: &WorldState, variance: f32) -> String {
+    let swing = CHAOS_SWING * variance;
+    format!(
+        "CHAOS\n\
+         On top of demand, each day's sales get a chaos roll between x{:.2} and x{:.2}: \
+         +/-{:.1} at normal, scaled by your fortune and the difficulty (x{:.2} this run). \
+         The roll is fixed for the day.\n\
+         Today: x{:.3}",
+        1.0 - swing,
+        1.0 + swing,
+        CHAOS_SWING,
+        variance,
+        world.daily_chaos(variance)
+    )
+}
+
+/// How reputation moves and what it's worth
+fn reputation_entry(thing: Option<&ThingDefinition>, modifiers: &RunModifiers) -> String {
+    let per_thing = match thing {
+        Some(thing) => format!(
+            "- Selling {}: {:+.4} stars a sale, {:.4} stars a day of decay\n",
+            thing.name, thing.reputation_per_sale, thing.reputation_decay
+        ),
+        None => String::new(),
+    };
+
+    format!(
+        "REPUTATION\n\
+         - Revenue: x stars / {:.1}, so a {:.1}-star business sells at full price\n\
+         - Customers who actually buy: {:.0}% at 0 stars, {:.0}% at {:.0} star, {:.0}% from {:.1} stars up\n\
+         {}\
+         - Marketing: +{:.3} stars a day per marketing level (not for Bad Things)\n\
+         - Your fortune: gains x{:.2}, losses x{:.2}\n\
+         - Under {:.0} star you're a Pariah: influencers and partners won't return your calls",
+        PAR_REPUTATION,
+        PAR_REPUTATION,
+        customer_volume_factor(0.0) * 100.0,
+        customer_volume_factor(SCANDAL_REPUTATION) * 100.0,
+        SCANDAL_REPUTATION,
+        customer_volume_factor(PAR_REPUTATION as f32) * 100.0,
+        PAR_REPUTATION,
+        per_thing,
+        MARKETING_REPUTATION_GAIN,
+        modifiers.reputation_gain,
+        modifiers.reputation_loss,
+        SCANDAL_REPUTATION
+    )
+}
+
+/// Rewrite the entries when the day moves on
+pub fn update_encyclopedia(
+    setup: RunSetup,
+    things: Res<ThingRegistry>,
+    mut query: Query<&mut Text, With<EncyclopediaText>>,
+    added_query: Query<(), Added<EncyclopediaText>>,
+    mut shown: Local<Option<GameDate>>,
+) {
+    let RunSetup {
+        game_state,
+        world,
+        modifiers,
+        difficulty,
+    } = setup;
+    if *shown == Some(world.date) && added_query.is_empty() {
+        return;
+    }
+    *shown = Some(world.date);
+
+    let variance = modifiers.chaos_variance * difficulty.chaos_variance;
+    let entries = format!(
+        "{}\n\n{}\n\n{}",
+        demand_entry(&world),
+        chaos_entry(&world, variance),
+        reputation_entry(game_state.thing_type.map(|t| &things[t]), &modifiers)
+    );
+    for mut text in &mut query {
+        **text = entries.clone();
+    }
+}
+
+/// The encyclopedia's header button only appears once the player has seen a full year
+pub fn update_encyclopedia_toggle_visibility(
+    first_times: Res<FirstTimes>,
+    mut toggles: OverlayToggleQuery,
+    mut panels: OverlayPanelQuery,
+) {
+    let available = first_times.has_seen(FirstTime::FullYear);
+    set_overlay_available(OverlayKind::Encyclopedia, available, &mut toggles, &mut panels);
+}
@@ -0,0 +1,226 @@
+//! Era skins - the UI quietly goes out of fashion as the years pass
+//!
+//! The game starts in 2012, so it starts out glossy and skeuomorphic. Flat
+//! design takes over in 2016, and from 2023 everything is an AI gradient.
+//! Panels tag themselves with a `Skinned` role and get restyled whenever the
+//! era changes; the Chirper feed picks its post style from the same era.
+//! While a Thing's scenery is showing, the backdrop and center panel let it
+//! show through. A condiment skin from the merch shop recolors the header and
+//! the side panels' edges, whatever the era.
+
+use bevy::prelude::*;
+use crate::economy::{GameDate, WorldState};
+use crate::merch::{MerchItem, MerchShop};
+use super::Scenery;
+
+/// How opaque the backdrop and center panel are over scenery
+const OVER_SCENERY_ALPHA: [(SkinRole, f32); 2] = [(SkinRole::Backdrop, 0.3), (SkinRole::Center, 0.6)];
+
+/// Design fashion of the day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Era {
+    /// 2012-2015: gloss, bevels, leather
+    Skeuomorphic,
+    /// 2016-2022: solid colors, hard edges
+    Flat,
+    /// 2023 on: everything is a purple-to-teal gradient
+    AiGradient,
+}
+
+impl Era {
+    pub fn on(date: GameDate) -> Self {
+        match date.year() {
+            ..=2015 => Era::Skeuomorphic,
+            2016..=2022 => Era::Flat,
+            _ => Era::AiGradient,
+        }
+    }
+
+    /// Look for one part of the screen
+    fn style(&self, role: SkinRole) -> PanelStyle {
+        match (self, role) {
+            (Era::Skeuomorphic, SkinRole::Backdrop) => PanelStyle::solid(Color::srgb(0.08, 0.06, 0.05)),
+            (Era::Skeuomorphic, SkinRole::Header) => {
+                PanelStyle::gradient(Color::srgb(0.3, 0.24, 0.18), Color::srgb(0.12, 0.09, 0.07))
+            }
+            (Era::Skeuomorphic, SkinRole::SidePanel) => PanelStyle {
+                border: Color::srgb(0.45, 0.35, 0.25),
+                ..PanelStyle::gradient(Color::srgb(0.14, 0.11, 0.09), Color::srgb(0.09, 0.07, 0.06))
+            },
+            (Era::Skeuomorphic, SkinRole::Center) => PanelStyle::solid(Color::srgb(0.1, 0.08, 0.07)),
+
+            (Era::Flat, SkinRole::Backdrop) => PanelStyle::solid(Color::srgb(0.05, 0.05, 0.1)),
+            (Era::Flat, SkinRole::Header) => PanelStyle::solid(Color::srgb(0.1, 0.1, 0.15)),
+            (Era::Flat, SkinRole::SidePanel) => PanelStyle::solid(Color::srgb(0.08, 0.08, 0.12)),
+            (Era::Flat, SkinRole::Center) => PanelStyle::solid(Color::srgb(0.06, 0.06, 0.1)),
+
+            (Era::AiGradient, SkinRole::Backdrop) => PanelStyle::solid(Color::srgb(0.05, 0.03, 0.1)),
+            (Era::AiGradient, SkinRole::Header) => {
+                PanelStyle::gradient(Color::srgb(0.3, 0.12, 0.45), Color::srgb(0.05, 0.3, 0.35))
+            }
+            (Era::AiGradient, SkinRole::SidePanel) => PanelStyle {
+                border: Color::srgb(0.5, 0.3, 0.8),
+                ..PanelStyle::gradient(Color::srgb(0.12, 0.06, 0.18), Color::srgb(0.04, 0.1, 0.13))
+            },
+            (Era::AiGradient, SkinRole::Center) => {
+                PanelStyle::gradient(Color::srgb(0.07, 0.04, 0.12), Color::srgb(0.03, 0.07, 0.09))
+            }
+        }
+    }
+
+    /// How a Chirper post looks, given its sentiment color
+    pub fn post_style(&self, accent: Color) -> PostStyle {
+        match self {
+            // Speech bubbles with a drop-shadow-ish outline
+            Era::Skeuomorphic => PostStyle {
+                border: UiRect::all(Val::Px(1.0)),
+                border_color: Color::srgb(0.4, 0.35, 0.3),
+                border_radius: BorderRadius::all(Val::Px(6.0)),
+                background: Color::srgb(0.16, 0.14, 0.12),
+            },
+            // A colored stripe and nothing else
+            Era::Flat => PostStyle {
+                border: UiRect::left(Val::Px(2.0)),
+                border_color: accent,
+                border_radius: BorderRadius::ZERO,
+                background: Color::NONE,
+            },
+            // Rounded cards with a glowing edge
+            Era::AiGradient => PostStyle {
+                border: UiRect::all(Val::Px(1.0)),
+                border_color: accent,
+                border_radius: BorderRadius::all(Val::Px(12.0)),
+                background: Color::srgb(0.1, 0.06, 0.16),
+            },
+        }
+    }
+}
+
+/// A merch skin's header colors (top, bottom) and panel edge
+fn merch_skin_colors(skin: MerchItem) -> Option<(Color, Color, Color)> {
+    match skin {
+        MerchItem::KetchupSkin => {
+            Some((Color::srgb(0.55, 0.1, 0.08), Color::srgb(0.25, 0.04, 0.03), Color::srgb(0.75, 0.2, 0.15)))
+        }
+        MerchItem::MustardSkin => {
+            Some((Color::srgb(0.6, 0.48, 0.08), Color::srgb(0.28, 0.2, 0.03), Color::srgb(0.85, 0.7, 0.15)))
+        }
+        MerchItem::RelishSkin => {
+            Some((Color::srgb(0.2, 0.45, 0.12), Color::srgb(0.07, 0.2, 0.05), Color::srgb(0.35, 0.65, 0.2)))
+        }
+        MerchItem::Mug | MerchItem::Nameplate | MerchItem::Bobblehead => None,
+    }
+}
+
+/// Which part of the screen a node is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkinRole {
+    /// Behind everything
+    Backdrop,
+    Header,
+    /// Terry and the upgrades
+    SidePanel,
+    /// Where the Things get made
+    Center,
+}
+
+/// Marks a node for restyling when the era changes
+#[derive(Component)]
+pub struct Skinned(pub SkinRole);
+
+struct PanelStyle {
+    background: Color,
+    /// Top to bottom, if the era is into that sort of thing
+    gradient: Option<(Color, Color)>,
+    border: Color,
+}
+
+impl PanelStyle {
+    fn solid(background: Color) -> Self {
+        Self {
+            background,
+            gradient: None,
+            border: Color::srgb(0.3, 0.3, 0.3),
+        }
+    }
+
+    fn gradient(top: Color, bottom: Color) -> Self {
+        Self {
+            gradient: Some((top, bottom)),
+            ..Self::solid(bottom)
+        }
+    }
+
+    /// Recolor with a merch skin: the header takes its colors, side panels its edge
+    fn with_skin(self, role: SkinRole, skin: Option<MerchItem>) -> Self {
+        let Some((top, bottom, edge)) = skin.and_then(merch_skin_colors) else {
+            return self;
+        };
+        match role {
+            SkinRole::Header => Self {
+                border: edge,
+                ..Self::gradient(top, bottom)
+            },
+            SkinRole::SidePanel => Self { border: edge, ..self },
+            SkinRole::Backdrop | SkinRole::Center => self,
+        }
+    }
+
+    fn with_alpha(self, alpha: f32) -> Self {
+        Self {
+            background: self.background.with_alpha(alpha),
+            gradient: self.gradient.map(|(top, bottom)| (top.with_alpha(alpha), bottom.with_alpha(alpha))),
+            ..self
+        }
+    }
+}
+
+/// How a Chirper post is drawn
+pub struct PostStyle {
+    pub border: UiRect,
+    pub border_color: Color,
+    pub border_radius: BorderRadius,
+    pub background: Color,
+}
+
+/// Restyle skinned nodes when the era or merch skin changes (or new ones appear)
+pub fn apply_era_skin(
+    mut commands: Commands,
+    world: Res<WorldState>,
+    scenery: Res<Scenery>,
+    merch: Res<MerchShop>,
+    mut current: Local<Option<(Era, Option<MerchItem>)>>,
+    added: Query<(), Added<Skinned>>,
+    mut skinned_query: Query<(Entity, &Skinned, &mut BackgroundColor, Option<&mut BorderColor>)>,
+) {
+    let era = Era::on(world.date);
+    let skin = merch.skin();
+    if *current == Some((era, skin)) && added.is_empty() && !scenery.is_changed() {
+        return;
+    }
+    *current = Some((era, skin));
+
+    for (entity, skinned, mut background, border) in &mut skinned_query {
+        let mut style = era.style(skinned.0).with_skin(skinned.0, skin);
+        if scenery.shown {
+            if let Some((_, alpha)) = OVER_SCENERY_ALPHA.iter().find(|(role, _)| *role == skinned.0) {
+                style = style.with_alpha(*alpha);
+            }
+        }
+        background.0 = style.background;
+        if let Some(mut border) = border {
+            *border = BorderColor::all(style.border);
+        }
+        match style.gradient {
+            Some((top, bottom)) => {
+                commands.entity(entity).insert(BackgroundGradient::from(LinearGradient::to_bottom(vec![
+                    ColorStop::auto(top),
+                    ColorStop::auto(bottom),
+                ])));
+            }
+            None => {
+                commands.entity(entity).remove::<BackgroundGradient>();
+            }
+        }
+    }
+}
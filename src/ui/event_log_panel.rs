@@ -0,0 +1,309 @@
+//! Event log overlay - everything that happened, filterable by category, browsable by date
+
+use bevy::prelude::*;
+use crate::economy::GameDate;
+use crate::event_log::{EventLog, LogCategory, Severity};
+use super::{spawn_overlay, OverlayKind, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+/// Most entries shown at once
+const MAX_SHOWN: usize = 60;
+
+const FILTER_ON: Color = Color::srgb(0.5, 0.9, 0.5);
+const FILTER_OFF: Color = Color::srgb(0.5, 0.5, 0.5);
+
+/// Which categories are shown, and which date the log is jumped to
+#[derive(Resource, Default)]
+pub struct EventLogView {
+    pub hidden: Vec<LogCategory>,
+    /// Show entries up to this date (None follows the latest)
+    pub until: Option<GameDate>,
+}
+
+/// Where a navigation button jumps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogJump {
+    PreviousMonth,
+    PreviousDay,
+    NextDay,
+    NextMonth,
+    Latest,
+}
+
+impl LogJump {
+    const ALL: [LogJump; 5] = [
+        LogJump::PreviousMonth,
+        LogJump::PreviousDay,
+        LogJump::NextDay,
+        LogJump::NextMonth,
+        LogJump::Latest,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            LogJump::PreviousMonth => "<< Month",
+            LogJump::PreviousDay => "< Day",
+            LogJump::NextDay => "Day >",
+            LogJump::NextMonth => "Month >>",
+            LogJump::Latest => "Latest",
+        }
+    }
+}
+
+/// Button that shows or hides a category
+#[derive(Component)]
+pub struct LogFilterButton(pub LogCategory);
+
+/// Button that jumps the log to another date
+#[derive(Component)]
+pub struct LogJumpButton(pub LogJump);
+
+/// Marker for the "showing up to" text
+#[derive(Component)]
+pub struct LogDateText;
+
+/// Container the log entries are rebuilt into
+#[derive(Component)]
+pub struct EventLogList;
+
+/// Event log overlay
+pub fn spawn_event_log_overlay(parent: &mut ChildSpawnerCommands) {
+    spawn_overlay(parent, OverlayKind::EventLog, "EVENT LOG", 440.0, |parent| {
+        parent
+            .spawn(Node {
+                width: Val::Percent(100.0),
+                flex_wrap: FlexWrap::Wrap,
+                column_gap: Val::Px(6.0),
+                row_gap: Val::Px(4.0),
+                margin: UiRect::bottom(Val::Px(6.0)),
+                ..default()
+            })
+            .with_children(|parent| {
+                for category in LogCategory::ALL {
+                    spawn_small_button(parent, category.name(), FILTER_ON, LogFilterButton(category));
+                }
+            });
+
+        parent
+            .spawn(Node {
+                width: Val::Percent(100.0),
+                flex_wrap: FlexWrap::Wrap,
+                column_gap: Val::Px(6.0),
+                row_gap: Val::Px(4.0),
+                margin: UiRect::bottom(Val::Px(6.0)),
+                ..default()
+            })
+            .with_children(|parent| {
+                for jump in LogJump::ALL {
+                    spawn_small_button(parent, jump.label(), Color::WHITE, LogJumpButton(jump));
+                }
+            });
+
+        parent.spawn((
+            Text::new(""),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            Node {
+                margin: UiRect::bottom(Val::Px(8.0)),
+                ..default()
+            },
+            LogDateText,
+        ));
+
+        parent.spawn((
+            Node {
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            EventLogList,
+        ));
+    });
+}
+
+fn spawn_small_button(parent: &mut ChildSpawnerCommands, label: &str, color: Color, marker: impl Component) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(6.0), Val::Px(4.0)),
+                justify_content: JustifyContent::Center,
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+            BackgroundColor(NORMAL_BUTTON),
+            marker,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(color),
+            ));
+        });
+}
+
+fn severity_color(severity: Severity) -> Color {
+    match severity {
+        Severity::Info => Color::srgb(0.6, 0.7, 0.9),
+        Severity::Good => Color::srgb(0.5, 0.9, 0.5),
+        Severity::Warning => Color::srgb(1.0, 0.8, 0.3),
+        Severity::Bad => Color::srgb(1.0, 0.4, 0.4),
+    }
+}
+
+/// The date with entries that a jump lands on (None means follow the latest)
+fn jump_target(dates: &[GameDate], from: GameDate, jump: LogJump) -> Option<GameDate> {
+    let target = match jump {
+        LogJump::PreviousDay => dates.iter().rev().find(|d| **d < from),
+        LogJump::NextDay => dates.iter().find(|d| **d > from),
+        LogJump::PreviousMonth => {
+            let month_start = GameDate::new(from.year(), from.month(), 1);
+            dates.iter().rev().find(|d| **d < month_start)
+        }
+        LogJump::NextMonth => {
+            let start = from.next_month();
+            let end = start.next_month();
+            dates.iter().rev().find(|d| **d >= start && **d < end).or_else(|| dates.iter().find(|d| **d >= start))
+        }
+        LogJump::Latest => return None,
+    };
+
+    // Nowhere to go stays put; landing on the newest date goes back to following the log
+    let landing = target.copied().unwrap_or(from);
+    if dates.last() == Some(&landing) {
+        None
+    } else {
+        Some(landing)
+    }
+}
+
+/// Show or hide a category
+pub fn handle_log_filter_buttons(
+    mut interaction_query: Query<(&Interaction, &LogFilterButton, &mut BackgroundColor, &Children), Changed<Interaction>>,
+    mut label_query: Query<&mut TextColor>,
+    mut view: ResMut<EventLogView>,
+) {
+    for (interaction, button, mut bg_color, children) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                let category = button.0;
+                let shown = if let Some(index) = view.hidden.iter().position(|c| *c == category) {
+                    view.hidden.remove(index);
+                    true
+                } else {
+                    view.hidden.push(category);
+                    false
+                };
+                for child in children.iter() {
+                    if let Ok(mut color) = label_query.get_mut(child) {
+                        color.0 = if shown { FILTER_ON } else { FILTER_OFF };
+                    }
+                }
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+/// Jump the log to another date
+pub fn handle_log_jump_buttons(
+    mut interaction_query: Query<(&Interaction, &LogJumpButton, &mut BackgroundColor), Changed<Interaction>>,
+    log: Res<EventLog>,
+    mut view: ResMut<EventLogView>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                let dates = log.dates();
+                let Some(latest) = dates.last().copied() else {
+                    continue;
+                };
+                view.until = jump_target(&dates, view.until.unwrap_or(latest), button.0);
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+/// Rebuild the list when something's logged or the view changes, newest first
+pub fn update_event_log_list(
+    mut commands: Commands,
+    log: Res<EventLog>,
+    view: Res<EventLogView>,
+    list_query: Query<Entity, With<EventLogList>>,
+    mut date_query: Query<&mut Text, With<LogDateText>>,
+) {
+    if !log.is_changed() && !view.is_changed() {
+        return;
+    }
+
+    for mut text in &mut date_query {
+        **text = match view.until {
+            Some(date) => format!("Showing events up to {}", date.format()),
+            None => "Showing the latest events".to_string(),
+        };
+    }
+
+    let entries: Vec<_> = log
+        .entries
+        .iter()
+        .rev()
+        .filter(|entry| view.until.is_none_or(|until| entry.date <= until))
+        .filter(|entry| !view.hidden.contains(&entry.category))
+        .take(MAX_SHOWN)
+        .collect();
+
+    for list in &list_query {
+        commands.entity(list).despawn_children().with_children(|parent| {
+            if entries.is_empty() {
+                parent.spawn((
+                    Text::new("Nothing logged yet."),
+                    TextFont {
+                        font_size: 12.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.5, 0.5, 0.5)),
+                ));
+            }
+
+            for entry in &entries {
+                parent
+                    .spawn(Node {
+                        width: Val::Percent(100.0),
+                        column_gap: Val::Px(6.0),
+                        margin: UiRect::bottom(Val::Px(3.0)),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Text::new(format!("[{}]", entry.severity.icon())),
+                            TextFont {
+                                font_size: 12.0,
+                                ..default()
+                            },
+                            TextColor(severity_color(entry.severity)),
+                        ));
+                        parent.spawn((
+                            Text::new(format!("{} {} - {}", entry.date.format(), entry.category.name(), entry.text)),
+                            TextFont {
+                                font_size: 12.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(0.85, 0.85, 0.85)),
+                        ));
+                    });
+            }
+        });
+    }
+}
@@ -0,0 +1,149 @@
+//! Fads section of the market overlay - what the public wants right now, and retooling to match
+
+use bevy::prelude::*;
+use crate::currency::money_whole;
+use crate::fads::{ChaseFadEvent, FadState};
+use crate::game_state::GameState;
+use crate::thing_type::ThingRegistry;
+use super::{ButtonInteractionQuery, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON, DISABLED_BUTTON};
+
+/// Marker for the current fad and how the player is riding it
+#[derive(Component)]
+pub struct FadStatusText;
+
+/// Marker for the "Retool" button (hidden unless there's a fad to chase)
+#[derive(Component)]
+pub struct ChaseFadButton;
+
+/// Marker for the retool button's label (shows the cost, and any whiplash)
+#[derive(Component)]
+pub struct ChaseFadText;
+
+/// Fads section (goes under the pricing section)
+pub fn spawn_fad_section(parent: &mut ChildSpawnerCommands) {
+    parent.spawn((
+        Text::new("FADS"),
+        TextFont {
+            font_size: 14.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.9, 0.8, 0.5)),
+        Node {
+            margin: UiRect::top(Val::Px(14.0)),
+            ..default()
+        },
+    ));
+
+    parent.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 12.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.7, 0.7, 0.7)),
+        Node {
+            margin: UiRect::top(Val::Px(6.0)),
+            ..default()
+        },
+        FadStatusText,
+    ));
+
+    parent
+        .spawn((
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(12.0), Val::Px(4.0)),
+                margin: UiRect::top(Val::Px(6.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                align_self: AlignSelf::FlexStart,
+                display: Display::None,
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.3, 0.7, 0.7)),
+            BackgroundColor(NORMAL_BUTTON),
+            ChaseFadButton,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                ChaseFadText,
+            ));
+        });
+}
+
+/// Retool for the current fad
+pub fn handle_chase_fad_button(
+    mut interaction_query: ButtonInteractionQuery<ChaseFadButton>,
+    fads: Res<FadState>,
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    mut chase_events: MessageWriter<ChaseFadEvent>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        let can_chase = fads.can_chase(&game_state, &things);
+
+        match *interaction {
+            Interaction::Pressed => {
+                if can_chase {
+                    *bg_color = PRESSED_BUTTON.into();
+                    chase_events.write(ChaseFadEvent);
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = if can_chase { HOVERED_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+            Interaction::None => {
+                *bg_color = if can_chase { NORMAL_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+        }
+    }
+}
+
+/// Keep the fad status and the retool button current
+pub fn update_fad_section(
+    fads: Res<FadState>,
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    mut status_query: Query<&mut Text, (With<FadStatusText>, Without<ChaseFadText>)>,
+    mut button_query: Query<&mut Node, With<ChaseFadButton>>,
+    mut label_query: Query<&mut Text, (With<ChaseFadText>, Without<FadStatusText>)>,
+) {
+    if !fads.is_changed() {
+        return;
+    }
+
+    let status = match fads.current {
+        Some(fad) if fads.riding => {
+            format!("\"{}\" - you're riding it, {} days left.", fad.name(), fads.days_left)
+        }
+        Some(fad) => format!("\"{}\" is all anyone wants ({} days left). {}", fad.name(), fads.days_left, fad.description()),
+        None => "Nothing's in fashion right now.".to_string(),
+    };
+    for mut text in &mut status_query {
+        **text = status.clone();
+    }
+
+    let show = fads.current.is_some() && !fads.riding;
+    for mut node in &mut button_query {
+        node.display = if show { Display::Flex } else { Display::None };
+    }
+
+    let whiplash = fads.whiplash_penalty();
+    let label = if whiplash > 0.0 {
+        format!(
+            "Retool ({}) - customers are getting whiplash: -{:.2} stars",
+            money_whole(FadState::retool_cost(&game_state, &things)),
+            whiplash
+        )
+    } else {
+        format!("Retool ({})", money_whole(FadState::retool_cost(&game_state, &things)))
+    };
+    for mut text in &mut label_query {
+        **text = label.clone();
+    }
+}
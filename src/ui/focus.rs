@@ -0,0 +1,301 @@
+//! Keyboard and gamepad focus - every screen can be played without a mouse
+//!
+//! Any `Button` on screen can take focus, whatever widget it belongs to. Tab
+//! and Shift+Tab (or the shoulder buttons) go through them in reading order,
+//! the arrow keys (or the D-pad) move to the nearest one in that direction,
+//! and Enter (or A) presses it, so each widget's own `Interaction` handling
+//! does the rest. While a [`FocusTrap`] panel is showing, focus stays inside
+//! it. A focused button in a scrolling panel is scrolled into view, and the
+//! ring only shows once the keyboard or a gamepad is being used.
+
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use bevy::window::CursorMoved;
+use crate::input::{HotkeyEvent, InputAction};
+
+const RING_COLOR: Color = Color::srgb(1.0, 0.85, 0.3);
+/// Buttons whose tops are within this many pixels count as one row for Tab order
+const ROW_HEIGHT: f32 = 8.0;
+
+/// Marker for a panel that keeps focus inside it while it's showing (the modals)
+#[derive(Component)]
+pub struct FocusTrap;
+
+/// Marker for the button wearing the focus ring
+#[derive(Component)]
+pub struct FocusRing;
+
+/// Which button has focus
+#[derive(Resource, Default)]
+pub struct UiFocus {
+    pub focused: Option<Entity>,
+    /// Whether the ring shows (the keyboard or a gamepad was used since the mouse)
+    pub visible: bool,
+    /// Button pressed with Enter or A last frame, to be let go
+    pressed: Option<Entity>,
+}
+
+/// A step through the focusable buttons
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FocusMove {
+    Next,
+    Previous,
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl FocusMove {
+    /// Which way it goes on screen (Tab order has no direction)
+    fn direction(&self) -> Option<Vec2> {
+        match self {
+            FocusMove::Next | FocusMove::Previous => None,
+            FocusMove::Up => Some(Vec2::NEG_Y),
+            FocusMove::Down => Some(Vec2::Y),
+            FocusMove::Left => Some(Vec2::NEG_X),
+            FocusMove::Right => Some(Vec2::X),
+        }
+    }
+}
+
+/// A button that can take focus, and where it is on screen
+struct Focusable {
+    entity: Entity,
+    center: Vec2,
+}
+
+/// The keys, gamepad buttons and mouse activity that drive focus
+#[derive(SystemParam)]
+pub struct FocusInput<'w, 's> {
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    mouse: Res<'w, ButtonInput<MouseButton>>,
+    cursor_moves: MessageReader<'w, 's, CursorMoved>,
+    gamepads: Query<'w, 's, &'static Gamepad>,
+}
+
+impl FocusInput<'_, '_> {
+    fn gamepad_pressed(&self, button: GamepadButton) -> bool {
+        self.gamepads.iter().any(|gamepad| gamepad.just_pressed(button))
+    }
+
+    fn focus_move(&self) -> Option<FocusMove> {
+        let shift = self.keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+        let moves = [
+            (KeyCode::ArrowUp, GamepadButton::DPadUp, FocusMove::Up),
+            (KeyCode::ArrowDown, GamepadButton::DPadDown, FocusMove::Down),
+            (KeyCode::ArrowLeft, GamepadButton::DPadLeft, FocusMove::Left),
+            (KeyCode::ArrowRight, GamepadButton::DPadRight, FocusMove::Right),
+        ];
+
+        if self.keys.just_pressed(KeyCode::Tab) {
+            Some(if shift { FocusMove::Previous } else { FocusMove::Next })
+        } else if self.gamepad_pressed(GamepadButton::RightTrigger) {
+            Some(FocusMove::Next)
+        } else if self.gamepad_pressed(GamepadButton::LeftTrigger) {
+            Some(FocusMove::Previous)
+        } else {
+            moves
+                .into_iter()
+                .find(|(key, button, _)| self.keys.just_pressed(*key) || self.gamepad_pressed(*button))
+                .map(|(_, _, step)| step)
+        }
+    }
+
+    fn activate(&self) -> bool {
+        self.keys.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter]) || self.gamepad_pressed(GamepadButton::South)
+    }
+
+    fn mouse_used(&mut self) -> bool {
+        let moved = self.cursor_moves.read().count() > 0;
+        moved || self.mouse.get_just_pressed().next().is_some()
+    }
+}
+
+/// Whether `entity` is laid out, i.e. neither it nor anything above it is `Display::None`
+fn is_shown(entity: Entity, node_query: &Query<(&Node, Option<&ChildOf>)>) -> bool {
+    let mut current = Some(entity);
+    while let Some(entity) = current {
+        let Ok((node, child_of)) = node_query.get(entity) else {
+            return true;
+        };
+        if node.display == Display::None {
+            return false;
+        }
+        current = child_of.map(|child_of| child_of.parent());
+    }
+    true
+}
+
+fn is_inside(entity: Entity, ancestor: Entity, node_query: &Query<(&Node, Option<&ChildOf>)>) -> bool {
+    let mut current = Some(entity);
+    while let Some(entity) = current {
+        if entity == ancestor {
+            return true;
+        }
+        current = node_query.get(entity).ok().and_then(|(_, child_of)| child_of.map(|child_of| child_of.parent()));
+    }
+    false
+}
+
+/// Where focus goes from `from` (anywhere to start with goes to the first button)
+fn next_focus(focusables: &[Focusable], from: Option<Entity>, step: FocusMove) -> Option<Entity> {
+    let Some(current) = from.and_then(|from| focusables.iter().position(|f| f.entity == from)) else {
+        return focusables.first().map(|f| f.entity);
+    };
+
+    let Some(direction) = step.direction() else {
+        let len = focusables.len();
+        let index = match step {
+            FocusMove::Previous => (current + len - 1) % len,
+            _ => (current + 1) % len,
+        };
+        return Some(focusables[index].entity);
+    };
+
+    // Nearest in that direction, with sideways distance counting double
+    let here = focusables[current].center;
+    focusables
+        .iter()
+        .filter_map(|f| {
+            let offset = f.center - here;
+            let along = offset.dot(direction);
+            (along > 1.0).then(|| (f.entity, along + 2.0 * (offset - direction * along).length()))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _)| entity)
+}
+
+/// Move focus, press the focused button, and let go of last frame's press
+pub fn navigate_focus(
+    mut input: FocusInput,
+    mut focus: ResMut<UiFocus>,
+    button_query: Query<(Entity, &ComputedNode, &UiGlobalTransform), With<Button>>,
+    mut interaction_query: Query<&mut Interaction>,
+    node_query: Query<(&Node, Option<&ChildOf>)>,
+    trap_query: Query<Entity, With<FocusTrap>>,
+    mut hotkey_events: MessageWriter<HotkeyEvent>,
+) {
+    // The mouse only lets go of what it pressed itself
+    if let Some(pressed) = focus.pressed.take() {
+        if let Ok(mut interaction) = interaction_query.get_mut(pressed) {
+            interaction.set_if_neq(Interaction::None);
+        }
+    }
+
+    if input.mouse_used() && focus.visible {
+        focus.visible = false;
+    }
+    if input.gamepad_pressed(GamepadButton::Start) {
+        hotkey_events.write(HotkeyEvent { action: InputAction::Pause });
+    }
+
+    let step = input.focus_move();
+    let activate = input.activate();
+    if step.is_none() && !activate {
+        return;
+    }
+
+    let trap = trap_query.iter().filter(|trap| is_shown(*trap, &node_query)).last();
+    let mut focusables: Vec<(f32, Focusable)> = button_query
+        .iter()
+        .filter(|(_, node, _)| node.size.x > 0.0 && node.size.y > 0.0)
+        .filter(|(entity, _, _)| is_shown(*entity, &node_query))
+        .filter(|(entity, _, _)| trap.is_none_or(|trap| is_inside(*entity, trap, &node_query)))
+        .map(|(entity, node, transform)| {
+            let center = transform.translation;
+            (center.y - node.size.y / 2.0, Focusable { entity, center })
+        })
+        .collect();
+    focusables.sort_by(|(a_top, a), (b_top, b)| {
+        let row = |top: f32| (top / ROW_HEIGHT).floor() as i32;
+        row(*a_top).cmp(&row(*b_top)).then(a.center.x.total_cmp(&b.center.x))
+    });
+    let focusables: Vec<Focusable> = focusables.into_iter().map(|(_, focusable)| focusable).collect();
+
+    // Whatever had focus may have closed since
+    if focus.focused.is_some_and(|focused| !focusables.iter().any(|f| f.entity == focused)) {
+        focus.focused = None;
+    }
+
+    if let Some(step) = step {
+        focus.visible = true;
+        focus.focused = next_focus(&focusables, focus.focused, step).or(focus.focused);
+    }
+
+    if activate {
+        let Some(focused) = focus.focused else {
+            return;
+        };
+        if let Ok(mut interaction) = interaction_query.get_mut(focused) {
+            *interaction = Interaction::Pressed;
+            focus.pressed = Some(focused);
+            focus.visible = true;
+        }
+    }
+}
+
+/// Outline the focused button while the ring is showing
+pub fn draw_focus_ring(mut commands: Commands, focus: Res<UiFocus>, ring_query: Query<Entity, With<FocusRing>>) {
+    if !focus.is_changed() {
+        return;
+    }
+
+    let target = focus.focused.filter(|_| focus.visible);
+    for entity in &ring_query {
+        if Some(entity) != target {
+            commands.entity(entity).try_remove::<(FocusRing, Outline)>();
+        }
+    }
+    if let Some(entity) = target.filter(|target| !ring_query.contains(*target)) {
+        commands
+            .entity(entity)
+            .try_insert((FocusRing, Outline::new(Val::Px(2.0), Val::Px(2.0), RING_COLOR)));
+    }
+}
+
+/// Scroll whatever panel the focused button is in so it can be seen
+pub fn scroll_focus_into_view(
+    focus: Res<UiFocus>,
+    mut scrolled_to: Local<Option<Entity>>,
+    node_query: Query<(&Node, &ComputedNode, &UiGlobalTransform, Option<&ChildOf>)>,
+    mut scroll_query: Query<&mut ScrollPosition>,
+) {
+    if *scrolled_to == focus.focused {
+        return;
+    }
+    *scrolled_to = focus.focused;
+    let Some(focused) = focus.focused else {
+        return;
+    };
+    let Ok((_, button, button_transform, child_of)) = node_query.get(focused) else {
+        return;
+    };
+
+    let button_top = button_transform.translation.y - button.size.y / 2.0;
+    let button_bottom = button_top + button.size.y;
+    let mut current = child_of.map(|child_of| child_of.parent());
+    while let Some(entity) = current {
+        let Ok((node, panel, panel_transform, child_of)) = node_query.get(entity) else {
+            return;
+        };
+        current = child_of.map(|child_of| child_of.parent());
+        if node.overflow.y != OverflowAxis::Scroll {
+            continue;
+        }
+
+        let panel_top = panel_transform.translation.y - panel.size.y / 2.0;
+        let panel_bottom = panel_top + panel.size.y;
+        let offset = if button_top < panel_top {
+            button_top - panel_top
+        } else if button_bottom > panel_bottom {
+            button_bottom - panel_bottom
+        } else {
+            return;
+        };
+        if let Ok(mut scroll) = scroll_query.get_mut(entity) {
+            scroll.y = (scroll.y + offset * panel.inverse_scale_factor()).max(0.0);
+        }
+        return;
+    }
+}
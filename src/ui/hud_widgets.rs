@@ -0,0 +1,339 @@
+//! HUD widgets - pinned stats, upgrades and channels floating over the main screen
+//!
+//! Each widget in the profile's `HudLayout` is a small box that can be dragged
+//! anywhere; letting go saves where it ended up. Upgrades and channels are
+//! pinned from their context menus, and stats from the HUD overlay, which
+//! lists every one of them.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use crate::business::{UpgradePurchasedEvent, UpgradeState};
+use crate::currency::{money, money_whole};
+use crate::economy::WorldState;
+use crate::game_state::GameState;
+use crate::hud::{HudLayout, HudStat, HudWidget};
+use crate::marketing::{ChannelToggledEvent, MarketingState};
+use crate::pivot::stock_on_hand;
+use crate::reputation::ReputationTier;
+use crate::thing_type::ThingRegistry;
+use super::{
+    spawn_overlay, ContextAction, ContextActionEvent, ContextMenuSource, ContextSubject, DemandOutlook, MainScreen,
+    OverlayKind, UiRoot, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON,
+};
+
+/// Most purchases a single "Buy max" makes
+const MAX_BULK_PURCHASES: u32 = 1000;
+/// Segments in the demand gauge
+const GAUGE_SEGMENTS: usize = 10;
+
+/// A pinned widget on screen
+#[derive(Component)]
+pub struct HudWidgetBox(pub HudWidget);
+
+/// Marker for a widget's reading
+#[derive(Component)]
+pub struct HudWidgetValue(pub HudWidget);
+
+/// Button on the HUD overlay that pins or unpins a stat
+#[derive(Component)]
+pub struct HudPinButton(pub HudStat);
+
+/// Label on a pin button
+#[derive(Component)]
+pub struct HudPinText(pub HudStat);
+
+/// Today's demand as a bar, from half of normal to half again
+fn demand_gauge(modifier: f32) -> String {
+    let filled = (((modifier - 0.5).clamp(0.0, 1.0)) * GAUGE_SEGMENTS as f32).round() as usize;
+    format!(
+        "{} [{}{}] {:+.0}%",
+        DemandOutlook::from_modifier(modifier).name(),
+        "#".repeat(filled),
+        "-".repeat(GAUGE_SEGMENTS - filled),
+        (modifier - 1.0) * 100.0
+    )
+}
+
+/// What a widget reads right now
+fn widget_value(
+    widget: HudWidget,
+    game_state: &GameState,
+    things: &ThingRegistry,
+    upgrades: &UpgradeState,
+    marketing: &MarketingState,
+    world: &WorldState,
+) -> String {
+    match widget {
+        HudWidget::Stat(stat) => match stat {
+            HudStat::Money => money(game_state.money),
+            HudStat::ThingsMade => game_state.things_produced.to_string(),
+            HudStat::Production => format!("{:.1} Things/sec", game_state.things_per_second),
+            HudStat::Customers => game_state.customers_served.to_string(),
+            HudStat::Reputation => format!("{:.2}", game_state.reputation),
+            HudStat::MarketingLevel => game_state.marketing_level.to_string(),
+            HudStat::Demand => demand_gauge(world.calculate_demand_modifier()),
+            HudStat::Stock => format!("{:.0} Things", stock_on_hand(game_state, things)),
+        },
+        HudWidget::Upgrade(upgrade) => {
+            let cost = upgrades.cost(upgrade);
+            let affordable = if game_state.money >= cost { "" } else { " (can't afford)" };
+            format!("x{} - next {}{}", upgrades.get_count(upgrade), money_whole(cost), affordable)
+        }
+        HudWidget::Channel(channel) if marketing.is_active(channel) => {
+            format!("ON ({}/day)", money_whole(marketing.channel_daily_cost(channel)))
+        }
+        HudWidget::Channel(_) => "OFF".to_string(),
+    }
+}
+
+/// Rebuild the widgets when the layout changes or the main screen is built
+pub fn spawn_hud_widgets(
+    mut commands: Commands,
+    layout: Res<HudLayout>,
+    root_query: Query<(Entity, Ref<UiRoot>), With<MainScreen>>,
+    widget_query: Query<Entity, With<HudWidgetBox>>,
+) {
+    let Some((root, added)) = root_query.iter().next().map(|(entity, root)| (entity, root.is_added())) else {
+        return;
+    };
+    if !layout.is_changed() && !added {
+        return;
+    }
+
+    for entity in &widget_query {
+        commands.entity(entity).despawn();
+    }
+    commands.entity(root).with_children(|parent| {
+        for placed in &layout.widgets {
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(placed.x),
+                        top: Val::Px(placed.y),
+                        flex_direction: FlexDirection::Column,
+                        padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    BorderColor::all(Color::srgb(0.35, 0.35, 0.5)),
+                    BackgroundColor(Color::srgba(0.08, 0.08, 0.12, 0.9)),
+                    GlobalZIndex(42),
+                    HudWidgetBox(placed.widget),
+                    ContextMenuSource(ContextSubject::from_hud_widget(placed.widget)),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(placed.widget.name()),
+                        TextFont {
+                            font_size: 11.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.6, 0.6, 0.6)),
+                    ));
+                    parent.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        HudWidgetValue(placed.widget),
+                    ));
+                });
+        }
+    });
+}
+
+/// Keep every widget's reading current
+pub fn update_hud_widgets(
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    upgrades: Res<UpgradeState>,
+    marketing: Res<MarketingState>,
+    world: Res<WorldState>,
+    mut value_query: Query<(Ref<HudWidgetValue>, &mut Text)>,
+) {
+    let changed = game_state.is_changed() || upgrades.is_changed() || marketing.is_changed() || world.is_changed();
+    for (value, mut text) in &mut value_query {
+        if !changed && !value.is_added() {
+            continue;
+        }
+        let reading = widget_value(value.0, &game_state, &things, &upgrades, &marketing, &world);
+        if **text != reading {
+            **text = reading;
+        }
+    }
+}
+
+/// Drag widgets around with the mouse, saving where they're dropped
+pub fn drag_hud_widgets(
+    mouse: Res<ButtonInput<MouseButton>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut widget_query: Query<(Entity, &Interaction, &HudWidgetBox, &mut Node)>,
+    mut dragging: Local<Option<(Entity, Vec2)>>,
+    mut layout: ResMut<HudLayout>,
+) {
+    let Some(cursor) = window_query.single().ok().and_then(|window| window.cursor_position()) else {
+        return;
+    };
+
+    if dragging.is_none() && mouse.just_pressed(MouseButton::Left) {
+        *dragging = widget_query
+            .iter()
+            .find(|(_, interaction, _, _)| **interaction == Interaction::Pressed)
+            .and_then(|(entity, _, widget, _)| {
+                let placed = layout.widgets.iter().find(|placed| placed.widget == widget.0)?;
+                Some((entity, cursor - Vec2::new(placed.x, placed.y)))
+            });
+    }
+    let Some((entity, grab)) = *dragging else {
+        return;
+    };
+    let Ok((_, _, widget, mut node)) = widget_query.get_mut(entity) else {
+        *dragging = None;
+        return;
+    };
+
+    let position = (cursor - grab).max(Vec2::ZERO);
+    node.left = Val::Px(position.x);
+    node.top = Val::Px(position.y);
+    if !mouse.pressed(MouseButton::Left) {
+        layout.place(widget.0, position);
+        *dragging = None;
+    }
+}
+
+/// Buy max, switch channels and pin or unpin, as picked from a context menu
+pub fn apply_context_actions(
+    mut action_events: MessageReader<ContextActionEvent>,
+    mut layout: ResMut<HudLayout>,
+    mut game_state: ResMut<GameState>,
+    mut upgrades: ResMut<UpgradeState>,
+    mut marketing: ResMut<MarketingState>,
+    mut purchase_events: MessageWriter<UpgradePurchasedEvent>,
+    mut toggle_events: MessageWriter<ChannelToggledEvent>,
+) {
+    for event in action_events.read() {
+        match (event.action, event.subject) {
+            (ContextAction::BuyMax, ContextSubject::Upgrade(upgrade)) => {
+                for _ in 0..MAX_BULK_PURCHASES {
+                    if !upgrades.purchase(upgrade, &mut game_state) {
+                        break;
+                    }
+                    purchase_events.write(UpgradePurchasedEvent { upgrade });
+                }
+            }
+            (ContextAction::SwitchOn | ContextAction::SwitchOff, ContextSubject::Channel(channel)) => {
+                let active = event.action == ContextAction::SwitchOn;
+                let tier = ReputationTier::from_reputation(game_state.reputation);
+                if marketing.set_active(channel, active, tier) {
+                    toggle_events.write(ChannelToggledEvent { channel, active });
+                }
+            }
+            (ContextAction::Pin, subject) => layout.pin(subject.hud_widget()),
+            (ContextAction::Unpin, subject) => layout.unpin(subject.hud_widget()),
+            _ => {}
+        }
+    }
+}
+
+/// HUD overlay - pin or unpin any stat
+pub fn spawn_hud_overlay(parent: &mut ChildSpawnerCommands) {
+    spawn_overlay(parent, OverlayKind::Hud, "HUD", 340.0, |parent| {
+        parent.spawn((
+            Text::new("Pinned widgets can be dragged anywhere. Right-click one to unpin it."),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            Node {
+                margin: UiRect::bottom(Val::Px(8.0)),
+                ..default()
+            },
+        ));
+
+        for stat in HudStat::ALL {
+            parent
+                .spawn(Node {
+                    width: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::SpaceBetween,
+                    margin: UiRect::bottom(Val::Px(6.0)),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(stat.name()),
+                        TextFont {
+                            font_size: 13.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                    ));
+                    parent
+                        .spawn((
+                            Button,
+                            Node {
+                                min_width: Val::Px(80.0),
+                                padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                                justify_content: JustifyContent::Center,
+                                border: UiRect::all(Val::Px(1.0)),
+                                ..default()
+                            },
+                            BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+                            BackgroundColor(NORMAL_BUTTON),
+                            HudPinButton(stat),
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Text::new("Pin"),
+                                TextFont {
+                                    font_size: 12.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                                HudPinText(stat),
+                            ));
+                        });
+                });
+        }
+    });
+}
+
+/// Pin or unpin a stat from the HUD overlay
+pub fn handle_hud_pin_buttons(
+    mut interaction_query: Query<(&Interaction, &HudPinButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut layout: ResMut<HudLayout>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                let widget = HudWidget::Stat(button.0);
+                if layout.contains(widget) {
+                    layout.unpin(widget);
+                } else {
+                    layout.pin(widget);
+                }
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+/// Label each pin button with what pressing it would do
+pub fn update_hud_pin_buttons(layout: Res<HudLayout>, mut text_query: Query<(Ref<HudPinText>, &mut Text)>) {
+    for (pin_text, mut text) in &mut text_query {
+        if !layout.is_changed() && !pin_text.is_added() {
+            continue;
+        }
+        let label = if layout.contains(HudWidget::Stat(pin_text.0)) { "Unpin" } else { "Pin" };
+        if **text != label {
+            **text = label.to_string();
+        }
+    }
+}
@@ -0,0 +1,152 @@
+//! Hustle meter bar and coffee button under the Make Thing button
+
+use bevy::prelude::*;
+use crate::clicker::{HustleMeter, COFFEE_COST};
+use crate::currency::money_whole;
+use crate::game_state::GameState;
+use crate::settings::Settings;
+use super::{ButtonInteractionQuery, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON, DISABLED_BUTTON};
+
+/// Marker for the hustle meter container (hidden when the meter is off)
+#[derive(Component)]
+pub struct HustleBar;
+
+/// Marker for the filled part of the hustle bar
+#[derive(Component)]
+pub struct HustleFill;
+
+/// Marker for the hustle readout
+#[derive(Component)]
+pub struct HustleText;
+
+/// Marker for the coffee button
+#[derive(Component)]
+pub struct CoffeeButton;
+
+const HUSTLE_FULL: Color = Color::srgb(0.9, 0.6, 0.2);
+const HUSTLE_EMPTY: Color = Color::srgb(0.6, 0.2, 0.2);
+
+pub fn spawn_hustle_bar(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            Node {
+                width: Val::Px(200.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                margin: UiRect::top(Val::Px(12.0)),
+                ..default()
+            },
+            HustleBar,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Hustle"),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.7, 0.5)),
+                HustleText,
+            ));
+
+            // Bar track
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Percent(100.0),
+                        height: Val::Px(12.0),
+                        margin: UiRect::vertical(Val::Px(4.0)),
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+                    BackgroundColor(Color::srgb(0.1, 0.1, 0.1)),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Node {
+                            width: Val::Percent(100.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(HUSTLE_FULL),
+                        HustleFill,
+                    ));
+                });
+
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        padding: UiRect::axes(Val::Px(10.0), Val::Px(4.0)),
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    BorderColor::all(Color::srgb(0.5, 0.35, 0.2)),
+                    BackgroundColor(NORMAL_BUTTON),
+                    CoffeeButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(format!("Coffee ({})", money_whole(COFFEE_COST))),
+                        TextFont {
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                    ));
+                });
+        });
+}
+
+/// Keep the bar's fill, color and readout in step with the meter
+pub fn update_hustle_bar(
+    hustle: Res<HustleMeter>,
+    settings: Res<Settings>,
+    mut bar_query: Query<&mut Node, (With<HustleBar>, Without<HustleFill>)>,
+    mut fill_query: Query<(&mut Node, &mut BackgroundColor), With<HustleFill>>,
+    mut text_query: Query<&mut Text, With<HustleText>>,
+) {
+    if !hustle.is_changed() && !settings.is_changed() {
+        return;
+    }
+
+    let display = if settings.hustle_meter { Display::Flex } else { Display::None };
+    for mut node in &mut bar_query {
+        node.display = display;
+    }
+
+    let fraction = hustle.fraction();
+    for (mut node, mut color) in &mut fill_query {
+        node.width = Val::Percent(fraction * 100.0);
+        color.0 = HUSTLE_EMPTY.mix(&HUSTLE_FULL, fraction);
+    }
+
+    for mut text in &mut text_query {
+        **text = format!("Hustle {:.0}/{:.0}", hustle.current, hustle.max);
+    }
+}
+
+/// Buy a coffee to top up the meter
+pub fn handle_coffee_button(
+    mut interaction_query: ButtonInteractionQuery<CoffeeButton>,
+    mut hustle: ResMut<HustleMeter>,
+    mut game_state: ResMut<GameState>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        let can_afford = game_state.money >= COFFEE_COST;
+
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                hustle.drink_coffee(&mut game_state);
+            }
+            Interaction::Hovered => {
+                *bg_color = if can_afford { HOVERED_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+            Interaction::None => {
+                *bg_color = if can_afford { NORMAL_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+        }
+    }
+}
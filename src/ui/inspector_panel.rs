@@ -0,0 +1,353 @@
+//! Resource inspector (debug builds) - F12 shows any gameplay resource's live value, and saves snapshots
+
+use bevy::prelude::*;
+use crate::dialogue::DialogueDatabase;
+use crate::profile::Profiles;
+use crate::settings::Settings;
+use crate::snapshot;
+use super::{ButtonInteractionQuery, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+/// Key that opens and closes the inspector (outside the bindable keys)
+const INSPECTOR_KEY: KeyCode = KeyCode::F12;
+/// Seconds between refreshes of the shown value
+const REFRESH_SECS: f32 = 0.5;
+/// Dialogue warnings listed under the count (the rest are in the log)
+const SHOWN_DIALOGUE_ISSUES: usize = 5;
+
+/// What the inspector is showing
+#[derive(Resource)]
+pub struct InspectorState {
+    pub open: bool,
+    pub selected: Option<String>,
+    /// Set by the save button, handled on the next refresh
+    pub save_requested: bool,
+    refresh: Timer,
+}
+
+impl Default for InspectorState {
+    fn default() -> Self {
+        Self {
+            open: false,
+            selected: None,
+            save_requested: false,
+            refresh: Timer::from_seconds(REFRESH_SECS, TimerMode::Repeating),
+        }
+    }
+}
+
+/// Marker for the inspector window (hidden until opened)
+#[derive(Component)]
+pub struct InspectorPanel;
+
+/// Container the resource buttons go into (filled the first time the inspector opens)
+#[derive(Component)]
+pub struct InspectorList;
+
+/// Button that shows a resource
+#[derive(Component)]
+pub struct InspectorResourceButton(pub String);
+
+/// Marker for the selected resource's value
+#[derive(Component)]
+pub struct InspectorValueText;
+
+/// Marker for the "Save snapshot" button
+#[derive(Component)]
+pub struct SaveSnapshotButton;
+
+/// Marker for the line under the value (where the last snapshot went)
+#[derive(Component)]
+pub struct InspectorStatusText;
+
+/// Marker for the dialogue warning count
+#[derive(Component)]
+pub struct InspectorDialogueText;
+
+pub struct InspectorPlugin;
+
+impl Plugin for InspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InspectorState>()
+            .add_systems(Startup, spawn_inspector_panel)
+            .add_systems(
+                Update,
+                (
+                    toggle_inspector,
+                    fill_inspector_list,
+                    handle_inspector_buttons,
+                    handle_save_snapshot_button,
+                    update_inspector_dialogue_text,
+                    refresh_inspector,
+                ).chain(),
+            );
+    }
+}
+
+/// Inspector window, kept across screens
+fn spawn_inspector_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(20.0),
+                left: Val::Px(20.0),
+                width: Val::Px(720.0),
+                height: Val::Percent(85.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                column_gap: Val::Px(10.0),
+                border: UiRect::all(Val::Px(2.0)),
+                display: Display::None,
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.3, 0.6, 0.9)),
+            BackgroundColor(Color::srgba(0.03, 0.05, 0.08, 0.95)),
+            GlobalZIndex(100),
+            InspectorPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    width: Val::Px(200.0),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(2.0),
+                    overflow: Overflow::scroll_y(),
+                    ..default()
+                },
+                InspectorList,
+            ));
+
+            parent
+                .spawn(Node {
+                    flex_grow: 1.0,
+                    flex_direction: FlexDirection::Column,
+                    overflow: Overflow::clip(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent
+                        .spawn(Node {
+                            column_gap: Val::Px(10.0),
+                            align_items: AlignItems::Center,
+                            margin: UiRect::bottom(Val::Px(8.0)),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            parent
+                                .spawn((
+                                    Button,
+                                    Node {
+                                        padding: UiRect::axes(Val::Px(10.0), Val::Px(4.0)),
+                                        border: UiRect::all(Val::Px(1.0)),
+                                        ..default()
+                                    },
+                                    BorderColor::all(Color::srgb(0.3, 0.5, 0.7)),
+                                    BackgroundColor(NORMAL_BUTTON),
+                                    SaveSnapshotButton,
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn((
+                                        Text::new("Save snapshot"),
+                                        TextFont {
+                                            font_size: 12.0,
+                                            ..default()
+                                        },
+                                        TextColor(Color::WHITE),
+                                    ));
+                                });
+                            parent.spawn((
+                                Text::new(""),
+                                TextFont {
+                                    font_size: 11.0,
+                                    ..default()
+                                },
+                                TextColor(Color::srgb(0.6, 0.6, 0.6)),
+                                InspectorStatusText,
+                            ));
+                        });
+                    parent.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: 11.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.9, 0.7, 0.4)),
+                        Node {
+                            margin: UiRect::bottom(Val::Px(8.0)),
+                            ..default()
+                        },
+                        InspectorDialogueText,
+                    ));
+                    parent.spawn((
+                        Text::new("Pick a resource"),
+                        TextFont {
+                            font_size: 11.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.8, 0.9, 0.8)),
+                        InspectorValueText,
+                    ));
+                });
+        });
+}
+
+/// F12 opens and closes the inspector
+fn toggle_inspector(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut inspector: ResMut<InspectorState>,
+    mut panel_query: Query<&mut Node, With<InspectorPanel>>,
+) {
+    if !keyboard.just_pressed(INSPECTOR_KEY) {
+        return;
+    }
+    inspector.open = !inspector.open;
+    for mut node in &mut panel_query {
+        node.display = if inspector.open { Display::Flex } else { Display::None };
+    }
+}
+
+/// List every registered gameplay resource the first time the inspector opens
+fn fill_inspector_list(
+    mut commands: Commands,
+    inspector: Res<InspectorState>,
+    registry: Res<AppTypeRegistry>,
+    list_query: Query<(Entity, Has<Children>), With<InspectorList>>,
+) {
+    if !inspector.open {
+        return;
+    }
+    for (list, filled) in &list_query {
+        if filled {
+            continue;
+        }
+        commands.entity(list).with_children(|parent| {
+            for name in snapshot::resource_names(&registry.read()) {
+                parent
+                    .spawn((
+                        Button,
+                        Node {
+                            padding: UiRect::axes(Val::Px(6.0), Val::Px(2.0)),
+                            ..default()
+                        },
+                        BackgroundColor(NORMAL_BUTTON),
+                        InspectorResourceButton(name.clone()),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Text::new(name),
+                            TextFont {
+                                font_size: 11.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                        ));
+                    });
+            }
+        });
+    }
+}
+
+/// Pick the resource to show
+fn handle_inspector_buttons(
+    mut interaction_query: Query<(&Interaction, &InspectorResourceButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut inspector: ResMut<InspectorState>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                inspector.selected = Some(button.0.clone());
+                // Show it straight away
+                let duration = inspector.refresh.duration();
+                inspector.refresh.set_elapsed(duration);
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+/// Ask for a snapshot of every resource
+fn handle_save_snapshot_button(
+    mut interaction_query: ButtonInteractionQuery<SaveSnapshotButton>,
+    mut inspector: ResMut<InspectorState>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                inspector.save_requested = true;
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Count what was skipped loading the dialogue files, and list the first few
+fn update_inspector_dialogue_text(
+    dialogue_db: Res<DialogueDatabase>,
+    added: Query<(), Added<InspectorDialogueText>>,
+    mut text_query: Query<&mut Text, With<InspectorDialogueText>>,
+) {
+    if !dialogue_db.is_changed() && added.is_empty() {
+        return;
+    }
+
+    let issues = &dialogue_db.issues;
+    let mut summary = format!("Dialogue: {} lines, {} warnings", dialogue_db.by_id.len(), issues.len());
+    for issue in issues.iter().take(SHOWN_DIALOGUE_ISSUES) {
+        summary += &format!("\n  {}", issue);
+    }
+    if issues.len() > SHOWN_DIALOGUE_ISSUES {
+        summary += &format!("\n  ...and {} more (see the log)", issues.len() - SHOWN_DIALOGUE_ISSUES);
+    }
+    for mut text in &mut text_query {
+        **text = summary.clone();
+    }
+}
+
+/// Re-read the selected resource every so often, and save a snapshot when asked
+/// (exclusive, since reflection reads resources straight from the world)
+fn refresh_inspector(world: &mut World) {
+    let delta = world.resource::<Time>().delta();
+    let (open, save_requested, due, selected) = {
+        let mut inspector = world.resource_mut::<InspectorState>();
+        let due = inspector.refresh.tick(delta).just_finished();
+        let save_requested = std::mem::take(&mut inspector.save_requested);
+        (inspector.open, save_requested, due, inspector.selected.clone())
+    };
+    if !open {
+        return;
+    }
+
+    if save_requested {
+        let dir = world
+            .resource::<Settings>()
+            .export_location
+            .dir(&world.resource::<Profiles>().dir());
+        let status = match snapshot::save(world, &dir) {
+            Ok(path) => format!("Saved {}", path.display()),
+            Err(err) => {
+                warn!("Snapshot failed: {}", err);
+                format!("Snapshot failed: {}", err)
+            }
+        };
+        let mut status_query = world.query_filtered::<&mut Text, With<InspectorStatusText>>();
+        for mut text in status_query.iter_mut(world) {
+            **text = status.clone();
+        }
+    }
+
+    let Some(name) = selected.filter(|_| due) else {
+        return;
+    };
+    let value = snapshot::describe(world, &name).unwrap_or_else(|| format!("{} isn't in the world", name));
+    let mut value_query = world.query_filtered::<&mut Text, With<InspectorValueText>>();
+    for mut text in value_query.iter_mut(world) {
+        **text = format!("{}\n\n{}", name, value);
+    }
+}
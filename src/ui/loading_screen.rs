@@ -0,0 +1,153 @@
+//! Loading screen - a progress bar and Terry killing time while content preloads
+
+use bevy::prelude::*;
+use crate::dialogue::DialogueDatabase;
+use crate::game_state::AppState;
+use crate::loading::Preloads;
+use super::UiRoot;
+
+/// Real seconds each quip stays up
+const QUIP_SECS: f32 = 2.5;
+/// Said when the dialogue files have no loading quips
+const FALLBACK_QUIP: &str = "Straightening my tie. Give me a second.";
+
+/// Marker for loading screen elements (despawned when it's done)
+#[derive(Component)]
+#[require(DespawnOnExit<AppState> = DespawnOnExit(AppState::Loading))]
+pub struct LoadingScreen;
+
+/// Marker for the filled part of the progress bar
+#[derive(Component)]
+pub struct LoadingBarFill;
+
+/// Marker for the line saying what's loading
+#[derive(Component)]
+pub struct LoadingStatusText;
+
+/// Marker for Terry's quip
+#[derive(Component)]
+pub struct LoadingQuipText;
+
+pub fn setup_loading_screen(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(16.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.05, 0.05, 0.1)),
+            UiRoot,
+            LoadingScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("THING SIMULATOR 2012"),
+                TextFont {
+                    font_size: 32.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.85, 0.4)),
+            ));
+
+            parent.spawn((
+                Text::new("🌭"),
+                TextFont {
+                    font_size: 48.0,
+                    ..default()
+                },
+            ));
+
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.8, 0.6)),
+                TextLayout::new_with_justify(Justify::Center),
+                Node {
+                    max_width: Val::Px(520.0),
+                    min_height: Val::Px(40.0),
+                    ..default()
+                },
+                LoadingQuipText,
+            ));
+
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(360.0),
+                        height: Val::Px(14.0),
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+                    BackgroundColor(Color::srgb(0.1, 0.1, 0.15)),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Node {
+                            width: Val::Percent(0.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.95, 0.8, 0.15)),
+                        LoadingBarFill,
+                    ));
+                });
+
+            parent.spawn((
+                Text::new("Loading..."),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.6, 0.6)),
+                LoadingStatusText,
+            ));
+        });
+}
+
+/// Fill the bar, say what's loading, and swap Terry's quip now and then
+pub fn update_loading_screen(
+    time: Res<Time<Real>>,
+    preloads: Res<Preloads>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut bar_query: Query<&mut Node, With<LoadingBarFill>>,
+    mut status_query: Query<&mut Text, (With<LoadingStatusText>, Without<LoadingQuipText>)>,
+    mut quip_query: Query<&mut Text, (With<LoadingQuipText>, Without<LoadingStatusText>)>,
+    mut since_quip: Local<Option<f32>>,
+) {
+    for mut node in &mut bar_query {
+        node.width = Val::Percent(preloads.progress() * 100.0);
+    }
+
+    let status = match preloads.loading {
+        Some(kind) => format!("Loading {}... {}/{}", kind.label(), preloads.done, preloads.total()),
+        None => "Ready".to_string(),
+    };
+    for mut text in &mut status_query {
+        if **text != status {
+            **text = status.clone();
+        }
+    }
+
+    // The first quip goes up straight away
+    let elapsed = since_quip.get_or_insert(QUIP_SECS);
+    *elapsed += time.delta_secs();
+    if *elapsed < QUIP_SECS {
+        return;
+    }
+    *elapsed = 0.0;
+    let quip = dialogue_db
+        .get_for_trigger("loading_quip")
+        .map_or(FALLBACK_QUIP, |line| line.text.as_str());
+    for mut text in &mut quip_query {
+        **text = format!("\"{}\"", quip);
+    }
+}
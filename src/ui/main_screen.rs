@@ -1,14 +1,27 @@
 //! Main game screen UI
 
 use bevy::prelude::*;
-use crate::game_state::GameState;
-use crate::business::{UpgradeState, UpgradeType};
-use crate::clicker::ClickEvent;
+use bevy::ecs::query::QueryFilter;
+use bevy::ecs::system::SystemParam;
+use crate::attract::AttractMode;
+use crate::currency::{money, money_whole};
+use crate::game_state::{GameState, InGame};
+use crate::business::{UpgradePurchasedEvent, UpgradeState, UpgradeType};
+use crate::clicker::HustleMeter;
 use crate::economy::WorldState;
-use super::{UiRoot, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON, DISABLED_BUTTON};
-
-/// Marker for main game screen elements
+use crate::fortune::RunModifiers;
+use crate::grace_period::UndoThingSelectionEvent;
+use crate::input::InputMap;
+use crate::marketing::MarketingState;
+use crate::org::Organization;
+use crate::settings::{ReputationStyle, Settings};
+use crate::thing_type::ThingRegistry;
+use crate::wellbeing::Wellbeing;
+use super::{ButtonInteractionQuery, ContextMenuSource, ContextSubject, OverlayKind, SkinRole, Skinned, UiRoot, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON, DISABLED_BUTTON};
+
+/// Marker for main game screen elements (despawned on leaving the game)
 #[derive(Component)]
+#[require(DespawnOnExit<InGame> = DespawnOnExit(InGame))]
 pub struct MainScreen;
 
 /// Marker for the "Make Thing" button
@@ -39,6 +52,18 @@ pub struct ProductionText;
 #[derive(Component)]
 pub struct DateText;
 
+/// Marker for click power display on the Make Thing button
+#[derive(Component)]
+pub struct ClickPowerText;
+
+/// Marker for marketing level display
+#[derive(Component)]
+pub struct MarketingText;
+
+/// Marker for the current Thing type display
+#[derive(Component)]
+pub struct ThingTypeText;
+
 /// Marker for upgrade buttons
 #[derive(Component)]
 pub struct UpgradeButton(pub UpgradeType);
@@ -47,8 +72,32 @@ pub struct UpgradeButton(pub UpgradeType);
 #[derive(Component)]
 pub struct UpgradeCostText(pub UpgradeType);
 
-pub fn setup_main_screen(mut commands: Commands, game_state: Res<GameState>, world: Res<WorldState>) {
-    let thing_type = game_state.thing_type.unwrap_or_default();
+/// Marker for the fortune cookie line under the stats
+#[derive(Component)]
+pub struct FortuneText;
+
+/// Marker for the pause menu's "pick a different Thing" button
+#[derive(Component)]
+pub struct UndoThingButton;
+
+/// The player's settings and key bindings, for the overlays built with the screen
+#[derive(SystemParam)]
+pub struct Preferences<'w> {
+    settings: Res<'w, Settings>,
+    input_map: Res<'w, InputMap>,
+}
+
+pub fn setup_main_screen(
+    mut commands: Commands,
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    world: Res<WorldState>,
+    preferences: Preferences,
+    marketing: Res<MarketingState>,
+    attract: Res<AttractMode>,
+) {
+    let Preferences { settings, input_map } = preferences;
+    let thing = &things[game_state.thing_type.unwrap_or_default()];
     let date_str = world.date.format();
 
     commands
@@ -60,6 +109,7 @@ pub fn setup_main_screen(mut commands: Commands, game_state: Res<GameState>, wor
                 ..default()
             },
             BackgroundColor(Color::srgb(0.05, 0.05, 0.1)),
+            Skinned(SkinRole::Backdrop),
             UiRoot,
             MainScreen,
         ))
@@ -75,6 +125,7 @@ pub fn setup_main_screen(mut commands: Commands, game_state: Res<GameState>, wor
                     ..default()
                 },
                 BackgroundColor(Color::srgb(0.1, 0.1, 0.15)),
+                Skinned(SkinRole::Header),
             ))
             .with_children(|parent| {
                 parent.spawn((
@@ -97,14 +148,56 @@ pub fn setup_main_screen(mut commands: Commands, game_state: Res<GameState>, wor
                     DateText,
                 ));
 
+                super::demand_weather::spawn_demand_weather(parent);
+
                 parent.spawn((
-                    Text::new(format!("Your Thing: {}", thing_type.name())),
+                    Text::new(format!("Your Thing: {}", thing.name)),
                     TextFont {
                         font_size: 20.0,
                         ..default()
                     },
-                    TextColor(thing_type.color()),
+                    TextColor(thing.color()),
+                    ThingTypeText,
                 ));
+
+                // Overlay toggles (wrapping once there are more than fit on one line)
+                parent
+                    .spawn(Node {
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::FlexEnd,
+                        flex_wrap: FlexWrap::Wrap,
+                        row_gap: Val::Px(4.0),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::Recovery, "Redemption");
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::Pivot, "Pivot");
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::Research, "R&D");
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::Seminars, "Seminars");
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::Quests, "Quests");
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::Automation, "Automation");
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::OrgChart, "Org Chart");
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::Marketing, "Marketing");
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::Market, "Market Share");
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::Surveys, "Surveys");
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::Counterfeits, "Fakes");
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::Returns, "Returns");
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::Suppliers, "Suppliers");
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::Venues, "Pop-ups");
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::Bundles, "Bundles");
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::Editions, "Editions");
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::Eco, "Eco");
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::Chirper, "Chirper");
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::Wardrobe, "Wardrobe");
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::Merch, "Merch");
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::Inbox, "Finances");
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::EventLog, "Log");
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::Replay, "Replay");
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::Hud, "HUD");
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::Encyclopedia, "Economy");
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::Stats, "Stats");
+                        super::overlay::spawn_overlay_toggle(parent, OverlayKind::Settings, "Settings");
+                    });
             });
 
             // Main content area
@@ -120,11 +213,81 @@ pub fn setup_main_screen(mut commands: Commands, game_state: Res<GameState>, wor
                     spawn_terry_panel(parent);
 
                     // Center panel - Stats and clicker
-                    spawn_center_panel(parent, &game_state);
+                    spawn_center_panel(parent, &game_state, &settings);
 
                     // Right panel - Upgrades
                     spawn_upgrades_panel(parent);
                 });
+
+            // Overlays (toggled from the header)
+            super::marketing_panel::spawn_marketing_overlay(parent, &marketing, &game_state);
+            super::recovery_panel::spawn_recovery_overlay(parent);
+            super::pivot_panel::spawn_pivot_overlay(parent, &things);
+            super::research_panel::spawn_research_overlay(parent);
+            super::seminar_panel::spawn_seminar_overlay(parent);
+            super::quest_panel::spawn_quest_overlay(parent);
+            super::automation_panel::spawn_automation_overlay(parent);
+            super::org_panel::spawn_org_overlay(parent);
+            super::market_panel::spawn_market_overlay(parent);
+            super::survey_panel::spawn_survey_overlay(parent);
+            super::counterfeit_panel::spawn_counterfeit_overlay(parent);
+            super::returns_panel::spawn_returns_overlay(parent);
+            super::supplier_panel::spawn_supplier_overlay(parent);
+            super::venue_panel::spawn_venue_overlay(parent);
+            super::bundle_panel::spawn_bundle_overlay(parent);
+            super::edition_panel::spawn_edition_overlay(parent);
+            super::eco_panel::spawn_eco_overlay(parent);
+            super::social_panel::spawn_social_overlay(parent);
+            super::wardrobe_panel::spawn_wardrobe_overlay(parent);
+            super::merch_panel::spawn_merch_overlay(parent);
+            super::encyclopedia_panel::spawn_encyclopedia_overlay(parent);
+            super::stats_panel::spawn_stats_overlay(parent);
+            super::report_panel::spawn_inbox_overlay(parent);
+            super::event_log_panel::spawn_event_log_overlay(parent);
+            super::replay_panel::spawn_replay_overlay(parent);
+            super::hud_widgets::spawn_hud_overlay(parent);
+            super::report_panel::spawn_report_modal(parent);
+            super::wrapped_panel::spawn_wrapped_card(parent);
+            super::reward_panel::spawn_reward_cards(parent);
+            super::vignette::spawn_milestone_vignette(parent);
+            super::settings_panel::spawn_settings_overlay(parent, &settings, &input_map);
+
+            // Hourly break reminder
+            super::session_panel::spawn_reminder_panel(parent);
+            super::tutorial_card::spawn_tutorial_card(parent);
+            super::objectives_panel::spawn_objectives_tracker(parent);
+            super::parallel_panel::spawn_parallel_panel(parent);
+
+            // Demo banner (attract mode only)
+            if attract.running {
+                parent
+                    .spawn((
+                        Node {
+                            position_type: PositionType::Absolute,
+                            bottom: Val::Px(20.0),
+                            left: Val::Percent(50.0),
+                            margin: UiRect::left(Val::Px(-170.0)),
+                            width: Val::Px(340.0),
+                            padding: UiRect::all(Val::Px(8.0)),
+                            justify_content: JustifyContent::Center,
+                            border: UiRect::all(Val::Px(2.0)),
+                            ..default()
+                        },
+                        BorderColor::all(Color::srgb(0.8, 0.5, 0.2)),
+                        BackgroundColor(Color::srgb(0.15, 0.1, 0.05)),
+                        GlobalZIndex(60),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Text::new("DEMO - press any key to play"),
+                            TextFont {
+                                font_size: 20.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(0.9, 0.6, 0.3)),
+                        ));
+                    });
+            }
         });
 }
 
@@ -141,6 +304,7 @@ fn spawn_terry_panel(parent: &mut ChildSpawnerCommands) {
             },
             BorderColor::all(Color::srgb(0.3, 0.3, 0.3)),
             BackgroundColor(Color::srgb(0.08, 0.08, 0.12)),
+            Skinned(SkinRole::SidePanel),
         ))
         .with_children(|parent| {
             // Terry placeholder image area
@@ -169,48 +333,106 @@ fn spawn_terry_panel(parent: &mut ChildSpawnerCommands) {
                         justify: Justify::Center,
                         ..default()
                     },
+                    super::vacation_panel::AdvisorPortraitText,
                 ));
-            });
 
-            // Terry dialogue label
-            parent.spawn((
-                Text::new("Terry says:"),
-                TextFont {
-                    font_size: 16.0,
-                    ..default()
-                },
-                TextColor(Color::srgb(0.7, 0.7, 0.7)),
-                Node {
-                    margin: UiRect::bottom(Val::Px(5.0)),
-                    ..default()
-                },
-            ));
+                // Accessories, layered over the portrait
+                super::wardrobe_panel::spawn_cosmetic_layers(parent);
+
+                // Flinching, sweating and showing off
+                super::terry_reactions::spawn_reaction_layers(parent);
+
+                // Merch on the desk
+                super::merch_panel::spawn_desk_merch_layer(parent);
 
-            // Terry dialogue box
+                // Speech bubble layout (floats over the center panel)
+                super::terry_box::spawn_terry_bubble(parent);
+            });
+
+            // Terry dialogue panel layout (label + box)
             parent
                 .spawn((
                     Node {
                         width: Val::Percent(100.0),
-                        min_height: Val::Px(120.0),
-                        padding: UiRect::all(Val::Px(10.0)),
+                        flex_direction: FlexDirection::Column,
+                        ..default()
+                    },
+                    super::terry_box::TerryDialoguePanel,
+                ))
+                .with_children(|parent| {
+                    // Terry dialogue label
+                    parent.spawn((
+                        Text::new("Terry says:"),
+                        TextFont {
+                            font_size: 16.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                        Node {
+                            margin: UiRect::bottom(Val::Px(5.0)),
+                            ..default()
+                        },
+                        super::vacation_panel::AdvisorNameText,
+                    ));
+
+                    // Terry dialogue box
+                    parent
+                        .spawn((
+                            Node {
+                                width: Val::Percent(100.0),
+                                min_height: Val::Px(120.0),
+                                padding: UiRect::all(Val::Px(10.0)),
+                                border: UiRect::all(Val::Px(1.0)),
+                                ..default()
+                            },
+                            BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+                            BackgroundColor(Color::srgb(0.1, 0.1, 0.15)),
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Text::new("Loading Terry's wisdom..."),
+                                TextFont {
+                                    font_size: 14.0,
+                                    ..default()
+                                },
+                                TextColor(Color::srgb(0.9, 0.85, 0.7)),
+                                super::terry_box::TerryDialogueText,
+                            ));
+                        });
+                });
+
+            // Skip button (moves on to the next queued line)
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        align_self: AlignSelf::FlexEnd,
+                        padding: UiRect::axes(Val::Px(10.0), Val::Px(4.0)),
+                        margin: UiRect::top(Val::Px(5.0)),
                         border: UiRect::all(Val::Px(1.0)),
                         ..default()
                     },
                     BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
-                    BackgroundColor(Color::srgb(0.1, 0.1, 0.15)),
+                    BackgroundColor(NORMAL_BUTTON),
+                    super::terry_box::TerrySkipButton,
                 ))
                 .with_children(|parent| {
                     parent.spawn((
-                        Text::new("Loading Terry's wisdom..."),
+                        Text::new("Skip >"),
                         TextFont {
-                            font_size: 14.0,
+                            font_size: 12.0,
                             ..default()
                         },
-                        TextColor(Color::srgb(0.9, 0.85, 0.7)),
-                        super::terry_box::TerryDialogueText,
+                        TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                        super::terry_box::TerrySkipText,
                     ));
                 });
 
+            super::vacation_panel::spawn_vacation_prompt(parent);
+
+            // Terry and Doug arguing it out
+            super::debate_panel::spawn_debate_panel(parent);
+
             // Terry's reason for being here
             parent.spawn((
                 Text::new("(Your mother asked him to help)"),
@@ -227,7 +449,7 @@ fn spawn_terry_panel(parent: &mut ChildSpawnerCommands) {
         });
 }
 
-fn spawn_center_panel(parent: &mut ChildSpawnerCommands, game_state: &GameState) {
+fn spawn_center_panel(parent: &mut ChildSpawnerCommands, game_state: &GameState, settings: &Settings) {
     parent
         .spawn((
             Node {
@@ -239,6 +461,7 @@ fn spawn_center_panel(parent: &mut ChildSpawnerCommands, game_state: &GameState)
                 ..default()
             },
             BackgroundColor(Color::srgb(0.06, 0.06, 0.1)),
+            Skinned(SkinRole::Center),
         ))
         .with_children(|parent| {
             // Stats display
@@ -266,7 +489,7 @@ fn spawn_center_panel(parent: &mut ChildSpawnerCommands, game_state: &GameState)
 
                     // Money
                     parent.spawn((
-                        Text::new(format!("${:.2}", game_state.money)),
+                        Text::new(money(game_state.money)),
                         TextFont {
                             font_size: 28.0,
                             ..default()
@@ -296,7 +519,7 @@ fn spawn_center_panel(parent: &mut ChildSpawnerCommands, game_state: &GameState)
 
                     // Reputation
                     parent.spawn((
-                        Text::new(format!("Reputation: {}", reputation_stars(game_state.reputation))),
+                        Text::new(reputation_label(game_state.reputation, settings.reputation_style)),
                         TextFont {
                             font_size: 20.0,
                             ..default()
@@ -308,6 +531,21 @@ fn spawn_center_panel(parent: &mut ChildSpawnerCommands, game_state: &GameState)
                             ..default()
                         },
                     ));
+
+                    // Fortune cookie (empty when none was drawn)
+                    parent.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.8, 0.65, 0.9)),
+                        FortuneText,
+                        Node {
+                            margin: UiRect::top(Val::Px(5.0)),
+                            ..default()
+                        },
+                    ));
                 });
 
             // Make Thing button
@@ -358,6 +596,7 @@ fn spawn_center_panel(parent: &mut ChildSpawnerCommands, game_state: &GameState)
                                 ..default()
                             },
                             TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                            ClickPowerText,
                             Node {
                                 margin: UiRect::top(Val::Px(10.0)),
                                 ..default()
@@ -366,6 +605,13 @@ fn spawn_center_panel(parent: &mut ChildSpawnerCommands, game_state: &GameState)
                     });
                 });
 
+            // Hustle meter (hidden when switched off in settings)
+            super::hustle_bar::spawn_hustle_bar(parent);
+            super::wellbeing_bar::spawn_wellbeing_bar(parent);
+
+            // Picket line (hidden unless the workers walk out)
+            super::strike_panel::spawn_picket_line(parent);
+
             // Marketing level indicator
             parent.spawn((
                 Text::new(format!("Marketing Level: {}", game_state.marketing_level)),
@@ -374,6 +620,7 @@ fn spawn_center_panel(parent: &mut ChildSpawnerCommands, game_state: &GameState)
                     ..default()
                 },
                 TextColor(Color::srgb(0.6, 0.8, 0.9)),
+                MarketingText,
                 Node {
                     margin: UiRect::top(Val::Px(20.0)),
                     ..default()
@@ -396,8 +643,12 @@ fn spawn_upgrades_panel(parent: &mut ChildSpawnerCommands) {
             },
             BorderColor::all(Color::srgb(0.3, 0.3, 0.3)),
             BackgroundColor(Color::srgb(0.08, 0.08, 0.12)),
+            Skinned(SkinRole::SidePanel),
         ))
         .with_children(|parent| {
+            super::pandemic_panel::spawn_pandemic_section(parent);
+            super::ai_panel::spawn_ai_section(parent);
+
             // Production upgrades header
             parent.spawn((
                 Text::new("PRODUCTION"),
@@ -413,7 +664,13 @@ fn spawn_upgrades_panel(parent: &mut ChildSpawnerCommands) {
             ));
 
             // Production upgrade buttons
-            for upgrade in [UpgradeType::BetterTools, UpgradeType::HireWorker, UpgradeType::Automation] {
+            for upgrade in [
+                UpgradeType::BetterTools,
+                UpgradeType::HireWorker,
+                UpgradeType::Automation,
+                UpgradeType::StandingDesk,
+                UpgradeType::SolarPanels,
+            ] {
                 spawn_upgrade_button(parent, upgrade);
             }
 
@@ -451,13 +708,10 @@ fn spawn_upgrade_button(parent: &mut ChildSpawnerCommands, upgrade: UpgradeType)
                 border: UiRect::all(Val::Px(2.0)),
                 ..default()
             },
-            BorderColor::all(if upgrade.is_production() {
-                Color::srgb(0.3, 0.5, 0.8)
-            } else {
-                Color::srgb(0.8, 0.5, 0.3)
-            }),
+            BorderColor::all(upgrade_border_color(upgrade)),
             BackgroundColor(NORMAL_BUTTON),
             UpgradeButton(upgrade),
+            ContextMenuSource(ContextSubject::Upgrade(upgrade)),
         ))
         .with_children(|parent| {
             parent.spawn((
@@ -479,7 +733,7 @@ fn spawn_upgrade_button(parent: &mut ChildSpawnerCommands, upgrade: UpgradeType)
             ));
 
             parent.spawn((
-                Text::new(format!("${:.0}", upgrade.base_cost())),
+                Text::new(money_whole(upgrade.base_cost())),
                 TextFont {
                     font_size: 14.0,
                     ..default()
@@ -490,6 +744,18 @@ fn spawn_upgrade_button(parent: &mut ChildSpawnerCommands, upgrade: UpgradeType)
         });
 }
 
+/// Resting border color for an upgrade button
+pub fn upgrade_border_color(upgrade: UpgradeType) -> Color {
+    if upgrade.is_production() {
+        Color::srgb(0.3, 0.5, 0.8)
+    } else {
+        Color::srgb(0.8, 0.5, 0.3)
+    }
+}
+
+/// Cells in the reputation bar (a quarter star each)
+const REPUTATION_BAR_CELLS: usize = 20;
+
 fn reputation_stars(reputation: f32) -> String {
     let full_stars = reputation.floor() as usize;
     let has_half = reputation.fract() >= 0.5;
@@ -503,35 +769,107 @@ fn reputation_stars(reputation: f32) -> String {
     stars
 }
 
+/// Reputation as the player asked to see it
+fn reputation_label(reputation: f32, style: ReputationStyle) -> String {
+    match style {
+        ReputationStyle::Stars => format!("Reputation: {}", reputation_stars(reputation)),
+        ReputationStyle::Number => format!("Reputation: {:.1} / 5", reputation),
+        ReputationStyle::Bar => {
+            let filled = ((reputation / 5.0) * REPUTATION_BAR_CELLS as f32).round() as usize;
+            let filled = filled.min(REPUTATION_BAR_CELLS);
+            format!("Reputation: {}{}", "▓".repeat(filled), "░".repeat(REPUTATION_BAR_CELLS - filled))
+        }
+    }
+}
+
 pub fn update_stats_display(
     game_state: Res<GameState>,
     world: Res<WorldState>,
-    mut things_query: Query<&mut Text, (With<ThingsText>, Without<MoneyText>, Without<ReputationText>, Without<ProductionText>, Without<DateText>)>,
-    mut money_query: Query<&mut Text, (With<MoneyText>, Without<ThingsText>, Without<ReputationText>, Without<ProductionText>, Without<DateText>)>,
-    mut rep_query: Query<&mut Text, (With<ReputationText>, Without<ThingsText>, Without<MoneyText>, Without<ProductionText>, Without<DateText>)>,
-    mut prod_query: Query<&mut Text, (With<ProductionText>, Without<ThingsText>, Without<MoneyText>, Without<ReputationText>, Without<DateText>)>,
-    mut date_query: Query<&mut Text, (With<DateText>, Without<ThingsText>, Without<MoneyText>, Without<ReputationText>, Without<ProductionText>)>,
+    settings: Res<Settings>,
+    mut things_query: Query<&mut Text, (With<ThingsText>, Without<MoneyText>, Without<ReputationText>, Without<DateText>)>,
+    mut money_query: Query<&mut Text, (With<MoneyText>, Without<ThingsText>, Without<ReputationText>, Without<DateText>)>,
+    mut rep_query: Query<&mut Text, (With<ReputationText>, Without<ThingsText>, Without<MoneyText>, Without<DateText>)>,
+    mut date_query: Query<&mut Text, (With<DateText>, Without<ThingsText>, Without<MoneyText>, Without<ReputationText>)>,
 ) {
-    for mut text in &mut things_query {
-        **text = format!("Things: {}", game_state.things_produced);
+    // Only touch text that changed, so unchanged labels aren't laid out again
+    set_text(&mut things_query, format!("Things: {}", game_state.things_produced));
+    set_text(&mut money_query, money(game_state.money));
+    set_text(&mut rep_query, reputation_label(game_state.reputation, settings.reputation_style));
+    set_text(&mut date_query, world.date.format());
+}
+
+/// Write a value into every text in a query that doesn't already show it
+fn set_text<F: QueryFilter>(query: &mut Query<&mut Text, F>, value: String) {
+    for mut text in query {
+        if **text != value {
+            **text = value.clone();
+        }
     }
+}
 
-    for mut text in &mut money_query {
-        **text = format!("${:.2}", game_state.money);
+/// Actual production rate, including managers and the Thing type
+pub fn update_production_display(
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    org: Res<Organization>,
+    upgrades: Res<UpgradeState>,
+    mut prod_query: Query<&mut Text, With<ProductionText>>,
+) {
+    let multiplier = game_state.thing_type.map_or(1.0, |t| things[t].production_multiplier);
+    let actual_rate = org.effective_rate(game_state.things_per_second, upgrades.workers) * multiplier;
+    let rate = if game_state.on_strike {
+        "0.0 Things/sec (on strike)".to_string()
+    } else if game_state.power_out {
+        "0.0 Things/sec (power out)".to_string()
+    } else if game_state.supply_cut {
+        "0.0 Things/sec (no materials)".to_string()
+    } else {
+        format!("{:.1} Things/sec", actual_rate)
+    };
+    set_text(&mut prod_query, rate);
+}
+
+/// Keep upgrade-driven stats (click power, marketing level) in sync
+pub fn update_upgrade_stats_display(
+    game_state: Res<GameState>,
+    mut click_query: Query<&mut Text, (With<ClickPowerText>, Without<MarketingText>)>,
+    mut marketing_query: Query<&mut Text, (With<MarketingText>, Without<ClickPowerText>)>,
+) {
+    if !game_state.is_changed() {
+        return;
     }
 
-    for mut text in &mut rep_query {
-        **text = format!("Reputation: {}", reputation_stars(game_state.reputation));
+    for mut text in &mut click_query {
+        **text = format!("+{} Thing", game_state.click_power);
     }
 
-    for mut text in &mut prod_query {
-        let multiplier = game_state.thing_type.map(|t| t.production_multiplier()).unwrap_or(1.0);
-        let actual_rate = game_state.things_per_second * multiplier;
-        **text = format!("{:.1} Things/sec", actual_rate);
+    for mut text in &mut marketing_query {
+        **text = format!("Marketing Level: {}", game_state.marketing_level);
     }
+}
 
-    for mut text in &mut date_query {
-        **text = world.date.format();
+/// Keep the header's Thing type current through pivots and rebrands
+pub fn update_thing_type_display(
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    mut query: Query<(&mut Text, &mut TextColor), With<ThingTypeText>>,
+) {
+    if !game_state.is_changed() {
+        return;
+    }
+
+    let thing = &things[game_state.thing_type.unwrap_or_default()];
+    let label = if game_state.retooling {
+        format!("Your Thing: {} (retooling)", thing.name)
+    } else {
+        format!("Your Thing: {}", thing.name)
+    };
+
+    for (mut text, mut color) in &mut query {
+        if **text != label {
+            **text = label.clone();
+        }
+        color.0 = thing.color();
     }
 }
 
@@ -540,8 +878,11 @@ pub fn handle_make_thing_button(
         (&Interaction, &mut BackgroundColor),
         (Changed<Interaction>, With<MakeThingButton>),
     >,
-    _click_events: MessageWriter<ClickEvent>,
     mut game_state: ResMut<GameState>,
+    things: Res<ThingRegistry>,
+    mut hustle: ResMut<HustleMeter>,
+    mut wellbeing: ResMut<Wellbeing>,
+    settings: Res<Settings>,
     mut thing_events: MessageWriter<crate::game_state::ThingProducedEvent>,
 ) {
     for (interaction, mut bg_color) in &mut interaction_query {
@@ -549,12 +890,18 @@ pub fn handle_make_thing_button(
             Interaction::Pressed => {
                 *bg_color = PRESSED_BUTTON.into();
                 // Directly handle click here since we need mutable access
+                if !game_state.can_produce()
+                    || !hustle.try_click(settings.hustle_meter)
+                    || !wellbeing.try_click(settings.wellbeing)
+                {
+                    continue;
+                }
                 if let Some(thing_type) = game_state.thing_type {
-                    let multiplier = thing_type.production_multiplier();
-                    let things = (game_state.click_power as f64 * multiplier).ceil() as u64;
-                    game_state.things_produced += things;
+                    let multiplier = things[thing_type].production_multiplier;
+                    let made = (game_state.click_power as f64 * multiplier).ceil() as u64;
+                    game_state.things_produced += made;
                     thing_events.write(crate::game_state::ThingProducedEvent {
-                        amount: things,
+                        amount: made,
                         from_click: true,
                     });
                 }
@@ -576,7 +923,7 @@ pub fn handle_upgrade_buttons(
     >,
     mut game_state: ResMut<GameState>,
     mut upgrade_state: ResMut<UpgradeState>,
-    mut cost_text_query: Query<(&mut Text, &UpgradeCostText)>,
+    mut purchase_events: MessageWriter<UpgradePurchasedEvent>,
 ) {
     for (interaction, upgrade_button, mut bg_color, _border_color) in &mut interaction_query {
         let upgrade = upgrade_button.0;
@@ -587,14 +934,8 @@ pub fn handle_upgrade_buttons(
             Interaction::Pressed => {
                 if can_afford {
                     *bg_color = PRESSED_BUTTON.into();
-                    upgrade_state.purchase(upgrade, &mut game_state);
-
-                    // Update cost display
-                    let new_cost = upgrade_state.cost(upgrade);
-                    for (mut text, cost_text) in &mut cost_text_query {
-                        if cost_text.0 == upgrade {
-                            **text = format!("${:.0}", new_cost);
-                        }
+                    if upgrade_state.purchase(upgrade, &mut game_state) {
+                        purchase_events.write(UpgradePurchasedEvent { upgrade });
                     }
                 }
             }
@@ -608,11 +949,52 @@ pub fn handle_upgrade_buttons(
     }
 }
 
-pub fn cleanup_main_screen(
-    mut commands: Commands,
-    query: Query<Entity, With<MainScreen>>,
+/// Show the new price of anything bought (by button, hotkey or automation rule)
+pub fn update_upgrade_costs(
+    mut purchase_events: MessageReader<UpgradePurchasedEvent>,
+    upgrade_state: Res<UpgradeState>,
+    mut cost_text_query: Query<(&mut Text, &UpgradeCostText)>,
+) {
+    for event in purchase_events.read() {
+        let new_cost = upgrade_state.cost(event.upgrade);
+        for (mut text, cost_text) in &mut cost_text_query {
+            if cost_text.0 == event.upgrade {
+                **text = money_whole(new_cost);
+            }
+        }
+    }
+}
+
+/// Show this run's fortune cookie
+pub fn update_fortune_text(
+    modifiers: Res<RunModifiers>,
+    mut query: Query<&mut Text, With<FortuneText>>,
 ) {
-    for entity in &query {
-        commands.entity(entity).despawn();
+    if !modifiers.is_changed() {
+        return;
+    }
+
+    for mut text in &mut query {
+        **text = match modifiers.fortune {
+            Some(fortune) => format!("Fortune: {} ({})", fortune.name(), fortune.effect()),
+            None => String::new(),
+        };
+    }
+}
+
+/// Take back the choice of Thing
+pub fn handle_undo_thing_button(
+    mut interaction_query: ButtonInteractionQuery<UndoThingButton>,
+    mut undo_events: MessageWriter<UndoThingSelectionEvent>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                undo_events.write(UndoThingSelectionEvent);
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
     }
 }
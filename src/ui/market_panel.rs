@@ -0,0 +1,216 @@
+//! Market share overlay - the weekly split of the market, as a stacked chart
+
+use bevy::prelude::*;
+use crate::economy::WorldState;
+use crate::market::{MarketShare, WeeklyShare, COMPETITORS, MAX_WEEKS};
+use crate::word_of_mouth::WordOfMouth;
+use super::{spawn_overlay, OverlayKind};
+
+/// Chart height in pixels
+const CHART_HEIGHT: f32 = 140.0;
+/// Colors for the player, each competitor, and the untapped market
+const PLAYER_COLOR: Color = Color::srgb(0.9, 0.6, 0.2);
+const COMPETITOR_COLORS: [Color; 3] = [Color::srgb(0.35, 0.45, 0.7), Color::srgb(0.3, 0.6, 0.55), Color::srgb(0.55, 0.4, 0.6)];
+const UNTAPPED_COLOR: Color = Color::srgb(0.12, 0.12, 0.15);
+
+/// Marker for the market summary text
+#[derive(Component)]
+pub struct MarketSummaryText;
+
+/// Marker for the word-of-mouth funnel text
+#[derive(Component)]
+pub struct WordOfMouthText;
+
+/// Container the chart's weekly columns are rebuilt into
+#[derive(Component)]
+pub struct MarketChart;
+
+/// Market share overlay
+pub fn spawn_market_overlay(parent: &mut ChildSpawnerCommands) {
+    spawn_overlay(parent, OverlayKind::Market, "MARKET SHARE", 380.0, |parent| {
+        parent.spawn((
+            Text::new("Nothing to report until the first week is out."),
+            TextFont {
+                font_size: 13.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            Node {
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+            MarketSummaryText,
+        ));
+
+        parent.spawn((
+            Text::new(""),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.7, 0.7, 0.7)),
+            Node {
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+            WordOfMouthText,
+        ));
+
+        parent.spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Px(CHART_HEIGHT),
+                flex_direction: FlexDirection::Row,
+                column_gap: Val::Px(1.0),
+                border: UiRect::all(Val::Px(1.0)),
+                margin: UiRect::bottom(Val::Px(8.0)),
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.3, 0.3, 0.3)),
+            BackgroundColor(UNTAPPED_COLOR),
+            MarketChart,
+        ));
+
+        // Legend
+        parent
+            .spawn(Node {
+                flex_direction: FlexDirection::Row,
+                flex_wrap: FlexWrap::Wrap,
+                column_gap: Val::Px(10.0),
+                row_gap: Val::Px(4.0),
+                ..default()
+            })
+            .with_children(|parent| {
+                spawn_legend_entry(parent, "You", PLAYER_COLOR);
+                for ((name, _), color) in COMPETITORS.iter().zip(COMPETITOR_COLORS) {
+                    spawn_legend_entry(parent, name, color);
+                }
+                spawn_legend_entry(parent, "Untapped", UNTAPPED_COLOR);
+            });
+
+        super::price_war_panel::spawn_pricing_section(parent);
+        super::fad_panel::spawn_fad_section(parent);
+    });
+}
+
+fn spawn_legend_entry(parent: &mut ChildSpawnerCommands, label: &str, color: Color) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(4.0),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    width: Val::Px(10.0),
+                    height: Val::Px(10.0),
+                    border: UiRect::all(Val::Px(1.0)),
+                    ..default()
+                },
+                BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+                BackgroundColor(color),
+            ));
+            parent.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 11.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+            ));
+        });
+}
+
+/// Redraw the summary and chart when a week closes
+pub fn update_market_panel(
+    mut commands: Commands,
+    market: Res<MarketShare>,
+    mut summary_query: Query<&mut Text, With<MarketSummaryText>>,
+    chart_query: Query<Entity, With<MarketChart>>,
+) {
+    if !market.is_changed() {
+        return;
+    }
+    let Some(latest) = market.latest() else {
+        return;
+    };
+
+    for mut text in &mut summary_query {
+        let rivals: Vec<String> = COMPETITORS
+            .iter()
+            .zip(latest.competitors)
+            .map(|((name, _), share)| format!("{} {:.1}%", name, share * 100.0))
+            .collect();
+        **text = format!(
+            "Week ending {}\nYour share: {:.1}%\nCompetitors: {}\nMarket saturation: {:.0}% (the fuller it gets, the harder every sale)",
+            latest.date.format(),
+            latest.player * 100.0,
+            rivals.join(", "),
+            (1.0 - latest.untapped()) * 100.0
+        );
+    }
+
+    for chart in &chart_query {
+        commands.entity(chart).despawn_children().with_children(|parent| {
+            for week in &market.weeks {
+                spawn_week_column(parent, week);
+            }
+        });
+    }
+}
+
+/// Show where the market stands in the funnel
+pub fn update_word_of_mouth_text(
+    funnel: Res<WordOfMouth>,
+    world: Res<WorldState>,
+    mut text_query: Query<&mut Text, With<WordOfMouthText>>,
+) {
+    if !funnel.is_changed() {
+        return;
+    }
+    for mut text in &mut text_query {
+        **text = format!(
+            "Word of mouth: {} never heard of it, {} aware, {} interested, {} customers",
+            people(funnel.unaware(&world)),
+            people(funnel.aware),
+            people(funnel.interested),
+            people(funnel.customers)
+        );
+    }
+}
+
+fn people(count: f64) -> String {
+    if count >= 1_000_000.0 {
+        format!("{:.1}M", count / 1_000_000.0)
+    } else if count >= 1_000.0 {
+        format!("{:.1}k", count / 1_000.0)
+    } else {
+        format!("{:.0}", count)
+    }
+}
+
+/// One week's column: you at the bottom, competitors on top, untapped showing through
+fn spawn_week_column(parent: &mut ChildSpawnerCommands, week: &WeeklyShare) {
+    parent
+        .spawn(Node {
+            width: Val::Percent(100.0 / MAX_WEEKS as f32),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::ColumnReverse,
+            ..default()
+        })
+        .with_children(|parent| {
+            let layers = std::iter::once((week.player, PLAYER_COLOR)).chain(week.competitors.into_iter().zip(COMPETITOR_COLORS));
+            for (share, color) in layers {
+                parent.spawn((
+                    Node {
+                        width: Val::Percent(100.0),
+                        height: Val::Percent(share * 100.0),
+                        ..default()
+                    },
+                    BackgroundColor(color),
+                ));
+            }
+        });
+}
@@ -0,0 +1,161 @@
+//! Marketing channels overlay - switch individual channels on and off
+
+use bevy::prelude::*;
+use crate::currency::money_whole;
+use crate::game_state::GameState;
+use crate::marketing::{ChannelToggledEvent, MarketingChannel, MarketingState};
+use crate::reputation::ReputationTier;
+use super::{spawn_overlay, ContextMenuSource, ContextSubject, OverlayKind, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON, DISABLED_BUTTON};
+
+/// Button that switches a marketing channel on/off
+#[derive(Component)]
+pub struct ChannelToggleButton(pub MarketingChannel);
+
+/// Text showing a channel's status (ON / OFF / locked)
+#[derive(Component)]
+pub struct ChannelStatusText(pub MarketingChannel);
+
+const CHANNEL_ON: Color = Color::srgb(0.5, 0.9, 0.5);
+const CHANNEL_OFF: Color = Color::srgb(0.7, 0.7, 0.7);
+const CHANNEL_LOCKED: Color = Color::srgb(0.8, 0.4, 0.3);
+
+/// Marketing overlay listing every channel
+pub fn spawn_marketing_overlay(
+    parent: &mut ChildSpawnerCommands,
+    marketing: &MarketingState,
+    game_state: &GameState,
+) {
+    let tier = ReputationTier::from_reputation(game_state.reputation);
+
+    spawn_overlay(parent, OverlayKind::Marketing, "MARKETING CHANNELS", 420.0, |parent| {
+        for channel in MarketingChannel::ALL {
+            spawn_channel_row(parent, channel, marketing, tier);
+        }
+    });
+}
+
+fn spawn_channel_row(
+    parent: &mut ChildSpawnerCommands,
+    channel: MarketingChannel,
+    marketing: &MarketingState,
+    tier: ReputationTier,
+) {
+    let (status, color) = channel_status(channel, marketing, tier);
+
+    parent
+        .spawn(Node {
+            width: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::SpaceBetween,
+            margin: UiRect::bottom(Val::Px(6.0)),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(format!("{} ({}/day)", channel.name(), money_whole(marketing.channel_daily_cost(channel)))),
+                TextFont {
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            ));
+
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        min_width: Val::Px(110.0),
+                        padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                        justify_content: JustifyContent::Center,
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+                    BackgroundColor(NORMAL_BUTTON),
+                    ChannelToggleButton(channel),
+                    ContextMenuSource(ContextSubject::Channel(channel)),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(status),
+                        TextFont {
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(color),
+                        ChannelStatusText(channel),
+                    ));
+                });
+        });
+}
+
+/// Status label and color for a channel at the current reputation
+fn channel_status(
+    channel: MarketingChannel,
+    marketing: &MarketingState,
+    tier: ReputationTier,
+) -> (&'static str, Color) {
+    if marketing.is_active(channel) {
+        ("ON", CHANNEL_ON)
+    } else if channel.is_partnership() && !tier.allows_partnerships() {
+        ("REFUSES", CHANNEL_LOCKED)
+    } else if channel.is_premium() && !tier.unlocks_premium() {
+        ("NEEDS 5 STARS", CHANNEL_LOCKED)
+    } else {
+        ("OFF", CHANNEL_OFF)
+    }
+}
+
+/// Switch channels on/off when their buttons are pressed
+pub fn handle_channel_toggles(
+    mut interaction_query: Query<
+        (&Interaction, &ChannelToggleButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    mut marketing: ResMut<MarketingState>,
+    game_state: Res<GameState>,
+    mut toggle_events: MessageWriter<ChannelToggledEvent>,
+) {
+    let tier = ReputationTier::from_reputation(game_state.reputation);
+
+    for (interaction, toggle, mut bg_color) in &mut interaction_query {
+        let channel = toggle.0;
+        let available = marketing.is_active(channel) || channel.available_at(tier);
+
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                let active = !marketing.is_active(channel);
+                if marketing.set_active(channel, active, tier) {
+                    toggle_events.write(ChannelToggledEvent { channel, active });
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = if available { HOVERED_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+            Interaction::None => {
+                *bg_color = if available { NORMAL_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+        }
+    }
+}
+
+/// Keep channel status labels current as marketing and reputation change
+pub fn update_channel_rows(
+    marketing: Res<MarketingState>,
+    game_state: Res<GameState>,
+    mut query: Query<(&mut Text, &mut TextColor, &ChannelStatusText)>,
+) {
+    if !marketing.is_changed() && !game_state.is_changed() {
+        return;
+    }
+
+    let tier = ReputationTier::from_reputation(game_state.reputation);
+    for (mut text, mut color, status_text) in &mut query {
+        let (status, status_color) = channel_status(status_text.0, &marketing, tier);
+        if **text != status {
+            **text = status.to_string();
+        }
+        color.0 = status_color;
+    }
+}
@@ -0,0 +1,193 @@
+//! Merch shop overlay, and the desk under Terry's portrait where the merch goes
+
+use bevy::prelude::*;
+use crate::merch::{MerchItem, MerchKind, MerchShop};
+use super::{spawn_overlay, OverlayKind, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON, DISABLED_BUTTON};
+
+/// Marker for the desk merch drawn along the bottom of the portrait
+#[derive(Component)]
+pub struct DeskMerchLayer;
+
+/// Marker for the points balance
+#[derive(Component)]
+pub struct MerchPointsText;
+
+/// Button that buys an item, or puts it out / away once bought
+#[derive(Component)]
+pub struct MerchButton(pub MerchItem);
+
+/// Marker for a merch button's label
+#[derive(Component)]
+pub struct MerchButtonText(pub MerchItem);
+
+/// Desk merch, lined up along the bottom of the portrait
+pub fn spawn_desk_merch_layer(parent: &mut ChildSpawnerCommands) {
+    parent.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 20.0,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(6.0),
+            left: Val::Px(10.0),
+            ..default()
+        },
+        DeskMerchLayer,
+    ));
+}
+
+/// Merch shop overlay
+pub fn spawn_merch_overlay(parent: &mut ChildSpawnerCommands) {
+    spawn_overlay(parent, OverlayKind::Merch, "TERRY MERCH", 340.0, |parent| {
+        parent.spawn((
+            Text::new(
+                "Milestones and accessories earn achievement points, kept between runs. \
+                 Spend them on merch for the desk or a new look for the screen. \
+                 Purely cosmetic; Terry insists it's a brand investment.",
+            ),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            Node {
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+        ));
+
+        parent.spawn((
+            Text::new(""),
+            TextFont {
+                font_size: 14.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.9, 0.8, 0.5)),
+            Node {
+                margin: UiRect::bottom(Val::Px(8.0)),
+                ..default()
+            },
+            MerchPointsText,
+        ));
+
+        for item in MerchItem::ALL {
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Percent(100.0),
+                        padding: UiRect::axes(Val::Px(10.0), Val::Px(6.0)),
+                        margin: UiRect::bottom(Val::Px(6.0)),
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    BorderColor::all(Color::srgb(0.8, 0.5, 0.2)),
+                    BackgroundColor(DISABLED_BUTTON),
+                    MerchButton(item),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: 13.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        MerchButtonText(item),
+                    ));
+                });
+        }
+    });
+}
+
+/// Buy merch, or put it out and away
+pub fn handle_merch_buttons(
+    mut interaction_query: Query<(&Interaction, &MerchButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut shop: ResMut<MerchShop>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        let item = button.0;
+        let usable = shop.owns(item) || shop.can_buy(item);
+
+        match *interaction {
+            Interaction::Pressed => {
+                if shop.owns(item) {
+                    *bg_color = PRESSED_BUTTON.into();
+                    shop.toggle(item);
+                } else if shop.buy(item) {
+                    *bg_color = PRESSED_BUTTON.into();
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = if usable { HOVERED_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+            Interaction::None => {
+                *bg_color = if usable { NORMAL_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+        }
+    }
+}
+
+/// Keep the points and the shop buttons current
+pub fn update_merch_panel(
+    shop: Res<MerchShop>,
+    mut points_query: Query<&mut Text, (With<MerchPointsText>, Without<MerchButtonText>)>,
+    mut button_query: Query<(&MerchButton, &mut BackgroundColor, &Interaction)>,
+    mut text_query: Query<(&mut Text, &MerchButtonText), Without<MerchPointsText>>,
+    added_query: Query<(), Added<MerchButton>>,
+) {
+    // The shop only changes when points come in or something's bought, but the screen can be new
+    if !shop.is_changed() && added_query.is_empty() {
+        return;
+    }
+
+    for mut text in &mut points_query {
+        **text = format!("Achievement points: {}", shop.points);
+    }
+
+    for (button, mut bg_color, interaction) in &mut button_query {
+        if *interaction == Interaction::None {
+            let usable = shop.owns(button.0) || shop.can_buy(button.0);
+            *bg_color = if usable { NORMAL_BUTTON } else { DISABLED_BUTTON }.into();
+        }
+    }
+
+    for (mut text, label) in &mut text_query {
+        let item = label.0;
+        let shown = match item.kind() {
+            MerchKind::Desk => "on the desk",
+            MerchKind::Skin => "in use",
+        };
+        **text = if shop.is_displayed(item) {
+            format!("{} ({})", item.name(), shown)
+        } else if shop.owns(item) {
+            item.name().to_string()
+        } else {
+            format!("{} - {} points", item.name(), item.price())
+        };
+    }
+}
+
+/// Put the desk merch out under Terry's portrait
+pub fn update_desk_merch(
+    shop: Res<MerchShop>,
+    mut desk_query: Query<&mut Text, With<DeskMerchLayer>>,
+    added_query: Query<(), Added<DeskMerchLayer>>,
+) {
+    if !shop.is_changed() && added_query.is_empty() {
+        return;
+    }
+
+    let desk: String = shop
+        .displayed
+        .iter()
+        .filter(|item| item.kind() == MerchKind::Desk)
+        .map(|item| item.glyph())
+        .collect::<Vec<_>>()
+        .join(" ");
+    for mut text in &mut desk_query {
+        **text = desk.clone();
+    }
+}
@@ -1,45 +1,447 @@
 //! UI module - all user interface components
 
+mod ai_panel;
+mod animation;
+mod automation_panel;
+mod bundle_panel;
+mod config_error_dialog;
+mod context_menu;
+mod counterfeit_panel;
+mod crypto_panel;
+mod debate_panel;
+mod demand_weather;
+mod eco_panel;
+mod edition_panel;
+mod encyclopedia_panel;
+mod era_skin;
+mod event_log_panel;
+mod fad_panel;
+mod focus;
+mod hud_widgets;
+mod hustle_bar;
+#[cfg(debug_assertions)]
+mod inspector_panel;
+mod loading_screen;
 mod main_screen;
+mod market_panel;
+mod marketing_panel;
+mod merch_panel;
+mod objectives_panel;
+mod org_panel;
+mod overlay;
+mod pandemic_panel;
+mod parallel_panel;
+mod pause_menu;
+mod pivot_panel;
+mod pool;
+mod price_war_panel;
+mod profile_panel;
+mod quest_panel;
+mod research_panel;
+mod returns_panel;
+mod recovery_panel;
+mod replay_panel;
+mod report_panel;
+mod reward_panel;
+mod save_panel;
+mod savings_panel;
+mod scenery;
 mod selection;
+mod seminar_panel;
+mod session_panel;
+mod settings_panel;
+mod social_panel;
+mod stats_panel;
+mod supplier_panel;
+mod survey_panel;
+mod strike_panel;
 mod terry_box;
+mod terry_reactions;
+mod text_field;
+mod thing_builder;
+mod thing_picker;
+mod tutorial_card;
+mod vacation_panel;
+mod venue_panel;
+mod vignette;
+mod wardrobe_panel;
+mod wellbeing_bar;
+mod wrapped_panel;
 
 use bevy::prelude::*;
 use bevy::ecs::schedule::IntoScheduleConfigs;
-use crate::game_state::AppState;
+use bevy::ui::UiSystems;
+use crate::game_state::{reset_resource, AppState, InGame};
+use crate::attract::attract_running;
+use crate::vacation::terry_in_office;
 use crate::business::UpgradeState;
 use crate::clicker::ClickEvent;
 
+pub use ai_panel::*;
+pub use animation::*;
+pub use automation_panel::*;
+pub use bundle_panel::*;
+pub use config_error_dialog::*;
+pub use context_menu::*;
+pub use counterfeit_panel::*;
+pub use crypto_panel::*;
+pub use debate_panel::*;
+pub use demand_weather::*;
+pub use eco_panel::*;
+pub use edition_panel::*;
+pub use encyclopedia_panel::*;
+pub use era_skin::*;
+pub use event_log_panel::*;
+pub use fad_panel::*;
+pub use focus::*;
+pub use hud_widgets::*;
+pub use hustle_bar::*;
+#[cfg(debug_assertions)]
+pub use inspector_panel::*;
+pub use loading_screen::*;
 pub use main_screen::*;
+pub use market_panel::*;
+pub use marketing_panel::*;
+pub use merch_panel::*;
+pub use objectives_panel::*;
+pub use org_panel::*;
+pub use overlay::*;
+pub use pandemic_panel::*;
+pub use parallel_panel::*;
+pub use pause_menu::*;
+pub use pivot_panel::*;
+pub use pool::*;
+pub use price_war_panel::*;
+pub use profile_panel::*;
+pub use quest_panel::*;
+pub use research_panel::*;
+pub use recovery_panel::*;
+pub use returns_panel::*;
+pub use replay_panel::*;
+pub use report_panel::*;
+pub use reward_panel::*;
+pub use save_panel::*;
+pub use savings_panel::*;
+pub use scenery::*;
 pub use selection::*;
+pub use seminar_panel::*;
+pub use session_panel::*;
+pub use settings_panel::*;
+pub use social_panel::*;
+pub use stats_panel::*;
+pub use supplier_panel::*;
+pub use survey_panel::*;
+pub use strike_panel::*;
 pub use terry_box::*;
+pub use terry_reactions::*;
+pub use text_field::*;
+pub use thing_builder::*;
+pub use thing_picker::*;
+pub use tutorial_card::*;
+pub use vacation_panel::*;
+pub use venue_panel::*;
+pub use vignette::*;
+pub use wardrobe_panel::*;
+pub use wellbeing_bar::*;
+pub use wrapped_panel::*;
 
 pub struct UiPlugin;
 
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<UpgradeState>()
+            .init_resource::<ReportModal>()
+            .init_resource::<EventLogView>()
+            .init_resource::<ObjectivesTracker>()
+            .init_resource::<ReplayCursor>()
+            .init_resource::<UiFocus>()
+            .init_resource::<ContextMenu>()
+            .init_resource::<WrappedCard>()
+            .init_resource::<MilestoneVignette>()
+            .init_resource::<Scenery>()
+            .insert_resource(UiPool::<UiFlyTo>::new(FLY_ICON_SPAWN_BUDGET))
+            .insert_resource(UiPool::<FloatingText>::new(FLOATING_TEXT_SPAWN_BUDGET))
             .add_message::<ClickEvent>()
+            .add_message::<ContextActionEvent>()
+            .add_systems(First, (reset_pool_budget::<UiFlyTo>, reset_pool_budget::<FloatingText>))
+            .add_systems(
+                PreUpdate,
+                (navigate_focus, draw_focus_ring, scroll_focus_into_view).chain().after(UiSystems::Focus),
+            )
+            .add_systems(OnEnter(AppState::Loading), setup_loading_screen)
+            .add_systems(Update, update_loading_screen.run_if(in_state(AppState::Loading)))
             .add_systems(OnEnter(AppState::ThingSelection), setup_selection_screen)
-            .add_systems(OnExit(AppState::ThingSelection), cleanup_selection_screen)
+            .add_systems(OnExit(AppState::ThingSelection), clear_selection_timer)
             .add_systems(
                 Update,
                 (
                     handle_selection_buttons,
+                    handle_accept_challenge_button,
+                    handle_parallel_mode_button,
+                    handle_other_thing_button,
+                    handle_trait_notches,
+                    handle_cancel_thing_builder_button,
+                    handle_start_custom_thing_button,
+                    update_thing_builder,
                     update_selection_timer,
+                    focus_text_fields,
+                    type_into_text_fields,
+                    update_text_fields,
+                    update_profile_list,
+                    handle_profile_buttons,
+                    handle_new_profile_button,
+                    update_config_error_dialog,
+                    handle_dismiss_config_error_button,
+                    handle_continue_button,
+                    update_continue_button,
+                    update_save_status,
                 ).run_if(in_state(AppState::ThingSelection)),
             )
-            .add_systems(OnEnter(AppState::Playing), setup_main_screen)
-            .add_systems(OnExit(AppState::Playing), cleanup_main_screen)
+            .add_systems(
+                Update,
+                (
+                    preview_hovered_thing,
+                    handle_ask_terry_button,
+                    handle_confirm_thing_button,
+                    handle_change_thing_button,
+                    update_thing_pick,
+                    handle_difficulty_button,
+                ).run_if(in_state(AppState::ThingSelection)),
+            )
+            .add_systems(OnEnter(InGame), setup_main_screen)
+            .add_systems(OnEnter(AppState::Paused), setup_pause_menu)
+            .add_systems(
+                OnExit(InGame),
+                (
+                    close_context_menu,
+                    reset_scenery,
+                    clear_milestone_vignette,
+                    clear_pool::<UiFlyTo>,
+                    clear_pool::<FloatingText>,
+                    reset_resource::<ReportModal>,
+                    reset_resource::<EventLogView>,
+                    reset_resource::<ReplayCursor>,
+                    reset_resource::<WrappedCard>,
+                ),
+            )
             .add_systems(
                 Update,
                 (
                     update_stats_display,
+                    update_upgrade_stats_display,
+                    update_thing_type_display,
                     update_terry_dialogue,
+                    handle_terry_skip_button,
                     handle_make_thing_button,
+                    update_hustle_bar,
+                    handle_coffee_button,
                     handle_upgrade_buttons,
+                    trigger_upgrade_feedback.after(handle_upgrade_buttons),
+                    spawn_click_numbers,
+                ).run_if(in_state(AppState::Playing)),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_channel_toggles,
+                    update_channel_rows,
+                    handle_recovery_buttons,
+                    update_recovery_status,
+                    update_recovery_toggle_visibility,
+                    handle_pivot_buttons,
+                    update_pivot_status,
+                ).run_if(in_state(AppState::Playing)),
+            )
+            // Still working with the pause menu open
+            .add_systems(
+                Update,
+                (
+                    apply_dialogue_layout,
+                    handle_overlay_toggles,
+                    handle_setting_buttons,
+                    update_setting_values,
+                    handle_key_bind_buttons,
+                    update_key_bindings,
+                    handle_pause_menu_buttons,
+                    handle_undo_thing_button,
+                ).run_if(in_state(InGame)),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_research_buttons,
+                    update_research_status,
+                    update_automation_toggle_visibility,
+                    handle_draft_field_buttons,
+                    handle_add_rule_button,
+                    handle_remove_rule_buttons,
+                    update_automation_panel,
+                    handle_export_buttons,
+                    update_run_summary,
+                    handle_overlay_hotkeys,
+                    update_upgrade_costs,
+                    update_fortune_text,
+                ).run_if(in_state(AppState::Playing)),
+            )
+            .add_systems(
+                Update,
+                (
+                    show_monthly_reports,
+                    handle_close_report_button,
+                    handle_report_to_inbox_button,
+                    handle_inbox_buttons,
+                    update_report_modal,
+                    update_inbox_list,
+                    update_org_toggle_visibility,
+                    handle_hire_manager_button,
+                    handle_fire_manager_button,
+                    update_org_chart,
+                    update_production_display,
+                    update_picket_line,
+                    animate_picket_signs,
+                    handle_strike_resolution_buttons,
+                    handle_certify_button,
+                    update_eco_panel,
+                    update_social_feed,
+                    show_play_reminders,
+                    handle_dismiss_reminder_button,
+                ).run_if(in_state(AppState::Playing)),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_cosmetic_buttons,
+                    update_wardrobe_panel,
+                    update_terry_cosmetics,
+                    handle_merch_buttons,
+                    update_merch_panel,
+                    update_desk_merch,
+                    update_market_panel,
+                    update_word_of_mouth_text,
+                    handle_refund_policy_buttons,
+                    handle_restocking_button,
+                    update_returns_panel,
+                    handle_book_venue_buttons,
+                    update_venue_list,
+                    update_bundle_toggle_visibility,
+                    handle_bundle_toggles,
+                    handle_bundle_price_buttons,
+                    update_bundle_panel,
+                ).run_if(in_state(AppState::Playing)),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_negotiate_buttons,
+                    update_supplier_panel,
+                    handle_seminar_buttons,
+                    update_seminar_status,
+                    handle_log_filter_buttons,
+                    handle_log_jump_buttons,
+                    update_event_log_list,
+                    update_demand_weather,
+                    show_demand_weather_tooltip,
+                    show_tutorial_cards,
+                    handle_dismiss_tutorial_button,
+                    update_reward_cards,
+                    handle_reward_card_buttons,
+                    spawn_reward_toasts,
+                    handle_savings_buttons,
+                    update_savings_text,
+                    handle_crypto_trade_buttons,
+                    update_crypto_section,
+                    handle_delivery_button,
+                    update_pandemic_section,
+                ).run_if(in_state(AppState::Playing)),
+            )
+            .add_systems(
+                Update,
+                (
+                    apply_era_skin,
+                    handle_ai_upgrade_buttons,
+                    update_ai_section,
+                    handle_vacation_buttons,
+                    update_vacation_panel,
+                    update_wellbeing_bar,
+                    handle_days_off_button,
+                    handle_price_buttons,
+                    handle_accept_truce_button,
+                    update_pricing_section,
+                    handle_chase_fad_button,
+                    update_fad_section,
+                    handle_survey_buttons,
+                    update_survey_reports,
+                    update_counterfeit_toggle_visibility,
+                    handle_counterfeit_buttons,
+                    update_counterfeit_panel,
+                    handle_order_edition_buttons,
+                    update_edition_panel,
+                ).run_if(in_state(AppState::Playing)),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_quest_outcome_buttons,
+                    update_quest_panel,
+                    handle_objectives_toggle,
+                    handle_objective_rows,
+                    update_objectives_tracker,
+                    update_parallel_panel,
+                    handle_replay_step_buttons,
+                    handle_replay_bars,
+                    update_replay_panel,
+                    open_context_menus,
+                    handle_context_menu_items,
+                    refresh_context_menu,
+                    apply_context_actions,
+                    spawn_hud_widgets,
+                    update_hud_widgets,
+                    drag_hud_widgets,
+                    handle_hud_pin_buttons,
+                    update_hud_pin_buttons,
+                    (handle_debate_buttons, update_debate_panel, reveal_debate_turns).chain(),
                 ).run_if(in_state(AppState::Playing)),
+            )
+            .add_systems(
+                Update,
+                (
+                    show_year_wrapped,
+                    handle_save_wrapped_button,
+                    handle_close_wrapped_button,
+                    update_wrapped_card,
+                    spawn_scenery,
+                    reveal_scenery,
+                    parallax_scenery,
+                    flash_reputation_changes,
+                    update_encyclopedia,
+                    update_encyclopedia_toggle_visibility,
+                    handle_save_game_button,
+                    update_save_status,
+                    (
+                        start_milestone_vignettes.run_if(not(attract_running)),
+                        animate_milestone_vignette,
+                        finish_milestone_vignette,
+                    ).chain(),
+                    update_milestone_vignette,
+                    trigger_terry_flinch,
+                    animate_terry_flinch,
+                    update_terry_reaction_layers,
+                    spawn_sweat_droplets.run_if(terry_in_office),
+                ).run_if(in_state(AppState::Playing)),
+            )
+            .add_systems(
+                Update,
+                (
+                    animate_pulses,
+                    animate_flashes,
+                    animate_text_highlights,
+                    animate_fly_to,
+                    animate_floating_texts,
+                ),
             );
+
+        #[cfg(debug_assertions)]
+        app.add_plugins(InspectorPlugin);
     }
 }
 
@@ -47,6 +449,14 @@ impl Plugin for UiPlugin {
 #[derive(Component)]
 pub struct UiRoot;
 
+/// Buttons with marker `T` whose interaction changed this frame
+pub type ButtonInteractionQuery<'w, 's, T> = Query<
+    'w,
+    's,
+    (&'static Interaction, &'static mut BackgroundColor),
+    (Changed<Interaction>, With<T>),
+>;
+
 /// Common button colors
 pub const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
 pub const HOVERED_BUTTON: Color = Color::srgb(0.25, 0.25, 0.25);
@@ -0,0 +1,252 @@
+//! Objectives tracker - a collapsible list in the bottom corner of everything in progress
+
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use crate::objectives::{ObjectiveKind, Objectives, TrackedObjective};
+use super::{open_overlay, ButtonInteractionQuery, OverlayKind, OverlayPanel, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+const BAR_EMPTY: Color = Color::srgb(0.2, 0.2, 0.25);
+const BAR_FULL: Color = Color::srgb(0.5, 0.8, 0.5);
+
+/// Whether the tracker is folded away
+#[derive(Resource, Default)]
+pub struct ObjectivesTracker {
+    pub collapsed: bool,
+}
+
+/// Header button that folds the tracker
+#[derive(Component)]
+pub struct ObjectivesToggleButton;
+
+/// Label on the header button
+#[derive(Component)]
+pub struct ObjectivesHeaderText;
+
+/// Container the rows are rebuilt into
+#[derive(Component)]
+pub struct ObjectivesList;
+
+/// A row; clicking it opens the overlay that deals with it
+#[derive(Component)]
+pub struct ObjectiveRowButton(pub Option<OverlayKind>);
+
+/// A row's detail line (index into the objectives)
+#[derive(Component)]
+pub struct ObjectiveDetailText(pub usize);
+
+/// The filled part of a row's progress bar (index into the objectives)
+#[derive(Component)]
+pub struct ObjectiveBarFill(pub usize);
+
+/// What a row shows that can change
+fn detail_line(objective: &TrackedObjective) -> String {
+    let deadline = objective.days_left.map_or(String::new(), |days| match days {
+        0 => " - ends today".to_string(),
+        1 => " - 1 day left".to_string(),
+        days => format!(" - {} days left", days),
+    });
+    format!("{}{}", objective.detail, deadline)
+}
+
+pub fn spawn_objectives_tracker(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(15.0),
+                left: Val::Px(15.0),
+                width: Val::Px(280.0),
+                flex_direction: FlexDirection::Column,
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.4, 0.5, 0.4)),
+            BackgroundColor(Color::srgb(0.08, 0.1, 0.08)),
+            GlobalZIndex(45),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        padding: UiRect::axes(Val::Px(10.0), Val::Px(5.0)),
+                        ..default()
+                    },
+                    BackgroundColor(NORMAL_BUTTON),
+                    ObjectivesToggleButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new("Objectives"),
+                        TextFont {
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.7, 0.9, 0.7)),
+                        ObjectivesHeaderText,
+                    ));
+                });
+
+            parent.spawn((
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(6.0)),
+                    row_gap: Val::Px(4.0),
+                    ..default()
+                },
+                ObjectivesList,
+            ));
+        });
+}
+
+fn spawn_objective_row(parent: &mut ChildSpawnerCommands, index: usize, objective: &TrackedObjective) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(5.0)),
+                row_gap: Val::Px(2.0),
+                ..default()
+            },
+            BackgroundColor(NORMAL_BUTTON),
+            ObjectiveRowButton(objective.focus),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(format!("{}: {}", objective.kind.name(), objective.label)),
+                TextFont {
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+            parent.spawn((
+                Text::new(detail_line(objective)),
+                TextFont {
+                    font_size: 11.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                ObjectiveDetailText(index),
+            ));
+            if let Some(progress) = objective.progress {
+                parent
+                    .spawn((
+                        Node {
+                            width: Val::Percent(100.0),
+                            height: Val::Px(5.0),
+                            ..default()
+                        },
+                        BackgroundColor(BAR_EMPTY),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Node {
+                                width: Val::Percent(progress * 100.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            BackgroundColor(BAR_FULL),
+                            ObjectiveBarFill(index),
+                        ));
+                    });
+            }
+        });
+}
+
+/// Fold or unfold the tracker
+pub fn handle_objectives_toggle(
+    mut interaction_query: ButtonInteractionQuery<ObjectivesToggleButton>,
+    mut tracker: ResMut<ObjectivesTracker>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                tracker.collapsed = !tracker.collapsed;
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+/// Open the overlay for a clicked objective
+pub fn handle_objective_rows(
+    mut interaction_query: Query<(&Interaction, &ObjectiveRowButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut overlay_query: Query<(&mut Node, &OverlayPanel)>,
+) {
+    for (interaction, row, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                if let Some(kind) = row.0 {
+                    open_overlay(kind, &mut overlay_query);
+                }
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+/// The tracker's parts that change
+#[derive(SystemParam)]
+pub struct TrackerWidgets<'w, 's> {
+    headers: Query<'w, 's, &'static mut Text, With<ObjectivesHeaderText>>,
+    details: Query<'w, 's, (&'static mut Text, &'static ObjectiveDetailText), Without<ObjectivesHeaderText>>,
+    lists: Query<'w, 's, (Entity, &'static mut Node), With<ObjectivesList>>,
+    fills: Query<'w, 's, (&'static mut Node, &'static ObjectiveBarFill), Without<ObjectivesList>>,
+    added: Query<'w, 's, (), Added<ObjectivesList>>,
+}
+
+/// Rebuild the rows when objectives come or go, refresh them in place as they progress,
+/// and fold them away when collapsed
+pub fn update_objectives_tracker(
+    mut commands: Commands,
+    objectives: Res<Objectives>,
+    tracker: Res<ObjectivesTracker>,
+    mut widgets: TrackerWidgets,
+    mut shown: Local<Vec<(ObjectiveKind, String)>>,
+) {
+    let respawned = !widgets.added.is_empty();
+    if !objectives.is_changed() && !tracker.is_changed() && !respawned {
+        return;
+    }
+
+    let arrow = if tracker.collapsed { "▸" } else { "▾" };
+    for mut text in &mut widgets.headers {
+        **text = format!("{} Objectives ({})", arrow, objectives.list.len());
+    }
+
+    let rows: Vec<(ObjectiveKind, String)> =
+        objectives.list.iter().map(|objective| (objective.kind, objective.label.clone())).collect();
+    let rebuild = respawned || *shown != rows;
+    for (list, mut node) in &mut widgets.lists {
+        node.display = if tracker.collapsed || objectives.list.is_empty() { Display::None } else { Display::Flex };
+        if rebuild {
+            commands.entity(list).despawn_children().with_children(|parent| {
+                for (index, objective) in objectives.list.iter().enumerate() {
+                    spawn_objective_row(parent, index, objective);
+                }
+            });
+        }
+    }
+    if rebuild {
+        *shown = rows;
+        return;
+    }
+
+    for (mut text, detail) in &mut widgets.details {
+        if let Some(objective) = objectives.list.get(detail.0) {
+            **text = detail_line(objective);
+        }
+    }
+    for (mut node, fill) in &mut widgets.fills {
+        if let Some(progress) = objectives.list.get(fill.0).and_then(|objective| objective.progress) {
+            node.width = Val::Percent(progress * 100.0);
+        }
+    }
+}
@@ -0,0 +1,271 @@
+//! Org chart overlay - hire managers and see the management layers pile up
+
+use bevy::prelude::*;
+use crate::business::UpgradeState;
+use crate::currency::money_whole;
+use crate::game_state::GameState;
+use crate::org::{Organization, FLAT_LAYERS, MANAGER_BOOST, MANAGER_SALARY, MANAGER_SPAN};
+use super::{
+    set_overlay_available, spawn_overlay, ButtonInteractionQuery, OverlayKind, OverlayPanelQuery,
+    OverlayToggleQuery, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON, DISABLED_BUTTON,
+};
+
+/// Boxes drawn per layer before the rest are summarised as "+N"
+const MAX_BOXES_PER_LAYER: u32 = 10;
+
+/// Marker for the "Hire Manager" button
+#[derive(Component)]
+pub struct HireManagerButton;
+
+/// Marker for the "Let One Go" button
+#[derive(Component)]
+pub struct FireManagerButton;
+
+/// Marker for the hire button's label (shows the price)
+#[derive(Component)]
+pub struct HireManagerText;
+
+/// Marker for the org summary text
+#[derive(Component)]
+pub struct OrgSummaryText;
+
+/// Container the chart's layers are rebuilt into
+#[derive(Component)]
+pub struct OrgChart;
+
+/// Org chart overlay
+pub fn spawn_org_overlay(parent: &mut ChildSpawnerCommands) {
+    spawn_overlay(parent, OverlayKind::OrgChart, "ORG CHART", 400.0, |parent| {
+        parent.spawn((
+            Text::new(format!(
+                "Each manager makes up to {} workers {:.0}% more productive and costs {}/day. \
+                 More than {} layers of management and things start to slow down.",
+                MANAGER_SPAN,
+                MANAGER_BOOST * 100.0,
+                money_whole(MANAGER_SALARY),
+                FLAT_LAYERS
+            )),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            Node {
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+        ));
+
+        parent
+            .spawn(Node {
+                column_gap: Val::Px(8.0),
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            })
+            .with_children(|parent| {
+                spawn_org_button(parent, "Hire Manager", HireManagerButton, Some(HireManagerText));
+                spawn_org_button(parent, "Let One Go", FireManagerButton, None::<HireManagerText>);
+            });
+
+        parent.spawn((
+            Node {
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(6.0),
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+            OrgChart,
+        ));
+
+        parent.spawn((
+            Text::new(""),
+            TextFont {
+                font_size: 13.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            OrgSummaryText,
+        ));
+    });
+}
+
+fn spawn_org_button<M: Component>(
+    parent: &mut ChildSpawnerCommands,
+    label: &str,
+    marker: impl Component,
+    label_marker: Option<M>,
+) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.5, 0.5, 0.7)),
+            BackgroundColor(NORMAL_BUTTON),
+            marker,
+        ))
+        .with_children(|parent| {
+            let mut text = parent.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+            if let Some(label_marker) = label_marker {
+                text.insert(label_marker);
+            }
+        });
+}
+
+/// The Org Chart header button appears once there's anyone to manage
+pub fn update_org_toggle_visibility(
+    upgrades: Res<UpgradeState>,
+    mut toggles: OverlayToggleQuery,
+    mut panels: OverlayPanelQuery,
+) {
+    set_overlay_available(OverlayKind::OrgChart, upgrades.workers > 0, &mut toggles, &mut panels);
+}
+
+/// Hire a manager
+pub fn handle_hire_manager_button(
+    mut interaction_query: ButtonInteractionQuery<HireManagerButton>,
+    mut org: ResMut<Organization>,
+    mut game_state: ResMut<GameState>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        let can_afford = game_state.money >= org.hire_cost();
+
+        match *interaction {
+            Interaction::Pressed => {
+                if can_afford {
+                    *bg_color = PRESSED_BUTTON.into();
+                    org.hire(&mut game_state);
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = if can_afford { HOVERED_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+            Interaction::None => {
+                *bg_color = if can_afford { NORMAL_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+        }
+    }
+}
+
+/// Let a manager go
+pub fn handle_fire_manager_button(
+    mut interaction_query: ButtonInteractionQuery<FireManagerButton>,
+    mut org: ResMut<Organization>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                org.let_go();
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Redraw the chart and summary when the org or the workforce changes
+pub fn update_org_chart(
+    mut commands: Commands,
+    org: Res<Organization>,
+    upgrades: Res<UpgradeState>,
+    chart_query: Query<Entity, With<OrgChart>>,
+    mut hire_text_query: Query<&mut Text, (With<HireManagerText>, Without<OrgSummaryText>)>,
+    mut summary_query: Query<&mut Text, (With<OrgSummaryText>, Without<HireManagerText>)>,
+) {
+    if !org.is_changed() && !upgrades.is_changed() {
+        return;
+    }
+
+    let workers = upgrades.workers;
+    let layers = org.layers(workers);
+
+    for chart in &chart_query {
+        commands.entity(chart).despawn_children().with_children(|parent| {
+            // Top of the company first
+            spawn_layer(parent, "Terry", 1, Color::srgb(0.9, 0.7, 0.3));
+            for &managers in layers.iter().rev() {
+                spawn_layer(parent, "M", managers, Color::srgb(0.6, 0.6, 0.9));
+            }
+            spawn_layer(parent, "W", workers, Color::srgb(0.5, 0.8, 0.5));
+        });
+    }
+
+    for mut text in &mut hire_text_query {
+        **text = format!("Hire Manager ({})", money_whole(org.hire_cost()));
+    }
+
+    let penalty = (1.0 - org.bureaucracy_factor(workers)) * 100.0;
+    for mut text in &mut summary_query {
+        **text = format!(
+            "Workers: {} ({} managed)\nManagers: {} in {} layers\nOverhead: {}/day\nBureaucracy: {}",
+            workers,
+            org.supervised_workers(workers),
+            org.managers,
+            layers.len(),
+            money_whole(org.daily_overhead()),
+            if penalty > 0.0 { format!("-{:.0}% production", penalty) } else { "none".to_string() },
+        );
+    }
+}
+
+/// One row of the chart: a box per person, with overflow summarised
+fn spawn_layer(parent: &mut ChildSpawnerCommands, label: &str, count: u32, color: Color) {
+    parent
+        .spawn(Node {
+            flex_wrap: FlexWrap::Wrap,
+            justify_content: JustifyContent::Center,
+            column_gap: Val::Px(3.0),
+            ..default()
+        })
+        .with_children(|parent| {
+            for _ in 0..count.min(MAX_BOXES_PER_LAYER) {
+                parent
+                    .spawn((
+                        Node {
+                            padding: UiRect::axes(Val::Px(5.0), Val::Px(2.0)),
+                            border: UiRect::all(Val::Px(1.0)),
+                            ..default()
+                        },
+                        BorderColor::all(color),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Text::new(label),
+                            TextFont {
+                                font_size: 11.0,
+                                ..default()
+                            },
+                            TextColor(color),
+                        ));
+                    });
+            }
+
+            if count > MAX_BOXES_PER_LAYER {
+                parent.spawn((
+                    Text::new(format!("+{}", count - MAX_BOXES_PER_LAYER)),
+                    TextFont {
+                        font_size: 11.0,
+                        ..default()
+                    },
+                    TextColor(color),
+                ));
+            }
+        });
+}
@@ -0,0 +1,205 @@
+//! Overlay panels opened from the main screen header
+//!
+//! Each overlay is a floating panel with a matching header toggle button.
+//! Only one overlay is open at a time.
+
+use bevy::prelude::*;
+use crate::input::{HotkeyEvent, InputAction};
+use super::{NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+/// The overlays available on the main screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OverlayKind {
+    Settings,
+    Marketing,
+    Market,
+    Surveys,
+    Counterfeits,
+    Returns,
+    Suppliers,
+    Venues,
+    Bundles,
+    Editions,
+    Recovery,
+    Pivot,
+    Research,
+    Seminars,
+    Quests,
+    Automation,
+    Stats,
+    Inbox,
+    EventLog,
+    OrgChart,
+    Eco,
+    Chirper,
+    Wardrobe,
+    Replay,
+    Hud,
+    Encyclopedia,
+    Merch,
+}
+
+/// Header toggles, for showing/hiding overlays that aren't always available
+pub type OverlayToggleQuery<'w, 's> = Query<'w, 's, (&'static mut Node, &'static OverlayToggleButton)>;
+
+/// Overlay panels, for closing overlays that stop being available
+pub type OverlayPanelQuery<'w, 's> =
+    Query<'w, 's, (&'static mut Node, &'static OverlayPanel), Without<OverlayToggleButton>>;
+
+/// Marker for an overlay panel
+#[derive(Component)]
+pub struct OverlayPanel(pub OverlayKind);
+
+/// Header button that opens/closes an overlay
+#[derive(Component)]
+pub struct OverlayToggleButton(pub OverlayKind);
+
+/// Header button that toggles an overlay
+pub fn spawn_overlay_toggle(parent: &mut ChildSpawnerCommands, kind: OverlayKind, label: &str) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(10.0), Val::Px(5.0)),
+                margin: UiRect::left(Val::Px(6.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+            BackgroundColor(NORMAL_BUTTON),
+            OverlayToggleButton(kind),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            ));
+        });
+}
+
+/// Floating overlay frame with a title; `content` fills in the body
+pub fn spawn_overlay(
+    parent: &mut ChildSpawnerCommands,
+    kind: OverlayKind,
+    title: &str,
+    width: f32,
+    content: impl FnOnce(&mut ChildSpawnerCommands),
+) {
+    parent
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(70.0),
+                right: Val::Px(15.0),
+                width: Val::Px(width),
+                max_height: Val::Percent(85.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(15.0)),
+                border: UiRect::all(Val::Px(2.0)),
+                overflow: Overflow::scroll_y(),
+                display: Display::None,
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.4, 0.5, 0.7)),
+            BackgroundColor(Color::srgb(0.08, 0.08, 0.14)),
+            GlobalZIndex(50),
+            OverlayPanel(kind),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(title),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(10.0)),
+                    ..default()
+                },
+            ));
+
+            content(parent);
+        });
+}
+
+/// Show or hide an overlay's header toggle; an overlay that becomes unavailable is closed
+pub fn set_overlay_available(
+    kind: OverlayKind,
+    available: bool,
+    toggles: &mut OverlayToggleQuery,
+    panels: &mut OverlayPanelQuery,
+) {
+    let display = if available { Display::Flex } else { Display::None };
+    for (mut node, toggle) in toggles.iter_mut() {
+        if toggle.0 == kind && node.display != display {
+            node.display = display;
+        }
+    }
+
+    if !available {
+        for (mut node, panel) in panels.iter_mut() {
+            if panel.0 == kind && node.display != Display::None {
+                node.display = Display::None;
+            }
+        }
+    }
+}
+
+/// Open the matching overlay (closing any other), or close it if already open
+pub fn handle_overlay_toggles(
+    mut interaction_query: Query<
+        (&Interaction, &OverlayToggleButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    mut overlay_query: Query<(&mut Node, &OverlayPanel)>,
+) {
+    for (interaction, toggle, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                toggle_overlay(toggle.0, &mut overlay_query);
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+
+/// Open the Marketing overlay from its hotkey
+pub fn handle_overlay_hotkeys(
+    mut hotkey_events: MessageReader<HotkeyEvent>,
+    mut overlay_query: Query<(&mut Node, &OverlayPanel)>,
+) {
+    for event in hotkey_events.read() {
+        if event.action == InputAction::ToggleMarketing {
+            toggle_overlay(OverlayKind::Marketing, &mut overlay_query);
+        }
+    }
+}
+
+/// Open an overlay (closing any other), leaving it open if it already is
+pub fn open_overlay(kind: OverlayKind, overlay_query: &mut Query<(&mut Node, &OverlayPanel)>) {
+    for (mut node, panel) in overlay_query.iter_mut() {
+        node.display = if panel.0 == kind { Display::Flex } else { Display::None };
+    }
+}
+
+fn toggle_overlay(kind: OverlayKind, overlay_query: &mut Query<(&mut Node, &OverlayPanel)>) {
+    for (mut node, panel) in overlay_query.iter_mut() {
+        node.display = if panel.0 == kind && node.display == Display::None {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+}
@@ -0,0 +1,167 @@
+//! Pandemic section of the upgrades panel - what the era is doing, and the pivot to delivery
+
+use bevy::prelude::*;
+use crate::currency::money_whole;
+use crate::game_state::GameState;
+use crate::pandemic::{PandemicState, PivotToDeliveryEvent, DELIVERY_COST};
+use super::{ButtonInteractionQuery, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+/// Container for the section (hidden outside the pandemic)
+#[derive(Component)]
+pub struct PandemicSection;
+
+/// Marker for the era description
+#[derive(Component)]
+pub struct PandemicText;
+
+/// Marker for the pivot to delivery button
+#[derive(Component)]
+pub struct DeliveryButton;
+
+/// Marker for the pivot to delivery button's label
+#[derive(Component)]
+pub struct DeliveryText;
+
+/// Pandemic section (goes at the top of the upgrades panel)
+pub fn spawn_pandemic_section(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                margin: UiRect::bottom(Val::Px(20.0)),
+                display: Display::None,
+                ..default()
+            },
+            PandemicSection,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("PANDEMIC"),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.5, 0.5)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(6.0)),
+                    ..default()
+                },
+            ));
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.75, 0.75, 0.75)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(8.0)),
+                    ..default()
+                },
+                PandemicText,
+            ));
+
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Percent(100.0),
+                        flex_direction: FlexDirection::Column,
+                        padding: UiRect::all(Val::Px(10.0)),
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BorderColor::all(Color::srgb(0.8, 0.4, 0.4)),
+                    BackgroundColor(NORMAL_BUTTON),
+                    DeliveryButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new("Pivot to Delivery"),
+                        TextFont {
+                            font_size: 16.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                    parent.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                        DeliveryText,
+                    ));
+                });
+        });
+}
+
+/// Pivot to delivery
+pub fn handle_delivery_button(
+    mut interaction_query: ButtonInteractionQuery<DeliveryButton>,
+    mut pivot_events: MessageWriter<PivotToDeliveryEvent>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                pivot_events.write(PivotToDeliveryEvent);
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Show the section during the pandemic and describe the current phase
+pub fn update_pandemic_section(
+    pandemic: Res<PandemicState>,
+    game_state: Res<GameState>,
+    mut section_query: Query<&mut Node, With<PandemicSection>>,
+    mut text_query: Query<&mut Text, (With<PandemicText>, Without<DeliveryText>)>,
+    mut delivery_query: Query<&mut Text, (With<DeliveryText>, Without<PandemicText>)>,
+) {
+    if !pandemic.is_changed() && !game_state.is_changed() {
+        return;
+    }
+
+    for mut node in &mut section_query {
+        node.display = if pandemic.phase.is_some() { Display::Flex } else { Display::None };
+    }
+    let Some(phase) = pandemic.phase else {
+        return;
+    };
+
+    let mut lines = vec![
+        format!(
+            "{}: physical ads and pop-ups reach {:.0}% of usual, online {:.0}%.",
+            phase.name(),
+            phase.physical_reach() * 100.0,
+            phase.online_reach() * 100.0
+        ),
+        "Unemployment sends shoppers to cheaper Things.".to_string(),
+    ];
+    if pandemic.mask_rush_days > 0 {
+        lines.push(format!("Masks & sanitizer rush: {} days left.", pandemic.mask_rush_days));
+    }
+    for mut text in &mut text_query {
+        **text = lines.join("\n");
+    }
+
+    let delivery = if pandemic.delivery {
+        "Done - delivery orders are rolling in".to_string()
+    } else if pandemic.can_pivot_to_delivery(&game_state) {
+        format!("Win back in-person customers - {}", money_whole(DELIVERY_COST))
+    } else {
+        format!("Win back in-person customers - {} (can't afford)", money_whole(DELIVERY_COST))
+    };
+    for mut text in &mut delivery_query {
+        **text = delivery.clone();
+    }
+}
@@ -0,0 +1,134 @@
+//! Parallel universe panel - your run and the bot's, side by side
+
+use bevy::prelude::*;
+use crate::currency::money_whole;
+use crate::game_state::GameState;
+use crate::parallel::{ParallelStandings, Standing};
+use crate::thing_type::ThingRegistry;
+
+/// Marker for the panel (hidden unless there's another universe)
+#[derive(Component)]
+pub struct ParallelPanel;
+
+/// Which run a column shows
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub enum UniverseColumn {
+    Player,
+    Other,
+}
+
+fn column_text(heading: &str, thing_name: &str, standing: &Standing) -> String {
+    format!(
+        "{} ({})\n{}\n{}\n{}\n{:.2}",
+        heading,
+        thing_name,
+        money_whole(standing.money),
+        standing.things_produced,
+        standing.customers_served,
+        standing.reputation
+    )
+}
+
+pub fn spawn_parallel_panel(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(15.0),
+                left: Val::Percent(50.0),
+                margin: UiRect::left(Val::Px(-190.0)),
+                width: Val::Px(380.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(8.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                display: Display::None,
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.5, 0.4, 0.7)),
+            BackgroundColor(Color::srgb(0.08, 0.07, 0.12)),
+            GlobalZIndex(45),
+            ParallelPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("MEANWHILE, IN ANOTHER UNIVERSE"),
+                TextFont {
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.6, 0.9)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(6.0)),
+                    ..default()
+                },
+            ));
+
+            parent
+                .spawn(Node {
+                    column_gap: Val::Px(16.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new("\nMoney\nThings made\nCustomers\nReputation"),
+                        TextFont {
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.6, 0.6, 0.6)),
+                    ));
+                    for column in [UniverseColumn::Player, UniverseColumn::Other] {
+                        parent.spawn((
+                            Text::new(""),
+                            TextFont {
+                                font_size: 12.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                            column,
+                        ));
+                    }
+                });
+        });
+}
+
+/// Keep both columns current, and hide the panel when there's only one universe
+pub fn update_parallel_panel(
+    standings: Res<ParallelStandings>,
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    mut panel_query: Query<&mut Node, With<ParallelPanel>>,
+    mut column_query: Query<(&mut Text, &mut TextColor, &UniverseColumn)>,
+) {
+    if !standings.is_changed() && !game_state.is_changed() {
+        return;
+    }
+
+    let other = standings.other;
+    for mut node in &mut panel_query {
+        let display = if other.is_some() { Display::Flex } else { Display::None };
+        if node.display != display {
+            node.display = display;
+        }
+    }
+    let (Some(other), Some(thing_type)) = (other, game_state.thing_type) else {
+        return;
+    };
+
+    let player = Standing {
+        thing_type,
+        money: game_state.money.dollars(),
+        things_produced: game_state.things_produced,
+        customers_served: game_state.customers_served,
+        reputation: game_state.reputation,
+    };
+    for (mut text, mut color, column) in &mut column_query {
+        let (heading, standing) = match column {
+            UniverseColumn::Player => ("You", &player),
+            UniverseColumn::Other => ("Bot", &other),
+        };
+        let thing = &things[standing.thing_type];
+        **text = column_text(heading, &thing.name, standing);
+        *color = TextColor(thing.color());
+    }
+}
@@ -0,0 +1,186 @@
+//! Pause menu - open while the game is in `AppState::Paused` (Esc, or Start on a gamepad)
+
+use bevy::prelude::*;
+use bevy::app::AppExit;
+use bevy::ui::FocusPolicy;
+use crate::game_state::{AppState, GameState};
+use crate::grace_period::SelectionGrace;
+use crate::save::SavedRun;
+use super::{open_overlay, OverlayKind, OverlayPanel, UndoThingButton, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+/// Marker for the pause menu
+#[derive(Component)]
+#[require(DespawnOnExit<AppState> = DespawnOnExit(AppState::Paused))]
+pub struct PauseMenu;
+
+/// What a pause menu button does
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub enum PauseMenuButton {
+    Resume,
+    Settings,
+    /// Save and go back to the selection screen
+    QuitToTitle,
+    /// Save and close the game
+    QuitGame,
+}
+
+impl PauseMenuButton {
+    const ALL: [PauseMenuButton; 4] = [
+        PauseMenuButton::Resume,
+        PauseMenuButton::Settings,
+        PauseMenuButton::QuitToTitle,
+        PauseMenuButton::QuitGame,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            PauseMenuButton::Resume => "Resume",
+            PauseMenuButton::Settings => "Settings",
+            PauseMenuButton::QuitToTitle => "Quit to title",
+            PauseMenuButton::QuitGame => "Quit game",
+        }
+    }
+}
+
+/// Open the pause menu: a dimmed backdrop that keeps clicks off the main
+/// screen, with the menu on it. Sits under the overlays, so Settings opens on
+/// top of it.
+pub fn setup_pause_menu(mut commands: Commands, game_state: Res<GameState>, grace: Res<SelectionGrace>) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::FlexStart,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.4)),
+            FocusPolicy::Block,
+            GlobalZIndex(46),
+            PauseMenu,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        margin: UiRect::top(Val::Px(70.0)),
+                        width: Val::Px(240.0),
+                        padding: UiRect::all(Val::Px(12.0)),
+                        row_gap: Val::Px(6.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Stretch,
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BorderColor::all(Color::srgb(0.9, 0.8, 0.3)),
+                    BackgroundColor(Color::srgb(0.15, 0.13, 0.05)),
+                ))
+                .with_children(|parent| spawn_menu_items(parent, grace.is_open(&game_state)));
+        });
+}
+
+/// The title, the buttons, and the free change of Thing if it's still on offer
+fn spawn_menu_items(parent: &mut ChildSpawnerCommands, can_undo_thing: bool) {
+    parent.spawn((
+        Text::new("PAUSED"),
+        TextFont {
+            font_size: 22.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.9, 0.8, 0.3)),
+        TextLayout::new_with_justify(Justify::Center),
+        Node {
+            margin: UiRect::bottom(Val::Px(4.0)),
+            ..default()
+        },
+    ));
+
+    for button in PauseMenuButton::ALL {
+        parent
+            .spawn((
+                Button,
+                Node {
+                    padding: UiRect::axes(Val::Px(10.0), Val::Px(5.0)),
+                    justify_content: JustifyContent::Center,
+                    border: UiRect::all(Val::Px(1.0)),
+                    ..default()
+                },
+                BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+                BackgroundColor(NORMAL_BUTTON),
+                button,
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Text::new(button.label()),
+                    TextFont {
+                        font_size: 14.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                ));
+            });
+    }
+
+    // Free change of Thing, early in the run only
+    if !can_undo_thing {
+        return;
+    }
+    parent
+        .spawn((
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(10.0), Val::Px(4.0)),
+                margin: UiRect::top(Val::Px(4.0)),
+                justify_content: JustifyContent::Center,
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.9, 0.8, 0.3)),
+            BackgroundColor(NORMAL_BUTTON),
+            UndoThingButton,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Pick a different Thing (free for now)"),
+                TextFont {
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// Resume, open the settings, or quit (saving first)
+pub fn handle_pause_menu_buttons(
+    mut interaction_query: Query<(&Interaction, &PauseMenuButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut overlay_query: Query<(&mut Node, &OverlayPanel)>,
+    mut saved: ResMut<SavedRun>,
+    mut exit_events: MessageWriter<AppExit>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                match button {
+                    PauseMenuButton::Resume => next_state.set(AppState::Playing),
+                    PauseMenuButton::Settings => open_overlay(OverlayKind::Settings, &mut overlay_query),
+                    // Saved at the end of this frame, before leaving the run throws it away
+                    PauseMenuButton::QuitToTitle => {
+                        saved.save_requested = true;
+                        next_state.set(AppState::ThingSelection);
+                    }
+                    // Saving on the way out is already handled
+                    PauseMenuButton::QuitGame => {
+                        exit_events.write(AppExit::Success);
+                    }
+                }
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! Pivot overlay - switch to a different kind of Thing
+
+use bevy::prelude::*;
+use crate::currency::money_whole;
+use crate::game_state::GameState;
+use crate::pivot::{PivotState, StartPivotEvent, RETOOLING_DAYS};
+use crate::thing_type::{ThingRegistry, ThingType};
+use super::{spawn_overlay, OverlayKind, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON, DISABLED_BUTTON};
+
+/// Button that starts a pivot to the given Thing type
+#[derive(Component)]
+pub struct PivotButton(pub ThingType);
+
+/// Text showing the cost breakdown or retooling progress
+#[derive(Component)]
+pub struct PivotStatusText;
+
+/// Pivot overlay with one button per Thing type
+pub fn spawn_pivot_overlay(parent: &mut ChildSpawnerCommands, things: &ThingRegistry) {
+    spawn_overlay(parent, OverlayKind::Pivot, "PIVOT", 360.0, |parent| {
+        parent.spawn((
+            Text::new(""),
+            TextFont {
+                font_size: 13.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            Node {
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+            PivotStatusText,
+        ));
+
+        for thing_type in things.all() {
+            let thing = &things[thing_type];
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Percent(100.0),
+                        padding: UiRect::all(Val::Px(8.0)),
+                        margin: UiRect::bottom(Val::Px(6.0)),
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BorderColor::all(thing.color()),
+                    BackgroundColor(NORMAL_BUTTON),
+                    PivotButton(thing_type),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(format!("Become a {} Thing company", thing.name)),
+                        TextFont {
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(thing.color()),
+                    ));
+                });
+        }
+    });
+}
+
+/// Start a pivot when a Thing type is picked
+pub fn handle_pivot_buttons(
+    mut interaction_query: Query<
+        (&Interaction, &PivotButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    pivot: Res<PivotState>,
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    mut start_events: MessageWriter<StartPivotEvent>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        let can_pivot = pivot.can_pivot(button.0, &game_state, &things);
+
+        match *interaction {
+            Interaction::Pressed => {
+                if can_pivot {
+                    *bg_color = PRESSED_BUTTON.into();
+                    start_events.write(StartPivotEvent { to: button.0 });
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = if can_pivot { HOVERED_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+            Interaction::None => {
+                *bg_color = if can_pivot { NORMAL_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+        }
+    }
+}
+
+/// Show what a pivot would cost, or how retooling is going
+pub fn update_pivot_status(
+    pivot: Res<PivotState>,
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    mut query: Query<&mut Text, With<PivotStatusText>>,
+) {
+    if !pivot.is_changed() && !game_state.is_changed() {
+        return;
+    }
+
+    let status = match pivot.active {
+        Some(active) => format!(
+            "Retooling for {} Things: {} days to go.\nThe line is shut down.",
+            things[active.to].name,
+            active.days_remaining
+        ),
+        None => format!(
+            "Retooling fee: {}\nStock write-off: {}\n{} days of downtime, and your reputation drifts halfway back to neutral.",
+            money_whole(pivot.retooling_cost()),
+            money_whole(pivot.inventory_write_off(&game_state, &things)),
+            RETOOLING_DAYS
+        ),
+    };
+
+    for mut text in &mut query {
+        if **text != status {
+            **text = status.clone();
+        }
+    }
+}
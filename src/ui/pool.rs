@@ -0,0 +1,64 @@
+//! Entity pools for short-lived UI effects
+//!
+//! Flying icons, floating numbers and the like come and go constantly. Instead of
+//! spawning and despawning an entity for every one, finished effects are hidden and
+//! handed back to their pool, and new effects reuse them. Fresh spawns are capped
+//! per frame, so a burst of clicks drops a few extra effects instead of hitching.
+
+use bevy::prelude::*;
+use std::marker::PhantomData;
+
+/// Recycled entities for effects driven by component `T`
+#[derive(Resource)]
+pub struct UiPool<T: Component> {
+    free: Vec<Entity>,
+    /// New entities allowed per frame once nothing is left to recycle
+    pub spawn_budget: u32,
+    spawned_this_frame: u32,
+    _effect: PhantomData<T>,
+}
+
+impl<T: Component> UiPool<T> {
+    pub fn new(spawn_budget: u32) -> Self {
+        Self {
+            free: Vec::new(),
+            spawn_budget,
+            spawned_this_frame: 0,
+            _effect: PhantomData,
+        }
+    }
+
+    /// An entity for a new effect: a recycled one if any are free, otherwise a fresh
+    /// one if this frame's budget allows. The caller inserts the effect's components.
+    pub fn acquire(&mut self, commands: &mut Commands) -> Option<Entity> {
+        while let Some(entity) = self.free.pop() {
+            // Pooled entities are despawned along with their screen
+            if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                entity_commands.insert(Visibility::Inherited);
+                return Some(entity);
+            }
+        }
+
+        if self.spawned_this_frame >= self.spawn_budget {
+            return None;
+        }
+        self.spawned_this_frame += 1;
+        Some(commands.spawn_empty().id())
+    }
+
+    /// Hide a finished effect and keep its entity for the next one
+    pub fn release(&mut self, commands: &mut Commands, entity: Entity) {
+        commands.entity(entity).remove::<T>().insert(Visibility::Hidden);
+        self.free.push(entity);
+    }
+}
+
+/// Give every frame a fresh spawn budget
+pub fn reset_pool_budget<T: Component>(mut pool: ResMut<UiPool<T>>) {
+    pool.spawned_this_frame = 0;
+}
+
+/// Forget pooled entities when their screen closes (they're despawned with it)
+pub fn clear_pool<T: Component>(mut pool: ResMut<UiPool<T>>) {
+    pool.free.clear();
+}
@@ -0,0 +1,183 @@
+//! Pricing section of the market overlay - the player's price, the competitors', and any price war
+
+use bevy::prelude::*;
+use crate::marketing::MarketingState;
+use crate::price_war::{stepped_price, AcceptTruceEvent, PriceWarState};
+use super::bundle_panel::spawn_small_button;
+use super::{ButtonInteractionQuery, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+/// Button that moves the player's price by a step (-1 or +1)
+#[derive(Component)]
+pub struct PriceButton(pub i32);
+
+/// Marker for the player's price
+#[derive(Component)]
+pub struct PriceText;
+
+/// Marker for the competitors' pricing and the war status
+#[derive(Component)]
+pub struct PriceWarStatusText;
+
+/// Marker for the "Accept truce" button (hidden unless there's an offer)
+#[derive(Component)]
+pub struct AcceptTruceButton;
+
+/// The competitors' price line, tinted by how close to war things are
+type PriceWarStatusQuery<'w, 's> =
+    Query<'w, 's, (&'static mut Text, &'static mut TextColor), (With<PriceWarStatusText>, Without<PriceText>)>;
+
+/// Pricing section (goes under the market chart's legend)
+pub fn spawn_pricing_section(parent: &mut ChildSpawnerCommands) {
+    parent.spawn((
+        Text::new("PRICING"),
+        TextFont {
+            font_size: 14.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.9, 0.8, 0.5)),
+        Node {
+            margin: UiRect::top(Val::Px(14.0)),
+            ..default()
+        },
+    ));
+
+    parent
+        .spawn(Node {
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(6.0),
+            margin: UiRect::vertical(Val::Px(6.0)),
+            ..default()
+        })
+        .with_children(|parent| {
+            spawn_small_button(parent, "-", 24.0, PriceButton(-1));
+            spawn_small_button(parent, "+", 24.0, PriceButton(1));
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                PriceText,
+            ));
+        });
+
+    parent.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 12.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.7, 0.7, 0.7)),
+        PriceWarStatusText,
+    ));
+
+    parent
+        .spawn((
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(12.0), Val::Px(4.0)),
+                margin: UiRect::top(Val::Px(6.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                align_self: AlignSelf::FlexStart,
+                display: Display::None,
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.4, 0.7, 0.4)),
+            BackgroundColor(NORMAL_BUTTON),
+            AcceptTruceButton,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Accept truce (back to list price)"),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// Raise or lower the player's price
+pub fn handle_price_buttons(
+    mut interaction_query: Query<(&Interaction, &PriceButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut marketing: ResMut<MarketingState>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                marketing.price_multiplier = stepped_price(marketing.price_multiplier, button.0);
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+/// Take the competitors' truce
+pub fn handle_accept_truce_button(
+    mut interaction_query: ButtonInteractionQuery<AcceptTruceButton>,
+    mut truce_events: MessageWriter<AcceptTruceEvent>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                truce_events.write(AcceptTruceEvent);
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Keep the prices, the war status and the truce button current
+pub fn update_pricing_section(
+    marketing: Res<MarketingState>,
+    price_war: Res<PriceWarState>,
+    mut price_query: Query<&mut Text, (With<PriceText>, Without<PriceWarStatusText>)>,
+    mut status_query: PriceWarStatusQuery,
+    mut truce_query: Query<&mut Node, With<AcceptTruceButton>>,
+) {
+    if !marketing.is_changed() && !price_war.is_changed() {
+        return;
+    }
+
+    for mut text in &mut price_query {
+        **text = format!("Your price: {:.0}% of list", marketing.price_multiplier * 100.0);
+    }
+
+    // Exactly how far they've cut is for a survey to find out
+    let competitors = if price_war.competitor_price < 1.0 {
+        "Competitors: below list price"
+    } else {
+        "Competitors: list price"
+    };
+    for (mut text, mut color) in &mut status_query {
+        let (status, tint) = if price_war.at_war {
+            (
+                format!("{} - PRICE WAR, day {}. Stop undercutting them to end it.", competitors, price_war.war_days),
+                Color::srgb(1.0, 0.5, 0.4),
+            )
+        } else if price_war.undercut_days > 0 {
+            (
+                format!("{} - they've noticed you undercutting them ({} days)", competitors, price_war.undercut_days),
+                Color::srgb(1.0, 0.8, 0.4),
+            )
+        } else {
+            (competitors.to_string(), Color::srgb(0.7, 0.7, 0.7))
+        };
+        **text = status;
+        color.0 = tint;
+    }
+
+    for mut node in &mut truce_query {
+        node.display = if price_war.truce_offer_days.is_some() { Display::Flex } else { Display::None };
+    }
+}
@@ -0,0 +1,267 @@
+//! Profile picker on the selection screen - switch, create, rename and delete profiles
+
+use bevy::prelude::*;
+use crate::profile::{Profiles, SwitchProfileEvent, MAX_PROFILE_NAME};
+use super::{spawn_text_field, ButtonInteractionQuery, TextField, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+/// What a profile row's button does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileAction {
+    Use,
+    Rename,
+    Delete,
+}
+
+impl ProfileAction {
+    pub const ALL: [ProfileAction; 3] = [ProfileAction::Use, ProfileAction::Rename, ProfileAction::Delete];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ProfileAction::Use => "Use",
+            ProfileAction::Rename => "Rename",
+            ProfileAction::Delete => "Delete",
+        }
+    }
+}
+
+/// Marker for the list of profiles (rebuilt when profiles change)
+#[derive(Component)]
+pub struct ProfileList;
+
+/// A button on a profile's row
+#[derive(Component)]
+pub struct ProfileButton {
+    pub action: ProfileAction,
+    pub name: String,
+}
+
+/// Marker for the profile name field
+#[derive(Component)]
+pub struct ProfileNameField;
+
+/// Marker for the "New" profile button
+#[derive(Component)]
+pub struct NewProfileButton;
+
+/// Marker for the profile panel's error/confirmation line
+#[derive(Component)]
+pub struct ProfileStatusText;
+
+/// Profile panel in the selection screen's top corner
+pub fn spawn_profile_panel(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(15.0),
+                right: Val::Px(15.0),
+                width: Val::Px(340.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(10.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.3, 0.3, 0.3)),
+            BackgroundColor(Color::srgb(0.08, 0.08, 0.12)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("PROFILES"),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.6, 0.3)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(6.0)),
+                    ..default()
+                },
+            ));
+
+            parent.spawn((
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    margin: UiRect::bottom(Val::Px(8.0)),
+                    ..default()
+                },
+                ProfileList,
+            ));
+
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(6.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    spawn_text_field(parent, 220.0, MAX_PROFILE_NAME, "Profile name...", ProfileNameField);
+                    spawn_small_button(parent, "New", NewProfileButton);
+                });
+
+            parent.spawn((
+                Text::new("Type a name, then New, or Rename a profile to it."),
+                TextFont {
+                    font_size: 11.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.6, 0.6)),
+                Node {
+                    margin: UiRect::top(Val::Px(6.0)),
+                    ..default()
+                },
+                ProfileStatusText,
+            ));
+        });
+}
+
+fn spawn_small_button(parent: &mut ChildSpawnerCommands, label: &str, marker: impl Bundle) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+            BackgroundColor(NORMAL_BUTTON),
+            marker,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// Rebuild the profile list when profiles change
+pub fn update_profile_list(
+    mut commands: Commands,
+    profiles: Res<Profiles>,
+    list_query: Query<Entity, With<ProfileList>>,
+) {
+    if !profiles.is_changed() {
+        return;
+    }
+
+    for list in &list_query {
+        commands.entity(list).despawn_children().with_children(|parent| {
+            for name in &profiles.names {
+                let active = *name == profiles.active;
+                parent
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(4.0),
+                        margin: UiRect::bottom(Val::Px(4.0)),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Text::new(if active { format!("> {}", name) } else { name.clone() }),
+                            TextFont {
+                                font_size: 13.0,
+                                ..default()
+                            },
+                            TextColor(if active { Color::srgb(0.9, 0.8, 0.6) } else { Color::srgb(0.7, 0.7, 0.7) }),
+                            Node {
+                                flex_grow: 1.0,
+                                ..default()
+                            },
+                        ));
+
+                        for action in ProfileAction::ALL {
+                            // The playing profile can't be switched to or deleted
+                            if active && action != ProfileAction::Rename {
+                                continue;
+                            }
+                            spawn_small_button(parent, action.label(), ProfileButton { action, name: name.clone() });
+                        }
+                    });
+            }
+        });
+    }
+}
+
+/// Switch to, rename or delete a profile
+pub fn handle_profile_buttons(
+    mut interaction_query: Query<(&Interaction, &ProfileButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut profiles: ResMut<Profiles>,
+    mut field_query: Query<&mut TextField, With<ProfileNameField>>,
+    mut status_query: Query<&mut Text, With<ProfileStatusText>>,
+    mut switch_events: MessageWriter<SwitchProfileEvent>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                let Ok(mut field) = field_query.single_mut() else {
+                    continue;
+                };
+                let new_name = field.value.clone();
+                let status = match button.action {
+                    ProfileAction::Use => {
+                        switch_events.write(SwitchProfileEvent { name: button.name.clone() });
+                        Ok(format!("Playing as {}", button.name))
+                    }
+                    ProfileAction::Rename => profiles
+                        .rename(&button.name, &new_name)
+                        .map(|_| format!("Renamed {} to {}", button.name, new_name)),
+                    ProfileAction::Delete => profiles.delete(&button.name).map(|_| format!("Deleted {}", button.name)),
+                };
+                if status.is_ok() && button.action == ProfileAction::Rename {
+                    field.value.clear();
+                }
+                for mut text in &mut status_query {
+                    **text = status.clone().unwrap_or_else(|err| err);
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Create a profile from the name field and switch to it
+pub fn handle_new_profile_button(
+    mut interaction_query: ButtonInteractionQuery<NewProfileButton>,
+    mut profiles: ResMut<Profiles>,
+    mut field_query: Query<&mut TextField, With<ProfileNameField>>,
+    mut status_query: Query<&mut Text, With<ProfileStatusText>>,
+    mut switch_events: MessageWriter<SwitchProfileEvent>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                let Ok(mut field) = field_query.single_mut() else {
+                    continue;
+                };
+                let name = field.value.clone();
+                let status = profiles.create(&name).map(|_| {
+                    field.value.clear();
+                    switch_events.write(SwitchProfileEvent { name: name.clone() });
+                    format!("Created {} - playing as {}", name, name)
+                });
+                for mut text in &mut status_query {
+                    **text = status.clone().unwrap_or_else(|err| err);
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
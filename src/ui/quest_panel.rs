@@ -0,0 +1,164 @@
+//! Quests overlay - the current step of the Thing's story, and what to do about it
+
+use bevy::prelude::*;
+use crate::game_state::GameState;
+use crate::quests::{ChooseQuestOutcomeEvent, QuestBook, QuestLog};
+use super::{spawn_overlay, OverlayKind, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+/// Most outcomes a step can offer
+const MAX_OUTCOMES: usize = 3;
+
+/// Which line of the tracker a text shows
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub enum QuestText {
+    Title,
+    Body,
+    Objective,
+    Journal,
+}
+
+/// Button picking an outcome (index into the step's outcomes)
+#[derive(Component)]
+pub struct QuestOutcomeButton(pub usize);
+
+/// Label on an outcome button
+#[derive(Component)]
+pub struct QuestOutcomeText(pub usize);
+
+/// Quest tracker overlay
+pub fn spawn_quest_overlay(parent: &mut ChildSpawnerCommands) {
+    spawn_overlay(parent, OverlayKind::Quests, "QUESTS", 400.0, |parent| {
+        for (kind, size, color) in [
+            (QuestText::Title, 16.0, Color::srgb(1.0, 0.85, 0.4)),
+            (QuestText::Body, 13.0, Color::srgb(0.8, 0.8, 0.8)),
+            (QuestText::Objective, 13.0, Color::srgb(0.5, 0.9, 0.5)),
+        ] {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: size,
+                    ..default()
+                },
+                TextColor(color),
+                Node {
+                    margin: UiRect::bottom(Val::Px(8.0)),
+                    ..default()
+                },
+                kind,
+            ));
+        }
+
+        for index in 0..MAX_OUTCOMES {
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Percent(100.0),
+                        padding: UiRect::all(Val::Px(8.0)),
+                        margin: UiRect::bottom(Val::Px(6.0)),
+                        border: UiRect::all(Val::Px(1.0)),
+                        display: Display::None,
+                        ..default()
+                    },
+                    BorderColor::all(Color::srgb(0.7, 0.6, 0.3)),
+                    BackgroundColor(NORMAL_BUTTON),
+                    QuestOutcomeButton(index),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: 13.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        QuestOutcomeText(index),
+                    ));
+                });
+        }
+
+        parent.spawn((
+            Text::new(""),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            Node {
+                margin: UiRect::top(Val::Px(8.0)),
+                ..default()
+            },
+            QuestText::Journal,
+        ));
+    });
+}
+
+/// Pick an outcome
+pub fn handle_quest_outcome_buttons(
+    mut interaction_query: Query<(&Interaction, &QuestOutcomeButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut choose_events: MessageWriter<ChooseQuestOutcomeEvent>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                choose_events.write(ChooseQuestOutcomeEvent { index: button.0 });
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+/// Keep the step, its progress and the outcome buttons current
+pub fn update_quest_panel(
+    book: Res<QuestBook>,
+    log: Res<QuestLog>,
+    game_state: Res<GameState>,
+    mut text_query: Query<(&mut Text, &QuestText)>,
+    mut outcome_text_query: Query<(&mut Text, &QuestOutcomeText), Without<QuestText>>,
+    mut button_query: Query<(&mut Node, &QuestOutcomeButton)>,
+) {
+    if !log.is_changed() && !game_state.is_changed() {
+        return;
+    }
+
+    let chain = log.thing_type.and_then(|thing_type| book.chain(thing_type));
+    let step = log.current(&book);
+    let outcomes = step.filter(|_| log.ready).map_or(&[][..], |step| &step.outcomes[..]);
+
+    for (mut text, kind) in &mut text_query {
+        **text = match (kind, step) {
+            (QuestText::Title, Some(step)) => {
+                format!("{}: {}", chain.map_or("", |chain| chain.title.as_str()), step.title)
+            }
+            (QuestText::Title, None) if log.finished() => {
+                format!("{}: the end", chain.map_or("", |chain| chain.title.as_str()))
+            }
+            (QuestText::Title, None) => "No story yet".to_string(),
+            (QuestText::Body, Some(step)) => step.text.clone(),
+            (QuestText::Body, None) if log.finished() => "That's the whole story. For now.".to_string(),
+            (QuestText::Body, None) => String::new(),
+            (QuestText::Objective, Some(_)) if log.ready => "Done - what now?".to_string(),
+            (QuestText::Objective, Some(step)) => format!(
+                "{} ({:.0}%)",
+                step.objective.describe(),
+                step.objective.progress(&log, &game_state) * 100.0
+            ),
+            (QuestText::Objective, None) => String::new(),
+            (QuestText::Journal, _) => log.journal.join("\n"),
+        };
+    }
+
+    for (mut node, button) in &mut button_query {
+        let display = if button.0 < outcomes.len() { Display::Flex } else { Display::None };
+        if node.display != display {
+            node.display = display;
+        }
+    }
+    for (mut text, label) in &mut outcome_text_query {
+        if let Some(outcome) = outcomes.get(label.0) {
+            **text = outcome.describe();
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! Redemption overlay - Rebrand and Apology Tour for disgraced Bad Things
+
+use bevy::prelude::*;
+use crate::currency::money_whole;
+use crate::game_state::GameState;
+use crate::thing_type::ThingRegistry;
+use crate::recovery::{RecoveryAction, RecoveryState, StartRecoveryEvent};
+use super::{set_overlay_available, spawn_overlay, OverlayKind, OverlayPanelQuery, OverlayToggleQuery, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON, DISABLED_BUTTON};
+
+/// Button that starts a recovery action
+#[derive(Component)]
+pub struct RecoveryActionButton(pub RecoveryAction);
+
+/// Text describing the recovery action underway
+#[derive(Component)]
+pub struct RecoveryStatusText;
+
+/// Redemption overlay listing the recovery actions
+pub fn spawn_recovery_overlay(parent: &mut ChildSpawnerCommands) {
+    spawn_overlay(parent, OverlayKind::Recovery, "REDEMPTION", 380.0, |parent| {
+        parent.spawn((
+            Text::new(""),
+            TextFont {
+                font_size: 14.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.9, 0.8, 0.5)),
+            Node {
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+            RecoveryStatusText,
+        ));
+
+        for action in RecoveryAction::ALL {
+            spawn_action_button(parent, action);
+        }
+    });
+}
+
+fn spawn_action_button(parent: &mut ChildSpawnerCommands, action: RecoveryAction) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(8.0)),
+                margin: UiRect::bottom(Val::Px(8.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+            BackgroundColor(NORMAL_BUTTON),
+            RecoveryActionButton(action),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(format!("{} - {}", action.name(), money_whole(action.cost()))),
+                TextFont {
+                    font_size: 15.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+            parent.spawn((
+                Text::new(action.description()),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+            ));
+            parent.spawn((
+                Text::new(format!(
+                    "{} weeks, {:.0}% chance it works",
+                    action.duration_days() / 7,
+                    action.success_chance() * 100.0
+                )),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            ));
+        });
+}
+
+/// Start a recovery action when its button is pressed
+pub fn handle_recovery_buttons(
+    mut interaction_query: Query<
+        (&Interaction, &RecoveryActionButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    recovery: Res<RecoveryState>,
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    mut start_events: MessageWriter<StartRecoveryEvent>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        let can_start = recovery.can_start(button.0, &game_state, &things);
+
+        match *interaction {
+            Interaction::Pressed => {
+                if can_start {
+                    *bg_color = PRESSED_BUTTON.into();
+                    start_events.write(StartRecoveryEvent { action: button.0 });
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = if can_start { HOVERED_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+            Interaction::None => {
+                *bg_color = if can_start { NORMAL_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+        }
+    }
+}
+
+/// Show progress on the action underway
+pub fn update_recovery_status(
+    recovery: Res<RecoveryState>,
+    mut query: Query<&mut Text, With<RecoveryStatusText>>,
+) {
+    if !recovery.is_changed() {
+        return;
+    }
+
+    let status = match recovery.active {
+        Some(active) => format!(
+            "{} underway: {} days to go ({:.0}% done)",
+            active.action.name(),
+            active.days_remaining,
+            active.progress() * 100.0
+        ),
+        None => "Terry's cousins are standing by.".to_string(),
+    };
+
+    for mut text in &mut query {
+        **text = status.clone();
+    }
+}
+
+/// The Redemption header button only appears while a shady Thing sits at rock bottom
+pub fn update_recovery_toggle_visibility(
+    recovery: Res<RecoveryState>,
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    mut toggles: OverlayToggleQuery,
+    mut panels: OverlayPanelQuery,
+) {
+    let available = recovery.unlocked
+        && !recovery.redeemed
+        && things.is_shady(game_state.thing_type);
+    set_overlay_available(OverlayKind::Recovery, available, &mut toggles, &mut panels);
+}
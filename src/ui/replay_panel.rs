@@ -0,0 +1,361 @@
+//! Replay overlay - scrub back and forth through the run a day at a time
+//!
+//! The charts fill in up to the chosen day, and under them are that day's
+//! numbers, what the player did, and what happened (Terry included).
+
+use bevy::prelude::*;
+use crate::currency::money;
+use crate::event_log::EventLog;
+use crate::replay::ReplayLog;
+use crate::stats::{DailySnapshot, RunStats};
+use crate::thing_type::ThingRegistry;
+use super::{spawn_overlay, OverlayKind, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+/// Bars per chart (longer runs are bucketed)
+const CHART_BARS: usize = 60;
+const CHART_HEIGHT: f32 = 50.0;
+const BAR_SHOWN: Color = Color::srgb(0.5, 0.8, 0.5);
+const BAR_CURSOR: Color = Color::srgb(1.0, 0.85, 0.4);
+
+/// Which day the replay is on
+#[derive(Resource, Default)]
+pub struct ReplayCursor {
+    /// Index into the run's daily snapshots (None follows the latest day)
+    pub day: Option<usize>,
+}
+
+/// Where a scrub button moves the cursor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayStep {
+    Start,
+    BackWeek,
+    BackDay,
+    ForwardDay,
+    ForwardWeek,
+    Latest,
+}
+
+impl ReplayStep {
+    const ALL: [ReplayStep; 6] = [
+        ReplayStep::Start,
+        ReplayStep::BackWeek,
+        ReplayStep::BackDay,
+        ReplayStep::ForwardDay,
+        ReplayStep::ForwardWeek,
+        ReplayStep::Latest,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            ReplayStep::Start => "|<",
+            ReplayStep::BackWeek => "<< Week",
+            ReplayStep::BackDay => "< Day",
+            ReplayStep::ForwardDay => "Day >",
+            ReplayStep::ForwardWeek => "Week >>",
+            ReplayStep::Latest => ">|",
+        }
+    }
+
+    /// The day it lands on out of `days` (None means back to following the latest)
+    fn apply(&self, from: usize, days: usize) -> Option<usize> {
+        let last = days.saturating_sub(1);
+        let target = match self {
+            ReplayStep::Start => 0,
+            ReplayStep::BackWeek => from.saturating_sub(7),
+            ReplayStep::BackDay => from.saturating_sub(1),
+            ReplayStep::ForwardDay => from + 1,
+            ReplayStep::ForwardWeek => from + 7,
+            ReplayStep::Latest => last,
+        };
+        (target < last).then_some(target)
+    }
+}
+
+/// What a chart plots
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayChart {
+    Money,
+    Reputation,
+}
+
+impl ReplayChart {
+    const ALL: [ReplayChart; 2] = [ReplayChart::Money, ReplayChart::Reputation];
+
+    fn name(&self) -> &'static str {
+        match self {
+            ReplayChart::Money => "Money",
+            ReplayChart::Reputation => "Reputation",
+        }
+    }
+
+    fn value(&self, snapshot: &DailySnapshot) -> f64 {
+        match self {
+            ReplayChart::Money => snapshot.money,
+            ReplayChart::Reputation => snapshot.reputation as f64,
+        }
+    }
+}
+
+/// Button that moves the cursor
+#[derive(Component)]
+pub struct ReplayStepButton(pub ReplayStep);
+
+/// A chart bar; clicking it jumps to the last day it covers
+#[derive(Component)]
+pub struct ReplayBar {
+    pub chart: ReplayChart,
+    pub index: usize,
+}
+
+/// Which part of the day's write-up a text shows
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayText {
+    Date,
+    Numbers,
+    Actions,
+    Events,
+}
+
+/// The last snapshot a bar covers, with `days` snapshots spread over the bars
+fn bar_day(bar: usize, days: usize) -> Option<usize> {
+    let bars = days.min(CHART_BARS);
+    (bar < bars).then(|| (bar + 1) * days / bars - 1)
+}
+
+/// Replay overlay
+pub fn spawn_replay_overlay(parent: &mut ChildSpawnerCommands) {
+    spawn_overlay(parent, OverlayKind::Replay, "REPLAY", 460.0, |parent| {
+        parent
+            .spawn(Node {
+                width: Val::Percent(100.0),
+                flex_wrap: FlexWrap::Wrap,
+                column_gap: Val::Px(6.0),
+                row_gap: Val::Px(4.0),
+                margin: UiRect::bottom(Val::Px(8.0)),
+                ..default()
+            })
+            .with_children(|parent| {
+                for step in ReplayStep::ALL {
+                    spawn_step_button(parent, step);
+                }
+            });
+
+        for chart in ReplayChart::ALL {
+            parent.spawn((
+                Text::new(chart.name()),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            ));
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Percent(100.0),
+                        height: Val::Px(CHART_HEIGHT),
+                        align_items: AlignItems::FlexEnd,
+                        column_gap: Val::Px(1.0),
+                        margin: UiRect::bottom(Val::Px(6.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.12, 0.12, 0.18)),
+                ))
+                .with_children(|parent| {
+                    for index in 0..CHART_BARS {
+                        parent.spawn((
+                            Button,
+                            Node {
+                                flex_grow: 1.0,
+                                height: Val::Percent(0.0),
+                                display: Display::None,
+                                ..default()
+                            },
+                            BackgroundColor(BAR_SHOWN),
+                            ReplayBar { chart, index },
+                        ));
+                    }
+                });
+        }
+
+        for (kind, size, color) in [
+            (ReplayText::Date, 16.0, Color::srgb(1.0, 0.85, 0.4)),
+            (ReplayText::Numbers, 13.0, Color::srgb(0.85, 0.85, 0.85)),
+            (ReplayText::Actions, 13.0, Color::srgb(0.5, 0.9, 0.5)),
+            (ReplayText::Events, 12.0, Color::srgb(0.7, 0.7, 0.8)),
+        ] {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: size,
+                    ..default()
+                },
+                TextColor(color),
+                Node {
+                    margin: UiRect::bottom(Val::Px(6.0)),
+                    ..default()
+                },
+                kind,
+            ));
+        }
+    });
+}
+
+fn spawn_step_button(parent: &mut ChildSpawnerCommands, step: ReplayStep) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(6.0), Val::Px(4.0)),
+                justify_content: JustifyContent::Center,
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+            BackgroundColor(NORMAL_BUTTON),
+            ReplayStepButton(step),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(step.label()),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// Step the replay backwards or forwards
+pub fn handle_replay_step_buttons(
+    mut interaction_query: Query<(&Interaction, &ReplayStepButton, &mut BackgroundColor), Changed<Interaction>>,
+    stats: Res<RunStats>,
+    mut cursor: ResMut<ReplayCursor>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                let days = stats.snapshots.len();
+                let from = cursor.day.unwrap_or(days.saturating_sub(1));
+                cursor.day = button.0.apply(from, days);
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+/// Jump straight to a bar's day
+pub fn handle_replay_bars(
+    interaction_query: Query<(&Interaction, &ReplayBar), Changed<Interaction>>,
+    stats: Res<RunStats>,
+    mut cursor: ResMut<ReplayCursor>,
+) {
+    for (interaction, bar) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let days = stats.snapshots.len();
+        if let Some(day) = bar_day(bar.index, days) {
+            cursor.day = (day + 1 < days).then_some(day);
+        }
+    }
+}
+
+/// Redraw the charts up to the cursor and write up the day it's on
+pub fn update_replay_panel(
+    stats: Res<RunStats>,
+    replay: Res<ReplayLog>,
+    log: Res<EventLog>,
+    cursor: Res<ReplayCursor>,
+    things: Res<ThingRegistry>,
+    mut bar_query: Query<(&mut Node, &mut BackgroundColor, &ReplayBar)>,
+    mut text_query: Query<(&mut Text, &ReplayText)>,
+) {
+    if !stats.is_changed() && !cursor.is_changed() && !replay.is_changed() && !log.is_changed() {
+        return;
+    }
+
+    let snapshots = &stats.snapshots;
+    let days = snapshots.len();
+    let day = cursor.day.filter(|day| *day < days).unwrap_or(days.saturating_sub(1));
+
+    // Scaled to the whole run, so the line climbs the same way every time it's replayed
+    let ranges: Vec<(ReplayChart, f64, f64)> = ReplayChart::ALL
+        .iter()
+        .map(|chart| {
+            let values = snapshots.iter().map(|snapshot| chart.value(snapshot));
+            let low = values.clone().fold(0.0, f64::min);
+            let high = values.fold(0.0, f64::max);
+            (*chart, low, high)
+        })
+        .collect();
+
+    for (mut node, mut color, bar) in &mut bar_query {
+        let shown = bar_day(bar.index, days).filter(|covers| *covers <= day);
+        let Some(covers) = shown else {
+            node.display = Display::None;
+            continue;
+        };
+        let Some((_, low, high)) = ranges.iter().find(|(chart, _, _)| *chart == bar.chart) else {
+            continue;
+        };
+        let value = bar.chart.value(&snapshots[covers]);
+        let fraction = if high > low { (value - low) / (high - low) } else { 0.0 };
+        node.display = Display::Flex;
+        node.height = Val::Percent((fraction * 100.0).max(2.0) as f32);
+        let last_shown = bar_day(bar.index + 1, days).is_none_or(|next| next > day);
+        *color = if last_shown { BAR_CURSOR } else { BAR_SHOWN }.into();
+    }
+
+    // A snapshot is taken as a day begins, so it's the result of what happened since the one before
+    let snapshot = snapshots.get(day);
+    let until = snapshot.map(|snapshot| snapshot.date.as_str()).unwrap_or_default();
+    let since = day.checked_sub(1).map(|previous| snapshots[previous].date.as_str()).unwrap_or_default();
+    let in_day = |date: String| date.as_str() >= since && date.as_str() < until;
+    for (mut text, kind) in &mut text_query {
+        **text = match (kind, snapshot) {
+            (_, None) => match kind {
+                ReplayText::Date => "Nothing to replay until the first day is over".to_string(),
+                _ => String::new(),
+            },
+            (ReplayText::Date, Some(snapshot)) => format!(
+                "Day {} of {} - {}{}",
+                day + 1,
+                days,
+                snapshot.date,
+                if cursor.day.is_none() { " (latest)" } else { "" }
+            ),
+            (ReplayText::Numbers, Some(snapshot)) => format!(
+                "{}: {}, reputation {:.2}, {} Things made, {} customers",
+                snapshot.thing_type,
+                money(snapshot.money),
+                snapshot.reputation,
+                snapshot.things_produced,
+                snapshot.customers_served
+            ),
+            (ReplayText::Actions, Some(_)) => {
+                let actions: Vec<String> = replay
+                    .days
+                    .iter()
+                    .filter(|actions| in_day(actions.date.iso()))
+                    .flat_map(|actions| actions.describe(&things))
+                    .collect();
+                if actions.is_empty() {
+                    "You didn't do anything that day.".to_string()
+                } else {
+                    actions.join("\n")
+                }
+            }
+            (ReplayText::Events, Some(_)) => log
+                .entries
+                .iter()
+                .filter(|entry| in_day(entry.date.iso()))
+                .map(|entry| format!("[{}] {}", entry.category.name(), entry.text))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        };
+    }
+}
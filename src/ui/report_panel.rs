@@ -0,0 +1,319 @@
+//! Monthly financial reports - the pop-up report and the inbox that keeps them all
+
+use bevy::prelude::*;
+use crate::accounting::{Accounting, MonthlyReportEvent};
+use crate::settings::Settings;
+use super::{spawn_overlay, ButtonInteractionQuery, FocusTrap, OverlayKind, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+/// The report currently open in the modal, if any (index into the inbox)
+#[derive(Resource, Default)]
+pub struct ReportModal {
+    pub showing: Option<usize>,
+}
+
+/// Marker for the report modal
+#[derive(Component)]
+pub struct ReportModalPanel;
+
+/// Marker for the report modal's title
+#[derive(Component)]
+pub struct ReportTitleText;
+
+/// Marker for the report modal's body
+#[derive(Component)]
+pub struct ReportBodyText;
+
+/// Marker for the modal's "Close" button
+#[derive(Component)]
+pub struct CloseReportButton;
+
+/// Marker for the modal's "don't show again" button
+#[derive(Component)]
+pub struct ReportToInboxButton;
+
+/// Container the inbox entries are rebuilt into
+#[derive(Component)]
+pub struct InboxList;
+
+/// Inbox entry that opens a report
+#[derive(Component)]
+pub struct InboxReportButton(pub usize);
+
+/// Finances overlay: the savings account, ThingCoin while it's around, then every monthly report
+pub fn spawn_inbox_overlay(parent: &mut ChildSpawnerCommands) {
+    spawn_overlay(parent, OverlayKind::Inbox, "FINANCES", 320.0, |parent| {
+        super::savings_panel::spawn_savings_section(parent);
+        super::crypto_panel::spawn_crypto_section(parent);
+        parent.spawn((
+            Text::new("MONTHLY REPORTS"),
+            TextFont {
+                font_size: 14.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.8, 0.9, 0.8)),
+            Node {
+                margin: UiRect::bottom(Val::Px(4.0)),
+                ..default()
+            },
+        ));
+        parent.spawn((
+            Node {
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            InboxList,
+        ));
+    });
+}
+
+/// Centered report window (hidden until a report is opened)
+pub fn spawn_report_modal(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(90.0),
+                left: Val::Percent(50.0),
+                margin: UiRect::left(Val::Px(-200.0)),
+                width: Val::Px(400.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(15.0)),
+                border: UiRect::all(Val::Px(2.0)),
+                display: Display::None,
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.5, 0.7, 0.5)),
+            BackgroundColor(Color::srgb(0.07, 0.1, 0.08)),
+            GlobalZIndex(70),
+            ReportModalPanel,
+            FocusTrap,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.9, 0.8)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(10.0)),
+                    ..default()
+                },
+                ReportTitleText,
+            ));
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(12.0)),
+                    ..default()
+                },
+                ReportBodyText,
+            ));
+
+            parent
+                .spawn(Node {
+                    column_gap: Val::Px(8.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    spawn_modal_button(parent, "Close", CloseReportButton);
+                    spawn_modal_button(parent, "Don't show again (Inbox only)", ReportToInboxButton);
+                });
+        });
+}
+
+fn spawn_modal_button(parent: &mut ChildSpawnerCommands, label: &str, marker: impl Component) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.4, 0.6, 0.4)),
+            BackgroundColor(NORMAL_BUTTON),
+            marker,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// Pop the new report up when a month closes (unless it should only go to the inbox)
+pub fn show_monthly_reports(
+    mut report_events: MessageReader<MonthlyReportEvent>,
+    settings: Res<Settings>,
+    mut modal: ResMut<ReportModal>,
+) {
+    for event in report_events.read() {
+        if settings.monthly_report_popup {
+            modal.showing = Some(event.index);
+        }
+    }
+}
+
+/// Close the modal
+pub fn handle_close_report_button(
+    mut interaction_query: ButtonInteractionQuery<CloseReportButton>,
+    mut modal: ResMut<ReportModal>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                modal.showing = None;
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Close the modal and send future reports straight to the inbox
+pub fn handle_report_to_inbox_button(
+    mut interaction_query: ButtonInteractionQuery<ReportToInboxButton>,
+    mut modal: ResMut<ReportModal>,
+    mut settings: ResMut<Settings>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                modal.showing = None;
+                settings.monthly_report_popup = false;
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Open a report from the inbox
+pub fn handle_inbox_buttons(
+    mut interaction_query: Query<
+        (&Interaction, &InboxReportButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    mut modal: ResMut<ReportModal>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                modal.showing = Some(button.0);
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Show or hide the modal and fill in the open report
+pub fn update_report_modal(
+    modal: Res<ReportModal>,
+    accounting: Res<Accounting>,
+    mut panel_query: Query<&mut Node, With<ReportModalPanel>>,
+    mut title_query: Query<&mut Text, (With<ReportTitleText>, Without<ReportBodyText>)>,
+    mut body_query: Query<&mut Text, (With<ReportBodyText>, Without<ReportTitleText>)>,
+) {
+    if !modal.is_changed() {
+        return;
+    }
+
+    let report = modal.showing.and_then(|index| accounting.reports.get(index));
+    for mut node in &mut panel_query {
+        node.display = if report.is_some() { Display::Flex } else { Display::None };
+    }
+
+    let Some(report) = report else {
+        return;
+    };
+    for mut text in &mut title_query {
+        **text = format!("MONTHLY REPORT - {}", report.month.to_uppercase());
+    }
+    for mut text in &mut body_query {
+        **text = report.body();
+    }
+}
+
+/// Add new reports to the inbox list, newest first
+pub fn update_inbox_list(
+    mut commands: Commands,
+    accounting: Res<Accounting>,
+    list_query: Query<Entity, With<InboxList>>,
+    mut shown: Local<Option<usize>>,
+) {
+    if *shown == Some(accounting.reports.len()) {
+        return;
+    }
+    *shown = Some(accounting.reports.len());
+
+    for list in &list_query {
+        commands.entity(list).despawn_children().with_children(|parent| {
+            if accounting.reports.is_empty() {
+                parent.spawn((
+                    Text::new("No reports yet. The first one arrives next month."),
+                    TextFont {
+                        font_size: 12.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.5, 0.5, 0.5)),
+                ));
+            }
+
+            for (index, report) in accounting.reports.iter().enumerate().rev() {
+                parent
+                    .spawn((
+                        Button,
+                        Node {
+                            width: Val::Percent(100.0),
+                            padding: UiRect::axes(Val::Px(8.0), Val::Px(5.0)),
+                            margin: UiRect::bottom(Val::Px(4.0)),
+                            border: UiRect::all(Val::Px(1.0)),
+                            ..default()
+                        },
+                        BorderColor::all(Color::srgb(0.3, 0.4, 0.3)),
+                        BackgroundColor(NORMAL_BUTTON),
+                        InboxReportButton(index),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Text::new(report.summary()),
+                            TextFont {
+                                font_size: 13.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(0.85, 0.85, 0.85)),
+                        ));
+                    });
+            }
+        });
+    }
+}
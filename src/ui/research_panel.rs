@@ -0,0 +1,120 @@
+//! R&D overlay - fund research into new capabilities
+
+use bevy::prelude::*;
+use crate::currency::money_whole;
+use crate::game_state::GameState;
+use crate::research::{ResearchState, StartResearchEvent, Tech};
+use super::{spawn_overlay, OverlayKind, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON, DISABLED_BUTTON};
+
+/// Button that starts research on a tech
+#[derive(Component)]
+pub struct ResearchButton(pub Tech);
+
+/// Text showing a tech's research status
+#[derive(Component)]
+pub struct ResearchStatusText(pub Tech);
+
+/// R&D overlay with one entry per tech
+pub fn spawn_research_overlay(parent: &mut ChildSpawnerCommands) {
+    spawn_overlay(parent, OverlayKind::Research, "R&D", 380.0, |parent| {
+        for tech in Tech::ALL {
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Percent(100.0),
+                        flex_direction: FlexDirection::Column,
+                        padding: UiRect::all(Val::Px(8.0)),
+                        margin: UiRect::bottom(Val::Px(8.0)),
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    BorderColor::all(Color::srgb(0.4, 0.6, 0.5)),
+                    BackgroundColor(NORMAL_BUTTON),
+                    ResearchButton(tech),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(format!("{} - {}, {} days", tech.name(), money_whole(tech.cost()), tech.duration_days())),
+                        TextFont {
+                            font_size: 15.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                    parent.spawn((
+                        Text::new(tech.description()),
+                        TextFont {
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                    ));
+                    parent.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.6, 0.9, 0.7)),
+                        ResearchStatusText(tech),
+                    ));
+                });
+        }
+    });
+}
+
+/// Start research when a tech is picked
+pub fn handle_research_buttons(
+    mut interaction_query: Query<
+        (&Interaction, &ResearchButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    research: Res<ResearchState>,
+    game_state: Res<GameState>,
+    mut start_events: MessageWriter<StartResearchEvent>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        let can_start = research.can_start(button.0, &game_state);
+
+        match *interaction {
+            Interaction::Pressed => {
+                if can_start {
+                    *bg_color = PRESSED_BUTTON.into();
+                    start_events.write(StartResearchEvent { tech: button.0 });
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = if can_start { HOVERED_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+            Interaction::None => {
+                *bg_color = if can_start { NORMAL_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+        }
+    }
+}
+
+/// Keep each tech's status line current
+pub fn update_research_status(
+    research: Res<ResearchState>,
+    mut query: Query<(&mut Text, &ResearchStatusText)>,
+) {
+    if !research.is_changed() {
+        return;
+    }
+
+    for (mut text, status_text) in &mut query {
+        let tech = status_text.0;
+        **text = if research.is_unlocked(tech) {
+            "Researched".to_string()
+        } else {
+            match research.active {
+                Some(active) if active.tech == tech => {
+                    format!("In the lab: {} days to go", active.days_remaining)
+                }
+                Some(_) => "Lab busy".to_string(),
+                None => "Not started".to_string(),
+            }
+        };
+    }
+}
@@ -0,0 +1,222 @@
+//! Returns overlay - refund policy, restocking, and what has come back so far
+
+use bevy::prelude::*;
+use crate::currency::money_whole;
+use crate::game_state::GameState;
+use crate::returns::{return_rate, RefundPolicy, ReturnsState};
+use crate::thing_type::ThingRegistry;
+use super::{spawn_overlay, ButtonInteractionQuery, OverlayKind, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+const POLICY_ACTIVE: Color = Color::srgb(0.5, 0.9, 0.5);
+const POLICY_INACTIVE: Color = Color::srgb(0.7, 0.7, 0.7);
+
+/// Button that switches to a refund policy
+#[derive(Component)]
+pub struct RefundPolicyButton(pub RefundPolicy);
+
+/// Label of a refund policy button
+#[derive(Component)]
+pub struct RefundPolicyText(pub RefundPolicy);
+
+/// Marker for the restocking on/off button
+#[derive(Component)]
+pub struct RestockingButton;
+
+/// Marker for the restocking button's label
+#[derive(Component)]
+pub struct RestockingText;
+
+/// Marker for the returns summary text
+#[derive(Component)]
+pub struct ReturnsSummaryText;
+
+/// Returns overlay
+pub fn spawn_returns_overlay(parent: &mut ChildSpawnerCommands) {
+    spawn_overlay(parent, OverlayKind::Returns, "RETURNS", 380.0, |parent| {
+        parent.spawn((
+            Text::new(
+                "Some Things come back about ten days after they're sold. \
+                 How you handle that is up to you.",
+            ),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            Node {
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+        ));
+
+        parent.spawn((
+            Text::new(""),
+            TextFont {
+                font_size: 13.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            Node {
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+            ReturnsSummaryText,
+        ));
+
+        for policy in RefundPolicy::ALL {
+            spawn_policy_row(parent, policy);
+        }
+
+        parent
+            .spawn((
+                Button,
+                Node {
+                    padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                    margin: UiRect::top(Val::Px(6.0)),
+                    border: UiRect::all(Val::Px(1.0)),
+                    ..default()
+                },
+                BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+                BackgroundColor(NORMAL_BUTTON),
+                RestockingButton,
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Text::new(""),
+                    TextFont {
+                        font_size: 13.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                    RestockingText,
+                ));
+            });
+    });
+}
+
+fn spawn_policy_row(parent: &mut ChildSpawnerCommands, policy: RefundPolicy) {
+    parent
+        .spawn(Node {
+            width: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(8.0),
+            margin: UiRect::bottom(Val::Px(6.0)),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        min_width: Val::Px(140.0),
+                        padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                        justify_content: JustifyContent::Center,
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+                    BackgroundColor(NORMAL_BUTTON),
+                    RefundPolicyButton(policy),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(policy.name()),
+                        TextFont {
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(POLICY_INACTIVE),
+                        RefundPolicyText(policy),
+                    ));
+                });
+
+            parent.spawn((
+                Text::new(policy.description()),
+                TextFont {
+                    font_size: 11.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.6, 0.6)),
+                Node {
+                    flex_shrink: 1.0,
+                    ..default()
+                },
+            ));
+        });
+}
+
+/// Switch refund policy
+pub fn handle_refund_policy_buttons(
+    mut interaction_query: Query<(&Interaction, &RefundPolicyButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut returns: ResMut<ReturnsState>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                returns.policy = button.0;
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+/// Switch restocking on/off
+pub fn handle_restocking_button(
+    mut interaction_query: ButtonInteractionQuery<RestockingButton>,
+    mut returns: ResMut<ReturnsState>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                returns.restocking = !returns.restocking;
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+/// Keep the summary, policy labels and restocking button current
+pub fn update_returns_panel(
+    returns: Res<ReturnsState>,
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    mut summary_query: Query<&mut Text, (With<ReturnsSummaryText>, Without<RestockingText>)>,
+    mut restocking_query: Query<&mut Text, (With<RestockingText>, Without<ReturnsSummaryText>)>,
+    mut policy_query: Query<(&mut TextColor, &RefundPolicyText)>,
+) {
+    if !returns.is_changed() {
+        return;
+    }
+
+    let rate = game_state
+        .thing_type
+        .map(|t| return_rate(&things[t], game_state.reputation, returns.policy))
+        .unwrap_or(0.0);
+    for mut text in &mut summary_query {
+        **text = format!(
+            "Return rate: {:.1}% of sales\nRefunds due in the next ten days: about {}\n\
+             Returned so far: {} Things, {} refunded\nRestocked: {} Things",
+            rate * 100.0,
+            money_whole(returns.refunds_in_flight(&game_state, &things)),
+            returns.total_returned,
+            money_whole(returns.total_refunded),
+            returns.total_restocked
+        );
+    }
+
+    for mut text in &mut restocking_query {
+        **text = if returns.restocking {
+            "Restocking: ON (resell returns open-box)".to_string()
+        } else {
+            "Restocking: OFF (returns go in the skip)".to_string()
+        };
+    }
+
+    for (mut color, policy_text) in &mut policy_query {
+        color.0 = if policy_text.0 == returns.policy { POLICY_ACTIVE } else { POLICY_INACTIVE };
+    }
+}
@@ -0,0 +1,134 @@
+//! Reward cards - pick one of three when a big milestone pays out
+
+use bevy::prelude::*;
+use crate::rewards::{ChooseRewardEvent, RewardState};
+use super::{FocusTrap, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+/// Marker for the reward card window
+#[derive(Component)]
+pub struct RewardCardPanel;
+
+/// A reward card (index into the pending choice)
+#[derive(Component)]
+pub struct RewardCardButton(pub usize);
+
+/// Label on a reward card
+#[derive(Component)]
+pub struct RewardCardText(pub usize);
+
+/// Centered reward card window (hidden until a choice is pending)
+pub fn spawn_reward_cards(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(140.0),
+                left: Val::Percent(50.0),
+                margin: UiRect::left(Val::Px(-250.0)),
+                width: Val::Px(500.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                padding: UiRect::all(Val::Px(15.0)),
+                border: UiRect::all(Val::Px(2.0)),
+                display: Display::None,
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.9, 0.75, 0.3)),
+            BackgroundColor(Color::srgb(0.12, 0.1, 0.06)),
+            GlobalZIndex(70),
+            RewardCardPanel,
+            FocusTrap,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("MILESTONE REWARD - PICK ONE"),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.85, 0.3)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(12.0)),
+                    ..default()
+                },
+            ));
+
+            parent
+                .spawn(Node {
+                    column_gap: Val::Px(10.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for index in 0..3 {
+                        parent
+                            .spawn((
+                                Button,
+                                Node {
+                                    width: Val::Px(145.0),
+                                    height: Val::Px(90.0),
+                                    padding: UiRect::all(Val::Px(8.0)),
+                                    border: UiRect::all(Val::Px(1.0)),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                BorderColor::all(Color::srgb(0.6, 0.5, 0.3)),
+                                BackgroundColor(NORMAL_BUTTON),
+                                RewardCardButton(index),
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    Text::new(""),
+                                    TextFont {
+                                        font_size: 14.0,
+                                        ..default()
+                                    },
+                                    TextColor(Color::WHITE),
+                                    TextLayout::new_with_justify(Justify::Center),
+                                    RewardCardText(index),
+                                ));
+                            });
+                    }
+                });
+        });
+}
+
+/// Show the cards while a choice is pending
+pub fn update_reward_cards(
+    rewards: Res<RewardState>,
+    mut panel_query: Query<&mut Node, With<RewardCardPanel>>,
+    mut text_query: Query<(&mut Text, &RewardCardText)>,
+    added_query: Query<(), Added<RewardCardPanel>>,
+) {
+    if !rewards.is_changed() && added_query.is_empty() {
+        return;
+    }
+
+    let display = if rewards.pending_choice.is_some() { Display::Flex } else { Display::None };
+    for mut node in &mut panel_query {
+        node.display = display;
+    }
+    let Some(cards) = rewards.pending_choice else {
+        return;
+    };
+    for (mut text, card) in &mut text_query {
+        **text = cards[card.0].describe();
+    }
+}
+
+/// Pick a card
+pub fn handle_reward_card_buttons(
+    mut interaction_query: Query<(&Interaction, &RewardCardButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut choose_events: MessageWriter<ChooseRewardEvent>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                choose_events.write(ChooseRewardEvent { index: button.0 });
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! Saved runs - Continue on the selection screen, and Save in the stats overlay
+
+use bevy::prelude::*;
+use crate::profile::Profiles;
+use crate::save::{load_thumbnail, peek, save_path, thumbnail_path, SavedRun};
+use super::{ButtonInteractionQuery, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+/// Marker for the "Continue" button (hidden without a saved run)
+#[derive(Component)]
+pub struct ContinueButton;
+
+/// Marker for the Continue button's label (says which run it is)
+#[derive(Component)]
+pub struct ContinueText;
+
+/// Marker for the saved run's screenshot on the Continue button
+#[derive(Component)]
+pub struct ContinueThumbnail;
+
+/// Marker for the "Save game" button
+#[derive(Component)]
+pub struct SaveGameButton;
+
+/// Marker for how the last save or load went
+#[derive(Component)]
+pub struct SaveStatusText;
+
+fn spawn_status(parent: &mut ChildSpawnerCommands) {
+    parent.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 12.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.6, 0.6, 0.6)),
+        Node {
+            margin: UiRect::top(Val::Px(6.0)),
+            ..default()
+        },
+        SaveStatusText,
+    ));
+}
+
+/// Continue button for the selection screen
+pub fn spawn_continue_row(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            margin: UiRect::top(Val::Px(30.0)),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        padding: UiRect::axes(Val::Px(16.0), Val::Px(8.0)),
+                        border: UiRect::all(Val::Px(2.0)),
+                        align_items: AlignItems::Center,
+                        display: Display::None,
+                        ..default()
+                    },
+                    BorderColor::all(Color::srgb(0.4, 0.5, 0.7)),
+                    BackgroundColor(NORMAL_BUTTON),
+                    ContinueButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        ImageNode::default(),
+                        Node {
+                            width: Val::Px(96.0),
+                            height: Val::Px(54.0),
+                            margin: UiRect::right(Val::Px(12.0)),
+                            display: Display::None,
+                            ..default()
+                        },
+                        ContinueThumbnail,
+                    ));
+                    parent.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: 16.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        ContinueText,
+                    ));
+                });
+            spawn_status(parent);
+        });
+}
+
+/// Save section for the stats overlay
+pub fn spawn_save_section(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                margin: UiRect::top(Val::Px(14.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                align_self: AlignSelf::FlexStart,
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.4, 0.5, 0.7)),
+            BackgroundColor(NORMAL_BUTTON),
+            SaveGameButton,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Save game"),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+    spawn_status(parent);
+}
+
+/// Pick the saved run back up
+pub fn handle_continue_button(
+    mut interaction_query: ButtonInteractionQuery<ContinueButton>,
+    mut saved: ResMut<SavedRun>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                saved.continue_requested = true;
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Offer the active profile's saved run, if it has one, with its screenshot
+pub fn update_continue_button(
+    profiles: Res<Profiles>,
+    mut images: ResMut<Assets<Image>>,
+    mut button_query: Query<&mut Node, (With<ContinueButton>, Without<ContinueThumbnail>)>,
+    mut text_query: Query<&mut Text, With<ContinueText>>,
+    mut thumbnail_query: Query<(&mut ImageNode, &mut Node), With<ContinueThumbnail>>,
+    added_query: Query<(), Added<ContinueButton>>,
+) {
+    if !profiles.is_changed() && added_query.is_empty() {
+        return;
+    }
+
+    let summary = peek(&save_path(&profiles));
+    for mut node in &mut button_query {
+        node.display = if summary.is_some() { Display::Flex } else { Display::None };
+    }
+    let Some(summary) = summary else {
+        return;
+    };
+    for mut text in &mut text_query {
+        **text = format!("Continue: {}", summary);
+    }
+
+    let thumbnail = load_thumbnail(&thumbnail_path(&profiles)).map(|image| images.add(image));
+    for (mut image, mut node) in &mut thumbnail_query {
+        node.display = if thumbnail.is_some() { Display::Flex } else { Display::None };
+        if let Some(handle) = &thumbnail {
+            image.image = handle.clone();
+        }
+    }
+}
+
+/// Save the run now
+pub fn handle_save_game_button(
+    mut interaction_query: ButtonInteractionQuery<SaveGameButton>,
+    mut saved: ResMut<SavedRun>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                saved.save_requested = true;
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Say how the last save or load went
+pub fn update_save_status(saved: Res<SavedRun>, mut query: Query<&mut Text, With<SaveStatusText>>) {
+    if !saved.is_changed() {
+        return;
+    }
+    let status = saved.status.clone().unwrap_or_default();
+    for mut text in &mut query {
+        if **text != status {
+            **text = status.clone();
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//! Savings section of the finances overlay - the balance, today's rate, and moving money
+
+use bevy::prelude::*;
+use crate::currency::money;
+use crate::economy::WorldState;
+use crate::savings::{SavingsMove, SavingsMoveEvent, SavingsState, WITHDRAWAL_DAYS};
+use super::{NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+/// Button that deposits or withdraws
+#[derive(Component)]
+pub struct SavingsButton(pub SavingsMove);
+
+/// Marker for the account summary text
+#[derive(Component)]
+pub struct SavingsText;
+
+/// Savings account section (goes at the top of the finances overlay)
+pub fn spawn_savings_section(parent: &mut ChildSpawnerCommands) {
+    parent.spawn((
+        Text::new("SAVINGS"),
+        TextFont {
+            font_size: 14.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.8, 0.9, 0.8)),
+        Node {
+            margin: UiRect::bottom(Val::Px(4.0)),
+            ..default()
+        },
+    ));
+    parent.spawn((
+        Text::new(format!(
+            "Earns the central bank rate, compounded daily. Withdrawals take {} days.",
+            WITHDRAWAL_DAYS
+        )),
+        TextFont {
+            font_size: 12.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.6, 0.6, 0.6)),
+        Node {
+            margin: UiRect::bottom(Val::Px(6.0)),
+            ..default()
+        },
+    ));
+    parent.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 13.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.8, 0.8, 0.8)),
+        Node {
+            margin: UiRect::bottom(Val::Px(6.0)),
+            ..default()
+        },
+        SavingsText,
+    ));
+
+    parent
+        .spawn(Node {
+            width: Val::Percent(100.0),
+            flex_wrap: FlexWrap::Wrap,
+            column_gap: Val::Px(6.0),
+            row_gap: Val::Px(4.0),
+            margin: UiRect::bottom(Val::Px(12.0)),
+            ..default()
+        })
+        .with_children(|parent| {
+            for savings_move in SavingsMove::ALL {
+                parent
+                    .spawn((
+                        Button,
+                        Node {
+                            padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                            border: UiRect::all(Val::Px(1.0)),
+                            ..default()
+                        },
+                        BorderColor::all(Color::srgb(0.4, 0.6, 0.4)),
+                        BackgroundColor(NORMAL_BUTTON),
+                        SavingsButton(savings_move),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Text::new(savings_move.label()),
+                            TextFont {
+                                font_size: 12.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                        ));
+                    });
+            }
+        });
+}
+
+/// Deposit or withdraw
+pub fn handle_savings_buttons(
+    mut interaction_query: Query<(&Interaction, &SavingsButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut move_events: MessageWriter<SavingsMoveEvent>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                move_events.write(SavingsMoveEvent { savings_move: button.0 });
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+/// Keep the balance, rate and pending withdrawals up to date
+pub fn update_savings_text(
+    savings: Res<SavingsState>,
+    world: Res<WorldState>,
+    mut text_query: Query<&mut Text, With<SavingsText>>,
+) {
+    if !savings.is_changed() && !world.is_changed() {
+        return;
+    }
+
+    let mut lines = vec![
+        format!("Balance: {}", money(savings.balance)),
+        format!("Interest rate: {:.2}% a year", world.interest_rate * 100.0),
+        format!("Interest earned: {}", money(savings.interest_earned)),
+    ];
+    for withdrawal in &savings.withdrawals {
+        lines.push(format!(
+            "On its way: {} (in {} day{})",
+            money(withdrawal.amount),
+            withdrawal.days_remaining,
+            if withdrawal.days_remaining == 1 { "" } else { "s" }
+        ));
+    }
+    for mut text in &mut text_query {
+        **text = lines.join("\n");
+    }
+}
@@ -0,0 +1,138 @@
+//! Scenery - a themed backdrop behind the main screen for each kind of Thing
+//!
+//! Each Thing has a scene in `assets/scenes/<dialogue prefix>/`: dollar-store
+//! shelves for Cheap, a workshop for Good, a boutique for Expensive and a back
+//! alley for Bad. A scene is up to three layers (`far.png`, `mid.png` and
+//! `near.png`) drawn as sprites behind the UI, drifting a little with the
+//! mouse (not in low power), nearer layers further. Missing or broken layers are skipped, and
+//! with none at all the panels just stay solid as before.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use std::path::Path;
+use crate::game_state::{GameState, InGame};
+use crate::power::PowerMode;
+use crate::thing_type::{ThingDefinition, ThingRegistry, ThingType};
+
+/// Scene layers, back to front, and how far each drifts with the mouse
+const LAYERS: [(&str, f32); 3] = [("far", 0.25), ("mid", 0.6), ("near", 1.0)];
+/// How far the nearest layer drifts at the edge of the window, in pixels
+const PARALLAX_PX: f32 = 14.0;
+
+/// One layer of the scene
+#[derive(Component)]
+#[require(DespawnOnExit<InGame> = DespawnOnExit(InGame))]
+pub struct SceneryLayer {
+    depth: f32,
+}
+
+/// Which Thing's scene is up, and whether any of it has loaded
+#[derive(Resource, Default)]
+pub struct Scenery {
+    pub thing_type: Option<ThingType>,
+    /// At least one layer is showing, so the panels let it through
+    pub shown: bool,
+}
+
+/// The layers of a Thing's scene that are on disk: where each goes (back to
+/// front), its asset path and how far it drifts. Checked up front so a Thing
+/// without art doesn't fill the log with load errors.
+pub fn scenery_layers(thing: &ThingDefinition) -> Vec<(usize, String, f32)> {
+    let folder = format!("scenes/{}", thing.dialogue_prefix);
+    LAYERS
+        .into_iter()
+        .enumerate()
+        .map(|(index, (name, depth))| (index, format!("{}/{}.png", folder, name), depth))
+        .filter(|(_, path, _)| Path::new("assets").join(path).exists())
+        .collect()
+}
+
+/// Put up the scene for the current Thing (again after a pivot)
+pub fn spawn_scenery(
+    mut commands: Commands,
+    game_state: Res<GameState>,
+    things: Res<ThingRegistry>,
+    asset_server: Res<AssetServer>,
+    mut scenery: ResMut<Scenery>,
+    layer_query: Query<Entity, With<SceneryLayer>>,
+) {
+    if scenery.thing_type == game_state.thing_type {
+        return;
+    }
+    for entity in &layer_query {
+        commands.entity(entity).despawn();
+    }
+    *scenery = Scenery {
+        thing_type: game_state.thing_type,
+        shown: false,
+    };
+    let Some(thing_type) = game_state.thing_type else {
+        return;
+    };
+
+    for (index, path, depth) in scenery_layers(&things[thing_type]) {
+        commands.spawn((
+            Sprite::from_image(asset_server.load(path)),
+            Transform::from_xyz(0.0, 0.0, -10.0 + index as f32),
+            SceneryLayer { depth },
+        ));
+    }
+}
+
+/// Drop layers that failed to load, and note when the first one is ready
+pub fn reveal_scenery(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    images: Res<Assets<Image>>,
+    mut scenery: ResMut<Scenery>,
+    layer_query: Query<(Entity, &Sprite), With<SceneryLayer>>,
+) {
+    let mut shown = false;
+    for (entity, sprite) in &layer_query {
+        if images.contains(&sprite.image) {
+            shown = true;
+        } else if asset_server.load_state(&sprite.image).is_failed() {
+            warn!("Couldn't load a scenery layer, leaving it out");
+            commands.entity(entity).despawn();
+        }
+    }
+    if scenery.shown != shown {
+        scenery.shown = shown;
+    }
+}
+
+/// Fill the window with the scene and drift the layers with the mouse
+pub fn parallax_scenery(
+    power: Res<PowerMode>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut layer_query: Query<(&SceneryLayer, &mut Transform, &mut Sprite)>,
+) {
+    let Ok(window) = window_query.single() else {
+        return;
+    };
+    let size = window.size();
+    // -1 to 1 across the window, y up like the world
+    let lean = window
+        .cursor_position()
+        .filter(|_| !power.low_power)
+        .map(|cursor| (cursor / size * 2.0 - Vec2::ONE) * Vec2::new(1.0, -1.0))
+        .unwrap_or(Vec2::ZERO);
+
+    for (layer, mut transform, mut sprite) in &mut layer_query {
+        // Oversized by the drift so the edges never show
+        let cover = size + Vec2::splat(PARALLAX_PX * 2.0);
+        if sprite.custom_size != Some(cover) {
+            sprite.custom_size = Some(cover);
+        }
+        let offset = -lean * PARALLAX_PX * layer.depth;
+        if transform.translation.truncate() != offset {
+            transform.translation.x = offset.x;
+            transform.translation.y = offset.y;
+        }
+    }
+}
+
+/// Forget the scene when leaving the main screen (its layers go with the state)
+pub fn reset_scenery(mut scenery: ResMut<Scenery>) {
+    *scenery = Scenery::default();
+}
@@ -1,12 +1,21 @@
 //! Thing type selection screen
 
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
+use crate::attract::AttractMode;
+use crate::difficulty::{Difficulty, DifficultyConfig};
+use crate::economy::WorldState;
+use crate::fortune::RunModifiers;
 use crate::game_state::{AppState, GameState};
-use crate::thing_type::ThingType;
-use super::{UiRoot, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+use crate::parallel::ParallelMode;
+use crate::settings::Settings;
+use crate::share_code::ShareCode;
+use crate::thing_type::{ThingRegistry, ThingType};
+use super::{spawn_text_field, ThingBuilder, ThingPick, ButtonInteractionQuery, TextField, UiRoot, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
 
-/// Marker for selection screen elements
+/// Marker for selection screen elements (despawned on leaving the screen)
 #[derive(Component)]
+#[require(DespawnOnExit<AppState> = DespawnOnExit(AppState::ThingSelection))]
 pub struct SelectionScreen;
 
 /// Marker for thing type buttons
@@ -17,6 +26,34 @@ pub struct ThingTypeButton(pub ThingType);
 #[derive(Component)]
 pub struct TerryDialogueText;
 
+/// Marker for the challenge code field
+#[derive(Component)]
+pub struct ShareCodeField;
+
+/// Marker for the "Accept challenge" button
+#[derive(Component)]
+pub struct AcceptChallengeButton;
+
+/// Marker for the line under the challenge code field (explains bad codes)
+#[derive(Component)]
+pub struct ShareCodeStatusText;
+
+/// Marker for the parallel universe on/off button
+#[derive(Component)]
+pub struct ParallelModeButton;
+
+/// Label on the parallel universe button
+#[derive(Component)]
+pub struct ParallelModeText;
+
+/// Marker for the difficulty button
+#[derive(Component)]
+pub struct DifficultyButton;
+
+/// Label on the difficulty button
+#[derive(Component)]
+pub struct DifficultyText;
+
 /// Tracks how long the player has been staring at the selection screen
 #[derive(Resource)]
 pub struct SelectionTimer {
@@ -44,8 +81,16 @@ impl Default for SelectionTimer {
     }
 }
 
-pub fn setup_selection_screen(mut commands: Commands) {
-    commands.insert_resource(SelectionTimer::default());
+pub fn setup_selection_screen(
+    mut commands: Commands,
+    things: Res<ThingRegistry>,
+    parallel: Res<ParallelMode>,
+    settings: Res<Settings>,
+) {
+    // Kept through attract-mode demos, so Terry's patience keeps running down
+    commands.init_resource::<SelectionTimer>();
+    commands.insert_resource(ThingBuilder::default());
+    commands.insert_resource(ThingPick::default());
 
     commands
         .spawn((
@@ -105,10 +150,22 @@ pub fn setup_selection_screen(mut commands: Commands) {
                     ..default()
                 })
                 .with_children(|parent| {
-                    for thing_type in [ThingType::Cheap, ThingType::Good, ThingType::Expensive, ThingType::Bad] {
-                        spawn_thing_button(parent, thing_type);
+                    for thing_type in things.all() {
+                        spawn_thing_button(parent, thing_type, &things[thing_type].name);
                     }
+                    super::thing_builder::spawn_other_thing_button(parent);
                 });
+
+            super::thing_picker::spawn_thing_picker(parent);
+            super::save_panel::spawn_continue_row(parent);
+
+            spawn_challenge_row(parent);
+            spawn_parallel_toggle(parent, &parallel);
+            spawn_difficulty_toggle(parent, settings.difficulty);
+
+            super::profile_panel::spawn_profile_panel(parent);
+            super::thing_builder::spawn_thing_builder(parent);
+            super::config_error_dialog::spawn_config_error_dialog(parent);
         });
 }
 
@@ -147,7 +204,136 @@ pub fn update_selection_timer(
     }
 }
 
-fn spawn_thing_button(parent: &mut ChildSpawnerCommands, thing_type: ThingType) {
+/// "Got a code?" field for starting from a friend's challenge code
+fn spawn_challenge_row(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(10.0),
+            margin: UiRect::top(Val::Px(50.0)),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Got a challenge code?"),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+            ));
+
+            spawn_text_field(parent, 130.0, 9, "ABC-DEFG", ShareCodeField);
+
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        padding: UiRect::axes(Val::Px(12.0), Val::Px(5.0)),
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+                    BackgroundColor(NORMAL_BUTTON),
+                    AcceptChallengeButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new("Accept challenge"),
+                        TextFont {
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+        });
+
+    parent.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 13.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.9, 0.5, 0.4)),
+        Node {
+            margin: UiRect::top(Val::Px(8.0)),
+            ..default()
+        },
+        ShareCodeStatusText,
+    ));
+}
+
+fn parallel_mode_label(parallel: &ParallelMode) -> &'static str {
+    if parallel.enabled {
+        "Parallel universe: ON (a bot runs another Thing on the same world)"
+    } else {
+        "Parallel universe: off"
+    }
+}
+
+/// Toggle for running a bot's Thing alongside yours
+fn spawn_parallel_toggle(parent: &mut ChildSpawnerCommands, parallel: &ParallelMode) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(12.0), Val::Px(5.0)),
+                margin: UiRect::top(Val::Px(20.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.5, 0.4, 0.7)),
+            BackgroundColor(NORMAL_BUTTON),
+            ParallelModeButton,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(parallel_mode_label(parallel)),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.6, 0.9)),
+                ParallelModeText,
+            ));
+        });
+}
+
+fn difficulty_label(difficulty: Difficulty) -> String {
+    format!("Difficulty: {} ({})", difficulty.name(), difficulty.description())
+}
+
+/// Button cycling through the difficulties for the next run
+fn spawn_difficulty_toggle(parent: &mut ChildSpawnerCommands, difficulty: Difficulty) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(12.0), Val::Px(5.0)),
+                margin: UiRect::top(Val::Px(10.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.7, 0.4, 0.4)),
+            BackgroundColor(NORMAL_BUTTON),
+            DifficultyButton,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(difficulty_label(difficulty)),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.6, 0.6)),
+                DifficultyText,
+            ));
+        });
+}
+
+fn spawn_thing_button(parent: &mut ChildSpawnerCommands, thing_type: ThingType, name: &str) {
     parent
         .spawn((
             Button,
@@ -166,7 +352,7 @@ fn spawn_thing_button(parent: &mut ChildSpawnerCommands, thing_type: ThingType)
         .with_children(|parent| {
             // Just the word - no description, no price
             parent.spawn((
-                Text::new(thing_type.name()),
+                Text::new(name),
                 TextFont {
                     font_size: 24.0,
                     ..default()
@@ -176,20 +362,19 @@ fn spawn_thing_button(parent: &mut ChildSpawnerCommands, thing_type: ThingType)
         });
 }
 
+/// Clicking a word puts it forward; the run starts once it's confirmed
 pub fn handle_selection_buttons(
     mut interaction_query: Query<
         (&Interaction, &ThingTypeButton, &mut BackgroundColor),
         (Changed<Interaction>, With<Button>),
     >,
-    mut game_state: ResMut<GameState>,
-    mut next_state: ResMut<NextState<AppState>>,
+    mut pick: ResMut<ThingPick>,
 ) {
     for (interaction, thing_button, mut bg_color) in &mut interaction_query {
         match *interaction {
             Interaction::Pressed => {
                 *bg_color = PRESSED_BUTTON.into();
-                game_state.thing_type = Some(thing_button.0);
-                next_state.set(AppState::Playing);
+                pick.pending = Some(thing_button.0);
             }
             Interaction::Hovered => {
                 *bg_color = HOVERED_BUTTON.into();
@@ -201,12 +386,111 @@ pub fn handle_selection_buttons(
     }
 }
 
-pub fn cleanup_selection_screen(
-    mut commands: Commands,
-    query: Query<Entity, With<SelectionScreen>>,
+/// Everything a challenge code sets up for the next run
+#[derive(SystemParam)]
+pub struct ChallengeSetup<'w> {
+    game_state: ResMut<'w, GameState>,
+    world: ResMut<'w, WorldState>,
+    modifiers: ResMut<'w, RunModifiers>,
+    settings: ResMut<'w, Settings>,
+    difficulty: ResMut<'w, DifficultyConfig>,
+}
+
+/// Start a run from a challenge code, or explain what's wrong with it
+pub fn handle_accept_challenge_button(
+    mut interaction_query: ButtonInteractionQuery<AcceptChallengeButton>,
+    field_query: Query<&TextField, With<ShareCodeField>>,
+    mut status_query: Query<&mut Text, With<ShareCodeStatusText>>,
+    mut setup: ChallengeSetup,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                let Ok(field) = field_query.single() else {
+                    continue;
+                };
+                match ShareCode::parse(&field.value) {
+                    Ok(code) => {
+                        setup.game_state.thing_type = Some(code.thing_type);
+                        setup.world.seed = code.seed;
+                        *setup.modifiers = RunModifiers::from_share_code(code.fortune);
+                        setup.settings.difficulty = code.difficulty;
+                        *setup.difficulty = DifficultyConfig::new(code.difficulty);
+                        setup.game_state.money = setup.difficulty.starting_money;
+                        next_state.set(AppState::Playing);
+                    }
+                    Err(err) => {
+                        for mut text in &mut status_query {
+                            **text = err.message();
+                        }
+                    }
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Switch the parallel universe on or off for the next run
+pub fn handle_parallel_mode_button(
+    mut interaction_query: ButtonInteractionQuery<ParallelModeButton>,
+    mut text_query: Query<&mut Text, With<ParallelModeText>>,
+    mut parallel: ResMut<ParallelMode>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                parallel.enabled = !parallel.enabled;
+                for mut text in &mut text_query {
+                    **text = parallel_mode_label(&parallel).to_string();
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Stop the selection timer on leaving the screen (a demo keeps it for next time)
+pub fn clear_selection_timer(mut commands: Commands, attract: Res<AttractMode>) {
+    if !attract.running {
+        commands.remove_resource::<SelectionTimer>();
+    }
+}
+
+/// Pick the next difficulty for the run about to start
+pub fn handle_difficulty_button(
+    mut interaction_query: ButtonInteractionQuery<DifficultyButton>,
+    mut text_query: Query<&mut Text, With<DifficultyText>>,
+    mut settings: ResMut<Settings>,
 ) {
-    commands.remove_resource::<SelectionTimer>();
-    for entity in &query {
-        commands.entity(entity).despawn();
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                settings.difficulty = settings.difficulty.next();
+                for mut text in &mut text_query {
+                    **text = difficulty_label(settings.difficulty);
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
+        }
     }
 }
@@ -0,0 +1,128 @@
+//! Seminars overlay - send Terry off to teach MBA courses
+
+use bevy::prelude::*;
+use crate::currency::money_whole;
+use crate::game_state::GameState;
+use crate::seminars::{Course, SeminarState, StartSeminarEvent};
+use crate::vacation::VacationState;
+use super::{spawn_overlay, OverlayKind, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON, DISABLED_BUTTON};
+
+/// Button that starts a course
+#[derive(Component)]
+pub struct SeminarButton(pub Course);
+
+/// Text showing a course's status
+#[derive(Component)]
+pub struct SeminarStatusText(pub Course);
+
+/// Seminars overlay with one entry per course
+pub fn spawn_seminar_overlay(parent: &mut ChildSpawnerCommands) {
+    spawn_overlay(parent, OverlayKind::Seminars, "TERRY'S MBA SEMINARS", 380.0, |parent| {
+        for course in Course::ALL {
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Percent(100.0),
+                        flex_direction: FlexDirection::Column,
+                        padding: UiRect::all(Val::Px(8.0)),
+                        margin: UiRect::bottom(Val::Px(8.0)),
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    BorderColor::all(Color::srgb(0.6, 0.5, 0.4)),
+                    BackgroundColor(NORMAL_BUTTON),
+                    SeminarButton(course),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(format!("{} - {}, {} days", course.name(), money_whole(course.cost()), course.duration_days())),
+                        TextFont {
+                            font_size: 15.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                    parent.spawn((
+                        Text::new(course.description()),
+                        TextFont {
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                    ));
+                    parent.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.9, 0.8, 0.5)),
+                        SeminarStatusText(course),
+                    ));
+                });
+        }
+    });
+}
+
+/// Start a course when one is picked
+pub fn handle_seminar_buttons(
+    mut interaction_query: Query<
+        (&Interaction, &SeminarButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    seminars: Res<SeminarState>,
+    game_state: Res<GameState>,
+    vacation: Res<VacationState>,
+    mut start_events: MessageWriter<StartSeminarEvent>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        // Nobody's teaching anything while Terry's away
+        let can_start = seminars.can_start(button.0, &game_state) && !vacation.is_away();
+
+        match *interaction {
+            Interaction::Pressed => {
+                if can_start {
+                    *bg_color = PRESSED_BUTTON.into();
+                    start_events.write(StartSeminarEvent { course: button.0 });
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = if can_start { HOVERED_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+            Interaction::None => {
+                *bg_color = if can_start { NORMAL_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+        }
+    }
+}
+
+/// Keep each course's status line current
+pub fn update_seminar_status(
+    seminars: Res<SeminarState>,
+    vacation: Res<VacationState>,
+    mut query: Query<(&mut Text, &SeminarStatusText)>,
+) {
+    if !seminars.is_changed() && !vacation.is_changed() {
+        return;
+    }
+
+    for (mut text, status_text) in &mut query {
+        let course = status_text.0;
+        **text = if seminars.is_taught(course) {
+            "Taught".to_string()
+        } else {
+            match seminars.active {
+                Some(active) if active.course == course => {
+                    format!("Teaching now: {} days to go", active.days_remaining)
+                }
+                Some(_) => "Terry's teaching another course".to_string(),
+                None if vacation.is_away() => "Terry's out of the office".to_string(),
+                None if seminars.cooldown_days > 0 => {
+                    format!("Terry needs {} more days to recover", seminars.cooldown_days)
+                }
+                None => "Not taught yet".to_string(),
+            }
+        };
+    }
+}
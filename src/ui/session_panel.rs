@@ -0,0 +1,128 @@
+//! Break reminders - a gentle hourly nudge with a few stats from the session
+
+use bevy::prelude::*;
+use crate::currency::money_whole;
+use crate::session::{format_duration, PlayReminderEvent};
+use super::{ButtonInteractionQuery, FocusTrap, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+/// Marker for the reminder popup
+#[derive(Component)]
+pub struct ReminderPanel;
+
+/// Marker for the reminder's text
+#[derive(Component)]
+pub struct ReminderText;
+
+/// Marker for the reminder's dismiss button
+#[derive(Component)]
+pub struct DismissReminderButton;
+
+/// Reminder popup in the bottom corner (hidden until an hour has gone by)
+pub fn spawn_reminder_panel(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(15.0),
+                right: Val::Px(15.0),
+                width: Val::Px(300.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(12.0)),
+                border: UiRect::all(Val::Px(2.0)),
+                display: Display::None,
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.5, 0.6, 0.8)),
+            BackgroundColor(Color::srgb(0.08, 0.09, 0.14)),
+            GlobalZIndex(65),
+            ReminderPanel,
+            FocusTrap,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.85, 0.85, 0.9)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(10.0)),
+                    ..default()
+                },
+                ReminderText,
+            ));
+
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                        border: UiRect::all(Val::Px(1.0)),
+                        align_self: AlignSelf::FlexEnd,
+                        ..default()
+                    },
+                    BorderColor::all(Color::srgb(0.4, 0.5, 0.7)),
+                    BackgroundColor(NORMAL_BUTTON),
+                    DismissReminderButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new("Thanks, Terry"),
+                        TextFont {
+                            font_size: 13.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+        });
+}
+
+/// Show the reminder with the session so far
+pub fn show_play_reminders(
+    mut reminder_events: MessageReader<PlayReminderEvent>,
+    mut panel_query: Query<&mut Node, With<ReminderPanel>>,
+    mut text_query: Query<&mut Text, With<ReminderText>>,
+) {
+    let Some(event) = reminder_events.read().last() else {
+        return;
+    };
+
+    for mut text in &mut text_query {
+        **text = format!(
+            "You've been playing for {}.\n\nThis session: {} Things made, {} earned, {} game days.\n\n\
+             Maybe stretch, grab some water, look at something far away. The Things will wait.",
+            format_duration(event.hours * 3_600),
+            event.stats.things_made,
+            money_whole(event.stats.money_earned),
+            event.stats.days_played
+        );
+    }
+    for mut node in &mut panel_query {
+        node.display = Display::Flex;
+    }
+}
+
+/// Close the reminder
+pub fn handle_dismiss_reminder_button(
+    mut interaction_query: ButtonInteractionQuery<DismissReminderButton>,
+    mut panel_query: Query<&mut Node, With<ReminderPanel>>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                for mut node in &mut panel_query {
+                    node.display = Display::None;
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
@@ -0,0 +1,208 @@
+//! Settings overlay on the main screen
+
+use bevy::prelude::*;
+use crate::input::{key_name, InputAction, InputMap, Rebinding};
+use crate::settings::{SettingOption, Settings};
+use super::{spawn_overlay, OverlayKind, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+/// Button that cycles a setting's value
+#[derive(Component)]
+pub struct SettingButton(pub SettingOption);
+
+/// Text showing a setting's current value
+#[derive(Component)]
+pub struct SettingValueText(pub SettingOption);
+
+/// Button that waits for a new key for an action
+#[derive(Component)]
+pub struct KeyBindButton(pub InputAction);
+
+/// Text showing the key bound to an action
+#[derive(Component)]
+pub struct KeyBindText(pub InputAction);
+
+/// Marker for the rebinding result / conflict message
+#[derive(Component)]
+pub struct RebindStatusText;
+
+/// Settings overlay (hidden until opened from the header)
+pub fn spawn_settings_overlay(parent: &mut ChildSpawnerCommands, settings: &Settings, input_map: &InputMap) {
+    spawn_overlay(parent, OverlayKind::Settings, "SETTINGS", 360.0, |parent| {
+        for option in SettingOption::ALL.into_iter().filter(SettingOption::available) {
+            spawn_setting_row(parent, option.label(), SettingButton(option), (
+                Text::new(settings.value_label(option)),
+                SettingValueText(option),
+            ));
+        }
+
+        parent.spawn((
+            Text::new("KEYS (click, then press a key)"),
+            TextFont {
+                font_size: 13.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.6, 0.6, 0.8)),
+            Node {
+                margin: UiRect::vertical(Val::Px(6.0)),
+                ..default()
+            },
+        ));
+
+        for action in InputAction::all() {
+            let key = input_map.key_for(action).map(key_name).unwrap_or_default();
+            spawn_setting_row(parent, &action.label(), KeyBindButton(action), (
+                Text::new(key),
+                KeyBindText(action),
+            ));
+        }
+
+        parent.spawn((
+            Text::new(""),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.9, 0.7, 0.4)),
+            RebindStatusText,
+        ));
+    });
+}
+
+/// A label on the left, a button on the right; `value` is the button's text and its marker
+fn spawn_setting_row(
+    parent: &mut ChildSpawnerCommands,
+    label: &str,
+    button_marker: impl Component,
+    value: (Text, impl Component),
+) {
+    parent
+        .spawn(Node {
+            width: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::SpaceBetween,
+            margin: UiRect::bottom(Val::Px(8.0)),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+            ));
+
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        min_width: Val::Px(140.0),
+                        padding: UiRect::axes(Val::Px(10.0), Val::Px(5.0)),
+                        justify_content: JustifyContent::Center,
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+                    BackgroundColor(NORMAL_BUTTON),
+                    button_marker,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        value,
+                        TextFont {
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+        });
+}
+
+/// Cycle a setting when its button is pressed
+pub fn handle_setting_buttons(
+    mut interaction_query: Query<
+        (&Interaction, &SettingButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    mut settings: ResMut<Settings>,
+) {
+    for (interaction, setting_button, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                settings.cycle(setting_button.0);
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Refresh setting value labels when settings change
+pub fn update_setting_values(
+    settings: Res<Settings>,
+    mut query: Query<(&mut Text, &SettingValueText)>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    for (mut text, value_text) in &mut query {
+        **text = settings.value_label(value_text.0);
+    }
+}
+
+/// Start (or cancel, on a second press) listening for a new key
+pub fn handle_key_bind_buttons(
+    mut interaction_query: Query<
+        (&Interaction, &KeyBindButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    mut rebinding: ResMut<Rebinding>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                rebinding.action = if rebinding.action == Some(button.0) { None } else { Some(button.0) };
+                rebinding.message = None;
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Refresh key labels and the rebinding message
+pub fn update_key_bindings(
+    input_map: Res<InputMap>,
+    rebinding: Res<Rebinding>,
+    mut key_query: Query<(&mut Text, &KeyBindText), Without<RebindStatusText>>,
+    mut status_query: Query<&mut Text, With<RebindStatusText>>,
+) {
+    if !input_map.is_changed() && !rebinding.is_changed() {
+        return;
+    }
+
+    for (mut text, key_text) in &mut key_query {
+        **text = if rebinding.action == Some(key_text.0) {
+            "Press a key...".to_string()
+        } else {
+            input_map.key_for(key_text.0).map(key_name).unwrap_or_default()
+        };
+    }
+
+    for mut text in &mut status_query {
+        **text = rebinding.message.clone().unwrap_or_default();
+    }
+}
@@ -0,0 +1,108 @@
+//! Chirper overlay - what the public is saying about your Thing
+
+use bevy::prelude::*;
+use crate::economy::WorldState;
+use crate::social::{Sentiment, SocialFeed};
+use super::{spawn_overlay, Era, OverlayKind};
+
+/// Container the feed's posts are rebuilt into
+#[derive(Component)]
+pub struct ChirperFeed;
+
+/// Chirper overlay
+pub fn spawn_social_overlay(parent: &mut ChildSpawnerCommands) {
+    spawn_overlay(parent, OverlayKind::Chirper, "CHIRPER", 360.0, |parent| {
+        parent.spawn((
+            Node {
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            ChirperFeed,
+        ));
+    });
+}
+
+fn sentiment_color(sentiment: Sentiment) -> Color {
+    match sentiment {
+        Sentiment::Positive => Color::srgb(0.6, 0.9, 0.6),
+        Sentiment::Neutral => Color::srgb(0.8, 0.8, 0.8),
+        Sentiment::Negative => Color::srgb(0.95, 0.55, 0.5),
+    }
+}
+
+/// Rebuild the feed when new posts arrive (or Chirper redesigns itself)
+pub fn update_social_feed(
+    mut commands: Commands,
+    feed: Res<SocialFeed>,
+    world: Res<WorldState>,
+    mut shown_era: Local<Option<Era>>,
+    feed_query: Query<Entity, With<ChirperFeed>>,
+) {
+    let era = Era::on(world.date);
+    if !feed.is_changed() && *shown_era == Some(era) {
+        return;
+    }
+    *shown_era = Some(era);
+
+    for list in &feed_query {
+        commands.entity(list).despawn_children().with_children(|parent| {
+            if feed.posts.is_empty() {
+                parent.spawn((
+                    Text::new("Nobody's talking about you yet."),
+                    TextFont {
+                        font_size: 12.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.5, 0.5, 0.5)),
+                ));
+            }
+
+            for post in &feed.posts {
+                let style = era.post_style(sentiment_color(post.sentiment));
+                parent
+                    .spawn((
+                        Node {
+                            width: Val::Percent(100.0),
+                            flex_direction: FlexDirection::Column,
+                            padding: UiRect::axes(Val::Px(8.0), Val::Px(5.0)),
+                            margin: UiRect::bottom(Val::Px(4.0)),
+                            border: style.border,
+                            border_radius: style.border_radius,
+                            ..default()
+                        },
+                        BorderColor::all(style.border_color),
+                        BackgroundColor(style.background),
+                    ))
+                    .with_children(|parent| {
+                        // Only the player knows who's really behind the sock puppets
+                        let byline = if post.astroturfed {
+                            format!("{} - {} [PAID BY YOU]", post.author, post.date.format())
+                        } else {
+                            format!("{} - {}", post.author, post.date.format())
+                        };
+                        parent.spawn((
+                            Text::new(byline),
+                            TextFont {
+                                font_size: 11.0,
+                                ..default()
+                            },
+                            TextColor(if post.astroturfed {
+                                Color::srgb(0.9, 0.7, 0.3)
+                            } else {
+                                Color::srgb(0.55, 0.6, 0.7)
+                            }),
+                        ));
+                        parent.spawn((
+                            Text::new(post.text.clone()),
+                            TextFont {
+                                font_size: 13.0,
+                                ..default()
+                            },
+                            TextColor(sentiment_color(post.sentiment)),
+                        ));
+                    });
+            }
+        });
+    }
+}
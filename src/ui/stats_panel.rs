@@ -0,0 +1,174 @@
+//! Stats overlay - run summary, export of the daily history, and saving the run
+
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use crate::currency::money;
+use crate::difficulty::DifficultyConfig;
+use crate::eco::EcoState;
+use crate::economy::WorldState;
+use crate::fortune::RunModifiers;
+use crate::game_state::GameState;
+use crate::session::{format_duration, Playtime};
+use crate::profile::Profiles;
+use crate::settings::Settings;
+use crate::share_code::ShareCode;
+use crate::stats::{ExportFormat, RunStats};
+use super::{spawn_overlay, OverlayKind, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+/// Button that exports the run in a format
+#[derive(Component)]
+pub struct ExportButton(pub ExportFormat);
+
+/// Marker for the run summary text
+#[derive(Component)]
+pub struct RunSummaryText;
+
+/// Marker for the result of the last export
+#[derive(Component)]
+pub struct ExportStatusText;
+
+/// Stats overlay with the run summary, export buttons and the Save button
+pub fn spawn_stats_overlay(parent: &mut ChildSpawnerCommands) {
+    spawn_overlay(parent, OverlayKind::Stats, "RUN STATS", 380.0, |parent| {
+        parent.spawn((
+            Text::new(""),
+            TextFont {
+                font_size: 14.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            Node {
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+            RunSummaryText,
+        ));
+
+        parent
+            .spawn(Node {
+                column_gap: Val::Px(8.0),
+                margin: UiRect::bottom(Val::Px(8.0)),
+                ..default()
+            })
+            .with_children(|parent| {
+                for (format, label) in [(ExportFormat::Csv, "Export CSV"), (ExportFormat::Json, "Export JSON")] {
+                    parent
+                        .spawn((
+                            Button,
+                            Node {
+                                padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                                border: UiRect::all(Val::Px(1.0)),
+                                ..default()
+                            },
+                            BorderColor::all(Color::srgb(0.4, 0.6, 0.4)),
+                            BackgroundColor(NORMAL_BUTTON),
+                            ExportButton(format),
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Text::new(label),
+                                TextFont {
+                                    font_size: 14.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+                        });
+                }
+            });
+
+        parent.spawn((
+            Text::new("Exports go to the folder chosen in Settings."),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            ExportStatusText,
+        ));
+
+        super::save_panel::spawn_save_section(parent);
+    });
+}
+
+/// Write the run history to disk
+pub fn handle_export_buttons(
+    mut interaction_query: Query<
+        (&Interaction, &ExportButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    stats: Res<RunStats>,
+    settings: Res<Settings>,
+    profiles: Res<Profiles>,
+    mut status_query: Query<&mut Text, With<ExportStatusText>>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                let status = match stats.export(button.0, &settings.export_location.dir(&profiles.dir())) {
+                    Ok(path) => format!("Saved {}", path.display()),
+                    Err(err) => {
+                        warn!("Stats export failed: {}", err);
+                        format!("Export failed: {}", err)
+                    }
+                };
+                for mut text in &mut status_query {
+                    **text = status.clone();
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// How the run was set up
+#[derive(SystemParam)]
+pub struct RunSetup<'w> {
+    pub game_state: Res<'w, GameState>,
+    pub world: Res<'w, WorldState>,
+    pub modifiers: Res<'w, RunModifiers>,
+    pub difficulty: Res<'w, DifficultyConfig>,
+}
+
+/// Summarize the run so far
+pub fn update_run_summary(
+    stats: Res<RunStats>,
+    eco: Res<EcoState>,
+    playtime: Res<Playtime>,
+    setup: RunSetup,
+    mut query: Query<&mut Text, With<RunSummaryText>>,
+) {
+    if !stats.is_changed() && !playtime.is_changed() {
+        return;
+    }
+
+    let days = stats.snapshots.len();
+    let peak_money = stats.snapshots.iter().map(|s| s.money).fold(0.0, f64::max);
+    let best_reputation = stats.snapshots.iter().map(|s| s.reputation).fold(0.0, f32::max);
+    let code = ShareCode::for_run(&setup.game_state, &setup.world, &setup.modifiers, setup.difficulty.difficulty)
+        .map(|code| code.encode())
+        .unwrap_or_default();
+    let summary = format!(
+        "Difficulty: {}\nDays recorded: {}\nPeak money: {}\nBest reputation: {:.1} stars\nEnvironmental impact: {}\n\
+         Played this session: {}\nPlayed all time: {}\nChallenge code: {}\n\n{}",
+        setup.difficulty.difficulty.name(),
+        days,
+        money(peak_money),
+        best_reputation,
+        eco.rating().name(),
+        format_duration(playtime.session_secs),
+        format_duration(playtime.lifetime_secs()),
+        code,
+        eco.rating().epilogue()
+    );
+
+    for mut text in &mut query {
+        **text = summary.clone();
+    }
+}
@@ -0,0 +1,230 @@
+//! Picket line - shown in the factory view while the workers are out
+
+use bevy::prelude::*;
+use crate::business::UpgradeState;
+use crate::currency::money_whole;
+use crate::game_state::GameState;
+use crate::labor::{LaborState, ResolveStrikeEvent, StrikeResolution};
+use super::{NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON, DISABLED_BUTTON};
+
+/// What the picket signs say
+const PICKET_SIGNS: [&str; 4] = ["ON STRIKE", "FAIR PAY FOR\nFAIR THINGS", "WE MAKE\nTHE THINGS", "TERRY IS\nA WEENIE"];
+/// How far the signs bob up and down, in pixels
+const SIGN_BOB_HEIGHT: f32 = 6.0;
+/// Bobs per second
+const SIGN_BOB_SPEED: f32 = 1.5;
+
+/// Marker for the picket line container (hidden unless there's a strike)
+#[derive(Component)]
+pub struct PicketLine;
+
+/// A picket sign; the phase keeps them from bobbing in unison
+#[derive(Component)]
+pub struct PicketSign {
+    pub phase: f32,
+}
+
+/// Marker for the strike status text
+#[derive(Component)]
+pub struct StrikeStatusText;
+
+/// Button that settles the strike a particular way
+#[derive(Component)]
+pub struct StrikeResolutionButton(pub StrikeResolution);
+
+/// Label for a resolution button (shows the price)
+#[derive(Component)]
+pub struct StrikeResolutionText(pub StrikeResolution);
+
+/// Picket line under the Make Thing button
+pub fn spawn_picket_line(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                margin: UiRect::top(Val::Px(15.0)),
+                padding: UiRect::all(Val::Px(10.0)),
+                border: UiRect::all(Val::Px(2.0)),
+                display: Display::None,
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.8, 0.3, 0.2)),
+            BackgroundColor(Color::srgb(0.15, 0.06, 0.05)),
+            PicketLine,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(Node {
+                    column_gap: Val::Px(10.0),
+                    margin: UiRect::bottom(Val::Px(8.0)),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for (i, sign) in PICKET_SIGNS.iter().enumerate() {
+                        parent
+                            .spawn((
+                                Node {
+                                    padding: UiRect::axes(Val::Px(6.0), Val::Px(4.0)),
+                                    border: UiRect::all(Val::Px(1.0)),
+                                    ..default()
+                                },
+                                BorderColor::all(Color::srgb(0.5, 0.35, 0.2)),
+                                BackgroundColor(Color::srgb(0.9, 0.85, 0.7)),
+                                UiTransform::default(),
+                                PicketSign { phase: i as f32 * 1.3 },
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    Text::new(*sign),
+                                    TextFont {
+                                        font_size: 11.0,
+                                        ..default()
+                                    },
+                                    TextColor(Color::srgb(0.7, 0.1, 0.1)),
+                                    TextLayout::new_with_justify(Justify::Center),
+                                ));
+                            });
+                    }
+                });
+
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.6, 0.5)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(8.0)),
+                    ..default()
+                },
+                StrikeStatusText,
+            ));
+
+            parent
+                .spawn(Node {
+                    flex_wrap: FlexWrap::Wrap,
+                    justify_content: JustifyContent::Center,
+                    column_gap: Val::Px(6.0),
+                    row_gap: Val::Px(6.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for resolution in StrikeResolution::ALL {
+                        spawn_resolution_button(parent, resolution);
+                    }
+                });
+        });
+}
+
+fn spawn_resolution_button(parent: &mut ChildSpawnerCommands, resolution: StrikeResolution) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                padding: UiRect::axes(Val::Px(8.0), Val::Px(5.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.6, 0.4, 0.3)),
+            BackgroundColor(NORMAL_BUTTON),
+            StrikeResolutionButton(resolution),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(resolution.name()),
+                TextFont {
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                StrikeResolutionText(resolution),
+            ));
+            parent.spawn((
+                Text::new(resolution.description()),
+                TextFont {
+                    font_size: 10.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+            ));
+        });
+}
+
+/// Show the picket line during a strike and keep its text current
+pub fn update_picket_line(
+    labor: Res<LaborState>,
+    upgrades: Res<UpgradeState>,
+    mut line_query: Query<&mut Node, With<PicketLine>>,
+    mut status_query: Query<&mut Text, (With<StrikeStatusText>, Without<StrikeResolutionText>)>,
+    mut label_query: Query<(&mut Text, &StrikeResolutionText), Without<StrikeStatusText>>,
+) {
+    if !labor.is_changed() && !upgrades.is_changed() {
+        return;
+    }
+
+    for mut node in &mut line_query {
+        node.display = if labor.strike.is_some() { Display::Flex } else { Display::None };
+    }
+
+    let Some(strike) = labor.strike else {
+        return;
+    };
+
+    for mut text in &mut status_query {
+        **text = match strike.negotiating {
+            Some(days) => format!("Talks under way - back to work in {} day(s)", days),
+            None => format!("WORKERS ON STRIKE - day {} - production halted", strike.days + 1),
+        };
+    }
+
+    for (mut text, label) in &mut label_query {
+        **text = format!("{} ({})", label.0.name(), money_whole(label.0.cost(upgrades.workers)));
+    }
+}
+
+/// Bob the picket signs up and down
+pub fn animate_picket_signs(time: Res<Time>, mut query: Query<(&PicketSign, &mut UiTransform)>) {
+    let t = time.elapsed_secs() * SIGN_BOB_SPEED * std::f32::consts::TAU;
+    for (sign, mut transform) in &mut query {
+        let offset = (t + sign.phase).sin();
+        transform.translation = Val2::px(0.0, offset * SIGN_BOB_HEIGHT);
+        transform.rotation = Rot2::degrees(offset * 4.0);
+    }
+}
+
+/// Pick how to settle the strike
+pub fn handle_strike_resolution_buttons(
+    mut interaction_query: Query<
+        (&Interaction, &StrikeResolutionButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    labor: Res<LaborState>,
+    upgrades: Res<UpgradeState>,
+    game_state: Res<GameState>,
+    mut resolve_events: MessageWriter<ResolveStrikeEvent>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        let resolution = button.0;
+        let available = labor.can_resolve(resolution, upgrades.workers, &game_state);
+
+        match *interaction {
+            Interaction::Pressed => {
+                if available {
+                    *bg_color = PRESSED_BUTTON.into();
+                    resolve_events.write(ResolveStrikeEvent { resolution });
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = if available { HOVERED_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+            Interaction::None => {
+                *bg_color = if available { NORMAL_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+        }
+    }
+}
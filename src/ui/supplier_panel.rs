@@ -0,0 +1,197 @@
+//! Suppliers overlay - the relationship, the current terms, and this month's offer
+
+use bevy::prelude::*;
+use crate::suppliers::{NegotiateEvent, NegotiationMove, SupplierState, DISRUPTION_THRESHOLD};
+use super::{spawn_overlay, OverlayKind, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON, DISABLED_BUTTON};
+
+/// Button that answers the supplier's offer
+#[derive(Component)]
+pub struct NegotiateButton(pub NegotiationMove);
+
+/// Marker for the relationship and current terms text
+#[derive(Component)]
+pub struct SupplierSummaryText;
+
+/// Marker for the offer text
+#[derive(Component)]
+pub struct SupplierOfferText;
+
+/// Suppliers overlay
+pub fn spawn_supplier_overlay(parent: &mut ChildSpawnerCommands) {
+    spawn_overlay(parent, OverlayKind::Suppliers, "SUPPLIERS", 380.0, |parent| {
+        parent.spawn((
+            Text::new(
+                "Every Thing needs materials. New terms arrive on the first of the \
+                 month. Haggling works better with friends. Burn the bridge and \
+                 deliveries stop.",
+            ),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            Node {
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+        ));
+
+        parent.spawn((
+            Text::new(""),
+            TextFont {
+                font_size: 13.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.85, 0.85, 0.85)),
+            Node {
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+            SupplierSummaryText,
+        ));
+
+        parent.spawn((
+            Text::new(""),
+            TextFont {
+                font_size: 13.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.9, 0.8, 0.5)),
+            Node {
+                margin: UiRect::bottom(Val::Px(8.0)),
+                ..default()
+            },
+            SupplierOfferText,
+        ));
+
+        parent
+            .spawn(Node {
+                width: Val::Percent(100.0),
+                column_gap: Val::Px(6.0),
+                ..default()
+            })
+            .with_children(|parent| {
+                for action in NegotiationMove::ALL {
+                    parent
+                        .spawn((
+                            Button,
+                            Node {
+                                min_width: Val::Px(100.0),
+                                padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                                justify_content: JustifyContent::Center,
+                                border: UiRect::all(Val::Px(1.0)),
+                                ..default()
+                            },
+                            BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+                            BackgroundColor(DISABLED_BUTTON),
+                            NegotiateButton(action),
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Text::new(action.name()),
+                                TextFont {
+                                    font_size: 12.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+                        });
+                }
+            });
+    });
+}
+
+/// Can this move be made right now?
+fn can_negotiate(supplier: &SupplierState, action: NegotiationMove) -> bool {
+    supplier.offer.is_some() && !(action == NegotiationMove::Haggle && supplier.haggled)
+}
+
+/// Answer the offer
+pub fn handle_negotiate_buttons(
+    mut interaction_query: Query<(&Interaction, &NegotiateButton, &mut BackgroundColor), Changed<Interaction>>,
+    supplier: Res<SupplierState>,
+    mut negotiate_events: MessageWriter<NegotiateEvent>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        let available = can_negotiate(&supplier, button.0);
+        match *interaction {
+            Interaction::Pressed => {
+                if available {
+                    *bg_color = PRESSED_BUTTON.into();
+                    negotiate_events.write(NegotiateEvent { action: button.0 });
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = if available { HOVERED_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+            Interaction::None => {
+                *bg_color = if available { NORMAL_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+        }
+    }
+}
+
+/// Keep the relationship, terms and offer current
+pub fn update_supplier_panel(
+    supplier: Res<SupplierState>,
+    mut summary_query: Query<&mut Text, (With<SupplierSummaryText>, Without<SupplierOfferText>)>,
+    mut offer_query: Query<&mut Text, (With<SupplierOfferText>, Without<SupplierSummaryText>)>,
+    mut button_query: Query<(&NegotiateButton, &Interaction, &mut BackgroundColor)>,
+) {
+    if !supplier.is_changed() {
+        return;
+    }
+
+    let mood = match supplier.relationship {
+        r if r < DISRUPTION_THRESHOLD => "burned",
+        r if r < 35.0 => "frosty",
+        r if r < 65.0 => "cordial",
+        r if r < 85.0 => "warm",
+        _ => "practically family",
+    };
+    let mut lines = vec![format!("Relationship: {:.0}/100 ({})", supplier.relationship, mood)];
+    if supplier.disruption_days > 0 {
+        lines.push(format!("NO DELIVERIES for {} more days", supplier.disruption_days));
+    }
+    match supplier.terms {
+        Some(terms) => {
+            lines.push(format!("Terms: {:.0}% of list price", terms.price * 100.0));
+            if terms.commitment > 0 {
+                lines.push(format!(
+                    "Committed to {} Things this month ({} so far)",
+                    terms.commitment, supplier.things_this_month
+                ));
+            }
+        }
+        None => lines.push(format!("No deal: buying at {:.0}% on the spot market", supplier.price() * 100.0)),
+    }
+    for mut text in &mut summary_query {
+        **text = lines.join("\n");
+    }
+
+    let offer = match supplier.offer {
+        Some(offer) => {
+            let commitment = if offer.commitment > 0 {
+                format!(" for at least {} Things", offer.commitment)
+            } else {
+                String::new()
+            };
+            let haggle = if supplier.haggled {
+                "Already haggled this month.".to_string()
+            } else {
+                format!("Haggling has a {:.0}% chance of working.", supplier.haggle_chance() * 100.0)
+            };
+            format!("Offer: {:.0}% of list price{}\n{}", offer.price * 100.0, commitment, haggle)
+        }
+        None => "No offer on the table.".to_string(),
+    };
+    for mut text in &mut offer_query {
+        **text = offer.clone();
+    }
+
+    for (button, interaction, mut bg_color) in &mut button_query {
+        if *interaction == Interaction::None {
+            *bg_color = if can_negotiate(&supplier, button.0) { NORMAL_BUTTON } else { DISABLED_BUTTON }.into();
+        }
+    }
+}
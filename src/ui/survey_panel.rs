@@ -0,0 +1,171 @@
+//! Surveys overlay - commission market research and read the reports
+
+use bevy::prelude::*;
+use crate::currency::money_whole;
+use crate::game_state::GameState;
+use crate::surveys::{CommissionSurveyEvent, SampleSize, SurveyState, SurveyTopic};
+use super::{spawn_overlay, OverlayKind, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON, DISABLED_BUTTON};
+
+/// Button that commissions a survey on a topic with a sample size
+#[derive(Component)]
+pub struct SurveyButton(pub SurveyTopic, pub SampleSize);
+
+/// Text showing a topic's fieldwork or latest report
+#[derive(Component)]
+pub struct SurveyReportText(pub SurveyTopic);
+
+/// Surveys overlay with one entry per topic
+pub fn spawn_survey_overlay(parent: &mut ChildSpawnerCommands) {
+    spawn_overlay(parent, OverlayKind::Surveys, "MARKET RESEARCH", 420.0, |parent| {
+        parent.spawn((
+            Text::new(
+                "Pay an agency to ask around. Bigger samples cost more and come back \
+                 with tighter estimates - but they're still estimates.",
+            ),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            Node {
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+        ));
+
+        for topic in SurveyTopic::ALL {
+            spawn_topic_entry(parent, topic);
+        }
+    });
+}
+
+fn spawn_topic_entry(parent: &mut ChildSpawnerCommands, topic: SurveyTopic) {
+    parent
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                margin: UiRect::bottom(Val::Px(8.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.4, 0.5, 0.6)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(topic.name()),
+                TextFont {
+                    font_size: 15.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+            parent.spawn((
+                Text::new(topic.description()),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+            ));
+
+            parent
+                .spawn(Node {
+                    column_gap: Val::Px(6.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for sample in SampleSize::ALL {
+                        parent
+                            .spawn((
+                                Button,
+                                Node {
+                                    padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                                    border: UiRect::all(Val::Px(1.0)),
+                                    ..default()
+                                },
+                                BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+                                BackgroundColor(NORMAL_BUTTON),
+                                SurveyButton(topic, sample),
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    Text::new(format!(
+                                        "{} ({}) {}",
+                                        sample.name(),
+                                        sample.respondents(),
+                                        money_whole(sample.cost())
+                                    )),
+                                    TextFont {
+                                        font_size: 12.0,
+                                        ..default()
+                                    },
+                                    TextColor(Color::WHITE),
+                                ));
+                            });
+                    }
+                });
+
+            parent.spawn((
+                Text::new("No survey yet"),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.8, 0.5)),
+                SurveyReportText(topic),
+            ));
+        });
+}
+
+/// Commission a survey when a sample size is picked
+pub fn handle_survey_buttons(
+    mut interaction_query: Query<(&Interaction, &SurveyButton, &mut BackgroundColor), Changed<Interaction>>,
+    surveys: Res<SurveyState>,
+    game_state: Res<GameState>,
+    mut commission_events: MessageWriter<CommissionSurveyEvent>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        let can_commission = surveys.can_commission(button.0, button.1, &game_state);
+
+        match *interaction {
+            Interaction::Pressed => {
+                if can_commission {
+                    *bg_color = PRESSED_BUTTON.into();
+                    commission_events.write(CommissionSurveyEvent {
+                        topic: button.0,
+                        sample: button.1,
+                    });
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = if can_commission { HOVERED_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+            Interaction::None => {
+                *bg_color = if can_commission { NORMAL_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+        }
+    }
+}
+
+/// Keep each topic's fieldwork status or latest report current
+pub fn update_survey_reports(surveys: Res<SurveyState>, mut query: Query<(&mut Text, &SurveyReportText)>) {
+    if !surveys.is_changed() {
+        return;
+    }
+
+    for (mut text, report_text) in &mut query {
+        let topic = report_text.0;
+        let latest = surveys.latest(topic).map(|report| report.summary());
+        **text = match (surveys.in_field(topic), latest) {
+            (Some(pending), Some(latest)) => {
+                format!("In the field: {} days to go\nLast time - {}", pending.days_remaining, latest)
+            }
+            (Some(pending), None) => format!("In the field: {} days to go", pending.days_remaining),
+            (None, Some(latest)) => latest,
+            (None, None) => "No survey yet".to_string(),
+        };
+    }
+}
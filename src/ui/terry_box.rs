@@ -1,22 +1,157 @@
 //! Terry's dialogue box UI component
 
 use bevy::prelude::*;
+use crate::power::PowerMode;
+use crate::settings::{DialogueLayout, Settings};
 use crate::terry::TerryState;
+use super::{ButtonInteractionQuery, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
 
 /// Marker for Terry's dialogue text
 #[derive(Component)]
 pub struct TerryDialogueText;
 
+/// Marker for the in-panel dialogue box (label + box), used by the Panel layout
+#[derive(Component)]
+pub struct TerryDialoguePanel;
+
+/// Marker for the floating speech bubble, used by the Bubble layout
+#[derive(Component)]
+pub struct TerryBubble;
+
+/// Bubble background (shared by the tail so they read as one shape)
+const BUBBLE_COLOR: Color = Color::srgb(0.95, 0.92, 0.85);
+
+/// Marker for the button that skips Terry's current line
+#[derive(Component)]
+pub struct TerrySkipButton;
+
+/// Marker for the skip button's label (shows how many lines are waiting)
+#[derive(Component)]
+pub struct TerrySkipText;
+
 /// Update Terry's dialogue display
 pub fn update_terry_dialogue(
     terry_state: Res<TerryState>,
-    mut query: Query<&mut Text, With<TerryDialogueText>>,
+    power: Res<PowerMode>,
+    mut query: Query<&mut Text, (With<TerryDialogueText>, Without<TerrySkipText>)>,
+    mut skip_query: Query<&mut Text, (With<TerrySkipText>, Without<TerryDialogueText>)>,
 ) {
+    // No typewriter in low power, the whole line goes up at once
+    let typed = if power.low_power {
+        terry_state.current_line.as_ref().map(|line| line.text.as_str())
+    } else {
+        terry_state.typed_text()
+    };
+    let dialogue = match typed {
+        Some(typed) => format!("\"{}\"", typed),
+        None => String::from("\"...\""),
+    };
+    let skip = match terry_state.queue.len() {
+        0 => String::from("Skip >"),
+        waiting => format!("Skip > ({} more)", waiting),
+    };
+
     for mut text in &mut query {
-        if let Some(ref line) = terry_state.current_line {
-            **text = format!("\"{}\"", line.text);
-        } else {
-            **text = String::from("\"...\"");
+        if **text != dialogue {
+            **text = dialogue.clone();
+        }
+    }
+    for mut text in &mut skip_query {
+        if **text != skip {
+            **text = skip.clone();
+        }
+    }
+}
+
+/// Spawn the speech bubble next to Terry's portrait (hidden until the Bubble layout is chosen)
+pub fn spawn_terry_bubble(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                // Just right of the portrait, spilling over the center panel
+                left: Val::Percent(92.0),
+                top: Val::Px(10.0),
+                max_width: Val::Px(360.0),
+                min_width: Val::Px(160.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                border_radius: BorderRadius::all(Val::Px(12.0)),
+                display: Display::None,
+                ..default()
+            },
+            BackgroundColor(BUBBLE_COLOR),
+            GlobalZIndex(40),
+            TerryBubble,
+        ))
+        .with_children(|parent| {
+            // Tail pointing back at Terry
+            parent.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(-7.0),
+                    top: Val::Px(18.0),
+                    width: Val::Px(14.0),
+                    height: Val::Px(14.0),
+                    ..default()
+                },
+                UiTransform::from_rotation(Rot2::degrees(45.0)),
+                BackgroundColor(BUBBLE_COLOR),
+            ));
+
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.15, 0.1, 0.05)),
+                TerryDialogueText,
+            ));
+        });
+}
+
+/// Show the dialogue in the panel or the bubble depending on settings
+pub fn apply_dialogue_layout(
+    settings: Res<Settings>,
+    terry_state: Res<TerryState>,
+    mut panel_query: Query<&mut Node, (With<TerryDialoguePanel>, Without<TerryBubble>)>,
+    mut bubble_query: Query<&mut Node, (With<TerryBubble>, Without<TerryDialoguePanel>)>,
+) {
+    let use_bubble = settings.dialogue_layout == DialogueLayout::Bubble;
+
+    for mut node in &mut panel_query {
+        let display = if use_bubble { Display::None } else { Display::Flex };
+        if node.display != display {
+            node.display = display;
+        }
+    }
+
+    // The bubble only pops up while Terry is actually talking
+    for mut node in &mut bubble_query {
+        let display = if use_bubble && !terry_state.is_idle() { Display::Flex } else { Display::None };
+        if node.display != display {
+            node.display = display;
+        }
+    }
+}
+
+/// Skip Terry's current line when the skip button is pressed
+pub fn handle_terry_skip_button(
+    mut interaction_query: ButtonInteractionQuery<TerrySkipButton>,
+    mut terry_state: ResMut<TerryState>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                terry_state.skip();
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
         }
     }
 }
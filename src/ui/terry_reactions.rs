@@ -0,0 +1,256 @@
+//! Terry's reactions on the portrait - flinching, sweating and showing off
+//!
+//! Each reaction has a cue, something that happened or a state the business
+//! is in, and a look: an overlay on the portrait, a recoil, or mustard
+//! droplets from the floating text pool. The table below is the whole
+//! mapping. With reduced motion or in low power nothing moves; the overlays
+//! stand in (a still sweat drop instead of the droplets). The temp filling in
+//! while Terry's on vacation doesn't react.
+
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use crate::game_state::{GameState, ReputationChangedEvent};
+use crate::power::PowerMode;
+use crate::reputation::{ReputationTierChangedEvent, SCANDAL_REPUTATION};
+use crate::settings::Settings;
+use crate::vacation::VacationState;
+use super::{AdvisorPortraitText, FloatingText, MainScreen, UiPool};
+
+/// Seconds a flinch lasts
+const FLINCH_SECS: f32 = 0.6;
+/// How far Terry recoils, in pixels
+const FLINCH_PX: f32 = 8.0;
+/// Real seconds between mustard droplets
+const SWEAT_INTERVAL: f32 = 0.7;
+const MUSTARD: Color = Color::srgb(0.95, 0.8, 0.15);
+
+/// Something Terry does on the portrait
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerryReaction {
+    Flinch,
+    Sweat,
+    Sunglasses,
+}
+
+/// What sets a reaction off
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReactionCue {
+    /// Reputation falls this far in one go, or drops a tier (a scandal)
+    ReputationFall(f32),
+    /// For as long as reputation is under this
+    ReputationBelow(f32),
+    /// For as long as there's at least this much money
+    MoneyAbove(f64),
+}
+
+/// Every reaction and its cue
+pub const TERRY_REACTIONS: [(TerryReaction, ReactionCue); 3] = [
+    (TerryReaction::Flinch, ReactionCue::ReputationFall(0.1)),
+    (TerryReaction::Sweat, ReactionCue::ReputationBelow(SCANDAL_REPUTATION + 0.5)),
+    (TerryReaction::Sunglasses, ReactionCue::MoneyAbove(1_000_000.0)),
+];
+
+impl TerryReaction {
+    /// Overlay on the portrait while the reaction shows, and where it goes (top, left, font size)
+    fn overlay(&self) -> (&'static str, Val, Val, f32) {
+        match self {
+            TerryReaction::Flinch => ("‼", Val::Px(30.0), Val::Percent(64.0), 24.0),
+            TerryReaction::Sweat => ("💧", Val::Px(40.0), Val::Percent(36.0), 16.0),
+            TerryReaction::Sunglasses => ("🕶", Val::Px(86.0), Val::Percent(44.0), 22.0),
+        }
+    }
+
+    fn cue(&self) -> ReactionCue {
+        TERRY_REACTIONS
+            .iter()
+            .find(|(reaction, _)| reaction == self)
+            .map(|(_, cue)| *cue)
+            .expect("every reaction has a cue")
+    }
+
+    /// Whether a state cue holds right now (event cues never do)
+    fn holds(&self, game_state: &GameState) -> bool {
+        match self.cue() {
+            ReactionCue::ReputationFall(_) => false,
+            ReactionCue::ReputationBelow(stars) => game_state.reputation < stars,
+            ReactionCue::MoneyAbove(dollars) => game_state.money.dollars() >= dollars,
+        }
+    }
+}
+
+/// An overlay drawn over Terry's portrait for a reaction
+#[derive(Component)]
+pub struct TerryReactionLayer(pub TerryReaction);
+
+/// Terry recoiling (on the portrait text)
+#[derive(Component)]
+pub struct TerryFlinch {
+    pub elapsed: f32,
+}
+
+/// Reaction overlays, stacked over the portrait (hidden until they're cued)
+pub fn spawn_reaction_layers(parent: &mut ChildSpawnerCommands) {
+    for (reaction, _) in TERRY_REACTIONS {
+        let (glyph, top, left, font_size) = reaction.overlay();
+        parent.spawn((
+            Text::new(glyph),
+            TextFont {
+                font_size,
+                ..default()
+            },
+            TextColor(if reaction == TerryReaction::Sweat { MUSTARD } else { Color::WHITE }),
+            Node {
+                position_type: PositionType::Absolute,
+                top,
+                left,
+                ..default()
+            },
+            Visibility::Hidden,
+            TerryReactionLayer(reaction),
+        ));
+    }
+}
+
+/// What decides whether reactions can move
+#[derive(SystemParam)]
+pub struct ReactionMotion<'w> {
+    settings: Res<'w, Settings>,
+    power: Res<'w, PowerMode>,
+}
+
+impl ReactionMotion<'_> {
+    /// Nothing should move: only the overlays show
+    fn still(&self) -> bool {
+        self.settings.reduced_motion || self.power.low_power
+    }
+}
+
+/// Flinch when reputation takes a hit
+pub fn trigger_terry_flinch(
+    mut commands: Commands,
+    mut rep_events: MessageReader<ReputationChangedEvent>,
+    mut tier_events: MessageReader<ReputationTierChangedEvent>,
+    vacation: Res<VacationState>,
+    portrait_query: Query<Entity, With<AdvisorPortraitText>>,
+    mut last_reputation: Local<Option<f32>>,
+) {
+    let ReactionCue::ReputationFall(fall) = TerryReaction::Flinch.cue() else {
+        return;
+    };
+    let mut flinch = tier_events.read().any(|event| event.new_tier < event.old_tier);
+    for event in rep_events.read() {
+        if last_reputation.is_some_and(|last| last - event.new_reputation >= fall) {
+            flinch = true;
+        }
+        *last_reputation = Some(event.new_reputation);
+    }
+
+    if flinch && !vacation.is_away() {
+        for entity in &portrait_query {
+            commands.entity(entity).insert(TerryFlinch { elapsed: 0.0 });
+        }
+    }
+}
+
+/// Recoil and shake back into place (or just hold the overlay, when nothing should move)
+pub fn animate_terry_flinch(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    motion: ReactionMotion,
+    mut query: Query<(Entity, &mut TerryFlinch, &mut UiTransform)>,
+) {
+    let still = motion.still();
+    for (entity, mut flinch, mut transform) in &mut query {
+        flinch.elapsed += time.delta_secs();
+        let t = (flinch.elapsed / FLINCH_SECS).min(1.0);
+
+        if !still {
+            // Jerk back, then a few shivers that die away
+            let shiver = (t * std::f32::consts::TAU * 4.0).sin() * (1.0 - t);
+            transform.translation = Val2::px(FLINCH_PX * shiver, -FLINCH_PX * 0.5 * (1.0 - t));
+            transform.scale = Vec2::splat(1.0 - 0.08 * (1.0 - t));
+        }
+
+        if t >= 1.0 {
+            *transform = UiTransform::default();
+            commands.entity(entity).remove::<TerryFlinch>();
+        }
+    }
+}
+
+/// Show the overlays whose cues hold
+pub fn update_terry_reaction_layers(
+    game_state: Res<GameState>,
+    vacation: Res<VacationState>,
+    motion: ReactionMotion,
+    flinch_query: Query<(), With<TerryFlinch>>,
+    mut layer_query: Query<(&mut Visibility, &TerryReactionLayer)>,
+) {
+    let still = motion.still();
+    for (mut visibility, layer) in &mut layer_query {
+        let shown = !vacation.is_away()
+            && match layer.0 {
+                TerryReaction::Flinch => !flinch_query.is_empty(),
+                // The droplets do the sweating, unless nothing should move
+                TerryReaction::Sweat => still && layer.0.holds(&game_state),
+                TerryReaction::Sunglasses => layer.0.holds(&game_state),
+            };
+        let target = if shown { Visibility::Inherited } else { Visibility::Hidden };
+        if *visibility != target {
+            *visibility = target;
+        }
+    }
+}
+
+/// Drip mustard off Terry's brow while reputation is low (only while Terry's in the office)
+pub fn spawn_sweat_droplets(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    game_state: Res<GameState>,
+    motion: ReactionMotion,
+    portrait_query: Query<(&UiGlobalTransform, &ComputedNode), With<AdvisorPortraitText>>,
+    mut pool: ResMut<UiPool<FloatingText>>,
+    mut since_drop: Local<f32>,
+) {
+    if motion.still() || !TerryReaction::Sweat.holds(&game_state) {
+        return;
+    }
+    *since_drop += time.delta_secs();
+    if *since_drop < SWEAT_INTERVAL {
+        return;
+    }
+    *since_drop = 0.0;
+
+    let Ok((transform, computed)) = portrait_query.single() else {
+        return;
+    };
+    let Some(entity) = pool.acquire(&mut commands) else {
+        return;
+    };
+    let center = transform.translation * computed.inverse_scale_factor();
+    let from = Vec2::new(center.x + (rand::random::<f32>() - 0.5) * 40.0, center.y - 30.0);
+    commands.entity(entity).insert((
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(from.x),
+            top: Val::Px(from.y),
+            ..default()
+        },
+        Text::new("●"),
+        TextFont {
+            font_size: 9.0,
+            ..default()
+        },
+        TextColor(MUSTARD),
+        GlobalZIndex(100),
+        FloatingText {
+            elapsed: 0.0,
+            duration: 0.9,
+            from,
+            // Falls rather than floats
+            rise: -36.0,
+            color: MUSTARD,
+        },
+        MainScreen,
+    ));
+}
@@ -0,0 +1,128 @@
+//! Single-line text fields - click to focus, type, Backspace to delete
+
+use bevy::prelude::*;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+
+/// An editable line of text
+#[derive(Component, Default)]
+pub struct TextField {
+    pub value: String,
+    pub max_len: usize,
+    pub placeholder: &'static str,
+    pub focused: bool,
+}
+
+/// Marker for a text field's displayed text
+#[derive(Component)]
+pub struct TextFieldText;
+
+/// Text field with a placeholder; `marker` tells callers' fields apart
+pub fn spawn_text_field(
+    parent: &mut ChildSpawnerCommands,
+    width: f32,
+    max_len: usize,
+    placeholder: &'static str,
+    marker: impl Bundle,
+) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(width),
+                padding: UiRect::axes(Val::Px(8.0), Val::Px(5.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+            BackgroundColor(Color::srgb(0.08, 0.08, 0.1)),
+            TextField {
+                max_len,
+                placeholder,
+                ..default()
+            },
+            marker,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(placeholder),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.45, 0.45, 0.45)),
+                TextFieldText,
+            ));
+        });
+}
+
+/// Clicking a field focuses it (and unfocuses the rest)
+pub fn focus_text_fields(mut field_query: Query<(Entity, Ref<Interaction>, &mut TextField, &mut BorderColor)>) {
+    let Some(clicked) = field_query
+        .iter()
+        .find(|(_, interaction, _, _)| interaction.is_changed() && **interaction == Interaction::Pressed)
+        .map(|(entity, _, _, _)| entity)
+    else {
+        return;
+    };
+
+    for (entity, _, mut field, mut border) in &mut field_query {
+        field.focused = entity == clicked;
+        *border = BorderColor::all(if field.focused {
+            Color::srgb(0.8, 0.6, 0.3)
+        } else {
+            Color::srgb(0.4, 0.4, 0.4)
+        });
+    }
+}
+
+/// Typing goes into the focused field
+pub fn type_into_text_fields(mut key_events: MessageReader<KeyboardInput>, mut field_query: Query<&mut TextField>) {
+    let keys: Vec<&KeyboardInput> = key_events.read().filter(|event| event.state == ButtonState::Pressed).collect();
+    if keys.is_empty() {
+        return;
+    }
+
+    for mut field in &mut field_query {
+        if !field.focused {
+            continue;
+        }
+        for event in &keys {
+            match &event.logical_key {
+                Key::Backspace => {
+                    field.value.pop();
+                }
+                Key::Space if field.value.chars().count() < field.max_len => field.value.push(' '),
+                Key::Character(chars) => {
+                    for c in chars.chars().filter(|c| !c.is_control()) {
+                        if field.value.chars().count() < field.max_len {
+                            field.value.push(c);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Show each field's value (with a cursor while focused), or its placeholder
+pub fn update_text_fields(
+    field_query: Query<(&TextField, &Children), Changed<TextField>>,
+    mut text_query: Query<(&mut Text, &mut TextColor), With<TextFieldText>>,
+) {
+    for (field, children) in &field_query {
+        for child in children.iter() {
+            let Ok((mut text, mut color)) = text_query.get_mut(child) else {
+                continue;
+            };
+            if field.value.is_empty() && !field.focused {
+                **text = field.placeholder.to_string();
+                *color = TextColor(Color::srgb(0.45, 0.45, 0.45));
+            } else {
+                **text = if field.focused { format!("{}|", field.value) } else { field.value.clone() };
+                *color = TextColor(Color::WHITE);
+            }
+        }
+    }
+}
@@ -0,0 +1,398 @@
+//! "Other..." on the selection screen - name your own Thing and set its sliders
+
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use crate::dialogue::DialogueDatabase;
+use crate::economy::WorldState;
+use crate::game_state::{AppState, GameState};
+use crate::share_code::MAX_SEED;
+use crate::thing_type::{ThingDefinition, ThingRegistry, ThingTraits, Trait, TRAIT_LEVELS};
+use super::{spawn_text_field, ButtonInteractionQuery, FocusTrap, TextField, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+const NOTCH_ON: Color = Color::srgb(0.8, 0.6, 0.3);
+const NOTCH_OFF: Color = Color::srgb(0.2, 0.2, 0.25);
+
+/// The builder's state while the selection screen is up
+#[derive(Resource, Default)]
+pub struct ThingBuilder {
+    pub open: bool,
+    pub traits: ThingTraits,
+    /// Terry's current reaction, with `{thing}` where the name goes
+    pub reaction: String,
+}
+
+/// The "Other..." button next to the four words
+#[derive(Component)]
+pub struct OtherThingButton;
+
+/// Marker for the builder window
+#[derive(Component)]
+pub struct ThingBuilderDialog;
+
+/// Marker for the name field
+#[derive(Component)]
+pub struct CustomNameField;
+
+/// One step on a slider
+#[derive(Component)]
+pub struct TraitNotchButton {
+    pub which: Trait,
+    pub level: u8,
+}
+
+/// What a slider's current step is called
+#[derive(Component)]
+pub struct TraitValueText(pub Trait);
+
+/// Terry's running commentary inside the builder
+#[derive(Component)]
+pub struct BuilderTerryText;
+
+/// Marker for the "Sell it" button
+#[derive(Component)]
+pub struct StartCustomThingButton;
+
+/// Marker for the "Never mind" button
+#[derive(Component)]
+pub struct CancelThingBuilderButton;
+
+/// Terry reacts to whichever slider is pushed furthest
+fn improvise(dialogue_db: &DialogueDatabase, traits: ThingTraits) -> String {
+    let top = TRAIT_LEVELS - 1;
+    let mut triggers = Vec::new();
+    if traits.price_point == top {
+        triggers.push("custom_price_high");
+    } else if traits.price_point == 0 {
+        triggers.push("custom_price_low");
+    }
+    if traits.quality == top {
+        triggers.push("custom_quality_high");
+    } else if traits.quality == 0 {
+        triggers.push("custom_quality_low");
+    }
+    if traits.sketchiness >= top - 1 {
+        triggers.push("custom_sketchy_high");
+    }
+    if triggers.is_empty() {
+        triggers.push("custom_middling");
+    }
+
+    let trigger = triggers[rand::random_range(0..triggers.len())];
+    dialogue_db
+        .get_for_trigger(trigger)
+        .map_or_else(|| "{thing}. Sure. Why not.".to_string(), |line| line.text.clone())
+}
+
+/// The name as typed, if it's one word
+fn one_word(field: &TextField) -> Option<&str> {
+    let name = field.value.trim();
+    (!name.is_empty() && !name.contains(char::is_whitespace)).then_some(name)
+}
+
+pub fn spawn_other_thing_button(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(140.0),
+                height: Val::Px(60.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                border: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.3, 0.3, 0.3)),
+            BackgroundColor(NORMAL_BUTTON),
+            OtherThingButton,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Other..."),
+                TextFont {
+                    font_size: 24.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+            ));
+        });
+}
+
+/// Centered builder window (hidden until "Other..." is clicked)
+pub fn spawn_thing_builder(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(100.0),
+                left: Val::Percent(50.0),
+                margin: UiRect::left(Val::Px(-250.0)),
+                width: Val::Px(500.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                padding: UiRect::all(Val::Px(15.0)),
+                border: UiRect::all(Val::Px(2.0)),
+                display: Display::None,
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.8, 0.6, 0.3)),
+            BackgroundColor(Color::srgb(0.08, 0.07, 0.1)),
+            GlobalZIndex(60),
+            ThingBuilderDialog,
+            FocusTrap,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("SOMETHING ELSE"),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.85, 0.5)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(12.0)),
+                    ..default()
+                },
+            ));
+
+            spawn_text_field(parent, 200.0, 16, "One word", CustomNameField);
+
+            for which in Trait::ALL {
+                spawn_trait_slider(parent, which);
+            }
+
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.8, 0.6)),
+                Node {
+                    margin: UiRect::vertical(Val::Px(12.0)),
+                    ..default()
+                },
+                TextLayout {
+                    justify: Justify::Center,
+                    ..default()
+                },
+                BuilderTerryText,
+            ));
+
+            parent
+                .spawn(Node {
+                    column_gap: Val::Px(12.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    spawn_builder_button(parent, "Sell it", StartCustomThingButton);
+                    spawn_builder_button(parent, "Never mind", CancelThingBuilderButton);
+                });
+        });
+}
+
+/// Label, a row of notches, and the current step's name
+fn spawn_trait_slider(parent: &mut ChildSpawnerCommands, which: Trait) {
+    parent
+        .spawn(Node {
+            width: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(10.0),
+            margin: UiRect::top(Val::Px(10.0)),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(which.name()),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                Node {
+                    width: Val::Px(100.0),
+                    ..default()
+                },
+            ));
+
+            parent
+                .spawn(Node {
+                    column_gap: Val::Px(3.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for level in 0..TRAIT_LEVELS {
+                        parent.spawn((
+                            Button,
+                            Node {
+                                width: Val::Px(36.0),
+                                height: Val::Px(16.0),
+                                ..default()
+                            },
+                            BackgroundColor(NOTCH_OFF),
+                            TraitNotchButton { which, level },
+                        ));
+                    }
+                });
+
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                TraitValueText(which),
+            ));
+        });
+}
+
+fn spawn_builder_button(parent: &mut ChildSpawnerCommands, label: &str, marker: impl Component) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(16.0), Val::Px(6.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.5, 0.5, 0.5)),
+            BackgroundColor(NORMAL_BUTTON),
+            marker,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// Open the builder
+pub fn handle_other_thing_button(
+    mut interaction_query: ButtonInteractionQuery<OtherThingButton>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut builder: ResMut<ThingBuilder>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                builder.open = true;
+                builder.reaction = improvise(&dialogue_db, builder.traits);
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+/// Move a slider, and let Terry have an opinion about it
+pub fn handle_trait_notches(
+    interaction_query: Query<(&Interaction, &TraitNotchButton), Changed<Interaction>>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut builder: ResMut<ThingBuilder>,
+) {
+    for (interaction, notch) in &interaction_query {
+        if *interaction == Interaction::Pressed && builder.traits.get(notch.which) != notch.level {
+            builder.traits.set(notch.which, notch.level);
+            builder.reaction = improvise(&dialogue_db, builder.traits);
+        }
+    }
+}
+
+/// Close the builder without starting
+pub fn handle_cancel_thing_builder_button(
+    mut interaction_query: ButtonInteractionQuery<CancelThingBuilderButton>,
+    mut builder: ResMut<ThingBuilder>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                builder.open = false;
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+/// What starting a run with the made-up Thing touches
+#[derive(SystemParam)]
+pub struct CustomRunStart<'w> {
+    things: ResMut<'w, ThingRegistry>,
+    game_state: ResMut<'w, GameState>,
+    world: ResMut<'w, WorldState>,
+    next_state: ResMut<'w, NextState<AppState>>,
+}
+
+/// Start a run selling the made-up Thing, if it has a one-word name
+pub fn handle_start_custom_thing_button(
+    mut interaction_query: ButtonInteractionQuery<StartCustomThingButton>,
+    field_query: Query<&TextField, With<CustomNameField>>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut builder: ResMut<ThingBuilder>,
+    mut start: CustomRunStart,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                let Ok(field) = field_query.single() else {
+                    continue;
+                };
+                match one_word(field) {
+                    Some(name) => {
+                        let definition = ThingDefinition::from_traits(name, builder.traits);
+                        start.game_state.thing_type = Some(start.things.set_custom(definition));
+                        start.world.seed = rand::random_range(0..=MAX_SEED);
+                        start.next_state.set(AppState::Playing);
+                    }
+                    None => {
+                        if let Some(line) = dialogue_db.get_for_trigger("custom_bad_name") {
+                            builder.reaction = line.text.clone();
+                        }
+                    }
+                }
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+/// Show or hide the builder and keep its sliders and Terry's line current
+pub fn update_thing_builder(
+    builder: Res<ThingBuilder>,
+    field_query: Query<Ref<TextField>, With<CustomNameField>>,
+    mut dialog_query: Query<&mut Node, With<ThingBuilderDialog>>,
+    mut notch_query: Query<(&TraitNotchButton, &mut BackgroundColor)>,
+    mut value_query: Query<(&mut Text, &TraitValueText)>,
+    mut terry_query: Query<&mut Text, (With<BuilderTerryText>, Without<TraitValueText>)>,
+) {
+    let Ok(field) = field_query.single() else {
+        return;
+    };
+    if !builder.is_changed() && !field.is_changed() {
+        return;
+    }
+
+    for mut node in &mut dialog_query {
+        node.display = if builder.open { Display::Flex } else { Display::None };
+    }
+    for (notch, mut bg_color) in &mut notch_query {
+        *bg_color = if notch.level <= builder.traits.get(notch.which) { NOTCH_ON } else { NOTCH_OFF }.into();
+    }
+    for (mut text, value) in &mut value_query {
+        **text = value.0.level_name(builder.traits.get(value.0)).to_string();
+    }
+
+    let name = one_word(&field).unwrap_or("your Thing");
+    for mut text in &mut terry_query {
+        **text = format!("\"{}\"", builder.reaction.replace("{thing}", name));
+    }
+}
@@ -0,0 +1,268 @@
+//! Picking a Thing on the selection screen - hover hints, Terry's pick and a confirm step
+//!
+//! Hovering one of the words gets a look from Terry and a hint of what that
+//! kind of business is like (never any numbers). "Terry, what do you think?"
+//! gets a recommendation, with Terry's usual biases. Clicking a word (or
+//! taking Terry's advice) only puts it forward: the run starts once the
+//! player confirms, so a misclick can't lock them into a long run of Bad.
+
+use bevy::prelude::*;
+use crate::dialogue::DialogueDatabase;
+use crate::economy::WorldState;
+use crate::game_state::{AppState, GameState};
+use crate::share_code::MAX_SEED;
+use crate::thing_type::{ThingDefinition, ThingRegistry, ThingType};
+use super::{ButtonInteractionQuery, ThingTypeButton, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+/// Border on the word that's been put forward
+const PICKED_BORDER: Color = Color::srgb(1.0, 0.85, 0.3);
+/// Border on the others
+const UNPICKED_BORDER: Color = Color::srgb(0.3, 0.3, 0.3);
+
+/// How much Terry leans towards each built-in Thing (margins are an MBA's love language)
+const TERRY_BIAS: [(ThingType, u32); 4] =
+    [(ThingType::CHEAP, 2), (ThingType::GOOD, 3), (ThingType::EXPENSIVE, 5), (ThingType::BAD, 1)];
+
+/// The Thing put forward, waiting to be confirmed
+#[derive(Resource, Default)]
+pub struct ThingPick {
+    pub pending: Option<ThingType>,
+}
+
+/// Terry's hint under the words
+#[derive(Component)]
+pub struct ThingPreviewText;
+
+/// Marker for the "Terry, what do you think?" button
+#[derive(Component)]
+pub struct AskTerryButton;
+
+/// The "Go with it?" row, hidden until something is picked
+#[derive(Component)]
+pub struct ConfirmThingRow;
+
+/// The question on the confirm row
+#[derive(Component)]
+pub struct ConfirmThingText;
+
+/// Marker for the button that starts the run
+#[derive(Component)]
+pub struct ConfirmThingButton;
+
+/// Marker for the button that takes the pick back
+#[derive(Component)]
+pub struct ChangeThingButton;
+
+/// Terry's hint for a Thing, falling back on its description
+fn hover_hint(dialogue_db: &DialogueDatabase, thing: &ThingDefinition) -> String {
+    let trigger = format!("hover_{}", thing.dialogue_prefix);
+    match dialogue_db.get_for_trigger(&trigger) {
+        Some(line) => format!("\"{}\"", line.text),
+        None => format!("*tilts head* \"{}\"", thing.description),
+    }
+}
+
+/// Terry's pick, weighted towards the Things Terry likes
+fn terry_pick() -> ThingType {
+    let total: u32 = TERRY_BIAS.iter().map(|(_, weight)| weight).sum();
+    let mut roll = rand::random_range(0..total);
+    for (thing_type, weight) in TERRY_BIAS {
+        if roll < weight {
+            return thing_type;
+        }
+        roll -= weight;
+    }
+    ThingType::GOOD
+}
+
+/// Hint line, "Terry, what do you think?" and the (hidden) confirm row
+pub fn spawn_thing_picker(parent: &mut ChildSpawnerCommands) {
+    parent.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 16.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.8, 0.75, 0.6)),
+        Node {
+            margin: UiRect::top(Val::Px(20.0)),
+            max_width: Val::Px(640.0),
+            min_height: Val::Px(40.0),
+            ..default()
+        },
+        TextLayout {
+            justify: Justify::Center,
+            ..default()
+        },
+        ThingPreviewText,
+    ));
+
+    spawn_picker_button(parent, "Terry, what do you think?", AskTerryButton);
+
+    parent
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(10.0),
+                margin: UiRect::top(Val::Px(16.0)),
+                display: Display::None,
+                ..default()
+            },
+            ConfirmThingRow,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                ConfirmThingText,
+            ));
+            spawn_picker_button(parent, "Let's do it", ConfirmThingButton);
+            spawn_picker_button(parent, "Wait, no", ChangeThingButton);
+        });
+}
+
+fn spawn_picker_button(parent: &mut ChildSpawnerCommands, label: &str, marker: impl Component) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(12.0), Val::Px(5.0)),
+                margin: UiRect::top(Val::Px(10.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.9, 0.8, 0.6)),
+            BackgroundColor(NORMAL_BUTTON),
+            marker,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// Terry eyes whichever word the player is hovering
+pub fn preview_hovered_thing(
+    interaction_query: Query<(&Interaction, &ThingTypeButton), Changed<Interaction>>,
+    things: Res<ThingRegistry>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut preview_query: Query<&mut Text, With<ThingPreviewText>>,
+) {
+    for (interaction, thing_button) in &interaction_query {
+        if *interaction != Interaction::Hovered {
+            continue;
+        }
+        let hint = hover_hint(&dialogue_db, &things[thing_button.0]);
+        for mut text in &mut preview_query {
+            **text = hint.clone();
+        }
+    }
+}
+
+/// Ask Terry to pick, and put the pick forward
+pub fn handle_ask_terry_button(
+    mut interaction_query: ButtonInteractionQuery<AskTerryButton>,
+    things: Res<ThingRegistry>,
+    dialogue_db: Res<DialogueDatabase>,
+    mut pick: ResMut<ThingPick>,
+    mut preview_query: Query<&mut Text, With<ThingPreviewText>>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                let thing_type = terry_pick();
+                pick.pending = Some(thing_type);
+                let name = &things[thing_type].name;
+                let advice = dialogue_db
+                    .get_for_trigger("recommend_thing")
+                    .map(|line| line.text.replace("{thing}", name))
+                    .unwrap_or_else(|| format!("{}. Definitely {}.", name, name));
+                for mut text in &mut preview_query {
+                    **text = format!("\"{}\"", advice);
+                }
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+/// Start the run with the Thing put forward
+pub fn handle_confirm_thing_button(
+    mut interaction_query: ButtonInteractionQuery<ConfirmThingButton>,
+    pick: Res<ThingPick>,
+    mut game_state: ResMut<GameState>,
+    mut world: ResMut<WorldState>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                let Some(thing_type) = pick.pending else {
+                    continue;
+                };
+                game_state.thing_type = Some(thing_type);
+                world.seed = rand::random_range(0..=MAX_SEED);
+                next_state.set(AppState::Playing);
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+/// Take the pick back
+pub fn handle_change_thing_button(
+    mut interaction_query: ButtonInteractionQuery<ChangeThingButton>,
+    mut pick: ResMut<ThingPick>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                pick.pending = None;
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+/// Show the confirm row for the pick and mark its word
+pub fn update_thing_pick(
+    pick: Res<ThingPick>,
+    things: Res<ThingRegistry>,
+    mut row_query: Query<&mut Node, With<ConfirmThingRow>>,
+    mut question_query: Query<&mut Text, With<ConfirmThingText>>,
+    mut button_query: Query<(&ThingTypeButton, &mut BorderColor)>,
+) {
+    if !pick.is_changed() {
+        return;
+    }
+
+    for mut node in &mut row_query {
+        node.display = if pick.pending.is_some() { Display::Flex } else { Display::None };
+    }
+    if let Some(thing_type) = pick.pending {
+        for mut text in &mut question_query {
+            **text = format!("Sell {}? There's no changing it later.", things[thing_type].name);
+        }
+    }
+    for (button, mut border) in &mut button_query {
+        let color = if pick.pending == Some(button.0) { PICKED_BORDER } else { UNPICKED_BORDER };
+        *border = BorderColor::all(color);
+    }
+}
@@ -0,0 +1,146 @@
+//! Tutorial explainer cards - a short note the first time something happens
+
+use bevy::prelude::*;
+use crate::settings::Settings;
+use crate::tutorial::FirstTimeEvent;
+use super::{ButtonInteractionQuery, FocusTrap, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+/// Marker for the explainer card
+#[derive(Component)]
+pub struct TutorialCard;
+
+/// Marker for the card's title
+#[derive(Component)]
+pub struct TutorialCardTitle;
+
+/// Marker for the card's body
+#[derive(Component)]
+pub struct TutorialCardText;
+
+/// Marker for the card's dismiss button
+#[derive(Component)]
+pub struct DismissTutorialButton;
+
+/// Explainer card in the bottom corner, left of the break reminder (hidden until needed)
+pub fn spawn_tutorial_card(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(15.0),
+                right: Val::Px(330.0),
+                width: Val::Px(300.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(12.0)),
+                border: UiRect::all(Val::Px(2.0)),
+                display: Display::None,
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.8, 0.7, 0.4)),
+            BackgroundColor(Color::srgb(0.12, 0.1, 0.08)),
+            GlobalZIndex(65),
+            TutorialCard,
+            FocusTrap,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 15.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.85, 0.5)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(6.0)),
+                    ..default()
+                },
+                TutorialCardTitle,
+            ));
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.85, 0.85, 0.9)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(10.0)),
+                    ..default()
+                },
+                TutorialCardText,
+            ));
+
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                        border: UiRect::all(Val::Px(1.0)),
+                        align_self: AlignSelf::FlexEnd,
+                        ..default()
+                    },
+                    BorderColor::all(Color::srgb(0.6, 0.5, 0.3)),
+                    BackgroundColor(NORMAL_BUTTON),
+                    DismissTutorialButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new("Got it"),
+                        TextFont {
+                            font_size: 13.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+        });
+}
+
+/// Show the explainer for something that just happened for the first time
+pub fn show_tutorial_cards(
+    mut first_events: MessageReader<FirstTimeEvent>,
+    settings: Res<Settings>,
+    mut card_query: Query<&mut Node, With<TutorialCard>>,
+    mut title_query: Query<&mut Text, (With<TutorialCardTitle>, Without<TutorialCardText>)>,
+    mut text_query: Query<&mut Text, (With<TutorialCardText>, Without<TutorialCardTitle>)>,
+) {
+    let Some(event) = first_events.read().last() else {
+        return;
+    };
+    if !settings.tutorial_cards {
+        return;
+    }
+
+    for mut text in &mut title_query {
+        **text = event.first.title().to_string();
+    }
+    for mut text in &mut text_query {
+        **text = event.first.explainer().to_string();
+    }
+    for mut node in &mut card_query {
+        node.display = Display::Flex;
+    }
+}
+
+/// Close the explainer
+pub fn handle_dismiss_tutorial_button(
+    mut interaction_query: ButtonInteractionQuery<DismissTutorialButton>,
+    mut card_query: Query<&mut Node, With<TutorialCard>>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                for mut node in &mut card_query {
+                    node.display = Display::None;
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
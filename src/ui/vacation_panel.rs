@@ -0,0 +1,168 @@
+//! Vacation prompt under Terry's dialogue - answering requests for time off, and who's advising today
+
+use bevy::prelude::*;
+use crate::vacation::{VacationDecisionEvent, VacationState, VACATION_DAYS};
+use super::{NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+const TERRY_PORTRAIT: &str = "[TERRY]\n🌭\nMBA, Hot Dog";
+const TEMP_PORTRAIT: &str = "[DOUG]\nTemp Advisor\nNo MBA";
+
+/// Marker for the "Terry says:" label
+#[derive(Component)]
+pub struct AdvisorNameText;
+
+/// Marker for the portrait placeholder text
+#[derive(Component)]
+pub struct AdvisorPortraitText;
+
+/// Container for the request and its buttons (hidden unless Terry is asking)
+#[derive(Component)]
+pub struct VacationPrompt;
+
+/// Button that answers the request (true grants it)
+#[derive(Component)]
+pub struct VacationButton(pub bool);
+
+/// Marker for the relationship / away text
+#[derive(Component)]
+pub struct VacationStatusText;
+
+/// Every text the vacation changes, and which one each is (the rest are the status line)
+type AdvisorTextQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static mut Text, Has<AdvisorNameText>, Has<AdvisorPortraitText>),
+    Or<(With<AdvisorNameText>, With<AdvisorPortraitText>, With<VacationStatusText>)>,
+>;
+
+/// Request prompt and relationship line (goes under Terry's dialogue)
+pub fn spawn_vacation_prompt(parent: &mut ChildSpawnerCommands) {
+    parent.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 12.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.6, 0.6, 0.6)),
+        Node {
+            margin: UiRect::top(Val::Px(10.0)),
+            ..default()
+        },
+        VacationStatusText,
+    ));
+
+    parent
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(8.0)),
+                margin: UiRect::top(Val::Px(6.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                display: Display::None,
+                ..default()
+            },
+            BorderColor::all(Color::srgb(0.8, 0.5, 0.2)),
+            BackgroundColor(Color::srgb(0.15, 0.12, 0.1)),
+            VacationPrompt,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(format!(
+                    "Terry wants {} days off. Terry's seminar bonuses go too, and a temp fills in.",
+                    VACATION_DAYS
+                )),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.85, 0.7)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(6.0)),
+                    ..default()
+                },
+            ));
+            parent
+                .spawn(Node {
+                    column_gap: Val::Px(6.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for (grant, label) in [(true, "Grant"), (false, "Refuse")] {
+                        parent
+                            .spawn((
+                                Button,
+                                Node {
+                                    padding: UiRect::axes(Val::Px(10.0), Val::Px(4.0)),
+                                    border: UiRect::all(Val::Px(1.0)),
+                                    ..default()
+                                },
+                                BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+                                BackgroundColor(NORMAL_BUTTON),
+                                VacationButton(grant),
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    Text::new(label),
+                                    TextFont {
+                                        font_size: 12.0,
+                                        ..default()
+                                    },
+                                    TextColor(Color::WHITE),
+                                ));
+                            });
+                    }
+                });
+        });
+}
+
+/// Answer the request
+pub fn handle_vacation_buttons(
+    mut interaction_query: Query<(&Interaction, &VacationButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut decision_events: MessageWriter<VacationDecisionEvent>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                decision_events.write(VacationDecisionEvent { grant: button.0 });
+            }
+            Interaction::Hovered => *bg_color = HOVERED_BUTTON.into(),
+            Interaction::None => *bg_color = NORMAL_BUTTON.into(),
+        }
+    }
+}
+
+/// Show the prompt while Terry's asking, and swap in the temp while Terry's away
+pub fn update_vacation_panel(
+    vacation: Res<VacationState>,
+    added: Query<(), Added<VacationStatusText>>,
+    mut prompt_query: Query<&mut Node, With<VacationPrompt>>,
+    mut text_query: AdvisorTextQuery,
+) {
+    if !vacation.is_changed() && added.is_empty() {
+        return;
+    }
+
+    for mut node in &mut prompt_query {
+        node.display = if vacation.request_days.is_some() { Display::Flex } else { Display::None };
+    }
+
+    let status = match &vacation.away {
+        Some(away) if away.burnout => format!("Terry burned out - back in {} days", away.days_remaining),
+        Some(away) => format!("Terry's on vacation - back in {} days", away.days_remaining),
+        None => match vacation.request_days {
+            Some(days) => format!("Terry needs an answer within {} days", days),
+            None => format!("Relationship with Terry: {:.0}/100", vacation.relationship),
+        },
+    };
+    for (mut text, is_name, is_portrait) in &mut text_query {
+        **text = if is_name {
+            if vacation.is_away() { "Doug (temp) says:" } else { "Terry says:" }.to_string()
+        } else if is_portrait {
+            if vacation.is_away() { TEMP_PORTRAIT } else { TERRY_PORTRAIT }.to_string()
+        } else {
+            status.clone()
+        };
+    }
+}
@@ -0,0 +1,189 @@
+//! Pop-up calendar overlay - upcoming venues to book, and the ones already booked
+
+use bevy::prelude::*;
+use crate::currency::money_whole;
+use crate::game_state::GameState;
+use crate::venues::{VenueBooking, Venues};
+use super::{spawn_overlay, OverlayKind, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON, DISABLED_BUTTON};
+
+/// Container the calendar entries are rebuilt into
+#[derive(Component)]
+pub struct VenueList;
+
+/// Button that books the venue at this index in `Venues::bookings`
+#[derive(Component)]
+pub struct BookVenueButton(pub usize);
+
+/// Pop-up calendar overlay
+pub fn spawn_venue_overlay(parent: &mut ChildSpawnerCommands) {
+    spawn_overlay(parent, OverlayKind::Venues, "POP-UP CALENDAR", 400.0, |parent| {
+        parent.spawn((
+            Text::new(
+                "Rent a stall somewhere busy for a few days. Venues for next month \
+                 are posted on the first. Outdoor ones are at the mercy of the weather.",
+            ),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            Node {
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+        ));
+
+        parent.spawn((
+            Node {
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            VenueList,
+        ));
+    });
+}
+
+fn spawn_venue_row(parent: &mut ChildSpawnerCommands, index: usize, booking: &VenueBooking) {
+    let kind = booking.kind;
+    parent
+        .spawn(Node {
+            width: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            margin: UiRect::bottom(Val::Px(8.0)),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn(Node {
+                    width: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::SpaceBetween,
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(format!(
+                            "{} - {}\n{} to {}",
+                            kind.name(),
+                            if kind.is_outdoor() { "outdoor" } else { "indoor" },
+                            booking.start.format(),
+                            booking.end().format()
+                        )),
+                        TextFont {
+                            font_size: 13.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.85, 0.85, 0.85)),
+                    ));
+
+                    if booking.booked {
+                        let status = if booking.washed_out_days > 0 {
+                            format!("BOOKED ({} days rained out)", booking.washed_out_days)
+                        } else {
+                            "BOOKED".to_string()
+                        };
+                        parent.spawn((
+                            Text::new(status),
+                            TextFont {
+                                font_size: 12.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(0.5, 0.9, 0.5)),
+                        ));
+                    } else {
+                        parent
+                            .spawn((
+                                Button,
+                                Node {
+                                    min_width: Val::Px(110.0),
+                                    padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                                    justify_content: JustifyContent::Center,
+                                    border: UiRect::all(Val::Px(1.0)),
+                                    ..default()
+                                },
+                                BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+                                BackgroundColor(NORMAL_BUTTON),
+                                BookVenueButton(index),
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    Text::new(format!("Book ({})", money_whole(kind.setup_cost()))),
+                                    TextFont {
+                                        font_size: 12.0,
+                                        ..default()
+                                    },
+                                    TextColor(Color::WHITE),
+                                ));
+                            });
+                    }
+                });
+
+            parent.spawn((
+                Text::new(format!("{} Sales x{:.1} while open.", kind.description(), kind.demand_boost())),
+                TextFont {
+                    font_size: 11.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            ));
+        });
+}
+
+/// Book a venue
+pub fn handle_book_venue_buttons(
+    mut interaction_query: Query<(&Interaction, &BookVenueButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut venues: ResMut<Venues>,
+    mut game_state: ResMut<GameState>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        let can_afford = venues
+            .bookings
+            .get(button.0)
+            .is_some_and(|booking| game_state.money >= booking.kind.setup_cost());
+
+        match *interaction {
+            Interaction::Pressed => {
+                if can_afford {
+                    *bg_color = PRESSED_BUTTON.into();
+                    venues.book(button.0, &mut game_state);
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = if can_afford { HOVERED_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+            Interaction::None => {
+                *bg_color = if can_afford { NORMAL_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+        }
+    }
+}
+
+/// Rebuild the calendar when venues are offered, booked or finished
+pub fn update_venue_list(
+    mut commands: Commands,
+    venues: Res<Venues>,
+    list_query: Query<Entity, With<VenueList>>,
+) {
+    if !venues.is_changed() {
+        return;
+    }
+
+    for list in &list_query {
+        commands.entity(list).despawn_children().with_children(|parent| {
+            if venues.bookings.is_empty() {
+                parent.spawn((
+                    Text::new("Nothing on the calendar. Check back on the first of the month."),
+                    TextFont {
+                        font_size: 12.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.6, 0.6, 0.6)),
+                ));
+            }
+            for (index, booking) in venues.bookings.iter().enumerate() {
+                spawn_venue_row(parent, index, booking);
+            }
+        });
+    }
+}
@@ -0,0 +1,322 @@
+//! Milestone vignettes - a short interstitial for the really big milestones
+//!
+//! A million dollars or a million Things stops the sim for a few seconds:
+//! Terry pops a bottle of champagne-flavored mustard, confetti comes down, and
+//! the scene freezes on a caption. Clicking anywhere skips it. The vignette
+//! runs on real time, since the clock it stops is the one it would otherwise
+//! animate with; in low power there's no confetti, just the freeze-frame.
+
+use bevy::prelude::*;
+use crate::game_state::{GameState, MilestoneEvent, MilestoneType};
+use crate::power::PowerMode;
+use super::FocusTrap;
+
+/// Milestones at or past this count get a vignette
+const VIGNETTE_MILESTONE: u64 = 1_000_000;
+/// Seconds of confetti before the freeze-frame
+const CONFETTI_SECS: f32 = 2.0;
+/// Seconds the whole vignette runs, freeze-frame included
+const VIGNETTE_SECS: f32 = 4.5;
+/// Confetti pieces per vignette
+const CONFETTI_PIECES: usize = 60;
+/// How fast confetti falls, in logical pixels per second per second
+const CONFETTI_GRAVITY: f32 = 420.0;
+const CONFETTI_COLORS: [Color; 5] = [
+    Color::srgb(0.95, 0.25, 0.25),
+    Color::srgb(1.0, 0.8, 0.2),
+    Color::srgb(0.3, 0.75, 0.35),
+    Color::srgb(0.35, 0.6, 1.0),
+    Color::srgb(0.9, 0.45, 0.85),
+];
+
+/// The vignette playing, if any
+#[derive(Resource, Default)]
+pub struct MilestoneVignette {
+    pub showing: Option<MilestoneType>,
+    /// Real seconds since it started
+    pub elapsed: f32,
+    /// Whether the sim was running before the vignette stopped it
+    resume: bool,
+}
+
+/// Marker for the full-screen vignette (itself the skip button)
+#[derive(Component)]
+pub struct VignettePanel;
+
+/// Container the confetti is spawned into
+#[derive(Component)]
+pub struct VignetteConfetti;
+
+/// Marker for Terry and the bottle
+#[derive(Component)]
+pub struct VignetteTerry;
+
+/// Marker for the freeze-frame caption
+#[derive(Component)]
+pub struct VignetteCaption;
+
+/// A piece of confetti (logical pixel coordinates inside the vignette)
+#[derive(Component)]
+pub struct Confetti {
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub spin: f32,
+}
+
+/// Full-screen vignette (hidden until a big milestone)
+pub fn spawn_milestone_vignette(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(0.0),
+                left: Val::Px(0.0),
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                display: Display::None,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.05, 0.03, 0.08, 0.85)),
+            GlobalZIndex(90),
+            VignettePanel,
+            FocusTrap,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(0.0),
+                    left: Val::Px(0.0),
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    overflow: Overflow::clip(),
+                    ..default()
+                },
+                VignetteConfetti,
+            ));
+
+            parent.spawn((
+                Text::new("🌭🍾"),
+                TextFont {
+                    font_size: 72.0,
+                    ..default()
+                },
+                UiTransform::default(),
+                VignetteTerry,
+            ));
+
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 26.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.85, 0.3)),
+                TextLayout::new_with_justify(Justify::Center),
+                Node {
+                    margin: UiRect::top(Val::Px(18.0)),
+                    max_width: Val::Px(560.0),
+                    ..default()
+                },
+                Visibility::Hidden,
+                VignetteCaption,
+            ));
+
+            parent.spawn((
+                Text::new("Click to skip"),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.55, 0.55, 0.55)),
+                Node {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(20.0),
+                    ..default()
+                },
+            ));
+        });
+}
+
+/// Freeze-frame caption for a milestone
+fn caption(milestone: MilestoneType) -> String {
+    match milestone {
+        MilestoneType::MoneyEarned(_) => {
+            "A MILLION DOLLARS.\nTerry pops the champagne-flavored mustard. Nobody asked what that is.".to_string()
+        }
+        MilestoneType::ThingsProduced(_) => {
+            "ONE MILLION THINGS.\nTerry pops the champagne-flavored mustard and salutes the assembly line.".to_string()
+        }
+        milestone => format!("{}!\nTerry pops the champagne-flavored mustard.", milestone.describe().to_uppercase()),
+    }
+}
+
+fn is_vignette_milestone(milestone: MilestoneType) -> bool {
+    match milestone {
+        MilestoneType::ThingsProduced(n) | MilestoneType::MoneyEarned(n) | MilestoneType::CustomersServed(n) => {
+            n >= VIGNETTE_MILESTONE
+        }
+        MilestoneType::ReputationReached(_) => false,
+    }
+}
+
+/// Stop the sim and start a vignette when a really big milestone comes in (not in demos)
+pub fn start_milestone_vignettes(
+    mut commands: Commands,
+    mut milestone_events: MessageReader<MilestoneEvent>,
+    power: Res<PowerMode>,
+    mut time: ResMut<Time<Virtual>>,
+    mut game_state: ResMut<GameState>,
+    mut vignette: ResMut<MilestoneVignette>,
+    confetti_query: Query<(Entity, &ComputedNode), With<VignetteConfetti>>,
+) {
+    let Some(milestone) = milestone_events
+        .read()
+        .map(|event| event.milestone_type)
+        .filter(|milestone| is_vignette_milestone(*milestone))
+        .last()
+    else {
+        return;
+    };
+    if vignette.showing.is_some() {
+        return;
+    }
+
+    vignette.showing = Some(milestone);
+    vignette.elapsed = 0.0;
+    vignette.resume = !game_state.paused;
+    game_state.paused = true;
+    time.pause();
+
+    if power.low_power {
+        return;
+    }
+    for (container, computed) in &confetti_query {
+        let width = (computed.size().x * computed.inverse_scale_factor()).max(800.0);
+        commands.entity(container).with_children(|parent| {
+            for i in 0..CONFETTI_PIECES {
+                let position = Vec2::new(rand::random::<f32>() * width, -20.0 - rand::random::<f32>() * 200.0);
+                parent.spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(position.x),
+                        top: Val::Px(position.y),
+                        width: Val::Px(8.0),
+                        height: Val::Px(12.0),
+                        ..default()
+                    },
+                    BackgroundColor(CONFETTI_COLORS[i % CONFETTI_COLORS.len()]),
+                    UiTransform::default(),
+                    Confetti {
+                        position,
+                        velocity: Vec2::new((rand::random::<f32>() - 0.5) * 160.0, rand::random::<f32>() * 120.0),
+                        spin: (rand::random::<f32>() - 0.5) * 12.0,
+                    },
+                ));
+            }
+        });
+    }
+}
+
+/// Run the vignette: confetti, Terry bouncing, then the freeze-frame
+pub fn animate_milestone_vignette(
+    time: Res<Time<Real>>,
+    mut vignette: ResMut<MilestoneVignette>,
+    mut confetti_query: Query<(&mut Confetti, &mut Node, &mut UiTransform), Without<VignetteTerry>>,
+    mut terry_query: Query<&mut UiTransform, (With<VignetteTerry>, Without<Confetti>)>,
+    mut caption_query: Query<&mut Visibility, With<VignetteCaption>>,
+) {
+    if vignette.showing.is_none() {
+        return;
+    }
+    let step = time.delta_secs();
+    vignette.elapsed += step;
+    let frozen = vignette.elapsed >= CONFETTI_SECS || confetti_query.is_empty();
+
+    for mut visibility in &mut caption_query {
+        *visibility = if frozen { Visibility::Inherited } else { Visibility::Hidden };
+    }
+    if frozen {
+        return;
+    }
+
+    for (mut confetti, mut node, mut transform) in &mut confetti_query {
+        confetti.velocity.y += CONFETTI_GRAVITY * step;
+        let velocity = confetti.velocity;
+        confetti.position += velocity * step;
+        node.left = Val::Px(confetti.position.x);
+        node.top = Val::Px(confetti.position.y);
+        transform.rotation = Rot2::radians(transform.rotation.as_radians() + confetti.spin * step);
+    }
+
+    // A couple of hops while the bottle goes off
+    let hop = (vignette.elapsed / CONFETTI_SECS * std::f32::consts::TAU * 2.0).sin().abs();
+    for mut transform in &mut terry_query {
+        transform.translation = Val2::px(0.0, -24.0 * hop);
+        transform.scale = Vec2::splat(1.0 + 0.1 * hop);
+    }
+}
+
+/// Finish the vignette when its time is up or it's clicked, and start the sim again
+pub fn finish_milestone_vignette(
+    mut commands: Commands,
+    mut vignette: ResMut<MilestoneVignette>,
+    mut time: ResMut<Time<Virtual>>,
+    mut game_state: ResMut<GameState>,
+    skip_query: Query<&Interaction, (Changed<Interaction>, With<VignettePanel>)>,
+    confetti_query: Query<Entity, With<VignetteConfetti>>,
+) {
+    if vignette.showing.is_none() {
+        return;
+    }
+    let skipped = skip_query.iter().any(|interaction| *interaction == Interaction::Pressed);
+    if !skipped && vignette.elapsed < VIGNETTE_SECS {
+        return;
+    }
+
+    vignette.showing = None;
+    for container in &confetti_query {
+        commands.entity(container).despawn_related::<Children>();
+    }
+    if vignette.resume {
+        game_state.paused = false;
+        time.unpause();
+    }
+}
+
+/// Show or hide the vignette and write its caption
+pub fn update_milestone_vignette(
+    vignette: Res<MilestoneVignette>,
+    mut panel_query: Query<&mut Node, With<VignettePanel>>,
+    mut caption_query: Query<&mut Text, With<VignetteCaption>>,
+    mut terry_query: Query<&mut UiTransform, With<VignetteTerry>>,
+    mut shown: Local<Option<MilestoneType>>,
+) {
+    // Elapsed time changes the resource every frame; only the milestone matters here
+    if *shown == vignette.showing {
+        return;
+    }
+    *shown = vignette.showing;
+
+    for mut node in &mut panel_query {
+        node.display = if vignette.showing.is_some() { Display::Flex } else { Display::None };
+    }
+    let Some(milestone) = vignette.showing else {
+        for mut transform in &mut terry_query {
+            *transform = UiTransform::default();
+        }
+        return;
+    };
+    for mut text in &mut caption_query {
+        **text = caption(milestone);
+    }
+}
+
+/// Drop any vignette when the screen closes (leaving Playing starts the clock again anyway)
+pub fn clear_milestone_vignette(mut vignette: ResMut<MilestoneVignette>) {
+    *vignette = MilestoneVignette::default();
+}
@@ -0,0 +1,167 @@
+//! Wardrobe overlay and Terry's accessory layers on the portrait
+
+use bevy::prelude::*;
+use crate::cosmetics::{Cosmetic, Slot, Wardrobe};
+use crate::economy::WorldState;
+use super::{spawn_overlay, OverlayKind, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON, DISABLED_BUTTON};
+
+/// Marker for an accessory layer drawn over Terry's portrait
+#[derive(Component)]
+pub struct TerryCosmeticLayer(pub Slot);
+
+/// Marker for an equip/unequip button
+#[derive(Component)]
+pub struct CosmeticButton(pub Cosmetic);
+
+/// Marker for an equip button's label
+#[derive(Component)]
+pub struct CosmeticButtonText(pub Cosmetic);
+
+/// Accessory layers, stacked over the portrait (hat on top, monocle on the face, briefcase in hand)
+pub fn spawn_cosmetic_layers(parent: &mut ChildSpawnerCommands) {
+    for slot in Slot::ALL {
+        let (top, left, font_size) = match slot {
+            Slot::Hat => (Val::Px(8.0), Val::Percent(44.0), 30.0),
+            Slot::Face => (Val::Px(88.0), Val::Percent(56.0), 18.0),
+            Slot::Hand => (Val::Px(120.0), Val::Percent(68.0), 22.0),
+        };
+        parent.spawn((
+            Text::new(""),
+            TextFont {
+                font_size,
+                ..default()
+            },
+            Node {
+                position_type: PositionType::Absolute,
+                top,
+                left,
+                ..default()
+            },
+            TerryCosmeticLayer(slot),
+        ));
+    }
+}
+
+/// Wardrobe overlay
+pub fn spawn_wardrobe_overlay(parent: &mut ChildSpawnerCommands) {
+    spawn_overlay(parent, OverlayKind::Wardrobe, "WARDROBE", 320.0, |parent| {
+        parent.spawn((
+            Text::new(
+                "Accessories are earned through achievements and kept between runs. \
+                 On holidays Terry dresses for the occasion regardless.",
+            ),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            Node {
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+        ));
+
+        for cosmetic in Cosmetic::EARNABLE {
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Percent(100.0),
+                        padding: UiRect::axes(Val::Px(10.0), Val::Px(6.0)),
+                        margin: UiRect::bottom(Val::Px(6.0)),
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    BorderColor::all(Color::srgb(0.5, 0.4, 0.3)),
+                    BackgroundColor(DISABLED_BUTTON),
+                    CosmeticButton(cosmetic),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: 13.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        CosmeticButtonText(cosmetic),
+                    ));
+                });
+        }
+    });
+}
+
+/// Put accessories on and take them off
+pub fn handle_cosmetic_buttons(
+    mut interaction_query: Query<(&Interaction, &CosmeticButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut wardrobe: ResMut<Wardrobe>,
+) {
+    for (interaction, button, mut bg_color) in &mut interaction_query {
+        let cosmetic = button.0;
+        let unlocked = wardrobe.is_unlocked(cosmetic);
+
+        match *interaction {
+            Interaction::Pressed => {
+                if unlocked {
+                    *bg_color = PRESSED_BUTTON.into();
+                    wardrobe.toggle(cosmetic);
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = if unlocked { HOVERED_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+            Interaction::None => {
+                *bg_color = if unlocked { NORMAL_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+        }
+    }
+}
+
+/// Keep the wardrobe buttons current
+pub fn update_wardrobe_panel(
+    wardrobe: Res<Wardrobe>,
+    mut button_query: Query<(&CosmeticButton, &mut BackgroundColor, &Interaction)>,
+    mut text_query: Query<(&mut Text, &CosmeticButtonText)>,
+) {
+    if !wardrobe.is_changed() {
+        return;
+    }
+
+    for (button, mut bg_color, interaction) in &mut button_query {
+        if *interaction == Interaction::None {
+            *bg_color = if wardrobe.is_unlocked(button.0) { NORMAL_BUTTON } else { DISABLED_BUTTON }.into();
+        }
+    }
+
+    for (mut text, label) in &mut text_query {
+        let cosmetic = label.0;
+        **text = if wardrobe.is_equipped(cosmetic) {
+            format!("{} {} (wearing)", cosmetic.glyph(), cosmetic.name())
+        } else if wardrobe.is_unlocked(cosmetic) {
+            format!("{} {}", cosmetic.glyph(), cosmetic.name())
+        } else {
+            format!("Locked: {}", cosmetic.unlock_hint())
+        };
+    }
+}
+
+/// Draw whatever Terry is wearing today onto the portrait
+pub fn update_terry_cosmetics(
+    wardrobe: Res<Wardrobe>,
+    world: Res<WorldState>,
+    mut layer_query: Query<(&mut Text, &TerryCosmeticLayer)>,
+) {
+    if !wardrobe.is_changed() && !world.is_changed() {
+        return;
+    }
+
+    for (mut text, layer) in &mut layer_query {
+        let glyph = wardrobe
+            .worn(layer.0, world.current_holiday)
+            .map(|c| c.glyph())
+            .unwrap_or_default();
+        if text.as_str() != glyph {
+            **text = glyph.to_string();
+        }
+    }
+}
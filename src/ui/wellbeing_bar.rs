@@ -0,0 +1,157 @@
+//! Wellbeing bar and days-off button under the hustle meter (work-life balance mode only)
+
+use bevy::prelude::*;
+use crate::settings::Settings;
+use crate::wellbeing::{TakeDaysOffEvent, Wellbeing, DAYS_OFF, LOW_WELLBEING};
+use super::{ButtonInteractionQuery, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON, DISABLED_BUTTON};
+
+/// Marker for the wellbeing container (hidden when the mode is off)
+#[derive(Component)]
+pub struct WellbeingBar;
+
+/// Marker for the filled part of the wellbeing bar
+#[derive(Component)]
+pub struct WellbeingFill;
+
+/// Marker for the wellbeing readout
+#[derive(Component)]
+pub struct WellbeingText;
+
+/// Marker for the days-off button
+#[derive(Component)]
+pub struct DaysOffButton;
+
+const WELLBEING_FULL: Color = Color::srgb(0.4, 0.8, 0.6);
+const WELLBEING_EMPTY: Color = Color::srgb(0.4, 0.3, 0.5);
+
+pub fn spawn_wellbeing_bar(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            Node {
+                width: Val::Px(200.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                margin: UiRect::top(Val::Px(12.0)),
+                display: Display::None,
+                ..default()
+            },
+            WellbeingBar,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Wellbeing"),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.8, 0.7)),
+                WellbeingText,
+            ));
+
+            // Bar track
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Percent(100.0),
+                        height: Val::Px(12.0),
+                        margin: UiRect::vertical(Val::Px(4.0)),
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    BorderColor::all(Color::srgb(0.4, 0.4, 0.4)),
+                    BackgroundColor(Color::srgb(0.1, 0.1, 0.1)),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Node {
+                            width: Val::Percent(100.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(WELLBEING_FULL),
+                        WellbeingFill,
+                    ));
+                });
+
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        padding: UiRect::axes(Val::Px(10.0), Val::Px(4.0)),
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    BorderColor::all(Color::srgb(0.3, 0.5, 0.4)),
+                    BackgroundColor(NORMAL_BUTTON),
+                    DaysOffButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(format!("Take {} days off", DAYS_OFF)),
+                        TextFont {
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                    ));
+                });
+        });
+}
+
+/// Keep the bar's fill, color and readout in step with the player
+pub fn update_wellbeing_bar(
+    wellbeing: Res<Wellbeing>,
+    settings: Res<Settings>,
+    mut bar_query: Query<&mut Node, (With<WellbeingBar>, Without<WellbeingFill>)>,
+    mut fill_query: Query<(&mut Node, &mut BackgroundColor), With<WellbeingFill>>,
+    mut text_query: Query<&mut Text, With<WellbeingText>>,
+) {
+    if !wellbeing.is_changed() && !settings.is_changed() {
+        return;
+    }
+
+    let display = if settings.wellbeing { Display::Flex } else { Display::None };
+    for mut node in &mut bar_query {
+        node.display = display;
+    }
+
+    let fraction = wellbeing.fraction();
+    for (mut node, mut color) in &mut fill_query {
+        node.width = Val::Percent(fraction * 100.0);
+        color.0 = WELLBEING_EMPTY.mix(&WELLBEING_FULL, fraction);
+    }
+
+    for mut text in &mut text_query {
+        **text = if wellbeing.days_off > 0 {
+            format!("Day off - {} to go", wellbeing.days_off)
+        } else if wellbeing.current < LOW_WELLBEING {
+            format!("Wellbeing {:.0} - running on empty", wellbeing.current)
+        } else {
+            format!("Wellbeing {:.0}", wellbeing.current)
+        };
+    }
+}
+
+/// Take a break
+pub fn handle_days_off_button(
+    mut interaction_query: ButtonInteractionQuery<DaysOffButton>,
+    wellbeing: Res<Wellbeing>,
+    mut days_off_events: MessageWriter<TakeDaysOffEvent>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        let available = wellbeing.days_off == 0;
+
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                days_off_events.write(TakeDaysOffEvent);
+            }
+            Interaction::Hovered => {
+                *bg_color = if available { HOVERED_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+            Interaction::None => {
+                *bg_color = if available { NORMAL_BUTTON } else { DISABLED_BUTTON }.into();
+            }
+        }
+    }
+}
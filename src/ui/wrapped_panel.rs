@@ -0,0 +1,316 @@
+//! Thing Wrapped card - the year's recap, popped up when December 31 closes
+//!
+//! There's no separate sharing service: "Save card" takes a screenshot of the
+//! window with the card showing and writes it next to the stats exports.
+
+use bevy::prelude::*;
+use bevy::render::view::screenshot::{save_to_disk, Screenshot};
+use crate::currency::money_change;
+use crate::profile::Profiles;
+use crate::settings::Settings;
+use crate::wrapped::{YearRecap, YearWrappedEvent};
+use super::{ButtonInteractionQuery, FocusTrap, NORMAL_BUTTON, HOVERED_BUTTON, PRESSED_BUTTON};
+
+/// The recap on the card, while it's showing
+#[derive(Resource, Default)]
+pub struct WrappedCard {
+    pub showing: Option<YearRecap>,
+}
+
+/// Marker for the card
+#[derive(Component)]
+pub struct WrappedCardPanel;
+
+/// Container the card's tiles are rebuilt into
+#[derive(Component)]
+pub struct WrappedTiles;
+
+/// Marker for the card's "Save card" button
+#[derive(Component)]
+pub struct SaveWrappedButton;
+
+/// Marker for the card's "Close" button
+#[derive(Component)]
+pub struct CloseWrappedButton;
+
+/// Marker for where the last saved card went
+#[derive(Component)]
+pub struct WrappedStatusText;
+
+/// Each tile gets its own loud color, in this order
+const TILE_COLORS: [Color; 5] = [
+    Color::srgb(0.95, 0.35, 0.45),
+    Color::srgb(1.0, 0.65, 0.2),
+    Color::srgb(0.55, 0.3, 0.85),
+    Color::srgb(0.2, 0.7, 0.55),
+    Color::srgb(0.25, 0.5, 0.95),
+];
+
+/// The card's headline tiles: label and what goes in big letters
+fn recap_tiles(recap: &YearRecap) -> [(&'static str, String); 5] {
+    [
+        ("THINGS MADE", recap.things_made.to_string()),
+        (
+            "BEST DAY",
+            recap
+                .best_day
+                .as_ref()
+                .map(|(date, gain)| format!("{} ({})", date, money_change(*gain)))
+                .unwrap_or_else(|| "Still waiting on it".to_string()),
+        ),
+        (
+            "BIGGEST SCANDAL",
+            recap
+                .scandal
+                .as_ref()
+                .map(|(text, fall)| {
+                    if *fall > 0.0 {
+                        format!("{} (-{:.2} reputation)", text, fall)
+                    } else {
+                        text.clone()
+                    }
+                })
+                .unwrap_or_else(|| "None. Suspiciously clean.".to_string()),
+        ),
+        (
+            "MOST-USED CHANNEL",
+            recap
+                .top_channel
+                .map(|(channel, days)| format!("{} ({} days)", channel.name(), days))
+                .unwrap_or_else(|| "Word of mouth, apparently".to_string()),
+        ),
+        (
+            "TERRY QUOTE OF THE YEAR",
+            recap.quote.clone().unwrap_or_else(|| "Terry was uncharacteristically quiet.".to_string()),
+        ),
+    ]
+}
+
+/// Centered recap card (hidden until a year is wrapped)
+pub fn spawn_wrapped_card(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(60.0),
+                left: Val::Percent(50.0),
+                margin: UiRect::left(Val::Px(-230.0)),
+                width: Val::Px(460.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(16.0)),
+                border: UiRect::all(Val::Px(3.0)),
+                display: Display::None,
+                ..default()
+            },
+            BorderColor::all(Color::srgb(1.0, 0.85, 0.3)),
+            BackgroundColor(Color::srgb(0.1, 0.05, 0.15)),
+            GlobalZIndex(72),
+            WrappedCardPanel,
+            FocusTrap,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    width: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(8.0),
+                    margin: UiRect::bottom(Val::Px(12.0)),
+                    ..default()
+                },
+                WrappedTiles,
+            ));
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 11.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.6, 0.6)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(6.0)),
+                    ..default()
+                },
+                WrappedStatusText,
+            ));
+            parent
+                .spawn(Node {
+                    column_gap: Val::Px(8.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    spawn_card_button(parent, "Save card", SaveWrappedButton);
+                    spawn_card_button(parent, "Close", CloseWrappedButton);
+                });
+        });
+}
+
+fn spawn_card_button(parent: &mut ChildSpawnerCommands, label: &str, marker: impl Component) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderColor::all(Color::srgb(1.0, 0.85, 0.3)),
+            BackgroundColor(NORMAL_BUTTON),
+            marker,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// Show the card when a year is wrapped up
+pub fn show_year_wrapped(mut wrapped_events: MessageReader<YearWrappedEvent>, mut card: ResMut<WrappedCard>) {
+    if let Some(event) = wrapped_events.read().last() {
+        card.showing = Some(event.recap.clone());
+    }
+}
+
+/// Screenshot the card into the export folder
+pub fn handle_save_wrapped_button(
+    mut commands: Commands,
+    mut interaction_query: ButtonInteractionQuery<SaveWrappedButton>,
+    card: Res<WrappedCard>,
+    settings: Res<Settings>,
+    profiles: Res<Profiles>,
+    mut status_query: Query<&mut Text, With<WrappedStatusText>>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                let Some(recap) = &card.showing else {
+                    continue;
+                };
+                let dir = settings.export_location.dir(&profiles.dir());
+                let status = match std::fs::create_dir_all(&dir) {
+                    Ok(()) => {
+                        let path = dir.join(format!("thing_wrapped_{}.png", recap.year));
+                        let status = format!("Saved {}", path.display());
+                        commands.spawn(Screenshot::primary_window()).observe(save_to_disk(path));
+                        status
+                    }
+                    Err(err) => {
+                        warn!("Saving the Thing Wrapped card failed: {}", err);
+                        format!("Save failed: {}", err)
+                    }
+                };
+                for mut text in &mut status_query {
+                    **text = status.clone();
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Put the card away
+pub fn handle_close_wrapped_button(
+    mut interaction_query: ButtonInteractionQuery<CloseWrappedButton>,
+    mut card: ResMut<WrappedCard>,
+) {
+    for (interaction, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg_color = PRESSED_BUTTON.into();
+                card.showing = None;
+            }
+            Interaction::Hovered => {
+                *bg_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *bg_color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+/// Show or hide the card and lay out the recap's tiles
+pub fn update_wrapped_card(
+    mut commands: Commands,
+    card: Res<WrappedCard>,
+    mut panel_query: Query<&mut Node, With<WrappedCardPanel>>,
+    tiles_query: Query<Entity, With<WrappedTiles>>,
+    mut status_query: Query<&mut Text, With<WrappedStatusText>>,
+) {
+    if !card.is_changed() {
+        return;
+    }
+
+    for mut node in &mut panel_query {
+        node.display = if card.showing.is_some() { Display::Flex } else { Display::None };
+    }
+    let Some(recap) = &card.showing else {
+        return;
+    };
+    for mut text in &mut status_query {
+        text.clear();
+    }
+
+    for tiles in &tiles_query {
+        commands.entity(tiles).despawn_children().with_children(|parent| {
+            parent.spawn((
+                Text::new(format!("THING WRAPPED {}", recap.year)),
+                TextFont {
+                    font_size: 26.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.85, 0.3)),
+            ));
+            parent.spawn((
+                Text::new("Your year in Things, lovingly assembled by Terry"),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.7, 0.9)),
+            ));
+
+            for ((label, value), color) in recap_tiles(recap).into_iter().zip(TILE_COLORS) {
+                parent
+                    .spawn((
+                        Node {
+                            width: Val::Percent(100.0),
+                            flex_direction: FlexDirection::Column,
+                            padding: UiRect::axes(Val::Px(12.0), Val::Px(8.0)),
+                            ..default()
+                        },
+                        BackgroundColor(color),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Text::new(label),
+                            TextFont {
+                                font_size: 11.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgba(1.0, 1.0, 1.0, 0.8)),
+                        ));
+                        parent.spawn((
+                            Text::new(value),
+                            TextFont {
+                                font_size: 17.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                        ));
+                    });
+            }
+        });
+    }
+}
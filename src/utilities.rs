@@ -0,0 +1,117 @@
+//! Utilities - the power bill, and what happens when the grid gives out
+//!
+//! The factory pays a daily utility bill that grows with every automated line.
+//! Heat waves push the bill up and strain the grid, sometimes knocking the
+//! power out for a day. Solar panels take a slice off the bill.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::accounting::ExpenseCategory;
+use crate::business::UpgradeState;
+use crate::difficulty::DifficultyConfig;
+use crate::economy::{DayAdvancedEvent, WorldState};
+use crate::game_state::{AppState, GameState};
+
+// === UTILITY BALANCE ===
+/// Daily bill for the lights and the coffee machine
+pub const BASE_UTILITY_BILL: f64 = 5.0;
+/// Extra daily bill per Automation upgrade
+pub const UTILITY_PER_AUTOMATION: f64 = 8.0;
+/// Bill saved per solar panel
+pub const SOLAR_SAVINGS: f64 = 0.1;
+/// Most of the bill solar can cover
+const MAX_SOLAR_SAVINGS: f64 = 0.8;
+/// Reputation bump for each solar panel installed
+pub const SOLAR_REPUTATION_BONUS: f32 = 0.1;
+/// Temperature (F) at which it counts as a heat wave
+pub const HEAT_WAVE_TEMP: f32 = 88.0;
+/// Bill multiplier while the air conditioning fights a heat wave
+const HEAT_WAVE_BILL_MULTIPLIER: f64 = 1.5;
+/// Daily chance of a blackout during a heat wave
+const HEAT_WAVE_OUTAGE_CHANCE: f32 = 0.15;
+/// Days the power stays out
+const OUTAGE_DAYS: u32 = 1;
+
+/// The factory's power supply
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct Utilities {
+    /// Days left without power (0 = lights on)
+    pub outage_days: u32,
+}
+
+impl Utilities {
+    /// Today's bill before heat waves
+    pub fn daily_bill(upgrades: &UpgradeState) -> f64 {
+        let gross = BASE_UTILITY_BILL + upgrades.automation as f64 * UTILITY_PER_AUTOMATION;
+        let savings = (upgrades.solar_panels as f64 * SOLAR_SAVINGS).min(MAX_SOLAR_SAVINGS);
+        gross * (1.0 - savings)
+    }
+}
+
+/// Why the power went out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutageCause {
+    HeatWave,
+    UnpaidBill,
+}
+
+/// Message fired when the power goes out or comes back
+#[derive(Event, Message, Clone)]
+pub struct PowerOutageEvent {
+    /// None when the power is back on
+    pub cause: Option<OutageCause>,
+}
+
+pub struct UtilitiesPlugin;
+
+impl Plugin for UtilitiesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Utilities>()
+            .add_message::<PowerOutageEvent>()
+            .add_systems(Update, pay_utilities.run_if(in_state(AppState::Playing)));
+    }
+}
+
+/// Pay the daily bill and roll for heat-wave blackouts
+fn pay_utilities(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    world: Res<WorldState>,
+    difficulty: Res<DifficultyConfig>,
+    upgrades: Res<UpgradeState>,
+    mut utilities: ResMut<Utilities>,
+    mut game_state: ResMut<GameState>,
+    mut outage_events: MessageWriter<PowerOutageEvent>,
+) {
+    for _ in day_events.read() {
+        if utilities.outage_days > 0 {
+            utilities.outage_days -= 1;
+            if utilities.outage_days == 0 {
+                game_state.power_out = false;
+                outage_events.write(PowerOutageEvent { cause: None });
+            }
+            // No power, no bill
+            continue;
+        }
+
+        let heat_wave = world.temperature >= HEAT_WAVE_TEMP;
+        let mut bill = Utilities::daily_bill(&upgrades);
+        if heat_wave {
+            bill *= HEAT_WAVE_BILL_MULTIPLIER;
+        }
+
+        let cause = if game_state.money < bill {
+            Some(OutageCause::UnpaidBill)
+        } else {
+            game_state.spend(ExpenseCategory::Utilities, bill);
+            let outage_chance = HEAT_WAVE_OUTAGE_CHANCE * difficulty.event_severity;
+            (heat_wave && rand::random::<f32>() < outage_chance).then_some(OutageCause::HeatWave)
+        };
+
+        if let Some(cause) = cause {
+            utilities.outage_days = OUTAGE_DAYS;
+            game_state.power_out = true;
+            outage_events.write(PowerOutageEvent { cause: Some(cause) });
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! Terry's vacations - and the temp who fills in
+//!
+//! Every so often Terry asks for a week off. Granting it costs the bonuses
+//! from Terry's seminars until Terry is back, and a temp advisor with much
+//! worse advice takes over the dialogue box, but Terry comes back happier.
+//! Refusing keeps the bonuses and costs goodwill, and once goodwill runs low
+//! Terry burns out and takes a longer break anyway.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::economy::DayAdvancedEvent;
+use crate::fortune::RunModifiers;
+use crate::game_state::AppState;
+use crate::seminars::{Course, SeminarState};
+
+/// Where the relationship with Terry starts
+const STARTING_RELATIONSHIP: f32 = 60.0;
+/// Days between requests, at least
+const MIN_DAYS_BETWEEN_REQUESTS: u32 = 60;
+/// Daily chance of a request once it's been long enough
+const REQUEST_CHANCE: f32 = 0.03;
+/// Days the player has to answer before it counts as a no
+const REQUEST_DAYS: u32 = 5;
+/// Length of a granted vacation
+pub const VACATION_DAYS: u32 = 7;
+/// Length of a burnout
+const BURNOUT_DAYS: u32 = 14;
+/// Relationship changes
+const GRANT_GOODWILL: f32 = 15.0;
+const REFUSAL_COST: f32 = 20.0;
+/// Relationship below which a refusal tips Terry into burnout
+const BURNOUT_THRESHOLD: f32 = 25.0;
+/// Where the relationship lands after a burnout
+const AFTER_BURNOUT_RELATIONSHIP: f32 = 40.0;
+
+/// Terry out of the office
+#[derive(Debug, Clone, Reflect)]
+pub struct Absence {
+    pub days_remaining: u32,
+    /// Forced by burnout rather than granted
+    pub burnout: bool,
+    /// Seminar bonuses taken off until Terry is back
+    suspended: Vec<Course>,
+}
+
+/// Terry's time off and how Terry feels about the boss
+#[derive(Resource, Reflect)]
+#[reflect(Resource, Default)]
+pub struct VacationState {
+    /// 0-100
+    pub relationship: f32,
+    /// Days left to answer a request for time off
+    pub request_days: Option<u32>,
+    pub away: Option<Absence>,
+    /// Days since the last request
+    pub days_since_request: u32,
+}
+
+impl Default for VacationState {
+    fn default() -> Self {
+        Self {
+            relationship: STARTING_RELATIONSHIP,
+            request_days: None,
+            away: None,
+            days_since_request: 0,
+        }
+    }
+}
+
+impl VacationState {
+    pub fn is_away(&self) -> bool {
+        self.away.is_some()
+    }
+
+    pub fn change_relationship(&mut self, amount: f32) {
+        self.relationship = (self.relationship + amount).clamp(0.0, 100.0);
+    }
+
+    fn leave(&mut self, days: u32, burnout: bool, seminars: &SeminarState, modifiers: &mut RunModifiers) {
+        for course in &seminars.taught {
+            course.suspend(modifiers);
+        }
+        self.away = Some(Absence {
+            days_remaining: days,
+            burnout,
+            suspended: seminars.taught.clone(),
+        });
+    }
+}
+
+/// Run condition: Terry is at work (the temp doesn't react to anything)
+pub fn terry_in_office(vacation: Res<VacationState>) -> bool {
+    !vacation.is_away()
+}
+
+/// What happened with Terry's time off
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VacationStage {
+    Requested,
+    Granted,
+    Refused,
+    /// Refused once too often; Terry leaves anyway
+    BurnedOut,
+    Returned,
+}
+
+/// Message fired as a vacation is asked for, answered, taken and ended
+#[derive(Event, Message, Clone)]
+pub struct VacationEvent {
+    pub stage: VacationStage,
+}
+
+/// Message answering Terry's request (sent by the UI)
+#[derive(Event, Message, Clone)]
+pub struct VacationDecisionEvent {
+    pub grant: bool,
+}
+
+pub struct VacationPlugin;
+
+impl Plugin for VacationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<VacationState>()
+            .add_message::<VacationEvent>()
+            .add_message::<VacationDecisionEvent>()
+            .add_systems(
+                Update,
+                (handle_vacation_decisions, progress_vacation)
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// Grant or refuse the request
+fn handle_vacation_decisions(
+    mut decision_events: MessageReader<VacationDecisionEvent>,
+    seminars: Res<SeminarState>,
+    mut modifiers: ResMut<RunModifiers>,
+    mut vacation: ResMut<VacationState>,
+    mut vacation_events: MessageWriter<VacationEvent>,
+) {
+    for event in decision_events.read() {
+        if vacation.request_days.take().is_none() {
+            continue;
+        }
+        if event.grant {
+            vacation.change_relationship(GRANT_GOODWILL);
+            vacation.leave(VACATION_DAYS, false, &seminars, &mut modifiers);
+            vacation_events.write(VacationEvent { stage: VacationStage::Granted });
+        } else {
+            refuse(&mut vacation, &seminars, &mut modifiers, &mut vacation_events);
+        }
+    }
+}
+
+/// A no, said or implied; one too many and Terry burns out
+fn refuse(
+    vacation: &mut VacationState,
+    seminars: &SeminarState,
+    modifiers: &mut RunModifiers,
+    vacation_events: &mut MessageWriter<VacationEvent>,
+) {
+    vacation.change_relationship(-REFUSAL_COST);
+    vacation_events.write(VacationEvent { stage: VacationStage::Refused });
+    if vacation.relationship < BURNOUT_THRESHOLD {
+        vacation.relationship = AFTER_BURNOUT_RELATIONSHIP;
+        vacation.leave(BURNOUT_DAYS, true, seminars, modifiers);
+        vacation_events.write(VacationEvent { stage: VacationStage::BurnedOut });
+    }
+}
+
+/// Each game day: bring Terry back, let an unanswered request lapse, or ask for time off
+fn progress_vacation(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    seminars: Res<SeminarState>,
+    mut modifiers: ResMut<RunModifiers>,
+    mut vacation: ResMut<VacationState>,
+    mut vacation_events: MessageWriter<VacationEvent>,
+) {
+    for _ in day_events.read() {
+        if let Some(away) = vacation.away.as_mut() {
+            away.days_remaining = away.days_remaining.saturating_sub(1);
+            if away.days_remaining == 0 {
+                for course in &away.suspended {
+                    course.apply(&mut modifiers);
+                }
+                vacation.away = None;
+                vacation.days_since_request = 0;
+                vacation_events.write(VacationEvent { stage: VacationStage::Returned });
+            }
+            continue;
+        }
+
+        if let Some(days) = vacation.request_days.as_mut() {
+            *days = days.saturating_sub(1);
+            if *days == 0 {
+                vacation.request_days = None;
+                refuse(&mut vacation, &seminars, &mut modifiers, &mut vacation_events);
+            }
+            continue;
+        }
+
+        vacation.days_since_request += 1;
+        // Not while Terry's teaching; a grumpier Terry asks more often
+        let chance = REQUEST_CHANCE * (1.5 - vacation.relationship / 100.0);
+        if seminars.active.is_none()
+            && vacation.days_since_request >= MIN_DAYS_BETWEEN_REQUESTS
+            && rand::random::<f32>() < chance
+        {
+            vacation.request_days = Some(REQUEST_DAYS);
+            vacation.days_since_request = 0;
+            vacation_events.write(VacationEvent { stage: VacationStage::Requested });
+        }
+    }
+}
@@ -0,0 +1,246 @@
+//! Pop-up venues - rent a stall somewhere busy for a few days
+//!
+//! On the first of each month, venues for the month after go on offer: a
+//! farmers market weekend in the warm months, a festival booth in summer,
+//! and a mall kiosk for the run-up to Christmas. Booking one costs a setup
+//! fee up front; while it runs, sales get a big local boost. Outdoor venues
+//! can be washed out by bad weather, and a washed-out day boosts nothing.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::accounting::ExpenseCategory;
+use crate::economy::{DayAdvancedEvent, GameDate, MonthStartedEvent, WorldState};
+use crate::game_state::{AppState, GameState};
+
+/// Daily chance of rain ruining an outdoor venue
+const RAIN_CHANCE: f32 = 0.2;
+/// Outdoor venues are a washout outside this temperature range (Fahrenheit)
+const MIN_OUTDOOR_TEMP: f32 = 40.0;
+const MAX_OUTDOOR_TEMP: f32 = 95.0;
+
+/// The kinds of temporary venue on offer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum VenueKind {
+    FarmersMarket,
+    FestivalBooth,
+    MallKiosk,
+}
+
+impl VenueKind {
+    pub const ALL: [VenueKind; 3] = [VenueKind::FarmersMarket, VenueKind::FestivalBooth, VenueKind::MallKiosk];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            VenueKind::FarmersMarket => "Farmers Market",
+            VenueKind::FestivalBooth => "Festival Booth",
+            VenueKind::MallKiosk => "Mall Kiosk",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            VenueKind::FarmersMarket => "A weekend stall between the honey guy and the artisanal soap lady.",
+            VenueKind::FestivalBooth => "Three days of sunburnt crowds who will buy anything. Anything.",
+            VenueKind::MallKiosk => "December at the mall. Indoors, relentless, and playing the same six carols.",
+        }
+    }
+
+    /// Setup fee, paid when booking
+    pub fn setup_cost(&self) -> f64 {
+        match self {
+            VenueKind::FarmersMarket => 150.0,
+            VenueKind::FestivalBooth => 600.0,
+            VenueKind::MallKiosk => 2_000.0,
+        }
+    }
+
+    /// Sales multiplier while the venue is open
+    pub fn demand_boost(&self) -> f64 {
+        match self {
+            VenueKind::FarmersMarket => 2.5,
+            VenueKind::FestivalBooth => 4.0,
+            VenueKind::MallKiosk => 1.8,
+        }
+    }
+
+    /// At the mercy of the weather?
+    pub fn is_outdoor(&self) -> bool {
+        *self != VenueKind::MallKiosk
+    }
+
+    /// When this venue runs in the given month, if it runs at all
+    fn dates_in(&self, year: i32, month: u8) -> Option<(GameDate, u32)> {
+        match self {
+            // The second weekend
+            VenueKind::FarmersMarket if (5..=10).contains(&month) => {
+                let eighth = GameDate::new(year, month, 8);
+                let to_saturday = (6 - eighth.day_of_week() as i32).rem_euclid(7);
+                Some((eighth.add_days(to_saturday), 2))
+            }
+            VenueKind::FestivalBooth if (6..=8).contains(&month) => Some((GameDate::new(year, month, 15), 3)),
+            VenueKind::MallKiosk if month == 12 => Some((GameDate::new(year, month, 1), 24)),
+            _ => None,
+        }
+    }
+}
+
+/// A venue on offer or booked
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct VenueBooking {
+    pub kind: VenueKind,
+    pub start: GameDate,
+    pub days: u32,
+    pub booked: bool,
+    /// Days lost to the weather so far
+    pub washed_out_days: u32,
+}
+
+impl VenueBooking {
+    /// The last day the venue is open
+    pub fn end(&self) -> GameDate {
+        self.start.add_days(self.days.saturating_sub(1) as i32)
+    }
+
+    pub fn is_open_on(&self, date: GameDate) -> bool {
+        date >= self.start && date <= self.end()
+    }
+}
+
+/// Venues on offer and booked, in date order
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct Venues {
+    pub bookings: Vec<VenueBooking>,
+    /// Today's sales multiplier from open venues (1.0 when none)
+    boost: f64,
+}
+
+impl Venues {
+    /// Sales multiplier from the venues open today
+    pub fn demand_boost(&self) -> f64 {
+        self.boost.max(1.0)
+    }
+
+    /// Book an offered venue, paying the setup fee
+    pub fn book(&mut self, index: usize, game_state: &mut GameState) -> bool {
+        let Some(booking) = self.bookings.get_mut(index) else {
+            return false;
+        };
+        if booking.booked || game_state.money < booking.kind.setup_cost() {
+            return false;
+        }
+        game_state.spend(ExpenseCategory::Venues, booking.kind.setup_cost());
+        booking.booked = true;
+        true
+    }
+}
+
+/// What happened at a venue today
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VenueStage {
+    Offered,
+    Opened,
+    WashedOut,
+    Closed,
+}
+
+/// Message fired as venues are offered, open, get rained on, and close
+#[derive(Event, Message, Clone)]
+pub struct VenueEvent {
+    pub kind: VenueKind,
+    pub stage: VenueStage,
+}
+
+pub struct VenuePlugin;
+
+impl Plugin for VenuePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Venues>()
+            .add_message::<VenueEvent>()
+            .add_systems(Update, (offer_venues, run_venues).chain().run_if(in_state(AppState::Playing)));
+    }
+}
+
+/// Offer next month's venues at the start of each month
+fn offer_venues(
+    mut month_events: MessageReader<MonthStartedEvent>,
+    mut venues: ResMut<Venues>,
+    mut venue_events: MessageWriter<VenueEvent>,
+) {
+    for event in month_events.read() {
+        let next_month = event.date.next_month();
+        for kind in VenueKind::ALL {
+            if let Some((start, days)) = kind.dates_in(next_month.year(), next_month.month()) {
+                venues.bookings.push(VenueBooking {
+                    kind,
+                    start,
+                    days,
+                    booked: false,
+                    washed_out_days: 0,
+                });
+                venue_events.write(VenueEvent {
+                    kind,
+                    stage: VenueStage::Offered,
+                });
+            }
+        }
+        venues.bookings.sort_by_key(|booking| booking.start);
+    }
+}
+
+/// Open, close and rain on booked venues
+fn run_venues(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    world: Res<WorldState>,
+    mut venues: ResMut<Venues>,
+    mut venue_events: MessageWriter<VenueEvent>,
+) {
+    for event in day_events.read() {
+        let date = event.date;
+
+        // Offers nobody took, and venues that are over, go away
+        // (checked first so the calendar only redraws when something changes)
+        let finished = |booking: &VenueBooking| booking.booked && date > booking.end();
+        let missed = |booking: &VenueBooking| !booking.booked && date >= booking.start;
+        if venues.bookings.iter().any(|b| finished(b) || missed(b)) {
+            venues.bookings.retain(|booking| {
+                if finished(booking) {
+                    venue_events.write(VenueEvent {
+                        kind: booking.kind,
+                        stage: VenueStage::Closed,
+                    });
+                }
+                !(finished(booking) || missed(booking))
+            });
+        }
+
+        let bad_weather = world.temperature < MIN_OUTDOOR_TEMP || world.temperature > MAX_OUTDOOR_TEMP;
+        let mut boost = 1.0;
+        let is_open = |booking: &VenueBooking| booking.booked && booking.is_open_on(date);
+        if !venues.bookings.iter().any(is_open) {
+            if venues.boost != boost {
+                venues.boost = boost;
+            }
+            continue;
+        }
+        for booking in venues.bookings.iter_mut().filter(|b| is_open(b)) {
+            if booking.kind.is_outdoor() && (bad_weather || rand::random::<f32>() < RAIN_CHANCE) {
+                booking.washed_out_days += 1;
+                venue_events.write(VenueEvent {
+                    kind: booking.kind,
+                    stage: VenueStage::WashedOut,
+                });
+                continue;
+            }
+            if date == booking.start {
+                venue_events.write(VenueEvent {
+                    kind: booking.kind,
+                    stage: VenueStage::Opened,
+                });
+            }
+            boost += booking.kind.demand_boost() - 1.0;
+        }
+
+        venues.boost = boost;
+    }
+}
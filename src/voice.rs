@@ -0,0 +1,93 @@
+//! Terry's voice - plays a line's voice clip while its text is typed out
+//!
+//! Lines without an `audio` clip, or whose clip is missing or won't load,
+//! stay text-only. Once a clip has loaded its length is handed to
+//! `TerryState`, which times the line and the typewriter to match it.
+
+use bevy::prelude::*;
+use bevy::audio::{Decodable, Source};
+use crate::game_state::{AppState, InGame};
+use crate::lifecycle::Expires;
+use crate::terry::TerryState;
+
+/// A clip still around after this long (one that never loaded, say) is cleared away
+const MAX_CLIP_SECS: f32 = 30.0;
+
+/// Marker for the clip Terry is currently speaking (Terry stops talking when the game does)
+#[derive(Component)]
+#[require(DespawnOnExit<InGame> = DespawnOnExit(InGame))]
+pub struct TerryVoice;
+
+/// The clip for the line on screen, until its length is known
+struct PendingClip {
+    line_id: String,
+    handle: Handle<AudioSource>,
+}
+
+pub struct VoicePlugin;
+
+impl Plugin for VoicePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, play_terry_voice.run_if(in_state(AppState::Playing)));
+    }
+}
+
+/// Length of a clip, counting samples when the format doesn't say
+fn clip_secs(source: &AudioSource) -> f32 {
+    let decoder = source.decoder();
+    if let Some(duration) = decoder.total_duration() {
+        return duration.as_secs_f32();
+    }
+    let samples_per_sec = decoder.sample_rate() as f32 * decoder.channels() as f32;
+    decoder.count() as f32 / samples_per_sec.max(1.0)
+}
+
+/// Start each new line's clip, stop it when the line goes, and time the line to it
+fn play_terry_voice(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    sources: Res<Assets<AudioSource>>,
+    mut terry_state: ResMut<TerryState>,
+    voice_query: Query<Entity, With<TerryVoice>>,
+    mut last_line: Local<Option<String>>,
+    mut pending: Local<Option<PendingClip>>,
+) {
+    let line_id = terry_state.current_line.as_ref().map(|line| line.id.clone());
+    let new_line = *last_line != line_id;
+    if new_line || terry_state.is_idle() {
+        for entity in &voice_query {
+            commands.entity(entity).try_despawn();
+        }
+    }
+
+    if new_line {
+        *last_line = line_id;
+        *pending = terry_state
+            .current_line
+            .as_ref()
+            .and_then(|line| Some((line.id.clone(), line.audio.as_ref()?)))
+            .map(|(line_id, path)| {
+                let handle: Handle<AudioSource> = asset_server.load(path);
+                commands.spawn((
+                    AudioPlayer(handle.clone()),
+                    PlaybackSettings::DESPAWN,
+                    TerryVoice,
+                    Expires::after(MAX_CLIP_SECS),
+                ));
+                PendingClip { line_id, handle }
+            });
+    }
+
+    let Some(clip) = pending.as_ref() else {
+        return;
+    };
+    if let Some(source) = sources.get(&clip.handle) {
+        if last_line.as_deref() == Some(clip.line_id.as_str()) {
+            terry_state.voice_secs = Some(clip_secs(source));
+        }
+        *pending = None;
+    } else if asset_server.load_state(&clip.handle).is_failed() {
+        warn!("Voice clip for {} didn't load, showing it as text only", clip.line_id);
+        *pending = None;
+    }
+}
@@ -0,0 +1,225 @@
+//! Work-life balance - the player character has limits too (optional mode)
+//!
+//! With the setting on, every manual click and every crisis wears the player
+//! down. Running on empty makes clicks miss and turns Terry gloomy. Taking a
+//! few days off restores wellbeing, but the factory runs at half speed without
+//! anyone watching it, and nothing gets made by hand.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::SystemParam;
+use crate::economy::DayAdvancedEvent;
+use crate::eco::ActivistEvent;
+use crate::fortune::RunModifiers;
+use crate::game_state::AppState;
+use crate::labor::{StrikeEvent, StrikeStage};
+use crate::reputation::ReputationTierChangedEvent;
+use crate::settings::Settings;
+use crate::utilities::PowerOutageEvent;
+
+/// Wellbeing when rested
+pub const MAX_WELLBEING: f32 = 100.0;
+/// Worn off by each manual click
+const WELLBEING_PER_CLICK: f32 = 0.5;
+/// Worn off by each crisis
+const CRISIS_COST: f32 = 10.0;
+/// Recovered on a quiet day
+const QUIET_DAY_REST: f32 = 1.0;
+/// Manual clicks in a day before it stops counting as quiet
+const QUIET_DAY_CLICKS: u32 = 3;
+/// Length of a break
+pub const DAYS_OFF: u32 = 10;
+/// Recovered each day off
+const DAY_OFF_REST: f32 = 5.0;
+/// Automatic production while nobody's watching the factory
+const DAY_OFF_PRODUCTION: f64 = 0.5;
+/// Below this, clicks start to miss
+pub const LOW_WELLBEING: f32 = 30.0;
+/// Chance a click misses at zero wellbeing (scales down to nothing at LOW_WELLBEING)
+const MAX_MISCLICK_CHANCE: f32 = 0.5;
+/// Back above this counts as recovered
+const RECOVERED_WELLBEING: f32 = 60.0;
+
+/// How the player character is holding up
+#[derive(Resource, Reflect)]
+#[reflect(Resource, Default)]
+pub struct Wellbeing {
+    pub current: f32,
+    /// Days left of a break
+    pub days_off: u32,
+    /// Manual clicks so far today
+    clicks_today: u32,
+    /// Below LOW_WELLBEING, until back above RECOVERED_WELLBEING
+    pub exhausted: bool,
+}
+
+impl Default for Wellbeing {
+    fn default() -> Self {
+        Self {
+            current: MAX_WELLBEING,
+            days_off: 0,
+            clicks_today: 0,
+            exhausted: false,
+        }
+    }
+}
+
+impl Wellbeing {
+    /// Wear the player down for a manual click. Returns false if the click misses
+    /// (or there's nobody at work to click). Always succeeds when the mode is off.
+    pub fn try_click(&mut self, enabled: bool) -> bool {
+        if !enabled {
+            return true;
+        }
+        if self.days_off > 0 {
+            return false;
+        }
+        self.clicks_today += 1;
+        self.current = (self.current - WELLBEING_PER_CLICK).max(0.0);
+        rand::random::<f32>() >= self.misclick_chance()
+    }
+
+    pub fn misclick_chance(&self) -> f32 {
+        if self.current >= LOW_WELLBEING {
+            return 0.0;
+        }
+        (1.0 - self.current / LOW_WELLBEING) * MAX_MISCLICK_CHANCE
+    }
+
+    pub fn fraction(&self) -> f32 {
+        self.current / MAX_WELLBEING
+    }
+
+    fn change(&mut self, amount: f32) {
+        self.current = (self.current + amount).clamp(0.0, MAX_WELLBEING);
+    }
+}
+
+/// What happened to the player's wellbeing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WellbeingStage {
+    /// Dropped below LOW_WELLBEING
+    Exhausted,
+    /// Back above RECOVERED_WELLBEING
+    Recovered,
+    DaysOffStarted,
+    DaysOffEnded,
+}
+
+/// Message fired as the player wears down, breaks and recovers
+#[derive(Event, Message, Clone)]
+pub struct WellbeingEvent {
+    pub stage: WellbeingStage,
+}
+
+/// Message requesting a break (sent by the UI)
+#[derive(Event, Message, Clone)]
+pub struct TakeDaysOffEvent;
+
+/// Messages that count as a crisis
+#[derive(SystemParam)]
+struct CrisisEvents<'w, 's> {
+    strikes: MessageReader<'w, 's, StrikeEvent>,
+    outages: MessageReader<'w, 's, PowerOutageEvent>,
+    activists: MessageReader<'w, 's, ActivistEvent>,
+    tiers: MessageReader<'w, 's, ReputationTierChangedEvent>,
+}
+
+impl CrisisEvents<'_, '_> {
+    /// Crises since last frame
+    fn count(&mut self) -> usize {
+        self.strikes.read().filter(|e| e.stage == StrikeStage::Started).count()
+            + self.outages.read().filter(|e| e.cause.is_some()).count()
+            + self.activists.read().count()
+            + self.tiers.read().filter(|e| e.new_tier < e.old_tier).count()
+    }
+}
+
+pub struct WellbeingPlugin;
+
+impl Plugin for WellbeingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Wellbeing>()
+            .add_message::<WellbeingEvent>()
+            .add_message::<TakeDaysOffEvent>()
+            .add_systems(
+                Update,
+                (handle_days_off, wear_down, rest_daily)
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// Start a break
+fn handle_days_off(
+    mut days_off_events: MessageReader<TakeDaysOffEvent>,
+    settings: Res<Settings>,
+    mut modifiers: ResMut<RunModifiers>,
+    mut wellbeing: ResMut<Wellbeing>,
+    mut wellbeing_events: MessageWriter<WellbeingEvent>,
+) {
+    for _ in days_off_events.read() {
+        if !settings.wellbeing || wellbeing.days_off > 0 {
+            continue;
+        }
+        wellbeing.days_off = DAYS_OFF;
+        modifiers.production *= DAY_OFF_PRODUCTION;
+        wellbeing_events.write(WellbeingEvent {
+            stage: WellbeingStage::DaysOffStarted,
+        });
+    }
+}
+
+/// Crises take their toll, and notice when the player has hit the wall
+fn wear_down(
+    mut crises: CrisisEvents,
+    settings: Res<Settings>,
+    mut wellbeing: ResMut<Wellbeing>,
+    mut wellbeing_events: MessageWriter<WellbeingEvent>,
+) {
+    let crises = crises.count();
+    if !settings.wellbeing {
+        return;
+    }
+    if crises > 0 {
+        wellbeing.change(-CRISIS_COST * crises as f32);
+    }
+
+    if !wellbeing.exhausted && wellbeing.current < LOW_WELLBEING {
+        wellbeing.exhausted = true;
+        wellbeing_events.write(WellbeingEvent {
+            stage: WellbeingStage::Exhausted,
+        });
+    } else if wellbeing.exhausted && wellbeing.current > RECOVERED_WELLBEING {
+        wellbeing.exhausted = false;
+        wellbeing_events.write(WellbeingEvent {
+            stage: WellbeingStage::Recovered,
+        });
+    }
+}
+
+/// Each game day: rest on a break or a quiet day
+fn rest_daily(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    settings: Res<Settings>,
+    mut modifiers: ResMut<RunModifiers>,
+    mut wellbeing: ResMut<Wellbeing>,
+    mut wellbeing_events: MessageWriter<WellbeingEvent>,
+) {
+    for _ in day_events.read() {
+        if wellbeing.days_off > 0 {
+            wellbeing.change(DAY_OFF_REST);
+            wellbeing.days_off -= 1;
+            if wellbeing.days_off == 0 {
+                modifiers.production /= DAY_OFF_PRODUCTION;
+                wellbeing_events.write(WellbeingEvent {
+                    stage: WellbeingStage::DaysOffEnded,
+                });
+            }
+        } else if settings.wellbeing && wellbeing.clicks_today < QUIET_DAY_CLICKS {
+            wellbeing.change(QUIET_DAY_REST);
+        }
+        wellbeing.clicks_today = 0;
+    }
+}
@@ -0,0 +1,147 @@
+//! Word of mouth - how people hear about the Thing, want one, and buy one
+//!
+//! The addressable market is split into people who have never heard of the
+//! Thing, people who are aware of it, people who are interested, and
+//! customers. Each day marketing and happy customers spread awareness,
+//! satisfaction turns awareness into interest, and sales turn interest into
+//! customers. Interest that never turns into a sale fades, and unhappy
+//! customers drift back to merely aware.
+//!
+//! Demand follows how many people are interested (and how many customers come
+//! back), so growth starts slow, snowballs, and flattens out on its own once
+//! there is nobody left to tell.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::economy::{DayAdvancedEvent, WorldState};
+use crate::game_state::{AppState, GameState};
+use crate::marketing::MarketingState;
+
+/// Share of the world population that could ever want a Thing
+const ADDRESSABLE_PER_PERSON: f64 = 0.000_1;
+/// Share of the market aware of / interested in the Thing on day one
+const START_AWARE: f64 = 0.05;
+const START_INTERESTED: f64 = 0.02;
+/// Share of the unaware that hear about the Thing anyway each day
+const BASE_REACH: f64 = 0.002;
+/// Extra daily reach per unit of marketing demand boost
+const AD_REACH: f64 = 0.01;
+/// Extra daily reach at full media buzz
+const BUZZ_REACH: f64 = 0.01;
+/// People a perfectly happy customer tells about the Thing each day
+const CONTACT_RATE: f64 = 0.3;
+/// Share of the aware who get interested each day at full satisfaction
+const INTEREST_RATE: f64 = 0.02;
+/// Share of the interested who lose interest each day without buying
+const FADE_RATE: f64 = 0.03;
+/// Share of customers who lose interest each day at zero satisfaction
+const CHURN_RATE: f64 = 0.02;
+/// How much a returning customer counts toward demand, next to a new one
+const REPEAT_WEIGHT: f64 = 0.3;
+/// Engaged share of the market (interested plus returning) that gives normal demand
+const REFERENCE_ENGAGED: f64 = START_INTERESTED;
+/// Limits on the demand multiplier
+const MIN_DEMAND: f64 = 0.25;
+const MAX_DEMAND: f64 = 2.0;
+
+/// The adoption funnel, in people
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource, Default)]
+pub struct WordOfMouth {
+    pub aware: f64,
+    pub interested: f64,
+    pub customers: f64,
+    /// Customers served as of the last update
+    last_customers_served: u64,
+}
+
+impl Default for WordOfMouth {
+    fn default() -> Self {
+        let market = WorldState::default().global_population * ADDRESSABLE_PER_PERSON;
+        Self {
+            aware: market * START_AWARE,
+            interested: market * START_INTERESTED,
+            customers: 0.0,
+            last_customers_served: 0,
+        }
+    }
+}
+
+impl WordOfMouth {
+    /// Everybody who could ever want a Thing
+    pub fn market_size(world: &WorldState) -> f64 {
+        world.global_population * ADDRESSABLE_PER_PERSON
+    }
+
+    /// People who have never heard of the Thing
+    pub fn unaware(&self, world: &WorldState) -> f64 {
+        (Self::market_size(world) - self.aware - self.interested - self.customers).max(0.0)
+    }
+
+    /// Brand recognition - the share of the market that's heard of the Thing
+    pub fn recognition(&self, world: &WorldState) -> f64 {
+        1.0 - self.unaware(world) / Self::market_size(world)
+    }
+
+    /// How busy the shop is compared to day one - multiplies would-be customers
+    pub fn demand_factor(&self, world: &WorldState) -> f64 {
+        let engaged = (self.interested + self.customers * REPEAT_WEIGHT) / Self::market_size(world);
+        (engaged / REFERENCE_ENGAGED).clamp(MIN_DEMAND, MAX_DEMAND)
+    }
+}
+
+/// How happy customers are with the Thing (0-1)
+fn satisfaction(game_state: &GameState) -> f64 {
+    (game_state.reputation / 5.0).clamp(0.0, 1.0) as f64
+}
+
+pub struct WordOfMouthPlugin;
+
+impl Plugin for WordOfMouthPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WordOfMouth>()
+            .add_systems(Update, spread_word_of_mouth.run_if(in_state(AppState::Playing)));
+    }
+}
+
+/// Move people along the funnel once a day
+fn spread_word_of_mouth(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    game_state: Res<GameState>,
+    marketing: Res<MarketingState>,
+    mut world: ResMut<WorldState>,
+    mut funnel: ResMut<WordOfMouth>,
+) {
+    for _ in day_events.read() {
+        let market = WordOfMouth::market_size(&world);
+        let unaware = funnel.unaware(&world);
+        let satisfaction = satisfaction(&game_state);
+
+        // Unaware -> aware: ads, the news, and customers talking
+        let ad_reach = BASE_REACH
+            + (marketing.calculate_demand_boost() as f64 - 1.0).max(0.0) * AD_REACH
+            + world.media_buzz as f64 * BUZZ_REACH;
+        let viral = CONTACT_RATE * satisfaction * funnel.customers / market;
+        let newly_aware = (unaware * (ad_reach + viral)).min(unaware);
+
+        // Aware -> interested: only if what people hear is good
+        let newly_interested = funnel.aware * INTEREST_RATE * satisfaction * world.trend_factor as f64;
+
+        // Interested -> customer: every new sale converts someone, while there is anyone left
+        let sold = game_state.customers_served.saturating_sub(funnel.last_customers_served);
+        let newly_customers = (sold as f64).min(funnel.interested + newly_interested);
+
+        // Interested -> aware: the ones who never got round to buying
+        let faded = (funnel.interested - newly_customers).max(0.0) * FADE_RATE;
+
+        // Customer -> aware: unhappy customers stop caring
+        let lapsed = funnel.customers * CHURN_RATE * (1.0 - satisfaction);
+
+        funnel.aware = (funnel.aware + newly_aware - newly_interested + faded + lapsed).max(0.0);
+        funnel.interested = (funnel.interested + newly_interested - newly_customers - faded).max(0.0);
+        funnel.customers = (funnel.customers + newly_customers - lapsed).max(0.0);
+        funnel.last_customers_served = game_state.customers_served;
+
+        world.viral_coefficient = viral as f32;
+    }
+}
@@ -0,0 +1,150 @@
+//! Thing Wrapped - the end-of-year recap
+//!
+//! When December 31 closes, the year is boiled down to a handful of
+//! headlines: how many Things were made, the best day for the bank balance,
+//! the scandal that hurt most, the marketing channel that ran longest and a
+//! Terry quote of the year. Everything but the channel comes from the daily
+//! snapshots in `RunStats` and the `EventLog`; channels are only tallied here,
+//! since neither of those says which ones were running on a given day.
+
+use bevy::prelude::*;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use crate::economy::DayAdvancedEvent;
+use crate::event_log::{EventLog, LogCategory, Severity};
+use crate::game_state::AppState;
+use crate::marketing::{MarketingChannel, MarketingState};
+use crate::stats::{record_daily_snapshot, RunStats};
+
+/// One year of the run, summed up
+#[derive(Debug, Clone, PartialEq)]
+pub struct YearRecap {
+    pub year: i32,
+    pub things_made: u64,
+    /// The day (ISO date) the bank balance grew the most, and by how much
+    pub best_day: Option<(String, f64)>,
+    /// The legal trouble on the day reputation fell furthest, and how far it fell
+    pub scandal: Option<(String, f32)>,
+    /// The channel that ran the most days
+    pub top_channel: Option<(MarketingChannel, u32)>,
+    pub quote: Option<String>,
+}
+
+impl YearRecap {
+    /// Sum up `year` from the run history and the channel tally
+    pub fn compose(year: i32, stats: &RunStats, log: &EventLog, tally: &YearTally) -> Self {
+        let prefix = format!("{}-", year);
+        // Each snapshot closes the day the one before it opened
+        let days: Vec<_> = stats
+            .snapshots
+            .windows(2)
+            .filter(|pair| pair[0].date.starts_with(&prefix))
+            .map(|pair| (&pair[0], &pair[1]))
+            .collect();
+
+        let things_made = days
+            .iter()
+            .map(|(open, close)| close.things_produced.saturating_sub(open.things_produced))
+            .sum();
+        let best_day = days
+            .iter()
+            .map(|(open, close)| (open.date.clone(), close.money - open.money))
+            .filter(|(_, gain)| *gain > 0.0)
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+
+        let year_entries = log.entries.iter().filter(|entry| entry.date.year() == year);
+        let scandal = year_entries
+            .clone()
+            .filter(|entry| entry.category == LogCategory::Legal && entry.severity == Severity::Bad)
+            .map(|entry| {
+                let date = entry.date.iso();
+                let fall = days
+                    .iter()
+                    .find(|(open, _)| open.date == date)
+                    .map(|(open, close)| open.reputation - close.reputation)
+                    .unwrap_or(0.0);
+                (entry.text.clone(), fall)
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+
+        let quotes: Vec<&str> = year_entries
+            .filter(|entry| entry.category == LogCategory::Terry)
+            .map(|entry| entry.text.as_str())
+            .collect();
+        let quote = (!quotes.is_empty()).then(|| quotes[rand::random_range(0..quotes.len())].to_string());
+
+        let top_channel = tally
+            .channel_days
+            .iter()
+            .copied()
+            .filter(|(_, days)| *days > 0)
+            .max_by_key(|(_, days)| *days);
+
+        Self {
+            year,
+            things_made,
+            best_day,
+            scandal,
+            top_channel,
+            quote,
+        }
+    }
+}
+
+/// Days each marketing channel has run so far this year
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct YearTally {
+    pub channel_days: Vec<(MarketingChannel, u32)>,
+}
+
+impl YearTally {
+    fn count(&mut self, channel: MarketingChannel) {
+        match self.channel_days.iter_mut().find(|(c, _)| *c == channel) {
+            Some((_, days)) => *days += 1,
+            None => self.channel_days.push((channel, 1)),
+        }
+    }
+}
+
+/// Message fired when a year closes, with its recap
+#[derive(Event, Message, Clone)]
+pub struct YearWrappedEvent {
+    pub recap: YearRecap,
+}
+
+pub struct WrappedPlugin;
+
+impl Plugin for WrappedPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<YearTally>()
+            .add_message::<YearWrappedEvent>()
+            .add_systems(
+                Update,
+                wrap_up_year.after(record_daily_snapshot).run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// Count the day's running channels, and wrap the year up once December 31 is over
+fn wrap_up_year(
+    mut day_events: MessageReader<DayAdvancedEvent>,
+    marketing: Res<MarketingState>,
+    stats: Res<RunStats>,
+    log: Res<EventLog>,
+    mut tally: ResMut<YearTally>,
+    mut wrapped_events: MessageWriter<YearWrappedEvent>,
+) {
+    for event in day_events.read() {
+        for channel in MarketingChannel::ALL {
+            if marketing.is_active(channel) {
+                tally.count(channel);
+            }
+        }
+
+        if event.date.month() == 1 && event.date.day() == 1 {
+            let recap = YearRecap::compose(event.date.year() - 1, &stats, &log, &tally);
+            wrapped_events.write(YearWrappedEvent { recap });
+            *tally = YearTally::default();
+        }
+    }
+}